@@ -67,6 +67,10 @@ fn copie_carte(
     e
 }
 
+fn copie_carte_optionnelle(e: HashMap<String, Option<u32>>) -> HashMap<String, Option<u32>> {
+    e
+}
+
 fn copie_dictionnaire(d: Dictionnaire) -> Dictionnaire {
     d
 }
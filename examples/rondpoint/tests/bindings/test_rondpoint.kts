@@ -6,6 +6,7 @@ assert(dico == copyDico)
 
 assert(copieEnumeration(Enumeration.DEUX) == Enumeration.DEUX)
 assert(copieEnumerations(listOf(Enumeration.UN, Enumeration.DEUX)) == listOf(Enumeration.UN, Enumeration.DEUX))
+assert(copieEnumerations(Enumeration.values().toList()) == listOf(Enumeration.UN, Enumeration.DEUX, Enumeration.TROIS))
 assert(copieCarte(mapOf(
     "0" to EnumerationAvecDonnees.Zero,
     "1" to EnumerationAvecDonnees.Un(1u),
@@ -16,6 +17,11 @@ assert(copieCarte(mapOf(
     "2" to EnumerationAvecDonnees.Deux(2u, "deux")
 ))
 
+// Map values which are optional can be `null`.
+val carteOptionnelle: Map<String, UInt?> = mapOf("absent" to null, "present" to 1u)
+assert(copieCarteOptionnelle(carteOptionnelle) == carteOptionnelle)
+assert(copieCarteOptionnelle(carteOptionnelle).containsKey("absent"))
+
 val var1: EnumerationAvecDonnees = EnumerationAvecDonnees.Zero
 val var2: EnumerationAvecDonnees = EnumerationAvecDonnees.Un(1u)
 val var3: EnumerationAvecDonnees = EnumerationAvecDonnees.Un(2u)
@@ -8,6 +8,7 @@ assert dico == copyDico
 
 assert copie_enumeration(Enumeration.DEUX) == Enumeration.DEUX
 assert copie_enumerations([Enumeration.UN, Enumeration.DEUX]) == [Enumeration.UN, Enumeration.DEUX]
+assert copie_enumerations(list(Enumeration)) == [Enumeration.UN, Enumeration.DEUX, Enumeration.TROIS]
 assert copie_carte({
     "0": EnumerationAvecDonnees.ZERO(),
     "1": EnumerationAvecDonnees.UN(1),
@@ -18,6 +19,8 @@ assert copie_carte({
     "2": EnumerationAvecDonnees.DEUX(2, "deux"),
 }
 
+assert copie_carte_optionnelle({"absent": None, "present": 1}) == {"absent": None, "present": 1}
+
 assert switcheroo(False) is True
 
 assert EnumerationAvecDonnees.ZERO() != EnumerationAvecDonnees.UN(1)
@@ -6,6 +6,7 @@ assert(dico == copyDico)
 
 assert(copieEnumeration(e: .deux) == .deux)
 assert(copieEnumerations(e: [.un, .deux]) == [.un, .deux])
+assert(copieEnumerations(e: Enumeration.allCases) == [.un, .deux, .trois])
 assert(copieCarte(c:
     ["0": .zero,
     "1": .un(premier: 1),
@@ -2,6 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 // namespace functions.
@@ -13,10 +14,18 @@ fn press(button: Arc<dyn Button>) -> Arc<dyn Button> {
     button
 }
 
+fn flip(toggle: Arc<dyn Toggle>) -> bool {
+    toggle.toggle()
+}
+
 pub trait Button: Send + Sync {
     fn name(&self) -> String;
 }
 
+pub trait Toggle: Send + Sync {
+    fn toggle(&self) -> bool;
+}
+
 struct GoButton {}
 
 impl Button for GoButton {
@@ -33,4 +42,27 @@ impl Button for StopButton {
     }
 }
 
+#[derive(Default)]
+struct ToggleButton {
+    on: AtomicBool,
+}
+
+impl ToggleButton {
+    fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Button for ToggleButton {
+    fn name(&self) -> String {
+        "toggle".to_string()
+    }
+}
+
+impl Toggle for ToggleButton {
+    fn toggle(&self) -> bool {
+        !self.on.fetch_xor(true, Ordering::SeqCst)
+    }
+}
+
 uniffi::include_scaffolding!("traits");
@@ -14,3 +14,10 @@ class KtButton : Button {
 }
 
 assert(press(KtButton()).name() == "KtButton")
+
+// Test a concrete object used as the narrower traits it implements
+val toggleButton = ToggleButton()
+assert(press(toggleButton).name() == "toggle")
+assert(flip(toggleButton))
+assert(!flip(toggleButton))
+assert(toggleButton.toggle())
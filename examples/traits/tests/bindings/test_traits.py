@@ -13,3 +13,10 @@ class PyButton(Button):
         return "PyButton"
 
 assert(press(PyButton()).name() == "PyButton")
+
+# Test a concrete object used as the narrower traits it implements
+toggle_button = ToggleButton()
+assert(press(toggle_button).name() == "toggle")
+assert(flip(toggle_button))
+assert(not flip(toggle_button))
+assert(toggle_button.toggle())
@@ -16,3 +16,10 @@ class SwiftButton: Button {
 }
 
 assert(press(button: SwiftButton()).name() == "SwiftButton")
+
+// Test a concrete object used as the narrower traits it implements
+let toggleButton = ToggleButton()
+assert(press(button: toggleButton).name() == "toggle")
+assert(flip(toggle: toggleButton))
+assert(!flip(toggle: toggleButton))
+assert(toggleButton.toggle())
@@ -0,0 +1,42 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+pub trait ProgressListener: Send + Sync {
+    fn on_progress(&self, step: u32);
+}
+
+/// Returns `Pending` once, so that each step is reported from a separate poll of the future.
+struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Report each of the steps to `listener`, then return the number of steps.
+pub async fn count_steps(steps: u32, listener: Box<dyn ProgressListener>) -> u32 {
+    for step in 1..=steps {
+        YieldNow { yielded: false }.await;
+        listener.on_progress(step);
+    }
+    steps
+}
+
+uniffi::include_scaffolding!("async_progress");
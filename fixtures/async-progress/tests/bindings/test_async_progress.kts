@@ -0,0 +1,26 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.asyncprogress.*
+import kotlinx.coroutines.*
+
+runBlocking {
+    val events = mutableListOf<UInt>()
+    val result = countStepsWithProgress(3u) { events.add(it) }
+    // All of the events were passed before the result was returned.
+    assert(result == 3u)
+    assert(events == listOf(1u, 2u, 3u)) { "$events" }
+}
+
+runBlocking {
+    // The function can still be called with a listener directly.
+    val events = mutableListOf<UInt>()
+    val listener = object : ProgressListener {
+        override fun onProgress(step: UInt) {
+            synchronized(events) { events.add(step) }
+        }
+    }
+    assert(countSteps(2u, listener) == 2u)
+    assert(events == listOf(1u, 2u)) { "$events" }
+}
@@ -0,0 +1,35 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import asyncio
+import threading
+
+from async_progress import *
+
+async def test_with_progress():
+    events = []
+    def on_progress(step):
+        # The events are passed on the thread which runs the event loop.
+        assert threading.current_thread() is threading.main_thread()
+        events.append(step)
+    result = await count_steps_with_progress(3, on_progress)
+    # All of the events were passed before the result was returned.
+    assert result == 3
+    assert events == [1, 2, 3], events
+
+async def test_listener():
+    # The function can still be called with a listener directly.
+    class Listener(ProgressListener):
+        def __init__(self):
+            self.events = []
+
+        def on_progress(self, step):
+            self.events.append(step)
+
+    listener = Listener()
+    assert await count_steps(2, listener) == 2
+    assert listener.events == [1, 2], listener.events
+
+asyncio.run(test_with_progress())
+asyncio.run(test_listener())
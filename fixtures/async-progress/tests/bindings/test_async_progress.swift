@@ -0,0 +1,20 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import Foundation
+import async_progress
+
+let counter = DispatchGroup()
+
+counter.enter()
+Task {
+    var events: [UInt32] = []
+    let result = await countStepsWithProgress(steps: 3) { events.append($0) }
+    // All of the events were passed before the result was returned.
+    assert(result == 3)
+    assert(events == [1, 2, 3], "\(events)")
+    counter.leave()
+}
+
+counter.wait()
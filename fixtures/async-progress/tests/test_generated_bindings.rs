@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_async_progress.py",
+    "tests/bindings/test_async_progress.kts",
+    "tests/bindings/test_async_progress.swift",
+);
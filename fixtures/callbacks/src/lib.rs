@@ -69,6 +69,15 @@ impl RustGetters {
     ) -> Result<Option<String>, ComplexError> {
         callback.get_option(v, arg2)
     }
+    fn get_option_twice(
+        &self,
+        callback: Box<dyn ForeignGetters>,
+        v: Option<String>,
+        arg2: bool,
+    ) -> Result<Option<String>, ComplexError> {
+        callback.get_option(v.clone(), arg2)?;
+        callback.get_option(v, arg2)
+    }
     fn get_list(
         &self,
         callback: Box<dyn ForeignGetters>,
@@ -122,4 +131,42 @@ impl RustStringifier {
     }
 }
 
+trait Observer: Send + Sync {
+    fn on_event(&self, value: i32);
+}
+
+#[derive(Default)]
+pub struct Subject {
+    next_token: std::sync::atomic::AtomicU64,
+    observers: std::sync::Mutex<Vec<(u64, Box<dyn Observer>)>>,
+}
+
+impl Subject {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn add_observer(&self, observer: Box<dyn Observer>) -> u64 {
+        let token = self
+            .next_token
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.observers.lock().unwrap().push((token, observer));
+        token
+    }
+
+    fn remove_observer(&self, token: u64) {
+        self.observers.lock().unwrap().retain(|(t, _)| *t != token);
+    }
+
+    fn publish(&self, value: i32) {
+        for (_, observer) in self.observers.lock().unwrap().iter() {
+            observer.on_event(value);
+        }
+    }
+
+    fn observer_count(&self) -> u32 {
+        self.observers.lock().unwrap().len() as u32
+    }
+}
+
 uniffi::include_scaffolding!("callbacks");
@@ -3,6 +3,9 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 import uniffi.fixture_callbacks.*
+import kotlinx.coroutines.*
+import kotlinx.coroutines.flow.take
+import kotlinx.coroutines.flow.toList
 
 // A bit more systematic in testing, but this time in English.
 //
@@ -141,3 +144,18 @@ listOf(1, 2).forEach { v ->
     assert(expected == observed) { "callback is sent on construction: $expected != $observed" }
 }
 rustStringifier.destroy()
+
+// 3. Subscribe to the events of an `[Observable]` object as a `Flow`.
+val subject = Subject()
+runBlocking {
+    val events = async(Dispatchers.Default) { subject.observe().take(3).toList() }
+    // The observer is added once the flow is collected.
+    while (subject.observerCount() == 0u) {
+        delay(1)
+    }
+    listOf(1, 2, 3).forEach { subject.publish(it) }
+    assert(events.await() == listOf(1, 2, 3))
+}
+// Collection stopped after 3 events, which removed the observer.
+assert(subject.observerCount() == 0u)
+subject.destroy()
@@ -3,6 +3,7 @@
 # file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 from fixture_callbacks import *
+import fixture_callbacks
 import unittest
 
 # A bit more systematic in testing, but this time in English.
@@ -51,6 +52,16 @@ class PythonGetters(ForeignGetters):
         elif v == "unexpected-error":
             raise ValueError("unexpected value")
 
+class FreeingGetters(PythonGetters):
+    # Frees its own handle the first time `get_option()` is called, as if the foreign code had
+    # already dropped it, so any later call from Rust finds the handle gone.
+    def get_option(self, v, arg2):
+        handle_map = fixture_callbacks._UniffiConverterCallbackInterfaceForeignGetters._handle_map
+        for handle, obj in list(handle_map._left_map.items()):
+            if obj is self:
+                handle_map.remove(handle)
+        return super().get_option(v, arg2)
+
 class ForeignGettersTest(unittest.TestCase):
     def test_get_bool(self):
         callback = PythonGetters()
@@ -130,4 +141,22 @@ class TestCallbackErrors(unittest.TestCase):
             rust_getters.get_option(callback, "unexpected-error", True)
         self.assertEqual(cm.exception.reason, repr(ValueError("unexpected value")))
 
+    def test_freed_handle(self):
+        # Only methods which return a `Result` see the freed handle, as an unexpected error.
+        with self.assertRaises(ComplexError.UnexpectedErrorWithReason) as cm:
+            rust_getters.get_option_twice(FreeingGetters(), "hello", True)
+        self.assertTrue(cm.exception.reason.startswith("CallbackInterfaceGone("))
+
+class TestObservable(unittest.TestCase):
+    def test_observe(self):
+        subject = Subject()
+        events = subject.observe()
+        self.assertEqual(subject.observer_count(), 1)
+        for value in [1, 2, 3]:
+            subject.publish(value)
+        self.assertEqual([next(events) for _ in range(3)], [1, 2, 3])
+        # Closing the generator removes the observer.
+        events.close()
+        self.assertEqual(subject.observer_count(), 0)
+
 unittest.main()
@@ -5,6 +5,7 @@
 #if canImport(fixture_callbacks)
     import fixture_callbacks
 #endif
+import Foundation // To get the `DispatchGroup` type.
 
 struct SomeOtherError: Error { }
 
@@ -150,3 +151,31 @@ do {
     }
 
 }
+
+// 3. Subscribe to the events of an `[Observable]` object as an `AsyncStream`.
+do {
+    let subject = Subject()
+    let counter = DispatchGroup()
+    counter.enter()
+    Task {
+        do {
+            let events = subject.observe()
+            assert(subject.observerCount() == 1)
+            for value: Int32 in [1, 2, 3] {
+                subject.publish(value: value)
+            }
+            var received: [Int32] = []
+            for await value in events {
+                received.append(value)
+                if received.count == 3 {
+                    break
+                }
+            }
+            assert(received == [1, 2, 3])
+        }
+        // Dropping the stream removed the observer.
+        assert(subject.observerCount() == 0)
+        counter.leave()
+    }
+    counter.wait()
+}
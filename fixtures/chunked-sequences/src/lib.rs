@@ -0,0 +1,30 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Point {
+    x: i32,
+    y: i32,
+    label: String,
+}
+
+fn set_chunk_size(size: u32) {
+    uniffi::set_sequence_chunk_size(size as usize);
+}
+
+fn get_points(count: u32) -> Vec<Point> {
+    (0..count as i32)
+        .map(|i| Point {
+            x: i,
+            y: -i,
+            label: format!("point {i}"),
+        })
+        .collect()
+}
+
+fn get_points_chunked(count: u32) -> Vec<Point> {
+    get_points(count)
+}
+
+uniffi::include_scaffolding!("chunked_sequences");
@@ -0,0 +1,17 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.chunked_sequences.*;
+
+// Use a chunk size which doesn't divide the sequence, so the last chunk is partial.
+setChunkSize(7u)
+
+val points = getPoints(10_000u)
+assert(points.size == 10_000)
+assert(getPointsChunked(10_000u) == points)
+
+// Sequences which fit in one chunk, or none at all.
+assert(getPointsChunked(3u) == getPoints(3u))
+assert(getPointsChunked(7u) == getPoints(7u))
+assert(getPointsChunked(0u).isEmpty())
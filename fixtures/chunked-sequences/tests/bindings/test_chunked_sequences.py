@@ -0,0 +1,17 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from chunked_sequences import *
+
+# Use a chunk size which doesn't divide the sequence, so the last chunk is partial.
+set_chunk_size(7)
+
+points = get_points(10_000)
+assert len(points) == 10_000
+assert get_points_chunked(10_000) == points
+
+# Sequences which fit in one chunk, or none at all.
+assert get_points_chunked(3) == get_points(3)
+assert get_points_chunked(7) == get_points(7)
+assert get_points_chunked(0) == []
@@ -0,0 +1,24 @@
+# frozen_string_literal: true
+
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+require 'test/unit'
+require 'chunked_sequences'
+
+class TestChunkedSequences < Test::Unit::TestCase
+  def test_chunked_sequence
+    # Use a chunk size which doesn't divide the sequence, so the last chunk is partial.
+    ChunkedSequences.set_chunk_size(7)
+
+    points = ChunkedSequences.get_points(10_000)
+    assert_equal(points.length, 10_000)
+    assert_equal(ChunkedSequences.get_points_chunked(10_000), points)
+
+    # Sequences which fit in one chunk, or none at all.
+    assert_equal(ChunkedSequences.get_points_chunked(3), ChunkedSequences.get_points(3))
+    assert_equal(ChunkedSequences.get_points_chunked(7), ChunkedSequences.get_points(7))
+    assert_equal(ChunkedSequences.get_points_chunked(0), [])
+  end
+end
@@ -0,0 +1,17 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import chunked_sequences
+
+// Use a chunk size which doesn't divide the sequence, so the last chunk is partial.
+setChunkSize(size: 7)
+
+let points = getPoints(count: 10_000)
+assert(points.count == 10_000)
+assert(getPointsChunked(count: 10_000) == points)
+
+// Sequences which fit in one chunk, or none at all.
+assert(getPointsChunked(count: 3) == getPoints(count: 3))
+assert(getPointsChunked(count: 7) == getPoints(count: 7))
+assert(getPointsChunked(count: 0).isEmpty)
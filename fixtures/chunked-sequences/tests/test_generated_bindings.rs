@@ -0,0 +1,6 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_chunked_sequences.py",
+    "tests/bindings/test_chunked_sequences.kts",
+    "tests/bindings/test_chunked_sequences.swift",
+    "tests/bindings/test_chunked_sequences.rb",
+);
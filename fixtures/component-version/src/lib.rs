@@ -0,0 +1,24 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// `uniffi_component_version()` is generated by `setup_scaffolding!()`, from
+// `expose_component_version` in `uniffi.toml`.
+uniffi::setup_scaffolding!();
+
+#[cfg(test)]
+mod test {
+    use crate::ffi_uniffi_versions_uniffi_contract_version;
+    use crate::uniffi_component_version::uniffi_component_version;
+
+    #[test]
+    fn test_component_version() {
+        let version = uniffi_component_version();
+        assert_eq!(version.crate_version, "1.2.3");
+        assert_eq!(version.uniffi_version, uniffi::PACKAGE_VERSION);
+        assert_eq!(
+            version.contract_version,
+            ffi_uniffi_versions_uniffi_contract_version()
+        );
+    }
+}
@@ -0,0 +1,10 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from uniffi_versions import uniffi_component_version
+
+version = uniffi_component_version()
+assert version.crate_version == "1.2.3"
+assert version.uniffi_version != ""
+assert version.contract_version > 0
@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_component_version.py",
+    "tests/bindings/test_component_version.kts",
+    "tests/bindings/test_component_version.swift",
+);
@@ -3,7 +3,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::SystemTime;
 
@@ -11,8 +11,9 @@ use once_cell::sync::Lazy;
 
 mod traits;
 pub use traits::{
-    ancestor_names, get_traits, make_rust_getters, test_getters, test_round_trip_through_foreign,
-    test_round_trip_through_rust, Getters, NodeTrait,
+    ancestor_names, get_same_trait_twice, get_traits, make_rust_getters, node_names,
+    round_trip_nodes, test_getters, test_round_trip_through_foreign, test_round_trip_through_rust,
+    Getters, NodeTrait,
 };
 
 static NUM_ALIVE: Lazy<RwLock<u64>> = Lazy::new(|| RwLock::new(0));
@@ -33,6 +34,24 @@ fn throw_flat_error() -> Result<(), CoverallFlatError> {
     Err(CoverallFlatError::TooManyVariants { num: 99 })
 }
 
+#[derive(Debug, thiserror::Error)]
+#[error("Failed to open the coverall")]
+pub struct CoverallOpenError(#[source] std::io::Error);
+
+fn throw_dyn_error() -> Result<(), uniffi::DynError> {
+    let error: Box<dyn std::error::Error + Send + Sync> = Box::new(CoverallOpenError(
+        std::io::Error::new(std::io::ErrorKind::PermissionDenied, "Permission denied"),
+    ));
+    Err(error.into())
+}
+
+#[uniffi::export]
+fn throw_anyhow_error() -> Result<(), uniffi::DynError> {
+    Err(anyhow::anyhow!("Disk full")
+        .context("Failed to write the coverall")
+        .into())
+}
+
 #[derive(Debug, thiserror::Error, uniffi::Error)]
 #[uniffi(flat_error)] // "flat" isn't really the correct terminology here.
 pub enum CoverallMacroError {
@@ -67,6 +86,50 @@ fn throw_rich_error_no_variant_data() -> Result<(), CoverallRichErrorNoVariantDa
     Err(CoverallRichErrorNoVariantData::TooManyPlainVariants)
 }
 
+fn capture_panic_locations() {
+    uniffi::capture_panic_locations();
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CoverallInfallibleError {}
+
+fn infallible_add(a: u32, b: u32) -> Result<u32, CoverallInfallibleError> {
+    Ok(a + b)
+}
+
+fn join_words(separator: String, words: Vec<String>) -> String {
+    words.join(&separator)
+}
+
+fn find_hole(index: u8) -> Result<Option<String>, CoverallError> {
+    match index {
+        0 => Ok(None),
+        1 => Ok(Some("hole 1".to_string())),
+        _ => Err(CoverallError::TooManyHoles),
+    }
+}
+
+fn echo_maybe_bytes(value: Option<Vec<u8>>) -> Option<Vec<u8>> {
+    value
+}
+
+fn describe_maybe_bytes(value: Option<Vec<u8>>) -> String {
+    match value {
+        None => "absent".to_string(),
+        Some(v) if v.is_empty() => "empty".to_string(),
+        Some(v) => format!("{} bytes", v.len()),
+    }
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum CoverallInfallibleMacroError {}
+
+#[uniffi::export]
+fn infallible_macro_add(a: u32, b: u32) -> Result<u32, CoverallInfallibleMacroError> {
+    Ok(a + b)
+}
+
 /// This error doesn't appear in the interface, instead
 /// we rely on an `Into<CoverallError>` impl to surface it to consumers.
 #[derive(Debug, thiserror::Error)]
@@ -93,6 +156,55 @@ pub enum ComplexError {
     UnknownError,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum CodedError {
+    #[error("NotFound: {path} ({code})")]
+    NotFound { code: i32, path: String },
+    #[error("Timeout ({code})")]
+    Timeout { code: i32 },
+    #[error("Invalid: {reason} ({code})")]
+    Invalid { reason: String, code: i32 },
+}
+
+fn throw_coded_error(variant: u8) -> Result<(), CodedError> {
+    Err(match variant {
+        0 => CodedError::NotFound {
+            code: 404,
+            path: "/hole".to_owned(),
+        },
+        1 => CodedError::Timeout { code: 408 },
+        _ => CodedError::Invalid {
+            reason: "bad hole".to_owned(),
+            code: 400,
+        },
+    })
+}
+
+// The union error for `[Throws=(CoverallError, ComplexError)]`.
+#[derive(Debug, thiserror::Error)]
+pub enum CoverallErrorOrComplexError {
+    #[error(transparent)]
+    Coverall {
+        #[from]
+        error: CoverallError,
+    },
+    #[error(transparent)]
+    Complex {
+        #[from]
+        error: ComplexError,
+    },
+}
+
+fn throw_either(complex: bool) -> Result<(), CoverallErrorOrComplexError> {
+    if complex {
+        Err(ComplexError::PermissionDenied {
+            reason: "Forbidden".to_owned(),
+        })?
+    } else {
+        Err(CoverallError::TooManyHoles)?
+    }
+}
+
 #[derive(Debug, thiserror::Error, uniffi::Error)]
 pub enum ComplexMacroError {
     #[error("OsError: {code} ({extended_code})")]
@@ -232,6 +344,112 @@ fn try_input_return_only_dict(_d: ReturnOnlyDict) {
     // FIXME: should be a compile-time error rather than a runtime error (#1850)
 }
 
+// Serialized in a different order to the declaration, see the UDL.
+pub struct ReorderedDict {
+    first: String,
+    second: u32,
+    third: Vec<String>,
+}
+
+fn round_trip_reordered_dict(d: ReorderedDict) -> ReorderedDict {
+    d
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Click { x: u32, y: u32 },
+    Close,
+}
+
+fn round_trip_event(event: Event) -> Event {
+    event
+}
+
+#[derive(Debug, Clone)]
+pub enum AccountEvent {
+    Created {
+        account: Account,
+    },
+    Tagged {
+        account: Account,
+        tags: Vec<AccountTag>,
+    },
+    Closed,
+}
+
+#[derive(Debug, Clone)]
+pub struct Account {
+    name: String,
+    tag: Option<AccountTag>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountTag {
+    label: String,
+}
+
+fn round_trip_account_event(event: AccountEvent) -> AccountEvent {
+    event
+}
+
+#[derive(Debug, Clone)]
+pub struct Permissions {
+    read: bool,
+    write: bool,
+    execute: bool,
+    share: bool,
+    rename: bool,
+    remove: bool,
+    admin: bool,
+    audit: bool,
+    level: u8,
+    hidden: bool,
+}
+
+fn round_trip_permissions(permissions: Permissions) -> Permissions {
+    permissions
+}
+
+fn permissions_wire_size(permissions: Permissions) -> u32 {
+    let mut buf = Vec::new();
+    <Permissions as uniffi::Lower<crate::UniFfiTag>>::write(permissions, &mut buf);
+    buf.len() as u32
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Opcode {
+    Read,
+    Write,
+    Seek,
+}
+
+fn round_trip_opcodes(opcodes: Vec<Opcode>) -> Vec<Opcode> {
+    opcodes
+}
+
+fn opcodes_wire_bytes(opcodes: Vec<Opcode>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    <Vec<Opcode> as uniffi::Lower<crate::UniFfiTag>>::write(opcodes, &mut buf);
+    buf
+}
+
+#[derive(Debug, Clone)]
+pub enum Packet {
+    Data { sequence: u32 },
+    Ack { sequence: u32 },
+    Close,
+}
+
+fn round_trip_packet(packet: Packet) -> Packet {
+    packet
+}
+
+fn packet_wire_bytes(packet: Packet) -> Vec<u8> {
+    let mut buf = Vec::new();
+    <Packet as uniffi::Lower<crate::UniFfiTag>>::write(packet, &mut buf);
+    buf
+}
+
 #[derive(Debug, Clone)]
 pub struct DictWithDefaults {
     name: String,
@@ -420,6 +638,22 @@ impl Coveralls {
         (*self.other.lock().unwrap()).as_ref().map(Arc::clone)
     }
 
+    fn maybe_get_node(&self, should_throw: bool) -> Result<Arc<dyn NodeTrait>> {
+        if should_throw {
+            Err(CoverallError::TooManyHoles)
+        } else {
+            Ok(Arc::new(traits::Trait1::default()))
+        }
+    }
+
+    fn maybe_get_nodes(&self, should_throw: bool) -> Result<Vec<Arc<dyn NodeTrait>>> {
+        if should_throw {
+            Err(CoverallError::TooManyHoles)
+        } else {
+            Ok(traits::get_traits())
+        }
+    }
+
     fn take_other_fallible(self: Arc<Self>) -> Result<()> {
         Err(CoverallError::TooManyHoles)
     }
@@ -556,6 +790,21 @@ impl ThreadsafeCounter {
     }
 }
 
+#[derive(Default)]
+struct SingletonCounter {
+    count: AtomicU32,
+}
+
+impl SingletonCounter {
+    fn increment(&self) -> u32 {
+        self.count.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    fn count(&self) -> u32 {
+        self.count.load(Ordering::SeqCst)
+    }
+}
+
 #[derive(Default)]
 pub struct IFirst;
 
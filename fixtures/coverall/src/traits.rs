@@ -10,6 +10,11 @@ pub fn get_traits() -> Vec<Arc<dyn NodeTrait>> {
     vec![Arc::new(Trait1::default()), Arc::new(Trait2::default())]
 }
 
+pub fn get_same_trait_twice() -> Vec<Arc<dyn NodeTrait>> {
+    let node: Arc<dyn NodeTrait> = Arc::new(Trait1::default());
+    vec![node.clone(), node]
+}
+
 pub trait NodeTrait: Send + Sync + std::fmt::Debug {
     fn name(&self) -> String;
 
@@ -32,6 +37,15 @@ pub fn ancestor_names(node: Arc<dyn NodeTrait>) -> Vec<String> {
     names
 }
 
+// Sequences of trait objects can mix Rust and foreign implementations.
+pub fn node_names(nodes: Vec<Arc<dyn NodeTrait>>) -> Vec<String> {
+    nodes.iter().map(|node| node.name()).collect()
+}
+
+pub fn round_trip_nodes(nodes: Vec<Arc<dyn NodeTrait>>) -> Vec<Arc<dyn NodeTrait>> {
+    nodes
+}
+
 /// Test trait
 ///
 /// The goal here is to test all possible arg, return, and error types.
@@ -71,6 +71,118 @@ createNoneDict().use { d ->
     assert(d.coveralls == null)
 }
 
+assert(Coveralls.MAX_HOLES == 16u)
+assert(Coveralls.MAX_HOLE_BYTES == 64u)
+
+// The fields are serialized in a different order, but still declared in order.
+ReorderedDict("first", 2u, listOf("third")).let { d ->
+    assert(roundTripReorderedDict(d) == d)
+}
+
+// Variants with named fields and without any fields can be mixed.
+(roundTripEvent(Event.Click(1u, 2u)) as Event.Click).let { click ->
+    assert(click.x == 1u)
+    assert(click.y == 2u)
+}
+assert(roundTripEvent(Event.Close) == Event.Close)
+
+// Variants can carry records which are declared after the enum.
+run {
+    val tag = AccountTag("admin")
+    val tagged = AccountEvent.Tagged(Account("bob", tag), listOf(tag, AccountTag("ops")))
+    assert(roundTripAccountEvent(tagged) == tagged)
+    assert(roundTripAccountEvent(AccountEvent.Closed) == AccountEvent.Closed)
+}
+
+// Adjacent booleans are packed into bytes.
+run {
+    for (bit in 0 until 8) {
+        val flags = List(8) { it == bit }
+        val p = Permissions(flags[0], flags[1], flags[2], flags[3], flags[4], flags[5], flags[6], flags[7], 3u, true)
+        assert(roundTripPermissions(p) == p)
+    }
+    val p = Permissions(true, true, true, true, true, true, true, true, 255u, false)
+    assert(roundTripPermissions(p) == p)
+    assert(permissionsWireSize(p) == 3u)
+}
+
+// Enum discriminants can be serialized with a fixed width and byte order.
+run {
+    val opcodes = listOf(Opcode.SEEK, Opcode.READ, Opcode.WRITE)
+    assert(roundTripOpcodes(opcodes) == opcodes)
+    // The sequence length, then each little-endian `u16` discriminant.
+    assert(opcodesWireBytes(opcodes).toList() == listOf<Byte>(0, 0, 0, 3, 3, 0, 1, 0, 2, 0))
+
+    for (packet in listOf(Packet.Data(7u), Packet.Ack(0x01020304u), Packet.Close)) {
+        assert(roundTripPacket(packet) == packet)
+    }
+    // A `u8` discriminant, then the big-endian `u32` field.
+    assert(packetWireBytes(Packet.Ack(0x01020304u)).toList() == listOf<Byte>(2, 1, 2, 3, 4))
+    assert(packetWireBytes(Packet.Close).toList() == listOf<Byte>(3))
+}
+
+// Build lots of random pairs of records, most of which share most of their field values, and check
+// that equal records always have equal hash codes.
+run {
+    val rng = kotlin.random.Random(42)
+    fun <T> pick(vararg values: T): T = values[rng.nextInt(values.size)]
+    fun <T> maybeChange(value: T, vararg values: T): T = if (rng.nextInt(20) == 0) pick(*values) else value
+
+    // `ByteArray` uses identity equality, so pick from a fixed set of arrays.
+    val byteArrays = arrayOf(byteArrayOf(), byteArrayOf(0), "some_bytes".toByteArray(Charsets.UTF_8))
+    fun randomDict() = SimpleDict(
+        text = pick("", "text", "other text"),
+        maybeText = pick(null, "", "maybe_text"),
+        someBytes = pick(*byteArrays),
+        maybeSomeBytes = pick(null, *byteArrays),
+        aBool = pick(true, false),
+        maybeABool = pick(null, true, false),
+        unsigned8 = pick(0u, 1u, 255u).toUByte(),
+        maybeUnsigned8 = pick(null, 0u.toUByte(), 1u.toUByte()),
+        unsigned16 = pick(0u, 1u, 65535u).toUShort(),
+        maybeUnsigned16 = pick(null, 0u.toUShort(), 1u.toUShort()),
+        unsigned64 = pick(0UL, 1UL, ULong.MAX_VALUE),
+        maybeUnsigned64 = pick(null, 0UL, 1UL),
+        signed8 = pick(Byte.MIN_VALUE, 0.toByte(), Byte.MAX_VALUE),
+        maybeSigned8 = pick(null, 0.toByte(), (-1).toByte()),
+        signed64 = pick(Long.MIN_VALUE, 0L, Long.MAX_VALUE),
+        maybeSigned64 = pick(null, 0L, -1L),
+        float32 = pick(0.0F, -0.0F, 1.5F),
+        maybeFloat32 = pick(null, 0.0F, 1.5F),
+        float64 = pick(0.0, -0.0, 2.25),
+        maybeFloat64 = pick(null, 0.0, 2.25),
+        coveralls = null,
+        testTrait = null,
+    )
+
+    var equalPairs = 0
+    repeat(1000) {
+        val a = randomDict()
+        val b = a.copy(
+            text = maybeChange(a.text, "", "text"),
+            maybeText = maybeChange(a.maybeText, null, "maybe_text"),
+            someBytes = maybeChange(a.someBytes, *byteArrays),
+            aBool = maybeChange(a.aBool, true, false),
+            unsigned8 = maybeChange(a.unsigned8, 0u.toUByte(), 1u.toUByte()),
+            unsigned64 = maybeChange(a.unsigned64, 0UL, 1UL),
+            signed8 = maybeChange(a.signed8, 0.toByte(), 1.toByte()),
+            signed64 = maybeChange(a.signed64, 0L, 1L),
+            float32 = maybeChange(a.float32, 0.0F, -0.0F),
+            float64 = maybeChange(a.float64, 0.0, -0.0),
+        )
+        if (a == b) {
+            equalPairs += 1
+            assert(a.hashCode() == b.hashCode()) { "$a and $b are equal but have different hash codes" }
+        }
+    }
+    // Make sure the property was actually exercised.
+    assert(equalPairs > 0)
+
+    // Records can be used as map keys.
+    val d = randomDict()
+    assert(mapOf(d to "value")[d.copy()] == "value")
+}
+
 
 // Test arcs.
 
@@ -202,6 +314,88 @@ Coveralls("test_complex_errors").use { coveralls ->
     }
 }
 
+// Functions which throw either of two errors throw a union of them.
+for (complex in listOf(false, true)) {
+    try {
+        throwEither(complex)
+        throw RuntimeException("Should have thrown")
+    } catch(e: CoverallErrorOrComplexException) {
+        when (e) {
+            is CoverallErrorOrComplexException.Coverall -> {
+                assert(!complex)
+                assert(e.error is CoverallException.TooManyHoles)
+            }
+            is CoverallErrorOrComplexException.Complex -> {
+                assert(complex)
+                val error = e.error
+                assert(error is ComplexException.PermissionDenied && error.reason == "Forbidden")
+            }
+        }
+    }
+}
+
+// Errors with no variants can't actually be thrown.
+assert(infallibleAdd(1u, 2u) == 3u)
+
+assert(joinWords(", ") == "")
+assert(joinWords(", ", "one") == "one")
+assert(joinWords(", ", "one", "two", "three") == "one, two, three")
+assert(joinWords(", ", *arrayOf("one", "two")) == "one, two")
+assert(infallibleMacroAdd(1u, 2u) == 3u)
+
+// `null` means there's no value, which is different to throwing an error.
+assert(findHole(0u) == null)
+assert(findHole(1u) == "hole 1")
+try {
+    findHole(2u)
+    throw RuntimeException("Expected method to throw exception")
+} catch(e: CoverallException.TooManyHoles) {
+    // Expected result
+}
+// `[DefaultOnNone]` substitutes the default for `null`, but still throws errors.
+assert(findHoleOrDefault(0u) == "no hole")
+assert(findHoleOrDefault(1u) == "hole 1")
+try {
+    findHoleOrDefault(2u)
+    throw RuntimeException("Expected method to throw exception")
+} catch(e: CoverallException.TooManyHoles) {
+    // Expected result
+}
+
+// Absent bytes are `null`, which is different to an empty `ByteArray`.
+assert(echoMaybeBytes(null) == null)
+assert(echoMaybeBytes(byteArrayOf())!!.isEmpty())
+assert(echoMaybeBytes(byteArrayOf(1, 2, 3))!!.contentEquals(byteArrayOf(1, 2, 3)))
+assert(describeMaybeBytes(null) == "absent")
+assert(describeMaybeBytes(byteArrayOf()) == "empty")
+assert(describeMaybeBytes(byteArrayOf(1, 2, 3)) == "3 bytes")
+
+// Every variant of `CodedException` has a `code`, so it's available on the base type.
+listOf(404, 408, 400).forEachIndexed { variant, expected ->
+    try {
+        throwCodedError(variant.toUByte())
+        throw RuntimeException("Expected method to throw exception")
+    } catch(e: CodedException) {
+        assert(e.code == expected)
+    }
+}
+
+try {
+    throwDynError()
+    throw RuntimeException("Expected method to throw exception")
+} catch(e: DynException) {
+    assert(e.message == "Failed to open the coverall")
+    assert(e.causeChain == listOf("Permission denied"))
+}
+
+try {
+    throwAnyhowError()
+    throw RuntimeException("Expected method to throw exception")
+} catch(e: DynException) {
+    assert(e.message == "Failed to write the coverall")
+    assert(e.causeChain == listOf("Disk full"))
+}
+
 Coveralls("test_error_values").use { _coveralls ->
     try {
         throwRootError()
@@ -399,6 +593,50 @@ getTraits().let { traits ->
     // not possible through the `NodeTrait` interface (see #1787).
 }
 
+// Sequences of trait objects can mix Rust and Kotlin implementations.
+listOf(getTraits()[0], KotlinNode()).let { nodes ->
+    assert(nodeNames(nodes) == listOf("node-1", "node-kt"))
+    assert(roundTripNodes(nodes).map { it.name() } == listOf("node-1", "node-kt"))
+    assert(nodeNames(listOf()).isEmpty())
+}
+
+// Two handles to the same Rust object are equal, even though they're different instances.
+Coveralls("test_object_identity").use { coveralls ->
+    coveralls.takeOther(coveralls)
+    coveralls.getOther()!!.use { other ->
+        assert(other !== coveralls)
+        assert(other == coveralls)
+        assert(other.hashCode() == coveralls.hashCode())
+    }
+    coveralls.cloneMe().use { c2 -> assert(c2 != coveralls) }
+    coveralls.takeOther(null)
+}
+
+getSameTraitTwice().let { (first, second) ->
+    assert(first == second)
+    assert(first != getTraits()[0])
+}
+
+// Methods can return a trait object or throw.
+Coveralls("test_fallible_traits").use { coveralls ->
+    assert(coveralls.maybeGetNode(false).name() == "node-1")
+    try {
+        coveralls.maybeGetNode(true)
+        throw RuntimeException("Should have thrown a TooManyHoles exception!")
+    } catch (e: CoverallException.TooManyHoles) {
+        // It's okay!
+    }
+
+    // And the same for a list of trait objects.
+    assert(coveralls.maybeGetNodes(false).map { it.name() } == listOf("node-1", "node-2"))
+    try {
+        coveralls.maybeGetNodes(true)
+        throw RuntimeException("Should have thrown a TooManyHoles exception!")
+    } catch (e: CoverallException.TooManyHoles) {
+        // It's okay!
+    }
+}
+
 makeRustGetters().let { rustGetters ->
     // Check that these don't cause use-after-free bugs
     testRoundTripThroughRust(rustGetters)
@@ -406,6 +644,17 @@ makeRustGetters().let { rustGetters ->
     testRoundTripThroughForeign(KotlinGetters())
 }
 
+// Every call site gets the same singleton instance, so they see each other's changes.
+SingletonCounter.instance.let { first ->
+    val second = SingletonCounter.instance
+    assert(first === second)
+    val count = first.count()
+    assert(first.increment() == count + 1u)
+    assert(second.increment() == count + 2u)
+    assert(first.count() == count + 2u)
+    assert(second.count() == count + 2u)
+}
+
 // This tests that the UniFFI-generated scaffolding doesn't introduce any unexpected locking.
 // We have one thread busy-wait for a some period of time, while a second thread repeatedly
 // increments the counter and then checks if the object is still busy. The second thread should
@@ -499,3 +748,14 @@ Coveralls("using_fakes_with_real_objects_crashes").use { coveralls ->
     }
     assert(exception != null)
 }
+
+// This is last, since it changes the panic messages for the rest of the process.
+capturePanicLocations()
+Coveralls("test_panic_locations").use { coveralls ->
+    try {
+        coveralls.panic("oh no")
+        throw RuntimeException("Expected method to throw exception")
+    } catch(e: InternalException) {
+        assert(Regex("""oh no \(at .*src[/\\]lib\.rs:\d+:\d+\)""").matches(e.message!!))
+    }
+}
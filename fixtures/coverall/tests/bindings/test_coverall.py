@@ -2,6 +2,7 @@
 # License, v. 2.0. If a copy of the MPL was not distributed with this
 # file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+import random
 import unittest
 from datetime import datetime, timezone
 
@@ -13,6 +14,55 @@ class TestCoverall(unittest.TestCase):
     def tearDown(self):
         self.assertEqual(get_num_alive(), 0)
 
+    def test_record_hash_consistent_with_eq(self):
+        # Build lots of random pairs of records, most of which share most of their
+        # field values, and check that equal records always have equal hashes.
+        rng = random.Random(42)
+        field_values = {
+            "text": ["", "text", "other text"],
+            "maybe_text": [None, "", "maybe_text"],
+            "some_bytes": [b"", b"\x00", b"some_bytes"],
+            "maybe_some_bytes": [None, b"", b"maybe_some_bytes"],
+            "a_bool": [True, False],
+            "maybe_a_bool": [None, True, False],
+            "unsigned8": [0, 1, 255],
+            "maybe_unsigned8": [None, 0, 1],
+            "unsigned16": [0, 1, 65535],
+            "maybe_unsigned16": [None, 0, 1],
+            "unsigned64": [0, 1, 2**64 - 1],
+            "maybe_unsigned64": [None, 0, 1],
+            "signed8": [-128, 0, 127],
+            "maybe_signed8": [None, 0, -1],
+            "signed64": [-2**63, 0, 2**63 - 1],
+            "maybe_signed64": [None, 0, -1],
+            "float32": [0.0, -0.0, 1.5],
+            "maybe_float32": [None, 0.0, 1.5],
+            "float64": [0.0, -0.0, 2.25],
+            "maybe_float64": [None, 0.0, 2.25],
+            "coveralls": [None],
+            "test_trait": [None],
+        }
+        equal_pairs = 0
+        for _ in range(1000):
+            a = {name: rng.choice(values) for name, values in field_values.items()}
+            b = {
+                name: a[name] if rng.random() < 0.95 else rng.choice(values)
+                for name, values in field_values.items()
+            }
+            record_a, record_b = SimpleDict(**a), SimpleDict(**b)
+            self.assertEqual(record_a == record_b, a == b)
+            if record_a == record_b:
+                equal_pairs += 1
+                self.assertEqual(hash(record_a), hash(record_b))
+        # Make sure the property was actually exercised.
+        self.assertGreater(equal_pairs, 0)
+
+        # Records can be used as dict keys and in sets.
+        d = create_none_dict()
+        self.assertEqual({d: "value"}[create_none_dict()], "value")
+        self.assertEqual(len({create_none_dict(), create_none_dict()}), 1)
+        self.assertNotEqual(d, "not a record")
+
     def test_some_dict(self):
         d = create_some_dict()
         self.assertEqual(d.text, "text")
@@ -122,6 +172,23 @@ class TestCoverall(unittest.TestCase):
         with self.assertRaisesRegex(InternalError, "expected panic: oh no"):
             coveralls.panic("expected panic: oh no")
 
+    def test_panic_locations(self):
+        capture_panic_locations()
+        coveralls = Coveralls("test_panic_locations")
+        with self.assertRaisesRegex(InternalError, r"^oh no \(at .*src[/\\]lib\.rs:\d+:\d+\)$"):
+            coveralls.panic("oh no")
+
+    def test_union_errors(self):
+        with self.assertRaises(CoverallErrorOrComplexError.Coverall) as cm:
+            throw_either(False)
+        self.assertIsInstance(cm.exception.error, CoverallError.TooManyHoles)
+
+        with self.assertRaises(CoverallErrorOrComplexError) as cm:
+            throw_either(True)
+        self.assertIsInstance(cm.exception, CoverallErrorOrComplexError.Complex)
+        self.assertIsInstance(cm.exception.error, ComplexError.PermissionDenied)
+        self.assertEqual(cm.exception.error.reason, "Forbidden")
+
     def test_flat_errors(self):
         # This is testing enums which have fields in Rust but are marked as "flat" for the ffi.
         with self.assertRaisesRegex(CoverallFlatError.TooManyVariants, "Too many variants: 99") as cm:
@@ -180,6 +247,58 @@ class TestCoverall(unittest.TestCase):
         with self.assertRaises(InternalError) as cm:
             coveralls.maybe_throw_complex(4)
 
+    def test_dyn_errors(self):
+        with self.assertRaises(DynError) as cm:
+            throw_dyn_error()
+        self.assertEqual(cm.exception.message, "Failed to open the coverall")
+        self.assertEqual(str(cm.exception), "Failed to open the coverall")
+        self.assertEqual(cm.exception.cause_chain, ["Permission denied"])
+
+        with self.assertRaises(DynError) as cm:
+            throw_anyhow_error()
+        self.assertEqual(cm.exception.message, "Failed to write the coverall")
+        self.assertEqual(cm.exception.cause_chain, ["Disk full"])
+
+    def test_infallible_errors(self):
+        # These are declared as throwing errors with no variants, which can't actually be thrown.
+        self.assertEqual(infallible_add(1, 2), 3)
+        self.assertEqual(infallible_macro_add(1, 2), 3)
+        self.assertTrue(issubclass(CoverallInfallibleError, Exception))
+
+    def test_variadic_arguments(self):
+        # Python takes the variadic arguments as a list.
+        self.assertEqual(join_words(", ", []), "")
+        self.assertEqual(join_words(", ", ["one"]), "one")
+        self.assertEqual(join_words(", ", ["one", "two", "three"]), "one, two, three")
+
+    def test_optional_throwing_return(self):
+        # `None` means there's no value, which is different to throwing an error.
+        self.assertIsNone(find_hole(0))
+        self.assertEqual(find_hole(1), "hole 1")
+        with self.assertRaises(CoverallError.TooManyHoles):
+            find_hole(2)
+        # `[DefaultOnNone]` substitutes the default for `None`, but still raises errors.
+        self.assertEqual(find_hole_or_default(0), "no hole")
+        self.assertEqual(find_hole_or_default(1), "hole 1")
+        with self.assertRaises(CoverallError.TooManyHoles):
+            find_hole_or_default(2)
+
+    def test_optional_bytes(self):
+        # Absent bytes are `None`, which is different to empty bytes.
+        self.assertIsNone(echo_maybe_bytes(None))
+        self.assertEqual(echo_maybe_bytes(b""), b"")
+        self.assertEqual(echo_maybe_bytes(b"\x01\x02\x03"), b"\x01\x02\x03")
+        self.assertEqual(describe_maybe_bytes(None), "absent")
+        self.assertEqual(describe_maybe_bytes(b""), "empty")
+        self.assertEqual(describe_maybe_bytes(b"\x01\x02\x03"), "3 bytes")
+
+    def test_common_error_fields(self):
+        # Every variant of `CodedError` has a `code`, so it can be read from the base type.
+        for variant, expected in enumerate([404, 408, 400]):
+            with self.assertRaises(CodedError) as cm:
+                throw_coded_error(variant)
+            self.assertEqual(cm.exception.code, expected)
+
     def test_error_values(self):
         with self.assertRaises(RootError.Complex) as cm:
             throw_root_error()
@@ -195,6 +314,55 @@ class TestCoverall(unittest.TestCase):
         e = get_simple_flat_macro_enum(0)
         self.assertTrue(isinstance(e, SimpleFlatMacroEnum.FIRST))
 
+    def test_associated_constants(self):
+        self.assertEqual(Coveralls.MAX_HOLES, 16)
+        self.assertEqual(Coveralls.MAX_HOLE_BYTES, 64)
+
+    def test_reordered_dict(self):
+        # The fields are serialized in a different order, but still declared in order.
+        d = ReorderedDict("first", 2, ["third"])
+        self.assertEqual(round_trip_reordered_dict(d), d)
+
+    def test_enum_variants_with_named_fields(self):
+        click = round_trip_event(Event.CLICK(x=1, y=2))
+        self.assertTrue(click.is_click())
+        self.assertEqual(click.x, 1)
+        self.assertEqual(click.y, 2)
+        self.assertEqual(click, Event.CLICK(x=1, y=2))
+        self.assertTrue(round_trip_event(Event.CLOSE()).is_close())
+
+    def test_enum_variants_with_records_declared_later(self):
+        tag = AccountTag(label="admin")
+        created = AccountEvent.CREATED(account=Account(name="alice", tag=None))
+        self.assertEqual(round_trip_account_event(created), created)
+        tagged = AccountEvent.TAGGED(account=Account(name="bob", tag=tag), tags=[tag, AccountTag(label="ops")])
+        self.assertEqual(round_trip_account_event(tagged), tagged)
+        self.assertEqual(round_trip_account_event(tagged).account.tag.label, "admin")
+        self.assertTrue(round_trip_account_event(AccountEvent.CLOSED()).is_closed())
+
+    def test_packed_dict(self):
+        flags = ["read", "write", "execute", "share", "rename", "remove", "admin", "audit"]
+        # Each of the eight booleans gets its own bit.
+        for set_flag in flags:
+            p = Permissions(level=3, hidden=True, **{flag: flag == set_flag for flag in flags})
+            self.assertEqual(round_trip_permissions(p), p)
+        p = Permissions(level=255, hidden=False, **{flag: True for flag in flags})
+        self.assertEqual(round_trip_permissions(p), p)
+        # One byte for the eight booleans, one for `level` and one for `hidden`.
+        self.assertEqual(permissions_wire_size(p), 3)
+
+    def test_enum_discriminant_encoding(self):
+        opcodes = [Opcode.SEEK, Opcode.READ, Opcode.WRITE]
+        self.assertEqual(round_trip_opcodes(opcodes), opcodes)
+        # The sequence length, then each little-endian `u16` discriminant.
+        self.assertEqual(opcodes_wire_bytes(opcodes), bytes([0, 0, 0, 3, 3, 0, 1, 0, 2, 0]))
+
+        for packet in [Packet.DATA(sequence=7), Packet.ACK(sequence=0x01020304), Packet.CLOSE()]:
+            self.assertEqual(round_trip_packet(packet), packet)
+        # A `u8` discriminant, then the big-endian `u32` field.
+        self.assertEqual(packet_wire_bytes(Packet.ACK(sequence=0x01020304)), bytes([2, 1, 2, 3, 4]))
+        self.assertEqual(packet_wire_bytes(Packet.CLOSE()), bytes([3]))
+
     def test_self_by_arc(self):
         coveralls = Coveralls("test_self_by_arc")
         # One reference is held by the handlemap, and one by the `Arc<Self>` method receiver.
@@ -254,6 +422,22 @@ class TestCoverall(unittest.TestCase):
         coveralls = None
         self.assertEqual(get_num_alive(), 0)
 
+    def test_object_identity(self):
+        # Two handles to the same Rust object are equal, even though they're different instances.
+        coveralls = Coveralls("test_object_identity")
+        coveralls.take_other(coveralls)
+        other = coveralls.get_other()
+        self.assertIsNot(other, coveralls)
+        self.assertEqual(other, coveralls)
+        self.assertEqual(hash(other), hash(coveralls))
+        self.assertNotEqual(coveralls.clone_me(), coveralls)
+        coveralls.take_other(None)
+
+        # Each handle to a trait object is a separate pointer, but they're still equal.
+        [first, second] = get_same_trait_twice()
+        self.assertEqual(first, second)
+        self.assertNotEqual(first, get_traits()[0])
+
     def test_bad_objects(self):
         coveralls = Coveralls("test_bad_objects")
         patch = Patch(Color.RED)
@@ -435,6 +619,37 @@ class TraitsTest(unittest.TestCase):
         py_node.set_parent(None)
         traits[0].set_parent(None)
 
+    def test_trait_sequences(self):
+        # Sequences of trait objects can mix Rust and Python implementations.
+        nodes = [get_traits()[0], PyNode()]
+        self.assertEqual(node_names(nodes), ["node-1", "node-py"])
+        self.assertEqual([node.name() for node in round_trip_nodes(nodes)], ["node-1", "node-py"])
+        self.assertEqual(node_names([]), [])
+
+    def test_fallible_traits(self):
+        # Methods can return a trait object or throw.
+        coveralls = Coveralls("test_fallible_traits")
+        self.assertEqual(coveralls.maybe_get_node(False).name(), "node-1")
+        with self.assertRaises(CoverallError.TooManyHoles):
+            coveralls.maybe_get_node(True)
+
+        # And the same for a list of trait objects.
+        self.assertEqual([node.name() for node in coveralls.maybe_get_nodes(False)], ["node-1", "node-2"])
+        with self.assertRaises(CoverallError.TooManyHoles):
+            coveralls.maybe_get_nodes(True)
+        coveralls = None
+
+    def test_singleton(self):
+        # Every call site gets the same instance, so they see each other's changes.
+        first = SingletonCounter.instance()
+        second = SingletonCounter.instance()
+        self.assertIs(first, second)
+        count = first.count()
+        self.assertEqual(first.increment(), count + 1)
+        self.assertEqual(second.increment(), count + 2)
+        self.assertEqual(first.count(), count + 2)
+        self.assertEqual(second.count(), count + 2)
+
     def test_round_tripping(self):
         rust_getters = make_rust_getters();
         coveralls = Coveralls("test_round_tripping")
@@ -128,6 +128,55 @@ class TestCoverall < Test::Unit::TestCase
     end
   end
 
+  def test_dyn_errors
+    err = assert_raise Coverall::DynError do
+      Coverall.throw_dyn_error
+    end
+    assert_equal err.message, 'Failed to open the coverall'
+    assert_equal err.cause_chain, ['Permission denied']
+  end
+
+  def test_infallible_errors
+    # This is declared as throwing an error with no variants, which can't actually be thrown.
+    assert_equal 3, Coverall.infallible_add(1, 2)
+  end
+
+  def test_optional_throwing_return
+    # `nil` means there's no value, which is different to raising an error.
+    assert_nil Coverall.find_hole(0)
+    assert_equal 'hole 1', Coverall.find_hole(1)
+    assert_raise Coverall::CoverallError::TooManyHoles do
+      Coverall.find_hole(2)
+    end
+    # `[DefaultOnNone]` substitutes the default for `nil`, but still raises errors.
+    assert_equal 'no hole', Coverall.find_hole_or_default(0)
+    assert_equal 'hole 1', Coverall.find_hole_or_default(1)
+    assert_raise Coverall::CoverallError::TooManyHoles do
+      Coverall.find_hole_or_default(2)
+    end
+  end
+
+  def test_optional_bytes
+    # Absent bytes are `nil`, which is different to an empty byte string.
+    assert_nil Coverall.echo_maybe_bytes(nil)
+    assert_equal '', Coverall.echo_maybe_bytes('')
+    assert_equal Encoding::BINARY, Coverall.echo_maybe_bytes('').encoding
+    assert_equal "\x01\x02\x03", Coverall.echo_maybe_bytes("\x01\x02\x03")
+    assert_equal 'absent', Coverall.describe_maybe_bytes(nil)
+    assert_equal 'empty', Coverall.describe_maybe_bytes('')
+    assert_equal '3 bytes', Coverall.describe_maybe_bytes("\x01\x02\x03")
+  end
+
+  def test_common_error_fields
+    # Every variant of `CodedError` has a `code`, so it can be read from the base type.
+    [404, 408, 400].each_with_index do |expected, variant|
+      err = assert_raise Coverall::CodedError do
+        Coverall.throw_coded_error(variant)
+      end
+      assert_equal expected, err.code
+    end
+  end
+
   def test_complex_errors
     coveralls = Coverall::Coveralls.new 'test_complex_errors'
     assert_equal coveralls.maybe_throw_complex(0), true
@@ -253,6 +302,18 @@ class TestCoverall < Test::Unit::TestCase
     end
   end
 
+  def test_singleton
+    # Every call site gets the same instance, so they see each other's changes.
+    first = Coverall::SingletonCounter.instance
+    second = Coverall::SingletonCounter.instance
+    assert_same first, second
+    count = first.count
+    assert_equal first.increment, count + 1
+    assert_equal second.increment, count + 2
+    assert_equal first.count, count + 2
+    assert_equal second.count, count + 2
+  end
+
   def test_bytes
     coveralls = Coverall::Coveralls.new "test_bytes"
     assert_equal coveralls.reverse("123"), "321"
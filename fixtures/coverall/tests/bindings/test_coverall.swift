@@ -73,6 +73,67 @@ do {
     assert(d.coveralls == nil)
 }
 
+assert(Coveralls.maxHoles == 16)
+
+// The fields are serialized in a different order, but still declared in order.
+do {
+    let d = ReorderedDict(first: "first", second: 2, third: ["third"])
+    assert(roundTripReorderedDict(d: d) == d)
+}
+
+// Variants with named fields and without any fields can be mixed.
+do {
+    guard case let .click(x, y) = roundTripEvent(event: .click(x: 1, y: 2)) else {
+        fatalError("Expected a click event")
+    }
+    assert(x == 1)
+    assert(y == 2)
+    assert(roundTripEvent(event: .close) == .close)
+}
+
+// Variants can carry records which are declared after the enum.
+do {
+    let tag = AccountTag(label: "admin")
+    let tagged = AccountEvent.tagged(account: Account(name: "bob", tag: tag), tags: [tag, AccountTag(label: "ops")])
+    assert(roundTripAccountEvent(event: tagged) == tagged)
+    assert(roundTripAccountEvent(event: .closed) == .closed)
+}
+
+// Adjacent booleans are packed into bytes.
+do {
+    for bit in 0..<8 {
+        let flags = (0..<8).map { $0 == bit }
+        let p = Permissions(
+            read: flags[0], write: flags[1], execute: flags[2], share: flags[3],
+            rename: flags[4], remove: flags[5], admin: flags[6], audit: flags[7],
+            level: 3, hidden: true
+        )
+        assert(roundTripPermissions(permissions: p) == p)
+    }
+    let p = Permissions(
+        read: true, write: true, execute: true, share: true,
+        rename: true, remove: true, admin: true, audit: true,
+        level: 255, hidden: false
+    )
+    assert(roundTripPermissions(permissions: p) == p)
+    assert(permissionsWireSize(permissions: p) == 3)
+}
+
+// Enum discriminants can be serialized with a fixed width and byte order.
+do {
+    let opcodes: [Opcode] = [.seek, .read, .write]
+    assert(roundTripOpcodes(opcodes: opcodes) == opcodes)
+    // The sequence length, then each little-endian `u16` discriminant.
+    assert(opcodesWireBytes(opcodes: opcodes) == Data([0, 0, 0, 3, 3, 0, 1, 0, 2, 0]))
+
+    for packet in [Packet.data(sequence: 7), .ack(sequence: 0x01020304), .close] {
+        assert(roundTripPacket(packet: packet) == packet)
+    }
+    // A `u8` discriminant, then the big-endian `u32` field.
+    assert(packetWireBytes(packet: .ack(sequence: 0x01020304)) == Data([2, 1, 2, 3, 4]))
+    assert(packetWireBytes(packet: .close) == Data([3]))
+}
+
 // Test arcs.
 do {
     let coveralls = Coveralls(name: "test_arcs")
@@ -179,6 +240,88 @@ do {
 
 }
 
+// Functions which throw either of two errors throw a union of them.
+do {
+    try throwEither(complex: false)
+    fatalError("should have thrown")
+} catch CoverallErrorOrComplexError.Coverall(let error) {
+    assert(error == .TooManyHoles(message: "The coverall has too many holes"))
+}
+
+do {
+    try throwEither(complex: true)
+    fatalError("should have thrown")
+} catch let e as CoverallErrorOrComplexError {
+    switch e {
+    case .Coverall:
+        fatalError("wrong error variant: \(e)")
+    case let .Complex(error):
+        assert(error == .PermissionDenied(reason: "Forbidden"))
+    }
+}
+
+// Errors with no variants can't actually be thrown.
+assert(try! infallibleAdd(a: 1, b: 2) == 3)
+
+assert(joinWords(separator: ", ") == "")
+assert(joinWords(separator: ", ", "one") == "one")
+assert(joinWords(separator: ", ", "one", "two", "three") == "one, two, three")
+assert(try! infallibleMacroAdd(a: 1, b: 2) == 3)
+
+// `nil` means there's no value, which is different to throwing an error.
+assert(try! findHole(index: 0) == nil)
+assert(try! findHole(index: 1) == "hole 1")
+do {
+    _ = try findHole(index: 2)
+    fatalError("should have thrown")
+} catch CoverallError.TooManyHoles {
+    // It's okay!
+}
+// `[DefaultOnNone]` substitutes the default for `nil`, but still throws errors.
+assert(try! findHoleOrDefault(index: 0) == "no hole")
+assert(try! findHoleOrDefault(index: 1) == "hole 1")
+do {
+    _ = try findHoleOrDefault(index: 2)
+    fatalError("should have thrown")
+} catch CoverallError.TooManyHoles {
+    // It's okay!
+}
+
+// Absent bytes are `nil`, which is different to an empty `Data`.
+assert(echoMaybeBytes(value: nil) == nil)
+assert(echoMaybeBytes(value: Data()) == Data())
+assert(echoMaybeBytes(value: Data([1, 2, 3])) == Data([1, 2, 3]))
+assert(describeMaybeBytes(value: nil) == "absent")
+assert(describeMaybeBytes(value: Data()) == "empty")
+assert(describeMaybeBytes(value: Data([1, 2, 3])) == "3 bytes")
+
+// Every variant of `CodedError` has a `code`, so it's available without matching on the variant.
+for (variant, expected) in [Int32(404), 408, 400].enumerated() {
+    do {
+        try throwCodedError(variant: UInt8(variant))
+        fatalError("should have thrown")
+    } catch let e as CodedError {
+        assert(e.code == expected)
+    }
+}
+
+// Test dynamically typed errors.
+do {
+    try throwDynError()
+    fatalError("should have thrown")
+} catch let e as DynError {
+    assert(e.message == "Failed to open the coverall")
+    assert(e.causeChain == ["Permission denied"])
+}
+
+do {
+    try throwAnyhowError()
+    fatalError("should have thrown")
+} catch let e as DynError {
+    assert(e.message == "Failed to write the coverall")
+    assert(e.causeChain == ["Disk full"])
+}
+
 // Test error values, including error enums with error variants.
 do {
     do {
@@ -449,6 +592,51 @@ do {
     traits[0].setParent(parent: nil)
 }
 
+// Sequences of trait objects can mix Rust and Swift implementations.
+do {
+    let nodes: [NodeTrait] = [getTraits()[0], SwiftNode()]
+    assert(nodeNames(nodes: nodes) == ["node-1", "node-swift"])
+    assert(roundTripNodes(nodes: nodes).map { $0.name() } == ["node-1", "node-swift"])
+    assert(nodeNames(nodes: []) == [])
+}
+
+// Two handles to the same Rust object are equal, even though they're different instances.
+do {
+    let coveralls = Coveralls(name: "test_object_identity")
+    coveralls.takeOther(other: coveralls)
+    let other = coveralls.getOther()!
+    assert(other !== coveralls)
+    assert(other == coveralls)
+    assert(other.hashValue == coveralls.hashValue)
+    assert(coveralls.cloneMe() != coveralls)
+    coveralls.takeOther(other: nil)
+
+    let nodes = getSameTraitTwice().map { $0 as! NodeTraitImpl }
+    assert(nodes[0] == nodes[1])
+    assert(nodes[0] != getTraits()[0] as! NodeTraitImpl)
+}
+
+// Methods can return a trait object or throw.
+do {
+    let coveralls = Coveralls(name: "test_fallible_traits")
+    assert(try! coveralls.maybeGetNode(shouldThrow: false).name() == "node-1")
+    do {
+        let _ = try coveralls.maybeGetNode(shouldThrow: true)
+        fatalError("Should have thrown")
+    } catch CoverallError.TooManyHoles {
+        // It's okay!
+    }
+
+    // And the same for a list of trait objects.
+    assert(try! coveralls.maybeGetNodes(shouldThrow: false).map { $0.name() } == ["node-1", "node-2"])
+    do {
+        let _ = try coveralls.maybeGetNodes(shouldThrow: true)
+        fatalError("Should have thrown")
+    } catch CoverallError.TooManyHoles {
+        // It's okay!
+    }
+}
+
 // Test round tripping
 do {
     let rustGetters = makeRustGetters()
@@ -457,3 +645,28 @@ do {
 
     testRoundTripThroughForeign(getters: SwiftGetters())
 }
+
+// Every call site gets the same singleton instance, so they see each other's changes.
+do {
+    let first = SingletonCounter.instance
+    let second = SingletonCounter.instance
+    assert(first === second)
+    let count = first.count()
+    assert(first.increment() == count + 1)
+    assert(second.increment() == count + 2)
+    assert(first.count() == count + 2)
+    assert(second.count() == count + 2)
+}
+
+// This is last, since it changes the panic messages for the rest of the process.
+capturePanicLocations()
+do {
+    let coveralls = Coveralls(name: "test_panic_locations")
+    do {
+        try coveralls.falliblePanic(message: "oh no")
+        fatalError("Should have thrown")
+    } catch UniffiInternalError.rustPanic(let message) {
+        assert(message.hasPrefix("oh no (at "))
+        assert(message.contains("lib.rs:"))
+    }
+}
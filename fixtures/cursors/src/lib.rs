@@ -0,0 +1,64 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Mutex;
+
+pub struct Row {
+    id: u32,
+    name: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum QueryError {
+    #[error("The cursor is closed")]
+    Closed,
+}
+
+#[derive(Default)]
+struct State {
+    next_id: u32,
+    batch_sizes: Vec<u32>,
+    closed: bool,
+}
+
+pub struct Rows {
+    count: u32,
+    state: Mutex<State>,
+}
+
+impl Rows {
+    fn new(count: u32) -> Self {
+        Self {
+            count,
+            state: Mutex::default(),
+        }
+    }
+
+    fn next_batch(&self, count: u32) -> Result<Vec<Row>, QueryError> {
+        let mut state = self.state.lock().unwrap();
+        if state.closed {
+            return Err(QueryError::Closed);
+        }
+        let end = self.count.min(state.next_id.saturating_add(count));
+        let rows: Vec<Row> = (state.next_id..end)
+            .map(|id| Row {
+                id,
+                name: format!("row {id}"),
+            })
+            .collect();
+        state.next_id = end;
+        state.batch_sizes.push(rows.len() as u32);
+        Ok(rows)
+    }
+
+    fn batch_sizes(&self) -> Vec<u32> {
+        self.state.lock().unwrap().batch_sizes.clone()
+    }
+
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+    }
+}
+
+uniffi::include_scaffolding!("cursors");
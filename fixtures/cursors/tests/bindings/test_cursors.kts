@@ -0,0 +1,32 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.cursors.*
+
+// The final short batch ends the sequence.
+val rows = Rows(7u)
+assert(rows.asSequence(3).map { it.id }.toList() == listOf(0u, 1u, 2u, 3u, 4u, 5u, 6u))
+assert(rows.batchSizes() == listOf(3u, 3u, 1u))
+
+// With a whole number of batches, the empty batch ends it.
+val exact = Rows(6u)
+assert(exact.asSequence(3).map { it.name }.toList() == (0 until 6).map { "row $it" })
+assert(exact.batchSizes() == listOf(3u, 3u, 0u))
+
+assert(Rows(0u).asSequence().toList().isEmpty())
+
+// Batches are only fetched as they're needed.
+val lazy = Rows(100u)
+assert(lazy.asSequence(10).take(15).count() == 15)
+assert(lazy.batchSizes() == listOf(10u, 10u))
+
+// Errors are thrown while iterating.
+val closed = Rows(5u)
+closed.close()
+try {
+    closed.asSequence().toList()
+    throw RuntimeException("Should have thrown")
+} catch (e: QueryException.Closed) {
+    // Expected
+}
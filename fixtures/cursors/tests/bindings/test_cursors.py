@@ -0,0 +1,40 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import itertools
+from cursors import *
+
+# The final short batch ends the iteration.
+rows = Rows(7)
+assert [row.id for row in rows.iter_items(3)] == list(range(7))
+assert rows.batch_sizes() == [3, 3, 1]
+
+# With a whole number of batches, the empty batch ends it.
+rows = Rows(6)
+assert [row.name for row in rows.iter_items(3)] == [f"row {i}" for i in range(6)]
+assert rows.batch_sizes() == [3, 3, 0]
+
+# Cursors can be iterated directly, with the default batch size.
+assert len(list(Rows(250))) == 250
+assert list(Rows(0)) == []
+
+# Batches are only fetched as they're needed.
+rows = Rows(100)
+assert len(list(itertools.islice(rows.iter_items(10), 15))) == 15
+assert rows.batch_sizes() == [10, 10]
+
+# Errors are raised while iterating.
+rows = Rows(5)
+rows.close()
+try:
+    list(rows)
+    raise Exception("Should have raised")
+except QueryError.Closed:
+    pass
+
+try:
+    next(Rows(5).iter_items(0))
+    raise Exception("Should have raised")
+except ValueError:
+    pass
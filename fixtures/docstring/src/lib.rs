@@ -40,6 +40,10 @@ impl ObjectTest {
     }
 
     pub fn test(&self) {}
+
+    pub fn test_with_arguments(&self, value: u32) -> u32 {
+        value
+    }
 }
 
 struct RecordTest {
@@ -53,6 +57,10 @@ pub fn test() {
 
 pub fn test_without_docstring() {}
 
+pub fn test_with_arguments(first_value: u32, second_value: u32) -> u32 {
+    first_value + second_value
+}
+
 pub trait CallbackTest {
     fn test(&self);
 }
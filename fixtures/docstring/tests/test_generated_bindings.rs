@@ -26,7 +26,13 @@ mod tests {
         "<docstring-error-variant-2>",
         "<docstring-error-variant>",
         "<docstring-error>",
+        "<docstring-function-argument-2>",
+        "<docstring-function-argument>",
+        "<docstring-function-return>",
+        "<docstring-function-with-arguments>",
         "<docstring-function>",
+        "<docstring-method-argument>",
+        "<docstring-method-return>",
         "<docstring-method>",
         "<docstring-namespace>",
         "<docstring-object>",
@@ -35,7 +41,7 @@ mod tests {
         "<docstring-record>",
     ];
 
-    fn test_docstring(language: TargetLanguage, file_extension: &str) {
+    fn test_docstring(language: TargetLanguage, file_extension: &str, argument_docs: &[&str]) {
         let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
 
         let out_dir = test_helper
@@ -73,6 +79,7 @@ mod tests {
             expected,
             DOCSTRINGS
                 .iter()
+                .chain(argument_docs)
                 .filter(|v| !bindings_source.contains(*v))
                 .map(|v| v.to_string())
                 .collect::<Vec::<_>>(),
@@ -83,16 +90,41 @@ mod tests {
 
     #[test]
     fn test_docstring_kotlin() {
-        test_docstring(TargetLanguage::Kotlin, "kt");
+        test_docstring(
+            TargetLanguage::Kotlin,
+            "kt",
+            &[
+                "@param firstValue <docstring-function-argument>\n",
+                "@param secondValue <docstring-function-argument-2>\n",
+                "@return <docstring-function-return>\n",
+                "@since 1.2.0\n",
+            ],
+        );
     }
 
     #[test]
     fn test_docstring_python() {
-        test_docstring(TargetLanguage::Python, "py");
+        test_docstring(
+            TargetLanguage::Python,
+            "py",
+            &[
+                "Args:\n        first_value: <docstring-function-argument>\n",
+                "        second_value: <docstring-function-argument-2>\n",
+                "Returns:\n        <docstring-function-return>\n\n    Since: 1.2.0\n",
+            ],
+        );
     }
 
     #[test]
     fn test_docstring_swift() {
-        test_docstring(TargetLanguage::Swift, "swift");
+        test_docstring(
+            TargetLanguage::Swift,
+            "swift",
+            &[
+                "- Parameter firstValue: <docstring-function-argument>\n",
+                "- Parameter secondValue: <docstring-function-argument-2>\n",
+                "- Returns: <docstring-function-return>\n * - Since: 1.2.0\n",
+            ],
+        );
     }
 }
@@ -0,0 +1,26 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(uniffi::Record)]
+pub struct Contact {
+    name: String,
+    nickname: Option<String>,
+}
+
+#[uniffi::export]
+fn echo_contact(contact: Contact) -> Contact {
+    contact
+}
+
+#[uniffi::export]
+fn describe_nickname(nickname: Option<String>) -> String {
+    format!("{nickname:?}")
+}
+
+#[uniffi::export]
+fn nickname(value: String) -> Option<String> {
+    Some(value)
+}
+
+uniffi::setup_scaffolding!();
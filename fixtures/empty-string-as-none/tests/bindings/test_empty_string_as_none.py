@@ -0,0 +1,21 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from uniffi_empty_string_as_none import *
+
+# Lowering: an empty string reaches Rust as `None`.
+assert describe_nickname("") == "None"
+assert describe_nickname(None) == "None"
+assert describe_nickname("Bob") == 'Some("Bob")'
+
+# Lifting: `Some("")` reaches Python as `None`.
+assert nickname("") is None
+assert nickname("Bob") == "Bob"
+
+# Round trips through a record field.
+assert echo_contact(Contact(name="Robert", nickname="")).nickname is None
+assert echo_contact(Contact(name="Robert", nickname=None)).nickname is None
+assert echo_contact(Contact(name="Robert", nickname="Bob")).nickname == "Bob"
+# Non-optional strings keep their empty value.
+assert echo_contact(Contact(name="", nickname=None)).name == ""
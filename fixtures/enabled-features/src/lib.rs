@@ -0,0 +1,7 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// `enabled_features()` is generated by `setup_scaffolding!()`, from `exposed_features` in
+// `uniffi.toml`.
+uniffi::setup_scaffolding!();
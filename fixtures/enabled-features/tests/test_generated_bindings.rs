@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_enabled_features.py",
+    "tests/bindings/test_enabled_features.kts",
+    "tests/bindings/test_enabled_features.swift",
+);
@@ -0,0 +1,53 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Unknown,
+}
+
+fn get_color(index: u32) -> Color {
+    match index {
+        0 => Color::Red,
+        1 => Color::Green,
+        _ => Color::Unknown,
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Shade {
+    Light,
+    Dark,
+}
+
+fn get_shade(index: u32) -> Shade {
+    match index {
+        0 => Shade::Light,
+        _ => Shade::Dark,
+    }
+}
+
+uniffi::include_scaffolding!("enum_default");
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uniffi::Lift;
+
+    #[test]
+    fn test_lift_unknown_discriminant() {
+        assert_eq!(
+            <Color as Lift<crate::UniFfiTag>>::try_lift(2).unwrap(),
+            Color::Green
+        );
+        assert_eq!(
+            <Color as Lift<crate::UniFfiTag>>::try_lift(42).unwrap(),
+            Color::Unknown
+        );
+    }
+}
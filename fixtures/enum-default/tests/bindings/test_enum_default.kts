@@ -0,0 +1,18 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.enum_default.*;
+
+assert(getColor(0u) == Color.RED)
+assert(getColor(7u) == Color.UNKNOWN)
+
+// Lifting an unknown discriminant falls back to the `[Default]` variant.
+assert(FfiConverterTypeColor.lift(2) == Color.GREEN)
+assert(FfiConverterTypeColor.lift(42) == Color.UNKNOWN)
+
+// Without a `[Default]` variant, unknown discriminants are lifted as an `Unknown` variant.
+assert(getShade(0u) == Shade.Light)
+assert(getShade(1u) == Shade.Dark)
+assert(FfiConverterTypeShade.lift(42) == Shade.Unknown(42))
+assert(FfiConverterTypeShade.lower(Shade.Unknown(42)) == 42)
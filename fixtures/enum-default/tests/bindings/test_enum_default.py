@@ -0,0 +1,22 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import enum_default
+from enum_default import *
+
+assert get_color(0) == Color.RED
+assert get_color(7) == Color.UNKNOWN
+
+# Lifting an unknown discriminant falls back to the `[Default]` variant.
+assert enum_default._UniffiConverterTypeColor.lift(2) == Color.GREEN
+assert enum_default._UniffiConverterTypeColor.lift(42) == Color.UNKNOWN
+
+# Without a `[Default]` variant, unknown discriminants are lifted as an `UNKNOWN` variant.
+assert get_shade(0) == Shade.LIGHT()
+assert get_shade(1).is_dark()
+shade = enum_default._UniffiConverterTypeShade.lift(42)
+assert shade.is_unknown()
+assert shade == Shade.UNKNOWN(42)
+assert shade.discriminant == 42
+assert enum_default._UniffiConverterTypeShade.lower(shade) == 42
@@ -0,0 +1,18 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import enum_default
+
+assert(getColor(index: 0) == .red)
+assert(getColor(index: 7) == .unknown)
+
+// Lifting an unknown discriminant falls back to the `[Default]` variant.
+assert(try! FfiConverterTypeColor.lift(2) == .green)
+assert(try! FfiConverterTypeColor.lift(42) == .unknown)
+
+// Without a `[Default]` variant, unknown discriminants are lifted as an `unknown` variant.
+assert(getShade(index: 0) == .light)
+assert(getShade(index: 1) == .dark)
+assert(try! FfiConverterTypeShade.lift(42) == .unknown(discriminant: 42))
+assert(FfiConverterTypeShade.lower(.unknown(discriminant: 42)) == 42)
@@ -0,0 +1,56 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use uniffi_bindgen::bindings::TargetLanguage;
+use uniffi_testing::UniFFITestHelper;
+
+// The cdylib for this crate contains the components of all the crates it depends on, so bindings
+// should be generated for each of them.
+#[test]
+fn test_generate_bindings_from_library() {
+    let test_helper = UniFFITestHelper::new(std::env!("CARGO_PKG_NAME")).unwrap();
+    let out_dir = test_helper
+        .create_out_dir(
+            std::env!("CARGO_TARGET_TMPDIR"),
+            "test-generate-bindings-from-library",
+        )
+        .unwrap();
+    let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir).unwrap();
+    let bindings_dir = out_dir.join("bindings");
+
+    let sources = uniffi_bindgen::generate_bindings_from_library(
+        &cdylib_path,
+        &[TargetLanguage::Python],
+        &bindings_dir,
+    )
+    .unwrap();
+
+    let mut crate_names: Vec<_> = sources.iter().map(|s| s.crate_name.as_str()).collect();
+    crate_names.sort();
+    let mut files: Vec<_> = std::fs::read_dir(&bindings_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+    assert_eq!(
+        crate_names,
+        vec![
+            "custom_types",
+            "ext_types_guid",
+            "uniffi_ext_types_lib",
+            "uniffi_one",
+            "uniffi_sublib",
+        ]
+    );
+    assert_eq!(
+        files,
+        vec![
+            "custom_types.py",
+            "ext_types_guid.py",
+            "imported_types_lib.py",
+            "imported_types_sublib.py",
+            "uniffi_one_ns.py",
+        ]
+    );
+}
@@ -5,7 +5,10 @@
 use std::{
     future::Future,
     pin::Pin,
-    sync::{Arc, Mutex, MutexGuard},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
     task::{Context, Poll, Waker},
     thread,
     time::Duration,
@@ -101,6 +104,68 @@ pub async fn sleep(ms: u16) -> bool {
     true
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum FlakyError {
+    #[error("Unavailable")]
+    Unavailable,
+    #[error("Fatal")]
+    Fatal,
+}
+
+static FLAKY_CALLS: AtomicU32 = AtomicU32::new(0);
+
+/// Async function that fails the first `failures` times it's called, then returns how many times
+/// it was called.
+///
+/// (This one is defined in the UDL, which marks it to be retried)
+pub async fn flaky(failures: u32) -> Result<u32, FlakyError> {
+    let calls = FLAKY_CALLS.fetch_add(1, Ordering::SeqCst) + 1;
+    if calls <= failures {
+        Err(FlakyError::Unavailable)
+    } else {
+        FLAKY_CALLS.store(0, Ordering::SeqCst);
+        Ok(calls)
+    }
+}
+
+static DROPPED_LONG_OPERATIONS: AtomicU32 = AtomicU32::new(0);
+
+// Counts the `long_operation` futures which were dropped, whether or not they completed.
+struct LongOperationGuard;
+
+impl Drop for LongOperationGuard {
+    fn drop(&mut self) {
+        DROPPED_LONG_OPERATIONS.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Async function that returns `ms` after that many milliseconds.
+///
+/// (This one is defined in the UDL, which marks it as cancellable)
+pub async fn long_operation(ms: u16) -> u16 {
+    let _guard = LongOperationGuard;
+    TimerFuture::new(Duration::from_millis(ms.into())).await;
+    ms
+}
+
+/// Async function that waits `ms` milliseconds, then returns `ms` or fails.
+///
+/// (This one is defined in the UDL, so the error goes through the UDL scaffolding)
+pub async fn sleep_then_fail(ms: u16, fail: bool) -> Result<u16, FlakyError> {
+    TimerFuture::new(Duration::from_millis(ms.into())).await;
+    if fail {
+        Err(FlakyError::Fatal)
+    } else {
+        Ok(ms)
+    }
+}
+
+/// How many `long_operation` futures have been dropped.
+#[uniffi::export]
+pub fn dropped_long_operations() -> u32 {
+    DROPPED_LONG_OPERATIONS.load(Ordering::SeqCst)
+}
+
 // Our error.
 #[derive(thiserror::Error, uniffi::Error, Debug)]
 pub enum MyError {
@@ -164,6 +229,23 @@ pub struct Megaphone;
 
 #[uniffi::export]
 impl Megaphone {
+    /// An async constructor.
+    #[uniffi::constructor]
+    pub async fn new() -> Arc<Self> {
+        TimerFuture::new(Duration::from_millis(0)).await;
+        Arc::new(Self)
+    }
+
+    /// An async constructor that can throw.
+    #[uniffi::constructor]
+    pub async fn fallible_new(do_fail: bool) -> Result<Arc<Self>, MyError> {
+        if do_fail {
+            Err(MyError::Foo)
+        } else {
+            Ok(Arc::new(Self))
+        }
+    }
+
     /// An async method that yells something after a certain time.
     pub async fn say_after(self: Arc<Self>, ms: u16, who: String) -> String {
         say_after(ms, who).await.to_uppercase()
@@ -188,6 +188,23 @@ runBlocking {
     println(" ... ok")
 }
 
+// Test async constructors.
+runBlocking {
+    val megaphone = Megaphone.new()
+    assert(megaphone.sayAfter(1U, "hi") == "HELLO, HI!")
+
+    val fallibleMegaphone = Megaphone.fallibleNew(false)
+    assert(fallibleMegaphone.fallibleMe(false) == 42.toUByte())
+
+    try {
+        Megaphone.fallibleNew(true)
+        assert(false) // should never be reached
+    } catch (exception: MyException) {
+        assert(true)
+    }
+    println("async constructors ... ok")
+}
+
 // Test record.
 runBlocking {
     val time = measureTimeMillis {
@@ -244,3 +261,91 @@ runBlocking {
     }
     println("useSharedResource (not canceled): ${time}ms")
 }
+
+// Test the cancellable handle.
+runBlocking {
+    val dropped = droppedLongOperations()
+    // Awaiting the handle returns the result.
+    assert(longOperationCancellable(10U).await() == 10.toUShort())
+    assert(droppedLongOperations() == dropped + 1U)
+
+    // Start a long operation, then cancel it from another thread.
+    val handle = longOperationCancellable(10000U)
+    val job = async { handle.await() }
+    delay(100)
+    assert(droppedLongOperations() == dropped + 1U)
+    val thread = Thread { handle.cancel() }
+    thread.start()
+    thread.join()
+    try {
+        job.await()
+        throw RuntimeException("The cancelled handle should have thrown")
+    } catch (e: CancellationException) {
+        // Expected
+    }
+    // The Rust future was dropped, without waiting for it to complete.
+    assert(droppedLongOperations() == dropped + 2U)
+    // Cancelling again does nothing.
+    handle.cancel()
+}
+
+// A cancelled call throws `CancellationException`, rather than the error it would have thrown.
+runBlocking {
+    try {
+        sleepThenFailCancellable(10U, true).await()
+        throw RuntimeException("sleepThenFailCancellable(10, true) should have thrown")
+    } catch (e: FlakyException.Fatal) {
+        // Expected
+    }
+
+    val handle = sleepThenFailCancellable(10000U, true)
+    val job = async { handle.await() }
+    delay(100)
+    handle.cancel()
+    try {
+        job.await()
+        throw RuntimeException("The cancelled handle should have thrown")
+    } catch (e: FlakyException) {
+        throw RuntimeException("The cancelled handle threw its error: $e")
+    } catch (e: CancellationException) {
+        // Expected
+    }
+}
+
+// Test the blocking variants, which can be called outside of a coroutine.
+assert(sayAfterBlocking(100U, "Alice") == "Hello, Alice!")
+voidBlocking()
+assert(fallibleMeBlocking(false) == 42.toUByte())
+try {
+    fallibleMeBlocking(true)
+    throw RuntimeException("fallibleMeBlocking(true) should have thrown")
+} catch (e: MyException.Foo) {
+    // Expected
+}
+
+// Test a function which is retried on a retryable error, with a backoff of 100ms then 200ms.
+runBlocking {
+    val time = measureTimeMillis {
+        assert(flaky(2U) == 3U)
+    }
+    assertApproximateTime(time, 300, "flaky")
+
+    // It gives up after 3 retries.
+    try {
+        flaky(10U)
+        throw RuntimeException("flaky(10) should have thrown")
+    } catch (e: FlakyException.Unavailable) {
+        // Expected
+    }
+}
+
+// Test a throwing function whose result is ready after the future has been suspended.
+runBlocking {
+    assert(sleepThenFail(10U, false) == 10.toUShort())
+    try {
+        sleepThenFail(10U, true)
+        throw RuntimeException("sleepThenFail(10, true) should have thrown")
+    } catch (e: FlakyException.Fatal) {
+        // Expected
+    }
+}
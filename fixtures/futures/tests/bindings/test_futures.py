@@ -2,6 +2,8 @@ from futures import *
 import unittest
 from datetime import datetime
 import asyncio
+import inspect
+import threading
 
 def now():
     return datetime.now()
@@ -13,6 +15,32 @@ class TestFutures(unittest.TestCase):
 
         asyncio.run(test())
 
+    def test_coroutine_functions(self):
+        # Async functions, methods and constructors are native coroutine functions.
+        self.assertTrue(inspect.iscoroutinefunction(say_after))
+        self.assertTrue(inspect.iscoroutinefunction(Megaphone.say_after))
+        self.assertTrue(inspect.iscoroutinefunction(Megaphone.new))
+
+        async def test():
+            coro = say_after(10, 'Alice')
+            self.assertTrue(inspect.iscoroutine(coro))
+            self.assertEqual(await coro, 'Hello, Alice!')
+
+        asyncio.run(test())
+
+    def test_cancel_coroutine(self):
+        async def test():
+            # Cancelling the awaiting task cancels the Rust future.
+            with self.assertRaises(asyncio.TimeoutError):
+                await asyncio.wait_for(say_after(5000, 'Alice'), 0.1)
+            # Cancelling a future which holds a lock drops it, releasing the lock.
+            with self.assertRaises(asyncio.TimeoutError):
+                await asyncio.wait_for(use_shared_resource(
+                    SharedResourceOptions(release_after_ms=5000, timeout_ms=100)), 0.05)
+            await use_shared_resource(SharedResourceOptions(release_after_ms=0, timeout_ms=1000))
+
+        asyncio.run(test())
+
     def test_void(self):
         async def test():
             self.assertEqual(await void(), None)
@@ -123,6 +151,16 @@ class TestFutures(unittest.TestCase):
 
         asyncio.run(test())
 
+    def test_fallible_after_suspending(self):
+        async def test():
+            # The result is ready after the future has been suspended, and is either returned or
+            # thrown.
+            self.assertEqual(await sleep_then_fail(10, False), 10)
+            with self.assertRaises(FlakyError.Fatal):
+                await sleep_then_fail(10, True)
+
+        asyncio.run(test())
+
     def test_fallible_struct(self):
         async def test():
             megaphone = await fallible_struct(False)
@@ -136,6 +174,26 @@ class TestFutures(unittest.TestCase):
 
         asyncio.run(test())
 
+    def test_async_constructors(self):
+        async def test():
+            megaphone = await Megaphone.new()
+            self.assertEqual(await megaphone.say_after(1, 'hi'), 'HELLO, HI!')
+
+            megaphone = await Megaphone.fallible_new(False)
+            self.assertEqual(await megaphone.fallible_me(False), 42)
+
+            try:
+                await Megaphone.fallible_new(True)
+                self.assertTrue(False) # should never be reached
+            except MyError as exception:
+                pass
+
+            # The primary constructor is async, so it can't be called directly.
+            with self.assertRaises(ValueError):
+                Megaphone()
+
+        asyncio.run(test())
+
     def test_record(self):
         async def test():
             result = await new_my_record("foo", 42)
@@ -178,5 +236,53 @@ class TestFutures(unittest.TestCase):
             await use_shared_resource(SharedResourceOptions(release_after_ms=0, timeout_ms=1000))
         asyncio.run(test())
 
+    def test_cancellable_handle(self):
+        async def test():
+            dropped = dropped_long_operations()
+            # Awaiting the handle returns the result.
+            self.assertEqual(await long_operation_cancellable(10), 10)
+            self.assertEqual(dropped_long_operations(), dropped + 1)
+
+            # Start a long operation, then cancel it from another thread.
+            handle = long_operation_cancellable(10000)
+            task = asyncio.ensure_future(handle)
+            await asyncio.sleep(0.1)
+            self.assertEqual(dropped_long_operations(), dropped + 1)
+            thread = threading.Thread(target=handle.cancel)
+            thread.start()
+            thread.join()
+            with self.assertRaises(asyncio.CancelledError):
+                await task
+            # The Rust future was dropped, without waiting for it to complete.
+            self.assertEqual(dropped_long_operations(), dropped + 2)
+            # Cancelling again does nothing.
+            handle.cancel()
+
+        asyncio.run(test())
+
+    def test_cancelled_call_is_not_an_error(self):
+        async def test():
+            # A failing call raises its error.
+            with self.assertRaises(FlakyError.Fatal):
+                await sleep_then_fail_cancellable(10, True)
+
+            # A cancelled call raises `CancelledError` instead, even if it would have failed.
+            handle = sleep_then_fail_cancellable(10000, True)
+            task = asyncio.ensure_future(handle)
+            await asyncio.sleep(0.1)
+            handle.cancel()
+            with self.assertRaises(asyncio.CancelledError):
+                await task
+
+        asyncio.run(test())
+
+    def test_blocking_variants(self):
+        # The blocking variants can be called without an event loop.
+        self.assertEqual(say_after_blocking(100, 'Alice'), 'Hello, Alice!')
+        self.assertEqual(void_blocking(), None)
+        self.assertEqual(fallible_me_blocking(False), 42)
+        with self.assertRaises(MyError.Foo):
+            fallible_me_blocking(True)
+
 if __name__ == '__main__':
     unittest.main()
@@ -209,6 +209,29 @@ Task {
 	}
 }
 
+// Test async constructors.
+counter.enter()
+
+Task {
+	let megaphone = await Megaphone()
+	let result = await megaphone.sayAfter(ms: 1, who: "hi")
+	assert(result == "HELLO, HI!")
+
+	let m = try await Megaphone.fallibleNew(doFail: false)
+	assert(try await m.fallibleMe(doFail: false) == 42)
+
+	do {
+		let _ = try await Megaphone.fallibleNew(doFail: true)
+		assert(false) // should never be reached
+	} catch MyError.Foo {
+		assert(true)
+	} catch {
+		assert(false)
+	}
+
+	counter.leave()
+}
+
 counter.enter()
 
 Task {
@@ -236,16 +259,20 @@ Task {
 counter.enter()
 Task {
 	let task = Task {
-	    try! await useSharedResource(options: SharedResourceOptions(releaseAfterMs: 100, timeoutMs: 1000))
+	    do {
+	        try await useSharedResource(options: SharedResourceOptions(releaseAfterMs: 5000, timeoutMs: 100))
+	        fatalError("useSharedResource should have been cancelled")
+	    } catch is CancellationError {
+	        // Expected
+	    }
 	}
 
 	// Wait some time to ensure the task has locked the shared resource
 	try await Task.sleep(nanoseconds: 50_000_000)
-	// Cancel the job task the shared resource has been released.
-	//
-	// FIXME: this test currently passes because `test.cancel()` doesn't actually cancel the
-	// operation.  We need to rework the Swift async handling to handle this properly.
+	// Cancel the task before the shared resource has been released.  This cancels the Rust future,
+	// which drops the lock guard.
 	task.cancel()
+	await task.value
 
 	// Try accessing the shared resource again.  The initial task should release the shared resource
 	// before the timeout expires.
@@ -253,6 +280,18 @@ Task {
 	counter.leave()
 }
 
+// Functions which can't throw aren't cancellable, so they run to completion even if their task is
+// cancelled.
+counter.enter()
+Task {
+	let task = Task {
+	    await sayAfter(ms: 100, who: "Alice")
+	}
+	task.cancel()
+	assert(await task.value == "Hello, Alice!")
+	counter.leave()
+}
+
 // Test a future that uses a lock and that is not cancelled.
 counter.enter()
 Task {
@@ -261,4 +300,102 @@ Task {
 	counter.leave()
 }
 
+// Test a function which is retried on a retryable error, with a backoff of 100ms then 200ms.
+counter.enter()
+Task {
+	let t0 = Date()
+	let result = try! await flaky(failures: 2)
+	let t1 = Date()
+
+	let tDelta = DateInterval(start: t0, end: t1)
+	assert(tDelta.duration > 0.3 && tDelta.duration < 0.4)
+	assert(result == 3)
+
+	// It gives up after 3 retries.
+	do {
+		_ = try await flaky(failures: 10)
+		fatalError("flaky(failures: 10) should have thrown")
+	} catch FlakyError.Unavailable {
+		// Expected
+	} catch {
+		fatalError("Unexpected error: \(error)")
+	}
+	counter.leave()
+}
+
+// Test a throwing function whose result is ready after the future has been suspended.
+counter.enter()
+Task {
+	let result = try! await sleepThenFail(ms: 10, fail: false)
+	assert(result == 10)
+
+	do {
+		_ = try await sleepThenFail(ms: 10, fail: true)
+		fatalError("sleepThenFail(ms: 10, fail: true) should have thrown")
+	} catch FlakyError.Fatal {
+		// Expected
+	} catch {
+		fatalError("Unexpected error: \(error)")
+	}
+	counter.leave()
+}
+
+// Test the cancellable handle.
+counter.enter()
+Task {
+	let dropped = droppedLongOperations()
+	// Awaiting the handle returns the result.
+	let result = try! await longOperationCancellable(ms: 10).value()
+	assert(result == 10)
+	assert(droppedLongOperations() == dropped + 1)
+
+	// Start a long operation, then cancel it from another thread.
+	let handle = longOperationCancellable(ms: 10000)
+	let task = Task { try await handle.value() }
+	try! await Task.sleep(nanoseconds: 100_000_000)
+	assert(droppedLongOperations() == dropped + 1)
+	let thread = Thread { handle.cancel() }
+	thread.start()
+	do {
+		_ = try await task.value
+		fatalError("The cancelled handle should have thrown")
+	} catch is CancellationError {
+		// Expected
+	} catch {
+		fatalError("Unexpected error: \(error)")
+	}
+	// The Rust future was dropped, without waiting for it to complete.
+	assert(droppedLongOperations() == dropped + 2)
+	// Cancelling again does nothing.
+	handle.cancel()
+	counter.leave()
+}
+
+// A cancelled call throws `CancellationError`, rather than the error it would have thrown.
+counter.enter()
+Task {
+	do {
+		_ = try await sleepThenFailCancellable(ms: 10, fail: true).value()
+		fatalError("sleepThenFailCancellable(ms: 10, fail: true) should have thrown")
+	} catch FlakyError.Fatal {
+		// Expected
+	} catch {
+		fatalError("Unexpected error: \(error)")
+	}
+
+	let handle = sleepThenFailCancellable(ms: 10000, fail: true)
+	let task = Task { try await handle.value() }
+	try! await Task.sleep(nanoseconds: 100_000_000)
+	handle.cancel()
+	do {
+		_ = try await task.value
+		fatalError("The cancelled handle should have thrown")
+	} catch is CancellationError {
+		// Expected
+	} catch {
+		fatalError("Unexpected error: \(error)")
+	}
+	counter.leave()
+}
+
 counter.wait()
@@ -0,0 +1,86 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+pub struct Animal {
+    name: String,
+    sound: String,
+}
+
+impl Animal {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            sound: "...".to_string(),
+        }
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn speak(&self) -> String {
+        format!("{} says {}", self.name, self.sound)
+    }
+}
+
+pub struct Dog {
+    animal: Arc<Animal>,
+}
+
+impl Dog {
+    fn new(name: String) -> Self {
+        Self {
+            animal: Arc::new(Animal {
+                name,
+                sound: "woof".to_string(),
+            }),
+        }
+    }
+
+    fn fetch(&self, item: String) -> String {
+        format!("{} fetched the {item}", self.animal.name)
+    }
+}
+
+impl uniffi::Upcast<Animal> for Dog {
+    fn upcast(self: Arc<Self>) -> Arc<Animal> {
+        self.animal.clone()
+    }
+}
+
+pub struct Puppy {
+    dog: Arc<Dog>,
+    weeks: u8,
+}
+
+impl Puppy {
+    fn new(name: String, weeks: u8) -> Self {
+        Self {
+            dog: Arc::new(Dog::new(name)),
+            weeks,
+        }
+    }
+
+    fn weeks(&self) -> u8 {
+        self.weeks
+    }
+}
+
+impl uniffi::Upcast<Dog> for Puppy {
+    fn upcast(self: Arc<Self>) -> Arc<Dog> {
+        self.dog.clone()
+    }
+}
+
+fn introduce(animal: Arc<Animal>) -> String {
+    format!("This is {}", animal.name)
+}
+
+fn adopt(name: String) -> Arc<Dog> {
+    Arc::new(Dog::new(name))
+}
+
+uniffi::include_scaffolding!("inheritance");
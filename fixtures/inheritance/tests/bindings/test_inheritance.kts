@@ -0,0 +1,31 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.inheritance.*
+
+Animal("Tom").use { animal ->
+    assert(animal.speak() == "Tom says ...")
+    assert(introduce(animal) == "This is Tom")
+}
+
+Dog("Rex").use { dog ->
+    assert(dog is Animal)
+    assert(dog.name() == "Rex")
+    assert(dog.speak() == "Rex says woof")
+    assert(dog.fetch("ball") == "Rex fetched the ball")
+    assert(introduce(dog) == "This is Rex")
+}
+
+Puppy("Bit", 8u).use { puppy ->
+    assert(puppy is Dog)
+    assert(puppy.weeks() == 8.toUByte())
+    assert(puppy.fetch("stick") == "Bit fetched the stick")
+    assert(puppy.speak() == "Bit says woof")
+    assert(introduce(puppy) == "This is Bit")
+}
+
+adopt("Fido").use { dog ->
+    assert(dog.speak() == "Fido says woof")
+    assert(dog.fetch("paper") == "Fido fetched the paper")
+}
@@ -0,0 +1,37 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import unittest
+from inheritance import *
+
+class TestInheritance(unittest.TestCase):
+    def test_parent(self):
+        animal = Animal("Tom")
+        self.assertEqual(animal.speak(), "Tom says ...")
+        self.assertEqual(introduce(animal), "This is Tom")
+
+    def test_child(self):
+        dog = Dog("Rex")
+        self.assertIsInstance(dog, Animal)
+        self.assertEqual(dog.name(), "Rex")
+        self.assertEqual(dog.speak(), "Rex says woof")
+        self.assertEqual(dog.fetch("ball"), "Rex fetched the ball")
+        self.assertEqual(introduce(dog), "This is Rex")
+
+    def test_grandchild(self):
+        puppy = Puppy("Bit", 8)
+        self.assertIsInstance(puppy, Dog)
+        self.assertEqual(puppy.weeks(), 8)
+        self.assertEqual(puppy.fetch("stick"), "Bit fetched the stick")
+        self.assertEqual(puppy.speak(), "Bit says woof")
+        self.assertEqual(introduce(puppy), "This is Bit")
+
+    def test_lifted_child(self):
+        dog = adopt("Fido")
+        self.assertIsInstance(dog, Dog)
+        self.assertEqual(dog.speak(), "Fido says woof")
+        self.assertEqual(dog.fetch("paper"), "Fido fetched the paper")
+
+if __name__=='__main__':
+    unittest.main()
@@ -0,0 +1,25 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import inheritance
+
+let animal = Animal(name: "Tom")
+assert(animal.speak() == "Tom says ...")
+assert(introduce(animal: animal) == "This is Tom")
+
+let dog = Dog(name: "Rex")
+assert(dog.name() == "Rex")
+assert(dog.speak() == "Rex says woof")
+assert(dog.fetch(item: "ball") == "Rex fetched the ball")
+assert(introduce(animal: dog) == "This is Rex")
+
+let puppy = Puppy(name: "Bit", weeks: 8)
+assert(puppy.weeks() == 8)
+assert(puppy.fetch(item: "stick") == "Bit fetched the stick")
+assert(puppy.speak() == "Bit says woof")
+assert(introduce(animal: puppy) == "This is Bit")
+
+let adopted = adopt(name: "Fido")
+assert(adopted.speak() == "Fido says woof")
+assert(adopted.fetch(item: "paper") == "Fido fetched the paper")
@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_inheritance.py",
+    "tests/bindings/test_inheritance.kts",
+    "tests/bindings/test_inheritance.swift"
+);
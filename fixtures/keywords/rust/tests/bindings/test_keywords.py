@@ -8,4 +8,4 @@ from datetime import datetime, timezone
 # A successful import all this test really needs...
 import keywords_rust
 # but might as well call something.
-keywords_rust._if(0)
+keywords_rust.if_(0)
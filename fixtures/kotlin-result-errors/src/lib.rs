@@ -0,0 +1,31 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, thiserror::Error)]
+pub enum MathError {
+    #[error("Division by zero")]
+    DivisionByZero,
+}
+
+fn divide(a: u64, b: u64) -> Result<u64, MathError> {
+    check_divisor(b)?;
+    Ok(a / b)
+}
+
+fn check_divisor(b: u64) -> Result<(), MathError> {
+    match b {
+        0 => Err(MathError::DivisionByZero),
+        _ => Ok(()),
+    }
+}
+
+async fn divide_async(a: u64, b: u64) -> Result<u64, MathError> {
+    divide(a, b)
+}
+
+fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+uniffi::include_scaffolding!("result_errors");
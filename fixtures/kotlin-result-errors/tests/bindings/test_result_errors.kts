@@ -0,0 +1,25 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.result_errors.*
+import kotlinx.coroutines.runBlocking
+
+assert(divide(6UL, 3UL) == Result.success(2UL))
+assert(checkDivisor(3UL).isSuccess)
+assert(add(1UL, 2UL) == 3UL)
+
+// The error is returned as the failure, rather than thrown.
+divide(6UL, 0UL).fold(
+    onSuccess = { throw RuntimeException("Should have failed") },
+    onFailure = { assert(it is MathException.DivisionByZero) },
+)
+when (val error = checkDivisor(0UL).exceptionOrNull()) {
+    is MathException.DivisionByZero -> Unit
+    else -> throw RuntimeException("Unexpected error: $error")
+}
+
+runBlocking {
+    assert(divideAsync(6UL, 3UL).getOrNull() == 2UL)
+    assert(divideAsync(6UL, 0UL).exceptionOrNull() is MathException.DivisionByZero)
+}
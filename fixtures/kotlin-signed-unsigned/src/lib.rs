@@ -0,0 +1,46 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+pub struct Sizes {
+    small: u16,
+    large: u64,
+}
+
+fn echo_u8(value: u8) -> u8 {
+    value
+}
+
+fn echo_u16(value: u16) -> u16 {
+    value
+}
+
+fn echo_u32(value: u32) -> u32 {
+    value
+}
+
+fn echo_u64(value: u64) -> u64 {
+    value
+}
+
+fn echo_u64s(values: Vec<u64>) -> Vec<u64> {
+    values
+}
+
+fn echo_sizes(sizes: Sizes) -> Sizes {
+    sizes
+}
+
+fn is_u32_max(value: u32) -> bool {
+    value == u32::MAX
+}
+
+fn is_u64_max(value: u64) -> bool {
+    value == u64::MAX
+}
+
+fn or_default(value: u32) -> u32 {
+    value
+}
+
+uniffi::include_scaffolding!("signed_unsigned");
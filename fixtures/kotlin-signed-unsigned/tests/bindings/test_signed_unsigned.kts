@@ -0,0 +1,30 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.signed_unsigned.*
+
+// Values which fit in the signed type are unchanged.
+assert(echoU8(7) == 7.toByte())
+assert(echoU16(7) == 7.toShort())
+assert(echoU32(7) == 7)
+assert(echoU64(7L) == 7L)
+
+// Larger values keep their bits, so they're negative in Kotlin.
+assert(echoU8(-1) == (-1).toByte())
+assert(echoU16(Short.MIN_VALUE) == Short.MIN_VALUE)
+assert(echoU32(Int.MIN_VALUE) == Int.MIN_VALUE)
+assert(echoU64(Long.MIN_VALUE) == Long.MIN_VALUE)
+assert(isU32Max(-1))
+assert(!isU32Max(Int.MAX_VALUE))
+assert(isU64Max(-1L))
+assert(!isU64Max(Long.MAX_VALUE))
+
+// The unsigned value can be recovered with Kotlin's conversions.
+assert(echoU32(-1).toUInt() == UInt.MAX_VALUE)
+assert(echoU64(-1L).toULong() == ULong.MAX_VALUE)
+
+assert(echoU64s(listOf(0L, -1L, Long.MIN_VALUE)) == listOf(0L, -1L, Long.MIN_VALUE))
+assert(echoSizes(Sizes(-1, -2L)) == Sizes(-1, -2L))
+assert(orDefault() == -1)
+assert(isU32Max(orDefault()))
@@ -0,0 +1,22 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+uniffi::export_runtime_config!();
+
+#[uniffi::export]
+fn greet(name: String) -> String {
+    let span = tracing::info_span!("greet", name = name.as_str());
+    let _guard = span.enter();
+    tracing::info!("hello {name}");
+    tracing::debug!(len = name.len(), "greeted");
+    format!("Hello, {name}")
+}
+
+/// Lift a serialized string as if it came from the foreign code, which follows the string policy.
+#[uniffi::export]
+fn lift_string(serialized: Vec<u8>) -> Option<String> {
+    <String as uniffi::Lift<crate::UniFfiTag>>::try_read(&mut serialized.as_slice()).ok()
+}
+
+uniffi::setup_scaffolding!();
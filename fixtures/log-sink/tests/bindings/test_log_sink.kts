@@ -0,0 +1,40 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.log_sink.*;
+
+class RecordingLogSink : LogSink {
+    val records = mutableListOf<Triple<LogLevel, String, String>>()
+
+    override fun log(level: LogLevel, target: String, message: String) {
+        records.add(Triple(level, target, message))
+    }
+}
+
+val sink = RecordingLogSink()
+setLogSink(sink, LogLevel.INFO)
+assert(greet("World") == "Hello, World")
+// The debug event is filtered out.
+assert(sink.records == listOf(
+    Triple(LogLevel.INFO, "uniffi_log_sink", "greet{name=\"World\"}: hello World"),
+))
+
+setLogSink(sink, LogLevel.DEBUG)
+greet("Rust")
+assert(sink.records.drop(1) == listOf(
+    Triple(LogLevel.INFO, "uniffi_log_sink", "greet{name=\"Rust\"}: hello Rust"),
+    Triple(LogLevel.DEBUG, "uniffi_log_sink", "greet{name=\"Rust\"}: greeted len=4"),
+))
+
+// `uniffiInitialize()` applies all the settings of a `UniffiRuntimeConfig` together.
+// `invalid` is a serialized string whose last byte isn't valid UTF-8.
+val invalid = byteArrayOf(0, 0, 0, 2, 97, -1)
+assert(liftString(invalid) == null)
+val configSink = RecordingLogSink()
+uniffiInitialize(UniffiRuntimeConfig().logSink(configSink).maxLogLevel(LogLevel.INFO).stringPolicy(StringPolicy.LOSSY))
+greet("Config")
+assert(configSink.records == listOf(
+    Triple(LogLevel.INFO, "uniffi_log_sink", "greet{name=\"Config\"}: hello Config"),
+))
+assert(liftString(invalid) == "a\uFFFD")
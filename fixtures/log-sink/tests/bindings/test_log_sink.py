@@ -0,0 +1,40 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from uniffi_log_sink import *
+
+class RecordingLogSink(LogSink):
+    def __init__(self):
+        self.records = []
+
+    def log(self, level, target, message):
+        self.records.append((level, target, message))
+
+sink = RecordingLogSink()
+set_log_sink(sink, LogLevel.INFO)
+assert greet("World") == "Hello, World"
+# The debug event is filtered out.
+assert sink.records == [
+    (LogLevel.INFO, "uniffi_log_sink", 'greet{name="World"}: hello World'),
+], sink.records
+
+set_log_sink(sink, LogLevel.DEBUG)
+greet("Rust")
+assert sink.records[1:] == [
+    (LogLevel.INFO, "uniffi_log_sink", 'greet{name="Rust"}: hello Rust'),
+    (LogLevel.DEBUG, "uniffi_log_sink", 'greet{name="Rust"}: greeted len=4'),
+], sink.records
+
+# `uniffi_initialize()` applies all the settings of a `UniffiRuntimeConfig` together.
+# `invalid` is a serialized string whose last byte isn't valid UTF-8.
+invalid = b"\x00\x00\x00\x02a\xff"
+assert lift_string(invalid) is None
+config_sink = RecordingLogSink()
+config = UniffiRuntimeConfig().log_sink(config_sink).max_log_level(LogLevel.INFO).string_policy(StringPolicy.LOSSY)
+uniffi_initialize(config)
+greet("Config")
+assert config_sink.records == [
+    (LogLevel.INFO, "uniffi_log_sink", 'greet{name="Config"}: hello Config'),
+], config_sink.records
+assert lift_string(invalid) == "a\ufffd"
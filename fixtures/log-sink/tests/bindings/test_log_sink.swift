@@ -0,0 +1,47 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import Foundation
+import uniffi_log_sink
+
+struct Record: Equatable {
+    let level: LogLevel
+    let target: String
+    let message: String
+}
+
+class RecordingLogSink: LogSink {
+    var records: [Record] = []
+
+    func log(level: LogLevel, target: String, message: String) {
+        records.append(Record(level: level, target: target, message: message))
+    }
+}
+
+let sink = RecordingLogSink()
+setLogSink(sink: sink, maxLevel: .info)
+assert(greet(name: "World") == "Hello, World")
+// The debug event is filtered out.
+assert(sink.records == [
+    Record(level: .info, target: "uniffi_log_sink", message: "greet{name=\"World\"}: hello World"),
+])
+
+setLogSink(sink: sink, maxLevel: .debug)
+let _ = greet(name: "Rust")
+assert(Array(sink.records.dropFirst()) == [
+    Record(level: .info, target: "uniffi_log_sink", message: "greet{name=\"Rust\"}: hello Rust"),
+    Record(level: .debug, target: "uniffi_log_sink", message: "greet{name=\"Rust\"}: greeted len=4"),
+])
+
+// `uniffiInitialize()` applies all the settings of a `UniffiRuntimeConfig` together.
+// `invalid` is a serialized string whose last byte isn't valid UTF-8.
+let invalid = Data([0, 0, 0, 2, 97, 255])
+assert(liftString(serialized: invalid) == nil)
+let configSink = RecordingLogSink()
+uniffiInitialize(UniffiRuntimeConfig().logSink(configSink).maxLogLevel(.info).stringPolicy(.lossy))
+let _ = greet(name: "Config")
+assert(configSink.records == [
+    Record(level: .info, target: "uniffi_log_sink", message: "greet{name=\"Config\"}: hello Config"),
+])
+assert(liftString(serialized: invalid) == "a\u{FFFD}")
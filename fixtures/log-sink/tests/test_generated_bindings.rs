@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_log_sink.kts",
+    "tests/bindings/test_log_sink.swift",
+    "tests/bindings/test_log_sink.py",
+);
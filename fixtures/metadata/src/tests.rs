@@ -181,15 +181,21 @@ mod test_metadata {
                         ty: Type::String,
                         default: Some(LiteralMetadata::String("test".to_owned())),
                         docstring: None,
+                        order: None,
                     },
                     FieldMetadata {
                         name: "age".into(),
                         ty: Type::UInt16,
                         default: None,
                         docstring: None,
+                        order: None,
                     },
                 ],
+                packed: false,
+                has_default: false,
                 docstring: None,
+                since: None,
+                deprecated: None,
             },
         );
     }
@@ -207,22 +213,31 @@ mod test_metadata {
                         discr: None,
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
+                        retryable: false,
                     },
                     VariantMetadata {
                         name: "Paper".into(),
                         discr: None,
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
+                        retryable: false,
                     },
                     VariantMetadata {
                         name: "Scissors".into(),
                         discr: None,
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
+                        retryable: false,
                     },
                 ],
                 non_exhaustive: false,
+                default_variant: None,
+                discriminant: None,
                 docstring: None,
+                since: None,
             },
         );
     }
@@ -240,6 +255,8 @@ mod test_metadata {
                         discr: None,
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
+                        retryable: false,
                     },
                     VariantMetadata {
                         name: "Initialized".into(),
@@ -249,8 +266,11 @@ mod test_metadata {
                             ty: Type::String,
                             default: None,
                             docstring: None,
+                            order: None,
                         }],
                         docstring: None,
+                        deprecated: None,
+                        retryable: false,
                     },
                     VariantMetadata {
                         name: "Complete".into(),
@@ -263,12 +283,18 @@ mod test_metadata {
                             },
                             default: None,
                             docstring: None,
+                            order: None,
                         }],
                         docstring: None,
+                        deprecated: None,
+                        retryable: false,
                     },
                 ],
                 non_exhaustive: false,
+                default_variant: None,
+                discriminant: None,
                 docstring: None,
+                since: None,
             },
         );
     }
@@ -286,22 +312,31 @@ mod test_metadata {
                         discr: Some(LiteralMetadata::new_uint(1)),
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
+                        retryable: false,
                     },
                     VariantMetadata {
                         name: "Three".into(),
                         discr: Some(LiteralMetadata::new_uint(3)),
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
+                        retryable: false,
                     },
                     VariantMetadata {
                         name: "Fifteen".into(),
                         discr: Some(LiteralMetadata::new_uint(15)),
                         fields: vec![],
                         docstring: None,
+                        deprecated: None,
+                        retryable: false,
                     },
                 ],
                 non_exhaustive: false,
+                default_variant: None,
+                discriminant: None,
                 docstring: None,
+                since: None,
             },
         );
     }
@@ -320,16 +355,23 @@ mod test_metadata {
                             discr: None,
                             fields: vec![],
                             docstring: None,
+                            deprecated: None,
+                            retryable: false,
                         },
                         VariantMetadata {
                             name: "DivideByZero".into(),
                             discr: None,
                             fields: vec![],
                             docstring: None,
+                            deprecated: None,
+                            retryable: false,
                         },
                     ],
                     non_exhaustive: false,
+                    default_variant: None,
+                    discriminant: None,
                     docstring: None,
+                    since: None,
                 },
                 is_flat: true,
             },
@@ -350,6 +392,8 @@ mod test_metadata {
                             discr: None,
                             fields: vec![],
                             docstring: None,
+                            deprecated: None,
+                            retryable: false,
                         },
                         VariantMetadata {
                             name: "PermissionDenied".into(),
@@ -359,8 +403,11 @@ mod test_metadata {
                                 ty: Type::String,
                                 default: None,
                                 docstring: None,
+                                order: None,
                             }],
                             docstring: None,
+                            deprecated: None,
+                            retryable: false,
                         },
                         VariantMetadata {
                             name: "InvalidWeapon".into(),
@@ -373,12 +420,18 @@ mod test_metadata {
                                 },
                                 default: None,
                                 docstring: None,
+                                order: None,
                             }],
                             docstring: None,
+                            deprecated: None,
+                            retryable: false,
                         },
                     ],
                     non_exhaustive: false,
+                    default_variant: None,
+                    discriminant: None,
                     docstring: None,
+                    since: None,
                 },
                 is_flat: false,
             },
@@ -393,7 +446,16 @@ mod test_metadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "Calculator".into(),
                 imp: ObjectImpl::Struct,
+                implements: vec![],
+                is_singleton: false,
+                parent: None,
+                constants: vec![],
+                observable: false,
+                cursor: false,
+                thread_bound: false,
+                on_poison: None,
                 docstring: None,
+                since: None,
             },
         );
     }
@@ -432,6 +494,15 @@ mod test_function_metadata {
         unimplemented!()
     }
 
+    /// Has attributes which aren't UniFFI's.
+    #[inline]
+    #[doc(alias = "other_name")]
+    #[uniffi::export]
+    #[must_use]
+    pub fn test_func_with_attributes() -> u8 {
+        unimplemented!()
+    }
+
     #[uniffi::export]
     pub fn test_func_that_throws() -> Result<State, FlatError> {
         unimplemented!()
@@ -453,9 +524,18 @@ mod test_function_metadata {
         unimplemented!()
     }
 
+    #[uniffi::export(constant)]
+    pub fn test_constant() -> u32 {
+        unimplemented!()
+    }
+
     #[uniffi::export]
     pub trait CalculatorDisplay: Send + Sync {
         fn display_result(&self, val: String);
+
+        fn display_error(&self, message: String) {
+            self.display_result(message)
+        }
     }
 
     #[uniffi::export]
@@ -504,6 +584,21 @@ mod test_function_metadata {
                 throws: None,
                 checksum: Some(UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC.checksum()),
                 docstring: None,
+                return_docstring: None,
+                since: None,
+                namespace_object: None,
+                deprecated: None,
+                retries: None,
+                main_thread: false,
+                chunked: false,
+                with_warnings: false,
+                segmented: false,
+                cancellable: false,
+                target: None,
+                extension: None,
+                default_on_none: None,
+                static_return: false,
+                runtime_initializer: false,
             },
         );
     }
@@ -523,6 +618,56 @@ mod test_function_metadata {
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_NO_RETURN.checksum(),
                 ),
                 docstring: None,
+                return_docstring: None,
+                since: None,
+                namespace_object: None,
+                deprecated: None,
+                retries: None,
+                main_thread: false,
+                chunked: false,
+                with_warnings: false,
+                segmented: false,
+                cancellable: false,
+                target: None,
+                extension: None,
+                default_on_none: None,
+                static_return: false,
+                runtime_initializer: false,
+            },
+        );
+    }
+
+    #[test]
+    fn test_function_with_attributes() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_WITH_ATTRIBUTES,
+            FnMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "test_func_with_attributes".into(),
+                is_async: false,
+                inputs: vec![],
+                return_type: Some(Type::UInt8),
+                throws: None,
+                checksum: Some(
+                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_WITH_ATTRIBUTES
+                        .checksum(),
+                ),
+                docstring: Some("Has attributes which aren't UniFFI's.".into()),
+                return_docstring: None,
+                since: None,
+                namespace_object: None,
+                deprecated: None,
+                retries: None,
+                main_thread: false,
+                chunked: false,
+                with_warnings: false,
+                segmented: false,
+                cancellable: false,
+                target: None,
+                extension: None,
+                default_on_none: None,
+                static_return: false,
+                runtime_initializer: false,
             },
         );
     }
@@ -548,6 +693,21 @@ mod test_function_metadata {
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_THAT_THROWS.checksum(),
                 ),
                 docstring: None,
+                return_docstring: None,
+                since: None,
+                namespace_object: None,
+                deprecated: None,
+                retries: None,
+                main_thread: false,
+                chunked: false,
+                with_warnings: false,
+                segmented: false,
+                cancellable: false,
+                target: None,
+                extension: None,
+                default_on_none: None,
+                static_return: false,
+                runtime_initializer: false,
             },
         );
     }
@@ -571,6 +731,37 @@ mod test_function_metadata {
                         .checksum(),
                 ),
                 docstring: None,
+                return_docstring: None,
+                since: None,
+                namespace_object: None,
+                deprecated: None,
+                retries: None,
+                main_thread: false,
+                chunked: false,
+                with_warnings: false,
+                segmented: false,
+                cancellable: false,
+                target: None,
+                extension: None,
+                default_on_none: None,
+                static_return: false,
+                runtime_initializer: false,
+            },
+        );
+    }
+
+    #[test]
+    fn test_const_fn() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_CONST_FN_TEST_CONSTANT,
+            ConstFnMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "test_constant".into(),
+                return_type: Type::UInt32,
+                checksum: Some(
+                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_CONST_FN_TEST_CONSTANT.checksum(),
+                ),
+                docstring: None,
             },
         );
     }
@@ -591,10 +782,13 @@ mod test_function_metadata {
                 return_type: Some(Type::UInt8),
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_ADD.checksum(),
                 ),
                 docstring: None,
+                return_docstring: None,
+                since: None,
             },
         );
     }
@@ -629,6 +823,21 @@ mod test_function_metadata {
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_ASYNC_FUNC.checksum(),
                 ),
                 docstring: None,
+                return_docstring: None,
+                since: None,
+                namespace_object: None,
+                deprecated: None,
+                retries: None,
+                main_thread: false,
+                chunked: false,
+                with_warnings: false,
+                segmented: false,
+                cancellable: false,
+                target: None,
+                extension: None,
+                default_on_none: None,
+                static_return: false,
+                runtime_initializer: false,
             },
         );
     }
@@ -655,6 +864,21 @@ mod test_function_metadata {
                         .checksum(),
                 ),
                 docstring: None,
+                return_docstring: None,
+                since: None,
+                namespace_object: None,
+                deprecated: None,
+                retries: None,
+                main_thread: false,
+                chunked: false,
+                with_warnings: false,
+                segmented: false,
+                cancellable: false,
+                target: None,
+                extension: None,
+                default_on_none: None,
+                static_return: false,
+                runtime_initializer: false,
             },
         );
     }
@@ -675,11 +899,14 @@ mod test_function_metadata {
                 return_type: Some(Type::UInt8),
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_ASYNC_SUB
                         .checksum(),
                 ),
                 docstring: None,
+                return_docstring: None,
+                since: None,
             },
         );
     }
@@ -701,11 +928,14 @@ mod test_function_metadata {
                 }),
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_GET_DISPLAY
                         .checksum(),
                 ),
                 docstring: None,
+                return_docstring: None,
+                since: None,
             },
         );
     }
@@ -720,6 +950,7 @@ mod test_function_metadata {
                 index: 0,
                 name: "display_result".into(),
                 is_async: false,
+                has_default: false,
                 inputs: vec![
                     FnParamMetadata::simple("val", Type::String),
                 ],
@@ -729,6 +960,30 @@ mod test_function_metadata {
                 checksum: Some(UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATORDISPLAY_DISPLAY_RESULT
                     .checksum()),
                 docstring: None,
+                return_docstring: None,
+                since: None,
+            },
+        );
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATORDISPLAY_DISPLAY_ERROR,
+            TraitMethodMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                trait_name: "CalculatorDisplay".into(),
+                index: 1,
+                name: "display_error".into(),
+                is_async: false,
+                has_default: true,
+                inputs: vec![
+                    FnParamMetadata::simple("message", Type::String),
+                ],
+                return_type: None,
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: Some(UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATORDISPLAY_DISPLAY_ERROR
+                    .checksum()),
+                docstring: None,
+                return_docstring: None,
+                since: None,
             },
         );
     }
@@ -751,6 +1006,7 @@ mod test_function_metadata {
                 index: 0,
                 name: "log".into(),
                 is_async: false,
+                has_default: false,
                 inputs: vec![FnParamMetadata::simple("message", Type::String)],
                 return_type: None,
                 throws: None,
@@ -759,6 +1015,8 @@ mod test_function_metadata {
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_LOGGER_LOG.checksum(),
                 ),
                 docstring: None,
+                return_docstring: None,
+                since: None,
             },
         );
     }
@@ -0,0 +1,34 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// `set_panic_callback()` is generated by `setup_scaffolding!()`, from `panic_callback` in
+// `uniffi.toml`.
+uniffi::setup_scaffolding!();
+
+#[derive(Debug, uniffi::Error)]
+pub enum DivideError {
+    NotWhole,
+}
+
+impl std::fmt::Display for DivideError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl std::error::Error for DivideError {}
+
+/// Divide two numbers, which panics if `divisor` is zero.
+///
+/// This throws, so that the Swift bindings throw the panic rather than crashing.
+#[uniffi::export]
+fn divide(dividend: u32, divisor: u32) -> Result<u32, DivideError> {
+    if divisor == 0 {
+        panic!("division by zero");
+    }
+    if dividend % divisor != 0 {
+        return Err(DivideError::NotWhole);
+    }
+    Ok(dividend / divisor)
+}
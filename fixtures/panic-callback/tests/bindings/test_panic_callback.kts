@@ -0,0 +1,35 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.panic_callback.*
+
+class Reporter : PanicCallback {
+    val panics = mutableListOf<Pair<String, String?>>()
+
+    override fun onPanic(message: String, backtrace: String?) {
+        synchronized(panics) { panics.add(Pair(message, backtrace)) }
+    }
+}
+
+val reporter = Reporter()
+setPanicCallback(reporter)
+assert(divide(6u, 3u) == 2u)
+try {
+    divide(7u, 3u)
+    throw RuntimeException("divide() should have thrown")
+} catch (e: DivideException.NotWhole) {
+    // Errors aren't panics, so they aren't reported.
+}
+assert(reporter.panics.isEmpty())
+
+// The panic is still thrown as an error, after it's reported once.
+try {
+    divide(1u, 0u)
+    throw RuntimeException("divide() should have panicked")
+} catch (e: InternalException) {
+    assert(e.message == "division by zero")
+}
+assert(reporter.panics.size == 1)
+assert(reporter.panics[0].first == "division by zero")
+assert(reporter.panics[0].second != null)
@@ -0,0 +1,34 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from uniffi_panic_callback import *
+
+class Reporter(PanicCallback):
+    def __init__(self):
+        self.panics = []
+
+    def on_panic(self, message, backtrace):
+        self.panics.append((message, backtrace))
+
+reporter = Reporter()
+set_panic_callback(reporter)
+assert divide(6, 3) == 2
+try:
+    divide(7, 3)
+    raise AssertionError("divide() should have thrown")
+except DivideError.NotWhole:
+    pass
+# Errors aren't panics, so they aren't reported.
+assert reporter.panics == []
+
+# The panic is still thrown as an error, after it's reported once.
+try:
+    divide(1, 0)
+    raise AssertionError("divide() should have panicked")
+except InternalError as e:
+    assert str(e) == "division by zero"
+assert len(reporter.panics) == 1
+message, backtrace = reporter.panics[0]
+assert message == "division by zero"
+assert backtrace is not None
@@ -0,0 +1,30 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import Foundation
+import uniffi_panic_callback
+
+class Reporter: PanicCallback {
+    var panics: [(String, String?)] = []
+
+    func onPanic(message: String, backtrace: String?) {
+        panics.append((message, backtrace))
+    }
+}
+
+let reporter = Reporter()
+setPanicCallback(callback: reporter)
+assert(try! divide(dividend: 6, divisor: 3) == 2)
+assert(reporter.panics.isEmpty)
+
+// The panic is still thrown as an error, after it's reported once.
+do {
+    _ = try divide(dividend: 1, divisor: 0)
+    fatalError("divide() should have panicked")
+} catch UniffiInternalError.rustPanic(let message) {
+    assert(message == "division by zero")
+}
+assert(reporter.panics.count == 1)
+assert(reporter.panics[0].0 == "division by zero")
+assert(reporter.panics[0].1 != nil)
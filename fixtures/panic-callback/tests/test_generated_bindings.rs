@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_panic_callback.py",
+    "tests/bindings/test_panic_callback.kts",
+    "tests/bindings/test_panic_callback.swift",
+);
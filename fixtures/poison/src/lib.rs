@@ -0,0 +1,70 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, thiserror::Error)]
+pub enum CounterError {
+    #[error("The counter overflowed")]
+    Overflow,
+}
+
+// The state shared by both counters.  `lock().unwrap()` panics once the mutex is poisoned, unless
+// the interface's `[OnPoison]` policy recovers from it, and the scaffolding turns that panic into
+// a `StatePoisoned` error.
+struct Counter {
+    value: uniffi::Mutex<u32>,
+}
+
+impl Counter {
+    fn new() -> Self {
+        Self {
+            value: uniffi::Mutex::new(0),
+        }
+    }
+
+    fn increment(&self) -> Result<u32, CounterError> {
+        let mut value = self.value.lock().unwrap();
+        *value = value.checked_add(1).ok_or(CounterError::Overflow)?;
+        Ok(*value)
+    }
+
+    fn panic_while_locked(&self) -> Result<(), CounterError> {
+        let mut value = self.value.lock().unwrap();
+        *value += 1;
+        panic!("panic while the counter is locked");
+    }
+}
+
+pub struct StrictCounter(Counter);
+
+impl StrictCounter {
+    fn new() -> Self {
+        Self(Counter::new())
+    }
+
+    fn increment(&self) -> Result<u32, CounterError> {
+        self.0.increment()
+    }
+
+    fn panic_while_locked(&self) -> Result<(), CounterError> {
+        self.0.panic_while_locked()
+    }
+}
+
+pub struct LenientCounter(Counter);
+
+impl LenientCounter {
+    fn new() -> Self {
+        Self(Counter::new())
+    }
+
+    fn increment(&self) -> Result<u32, CounterError> {
+        self.0.increment()
+    }
+
+    fn panic_while_locked(&self) -> Result<(), CounterError> {
+        self.0.panic_while_locked()
+    }
+}
+
+uniffi::include_scaffolding!("poison");
@@ -0,0 +1,34 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.poison.*
+
+val strict = StrictCounter()
+assert(strict.increment() == 1u)
+try {
+    strict.panicWhileLocked()
+    throw RuntimeException("Should have thrown")
+} catch (e: InternalException) {
+    // It's okay!
+}
+// Every later call fails, rather than using the state the panic left behind.
+for (i in 0 until 2) {
+    try {
+        strict.increment()
+        throw RuntimeException("Should have thrown")
+    } catch (e: StatePoisonedException) {
+        assert(e.message == "state poisoned by a panic in an earlier call")
+    }
+}
+
+val lenient = LenientCounter()
+assert(lenient.increment() == 1u)
+try {
+    lenient.panicWhileLocked()
+    throw RuntimeException("Should have thrown")
+} catch (e: InternalException) {
+    // It's okay!
+}
+// The counter carries on from the value the panic left behind.
+assert(lenient.increment() == 3u)
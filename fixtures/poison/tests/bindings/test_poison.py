@@ -0,0 +1,30 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from poison import *
+
+strict = StrictCounter()
+assert strict.increment() == 1
+try:
+    strict.panic_while_locked()
+    raise Exception("Should have raised")
+except InternalError:
+    pass
+# Every later call fails, rather than using the state the panic left behind.
+for _ in range(2):
+    try:
+        strict.increment()
+        raise Exception("Should have raised")
+    except StatePoisonedError as e:
+        assert str(e) == "state poisoned by a panic in an earlier call", str(e)
+
+lenient = LenientCounter()
+assert lenient.increment() == 1
+try:
+    lenient.panic_while_locked()
+    raise Exception("Should have raised")
+except InternalError:
+    pass
+# The counter carries on from the value the panic left behind.
+assert lenient.increment() == 3
@@ -0,0 +1,37 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import poison
+
+// Only methods which throw can report the error, others crash like they do for a panic.
+let strict = StrictCounter()
+assert(try! strict.increment() == 1)
+do {
+    try strict.panicWhileLocked()
+    fatalError("Should have thrown")
+} catch is StatePoisonedError {
+    fatalError("The first panic isn't a StatePoisoned error")
+} catch {
+    // It's okay!
+}
+// Every later call fails, rather than using the state the panic left behind.
+for _ in 0..<2 {
+    do {
+        _ = try strict.increment()
+        fatalError("Should have thrown")
+    } catch let error as StatePoisonedError {
+        assert(error.message == "state poisoned by a panic in an earlier call")
+    }
+}
+
+let lenient = LenientCounter()
+assert(try! lenient.increment() == 1)
+do {
+    try lenient.panicWhileLocked()
+    fatalError("Should have thrown")
+} catch {
+    // It's okay!
+}
+// The counter carries on from the value the panic left behind.
+assert(try! lenient.increment() == 3)
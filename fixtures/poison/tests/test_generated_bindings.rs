@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_poison.py",
+    "tests/bindings/test_poison.kts",
+    "tests/bindings/test_poison.swift",
+);
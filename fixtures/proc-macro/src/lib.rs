@@ -2,11 +2,24 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    num::{NonZeroI64, NonZeroU32, NonZeroU8},
+    ops::{Range, RangeInclusive},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+use url::Url;
 
 mod callback_interface;
 
-use callback_interface::TestCallbackInterface;
+use callback_interface::{ProgressListener, TestCallbackInterface};
 
 #[derive(uniffi::Record)]
 pub struct One {
@@ -41,6 +54,25 @@ pub struct RecordWithBytes {
     some_bytes: Vec<u8>,
 }
 
+// Serialized in a different order to the declaration.
+#[derive(uniffi::Record)]
+pub struct Reordered {
+    #[uniffi(order = 1)]
+    a: String,
+    #[uniffi(order = 0)]
+    b: u32,
+}
+
+// Adjacent booleans are packed into bytes.
+#[derive(uniffi::Record)]
+#[uniffi(packed)]
+pub struct Packed {
+    a: bool,
+    b: bool,
+    count: u32,
+    c: bool,
+}
+
 // An object that's not used anywhere (ie, in records, function signatures, etc)
 // should not break things.
 #[derive(uniffi::Object)]
@@ -57,6 +89,11 @@ impl Unused {
 #[uniffi::export]
 pub trait Trait: Send + Sync {
     fn name(&self) -> String;
+
+    // Foreign implementations can omit this method to use the default implementation.
+    fn greeting(&self) -> String {
+        format!("Hello from {}", self.name())
+    }
 }
 
 struct TraitImpl {}
@@ -101,6 +138,14 @@ impl Object {
         assert!(matches!(e, BasicError::InvalidInput));
         42
     }
+
+    fn describe(&self, verbose: bool) -> Cow<str> {
+        if verbose {
+            Cow::Owned("Object".to_string() + " (verbose)")
+        } else {
+            Cow::Borrowed("Object")
+        }
+    }
 }
 
 #[uniffi::export]
@@ -108,6 +153,98 @@ fn get_trait_name_by_ref(t: &dyn Trait) -> String {
     t.name()
 }
 
+#[uniffi::export]
+fn get_trait_greeting(t: Arc<dyn Trait>) -> String {
+    t.greeting()
+}
+
+#[uniffi::export]
+pub fn describe_labelled(label: Option<String>, obj: Arc<Object>) -> String {
+    match label {
+        Some(label) => format!("{label}: {}", obj.describe(false)),
+        None => obj.describe(false).into_owned(),
+    }
+}
+
+#[uniffi::export]
+fn cow_string(owned: bool) -> Cow<'static, str> {
+    if owned {
+        Cow::Owned("owned".repeat(2))
+    } else {
+        Cow::Borrowed("borrowed")
+    }
+}
+
+#[uniffi::export]
+fn boxed_range(n: u32) -> Box<[u32]> {
+    (0..n).collect()
+}
+
+#[uniffi::export]
+fn reverse_boxed(mut values: Box<[u32]>) -> Box<[u32]> {
+    values.reverse();
+    values
+}
+
+#[uniffi::export]
+fn measure_sleep(millis: u64) -> uniffi::MonotonicDuration {
+    let start = Instant::now();
+    std::thread::sleep(Duration::from_millis(millis));
+    uniffi::MonotonicDuration::since(start)
+}
+
+#[uniffi::export]
+fn add_millis(elapsed: uniffi::MonotonicDuration, millis: u64) -> uniffi::MonotonicDuration {
+    (elapsed.as_duration() + Duration::from_millis(millis)).into()
+}
+
+#[uniffi::export]
+fn shift_window(window: Range<Duration>, by: Duration) -> Range<Duration> {
+    window.start + by..window.end + by
+}
+
+#[uniffi::export]
+fn shift_closed_window(window: RangeInclusive<Duration>, by: Duration) -> RangeInclusive<Duration> {
+    *window.start() + by..=*window.end() + by
+}
+
+#[uniffi::export]
+fn window_is_empty(window: Range<Duration>) -> bool {
+    window.is_empty()
+}
+
+#[uniffi::export]
+fn closed_window_is_empty(window: RangeInclusive<Duration>) -> bool {
+    window.is_empty()
+}
+
+#[uniffi::export]
+fn echo_handle(handle: uniffi::OpaqueHandle) -> uniffi::OpaqueHandle {
+    handle
+}
+
+// A handle to a value which the foreign code can't see, like one from a C library.
+#[uniffi::export]
+fn new_counter_handle(start: u32) -> uniffi::OpaqueHandle {
+    uniffi::OpaqueHandle::from_ptr(Box::into_raw(Box::new(start)).cast())
+}
+
+#[uniffi::export]
+fn take_counter_handle(handle: uniffi::OpaqueHandle) -> u32 {
+    // Safety: the tests only pass handles from `new_counter_handle`, and take each of them once.
+    *unsafe { Box::from_raw(handle.as_ptr().cast::<u32>()) }
+}
+
+#[uniffi::export]
+fn join_tags(tags: Vec<Arc<str>>) -> Arc<str> {
+    Arc::from(tags.join(","))
+}
+
+#[uniffi::export]
+fn reverse_bytes(data: Arc<[u8]>) -> Arc<[u8]> {
+    data.iter().rev().copied().collect()
+}
+
 #[uniffi::export]
 fn make_one(inner: i32) -> One {
     One { inner }
@@ -134,6 +271,24 @@ fn take_record_with_bytes(rwb: RecordWithBytes) -> Vec<u8> {
     rwb.some_bytes
 }
 
+#[uniffi::export]
+fn swap_reordered(r: Reordered) -> Reordered {
+    Reordered {
+        a: r.b.to_string(),
+        b: r.a.len() as u32,
+    }
+}
+
+#[uniffi::export]
+fn invert_packed(p: Packed) -> Packed {
+    Packed {
+        a: !p.a,
+        b: !p.b,
+        count: p.count + 1,
+        c: !p.c,
+    }
+}
+
 #[uniffi::export]
 fn call_callback_interface(cb: Box<dyn TestCallbackInterface>) {
     cb.do_nothing();
@@ -158,6 +313,18 @@ fn call_callback_interface(cb: Box<dyn TestCallbackInterface>) {
     assert_eq!(42, cb.callback_handler(Object::new()));
 }
 
+#[uniffi::export]
+fn count_with_progress(total: u32, listener: Option<Arc<dyn ProgressListener>>) -> u32 {
+    let mut done = 0;
+    while done < total {
+        done += 1;
+        if let Some(listener) = &listener {
+            listener.on_progress(done, total);
+        }
+    }
+    done
+}
+
 // Type that's defined in the UDL and not wrapped with #[uniffi::export]
 pub struct Zero {
     inner: String,
@@ -191,11 +358,80 @@ pub enum ReprU8 {
     Three = 0x3,
 }
 
+#[uniffi::export]
+impl MaybeBool {
+    fn negate(&self) -> MaybeBool {
+        match self {
+            Self::True => Self::False,
+            Self::False => Self::True,
+            Self::Uncertain => Self::Uncertain,
+        }
+    }
+}
+
 #[uniffi::export]
 fn enum_identity(value: MaybeBool) -> MaybeBool {
     value
 }
 
+#[uniffi::export]
+fn repr_u8_identity(value: ReprU8) -> ReprU8 {
+    value
+}
+
+// The discriminant is serialized as a big-endian `u16`, rather than an `i32`.
+#[derive(uniffi::Enum)]
+#[uniffi(discriminant = u16_be)]
+pub enum Command {
+    Start { delay: u32 },
+    Stop,
+}
+
+#[uniffi::export]
+fn toggle_command(command: Command) -> Command {
+    match command {
+        Command::Start { .. } => Command::Stop,
+        Command::Stop => Command::Start { delay: 5 },
+    }
+}
+
+#[uniffi::export]
+fn command_wire_bytes(command: Command) -> Vec<u8> {
+    let mut buf = Vec::new();
+    <Command as uniffi::Lower<crate::UniFfiTag>>::write(command, &mut buf);
+    buf
+}
+
+#[derive(uniffi::Enum)]
+pub enum MixedEnum {
+    Empty,
+    Text { s: String },
+    Numbers { n: Vec<i64> },
+}
+
+#[uniffi::export]
+impl MixedEnum {
+    fn describe(&self) -> String {
+        match self {
+            Self::Empty => "empty".to_owned(),
+            Self::Text { s } => format!("text: {s}"),
+            Self::Numbers { n } => format!("{} numbers", n.len()),
+        }
+    }
+
+    fn with_suffix(self, suffix: String) -> MixedEnum {
+        match self {
+            Self::Text { s } => Self::Text { s: s + &suffix },
+            other => other,
+        }
+    }
+}
+
+#[uniffi::export]
+fn mixed_enum_identity(value: MixedEnum) -> MixedEnum {
+    value
+}
+
 #[derive(thiserror::Error, uniffi::Error, Debug, PartialEq, Eq)]
 pub enum BasicError {
     #[error("InvalidInput")]
@@ -243,6 +479,62 @@ impl Object {
     }
 }
 
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum PortError {
+    #[error("Invalid UTF-8: {reason}")]
+    InvalidUtf8 { reason: String },
+    #[error("Invalid port: {reason}")]
+    InvalidPort { reason: String },
+}
+
+impl From<std::str::Utf8Error> for PortError {
+    fn from(e: std::str::Utf8Error) -> Self {
+        Self::InvalidUtf8 {
+            reason: e.to_string(),
+        }
+    }
+}
+
+impl From<std::num::ParseIntError> for PortError {
+    fn from(e: std::num::ParseIntError) -> Self {
+        Self::InvalidPort {
+            reason: e.to_string(),
+        }
+    }
+}
+
+// Only the declared error is lowered, whichever error `?` converted it from.
+#[uniffi::export]
+fn parse_port(data: Vec<u8>) -> Result<u16, PortError> {
+    let text = std::str::from_utf8(&data)?;
+    Ok(text.trim().parse()?)
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum UrlError {
+    #[error("Invalid URL: {reason}")]
+    InvalidUrl { reason: String },
+}
+
+// A custom type whose conversion can fail, so that invalid strings are rejected by the lift.
+uniffi::custom_type!(Url, String, {
+    try_lift: |val| {
+        Url::parse(&val).map_err(|e| {
+            UrlError::InvalidUrl {
+                reason: e.to_string(),
+            }
+            .into()
+        })
+    },
+    lower: |obj| obj.into(),
+});
+
+// If a URL fails to lift, the `UrlError` is thrown to the caller.
+#[uniffi::export]
+fn get_url_host(url: Url) -> Result<String, UrlError> {
+    Ok(url.host_str().unwrap_or_default().to_string())
+}
+
 // defined in UDL.
 fn get_one(one: Option<One>) -> One {
     one.unwrap_or(One { inner: 0 })
@@ -275,4 +567,177 @@ pub fn join(parts: &[String], sep: &str) -> String {
     parts.join(sep)
 }
 
+#[uniffi::export]
+pub fn json_roundtrip(value: serde_json::Value) -> serde_json::Value {
+    value
+}
+
+// The kind of JSON value that Rust received, to check the bindings didn't pass it as a string.
+#[uniffi::export]
+pub fn json_kind(value: serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "bool",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+    .to_owned()
+}
+
+// Zero is rejected when it's lifted, which `DynError` reports to the caller.
+#[uniffi::export]
+pub fn non_zero_roundtrip(value: NonZeroU32) -> Result<NonZeroU32, uniffi::DynError> {
+    Ok(value)
+}
+
+#[derive(uniffi::Record)]
+pub struct NonZeroCounts {
+    pub small: NonZeroU8,
+    pub large: Option<NonZeroI64>,
+}
+
+#[uniffi::export]
+pub fn non_zero_counts_roundtrip(counts: NonZeroCounts) -> NonZeroCounts {
+    counts
+}
+
+#[uniffi::export]
+pub fn path_join(base: &Path, file_name: String) -> PathBuf {
+    base.join(file_name)
+}
+
+// Paths which aren't valid UTF-8 are lowered lossily.
+#[uniffi::export]
+pub fn non_utf8_path() -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+        PathBuf::from(OsStr::from_bytes(b"caf\xe9"))
+    }
+    #[cfg(windows)]
+    {
+        use std::{ffi::OsString, os::windows::ffi::OsStringExt};
+        PathBuf::from(OsString::from_wide(&[0x63, 0x61, 0x66, 0xD800]))
+    }
+}
+
+static FIXTURE_VERSION_CALLS: AtomicU32 = AtomicU32::new(0);
+
+/// The version of this fixture, computed when it's first accessed.
+#[uniffi::export(constant)]
+pub fn fixture_version() -> String {
+    FIXTURE_VERSION_CALLS.fetch_add(1, Ordering::SeqCst);
+    format!("proc-macro {}", env!("CARGO_PKG_VERSION"))
+}
+
+#[uniffi::export]
+pub fn fixture_version_calls() -> u32 {
+    FIXTURE_VERSION_CALLS.load(Ordering::SeqCst)
+}
+
+static API_VERSION_CALLS: AtomicU32 = AtomicU32::new(0);
+
+/// This returns a `'static` borrow, so `static_functions_as_constants` in `uniffi.toml` exposes
+/// it as a constant.
+#[uniffi::export]
+pub fn api_version() -> &'static str {
+    API_VERSION_CALLS.fetch_add(1, Ordering::SeqCst);
+    "3.1"
+}
+
+#[uniffi::export]
+pub fn api_version_calls() -> u32 {
+    API_VERSION_CALLS.load(Ordering::SeqCst)
+}
+
+#[derive(uniffi::Object)]
+pub struct StringBuilder {
+    parts: Mutex<Vec<String>>,
+}
+
+#[uniffi::export]
+impl StringBuilder {
+    #[uniffi::constructor]
+    fn new() -> Self {
+        Self {
+            parts: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn append(&self, part: String) {
+        self.parts.lock().unwrap().push(part);
+    }
+
+    /// The parts appended so far, copied at the time of the call.
+    #[uniffi::snapshot]
+    fn view(&self) -> StringBuilderView<'_> {
+        StringBuilderView(self.parts.lock().unwrap())
+    }
+
+    /// Consumes the builder, so it can't be used afterwards.
+    fn build(self) -> String {
+        self.parts.into_inner().unwrap().concat()
+    }
+}
+
+/// A view of the parts of a `StringBuilder`, which keeps it locked.
+pub struct StringBuilderView<'a>(MutexGuard<'a, Vec<String>>);
+
+#[derive(uniffi::Record)]
+pub struct StringBuilderSnapshot {
+    parts: Vec<String>,
+    len: u32,
+}
+
+impl uniffi::Snapshot for StringBuilderView<'_> {
+    type Owned = StringBuilderSnapshot;
+
+    fn snapshot(self) -> StringBuilderSnapshot {
+        StringBuilderSnapshot {
+            parts: self.0.clone(),
+            len: self.0.iter().map(String::len).sum::<usize>() as u32,
+        }
+    }
+}
+
+#[derive(uniffi::Object)]
+pub struct Catalog {
+    names: Vec<String>,
+}
+
+#[uniffi::export]
+impl Catalog {
+    #[uniffi::constructor]
+    fn new(names: Vec<String>) -> Self {
+        Self { names }
+    }
+
+    /// The names starting with `prefix`, collected into a list during the call.
+    #[uniffi::collect]
+    fn names_with_prefix(&self, prefix: String) -> impl Iterator<Item = String> + '_ {
+        self.names
+            .iter()
+            .filter(move |name| name.starts_with(&prefix))
+            .cloned()
+    }
+}
+
+/// Returns the quotient, and the remainder in the `remainder` out-parameter.
+#[uniffi::export]
+pub fn divide(dividend: u32, divisor: u32, remainder: &mut u32) -> Result<u32, BasicError> {
+    if divisor == 0 {
+        return Err(BasicError::InvalidInput);
+    }
+    *remainder = dividend % divisor;
+    Ok(dividend / divisor)
+}
+
+/// Trims the whitespace around `text` in place.
+#[uniffi::export]
+pub fn trim_in_place(text: &mut String) {
+    *text = text.trim().to_string();
+}
+
 uniffi::include_scaffolding!("proc-macro");
@@ -14,14 +14,62 @@ assert(takeTwo(two) == "a")
 val rwb = RecordWithBytes(byteArrayOf(1,2,3))
 assert(takeRecordWithBytes(rwb).contentEquals(byteArrayOf(1, 2, 3)))
 
+val reordered = swapReordered(Reordered("abc", 12u))
+assert(reordered.a == "12")
+assert(reordered.b == 3u)
+
+val packed = invertPacked(Packed(true, false, 1u, false))
+assert(packed == Packed(false, true, 2u, true))
+
+assert(toggleCommand(Command.Stop) == Command.Start(5u))
+assert(toggleCommand(Command.Start(1u)) == Command.Stop)
+assert(commandWireBytes(Command.Start(5u)).toList() == listOf<Byte>(0, 1, 0, 0, 0, 5))
+
 var obj = Object()
 obj = Object.namedCtor(1u)
 assert(obj.isHeavy() == MaybeBool.UNCERTAIN)
 var obj2 = Object()
 assert(obj.isOtherHeavy(obj2) == MaybeBool.UNCERTAIN)
+assert(obj.describe(false) == "Object")
+assert(obj.describe(true) == "Object (verbose)")
+assert(describeLabelled("obj", obj) == "obj: Object")
+assert(cowString(false) == "borrowed")
+assert(cowString(true) == "ownedowned")
+assert(boxedRange(3u) == listOf(0u, 1u, 2u))
+assert(reverseBoxed(listOf(1u, 2u, 3u)) == listOf(3u, 2u, 1u))
+assert(measureSleep(10uL) >= java.time.Duration.ofMillis(10))
+assert(addMillis(java.time.Duration.ofSeconds(1), 500uL) == java.time.Duration.ofMillis(1500))
+val second = java.time.Duration.ofSeconds(1)
+assert(shiftWindow(second.rangeUntil(second.multipliedBy(3)), second) == second.multipliedBy(2).rangeUntil(second.multipliedBy(4)))
+assert(!windowIsEmpty(second.rangeUntil(second.multipliedBy(3))))
+assert(windowIsEmpty(second.rangeUntil(second)))
+assert(shiftClosedWindow(second..second.multipliedBy(3), second) == second.multipliedBy(2)..second.multipliedBy(4))
+assert(!closedWindowIsEmpty(second..second))
+// Empty ranges whose end is before their start are passed unchanged.
+assert(closedWindowIsEmpty(second.multipliedBy(3)..second))
+assert(shiftClosedWindow(second.multipliedBy(3)..second, second).isEmpty())
+val handle = newCounterHandle(7u)
+assert(echoHandle(handle) == handle)
+assert(takeCounterHandle(echoHandle(handle)) == 7u)
+assert(echoHandle(OpaqueHandle(-1L)).value == -1L)
+assert(reverseBytes(byteArrayOf(1, 2, 3)).contentEquals(byteArrayOf(3, 2, 1)))
 
 assert(enumIdentity(MaybeBool.TRUE) == MaybeBool.TRUE)
 
+// `MixedEnum` is generated as a `sealed interface`, see uniffi.toml
+val mixed: MixedEnum = MixedEnum.Text("hello")
+assert(mixedEnumIdentity(mixed) == MixedEnum.Text("hello"))
+assert(mixedEnumIdentity(MixedEnum.Empty) == MixedEnum.Empty)
+assert(mixedEnumIdentity(MixedEnum.Numbers(listOf(1L, 2L))) == MixedEnum.Numbers(listOf(1L, 2L)))
+
+// Methods exported for enums are called with the value they're called on.
+assert(MixedEnum.Text("hello").describe() == "text: hello")
+assert(MixedEnum.Numbers(listOf(1L, 2L)).describe() == "2 numbers")
+assert(MixedEnum.Empty.describe() == "empty")
+assert(MixedEnum.Text("hello").withSuffix("!") == MixedEnum.Text("hello!"))
+assert(MaybeBool.TRUE.negate() == MaybeBool.FALSE)
+assert(MaybeBool.UNCERTAIN.negate() == MaybeBool.UNCERTAIN)
+
 // just make sure this works / doesn't crash
 val three = Three(obj)
 
@@ -29,12 +77,102 @@ assert(makeZero().inner == "ZERO")
 assert(makeRecordWithBytes().someBytes.contentEquals(byteArrayOf(0, 1, 2, 3, 4)))
 assert(join(listOf("a", "b", "c"), ":") == "a:b:c")
 
+// JSON values are passed as their JSON text.
+assert(jsonRoundtrip("""{"name":"uniffi","nested":{"list":[1,2.5,null],"on":true}}""") == """{"name":"uniffi","nested":{"list":[1,2.5,null],"on":true}}""")
+assert(jsonKind("""{"a": {}}""") == "object")
+assert(jsonRoundtrip("[ 1, \"two\", [] ]") == "[1,\"two\",[]]")
+assert(jsonKind("[]") == "array")
+assert(jsonRoundtrip("\"text\"") == "\"text\"")
+assert(jsonKind("\"text\"") == "string")
+assert(jsonRoundtrip("42") == "42")
+assert(jsonKind("false") == "bool")
+assert(jsonKind("null") == "null")
+try {
+    jsonRoundtrip("{not json")
+    throw RuntimeException("Invalid JSON should have been rejected")
+} catch (e: InternalException) {
+    // Expected
+}
+
+// Non-zero integers are value classes which can't hold zero.
+assert(nonZeroRoundtrip(NonZeroUInt(7u)) == NonZeroUInt(7u))
+assert(nonZeroRoundtrip(NonZeroUInt(UInt.MAX_VALUE)).value == UInt.MAX_VALUE)
+try {
+    NonZeroUInt(0u)
+    throw RuntimeException("Zero should have been rejected")
+} catch (e: IllegalArgumentException) {
+    // Expected
+}
+val counts = nonZeroCountsRoundtrip(NonZeroCounts(NonZeroUByte(3u), NonZeroLong(-5L)))
+assert(counts.small.value == 3.toUByte())
+assert(counts.large == NonZeroLong(-5L))
+assert(nonZeroCountsRoundtrip(NonZeroCounts(NonZeroUByte(255u), null)).large == null)
+
+assert(pathJoin(java.io.File("some/dir"), "file.txt") == java.io.File("some/dir/file.txt"))
+assert(nonUtf8Path() == java.io.File("caf\uFFFD"))
+
+// Constants are computed once, when they're first accessed.
+assert(fixtureVersionCalls() == 0u)
+assert(fixtureVersion == "proc-macro 0.22.0")
+assert(fixtureVersion == "proc-macro 0.22.0")
+assert(fixtureVersionCalls() == 1u)
+
+// Functions returning a `'static` borrow are cached the same way.
+assert(apiVersionCalls() == 0u)
+assert(apiVersion == "3.1")
+assert(apiVersion == "3.1")
+assert(apiVersionCalls() == 1u)
+
+// Methods which take `self` by value consume the object, which can't be used afterwards.
+val builder = StringBuilder()
+builder.append("Hello, ")
+val snapshot = builder.view()
+builder.append("world")
+// The snapshot is a copy, so it doesn't see later changes.
+assert(snapshot == StringBuilderSnapshot(listOf("Hello, "), 7u))
+assert(builder.view() == StringBuilderSnapshot(listOf("Hello, ", "world"), 12u))
+assert(builder.build() == "Hello, world")
+for (useBuilder in listOf({ builder.append("!") }, { builder.build() })) {
+    try {
+        useBuilder()
+        throw RuntimeException("The builder should have been consumed")
+    } catch (e: IllegalStateException) {
+        assert(e.message!!.contains("already been consumed"))
+    }
+}
+
+// Methods returning `impl Iterator` return a list of the items.
+val catalog = Catalog(listOf("apple", "banana", "avocado"))
+assert(catalog.namesWithPrefix("a") == listOf("apple", "avocado"))
+assert(catalog.namesWithPrefix("c").isEmpty())
+
+// `&mut` out-parameters are returned along with the function's value.
+assert(divide(17u, 5u, 0u) == DivideOutputs(3u, 2u))
+assert(trimInPlace("  padded ") == "padded")
+try {
+    divide(1u, 0u, 0u)
+    throw RuntimeException("divide should have thrown")
+} catch (e: BasicException.InvalidInput) {
+}
+
 try {
     alwaysFails()
     throw RuntimeException("alwaysFails should have thrown")
 } catch (e: BasicException) {
 }
 
+assert(parsePort(" 8080\n".toByteArray()) == 8080.toUShort())
+try {
+    parsePort(byteArrayOf(-1))
+    throw RuntimeException("parsePort should have thrown")
+} catch (e: PortException.InvalidUtf8) {
+}
+try {
+    parsePort("http".toByteArray())
+    throw RuntimeException("parsePort should have thrown")
+} catch (e: PortException.InvalidPort) {
+}
+
 obj.doStuff(5u)
 
 try {
@@ -47,6 +185,23 @@ val traitImpl = obj.getTrait(null)
 assert(traitImpl.name() == "TraitImpl")
 assert(obj.getTrait(traitImpl).name() == "TraitImpl")
 assert(getTraitNameByRef(traitImpl) == "TraitImpl")
+assert(traitImpl.greeting() == "Hello from TraitImpl")
+assert(getTraitGreeting(traitImpl) == "Hello from TraitImpl")
+
+// Foreign implementations can omit methods with a default implementation in Rust.
+class KtTrait : Trait {
+    override fun name() = "KtTrait"
+}
+
+class KtTraitWithGreeting : Trait {
+    override fun name() = "KtTraitWithGreeting"
+
+    override fun greeting() = "Hi"
+}
+
+assert(KtTrait().greeting() == "Hello from KtTrait")
+assert(getTraitGreeting(KtTrait()) == "Hello from KtTrait")
+assert(getTraitGreeting(KtTraitWithGreeting()) == "Hi")
 
 
 class KtTestCallbackInterface : TestCallbackInterface {
@@ -77,3 +232,16 @@ class KtTestCallbackInterface : TestCallbackInterface {
 }
 
 callCallbackInterface(KtTestCallbackInterface())
+
+class KtProgressListener : ProgressListener {
+    val calls = mutableListOf<Pair<UInt, UInt>>()
+
+    override fun onProgress(done: UInt, total: UInt) {
+        calls.add(Pair(done, total))
+    }
+}
+
+val listener = KtProgressListener()
+assert(countWithProgress(3u, listener) == 3u)
+assert(listener.calls == listOf(Pair(1u, 3u), Pair(2u, 3u), Pair(3u, 3u)))
+assert(countWithProgress(3u, null) == 3u)
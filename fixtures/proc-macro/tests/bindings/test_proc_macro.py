@@ -2,6 +2,9 @@
 # License, v. 2.0. If a copy of the MPL was not distributed with this
 # file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+import datetime
+import pathlib
+import proc_macro
 from proc_macro import *
 
 one = make_one(123)
@@ -14,18 +17,100 @@ assert take_two(two) == "a"
 rwb = RecordWithBytes(bytes([1,2,3]))
 assert take_record_with_bytes(rwb) == bytes([1,2,3])
 
+reordered = swap_reordered(Reordered("abc", 12))
+assert reordered.a == "12"
+assert reordered.b == 3
+
+packed = invert_packed(Packed(a=True, b=False, count=1, c=False))
+assert (packed.a, packed.b, packed.count, packed.c) == (False, True, 2, True)
+
+assert toggle_command(Command.STOP()) == Command.START(delay=5)
+assert toggle_command(Command.START(delay=1)) == Command.STOP()
+assert command_wire_bytes(Command.START(delay=5)) == bytes([0, 1, 0, 0, 0, 5])
+
 obj = Object()
 obj = Object.named_ctor(1)
 assert obj.is_heavy() == MaybeBool.UNCERTAIN
 obj2 = Object()
 assert obj.is_other_heavy(obj2) == MaybeBool.UNCERTAIN
+assert obj.describe(False) == "Object"
+assert obj.describe(True) == "Object (verbose)"
+assert describe_labelled("obj", obj) == "obj: Object"
+assert cow_string(False) == "borrowed"
+assert cow_string(True) == "ownedowned"
+assert boxed_range(3) == [0, 1, 2]
+assert reverse_boxed([1, 2, 3]) == [3, 2, 1]
+assert measure_sleep(10) >= datetime.timedelta(milliseconds=10)
+assert add_millis(datetime.timedelta(seconds=1), 500) == datetime.timedelta(seconds=1.5)
+second = datetime.timedelta(seconds=1)
+window = DurationRange(second, 3 * second)
+assert shift_window(window, second) == DurationRange(2 * second, 4 * second)
+assert 2 * second in window and 3 * second not in window
+assert not window_is_empty(window)
+assert window_is_empty(DurationRange(second, second))
+assert shift_window(DurationRange(second, second), second) == DurationRange(2 * second, 2 * second)
+closed = DurationRange(second, 3 * second, inclusive=True)
+assert shift_closed_window(closed, second) == DurationRange(2 * second, 4 * second, True)
+assert 3 * second in closed
+assert not closed_window_is_empty(DurationRange(second, second, True))
+# Empty ranges whose end is before their start are passed unchanged.
+empty = DurationRange(3 * second, second, True)
+assert closed_window_is_empty(empty) and empty.is_empty()
+assert shift_closed_window(empty, second) == DurationRange(4 * second, 2 * second, True)
+try:
+    shift_window(closed, second)
+    raise AssertionError("shift_window should only accept an exclusive range")
+except ValueError:
+    pass
+handle = new_counter_handle(7)
+assert isinstance(handle, OpaqueHandle)
+assert echo_handle(handle) == handle
+assert take_counter_handle(echo_handle(handle)) == 7
+assert echo_handle(OpaqueHandle(2**64 - 1)).value == 2**64 - 1
+try:
+    echo_handle(7)
+    raise AssertionError("echo_handle should only accept an OpaqueHandle")
+except TypeError:
+    pass
+assert join_tags(["a", "b", "a"]) == "a,b,a"
+assert reverse_bytes(b"abc") == b"cba"
 
 trait_impl = obj.get_trait(None)
 assert trait_impl.name() == "TraitImpl"
 assert obj.get_trait(trait_impl).name() == "TraitImpl"
 assert get_trait_name_by_ref(trait_impl) == "TraitImpl"
+assert trait_impl.greeting() == "Hello from TraitImpl"
+assert get_trait_greeting(trait_impl) == "Hello from TraitImpl"
+
+# Foreign implementations can omit methods with a default implementation in Rust.
+class PyTrait(Trait):
+    def name(self):
+        return "PyTrait"
+
+class PyTraitWithGreeting(Trait):
+    def name(self):
+        return "PyTraitWithGreeting"
+
+    def greeting(self):
+        return "Hi"
+
+py_trait = PyTrait()
+assert py_trait.greeting() == "Hello from PyTrait"
+assert get_trait_greeting(py_trait) == "Hello from PyTrait"
+assert get_trait_greeting(PyTraitWithGreeting()) == "Hi"
 
 assert enum_identity(MaybeBool.TRUE) == MaybeBool.TRUE
+assert mixed_enum_identity(MixedEnum.TEXT("hello")) == MixedEnum.TEXT("hello")
+assert mixed_enum_identity(MixedEnum.EMPTY()) == MixedEnum.EMPTY()
+assert mixed_enum_identity(MixedEnum.NUMBERS([1, 2])) == MixedEnum.NUMBERS([1, 2])
+
+# Methods exported for enums are called with the value they're called on.
+assert MixedEnum.TEXT("hello").describe() == "text: hello"
+assert MixedEnum.NUMBERS([1, 2]).describe() == "2 numbers"
+assert MixedEnum.EMPTY().describe() == "empty"
+assert MixedEnum.TEXT("hello").with_suffix("!") == MixedEnum.TEXT("hello!")
+assert MaybeBool.TRUE.negate() == MaybeBool.FALSE
+assert MaybeBool.UNCERTAIN.negate() == MaybeBool.UNCERTAIN
 
 # just make sure this works / doesn't crash
 three = Three(obj)
@@ -40,6 +125,83 @@ assert(make_hashmap(1, 2) == {1: 2})
 
 assert(join(["a", "b", "c"], ":") == "a:b:c")
 
+nested = {"name": "uniffi", "tags": ["ffi", "json"], "nested": {"count": 3, "ratio": 0.5, "on": True, "none": None}}
+assert json_roundtrip(nested) == nested
+assert json_kind(nested) == "object"
+assert json_roundtrip([1, "two", [3.5], {}]) == [1, "two", [3.5], {}]
+assert json_kind([]) == "array"
+for scalar, kind in [("text", "string"), (42, "number"), (-1.25, "number"), (True, "bool"), (None, "null")]:
+    assert json_roundtrip(scalar) == scalar
+    assert json_kind(scalar) == kind
+try:
+    json_roundtrip(float("nan"))
+    raise AssertionError("NaN isn't valid JSON")
+except ValueError:
+    pass
+
+assert non_zero_roundtrip(7) == 7
+assert non_zero_roundtrip(2**32 - 1) == 2**32 - 1
+try:
+    non_zero_roundtrip(0)
+    raise AssertionError("Zero should have been rejected")
+except ValueError:
+    pass
+counts = non_zero_counts_roundtrip(NonZeroCounts(small=3, large=-5))
+assert (counts.small, counts.large) == (3, -5)
+assert non_zero_counts_roundtrip(NonZeroCounts(small=255, large=None)).large is None
+try:
+    non_zero_counts_roundtrip(NonZeroCounts(small=0, large=None))
+    raise AssertionError("Zero should have been rejected")
+except ValueError:
+    pass
+
+assert path_join(pathlib.Path("some") / "dir", "file.txt") == pathlib.Path("some/dir/file.txt")
+assert path_join("some/dir", "file.txt") == pathlib.Path("some/dir/file.txt")
+assert non_utf8_path() == pathlib.Path("caf\ufffd")
+
+# Constants are computed once, when they're first accessed.
+assert fixture_version_calls() == 0
+assert proc_macro.fixture_version == "proc-macro 0.22.0"
+assert proc_macro.fixture_version == "proc-macro 0.22.0"
+assert fixture_version_calls() == 1
+
+# Functions returning a `'static` borrow are cached the same way.
+assert api_version_calls() == 0
+assert proc_macro.api_version == "3.1"
+assert proc_macro.api_version == "3.1"
+assert api_version_calls() == 1
+
+# Methods which take `self` by value consume the object, which can't be used afterwards.
+builder = StringBuilder()
+builder.append("Hello, ")
+snapshot = builder.view()
+builder.append("world")
+# The snapshot is a copy, so it doesn't see later changes.
+assert snapshot == StringBuilderSnapshot(parts=["Hello, "], len=7)
+assert builder.view() == StringBuilderSnapshot(parts=["Hello, ", "world"], len=12)
+assert builder.build() == "Hello, world"
+for use_builder in [lambda: builder.append("!"), lambda: builder.build()]:
+    try:
+        use_builder()
+        raise AssertionError("The builder should have been consumed")
+    except ValueError as e:
+        assert "already been consumed" in str(e)
+
+# Methods returning `impl Iterator` return a list of the items.
+catalog = Catalog(["apple", "banana", "avocado"])
+assert catalog.names_with_prefix("a") == ["apple", "avocado"]
+assert catalog.names_with_prefix("c") == []
+
+# `&mut` out-parameters are returned along with the function's value.
+assert divide(17, 5, 0) == (3, 2)
+assert trim_in_place("  padded ") == "padded"
+try:
+    divide(1, 0, 0)
+except BasicError.InvalidInput:
+    pass
+else:
+    raise Exception("divide should have thrown")
+
 try:
     always_fails()
 except BasicError.OsError:
@@ -86,6 +248,39 @@ class PyTestCallbackInterface(TestCallbackInterface):
 
 call_callback_interface(PyTestCallbackInterface())
 
+class PyProgressListener(ProgressListener):
+    def __init__(self):
+        self.calls = []
+
+    def on_progress(self, done, total):
+        self.calls.append((done, total))
+
+listener = PyProgressListener()
+assert count_with_progress(3, listener) == 3
+assert listener.calls == [(1, 3), (2, 3), (3, 3)]
+assert count_with_progress(3, None) == 3
+
+# Errors converted with `?` are thrown as the function's error.
+assert parse_port(b" 8080\n") == 8080
+try:
+    parse_port(b"\xff")
+    raise Exception("parse_port should have thrown")
+except PortError.InvalidUtf8 as e:
+    assert "invalid utf-8" in str(e)
+try:
+    parse_port(b"http")
+    raise Exception("parse_port should have thrown")
+except PortError.InvalidPort as e:
+    assert "invalid digit" in str(e)
+
+# Custom types whose conversion fails throw the function's error.
+assert get_url_host("https://example.com/path") == "example.com"
+try:
+    get_url_host("not a url")
+    raise Exception("get_url_host should have thrown")
+except UrlError.InvalidUrl as e:
+    assert "relative URL without a base" in str(e)
+
 # udl exposed functions with procmacro types.
 assert get_one(None).inner == 0
 assert get_bool(None) == MaybeBool.UNCERTAIN
@@ -100,3 +295,8 @@ assert(MaybeBool.UNCERTAIN.value == 2)
 # values with an explicit value should be that value.
 assert(ReprU8.ONE.value == 1)
 assert(ReprU8.THREE.value == 3)
+
+# The stdlib enum's value is the discriminant, so it can be used to look variants up, and
+# round-trips through Rust.
+assert(repr_u8_identity(ReprU8(3)) == ReprU8.THREE)
+assert(repr_u8_identity(ReprU8.ONE).value == 1)
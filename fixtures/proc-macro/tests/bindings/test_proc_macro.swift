@@ -15,18 +15,83 @@ assert(takeTwo(two: two) == "a")
 let rwb = RecordWithBytes(someBytes: Data([1, 2, 3]))
 assert(takeRecordWithBytes(rwb: rwb) == Data([1, 2, 3]))
 
+let reordered = swapReordered(r: Reordered(a: "abc", b: 12))
+assert(reordered.a == "12")
+assert(reordered.b == 3)
+
+let packed = invertPacked(p: Packed(a: true, b: false, count: 1, c: false))
+assert(packed == Packed(a: false, b: true, count: 2, c: true))
+
+assert(toggleCommand(command: .stop) == .start(delay: 5))
+assert(toggleCommand(command: .start(delay: 1)) == .stop)
+assert(commandWireBytes(command: .start(delay: 5)) == Data([0, 1, 0, 0, 0, 5]))
+
 var obj = Object()
 obj = Object.namedCtor(arg: 1)
 assert(obj.isHeavy() == .uncertain)
 let obj2 = Object()
 assert(obj.isOtherHeavy(other: obj2) == .uncertain)
+assert(obj.describe(verbose: false) == "Object")
+assert(obj.describe(verbose: true) == "Object (verbose)")
+assert(describeLabelled(label: "obj", obj: obj) == "obj: Object")
+assert(cowString(owned: false) == "borrowed")
+assert(cowString(owned: true) == "ownedowned")
+assert(boxedRange(n: 3) == [0, 1, 2])
+assert(reverseBoxed(values: [1, 2, 3]) == [3, 2, 1])
+assert(measureSleep(millis: 10) >= 0.01)
+assert(addMillis(elapsed: 1.0, millis: 500) == 1.5)
+assert(shiftWindow(window: 1.0..<3.0, by: 1.0) == 2.0..<4.0)
+assert(!windowIsEmpty(window: 1.0..<3.0))
+assert(windowIsEmpty(window: 1.0..<1.0))
+assert(shiftWindow(window: 1.0..<1.0, by: 1.0).isEmpty)
+assert(shiftClosedWindow(window: 1.0...3.0, by: 1.0) == 2.0...4.0)
+assert(!closedWindowIsEmpty(window: 1.0...1.0))
+let handle = newCounterHandle(start: 7)
+assert(echoHandle(handle: handle) == handle)
+assert(takeCounterHandle(handle: echoHandle(handle: handle)) == 7)
+assert(echoHandle(handle: OpaqueHandle(rawValue: UInt64.max)).rawValue == UInt64.max)
+assert(reverseBytes(data: Data([1, 2, 3])) == Data([3, 2, 1]))
 
 let traitImpl = obj.getTrait(inc: nil)
 assert(traitImpl.name() == "TraitImpl")
 assert(obj.getTrait(inc: traitImpl).name() == "TraitImpl")
 assert(getTraitNameByRef(t: traitImpl) == "TraitImpl")
+assert(traitImpl.greeting() == "Hello from TraitImpl")
+assert(getTraitGreeting(t: traitImpl) == "Hello from TraitImpl")
+
+// Foreign implementations can omit methods with a default implementation in Rust.
+class SwiftTrait: Trait {
+    func name() -> String {
+        return "SwiftTrait"
+    }
+}
+
+class SwiftTraitWithGreeting: Trait {
+    func name() -> String {
+        return "SwiftTraitWithGreeting"
+    }
+
+    func greeting() -> String {
+        return "Hi"
+    }
+}
+
+assert(SwiftTrait().greeting() == "Hello from SwiftTrait")
+assert(getTraitGreeting(t: SwiftTrait()) == "Hello from SwiftTrait")
+assert(getTraitGreeting(t: SwiftTraitWithGreeting()) == "Hi")
 
 assert(enumIdentity(value: .true) == .true)
+assert(mixedEnumIdentity(value: .text(s: "hello")) == .text(s: "hello"))
+assert(mixedEnumIdentity(value: .empty) == .empty)
+assert(mixedEnumIdentity(value: .numbers(n: [1, 2])) == .numbers(n: [1, 2]))
+
+// Methods exported for enums are called with the value they're called on.
+assert(MixedEnum.text(s: "hello").describe() == "text: hello")
+assert(MixedEnum.numbers(n: [1, 2]).describe() == "2 numbers")
+assert(MixedEnum.empty.describe() == "empty")
+assert(MixedEnum.text(s: "hello").withSuffix(suffix: "!") == .text(s: "hello!"))
+assert(MaybeBool.true.negate() == .false)
+assert(MaybeBool.uncertain.negate() == .uncertain)
 
 // just make sure this works / doesn't crash
 let three = Three(obj: obj)
@@ -35,12 +100,89 @@ assert(makeZero().inner == "ZERO")
 assert(makeRecordWithBytes().someBytes == Data([0, 1, 2, 3, 4]))
 assert(join(parts: ["a", "b", "c"], sep: ":") == "a:b:c")
 
+// JSON values are passed as their JSON text.
+assert(jsonRoundtrip(value: #"{"name":"uniffi","nested":{"list":[1,2.5,null],"on":true}}"#) == #"{"name":"uniffi","nested":{"list":[1,2.5,null],"on":true}}"#)
+assert(jsonKind(value: #"{"a": {}}"#) == "object")
+assert(jsonRoundtrip(value: #"[ 1, "two", [] ]"#) == #"[1,"two",[]]"#)
+assert(jsonKind(value: "[]") == "array")
+assert(jsonRoundtrip(value: #""text""#) == #""text""#)
+assert(jsonKind(value: #""text""#) == "string")
+assert(jsonRoundtrip(value: "42") == "42")
+assert(jsonKind(value: "false") == "bool")
+assert(jsonKind(value: "null") == "null")
+
+// Non-zero integers use the plain integer types, and zero is rejected by Rust.
+assert(try! nonZeroRoundtrip(value: 7) == 7)
+assert(try! nonZeroRoundtrip(value: UInt32.max) == UInt32.max)
+do {
+    _ = try nonZeroRoundtrip(value: 0)
+    fatalError("Zero should have been rejected")
+} catch is DynError {
+    // Expected
+}
+let counts = nonZeroCountsRoundtrip(counts: NonZeroCounts(small: 3, large: -5))
+assert(counts.small == 3)
+assert(counts.large == -5)
+assert(nonZeroCountsRoundtrip(counts: NonZeroCounts(small: 255, large: nil)).large == nil)
+
+assert(pathJoin(base: URL(fileURLWithPath: "/some/dir"), fileName: "file.txt").path == "/some/dir/file.txt")
+assert(nonUtf8Path().lastPathComponent == "caf\u{FFFD}")
+
+// Constants are computed once, when they're first accessed.
+assert(fixtureVersionCalls() == 0)
+assert(fixtureVersion == "proc-macro 0.22.0")
+assert(fixtureVersion == "proc-macro 0.22.0")
+assert(fixtureVersionCalls() == 1)
+
+// Functions returning a `'static` borrow are cached the same way.
+assert(apiVersionCalls() == 0)
+assert(apiVersion == "3.1")
+assert(apiVersion == "3.1")
+assert(apiVersionCalls() == 1)
+
+// Methods which take `self` by value consume the object.  Using it afterwards is a precondition
+// failure, so that can't be tested here.
+let builder = StringBuilder()
+builder.append(part: "Hello, ")
+let snapshot = builder.view()
+builder.append(part: "world")
+// The snapshot is a copy, so it doesn't see later changes.
+assert(snapshot == StringBuilderSnapshot(parts: ["Hello, "], len: 7))
+assert(builder.view() == StringBuilderSnapshot(parts: ["Hello, ", "world"], len: 12))
+assert(builder.build() == "Hello, world")
+
+// Methods returning `impl Iterator` return a list of the items.
+let catalog = Catalog(names: ["apple", "banana", "avocado"])
+assert(catalog.namesWithPrefix(prefix: "a") == ["apple", "avocado"])
+assert(catalog.namesWithPrefix(prefix: "c").isEmpty)
+
+// `&mut` out-parameters are returned along with the function's value.
+assert(try! divide(dividend: 17, divisor: 5, remainder: 0) == (value: 3, remainder: 2))
+assert(trimInPlace(text: "  padded ") == "padded")
+do {
+    _ = try divide(dividend: 1, divisor: 0, remainder: 0)
+    fatalError("divide should have thrown")
+} catch BasicError.InvalidInput {
+}
+
 do {
     try alwaysFails()
     fatalError("alwaysFails should have thrown")
 } catch BasicError.OsError {
 }
 
+assert(try! parsePort(data: " 8080\n".data(using: .utf8)!) == 8080)
+do {
+    _ = try parsePort(data: Data([0xff]))
+    fatalError("parsePort should have thrown")
+} catch PortError.InvalidUtf8 {
+}
+do {
+    _ = try parsePort(data: "http".data(using: .utf8)!)
+    fatalError("parsePort should have thrown")
+} catch PortError.InvalidPort {
+}
+
 try! obj.doStuff(times: 5)
 
 do {
@@ -86,3 +228,16 @@ class SwiftTestCallbackInterface : TestCallbackInterface {
 }
 
 callCallbackInterface(cb: SwiftTestCallbackInterface())
+
+class SwiftProgressListener : ProgressListener {
+    var calls: [[UInt32]] = []
+
+    func onProgress(done: UInt32, total: UInt32) {
+        calls.append([done, total])
+    }
+}
+
+let listener = SwiftProgressListener()
+assert(countWithProgress(total: 3, listener: listener) == 3)
+assert(listener.calls == [[1, 3], [2, 3], [3, 3]])
+assert(countWithProgress(total: 3, listener: nil) == 3)
@@ -0,0 +1,81 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! How often the buffer is reallocated while lowering a sequence of fixed-size records, with each
+//! buffer growth policy.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use uniffi::{set_buffer_growth, BufferGrowth, Lift, Lower};
+use uniffi_proc_macro::{One, UniFfiTag};
+
+static REALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        REALLOCS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const RECORD_COUNT: i32 = 1000;
+// The item count, followed by each record's `i32` field.
+const SERIALIZED_LEN: usize = 4 + 4 * RECORD_COUNT as usize;
+
+fn records() -> Vec<One> {
+    (0..RECORD_COUNT)
+        .map(|i| <One as Lift<UniFfiTag>>::try_lift_from_bytes(&i.to_be_bytes()).unwrap())
+        .collect()
+}
+
+// Lower the records with `growth`, returning how many times the buffer was reallocated and its
+// final capacity.
+fn lower_records(growth: BufferGrowth) -> (usize, usize) {
+    set_buffer_growth(growth);
+    let records = records();
+    let before = REALLOCS.load(Ordering::SeqCst);
+    let buf = <Vec<One> as Lower<UniFfiTag>>::lower(records);
+    let reallocs = REALLOCS.load(Ordering::SeqCst) - before;
+    let vec = buf.destroy_into_vec();
+    assert_eq!(vec.len(), SERIALIZED_LEN);
+    (reallocs, vec.capacity())
+}
+
+// The policy is global, so all of them are measured in a single test.
+#[test]
+fn fixed_size_record_sequence() {
+    assert_eq!(
+        <Vec<One> as Lower<UniFfiTag>>::size_hint(&records()),
+        SERIALIZED_LEN
+    );
+
+    let (doubling, _) = lower_records(BufferGrowth::Doubling);
+    let (fixed_increment, _) = lower_records(BufferGrowth::FixedIncrement(1024));
+    let (size_hint, capacity) = lower_records(BufferGrowth::SizeHint);
+
+    // The size hint is exact, so the buffer is allocated once at the right size.
+    assert_eq!(size_hint, 0);
+    assert_eq!(capacity, SERIALIZED_LEN);
+    // Pre-sizing from the hint reallocates less often than the other policies.
+    assert!(size_hint <= fixed_increment);
+    assert!(size_hint < doubling);
+    // The records have a fixed size, so the other policies also grow the buffer only once, after
+    // the item count is written.
+    assert_eq!(fixed_increment, 1);
+    assert_eq!(doubling, 1);
+}
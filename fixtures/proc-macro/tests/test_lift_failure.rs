@@ -0,0 +1,30 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Calls where an argument fails to lift, which must still take over the handles passed for the
+//! other arguments.
+
+use std::sync::Arc;
+
+use uniffi::{RustBuffer, RustCallStatus, RustCallStatusCode};
+use uniffi_proc_macro::{uniffi_uniffi_proc_macro_fn_func_describe_labelled, Object};
+
+#[test]
+fn later_arguments_are_dropped_when_an_argument_fails_to_lift() {
+    let obj = Arc::new(Object);
+    // The foreign code passes its own reference to the object.
+    let handle = Arc::into_raw(Arc::clone(&obj)) as *const std::os::raw::c_void;
+    // An invalid `Option` tag, so the label fails to lift before the object is lifted.
+    let label = RustBuffer::from_vec(vec![2]);
+
+    let mut call_status = RustCallStatus::default();
+    let result =
+        uniffi_uniffi_proc_macro_fn_func_describe_labelled(label, handle, &mut call_status);
+    assert_eq!(call_status.code, RustCallStatusCode::UnexpectedError);
+    assert!(result.is_empty());
+    unsafe { call_status.error_buf.assume_init() }.destroy();
+
+    // The reference passed for the object was dropped, rather than leaked.
+    assert_eq!(Arc::strong_count(&obj), 1);
+}
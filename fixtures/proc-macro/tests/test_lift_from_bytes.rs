@@ -0,0 +1,46 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Lifting records from arbitrary bytes, as a fuzzer would, with the `fuzzing` feature.
+
+use uniffi::Lift;
+use uniffi_proc_macro::{Three, Two, UniFfiTag};
+
+// `Two { a: "xy", b: Some([true, false]) }`
+const TWO: &[u8] = &[0, 0, 0, 2, b'x', b'y', 1, 0, 0, 0, 2, 1, 0];
+
+#[test]
+fn record_lift_from_bytes() {
+    assert!(<Two as Lift<UniFfiTag>>::try_lift_from_bytes(TWO).is_ok());
+}
+
+#[test]
+fn record_lift_from_truncated_bytes() {
+    for len in 0..TWO.len() {
+        assert!(<Two as Lift<UniFfiTag>>::try_lift_from_bytes(&TWO[..len]).is_err());
+    }
+}
+
+#[test]
+fn record_lift_from_oversized_bytes() {
+    // Junk after the record.
+    let mut bytes = TWO.to_vec();
+    bytes.extend([0xff; 16]);
+    assert!(<Two as Lift<UniFfiTag>>::try_lift_from_bytes(&bytes).is_err());
+
+    // Lengths which are larger than the remaining bytes.
+    assert!(<Two as Lift<UniFfiTag>>::try_lift_from_bytes(&[0x7f, 0xff, 0xff, 0xff, 0]).is_err());
+    assert!(<Two as Lift<UniFfiTag>>::try_lift_from_bytes(&[
+        0, 0, 0, 0, 1, 0x7f, 0xff, 0xff, 0xff, 1
+    ])
+    .is_err());
+    // A negative length.
+    assert!(<Two as Lift<UniFfiTag>>::try_lift_from_bytes(&[0xff, 0xff, 0xff, 0xff]).is_err());
+}
+
+#[test]
+fn record_with_object_lift_from_bytes() {
+    // The object handle would be a dangling pointer, so it's never lifted.
+    assert!(<Three as Lift<UniFfiTag>>::try_lift_from_bytes(&[0, 0, 0, 0, 0, 0, 0x10, 0]).is_err());
+}
@@ -0,0 +1,43 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::{Arc, Mutex};
+
+#[derive(uniffi::Enum)]
+pub enum Suit {
+    Hearts,
+    Spades,
+}
+
+#[derive(uniffi::Record)]
+pub struct Card {
+    suit: Suit,
+    rank: u8,
+}
+
+#[derive(uniffi::Object)]
+pub struct Deck {
+    cards: Mutex<Vec<Card>>,
+}
+
+#[uniffi::export]
+impl Deck {
+    #[uniffi::constructor]
+    fn new(cards: Vec<Card>) -> Arc<Self> {
+        Arc::new(Self {
+            cards: Mutex::new(cards),
+        })
+    }
+
+    fn draw(&self) -> Option<Card> {
+        self.cards.lock().unwrap().pop()
+    }
+}
+
+#[uniffi::export]
+fn ace_of(suit: Suit) -> Card {
+    Card { suit, rank: 1 }
+}
+
+uniffi::setup_scaffolding!();
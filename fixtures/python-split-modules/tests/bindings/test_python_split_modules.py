@@ -0,0 +1,27 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import uniffi_python_split_modules
+from uniffi_python_split_modules import Card, Deck, Suit, ace_of
+from uniffi_python_split_modules.enums import Suit as EnumsSuit
+from uniffi_python_split_modules.records import Card as RecordsCard
+from uniffi_python_split_modules.interfaces import Deck as InterfacesDeck
+
+# The top-level package re-exports the submodules.
+assert Card is RecordsCard
+assert Suit is EnumsSuit
+assert Deck is InterfacesDeck
+assert "Card" in uniffi_python_split_modules.__all__
+assert "ace_of" in uniffi_python_split_modules.__all__
+assert uniffi_python_split_modules.records.__all__ == ["Card"]
+
+# Types from different submodules refer to each other.
+card = ace_of(Suit.SPADES)
+assert card == Card(suit=Suit.SPADES, rank=1)
+assert isinstance(card, RecordsCard)
+
+deck = Deck([Card(suit=Suit.HEARTS, rank=12), card])
+assert deck.draw() == card
+assert deck.draw().suit == Suit.HEARTS
+assert deck.draw() is None
@@ -0,0 +1,37 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    name: String,
+    tags: Vec<String>,
+    timeout_secs: Option<u32>,
+    retries: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            name: "untitled".to_string(),
+            tags: vec!["new".to_string()],
+            timeout_secs: Some(30),
+            retries: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Counter {
+    count: u64,
+}
+
+fn default_settings() -> Settings {
+    Settings::default()
+}
+
+fn default_counter() -> Counter {
+    Counter::default()
+}
+
+uniffi::include_scaffolding!("record_default");
@@ -0,0 +1,19 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.recorddefault.*
+
+val settings = Settings.default()
+assert(settings == defaultSettings())
+assert(settings.name == "untitled")
+assert(settings.retries == 3u)
+assert(settings.tags == listOf("new"))
+assert(settings.timeoutSecs == 30u)
+
+// Each call returns a new value, which can be changed without affecting the next one.
+settings.retries = 5u
+assert(Settings.default() == defaultSettings())
+
+assert(Counter.default() == defaultCounter())
+assert(Counter.default().count == 0uL)
@@ -0,0 +1,19 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from record_default import *
+
+settings = Settings.default()
+assert settings == default_settings(), settings
+assert settings.name == "untitled"
+assert settings.retries == 3
+assert settings.tags == ["new"]
+assert settings.timeout_secs == 30
+
+# Each call returns a new value, which can be changed without affecting the next one.
+settings.tags.append("changed")
+assert Settings.default() == default_settings()
+
+assert Counter.default() == default_counter()
+assert Counter.default().count == 0
@@ -0,0 +1,19 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import record_default
+
+var settings = Settings.default()
+assert(settings == defaultSettings())
+assert(settings.name == "untitled")
+assert(settings.retries == 3)
+assert(settings.tags == ["new"])
+assert(settings.timeoutSecs == 30)
+
+// Each call returns a new value, which can be changed without affecting the next one.
+settings.retries = 5
+assert(Settings.default() == defaultSettings())
+
+assert(Counter.default() == defaultCounter())
+assert(Counter.default().count == 0)
@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_record_default.py",
+    "tests/bindings/test_record_default.kts",
+    "tests/bindings/test_record_default.swift",
+);
@@ -0,0 +1,27 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use uniffi::ByteSegments;
+
+fn reverse_segments(segments: ByteSegments) -> ByteSegments {
+    let mut segments = segments.into_inner();
+    segments.reverse();
+    for segment in segments.iter_mut() {
+        segment.reverse();
+    }
+    segments.into()
+}
+
+fn segment_lengths(segments: ByteSegments) -> Vec<u64> {
+    segments.iter().map(|s| s.len() as u64).collect()
+}
+
+fn split(data: Vec<u8>, size: u32) -> ByteSegments {
+    data.chunks(size.max(1) as usize)
+        .map(|chunk| chunk.to_vec())
+        .collect::<Vec<_>>()
+        .into()
+}
+
+uniffi::include_scaffolding!("segmented_bytes");
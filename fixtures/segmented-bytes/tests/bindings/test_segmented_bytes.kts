@@ -0,0 +1,18 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.segmentedbytes.*
+
+fun bytes(vararg values: Int) = values.map { it.toByte() }.toByteArray()
+
+val segments = listOf(bytes(1, 2, 3), bytes(), bytes(0, 255), bytes(4))
+val reversed = reverseSegments(segments)
+assert(reversed.map { it.toList() } == listOf(bytes(4), bytes(255, 0), bytes(), bytes(3, 2, 1)).map { it.toList() })
+assert(reverseSegments(listOf()).isEmpty())
+assert(segmentLengths(segments) == listOf(3uL, 0uL, 2uL, 1uL))
+assert(segmentLengths(listOf(ByteArray(100000))) == listOf(100000uL))
+
+val chunks = split(bytes(1, 2, 3, 4, 5, 6, 7), 3u)
+assert(chunks.map { it.toList() } == listOf(bytes(1, 2, 3), bytes(4, 5, 6), bytes(7)).map { it.toList() })
+assert(split(bytes(), 3u).isEmpty())
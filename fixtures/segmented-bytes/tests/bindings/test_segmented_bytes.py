@@ -0,0 +1,14 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from segmented_bytes import *
+
+segments = [b"abc", b"", b"\x00\xff", b"d"]
+assert reverse_segments(segments) == [b"d", b"\xff\x00", b"", b"cba"]
+assert reverse_segments([]) == []
+assert segment_lengths(segments) == [3, 0, 2, 1]
+assert segment_lengths([b"x" * 100000]) == [100000]
+
+assert split(b"abcdefg", 3) == [b"abc", b"def", b"g"]
+assert split(b"", 3) == []
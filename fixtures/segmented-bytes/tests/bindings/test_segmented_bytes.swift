@@ -0,0 +1,15 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import Foundation
+import segmented_bytes
+
+let segments = [Data([1, 2, 3]), Data(), Data([0, 255]), Data([4])]
+assert(reverseSegments(segments: segments) == [Data([4]), Data([255, 0]), Data(), Data([3, 2, 1])])
+assert(reverseSegments(segments: []) == [])
+assert(segmentLengths(segments: segments) == [3, 0, 2, 1])
+assert(segmentLengths(segments: [Data(count: 100000)]) == [100000])
+
+assert(split(data: Data([1, 2, 3, 4, 5, 6, 7]), size: 3) == [Data([1, 2, 3]), Data([4, 5, 6]), Data([7])])
+assert(split(data: Data(), size: 3) == [])
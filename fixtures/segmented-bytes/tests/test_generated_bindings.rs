@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_segmented_bytes.py",
+    "tests/bindings/test_segmented_bytes.kts",
+    "tests/bindings/test_segmented_bytes.swift",
+);
@@ -0,0 +1,32 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The functions behind the self-test are generated by `setup_scaffolding!()`, from
+// `expose_self_test` in `uniffi.toml`.
+uniffi::setup_scaffolding!();
+
+#[cfg(test)]
+mod test {
+    use crate::uniffi_self_test::{uniffi_self_test_check, uniffi_self_test_values};
+
+    #[test]
+    fn test_self_test_check() {
+        let report = uniffi_self_test_check(uniffi_self_test_values());
+        assert!(report.passed);
+        assert!(report.failures.is_empty());
+
+        let mut values = uniffi_self_test_values();
+        values.uint32 = 1;
+        values.float64 = -0.1;
+        let report = uniffi_self_test_check(values);
+        assert!(!report.passed);
+        assert_eq!(
+            report.failures,
+            [
+                "uint32: Rust received 1, expected 4294967295",
+                "float64: Rust received -0.1, expected 0.1",
+            ]
+        );
+    }
+}
@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_self_test.py",
+    "tests/bindings/test_self_test.kts",
+    "tests/bindings/test_self_test.swift",
+);
@@ -47,4 +47,13 @@ fn set_contains(set: Arc<MyHashSet>, value: String) -> bool {
 #[uniffi::export]
 fn dummy(_arg: Option<i8>) {}
 
+// These are grouped under the `SimpleMath` namespace object in the UDL.
+fn add_u32(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+fn double_u32(value: u32) -> u32 {
+    value * 2
+}
+
 uniffi::include_scaffolding!("simple-fns");
@@ -15,3 +15,6 @@ addToSet(aSet, "bar")
 assert(setContains(aSet, "foo"))
 assert(setContains(aSet, "bar"))
 assert(!setContains(aSet, "baz"))
+
+assert(SimpleMath.addU32(1U, 2U) == 3U)
+assert(SimpleMath.doubleU32(21U) == 42U)
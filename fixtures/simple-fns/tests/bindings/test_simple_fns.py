@@ -2,9 +2,21 @@
 # License, v. 2.0. If a copy of the MPL was not distributed with this
 # file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+import uniffi_simple_fns
 from uniffi_simple_fns import *
 
+# The bindings are generated with `lazy_load`, so importing them doesn't load the library.
+assert uniffi_simple_fns._UniffiLib._uniffi_lib is None
+loads = []
+load_indirect = uniffi_simple_fns._uniffi_load_indirect
+def counting_load_indirect():
+    loads.append(None)
+    return load_indirect()
+uniffi_simple_fns._uniffi_load_indirect = counting_load_indirect
+
 assert get_string() == "String created by Rust"
+# The first call loaded it, and later calls use the same library.
+assert len(loads) == 1
 assert get_int() == 1289
 assert string_identity("String created by Python") == "String created by Python"
 assert byte_to_u32(255) == 255
@@ -15,3 +27,21 @@ add_to_set(a_set, "bar")
 assert set_contains(a_set, "foo")
 assert set_contains(a_set, "bar")
 assert not set_contains(a_set, "baz")
+
+assert SimpleMath.add_u32(1, 2) == 3
+assert SimpleMath.double_u32(21) == 42
+# Functions grouped under a namespace object aren't exposed at the top level.
+assert "add_u32" not in globals()
+
+assert len(loads) == 1
+
+# Errors loading the library are raised by the first call.
+def failing_load_indirect():
+    raise OSError("library not found")
+uniffi_simple_fns._uniffi_load_indirect = failing_load_indirect
+try:
+    uniffi_simple_fns._UniffiLazyLib().uniffi_ffi_function
+    raise AssertionError("Loading the library should have failed")
+except OSError as e:
+    assert str(e) == "library not found"
+uniffi_simple_fns._uniffi_load_indirect = load_indirect
@@ -15,3 +15,6 @@ addToSet(set: aSet, value: "bar")
 assert(setContains(set: aSet, value: "foo"))
 assert(setContains(set: aSet, value: "bar"))
 assert(!setContains(set: aSet, value: "baz"))
+
+assert(SimpleMath.addU32(a: 1, b: 2) == 3)
+assert(SimpleMath.doubleU32(value: 21) == 42)
@@ -0,0 +1,62 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+#[derive(uniffi::Enum)]
+pub enum TravelMode {
+    Walking,
+    Cycling,
+}
+
+#[derive(uniffi::Record)]
+pub struct Waypoint {
+    latitude: f64,
+    longitude: f64,
+    label: Option<String>,
+}
+
+#[derive(uniffi::Record)]
+pub struct Route {
+    name: String,
+    mode: TravelMode,
+    waypoints: Vec<Waypoint>,
+    destination: Option<Waypoint>,
+}
+
+// Objects can't be encoded, so neither can records which hold them.
+#[derive(uniffi::Object)]
+pub struct Navigator;
+
+#[derive(uniffi::Record)]
+pub struct Trip {
+    route: Route,
+    navigator: Arc<Navigator>,
+}
+
+#[uniffi::export]
+fn reverse_route(route: Route) -> Route {
+    let mut waypoints = route.waypoints;
+    waypoints.reverse();
+    Route {
+        name: format!("{} (reversed)", route.name),
+        destination: waypoints.last().map(|w| Waypoint {
+            latitude: w.latitude,
+            longitude: w.longitude,
+            label: w.label.clone(),
+        }),
+        waypoints,
+        ..route
+    }
+}
+
+#[uniffi::export]
+fn start_trip(route: Route) -> Trip {
+    Trip {
+        route,
+        navigator: Arc::new(Navigator),
+    }
+}
+
+uniffi::setup_scaffolding!();
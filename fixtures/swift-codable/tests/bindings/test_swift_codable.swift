@@ -0,0 +1,37 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import Foundation
+import uniffi_swift_codable
+
+let route = Route(
+    name: "Coast",
+    mode: .cycling,
+    waypoints: [
+        Waypoint(latitude: 51.5, longitude: -0.1, label: "Start"),
+        Waypoint(latitude: 50.8, longitude: -0.1, label: nil),
+    ],
+    destination: nil
+)
+
+// Records round-trip through JSON, including optional fields and nested records.
+let data = try! JSONEncoder().encode(route)
+let decoded = try! JSONDecoder().decode(Route.self, from: data)
+assert(decoded == route)
+
+// Records returned by Rust can be encoded too.
+let reversed = reverseRoute(route: route)
+let reversedData = try! JSONEncoder().encode(reversed)
+assert(try! JSONDecoder().decode(Route.self, from: reversedData) == reversed)
+assert(reversed.destination?.label == "Start")
+
+// Enums without fields are encoded on their own.
+let modes = try! JSONDecoder().decode(
+    [TravelMode].self,
+    from: try! JSONEncoder().encode([TravelMode.walking, TravelMode.cycling])
+)
+assert(modes == [.walking, .cycling])
+
+// Records holding objects aren't `Codable`, but are otherwise usable.
+assert(!(startTrip(route: route) as Any is Encodable))
@@ -0,0 +1,45 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum DrawError {
+    #[error("Nothing to draw")]
+    NoFrames,
+}
+
+pub struct Context {
+    name: String,
+    frames: AtomicU32,
+}
+
+impl Context {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            frames: AtomicU32::new(0),
+        }
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn draw(&self, frames: u32) -> Result<u32, DrawError> {
+        if frames == 0 {
+            return Err(DrawError::NoFrames);
+        }
+        Ok(self.frames.fetch_add(frames, Ordering::Relaxed) + frames)
+    }
+
+    fn same(self: Arc<Self>) -> Arc<Self> {
+        self
+    }
+}
+
+uniffi::include_scaffolding!("thread_bound");
@@ -0,0 +1,47 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.threadbound.*
+import kotlin.concurrent.thread
+
+fun <T> runOnOtherThread(block: () -> T): Result<T> {
+    var result: Result<T>? = null
+    thread { result = runCatching(block) }.join()
+    return result!!
+}
+
+val context = Context("main")
+assert(context.name() == "main")
+assert(context.draw(2u) == 2u)
+
+val err = runOnOtherThread { context.name() }.exceptionOrNull()
+assert(err is WrongThreadException)
+assert(err!!.message!!.contains("`Context.name` called on thread"))
+assert(runOnOtherThread { context.draw(1u) }.exceptionOrNull() is WrongThreadException)
+
+// The calls which failed didn't draw anything, and errors are still thrown as usual.
+assert(context.draw(1u) == 3u)
+try {
+    context.draw(0u)
+    throw RuntimeException("Should have thrown")
+} catch (e: DrawException.NoFrames) {
+    // It's okay!
+}
+
+// Objects constructed on another thread are bound to that thread instead.
+val other = runOnOtherThread { Context("other") }.getOrThrow()
+try {
+    other.name()
+    throw RuntimeException("Should have thrown")
+} catch (e: WrongThreadException) {
+    // It's okay!
+}
+
+// Only constructors can pass the context to Kotlin, since only they know which thread it's bound to.
+try {
+    context.same()
+    throw RuntimeException("Should have thrown")
+} catch (e: InternalException) {
+    assert(e.message!!.contains("`Context` is thread-bound"))
+}
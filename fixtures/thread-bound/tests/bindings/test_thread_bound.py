@@ -0,0 +1,53 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import threading
+
+from thread_bound import *
+
+def run_on_other_thread(fn):
+    result = []
+    def run():
+        try:
+            result.append(fn())
+        except Exception as e:
+            result.append(e)
+    thread = threading.Thread(target=run)
+    thread.start()
+    thread.join()
+    return result[0]
+
+context = Context("main")
+assert context.name() == "main"
+assert context.draw(2) == 2
+
+err = run_on_other_thread(context.name)
+assert isinstance(err, WrongThreadError), err
+assert "`Context.name` called on thread" in str(err), str(err)
+err = run_on_other_thread(lambda: context.draw(1))
+assert isinstance(err, WrongThreadError), err
+
+# The calls which failed didn't draw anything, and errors are still raised as usual.
+assert context.draw(1) == 3
+try:
+    context.draw(0)
+    raise Exception("Should have raised")
+except DrawError.NoFrames:
+    pass
+
+# Objects constructed on another thread are bound to that thread instead.
+other = run_on_other_thread(lambda: Context("other"))
+assert isinstance(other, Context), other
+try:
+    other.name()
+    raise Exception("Should have raised")
+except WrongThreadError:
+    pass
+
+# Only constructors can pass the context to Python, since only they know which thread it's bound to.
+try:
+    context.same()
+    raise Exception("Should have raised")
+except InternalError as e:
+    assert "`Context` is thread-bound" in str(e), str(e)
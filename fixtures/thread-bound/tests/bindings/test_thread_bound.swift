@@ -0,0 +1,51 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import Foundation
+import thread_bound
+
+func runOnOtherThread<T>(_ block: @escaping () -> T) -> T {
+    var result: T?
+    let done = DispatchSemaphore(value: 0)
+    Thread {
+        result = block()
+        done.signal()
+    }.start()
+    done.wait()
+    return result!
+}
+
+let context = Context(name: "main")
+assert(context.name() == "main")
+assert(try! context.draw(frames: 2) == 2)
+
+// Only methods which throw can report the error, others crash like they do for a panic.
+let err = runOnOtherThread { () -> Error? in
+    do {
+        _ = try context.draw(frames: 1)
+        return nil
+    } catch {
+        return error
+    }
+}
+assert(err is WrongThreadError)
+assert((err as! WrongThreadError).message.contains("`Context.draw` called on thread"))
+
+// The call which failed didn't draw anything, and errors are still thrown as usual.
+assert(try! context.draw(frames: 1) == 3)
+do {
+    _ = try context.draw(frames: 0)
+    fatalError("Should have thrown")
+} catch DrawError.NoFrames {
+    // It's okay!
+}
+
+// Objects constructed on another thread are bound to that thread instead.
+let other = runOnOtherThread { Context(name: "other") }
+do {
+    _ = try other.draw(frames: 1)
+    fatalError("Should have thrown")
+} catch is WrongThreadError {
+    // It's okay!
+}
@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_thread_bound.py",
+    "tests/bindings/test_thread_bound.kts",
+    "tests/bindings/test_thread_bound.swift",
+);
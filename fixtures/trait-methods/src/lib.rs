@@ -21,6 +21,20 @@ impl std::fmt::Display for TraitMethods {
     }
 }
 
+pub struct Stringified {
+    val: String,
+}
+
+impl Stringified {
+    fn new(val: String) -> Self {
+        Self { val }
+    }
+
+    fn describe(&self) -> String {
+        format!("Stringified({})", self.val)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, uniffi::Object)]
 #[uniffi::export(Debug, Display, Eq, Hash)]
 pub struct ProcTraitMethods {
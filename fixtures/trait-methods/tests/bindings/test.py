@@ -29,6 +29,12 @@ class TestTraitMethods(unittest.TestCase):
         d[m] = "m"
         self.assertTrue(m in d)
 
+class TestStringifier(unittest.TestCase):
+    def test_str(self):
+        m = Stringified("yo")
+        self.assertEqual(str(m), "Stringified(yo)")
+        self.assertEqual(m.describe(), "Stringified(yo)")
+
 class TestProcmacroTraitMethods(unittest.TestCase):
     def test_str(self):
         m = ProcTraitMethods("yo")
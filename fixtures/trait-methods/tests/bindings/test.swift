@@ -10,3 +10,8 @@ assert(m == TraitMethods(name: "yo"))
 // hash
 var set: Set = [TraitMethods(name: "yo")]
 assert(set.contains(TraitMethods(name: "yo")))
+
+// stringifier
+let s = Stringified(name: "yo")
+assert(String(describing: s) == "Stringified(yo)")
+assert(s.describe() == "Stringified(yo)")
@@ -45,4 +45,15 @@ fn take_bytes(v: Vec<u8>) -> Vec<u8> {
     v
 }
 
+pub struct Widths {
+    narrow_signed: i8,
+    narrow_unsigned: u16,
+    wide_signed: i32,
+    wide_unsigned: u64,
+}
+
+fn take_widths(v: Widths) -> Widths {
+    v
+}
+
 uniffi::include_scaffolding!("type-limits");
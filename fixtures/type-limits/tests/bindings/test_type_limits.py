@@ -5,6 +5,7 @@
 from uniffi_type_limits import *
 
 import math
+import typing
 import unittest
 
 class TestTypeLimits(unittest.TestCase):
@@ -65,6 +66,33 @@ class TestTypeLimits(unittest.TestCase):
         self.assertEqual(take_u32(10**9), 10**9)
         self.assertEqual(take_u64(10**19), 10**19)
 
+    def test_record_fields(self):
+        widths = Widths(narrow_signed=-2**7, narrow_unsigned=2**16 - 1, wide_signed=2**31 - 1, wide_unsigned=0)
+        self.assertEqual(take_widths(widths), widths)
+        widths = Widths(narrow_signed=5, narrow_unsigned=500, wide_signed=-5, wide_unsigned=10**19)
+        self.assertEqual(take_widths(widths), widths)
+
+        for (field, value, message) in [
+            ("narrow_signed", 2**7, "i8 requires -128 <= value < 128"),
+            ("narrow_unsigned", -1, "u16 requires 0 <= value < 65536"),
+            ("wide_signed", -2**31 - 1, "i32 requires -2147483648 <= value < 2147483648"),
+            ("wide_unsigned", 2**64, "u64 requires 0 <= value < 18446744073709551616"),
+        ]:
+            fields = dict(narrow_signed=0, narrow_unsigned=0, wide_signed=0, wide_unsigned=0)
+            fields[field] = value
+            with self.assertRaises(ValueError) as cm:
+                take_widths(Widths(**fields))
+            self.assertEqual(str(cm.exception), message)
+
+    def test_annotations(self):
+        # The Rust type of an integer is recorded in its annotation.
+        hints = typing.get_type_hints(take_u8, include_extras=True)
+        self.assertEqual(hints["v"], typing.Annotated[int, 'u8'])
+        self.assertEqual(hints["return"], typing.Annotated[int, 'u8'])
+        hints = typing.get_type_hints(Widths, include_extras=True)
+        self.assertEqual(hints["narrow_signed"], typing.Annotated[int, 'i8'])
+        self.assertEqual(hints["wide_unsigned"], typing.Annotated[int, 'u64'])
+
     def test_non_integer(self):
         self.assertRaises(TypeError, lambda: take_i8(None))
         self.assertRaises(TypeError, lambda: take_i16(None))
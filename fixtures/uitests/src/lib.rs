@@ -4,11 +4,21 @@
 
 /// This crate only exists for its tests.
 
+/// An enum without fields, for tests which use it as an external type.
+#[derive(uniffi::Enum)]
+pub enum ExternalFieldlessEnum {
+    One,
+    Two,
+}
+
+uniffi::setup_scaffolding!();
+
 #[cfg(test)]
 mod test {
     #[test]
     fn trybuild_ui_tests() {
         let t = trybuild::TestCases::new();
         t.compile_fail("tests/ui/*.rs");
+        t.pass("tests/pass/*.rs");
     }
 }
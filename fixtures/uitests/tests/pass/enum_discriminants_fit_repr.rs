@@ -0,0 +1,18 @@
+fn main() { /* empty main required by `trybuild` */}
+
+#[derive(uniffi::Enum)]
+#[repr(u8)]
+pub enum Size {
+    Small = 1,
+    Medium,
+    Large = 255,
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+#[repr(i16)]
+pub enum SizeError {
+    #[error("too small")]
+    TooSmall = 32767,
+}
+
+uniffi_macros::setup_scaffolding!();
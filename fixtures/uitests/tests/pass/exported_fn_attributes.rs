@@ -0,0 +1,44 @@
+fn main() { /* empty main required by `trybuild` */}
+
+// Attributes which aren't UniFFI's are kept on the exported items, and don't stop them being
+// exported.
+
+/// Adds two numbers.
+#[inline]
+#[doc(alias = "sum")]
+#[uniffi::export]
+pub fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[uniffi::export]
+#[inline(always)]
+#[must_use]
+#[cfg_attr(debug_assertions, allow(clippy::needless_pass_by_value))]
+#[doc = "Multiplies two numbers."]
+pub fn multiply(a: u32, b: u32) -> u32 {
+    a * b
+}
+
+#[derive(uniffi::Object)]
+pub struct Calculator;
+
+#[uniffi::export]
+impl Calculator {
+    #[uniffi::constructor]
+    #[inline]
+    #[doc(hidden)]
+    pub fn new() -> Self {
+        Calculator
+    }
+
+    /// The value of the last calculation.
+    #[inline]
+    #[allow(clippy::unused_self)]
+    #[cfg_attr(debug_assertions, must_use)]
+    pub fn last(&self) -> u32 {
+        0
+    }
+}
+
+uniffi_macros::setup_scaffolding!();
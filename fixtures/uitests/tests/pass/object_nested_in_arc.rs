@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn main() { /* empty main required by `trybuild` */}
+
+#[derive(uniffi::Object)]
+pub struct MyObject;
+
+// Objects can be returned directly, either by value or wrapped in an `Arc`.
+#[uniffi::export]
+pub fn get_object() -> MyObject {
+    MyObject
+}
+
+#[uniffi::export]
+pub fn get_arc_object() -> Arc<MyObject> {
+    Arc::new(MyObject)
+}
+
+// When nested inside another type, objects must be wrapped in an `Arc`.
+#[uniffi::export]
+pub fn get_objects() -> Vec<Arc<MyObject>> {
+    vec![Arc::new(MyObject)]
+}
+
+#[uniffi::export]
+pub fn get_optional_object() -> Option<Arc<MyObject>> {
+    None
+}
+
+#[uniffi::export]
+pub fn get_object_map() -> HashMap<String, Arc<MyObject>> {
+    HashMap::new()
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+fn main() { /* empty main required by `trybuild` */}
+
+// Methods can borrow their arguments, with either explicit or elided lifetimes.
+#[uniffi::export]
+pub trait Greeter: Send + Sync {
+    fn greet<'a>(&self, name: &'a str) -> String;
+    fn greet_all(&self, names: &[String]) -> String;
+    fn greet_twice<'a, 'b: 'a>(&self, first: &'a str, second: &'b str) -> String {
+        format!("{} {}", self.greet(first), self.greet(second))
+    }
+}
+
+#[uniffi::export(callback_interface)]
+pub trait Listener: Send + Sync {
+    fn on_event<'a>(&self, name: &'a str, payload: &[u8]);
+}
+
+#[uniffi::export]
+pub fn make_greeter() -> Arc<dyn Greeter> {
+    unimplemented!()
+}
+
+#[uniffi::export]
+pub fn notify<'a>(listener: Box<dyn Listener>, name: &'a str) {
+    listener.on_event(name, &[])
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,18 @@
+fn main() { /* empty main required by `trybuild` */}
+
+#[uniffi::export]
+pub trait Named: Send + Sync {
+    fn name(&self) -> String;
+}
+
+// `Send + Sync` can also come from a supertrait.
+pub trait ThreadSafe: Send + Sync {}
+
+impl<T: ?Sized + Send + Sync> ThreadSafe for T {}
+
+#[uniffi::export]
+pub trait Counted: ThreadSafe {
+    fn count(&self) -> u32;
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,19 @@
+fn main() { /* empty main required by `trybuild` */}
+
+// `Large` follows `Largest`, so its discriminant is 256, which doesn't fit in a `u8`.
+#[derive(uniffi::Enum)]
+#[repr(u8)]
+pub enum Size {
+    Small = 1,
+    Largest = 255,
+    Large,
+}
+
+#[derive(uniffi::Enum)]
+#[repr(i8)]
+pub enum Temperature {
+    Freezing = 0,
+    Boiling = 212,
+}
+
+uniffi_macros::setup_scaffolding!();
@@ -0,0 +1,9 @@
+fn main() { /* empty main required by `trybuild` */}
+
+// Only lifetime parameters are supported, since the scaffolding can't pick a type for `T`.
+#[uniffi::export]
+pub trait Store: Send + Sync {
+    fn put<T: Into<String>>(&self, value: T);
+}
+
+uniffi_macros::setup_scaffolding!();
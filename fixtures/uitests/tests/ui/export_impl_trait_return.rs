@@ -0,0 +1,16 @@
+fn main() { /* empty main required by `trybuild` */}
+
+#[derive(uniffi::Object)]
+pub struct Catalog {
+    names: Vec<String>,
+}
+
+// Iterators can't be lowered, so they need to be collected with `#[uniffi::collect]`.
+#[uniffi::export]
+impl Catalog {
+    fn names(&self) -> impl Iterator<Item = String> + '_ {
+        self.names.iter().cloned()
+    }
+}
+
+uniffi_macros::setup_scaffolding!();
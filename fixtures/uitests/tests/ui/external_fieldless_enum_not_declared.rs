@@ -0,0 +1,10 @@
+use uniffi_uitests::ExternalFieldlessEnum;
+
+// Unfortunately, path is relative to a temporary build directory :-/
+uniffi_macros::generate_and_include_scaffolding!("../../../../fixtures/uitests/src/external_enum.udl");
+
+fn main() { /* empty main required by `trybuild` */}
+
+fn get_enum(value: ExternalFieldlessEnum) -> ExternalFieldlessEnum {
+    value
+}
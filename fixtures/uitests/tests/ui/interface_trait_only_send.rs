@@ -0,0 +1,9 @@
+fn main() { /* empty main required by `trybuild` */}
+
+// This will fail to compile, because the trait is only `Send`, so the trait object isn't `Sync`.
+#[uniffi::export]
+pub trait OnlySend: Send {
+    fn name(&self) -> String;
+}
+
+uniffi_macros::setup_scaffolding!();
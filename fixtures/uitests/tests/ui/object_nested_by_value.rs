@@ -0,0 +1,28 @@
+fn main() { /* empty main required by `trybuild` */}
+
+#[derive(uniffi::Object)]
+pub struct MyObject;
+
+#[derive(uniffi::Record)]
+pub struct MyRecord {
+    value: u32,
+}
+
+// Records can be nested by value
+#[uniffi::export]
+pub fn get_records() -> Vec<MyRecord> {
+    vec![]
+}
+
+// Objects can be returned directly by value, but when nested they must be wrapped in an `Arc`.
+#[uniffi::export]
+pub fn get_objects() -> Vec<MyObject> {
+    vec![]
+}
+
+#[uniffi::export]
+pub fn get_optional_object() -> Option<MyObject> {
+    None
+}
+
+uniffi_macros::setup_scaffolding!();
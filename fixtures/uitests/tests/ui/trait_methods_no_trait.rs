@@ -12,3 +12,15 @@ impl TraitMethods {
         unreachable!();
     }
 }
+
+pub struct Stringified {}
+
+impl Stringified {
+    fn new(name: String) -> Self {
+        unreachable!();
+    }
+
+    fn describe(&self) -> String {
+        unreachable!();
+    }
+}
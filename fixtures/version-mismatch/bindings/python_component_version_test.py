@@ -0,0 +1,10 @@
+# The bindings check the component version when they're loaded, so importing them should fail
+# with an error naming both versions.
+try:
+    import fixture_version_mismatch
+except Exception as e:
+    assert str(e) == 'UniFFI component version mismatch: the bindings are version "2.0.0" but the library is version "1.0.0"', str(e)
+else:
+    raise AssertionError("Loading the bindings should have failed")
+
+print("Script completed successfully")
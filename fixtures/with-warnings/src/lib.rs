@@ -0,0 +1,38 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use uniffi::WithWarnings;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseError {
+    #[error("The input isn't a number")]
+    InvalidNumber,
+}
+
+fn parse_count(input: String) -> Result<WithWarnings<u32>, ParseError> {
+    let mut warnings = Vec::new();
+    let trimmed = input.trim();
+    if trimmed.len() != input.len() {
+        warnings.push("Ignored surrounding whitespace".to_string());
+    }
+    if trimmed.len() > 1 && trimmed.starts_with('0') {
+        warnings.push("Ignored leading zeros".to_string());
+    }
+    let value = trimmed.parse().map_err(|_| ParseError::InvalidNumber)?;
+    Ok(WithWarnings { value, warnings })
+}
+
+fn split_words(input: String) -> WithWarnings<Vec<String>> {
+    let mut result = WithWarnings::new(Vec::new());
+    for (index, word) in input.split(' ').enumerate() {
+        if word.is_empty() {
+            result = result.warn(format!("Skipped an empty word at {index}"));
+        } else {
+            result.value.push(word.to_string());
+        }
+    }
+    result
+}
+
+uniffi::include_scaffolding!("with_warnings");
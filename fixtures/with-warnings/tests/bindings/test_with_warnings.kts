@@ -0,0 +1,22 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.withwarnings.*
+
+val count = parseCount(" 042")
+assert(count.value == 42u)
+assert(count.warnings == listOf("Ignored surrounding whitespace", "Ignored leading zeros"))
+
+assert(parseCount("7") == WithWarnings(7u, listOf()))
+
+try {
+    parseCount("seven")
+    throw RuntimeException("Should have thrown")
+} catch (e: ParseException.InvalidNumber) {
+    // It's okay!
+}
+
+val words = splitWords("one  two ")
+assert(words.value == listOf("one", "two"))
+assert(words.warnings == listOf("Skipped an empty word at 1", "Skipped an empty word at 3"))
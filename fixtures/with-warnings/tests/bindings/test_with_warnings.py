@@ -0,0 +1,21 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from with_warnings import *
+
+result = parse_count(" 042")
+assert result.value == 42
+assert result.warnings == ["Ignored surrounding whitespace", "Ignored leading zeros"]
+
+assert parse_count("7") == WithWarnings(7, [])
+
+try:
+    parse_count("seven")
+    raise Exception("Should have raised")
+except ParseError.InvalidNumber:
+    pass
+
+result = split_words("one  two ")
+assert result.value == ["one", "two"]
+assert result.warnings == ["Skipped an empty word at 1", "Skipped an empty word at 3"]
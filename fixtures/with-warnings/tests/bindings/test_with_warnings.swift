@@ -0,0 +1,22 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import with_warnings
+
+let count = try! parseCount(input: " 042")
+assert(count.value == 42)
+assert(count.warnings == ["Ignored surrounding whitespace", "Ignored leading zeros"])
+
+assert(try! parseCount(input: "7") == WithWarnings(value: 7, warnings: []))
+
+do {
+    _ = try parseCount(input: "seven")
+    fatalError("Should have thrown")
+} catch ParseError.InvalidNumber {
+    // It's okay!
+}
+
+let words = splitWords(input: "one  two ")
+assert(words.value == ["one", "two"])
+assert(words.warnings == ["Skipped an empty word at 1", "Skipped an empty word at 3"])
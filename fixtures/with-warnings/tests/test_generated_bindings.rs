@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_with_warnings.py",
+    "tests/bindings/test_with_warnings.kts",
+    "tests/bindings/test_with_warnings.swift",
+);
@@ -53,7 +53,22 @@ enum Commands {
         #[clap(long = "crate")]
         crate_name: Option<String>,
 
-        /// Path to the UDL file, or cdylib if `library-mode` is specified
+        /// Write the parsed interface to a `.uniffi.json` metadata file rather than generating
+        /// bindings.  The bindings can then be generated from that file with `--metadata`.
+        #[clap(long)]
+        metadata_only: bool,
+
+        /// Pass in a metadata file written by `--metadata-only` rather than a UDL file
+        #[clap(long = "metadata")]
+        metadata_mode: bool,
+
+        /// Print a report listing each exported item with the Rust module it came from, rather
+        /// than generating bindings.
+        #[clap(long)]
+        coverage: bool,
+
+        /// Path to the UDL file, or cdylib if `library-mode` is specified, or metadata file if
+        /// `metadata-mode` is specified
         source: Utf8PathBuf,
     },
 
@@ -71,6 +86,16 @@ enum Commands {
         udl_file: Utf8PathBuf,
     },
 
+    /// Check a UDL file for likely mistakes and style problems
+    Lint {
+        /// Use this as the crate name instead of attempting to locate and parse Cargo.toml.
+        #[clap(long = "crate")]
+        crate_name: Option<String>,
+
+        /// Path to the UDL file.
+        udl_file: Utf8PathBuf,
+    },
+
     /// Print a debug representation of the interface from a dynamic library
     PrintRepr {
         /// Path to the library file (.so, .dll, .dylib, or .a)
@@ -90,8 +115,41 @@ pub fn run_main() -> anyhow::Result<()> {
             source,
             crate_name,
             library_mode,
+            metadata_only,
+            metadata_mode,
+            coverage,
         } => {
-            if library_mode {
+            if coverage {
+                if library_mode || metadata_mode || metadata_only {
+                    panic!("--coverage is not compatible with --library, --metadata or --metadata-only.")
+                }
+                uniffi_bindgen::coverage_report::print_coverage_report(
+                    &source,
+                    lib_file.as_deref(),
+                    crate_name.as_deref(),
+                )?;
+            } else if metadata_only {
+                if library_mode || metadata_mode {
+                    panic!("--metadata-only is not compatible with --library or --metadata.")
+                }
+                uniffi_bindgen::metadata_artifact::write_metadata_artifact(
+                    &source,
+                    out_dir.as_deref(),
+                    lib_file.as_deref(),
+                    crate_name.as_deref(),
+                )?;
+            } else if metadata_mode {
+                if library_mode || lib_file.is_some() || crate_name.is_some() {
+                    panic!("--library, --lib-file and --crate are not compatible with --metadata.")
+                }
+                uniffi_bindgen::metadata_artifact::generate_bindings_from_artifact(
+                    &source,
+                    config.as_deref(),
+                    language,
+                    out_dir.as_deref(),
+                    !no_format,
+                )?;
+            } else if library_mode {
                 if lib_file.is_some() {
                     panic!("--lib-file is not compatible with --library.")
                 }
@@ -130,6 +188,12 @@ pub fn run_main() -> anyhow::Result<()> {
                 !no_format,
             )?;
         }
+        Commands::Lint {
+            crate_name,
+            udl_file,
+        } => {
+            uniffi_bindgen::lint::print_lint_report(&udl_file, crate_name.as_deref())?;
+        }
         Commands::PrintRepr { path } => {
             uniffi_bindgen::print_repr(&path)?;
         }
@@ -17,8 +17,9 @@ pub use uniffi_bindgen::bindings::ruby::run_test as ruby_run_test;
 pub use uniffi_bindgen::bindings::swift::run_test as swift_run_test;
 #[cfg(feature = "bindgen")]
 pub use uniffi_bindgen::{
-    bindings::TargetLanguage, generate_bindings, generate_component_scaffolding,
-    generate_component_scaffolding_for_crate, print_repr,
+    bindings::TargetLanguage, generate_bindings, generate_bindings_from_library,
+    generate_component_scaffolding, generate_component_scaffolding_for_crate, generate_to_memory,
+    print_repr,
 };
 #[cfg(feature = "build")]
 pub use uniffi_build::{generate_scaffolding, generate_scaffolding_for_crate};
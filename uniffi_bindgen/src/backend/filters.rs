@@ -71,6 +71,6 @@ pub fn get_callback_interface_definition<'a>(
 }
 
 /// Get the FfiType for a Type
-pub fn ffi_type(type_: &impl AsType) -> Result<FfiType, askama::Error> {
-    Ok(type_.as_type().into())
+pub fn ffi_type(type_: &impl AsType, ci: &ComponentInterface) -> Result<FfiType, askama::Error> {
+    Ok(ci.ffi_type(&type_.as_type()))
 }
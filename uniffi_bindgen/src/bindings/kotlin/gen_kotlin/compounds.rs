@@ -2,24 +2,31 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use super::{AsCodeType, CodeType};
+use super::{AsCodeType, CodeType, Config};
 use crate::backend::{Literal, Type};
 use crate::ComponentInterface;
 use paste::paste;
 
-fn render_literal(literal: &Literal, inner: &Type, ci: &ComponentInterface) -> String {
+fn render_literal(
+    literal: &Literal,
+    inner: &Type,
+    ci: &ComponentInterface,
+    config: &Config,
+) -> String {
     match literal {
         Literal::Null => "null".into(),
         Literal::EmptySequence => "listOf()".into(),
         Literal::EmptyMap => "mapOf()".into(),
 
         // For optionals
-        _ => super::KotlinCodeOracle.find(inner).literal(literal, ci),
+        _ => super::KotlinCodeOracle
+            .find(inner)
+            .literal(literal, ci, config),
     }
 }
 
 macro_rules! impl_code_type_for_compound {
-     ($T:ty, $type_label_pattern:literal, $canonical_name_pattern: literal) => {
+     ($T:ty, $variant:ident, $type_label_pattern:literal, $canonical_name_pattern: literal) => {
         paste! {
             #[derive(Debug)]
             pub struct $T {
@@ -36,24 +43,29 @@ macro_rules! impl_code_type_for_compound {
             }
 
             impl CodeType for $T  {
-                fn type_label(&self, ci: &ComponentInterface) -> String {
-                    format!($type_label_pattern, super::KotlinCodeOracle.find(self.inner()).type_label(ci))
+                fn type_label(&self, ci: &ComponentInterface, config: &Config) -> String {
+                    // Shared types are declared once as a `typealias`.
+                    let type_ = Type::$variant { inner_type: Box::new(self.inner.clone()) };
+                    if ci.is_shared_composite_type(&type_) {
+                        return self.canonical_name();
+                    }
+                    format!($type_label_pattern, super::KotlinCodeOracle.find(self.inner()).type_label(ci, config))
                 }
 
                 fn canonical_name(&self) -> String {
                     format!($canonical_name_pattern, super::KotlinCodeOracle.find(self.inner()).canonical_name())
                 }
 
-                fn literal(&self, literal: &Literal, ci: &ComponentInterface) -> String {
-                    render_literal(literal, self.inner(), ci)
+                fn literal(&self, literal: &Literal, ci: &ComponentInterface, config: &Config) -> String {
+                    render_literal(literal, self.inner(), ci, config)
                 }
             }
         }
     }
  }
 
-impl_code_type_for_compound!(OptionalCodeType, "{}?", "Optional{}");
-impl_code_type_for_compound!(SequenceCodeType, "List<{}>", "Sequence{}");
+impl_code_type_for_compound!(OptionalCodeType, Optional, "{}?", "Optional{}");
+impl_code_type_for_compound!(SequenceCodeType, Sequence, "List<{}>", "Sequence{}");
 
 #[derive(Debug)]
 pub struct MapCodeType {
@@ -76,11 +88,23 @@ impl MapCodeType {
 }
 
 impl CodeType for MapCodeType {
-    fn type_label(&self, ci: &ComponentInterface) -> String {
+    fn type_label(&self, ci: &ComponentInterface, config: &Config) -> String {
+        // Shared types are declared once as a `typealias`.
+        let type_ = Type::Map {
+            key_type: Box::new(self.key.clone()),
+            value_type: Box::new(self.value.clone()),
+        };
+        if ci.is_shared_composite_type(&type_) {
+            return self.canonical_name();
+        }
         format!(
             "Map<{}, {}>",
-            super::KotlinCodeOracle.find(self.key()).type_label(ci),
-            super::KotlinCodeOracle.find(self.value()).type_label(ci),
+            super::KotlinCodeOracle
+                .find(self.key())
+                .type_label(ci, config),
+            super::KotlinCodeOracle
+                .find(self.value())
+                .type_label(ci, config),
         )
     }
 
@@ -92,7 +116,36 @@ impl CodeType for MapCodeType {
         )
     }
 
-    fn literal(&self, literal: &Literal, ci: &ComponentInterface) -> String {
-        render_literal(literal, &self.value, ci)
+    fn literal(&self, literal: &Literal, ci: &ComponentInterface, config: &Config) -> String {
+        render_literal(literal, &self.value, ci, config)
+    }
+}
+
+#[derive(Debug)]
+pub struct NonZeroCodeType {
+    inner: Type,
+}
+
+impl NonZeroCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for NonZeroCodeType {
+    fn type_label(&self, _ci: &ComponentInterface, _config: &Config) -> String {
+        self.canonical_name()
+    }
+
+    fn canonical_name(&self) -> String {
+        format!("NonZero{}", self.inner.as_codetype().canonical_name())
+    }
+
+    fn literal(&self, literal: &Literal, ci: &ComponentInterface, config: &Config) -> String {
+        format!(
+            "{}({})",
+            self.type_label(ci, config),
+            self.inner.as_codetype().literal(literal, ci, config)
+        )
     }
 }
@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use super::CodeType;
+use super::{CodeType, Config};
 use crate::ComponentInterface;
 
 #[derive(Debug)]
@@ -17,7 +17,7 @@ impl CustomCodeType {
 }
 
 impl CodeType for CustomCodeType {
-    fn type_label(&self, _ci: &ComponentInterface) -> String {
+    fn type_label(&self, _ci: &ComponentInterface, _config: &Config) -> String {
         self.name.clone()
     }
 
@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use super::CodeType;
+use super::{CodeType, Config};
 use crate::backend::Literal;
 use crate::ComponentInterface;
 
@@ -18,7 +18,7 @@ impl EnumCodeType {
 }
 
 impl CodeType for EnumCodeType {
-    fn type_label(&self, ci: &ComponentInterface) -> String {
+    fn type_label(&self, ci: &ComponentInterface, _config: &Config) -> String {
         super::KotlinCodeOracle.class_name(ci, &self.id)
     }
 
@@ -26,13 +26,14 @@ impl CodeType for EnumCodeType {
         format!("Type{}", self.id)
     }
 
-    fn literal(&self, literal: &Literal, ci: &ComponentInterface) -> String {
+    fn literal(&self, literal: &Literal, ci: &ComponentInterface, config: &Config) -> String {
         if let Literal::Enum(v, _) = literal {
-            format!(
-                "{}.{}",
-                self.type_label(ci),
-                super::KotlinCodeOracle.enum_variant_name(v)
-            )
+            // Enums with an `Unknown` variant are sealed classes, with a class for each variant.
+            let variant_name = match ci.get_enum_definition(&self.id) {
+                Some(e) if e.has_unknown_variant() => super::KotlinCodeOracle.class_name(ci, v),
+                _ => super::KotlinCodeOracle.enum_variant_name(v),
+            };
+            format!("{}.{variant_name}", self.type_label(ci, config))
         } else {
             unreachable!();
         }
@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use super::CodeType;
+use super::{CodeType, Config};
 use crate::ComponentInterface;
 use paste::paste;
 
@@ -13,7 +13,7 @@ macro_rules! impl_code_type_for_miscellany {
             pub struct $T;
 
             impl CodeType for $T  {
-                fn type_label(&self, _ci: &ComponentInterface) -> String {
+                fn type_label(&self, _ci: &ComponentInterface, _config: &Config) -> String {
                     $class_name.into()
                 }
 
@@ -28,3 +28,23 @@ macro_rules! impl_code_type_for_miscellany {
 impl_code_type_for_miscellany!(TimestampCodeType, "java.time.Instant", "Timestamp");
 
 impl_code_type_for_miscellany!(DurationCodeType, "java.time.Duration", "Duration");
+
+impl_code_type_for_miscellany!(
+    DurationRangeCodeType,
+    "OpenEndRange<java.time.Duration>",
+    "DurationRange"
+);
+
+impl_code_type_for_miscellany!(
+    DurationRangeInclusiveCodeType,
+    "ClosedRange<java.time.Duration>",
+    "DurationRangeInclusive"
+);
+
+impl_code_type_for_miscellany!(PathCodeType, "java.io.File", "Path");
+
+impl_code_type_for_miscellany!(JsonCodeType, "String", "Json");
+
+impl_code_type_for_miscellany!(DynErrorCodeType, "DynException", "DynError");
+
+impl_code_type_for_miscellany!(OpaqueHandleCodeType, "OpaqueHandle", "OpaqueHandle");
@@ -7,7 +7,7 @@ use std::cell::RefCell;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::Debug;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use askama::Template;
 use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToUpperCamelCase};
 use serde::{Deserialize, Serialize};
@@ -30,7 +30,7 @@ mod variant;
 trait CodeType: Debug {
     /// The language specific label used to reference this type. This will be used in
     /// method signatures and property declarations.
-    fn type_label(&self, ci: &ComponentInterface) -> String;
+    fn type_label(&self, ci: &ComponentInterface, config: &Config) -> String;
 
     /// A representation of this type label that can be used as part of another
     /// identifier. e.g. `read_foo()`, or `FooInternals`.
@@ -39,8 +39,8 @@ trait CodeType: Debug {
     /// with this type only.
     fn canonical_name(&self) -> String;
 
-    fn literal(&self, _literal: &Literal, ci: &ComponentInterface) -> String {
-        unimplemented!("Unimplemented for {}", self.type_label(ci))
+    fn literal(&self, _literal: &Literal, ci: &ComponentInterface, config: &Config) -> String {
+        unimplemented!("Unimplemented for {}", self.type_label(ci, config))
     }
 
     /// Name of the FfiConverter
@@ -73,10 +73,59 @@ pub struct Config {
     package_name: Option<String>,
     cdylib_name: Option<String>,
     generate_immutable_records: Option<bool>,
+    derive_display: Option<bool>,
+    sealed_kind: Option<SealedKind>,
+    error_style: Option<ErrorStyle>,
+    generate_blocking_variants: Option<bool>,
+    java_interop: Option<bool>,
+    kmp: Option<bool>,
+    generate_test_stubs: Option<bool>,
+    file_header: Option<String>,
+    hash_header: Option<bool>,
+    ownership_docs: Option<bool>,
+    unsigned_style: Option<UnsignedStyle>,
+    #[serde(default)]
+    extra_imports: Vec<String>,
     #[serde(default)]
     custom_types: HashMap<String, CustomTypeConfig>,
     #[serde(default)]
     external_packages: HashMap<String, String>,
+    #[serde(default)]
+    targets: Vec<String>,
+}
+
+/// How enums with associated data are represented in Kotlin.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SealedKind {
+    /// A `sealed class`, with variants which extend it.
+    #[default]
+    Class,
+    /// A `sealed interface`, with variants which implement it.
+    Interface,
+}
+
+/// How top-level functions report the errors they throw.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorStyle {
+    /// Throw the error as an exception.
+    #[default]
+    Throw,
+    /// Return a `kotlin.Result`, with the error as its failure.
+    Result,
+}
+
+/// How Rust's unsigned integers are represented in Kotlin.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnsignedStyle {
+    /// Kotlin's unsigned types, like `UInt` and `ULong`.
+    #[default]
+    Native,
+    /// The signed type of the same size, like `Int` and `Long`, holding the same bits.  Values
+    /// above the signed type's maximum are negative, as in two's complement.
+    Signed,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -108,6 +157,79 @@ impl Config {
     pub fn generate_immutable_records(&self) -> bool {
         self.generate_immutable_records.unwrap_or(false)
     }
+
+    /// Whether to generate a `toString()` for records which lists their fields, summarizing
+    /// byte arrays rather than printing their contents.
+    pub fn derive_display(&self) -> bool {
+        self.derive_display.unwrap_or(false)
+    }
+
+    /// Whether to generate enums with associated data as a `sealed interface` rather than a
+    /// `sealed class`
+    pub fn generate_sealed_interfaces(&self) -> bool {
+        self.sealed_kind.unwrap_or_default() == SealedKind::Interface
+    }
+
+    /// Whether top-level functions which throw should return a `kotlin.Result` instead
+    pub fn result_errors(&self) -> bool {
+        self.error_style.unwrap_or_default() == ErrorStyle::Result
+    }
+
+    /// Whether to generate a `Blocking` variant of each async function, which calls it with
+    /// `runBlocking`
+    pub fn generate_blocking_variants(&self) -> bool {
+        self.generate_blocking_variants.unwrap_or(false)
+    }
+
+    /// Whether to also annotate constructors and interface methods with `@Throws`, so Java
+    /// callers see the checked exceptions they can throw.
+    pub fn java_interop(&self) -> bool {
+        self.java_interop.unwrap_or(false)
+    }
+
+    /// Whether to generate Kotlin Multiplatform bindings, with `expect` declarations in a common
+    /// source file and the `actual` implementations in a platform one.
+    pub fn kmp(&self) -> bool {
+        self.kmp.unwrap_or(false)
+    }
+
+    /// Whether to also generate a file of stub implementations of the callback interfaces, for
+    /// tests.
+    pub fn generate_test_stubs(&self) -> bool {
+        self.generate_test_stubs.unwrap_or(false)
+    }
+
+    /// Whether to generate a function for the given target variant, which it does for functions
+    /// without a target and for the targets listed in `targets`.
+    pub fn generates_target(&self, target: Option<&str>) -> bool {
+        match target {
+            None => true,
+            Some(target) => self.targets.iter().any(|t| t == target),
+        }
+    }
+
+    /// Text to insert verbatim at the top of each generated file, such as a license header.
+    pub fn file_header(&self) -> Option<&str> {
+        self.file_header.as_deref()
+    }
+
+    /// Whether to add a comment at the top of each generated file with the uniffi-bindgen version
+    /// and the interface's stable hash, so a checked-in copy can be checked against its source.
+    pub fn hash_header(&self) -> bool {
+        self.hash_header.unwrap_or(false)
+    }
+
+    /// Whether to document who owns the Rust object behind each object, its constructors and its
+    /// methods.
+    pub fn ownership_docs(&self) -> bool {
+        self.ownership_docs.unwrap_or(false)
+    }
+
+    /// Whether to represent unsigned integers by the signed types of the same size, rather than
+    /// Kotlin's experimental unsigned types.
+    pub fn unsigned_as_signed(&self) -> bool {
+        self.unsigned_style.unwrap_or_default() == UnsignedStyle::Signed
+    }
 }
 
 impl BindingsConfig for Config {
@@ -134,12 +256,83 @@ impl BindingsConfig for Config {
 }
 
 // Generate kotlin bindings for the given ComponentInterface, as a string.
+//
+// In KMP mode these are the `actual` implementations for the JVM and Android source sets.
 pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    if config.kmp() {
+        check_kmp_support(config, ci)?;
+    }
     KotlinWrapper::new(config.clone(), ci)
         .render()
         .context("failed to render kotlin bindings")
 }
 
+// Generate the `expect` declarations for the common source set of a Kotlin Multiplatform project.
+pub fn generate_common_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    check_kmp_support(config, ci)?;
+    KotlinCommonWrapper {
+        config: config.clone(),
+        ci,
+    }
+    .render()
+    .context("failed to render kotlin common bindings")
+}
+
+// Generate a stub implementation of each callback interface, for tests of the foreign code which
+// uses them.
+pub fn generate_test_stubs(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    KotlinTestStubs {
+        config: config.clone(),
+        ci,
+        imports: KotlinWrapper::new(config.clone(), ci).imports(),
+    }
+    .render()
+    .context("failed to render kotlin test stubs")
+}
+
+// Only records and builtin types can be declared in common code so far. Everything else either
+// needs platform types (like `java.time.Instant`) or has FFI plumbing mixed into its declaration.
+fn check_kmp_support(config: &Config, ci: &ComponentInterface) -> Result<()> {
+    for type_ in ci.iter_types() {
+        match type_ {
+            Type::Timestamp
+            | Type::Duration
+            | Type::DurationRange { .. }
+            | Type::Object { .. }
+            | Type::Enum { .. }
+            | Type::CallbackInterface { .. }
+            | Type::Custom { .. }
+            | Type::External { .. }
+            | Type::NonZero { .. }
+            | Type::OpaqueHandle => bail!(
+                "Kotlin Multiplatform bindings don't support `{}` yet",
+                KotlinCodeOracle.find(type_).type_label(ci, config)
+            ),
+            _ => {}
+        }
+    }
+    if !ci.constant_definitions().is_empty() || !ci.namespace_objects().is_empty() {
+        bail!("Kotlin Multiplatform bindings don't support constants or namespace objects yet");
+    }
+    Ok(())
+}
+
+// The imports which `wrapper.kt` always needs, for its helper code.
+const BUILTIN_IMPORTS: &[&str] = &[
+    "com.sun.jna.Library",
+    "com.sun.jna.IntegerType",
+    "com.sun.jna.Native",
+    "com.sun.jna.Pointer",
+    "com.sun.jna.Structure",
+    "com.sun.jna.Callback",
+    "com.sun.jna.ptr.*",
+    "java.nio.ByteBuffer",
+    "java.nio.ByteOrder",
+    "java.nio.CharBuffer",
+    "java.nio.charset.CodingErrorAction",
+    "java.util.concurrent.ConcurrentHashMap",
+];
+
 /// A struct to record a Kotlin import statement.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum ImportRequirement {
@@ -243,6 +436,45 @@ pub struct KotlinWrapper<'a> {
     type_imports: BTreeSet<ImportRequirement>,
 }
 
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "KmpCommon.kt")]
+pub struct KotlinCommonWrapper<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "TestStubs.kt")]
+pub struct KotlinTestStubs<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+    imports: Vec<ImportRequirement>,
+}
+
+impl<'a> KotlinTestStubs<'a> {
+    /// The value a stub method returns, if its return type has an obvious default.
+    pub fn default_value(&self, type_: &Type) -> Option<&'static str> {
+        Some(match type_ {
+            Type::Boolean => "false",
+            Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 => "0",
+            Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64
+                if self.config.unsigned_as_signed() =>
+            {
+                "0"
+            }
+            Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64 => "0u",
+            Type::Float32 => "0.0f",
+            Type::Float64 => "0.0",
+            Type::String => "\"\"",
+            Type::Bytes => "byteArrayOf()",
+            Type::Optional { .. } => "null",
+            Type::Sequence { .. } => "listOf()",
+            Type::Map { .. } => "mapOf()",
+            _ => return None,
+        })
+    }
+}
+
 impl<'a> KotlinWrapper<'a> {
     pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
         let type_renderer = TypeRenderer::new(&config, ci);
@@ -264,11 +496,40 @@ impl<'a> KotlinWrapper<'a> {
             .collect()
     }
 
+    pub fn builtin_imports(&self) -> &'static [&'static str] {
+        BUILTIN_IMPORTS
+    }
+
+    /// The imports needed by the types in use and those from the `extra_imports` config, without
+    /// any of the builtin imports.
     pub fn imports(&self) -> Vec<ImportRequirement> {
-        self.type_imports.iter().cloned().collect()
+        let extra_imports = self
+            .config
+            .extra_imports
+            .iter()
+            .map(|name| ImportRequirement::Import { name: name.clone() });
+        self.type_imports
+            .iter()
+            .cloned()
+            .chain(extra_imports)
+            .filter(|req| match req {
+                ImportRequirement::Import { name } => !BUILTIN_IMPORTS.contains(&name.as_str()),
+                ImportRequirement::ImportAs { .. } => true,
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
     }
 }
 
+/// Quote a name for use as a Kotlin identifier.
+///
+/// Kotlin allows any identifier to be quoted with backticks, so rather than tracking which names
+/// are keywords, we quote every function and variable name.
+fn quote_keyword(nm: String) -> String {
+    format!("`{nm}`")
+}
+
 #[derive(Clone)]
 pub struct KotlinCodeOracle;
 
@@ -295,12 +556,12 @@ impl KotlinCodeOracle {
 
     /// Get the idiomatic Kotlin rendering of a function name.
     fn fn_name(&self, nm: &str) -> String {
-        format!("`{}`", nm.to_string().to_lower_camel_case())
+        quote_keyword(nm.to_string().to_lower_camel_case())
     }
 
     /// Get the idiomatic Kotlin rendering of a variable name.
     fn var_name(&self, nm: &str) -> String {
-        format!("`{}`", nm.to_string().to_lower_camel_case())
+        quote_keyword(nm.to_string().to_lower_camel_case())
     }
 
     /// Get the idiomatic Kotlin rendering of an individual enum variant.
@@ -360,6 +621,74 @@ impl KotlinCodeOracle {
             }
         }
     }
+
+    /// The signature that `func` is compiled to on the JVM, ignoring the return type.
+    ///
+    /// Generic type arguments are erased and nullability is dropped, except for the types which
+    /// are only boxed when they're nullable.  Functions declared in the same scope with the same
+    /// signature clash when they're compiled, even if their Kotlin signatures are different.
+    fn jvm_signature(&self, ci: &ComponentInterface, config: &Config, func: &Function) -> String {
+        let params = func
+            .arguments()
+            .into_iter()
+            .map(|arg| {
+                let label = arg.as_codetype().type_label(ci, config);
+                let nullable = label.ends_with('?');
+                let erased = label
+                    .split(['<', '?'])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                match erased.as_str() {
+                    "Boolean" | "Byte" | "Short" | "Int" | "Long" | "Float" | "Double"
+                    | "UByte" | "UShort" | "UInt" | "ULong"
+                        if nullable =>
+                    {
+                        format!("{erased}?")
+                    }
+                    _ => erased,
+                }
+            })
+            .collect::<Vec<_>>();
+        let scope = func
+            .namespace_object()
+            .map(|obj| format!("{obj}."))
+            .unwrap_or_default();
+        // Suspend functions take an extra `Continuation` parameter.
+        let continuation = if func.is_async() {
+            ", Continuation"
+        } else {
+            ""
+        };
+        format!(
+            "{scope}{}({}{continuation})",
+            self.fn_name(func.name()),
+            params.join(", ")
+        )
+    }
+
+    /// Get the `@JvmName` to compile `func` with, if its JVM signature clashes with another
+    /// function's.
+    ///
+    /// Functions only share a Kotlin name if their names differ in case or underscores, so each
+    /// one is compiled using its original name instead, apart from the function whose original
+    /// name is the Kotlin name.
+    fn jvm_name(
+        &self,
+        ci: &ComponentInterface,
+        config: &Config,
+        func: &Function,
+    ) -> Option<String> {
+        let signature = self.jvm_signature(ci, config, func);
+        let clashes = ci
+            .function_definitions()
+            .iter()
+            .filter(|other| self.jvm_signature(ci, config, other) == signature)
+            .count()
+            > 1;
+        (clashes && func.name() != self.fn_name(func.name()).trim_matches('`'))
+            .then(|| func.name().to_string())
+    }
 }
 
 trait AsCodeType {
@@ -392,6 +721,14 @@ impl<T: AsType> AsCodeType for T {
 
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
+            Type::DurationRange { inclusive: false } => Box::new(miscellany::DurationRangeCodeType),
+            Type::DurationRange { inclusive: true } => {
+                Box::new(miscellany::DurationRangeInclusiveCodeType)
+            }
+            Type::Path => Box::new(miscellany::PathCodeType),
+            Type::Json => Box::new(miscellany::JsonCodeType),
+            Type::DynError => Box::new(miscellany::DynErrorCodeType),
+            Type::OpaqueHandle => Box::new(miscellany::OpaqueHandleCodeType),
 
             Type::Enum { name, .. } => Box::new(enum_::EnumCodeType::new(name)),
             Type::Object { name, imp, .. } => Box::new(object::ObjectCodeType::new(name, imp)),
@@ -405,6 +742,7 @@ impl<T: AsType> AsCodeType for T {
             Type::Sequence { inner_type } => {
                 Box::new(compounds::SequenceCodeType::new(*inner_type))
             }
+            Type::NonZero { inner_type } => Box::new(compounds::NonZeroCodeType::new(*inner_type)),
             Type::Map {
                 key_type,
                 value_type,
@@ -422,8 +760,9 @@ mod filters {
     pub(super) fn type_name(
         as_ct: &impl AsCodeType,
         ci: &ComponentInterface,
+        config: &Config,
     ) -> Result<String, askama::Error> {
-        Ok(as_ct.as_codetype().type_label(ci))
+        Ok(as_ct.as_codetype().type_label(ci, config))
     }
 
     pub(super) fn canonical_name(as_ct: &impl AsCodeType) -> Result<String, askama::Error> {
@@ -467,8 +806,9 @@ mod filters {
         literal: &Literal,
         as_ct: &impl AsType,
         ci: &ComponentInterface,
+        config: &Config,
     ) -> Result<String, askama::Error> {
-        Ok(as_ct.as_codetype().literal(literal, ci))
+        Ok(as_ct.as_codetype().literal(literal, ci, config))
     }
 
     pub fn ffi_type_name_by_value(type_: &FfiType) -> Result<String, askama::Error> {
@@ -490,6 +830,11 @@ mod filters {
         Ok(KotlinCodeOracle.var_name(nm))
     }
 
+    /// Get the idiomatic Kotlin rendering of a constant name.
+    pub fn const_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_shouty_snake_case())
+    }
+
     /// Get a String representing the name used for an individual enum variant.
     pub fn variant_name(v: &Variant) -> Result<String, askama::Error> {
         Ok(KotlinCodeOracle.enum_variant_name(v.name()))
@@ -500,6 +845,92 @@ mod filters {
         Ok(KotlinCodeOracle.convert_error_suffix(&name))
     }
 
+    /// Get the condition for retrying a function marked with `[Retry=N]`, on the exception `it`
+    /// which it threw.
+    pub fn retry_predicate(
+        func: &Function,
+        ci: &ComponentInterface,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        let error_name = match func.throws_type() {
+            Some(throws) => type_name(throws, ci, config)?,
+            None => return Ok("false".to_string()),
+        };
+        Ok(ci
+            .retryable_variants(func)
+            .into_iter()
+            .map(|v| Ok(format!("it is {error_name}.{}", error_variant_name(v)?)))
+            .collect::<Result<Vec<_>, askama::Error>>()?
+            .join(" || "))
+    }
+
+    /// The `Result` type returned by a function which throws when the `error_style` is `result`.
+    pub fn result_type_name(
+        func: &Function,
+        ci: &ComponentInterface,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        Ok(format!("Result<{}>", ok_type_name(func, ci, config)?))
+    }
+
+    /// Get the call to `uniffiResultOf` which catches the error thrown by `func`, to return it as
+    /// a `Result` instead.
+    pub fn result_of(
+        func: &Function,
+        ci: &ComponentInterface,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        let error_type_name = match func.throws_type() {
+            Some(throws) => type_name(throws, ci, config)?,
+            None => "Exception".to_string(),
+        };
+        Ok(format!(
+            "uniffiResultOf<{}, {error_type_name}>",
+            ok_type_name(func, ci, config)?
+        ))
+    }
+
+    fn ok_type_name(
+        func: &Function,
+        ci: &ComponentInterface,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        if func.has_out_args() {
+            return out_params_type_name(func, ci, config);
+        }
+        match func.return_type() {
+            Some(return_type) if func.is_with_warnings() => Ok(format!(
+                "WithWarnings<{}>",
+                type_name(return_type, ci, config)?
+            )),
+            Some(return_type) => type_name(return_type, ci, config),
+            None => Ok("Unit".to_string()),
+        }
+    }
+
+    /// The type returned by a function with out-parameters: the only output if there's one,
+    /// otherwise the `{Function}Outputs` data class holding all of them.
+    pub fn out_params_type_name(
+        func: &Function,
+        ci: &ComponentInterface,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        match func.out_params_outputs().as_slice() {
+            [(_, type_)] => type_name(*type_, ci, config),
+            _ => Ok(KotlinCodeOracle.class_name(ci, &format!("{}_outputs", func.name()))),
+        }
+    }
+
+    /// The name to compile a top-level function with on the JVM, if it differs from the Kotlin
+    /// name to avoid a clash.
+    pub fn jvm_name(
+        func: &Function,
+        ci: &ComponentInterface,
+        config: &Config,
+    ) -> Result<Option<String>, askama::Error> {
+        Ok(KotlinCodeOracle.jvm_name(ci, config, func))
+    }
+
     pub fn object_names(
         obj: &Object,
         ci: &ComponentInterface,
@@ -507,6 +938,19 @@ mod filters {
         Ok(KotlinCodeOracle.object_names(ci, obj))
     }
 
+    /// The name of the interface generated for the parent of `obj`, or an empty string if it
+    /// doesn't inherit from another interface.
+    pub fn parent_interface_name(
+        obj: &Object,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        Ok(obj
+            .parent()
+            .and_then(|parent| ci.get_object_definition(parent))
+            .map(|parent| KotlinCodeOracle.object_names(ci, parent).0)
+            .unwrap_or_default())
+    }
+
     pub fn async_poll(
         callable: impl Callable,
         ci: &ComponentInterface,
@@ -548,6 +992,16 @@ mod filters {
         ))
     }
 
+    pub fn async_cancel(
+        callable: impl Callable,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        let ffi_func = callable.ffi_rust_future_cancel(ci);
+        Ok(format!(
+            "{{ future -> UniffiLib.INSTANCE.{ffi_func}(future) }}"
+        ))
+    }
+
     /// Remove the "`" chars we put around function/variable names
     ///
     /// These are used to avoid name clashes with kotlin identifiers, but sometimes you want to
@@ -565,4 +1019,111 @@ mod filters {
         let spaces = usize::try_from(*spaces).unwrap_or_default();
         Ok(textwrap::indent(&wrapped, &" ".repeat(spaces)))
     }
+
+    /// Get the value of a record field in a `toString()` template, with byte arrays summarized
+    /// by their size.
+    pub fn display_field(field: &Field) -> Result<String, askama::Error> {
+        let name = var_name(field.name())?;
+        Ok(match field.as_type() {
+            Type::Bytes => format!("<${{{name}.size}} bytes>"),
+            Type::Optional { inner_type } if *inner_type == Type::Bytes => {
+                format!("${{{name}?.let {{ \"<${{it.size}} bytes>\" }}}}")
+            }
+            _ => format!("${{{name}}}"),
+        })
+    }
+
+    /// Get a `@Deprecated` annotation with the deprecation message.
+    pub fn deprecated(message: &str, spaces: &i32) -> Result<String, askama::Error> {
+        let message = message
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "\\$")
+            .replace('\n', "\\n");
+        let spaces = usize::try_from(*spaces).unwrap_or_default();
+        Ok(format!("{}@Deprecated(\"{message}\")", " ".repeat(spaces)))
+    }
+
+    /// Add a `@since` KDoc tag to the docstring of a type, if it has a version.
+    pub fn with_since(
+        docstring: Option<&str>,
+        since: Option<&str>,
+    ) -> Result<Option<String>, askama::Error> {
+        Ok(match (docstring, since) {
+            (docstring, None) => docstring.map(ToOwned::to_owned),
+            (None, Some(version)) => Some(format!("@since {version}")),
+            (Some(docstring), Some(version)) => Some(format!("{docstring}\n\n@since {version}")),
+        })
+    }
+
+    /// Get the docstring of a function, method or constructor, with `@param`, `@return` and
+    /// `@since` KDoc tags for the argument and return value docs and the version.
+    pub fn callable_docstring<T: Callable>(callable: &T) -> Result<Option<String>, askama::Error> {
+        callable_docstring_with_note(callable, None)
+    }
+
+    /// How the bindings release an object's reference, for the `ownership_docs` notes.
+    const OWNERSHIP_RELEASE: &str = "when `destroy()` or `close()` is called";
+
+    /// Get the docstring of an object, with a note on who owns its Rust object.
+    pub fn object_ownership_docstring(obj: &Object) -> Result<Option<String>, askama::Error> {
+        let docstring = join_note(obj.docstring(), obj.ownership_doc(OWNERSHIP_RELEASE));
+        with_since(Some(&docstring), obj.since())
+    }
+
+    /// Get the docstring of a constructor, with a note on who owns the new instance.
+    pub fn constructor_ownership_docstring(
+        cons: &Constructor,
+    ) -> Result<Option<String>, askama::Error> {
+        callable_docstring_with_note(cons, Some(cons.ownership_doc(OWNERSHIP_RELEASE)))
+    }
+
+    /// Get the docstring of a method, with a note on who owns the object during the call.
+    pub fn method_ownership_docstring(meth: &Method) -> Result<Option<String>, askama::Error> {
+        callable_docstring_with_note(meth, Some(meth.ownership_doc(OWNERSHIP_RELEASE)))
+    }
+
+    fn join_note(docstring: Option<&str>, note: String) -> String {
+        match docstring {
+            Some(docstring) => format!("{docstring}\n\n{note}"),
+            None => note,
+        }
+    }
+
+    /// The note is added to the description, before the tags.
+    fn callable_docstring_with_note<T: Callable>(
+        callable: &T,
+        note: Option<String>,
+    ) -> Result<Option<String>, askama::Error> {
+        let mut tags: Vec<String> = callable
+            .arguments()
+            .into_iter()
+            .filter_map(|arg| {
+                let name = KotlinCodeOracle.var_name(arg.name());
+                Some(format!(
+                    "@param {} {}",
+                    unquote(&name).ok()?,
+                    arg.docstring()?
+                ))
+            })
+            .collect();
+        tags.extend(callable.return_docstring().map(|d| format!("@return {d}")));
+        tags.extend(callable.since().map(|v| format!("@since {v}")));
+        if callable.is_main_thread() {
+            // Before the block tags, so it's part of the description.
+            tags.insert(0, "Must be called from the main thread.".to_string());
+        }
+        let docstring = match note {
+            Some(note) => Some(join_note(callable.docstring(), note)),
+            None => callable.docstring().map(ToOwned::to_owned),
+        };
+        Ok(match (docstring, tags.is_empty()) {
+            (docstring, true) => docstring,
+            (None, false) => Some(tags.join("\n")),
+            (Some(docstring), false) => Some(format!("{docstring}\n\n{}", tags.join("\n"))),
+        })
+    }
 }
+
+#[cfg(test)]
+mod tests;
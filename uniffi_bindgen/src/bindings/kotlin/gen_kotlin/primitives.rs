@@ -2,12 +2,12 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use super::CodeType;
+use super::{CodeType, Config};
 use crate::backend::Literal;
 use crate::interface::{ComponentInterface, Radix, Type};
 use paste::paste;
 
-fn render_literal(literal: &Literal, _ci: &ComponentInterface) -> String {
+fn render_literal(literal: &Literal, _ci: &ComponentInterface, config: &Config) -> String {
     fn typed_number(type_: &Type, num_str: String) -> String {
         match type_ {
             // Bytes, Shorts and Ints can all be inferred from the type.
@@ -23,9 +23,38 @@ fn render_literal(literal: &Literal, _ci: &ComponentInterface) -> String {
         }
     }
 
+    // With `unsigned_style = "signed"`, unsigned values are written with the same bits as the
+    // signed type, so the large ones are negative.
+    fn signed_number(type_: &Type, value: u64) -> String {
+        match type_ {
+            Type::UInt8 => (value as u8 as i8).to_string(),
+            Type::UInt16 => (value as u16 as i16).to_string(),
+            // `-2147483648` is the negation of a `Long` literal, so it's a `Long` too.
+            Type::UInt32 => match value as u32 as i32 {
+                i32::MIN => "Int.MIN_VALUE".to_string(),
+                v => v.to_string(),
+            },
+            Type::UInt64 => match value as i64 {
+                i64::MIN => "Long.MIN_VALUE".to_string(),
+                v => format!("{v}L"),
+            },
+            _ => panic!("Unexpected literal: {value} is not an unsigned number"),
+        }
+    }
+
     match literal {
         Literal::Boolean(v) => format!("{v}"),
         Literal::String(s) => format!("\"{s}\""),
+        Literal::Int(i, _, type_ @ (Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64))
+            if config.unsigned_as_signed() =>
+        {
+            signed_number(type_, *i as u64)
+        }
+        Literal::UInt(i, _, type_ @ (Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64))
+            if config.unsigned_as_signed() =>
+        {
+            signed_number(type_, *i)
+        }
         Literal::Int(i, radix, type_) => typed_number(
             type_,
             match radix {
@@ -55,16 +84,45 @@ macro_rules! impl_code_type_for_primitive {
             pub struct $T;
 
             impl CodeType for $T  {
-                fn type_label(&self, _ci: &ComponentInterface) -> String {
+                fn type_label(&self, _ci: &ComponentInterface, _config: &Config) -> String {
+                    $class_name.into()
+                }
+
+                fn canonical_name(&self) -> String {
                     $class_name.into()
                 }
 
+                fn literal(&self, literal: &Literal, ci: &ComponentInterface, config: &Config) -> String {
+                    render_literal(&literal, ci, config)
+                }
+            }
+        }
+    };
+}
+
+// Unsigned integers are represented by the signed type of the same size with
+// `unsigned_style = "signed"`, but keep their own `FfiConverter`.
+macro_rules! impl_code_type_for_unsigned {
+    ($T:ty, $class_name:literal, $signed_class_name:literal) => {
+        paste! {
+            #[derive(Debug)]
+            pub struct $T;
+
+            impl CodeType for $T  {
+                fn type_label(&self, _ci: &ComponentInterface, config: &Config) -> String {
+                    if config.unsigned_as_signed() {
+                        $signed_class_name.into()
+                    } else {
+                        $class_name.into()
+                    }
+                }
+
                 fn canonical_name(&self) -> String {
                     $class_name.into()
                 }
 
-                fn literal(&self, literal: &Literal, ci: &ComponentInterface) -> String {
-                    render_literal(&literal, ci)
+                fn literal(&self, literal: &Literal, ci: &ComponentInterface, config: &Config) -> String {
+                    render_literal(&literal, ci, config)
                 }
             }
         }
@@ -78,9 +136,9 @@ impl_code_type_for_primitive!(Int8CodeType, "Byte");
 impl_code_type_for_primitive!(Int16CodeType, "Short");
 impl_code_type_for_primitive!(Int32CodeType, "Int");
 impl_code_type_for_primitive!(Int64CodeType, "Long");
-impl_code_type_for_primitive!(UInt8CodeType, "UByte");
-impl_code_type_for_primitive!(UInt16CodeType, "UShort");
-impl_code_type_for_primitive!(UInt32CodeType, "UInt");
-impl_code_type_for_primitive!(UInt64CodeType, "ULong");
+impl_code_type_for_unsigned!(UInt8CodeType, "UByte", "Byte");
+impl_code_type_for_unsigned!(UInt16CodeType, "UShort", "Short");
+impl_code_type_for_unsigned!(UInt32CodeType, "UInt", "Int");
+impl_code_type_for_unsigned!(UInt64CodeType, "ULong", "Long");
 impl_code_type_for_primitive!(Float32CodeType, "Float");
 impl_code_type_for_primitive!(Float64CodeType, "Double");
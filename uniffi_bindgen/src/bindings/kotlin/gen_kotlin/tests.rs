@@ -0,0 +1,1361 @@
+use super::{generate_bindings, generate_common_bindings, generate_test_stubs, Config};
+use crate::bindings::{
+    ci_with_metadata, contains_code, fn_metadata, generate_from_udl,
+    generate_with_runtime_initializer, method_metadata,
+};
+use crate::interface::ComponentInterface;
+
+const NAMESPACE_UDL: &str = r#"
+    namespace test {
+        u32 top_level();
+        [Namespace=MyApi]
+        u32 grouped_one();
+        [Namespace=MyApi, Async]
+        u32 grouped_two(u32 value);
+    };
+"#;
+
+#[test]
+fn test_namespace_object_functions() {
+    let kotlin = generate_from_udl(NAMESPACE_UDL, "", generate_bindings);
+    assert!(kotlin.contains("\nfun `topLevel`()"));
+    let (_, kotlin_object) = kotlin.split_once("\nobject MyApi {\n").unwrap();
+    assert!(kotlin_object.contains("fun `groupedOne`()"));
+    assert!(kotlin_object.contains("suspend fun `groupedTwo`(`value`: UInt)"));
+}
+
+#[test]
+fn test_blocking_variants() {
+    // Blocking variants are only generated when they're asked for.
+    let kotlin = generate_from_udl(NAMESPACE_UDL, "", generate_bindings);
+    assert!(!kotlin.contains("Blocking("));
+
+    let kotlin = generate_from_udl(
+        NAMESPACE_UDL,
+        "generate_blocking_variants = true",
+        generate_bindings,
+    );
+    let (_, kotlin_object) = kotlin.split_once("\nobject MyApi {\n").unwrap();
+    assert!(contains_code(
+        kotlin_object,
+        "fun `groupedTwoBlocking`(`value`: UInt) : UInt =\n    \
+         kotlinx.coroutines.runBlocking { `groupedTwo`(`value`) }"
+    ));
+    assert!(!kotlin.contains("`groupedOneBlocking`"));
+}
+
+#[test]
+fn test_deprecated() {
+    const UDL: &str = r#"
+        namespace test {
+            [Deprecated="Use new_thing instead"]
+            u32 old_thing();
+        };
+
+        enum Shape {
+            "Circle",
+            [Deprecated="Squares are going away"]
+            "Square",
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("@Deprecated(\"Use new_thing instead\")\n"));
+    assert!(contains_code(
+        &kotlin,
+        "    @Deprecated(\"Squares are going away\")\n    SQUARE"
+    ));
+}
+
+#[test]
+fn test_sealed_kind() {
+    const UDL: &str = r#"
+        namespace test {};
+
+        [Enum]
+        interface Mixed {
+            Empty();
+            Text(string s);
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("sealed class Mixed {"));
+    assert!(kotlin.contains("object Empty : Mixed()\n"));
+    assert!(kotlin.contains(") : Mixed() {"));
+
+    let kotlin_interface = generate_from_udl(UDL, "sealed_kind = \"interface\"", generate_bindings);
+    assert!(kotlin_interface.contains("sealed interface Mixed {"));
+    assert!(kotlin_interface.contains("object Empty : Mixed\n"));
+    assert!(kotlin_interface.contains(") : Mixed {"));
+
+    // The FfiConverter is the same either way.
+    let converter = |kotlin: &str| {
+        let (_, converter) = kotlin
+            .split_once("public object FfiConverterTypeMixed")
+            .unwrap();
+        converter.split_once("\n}\n").unwrap().0.to_owned()
+    };
+    assert_eq!(converter(&kotlin), converter(&kotlin_interface));
+}
+
+#[test]
+fn test_kmp() {
+    const UDL: &str = r#"
+        namespace test {
+            u32 add(u32 a, optional u32 b = 1);
+            Point origin();
+        };
+
+        dictionary Point {
+            i32 x;
+            i32 y = 0;
+        };
+    "#;
+    // The common code declares the public interface, with the default values.
+    let common = generate_from_udl(UDL, "kmp = true", generate_common_bindings);
+    assert!(contains_code(
+        &common,
+        "expect class Point(\n    `x`: Int, \n    `y`: Int = 0\n) {\n    \
+         var `x`: Int\n    var `y`: Int\n}\n"
+    ));
+    assert!(common.contains("expect fun `add`(`a`: UInt, `b`: UInt = 1u): UInt\n"));
+    assert!(common.contains("expect fun `origin`(): Point\n"));
+    assert!(!common.contains("UniffiLib"));
+
+    // The platform code implements it by calling into Rust.
+    let platform = generate_from_udl(UDL, "kmp = true", generate_bindings);
+    assert!(contains_code(
+        &platform,
+        "actual data class Point actual constructor(\n    \
+         actual var `x`: Int, \n    actual var `y`: Int\n) {"
+    ));
+    assert!(contains_code(
+        &platform,
+        "actual fun `add`(`a`: UInt, `b`: UInt): UInt {\n    \
+         return FfiConverterUInt.lift(\n    uniffiRustCall() { _status ->\n    \
+         UniffiLib.INSTANCE.uniffi_crate_name_fn_func_add("
+    ));
+
+    // Without KMP there's a single file with no `actual` modifiers.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "data class Point (\n    var `x`: Int, \n    var `y`: Int = 0\n)"
+    ));
+    assert!(kotlin.contains("fun `add`(`a`: UInt, `b`: UInt = 1u): UInt {"));
+    assert!(!kotlin.contains("actual "));
+
+    // Types with platform-specific declarations aren't supported yet.
+    const UNSUPPORTED_UDL: &str = r#"
+        namespace test {
+            timestamp now();
+        };
+    "#;
+    let ci = ComponentInterface::from_webidl(UNSUPPORTED_UDL, "crate_name").unwrap();
+    let config: Config = toml::from_str("kmp = true").unwrap();
+    let err = generate_common_bindings(&config, &ci).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Kotlin Multiplatform bindings don't support `java.time.Instant` yet"
+    );
+    assert!(generate_bindings(&config, &ci).is_err());
+}
+
+#[test]
+fn test_optional_throwing_return() {
+    const UDL: &str = r#"
+        namespace test {
+            [Throws=Failure]
+            string? find(u8 index);
+        };
+
+        [Error]
+        enum Failure { "Oops" };
+    "#;
+    // The optional is lifted from the return value of the call that checks for errors.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "fun `find`(`index`: UByte): String? {\n    \
+         return FfiConverterOptionalString.lift(\n    \
+         uniffiRustCallWithError(Failure) {"
+    ));
+}
+
+#[test]
+fn test_common_error_fields() {
+    const UDL: &str = r#"
+        namespace test {};
+
+        [Error]
+        interface CodedError {
+            NotFound(i32 code, string path);
+            Timeout(i32 code);
+        };
+    "#;
+    // The field shared by every variant is declared on the base exception.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "sealed class CodedException: Exception() {\n    abstract val `code`: Int\n"
+    ));
+    assert!(kotlin.contains("override val `code`: Int, \n"));
+    assert!(!kotlin.contains("override val `path`"));
+}
+
+#[test]
+fn test_shared_error_generated_once() {
+    const UDL: &str = r#"
+        namespace test {
+            [Throws=StoreError]
+            void open();
+            [Throws=StoreError]
+            string read(u32 key);
+            [Throws=StoreError]
+            void write(u32 key, string value);
+        };
+
+        [Error]
+        enum StoreError { "NotFound", "ReadOnly" };
+    "#;
+    // However many functions throw it, the exception hierarchy is only generated once.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert_eq!(kotlin.matches("sealed class StoreException(").count(), 1);
+    assert_eq!(kotlin.matches("class NotFound(").count(), 1);
+    assert_eq!(
+        kotlin
+            .matches("public object FfiConverterTypeStoreError ")
+            .count(),
+        1
+    );
+    assert_eq!(kotlin.matches("@Throws(StoreException::class)").count(), 3);
+}
+
+#[test]
+fn test_cancelled_call_status() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async, Cancellable]
+            u32 download();
+        };
+    "#;
+    // The `Cancelled` call status is raised as a cancellation, rather than as an error.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "    } else if (status.isCancelled()) {\n        \
+        // Only async calls can be cancelled."
+    ));
+}
+
+#[test]
+fn test_wrong_thread_call_status() {
+    const UDL: &str = r#"
+        namespace test {};
+        [ThreadBound]
+        interface Context {
+            constructor();
+            void draw();
+        };
+    "#;
+    // The `WrongThread` call status is raised as its own error type.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin
+        .contains("class WrongThreadException(message: String) : IllegalStateException(message)"));
+    assert!(contains_code(
+        &kotlin,
+        "    } else if (status.isWrongThread()) {\n        \
+        throw WrongThreadException(FfiConverterString.lift(status.error_buf))"
+    ));
+
+    // The error type is only generated for components with thread-bound objects.
+    let kotlin = generate_from_udl(
+        "namespace test {}; interface Context { void draw(); };",
+        "",
+        generate_bindings,
+    );
+    assert!(!kotlin.contains("WrongThreadException"));
+}
+
+#[test]
+fn test_state_poisoned_call_status() {
+    const UDL: &str = r#"
+        namespace test {};
+        [OnPoison=error]
+        interface Counter {
+            constructor();
+            u32 increment();
+        };
+    "#;
+    // The `StatePoisoned` call status is raised as its own error type.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains(
+        "class StatePoisonedException(message: String) : IllegalStateException(message)"
+    ));
+    assert!(kotlin.contains("} else if (status.isStatePoisoned()) {"));
+    assert!(
+        kotlin.contains("throw StatePoisonedException(FfiConverterString.lift(status.error_buf))")
+    );
+
+    // The error type is only generated for components with `[OnPoison]` objects.
+    let kotlin = generate_from_udl(
+        "namespace test {}; interface Counter { u32 increment(); };",
+        "",
+        generate_bindings,
+    );
+    assert!(!kotlin.contains("StatePoisonedException"));
+}
+
+#[test]
+fn test_runtime_config() {
+    // The runtime initializer is wrapped in a builder, rather than exposed as a function.
+    let kotlin = generate_with_runtime_initializer(generate_bindings);
+    assert!(kotlin.contains("class UniffiRuntimeConfig {"));
+    assert!(kotlin.contains("fun `maxBufferLen`(value: ULong): UniffiRuntimeConfig {"));
+    assert!(kotlin.contains("fun `maxReadDepth`(value: UInt): UniffiRuntimeConfig {"));
+    assert!(kotlin.contains("fun uniffiInitialize(config: UniffiRuntimeConfig) {"));
+    assert!(kotlin.contains("val `maxReadDepth` = config.`maxReadDepth`"));
+    assert!(!kotlin.contains("fun uniffiInitialize(`maxBufferLen`"));
+}
+
+#[test]
+fn test_constants() {
+    let ci = ci_with_metadata(
+        "namespace test {};",
+        vec![uniffi_meta::ConstFnMetadata {
+            module_path: "crate_name".into(),
+            name: "app_version".into(),
+            return_type: uniffi_meta::Type::String,
+            checksum: None,
+            docstring: None,
+        }
+        .into()],
+    );
+    let kotlin = generate_bindings(&Default::default(), &ci).unwrap();
+    assert!(kotlin.contains("val `appVersion`: String by lazy {\n"));
+}
+
+#[test]
+fn test_non_zero() {
+    let non_zero_u32 = uniffi_meta::Type::NonZero {
+        inner_type: Box::new(uniffi_meta::Type::UInt32),
+    };
+    let ci = ci_with_metadata(
+        "namespace test {};",
+        vec![fn_metadata(
+            "next_id",
+            vec![uniffi_meta::FnParamMetadata::simple(
+                "id",
+                non_zero_u32.clone(),
+            )],
+            Some(non_zero_u32),
+        )
+        .into()],
+    );
+    // The integer is wrapped in a value class.
+    let kotlin = generate_bindings(&Default::default(), &ci).unwrap();
+    assert!(contains_code(
+        &kotlin,
+        "@JvmInline\nvalue class NonZeroUInt(val value: UInt) {"
+    ));
+    assert!(kotlin.contains("fun `nextId`(`id`: NonZeroUInt): NonZeroUInt {\n"));
+}
+
+#[test]
+fn test_opaque_handle() {
+    const UDL: &str = r#"
+        namespace test {
+            opaque_handle echo_handle(opaque_handle handle);
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("@JvmInline\nvalue class OpaqueHandle(val value: Long) {\n"));
+    assert!(kotlin.contains("fun `echoHandle`(`handle`: OpaqueHandle): OpaqueHandle {\n"));
+}
+
+#[test]
+fn test_duration_ranges() {
+    const UDL: &str = r#"
+        namespace test {
+            duration_range shift(duration_range window, duration by);
+            duration_range_inclusive shift_closed(duration_range_inclusive window, duration by);
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("fun `shift`(`window`: OpenEndRange<java.time.Duration>, `by`: java.time.Duration): OpenEndRange<java.time.Duration> {\n"));
+    assert!(kotlin.contains("fun `shiftClosed`(`window`: ClosedRange<java.time.Duration>, `by`: java.time.Duration): ClosedRange<java.time.Duration> {\n"));
+    assert!(kotlin.contains("return start.rangeUntil(end)\n"));
+    assert!(kotlin.contains("FfiConverterDuration.write(value.endInclusive, buf)\n"));
+}
+
+#[test]
+fn test_keyword_escaping() {
+    const UDL: &str = r#"
+        namespace test {
+            Keywords make_keywords(i32 class);
+        };
+        dictionary Keywords {
+            i32 class;
+            i32 in;
+            i32 None;
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "    var `class`: Int, \n    var `in`: Int, \n    var `none`: Int"
+    ));
+    assert!(kotlin.contains("fun `makeKeywords`(`class`: Int): Keywords"));
+}
+
+#[test]
+fn test_java_interop() {
+    const UDL: &str = r#"
+        namespace test {
+            [Throws=NetworkError]
+            string download(string url);
+            [Throws=(NetworkError, ParseError)]
+            u32 fetch(string url);
+        };
+        [Error]
+        enum NetworkError { "Offline" };
+        [Error]
+        enum ParseError { "Invalid" };
+        interface Client {
+            [Throws=NetworkError]
+            constructor();
+            [Throws=NetworkError]
+            string get(string url);
+        };
+    "#;
+    // Functions and methods are always annotated, but constructors and interface methods
+    // are only annotated for Java interop.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("@Throws(NetworkException::class)\n\nfun `download`("));
+    assert!(!contains_code(
+        &kotlin,
+        "@Throws(NetworkException::class)\n    constructor("
+    ));
+    assert!(!contains_code(
+        &kotlin,
+        "@Throws(NetworkException::class)\n    fun `get`("
+    ));
+
+    let kotlin = generate_from_udl(UDL, "java_interop = true", generate_bindings);
+    assert!(kotlin.contains("@Throws(NetworkException::class)\n\nfun `download`("));
+    assert!(kotlin.contains("@Throws(NetworkErrorOrParseException::class)\n\nfun `fetch`("));
+    assert!(contains_code(
+        &kotlin,
+        "@Throws(NetworkException::class)\n    constructor("
+    ));
+    assert!(contains_code(
+        &kotlin,
+        "@Throws(NetworkException::class)\n    fun `get`("
+    ));
+}
+
+#[test]
+fn test_record_wire_order() {
+    const UDL: &str = r#"
+        namespace test {};
+        dictionary Reordered {
+            [Order=1] string first;
+            [Order=0] u32 second;
+        };
+    "#;
+    // Fields are read and written in wire order, but constructed in declaration order.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "            `second` = FfiConverterUInt.read(buf),\n            \
+         `first` = FfiConverterString.read(buf),\n"
+    ));
+    assert!(contains_code(
+        &kotlin,
+        "            FfiConverterUInt.write(value.`second`, buf)\n            \
+         FfiConverterString.write(value.`first`, buf)\n"
+    ));
+}
+
+#[test]
+fn test_associated_constants() {
+    const UDL: &str = r#"
+        namespace test {};
+        interface Color {
+            /// Pure red
+            const u32 RED = 0xFF0000;
+            const boolean IS_OPAQUE = true;
+            const f64 SCALE = 1.5;
+            constructor();
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "    companion object {\n        /**\n         * Pure red\n         */\n        \
+         val RED: UInt = 0xff0000u\n        \
+         val IS_OPAQUE: Boolean = true\n        \
+         val SCALE: Double = 1.5\n"
+    ));
+}
+
+#[test]
+fn test_file_header_and_extra_imports() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async]
+            u32 wait();
+        };
+        interface Counter {
+            constructor();
+        };
+    "#;
+    // Extra imports which are already generated, either always or for the types in use,
+    // are only imported once.
+    const CONFIG: &str = r#"
+        file_header = "// Copyright Example Corp."
+        extra_imports = [
+            "java.nio.ByteBuffer",
+            "java.util.concurrent.atomic.AtomicLong",
+            "org.example.Extra",
+            "org.example.Extra",
+        ]
+    "#;
+    let kotlin = generate_from_udl(UDL, CONFIG, generate_bindings);
+    let count = |needle: &str| kotlin.matches(needle).count();
+    assert!(kotlin.starts_with("// Copyright Example Corp.\n// This file was autogenerated"));
+    assert_eq!(count("// Copyright Example Corp."), 1);
+    assert_eq!(count("\nimport java.nio.ByteBuffer\n"), 1);
+    assert_eq!(
+        count("\nimport java.util.concurrent.atomic.AtomicLong\n"),
+        1
+    );
+    assert_eq!(count("\nimport org.example.Extra\n"), 1);
+}
+
+#[test]
+fn test_hash_header() {
+    const UDL: &str = r#"
+        namespace test {
+            u32 add(u32 a, u32 b);
+        };
+    "#;
+    let hash = ComponentInterface::from_webidl(UDL, "crate_name")
+        .unwrap()
+        .stable_hash();
+    const CONFIG: &str = r#"
+        file_header = "// Copyright Example Corp."
+        hash_header = true
+    "#;
+    let kotlin = generate_from_udl(UDL, CONFIG, generate_bindings);
+    let plain = generate_from_udl(UDL, "", generate_bindings);
+    // The header goes after `file_header`, and the rest of the file is the same as without it.
+    let expected = format!(
+        "// Copyright Example Corp.\n\
+         // Generated by uniffi-bindgen {} from an interface with hash {hash:016x}\n{plain}",
+        env!("CARGO_PKG_VERSION"),
+    );
+    assert_eq!(kotlin, expected);
+}
+
+#[test]
+fn test_retry() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async, Retry=3, Throws=NetworkError]
+            string fetch();
+        };
+        [Error]
+        enum NetworkError {
+            [Retryable] "Timeout",
+            [Retryable] "Unavailable",
+            "NotFound",
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "    return uniffiRetryAsync(3, { it is NetworkException.Timeout || \
+         it is NetworkException.Unavailable }) { uniffiRustCallAsync(\n"
+    ));
+}
+
+#[test]
+fn test_target_functions() {
+    const UDL: &str = r#"
+        namespace test {
+            u32 everywhere();
+            [Target="ios"]
+            void request_review(string prompt);
+            [Target="android"]
+            void show_toast(string message);
+        };
+    "#;
+    // Only the functions for the targets it's configured for are generated.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("fun `everywhere`()"));
+    assert!(!kotlin.contains("requestReview"));
+    assert!(!kotlin.contains("showToast"));
+    let kotlin = generate_from_udl(UDL, "targets = [\"android\"]", generate_bindings);
+    assert!(kotlin.contains("fun `showToast`(`message`: String)"));
+    assert!(!kotlin.contains("requestReview"));
+
+    // In Kotlin Multiplatform projects, functions for a target are only declared in the
+    // platform code, without an `expect` declaration.
+    let config = "kmp = true\ntargets = [\"android\"]";
+    let common = generate_from_udl(UDL, config, generate_common_bindings);
+    assert!(common.contains("expect fun `everywhere`(): UInt"));
+    assert!(!common.contains("showToast"));
+    let platform = generate_from_udl(UDL, config, generate_bindings);
+    assert!(platform.contains("actual fun `everywhere`(): UInt"));
+    assert!(platform.contains("\nfun `showToast`(`message`: String)"));
+}
+
+#[test]
+fn test_observable_interfaces() {
+    const UDL: &str = r#"
+        namespace test {};
+        callback interface Listener {
+            void on_tick(u32 count);
+        };
+        [Observable]
+        interface Clock {
+            void add_observer(Listener listener);
+            void remove_observer(Listener listener);
+        };
+        interface Plain {};
+    "#;
+    // Without a token from `add_observer`, the observer itself is removed.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "    fun observe(): Flow<UInt> = callbackFlow {\n        \
+         val observer = object : Listener {\n            \
+         override fun `onTick`(`count`: UInt) {\n                \
+         trySendBlocking(`count`)\n            }\n        }\n        \
+         `addObserver`(observer)\n        \
+         awaitClose { `removeObserver`(observer) }\n    }\n"
+    ));
+    assert!(kotlin.contains("import kotlinx.coroutines.flow.callbackFlow\n"));
+    assert_eq!(kotlin.matches("fun observe()").count(), 1);
+}
+
+#[test]
+fn test_cursor_interfaces() {
+    const UDL: &str = r#"
+        namespace test {};
+        [Cursor]
+        interface Rows {
+            [Throws=QueryError]
+            sequence<string> next_batch(u32 count);
+        };
+        interface Plain {};
+        [Error]
+        enum QueryError { "Closed" };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "    fun asSequence(batchSize: Int = 100): Sequence<String> {\n        \
+         require(batchSize > 0) { \"batchSize must be positive\" }\n        \
+         return sequence {\n            while (true) {\n                \
+         val batch = `nextBatch`(batchSize.toUInt())\n                \
+         yieldAll(batch)\n                \
+         if (batch.size < batchSize) {\n                    break\n"
+    ));
+    assert_eq!(kotlin.matches("fun asSequence(").count(), 1);
+
+    // The count is passed as the type `next_batch` takes.
+    let kotlin = generate_from_udl(UDL, "unsigned_style = \"signed\"", generate_bindings);
+    assert!(kotlin.contains("val batch = `nextBatch`(batchSize.toInt())\n"));
+}
+
+#[test]
+fn test_extension_functions() {
+    const UDL: &str = r#"
+        namespace test {
+            [Extension=Client]
+            string process(Client client, u32 count);
+            [Extension=Client, Async]
+            void upload(Client client, bytes data);
+            [Extension=Point]
+            double length(Point point);
+        };
+        interface Client {};
+        dictionary Point { double x; double y; };
+    "#;
+    // The receiver isn't declared, and is lowered as `this`.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "fun Client.`process`(`count`: UInt): String {\n    \
+         return FfiConverterString.lift(\n    uniffiRustCall() { _status ->\n    \
+         UniffiLib.INSTANCE.uniffi_crate_name_fn_func_process(\
+         FfiConverterTypeClient.lower(this),FfiConverterUInt.lower(`count`),_status)\n})\n}\n"
+    ));
+    assert!(kotlin.contains("suspend fun Client.`upload`(`data`: ByteArray) {"));
+    assert!(kotlin.contains("fun Point.`length`(): Double {"));
+}
+
+#[test]
+fn test_variadic_arguments() {
+    const UDL: &str = r#"
+        namespace test {
+            string join_words(string separator, [Variadic] sequence<string> words);
+            [Async]
+            u32 sum([Variadic] sequence<u32> values);
+        };
+        interface Counter {
+            constructor([Variadic] sequence<u32> steps);
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("fun `joinWords`(`separator`: String, vararg `words`: String)"));
+    assert!(kotlin.contains("FfiConverterSequenceString.lower(`words`.toList())"));
+    assert!(kotlin.contains("constructor(vararg `steps`: UInt)"));
+    let kotlin = generate_from_udl(UDL, "generate_blocking_variants = true", generate_bindings);
+    assert!(kotlin.contains("runBlocking { `sum`(*`values`) }"));
+}
+
+#[test]
+fn test_main_thread() {
+    const UDL: &str = r#"
+        namespace test {
+            [MainThread]
+            void update_ui(string text);
+            [MainThread]
+            u32 read_ui();
+            u32 anywhere();
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    let guard = "check(Thread.currentThread().name == \"main\")";
+    assert!(contains_code(&kotlin, &format!(
+        "fun `updateUi`(`text`: String) {{\n    {guard} {{ \"`updateUi` must be called from the main thread\" }}\n"
+    )));
+    assert!(contains_code(&kotlin, &format!(
+        "fun `readUi`(): UInt {{\n    {guard} {{ \"`readUi` must be called from the main thread\" }}\n"
+    )));
+    assert_eq!(kotlin.matches(guard).count(), 2);
+    assert_eq!(
+        kotlin
+            .matches(" * Must be called from the main thread.\n")
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn test_derive_display() {
+    const UDL: &str = r#"
+        namespace test {};
+        dictionary Point {
+            i32 x;
+            i32 y;
+        };
+        dictionary Shape {
+            Point origin;
+            string? label;
+            bytes data;
+            bytes? thumbnail;
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(!kotlin.contains("override fun toString()"));
+    let kotlin = generate_from_udl(UDL, "derive_display = true", generate_bindings);
+    assert!(kotlin.contains("return \"Point(x=${`x`}, y=${`y`})\""));
+    assert!(kotlin.contains(
+        "return \"Shape(origin=${`origin`}, label=${`label`}, data=<${`data`.size} bytes>, thumbnail=${`thumbnail`?.let { \"<${it.size} bytes>\" }})\""
+    ));
+}
+
+#[test]
+fn test_since() {
+    const UDL: &str = r#"
+        namespace test {};
+        /// A point.
+        /// @since 1.2.0
+        dictionary Point {
+            i32 x;
+        };
+        /// @since 1.3.0
+        interface Counter {
+            constructor();
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "/**\n * A point.\n *\n * @since 1.2.0\n */\ndata class Point"
+    ));
+    assert!(contains_code(
+        &kotlin,
+        "/**\n * @since 1.3.0\n */\nopen class Counter"
+    ));
+}
+
+#[test]
+fn test_jvm_name_clashes() {
+    const UDL: &str = r#"
+        namespace test {
+            // Both are `getItems(List)` on the JVM.
+            u32 get_items(sequence<u32> items);
+            u32 getItems(sequence<string> items);
+            // `setValue(int)` and `setValue(Integer)` don't clash.
+            void set_value(i32 value);
+            void setValue(i32? value);
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("@JvmName(\"get_items\")\n\nfun `getItems`(`items`: List<UInt>)"));
+    assert!(!kotlin.contains("@JvmName(\"getItems\")"));
+    assert_eq!(kotlin.matches("@JvmName").count(), 1);
+}
+
+#[test]
+fn test_shared_composite_types() {
+    const UDL: &str = r#"
+        namespace test {
+            u32 count(record<string, sequence<u32>> groups);
+            record<string, sequence<u32>> merge(record<string, sequence<u32>> groups);
+            sequence<string> names();
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert_eq!(
+        kotlin
+            .matches("public typealias MapStringSequenceUInt = Map<String, SequenceUInt>\n")
+            .count(),
+        1
+    );
+    assert_eq!(
+        kotlin
+            .matches("public typealias SequenceUInt = List<UInt>\n")
+            .count(),
+        1
+    );
+    assert_eq!(
+        kotlin
+            .matches("public object FfiConverterMapStringSequenceUInt")
+            .count(),
+        1
+    );
+    assert!(kotlin.contains("fun `merge`(`groups`: MapStringSequenceUInt): MapStringSequenceUInt"));
+    // `sequence<string>` is only used once, so it's spelled out.
+    assert!(!kotlin.contains("typealias SequenceString"));
+    assert!(kotlin.contains("fun `names`(): List<String>"));
+
+    // Kotlin Multiplatform declares the aliases in the common source set.
+    let common = generate_from_udl(UDL, "kmp = true", generate_common_bindings);
+    assert!(common.contains("public typealias MapStringSequenceUInt = Map<String, SequenceUInt>\n"));
+    let kotlin = generate_from_udl(UDL, "kmp = true", generate_bindings);
+    assert!(!kotlin.contains("typealias"));
+}
+
+#[test]
+fn test_optional_map_values() {
+    const UDL: &str = r#"
+        namespace test {
+            record<string, u32?> copy(record<string, u32?> values);
+            record<string, u32> copy_required(record<string, u32> values);
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("public typealias MapStringOptionalUInt = Map<String, UInt?>\n"));
+    assert!(kotlin.contains(
+        "public object FfiConverterMapStringOptionalUInt: FfiConverterRustBuffer<Map<String, UInt?>>"
+    ));
+    assert!(kotlin.contains("val v = FfiConverterOptionalUInt.read(buf)"));
+    assert!(kotlin.contains("FfiConverterOptionalUInt.write(v, buf)"));
+    assert!(kotlin.contains("public typealias MapStringUInt = Map<String, UInt>\n"));
+    assert!(kotlin.contains("val v = FfiConverterUInt.read(buf)"));
+}
+
+#[test]
+fn test_error_style() {
+    const UDL: &str = r#"
+        namespace test {
+            [Throws=MathError]
+            u64 divide(u64 a, u64 b);
+            [Throws=MathError]
+            void check(u64 b);
+            [Async, Throws=MathError]
+            u64 divide_async(u64 a, u64 b);
+        };
+        [Error]
+        enum MathError {
+            "DivisionByZero",
+        };
+    "#;
+    // By default, the functions throw.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains(
+        "@Throws(MathException::class)\n\nfun `divide`(`a`: ULong, `b`: ULong): ULong {"
+    ));
+    assert!(!kotlin.contains(": Result<ULong>"));
+
+    let kotlin = generate_from_udl(UDL, "error_style = \"result\"", generate_bindings);
+    assert!(!kotlin.contains("@Throws(MathException::class)"));
+    assert!(contains_code(
+        &kotlin,
+        "fun `divide`(`a`: ULong, `b`: ULong): Result<ULong> {\n    \
+         return uniffiResultOf<ULong, MathException> {"
+    ));
+    assert!(contains_code(
+        &kotlin,
+        "fun `check`(`b`: ULong): Result<Unit> {\n    \
+         return uniffiResultOf<Unit, MathException> {"
+    ));
+    assert!(contains_code(
+        &kotlin,
+        "suspend fun `divideAsync`(`a`: ULong, `b`: ULong) : Result<ULong> {\n    \
+         return uniffiResultOf<ULong, MathException> { uniffiRustCallAsync("
+    ));
+}
+
+#[test]
+fn test_enum_default_variant() {
+    const UDL: &str = r#"
+        namespace test {};
+        [NonExhaustive]
+        enum Color { "Red", [Default] "Unknown" };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains(
+        "override fun lift(value: Int) = Color.values().getOrElse(value - 1) { Color.UNKNOWN }"
+    ));
+}
+
+#[test]
+fn test_enum_unknown_variant() {
+    const UDL: &str = r#"
+        namespace test {};
+        [NonExhaustive]
+        enum Color { "Red", "Green" };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("sealed class Color {"));
+    assert!(kotlin.contains("data class Unknown(val discriminant: Int) : Color()"));
+    assert!(kotlin.contains("else -> Color.Unknown(value)\n"));
+    assert!(kotlin.contains("is Color.Unknown -> value.discriminant\n"));
+}
+
+#[test]
+fn test_enum_discriminant_encoding() {
+    const UDL: &str = r#"
+        namespace test {};
+        [Discriminant=u16_le]
+        enum Opcode { "Read", "Write" };
+        [Enum, Discriminant=u8]
+        interface Shape {
+            Circle(double radius);
+            Point();
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains(
+        "override fun read(buf: ByteBuffer) = lift((java.lang.Short.reverseBytes(buf.getShort()).toInt() and 0xffff))"
+    ));
+    assert!(kotlin.contains("buf.putShort(java.lang.Short.reverseBytes((lower(value)).toShort()))"));
+    assert!(kotlin.contains("override fun allocationSize(value: Opcode) = 2\n"));
+    assert!(kotlin.contains("return when((buf.get().toInt() and 0xff)) {"));
+    assert!(kotlin.contains("buf.put((2).toByte())"));
+}
+
+#[test]
+fn test_object_identity_equality() {
+    const UDL: &str = r#"
+        namespace test {};
+        interface Counter {
+            constructor();
+        };
+        [Traits=(Eq)]
+        interface Point {
+            constructor();
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(
+        kotlin.contains("UniffiLib.INSTANCE.uniffi_crate_name_fn_handle_id_counter(ptr, status)")
+    );
+    assert!(kotlin.contains("return uniffiHandleId() == other.uniffiHandleId()"));
+    assert_eq!(kotlin.matches("fun uniffiHandleId()").count(), 1);
+}
+
+#[test]
+fn test_chunked_sequences() {
+    const UDL: &str = r#"
+        namespace test {
+            [Chunked]
+            sequence<string> get_names();
+            sequence<string> get_names_unchunked();
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(
+        kotlin.contains("UniffiLib.INSTANCE.ffi_crate_name_chunked_sequence_next(handle, _status)")
+    );
+    assert!(kotlin.contains("}, FfiConverterSequenceString)"));
+    assert_eq!(
+        kotlin.matches("return uniffiLiftChunkedSequence(").count(),
+        1
+    );
+}
+
+#[test]
+fn test_with_warnings() {
+    const UDL: &str = r#"
+        namespace test {
+            [WithWarnings]
+            u32 parse(string input);
+            [WithWarnings, Throws=Error]
+            string load();
+        };
+        [Error]
+        enum Error { "Oops" };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("fun `parse`(`input`: String): WithWarnings<UInt> {"));
+    assert!(kotlin.contains("}, FfiConverterUInt)"));
+    assert!(kotlin.contains("val warnings = FfiConverterSequenceString.read(byteBuf)"));
+}
+
+#[test]
+fn test_segmented() {
+    const UDL: &str = r#"
+        namespace test {
+            [Segmented]
+            sequence<bytes> split(bytes data, u32 size);
+            [Throws=Error]
+            u64 total_len([Segmented] sequence<bytes> segments);
+        };
+        [Error]
+        enum Error { "Oops" };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("return uniffiLiftByteSegments(\n"));
+    assert!(kotlin.contains(
+        "uniffi_crate_name_fn_func_total_len(uniffiLowerByteSegments(`segments`),_status)"
+    ));
+}
+
+#[test]
+fn test_has_default() {
+    const UDL: &str = r#"
+        namespace test {};
+        [HasDefault]
+        dictionary Settings {
+            string name;
+            u32 retries;
+        };
+        dictionary Point {
+            i32 x;
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains(
+        "fun default(): Settings = FfiConverterTypeSettings.lift(uniffiRustCall() { _status ->"
+    ));
+    assert_eq!(kotlin.matches("fun default()").count(), 1);
+}
+
+#[test]
+fn test_progress() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async]
+            u32 count_steps(u32 steps, [Progress] ProgressListener listener);
+        };
+        callback interface ProgressListener {
+            void on_progress(u32 step);
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains(
+        "suspend fun `countStepsWithProgress`(`steps`: UInt, `listener`: (UInt) -> Unit) : UInt ="
+    ));
+    assert!(kotlin.contains("override fun `onProgress`(`step`: UInt) = uniffiSend(`step`)"));
+    assert!(kotlin.contains("import kotlinx.coroutines.channels.Channel"));
+}
+
+#[test]
+fn test_enum_methods() {
+    const UDL: &str = r#"
+        namespace test {};
+        [Enum]
+        interface Shape {
+            Circle(double radius);
+            Point();
+        };
+    "#;
+    let ci = ci_with_metadata(
+        UDL,
+        vec![method_metadata("Shape", "area", Some(uniffi_meta::Type::Float64)).into()],
+    );
+    let kotlin = generate_bindings(&Default::default(), &ci).unwrap();
+    assert!(kotlin.contains("fun `area`(): Double ="));
+    assert!(kotlin.contains(
+        "UniffiLib.INSTANCE.uniffi_crate_name_fn_method_shape_area(FfiConverterTypeShape.lower(this),"
+    ));
+}
+
+#[test]
+fn test_consuming_methods() {
+    const UDL: &str = r#"
+        namespace test {};
+        interface Builder {
+            constructor();
+            void append(string part);
+        };
+    "#;
+    let ci = ci_with_metadata(
+        UDL,
+        vec![uniffi_meta::MethodMetadata {
+            takes_self_by_value: true,
+            ..method_metadata("Builder", "build", Some(uniffi_meta::Type::String))
+        }
+        .into()],
+    );
+    let kotlin = generate_bindings(&Default::default(), &ci).unwrap();
+    assert!(contains_code(
+        &kotlin,
+        "override fun `build`(): String = callWithConsumedPointer {"
+    ));
+    assert!(contains_code(
+        &kotlin,
+        "override fun `append`(`part`: String) = callWithPointer {"
+    ));
+}
+
+#[test]
+fn test_cancellable() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async, Cancellable]
+            u32 download(string url);
+            [Async]
+            u32 upload(string url);
+        };
+    "#;
+    // The handle type is generated, along with a variant of the function returning it.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("class UniffiCancellableHandle<T> internal constructor(\n"));
+    assert!(kotlin
+        .contains("fun `downloadCancellable`(`url`: String): UniffiCancellableHandle<UInt> {\n"));
+    assert!(kotlin.contains(
+        "{ future -> UniffiLib.INSTANCE.ffi_crate_name_rust_future_cancel_u32(future) },\n"
+    ));
+    assert!(!kotlin.contains("uploadCancellable"));
+}
+
+#[test]
+fn test_test_stubs() {
+    const UDL: &str = r#"
+        namespace test {
+            void register(Listener listener);
+        };
+        [Error]
+        enum StoreError { "NotFound" };
+        dictionary Entry { string key; };
+        callback interface Listener {
+            u32 on_event(string name);
+            [Throws=StoreError]
+            Entry lookup(string key);
+        };
+    "#;
+    // The stubs are only written when they're enabled, next to the bindings.
+    let paths = |config: &str| -> Vec<_> {
+        generate_from_udl(UDL, config, crate::bindings::kotlin::render_bindings)
+            .into_iter()
+            .map(|(path, _)| path.into_string())
+            .collect()
+    };
+    assert_eq!(paths(""), ["uniffi/test.kt"]);
+    assert_eq!(
+        paths("generate_test_stubs = true"),
+        ["uniffi/test.kt", "uniffi/testTestStubs.kt"]
+    );
+
+    let stubs = generate_from_udl(UDL, "generate_test_stubs = true", generate_test_stubs);
+    let stubs = stubs.split_once("\n/**\n").unwrap().1;
+    assert_eq!(
+        stubs,
+        r#" * Thrown by a stub method which has no default value to return.  Override the method to stub it.
+ */
+public class UniffiNotStubbedException(method: String) : Exception("`$method` is not stubbed")
+
+/**
+ * A stub [Listener] for tests, which records each call in [calls].
+ *
+ * Methods return a default value, like `0`, `null` or an empty list, or throw
+ * [UniffiNotStubbedException] if their return type doesn't have one.  Override a method to stub it.
+ */
+public open class ListenerStub : Listener {
+    /** Each call to this stub, in order: the method name and its arguments. */
+    public val calls: MutableList<Pair<String, List<Any?>>> =
+        java.util.Collections.synchronizedList(mutableListOf())
+
+    override fun `onEvent`(`name`: String): UInt {
+        calls.add(Pair("onEvent", listOf<Any?>(`name`)))
+        return 0u
+    }
+
+    override fun `lookup`(`key`: String): Entry {
+        calls.add(Pair("lookup", listOf<Any?>(`key`)))
+        throw UniffiNotStubbedException("lookup")
+    }
+}
+
+"#
+    );
+}
+
+#[test]
+fn test_default_on_none() {
+    const UDL: &str = r#"
+        namespace test {
+            [DefaultOnNone="7"]
+            u32? find_count(string key);
+            [DefaultOnNone="unknown"]
+            string? find_name();
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(contains_code(
+        &kotlin,
+        "fun `findCountOrDefault`(`key`: String): UInt =\n    `findCount`(`key`) ?: 7u\n"
+    ));
+    assert!(contains_code(
+        &kotlin,
+        "fun `findNameOrDefault`(): String =\n    `findName`() ?: \"unknown\"\n"
+    ));
+}
+
+#[test]
+fn test_ownership_docs() {
+    const UDL: &str = r#"
+        namespace test {};
+        /// A node in a tree.
+        interface Node {
+            /// Create a root node.
+            constructor(string name);
+            /// The parent of this node.
+            Node? parent();
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "ownership_docs = true", generate_bindings);
+    assert!(kotlin.contains(
+        "/**
+ * A node in a tree.
+ *
+ * Each instance owns a reference to a Rust `Arc<Node>`, which is released when `destroy()` or `close()` is called. The Rust object is freed when its last reference is released.
+ */
+open class Node"
+    ));
+    assert!(kotlin.contains("/**
+     * Create a root node.
+     *
+     * The new instance owns a reference to the Rust object, which is released when `destroy()` or `close()` is called.
+     */
+    constructor(`name`: String)"));
+    assert!(kotlin.contains("/**
+     * The parent of this node.
+     *
+     * Rust borrows this object for the duration of the call. Returned objects own a reference to their Rust object, which is released when `destroy()` or `close()` is called.
+     */override fun `parent`()"));
+    // The interface only documents the methods.
+    assert!(contains_code(
+        &kotlin,
+        "    /**\n     * The parent of this node.\n     */\n    fun `parent`()"
+    ));
+
+    // Without the config, only the docstrings are there.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(!kotlin.contains("owns a reference"));
+}
+
+#[test]
+fn test_unsigned_style() {
+    const UDL: &str = r#"
+        namespace test {
+            u64 mix(u32 a, u64 b, optional u32 c = 4294967295, optional u64 d = 9223372036854775808);
+        };
+    "#;
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains(
+        "fun `mix`(`a`: UInt, `b`: ULong, `c`: UInt = 4294967295u, `d`: ULong = 9223372036854775808uL): ULong {"
+    ));
+    assert!(kotlin.contains("public object FfiConverterUInt: FfiConverter<UInt, Int> {"));
+    assert!(kotlin.contains("return value.toUInt()\n"));
+
+    let kotlin = generate_from_udl(UDL, "unsigned_style = \"signed\"", generate_bindings);
+    // The defaults keep their bits, so they're negative.
+    assert!(kotlin.contains(
+        "fun `mix`(`a`: Int, `b`: Long, `c`: Int = -1, `d`: Long = Long.MIN_VALUE): Long {"
+    ));
+    assert!(kotlin.contains("public object FfiConverterUInt: FfiConverter<Int, Int> {"));
+    assert!(kotlin.contains("public object FfiConverterULong: FfiConverter<Long, Long> {"));
+    assert!(!kotlin.contains("toUInt()"));
+    assert!(!kotlin.contains("toULong()"));
+}
+
+#[test]
+fn test_out_params() {
+    let out_param = |name: &str, ty| uniffi_meta::FnParamMetadata {
+        out: true,
+        ..uniffi_meta::FnParamMetadata::simple(name, ty)
+    };
+    let ci = ci_with_metadata(
+        "namespace test {};",
+        vec![
+            fn_metadata(
+                "divide",
+                vec![
+                    uniffi_meta::FnParamMetadata::simple("dividend", uniffi_meta::Type::UInt32),
+                    uniffi_meta::FnParamMetadata::simple("divisor", uniffi_meta::Type::UInt32),
+                    out_param("remainder", uniffi_meta::Type::UInt32),
+                ],
+                Some(uniffi_meta::Type::UInt32),
+            )
+            .into(),
+            fn_metadata(
+                "trim",
+                vec![out_param("text", uniffi_meta::Type::String)],
+                None,
+            )
+            .into(),
+        ],
+    );
+    let kotlin = generate_bindings(&Default::default(), &ci).unwrap();
+    // Several outputs are returned together, a single one is returned directly.
+    assert!(contains_code(
+        &kotlin,
+        "data class DivideOutputs(val `value`: UInt, val `remainder`: UInt,)"
+    ));
+    assert!(kotlin.contains(
+        "fun `divide`(`dividend`: UInt, `divisor`: UInt, `remainder`: UInt): DivideOutputs {"
+    ));
+    assert!(kotlin.contains("fun `trim`(`text`: String): String {"));
+    assert!(contains_code(
+        &kotlin,
+        "}) { buf -> FfiConverterString.read(buf) }"
+    ));
+}
+
+#[test]
+fn test_callback_interface_gone() {
+    const UDL: &str = r#"
+        namespace test {
+            void register(Listener listener);
+        };
+        callback interface Listener {
+            void notify();
+        };
+    "#;
+    // A handle which isn't in the handle map is reported to Rust, rather than thrown.
+    let kotlin = generate_from_udl(UDL, "", generate_bindings);
+    assert!(kotlin.contains("internal const val UNIFFI_CALLBACK_INTERFACE_GONE = 3"));
+    assert!(contains_code(
+        &kotlin,
+        "val cb = FfiConverterTypeListener.handleMap.getOrNull(handle) ?: return UNIFFI_CALLBACK_INTERFACE_GONE"
+    ));
+}
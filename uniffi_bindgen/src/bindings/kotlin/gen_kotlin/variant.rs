@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use super::{AsCodeType, CodeType, KotlinCodeOracle};
+use super::{AsCodeType, CodeType, Config, KotlinCodeOracle};
 use crate::interface::{ComponentInterface, Variant};
 
 #[derive(Debug)]
@@ -11,7 +11,7 @@ pub(super) struct VariantCodeType {
 }
 
 impl CodeType for VariantCodeType {
-    fn type_label(&self, ci: &ComponentInterface) -> String {
+    fn type_label(&self, ci: &ComponentInterface, _config: &Config) -> String {
         KotlinCodeOracle.class_name(ci, self.v.name())
     }
 
@@ -4,11 +4,10 @@
 
 use anyhow::Result;
 use camino::{Utf8Path, Utf8PathBuf};
-use fs_err as fs;
 use std::process::Command;
 
 pub mod gen_kotlin;
-pub use gen_kotlin::{generate_bindings, Config};
+pub use gen_kotlin::{generate_bindings, generate_common_bindings, generate_test_stubs, Config};
 mod test;
 
 use super::super::interface::ComponentInterface;
@@ -20,21 +19,79 @@ pub fn write_bindings(
     out_dir: &Utf8Path,
     try_format_code: bool,
 ) -> Result<()> {
-    let mut kt_file = full_bindings_path(config, out_dir);
-    fs::create_dir_all(&kt_file)?;
-    kt_file.push(format!("{}.kt", ci.namespace()));
-    fs::write(&kt_file, generate_bindings(config, ci)?)?;
+    write_bindings_with_formatter(config, ci, out_dir, try_format_code, None)
+}
+
+/// Like [`write_bindings`], but passes the generated source through `formatter` before it's
+/// written.
+pub fn write_bindings_with_formatter(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+    formatter: Option<&super::Formatter>,
+) -> Result<()> {
+    let mut kt_files = Vec::new();
+    for (path, contents) in render_bindings(config, ci)? {
+        let kt_file = out_dir.join(path);
+        super::write_source_file(&kt_file, contents, formatter)?;
+        kt_files.push(kt_file);
+    }
     if try_format_code {
-        if let Err(e) = Command::new("ktlint").arg("-F").arg(&kt_file).output() {
-            println!(
-                "Warning: Unable to auto-format {} using ktlint: {e:?}",
-                kt_file.file_name().unwrap(),
-            );
+        for kt_file in kt_files {
+            if let Err(e) = Command::new("ktlint").arg("-F").arg(&kt_file).output() {
+                println!(
+                    "Warning: Unable to auto-format {} using ktlint: {e:?}",
+                    kt_file.file_name().unwrap(),
+                );
+            }
         }
     }
     Ok(())
 }
 
+/// Render the Kotlin bindings, keyed by their paths relative to the output directory.
+pub fn render_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<Vec<(Utf8PathBuf, String)>> {
+    let namespace = ci.namespace();
+    // Kotlin Multiplatform projects keep each source set in its own directory.
+    let platform_dir = if config.kmp() {
+        Utf8Path::new("jvmMain").join("kotlin")
+    } else {
+        Utf8PathBuf::new()
+    };
+    let mut sources = Vec::new();
+    if config.kmp() {
+        sources.push((
+            Utf8Path::new("commonMain").join("kotlin"),
+            format!("{namespace}.kt"),
+            generate_common_bindings(config, ci)?,
+        ));
+    }
+    sources.push((
+        platform_dir.clone(),
+        format!("{namespace}.kt"),
+        generate_bindings(config, ci)?,
+    ));
+    if config.generate_test_stubs() {
+        sources.push((
+            platform_dir,
+            format!("{namespace}TestStubs.kt"),
+            generate_test_stubs(config, ci)?,
+        ));
+    }
+    Ok(sources
+        .into_iter()
+        .map(|(source_dir, file_name, contents)| {
+            let mut kt_file = full_bindings_path(config, &source_dir);
+            kt_file.push(file_name);
+            (kt_file, contents)
+        })
+        .collect())
+}
+
 fn full_bindings_path(config: &Config, out_dir: &Utf8Path) -> Utf8PathBuf {
     let package_path: Utf8PathBuf = config.package_name().split('.').collect();
     Utf8PathBuf::from(out_dir).join(package_path)
@@ -12,6 +12,21 @@ internal object uniffiRustFutureContinuationCallback: UniFffiRustFutureContinuat
     }
 }
 
+internal suspend fun uniffiPollRustFuture(
+    rustFuture: Pointer,
+    pollFunc: (Pointer, UniFffiRustFutureContinuationCallbackType, USize) -> Unit
+) {
+    do {
+        val pollResult = suspendCancellableCoroutine<Byte> { continuation ->
+            pollFunc(
+                rustFuture,
+                uniffiRustFutureContinuationCallback,
+                uniffiContinuationHandleMap.insert(continuation)
+            )
+        }
+    } while (pollResult != UNIFFI_RUST_FUTURE_POLL_READY);
+}
+
 internal suspend fun<T, F, E: Exception> uniffiRustCallAsync(
     rustFuture: Pointer,
     pollFunc: (Pointer, UniFffiRustFutureContinuationCallbackType, USize) -> Unit,
@@ -21,16 +36,7 @@ internal suspend fun<T, F, E: Exception> uniffiRustCallAsync(
     errorHandler: UniffiRustCallStatusErrorHandler<E>
 ): T {
     try {
-        do {
-            val pollResult = suspendCancellableCoroutine<Byte> { continuation ->
-                pollFunc(
-                    rustFuture,
-                    uniffiRustFutureContinuationCallback,
-                    uniffiContinuationHandleMap.insert(continuation)
-                )
-            }
-        } while (pollResult != UNIFFI_RUST_FUTURE_POLL_READY);
-
+        uniffiPollRustFuture(rustFuture, pollFunc)
         return liftFunc(
             uniffiRustCallWithError(errorHandler, { status -> completeFunc(rustFuture, status) })
         )
@@ -38,4 +44,113 @@ internal suspend fun<T, F, E: Exception> uniffiRustCallAsync(
         freeFunc(rustFuture)
     }
 }
+{%- if ci.has_cancellable_fns() %}
+
+/**
+ * A call to an async function marked with `[Cancellable]`.
+ *
+ * [await] waits for the call to complete, and can only be called once.  Calling [cancel], from
+ * any thread, cancels the Rust future, which makes [await] throw `CancellationException`.
+ */
+class UniffiCancellableHandle<T> internal constructor(
+    private val rustFuture: Pointer,
+    private val pollFunc: (Pointer, UniFffiRustFutureContinuationCallbackType, USize) -> Unit,
+    private val completeFunc: (Pointer) -> T,
+    private val freeFunc: (Pointer) -> Unit,
+    private val cancelFunc: (Pointer) -> Unit
+) {
+    // Guarded by `this`, so that the Rust future is never cancelled after it's been freed.
+    private var awaited = false
+    private var cancelled = false
+    private var freed = false
+
+    fun cancel() = synchronized(this) {
+        if (!cancelled && !freed) {
+            cancelled = true
+            cancelFunc(rustFuture)
+        }
+    }
+
+    suspend fun await(): T {
+        synchronized(this) {
+            check(!awaited) { "UniffiCancellableHandle can only be awaited once" }
+            awaited = true
+        }
+        try {
+            uniffiPollRustFuture(rustFuture, pollFunc)
+            // A cancelled future completes with the `Cancelled` call status, which throws
+            // `CancellationException`.
+            return completeFunc(rustFuture)
+        } finally {
+            synchronized(this) {
+                freed = true
+                freeFunc(rustFuture)
+            }
+        }
+    }
+}
 
+internal fun<T, F, E: Exception> uniffiRustCallCancellable(
+    rustFuture: Pointer,
+    pollFunc: (Pointer, UniFffiRustFutureContinuationCallbackType, USize) -> Unit,
+    completeFunc: (Pointer, UniffiRustCallStatus) -> F,
+    freeFunc: (Pointer) -> Unit,
+    cancelFunc: (Pointer) -> Unit,
+    liftFunc: (F) -> T,
+    errorHandler: UniffiRustCallStatusErrorHandler<E>
+): UniffiCancellableHandle<T> = UniffiCancellableHandle(
+    rustFuture,
+    pollFunc,
+    { future -> liftFunc(uniffiRustCallWithError(errorHandler, { status -> completeFunc(future, status) })) },
+    freeFunc,
+    cancelFunc
+)
+{%- endif %}
+
+
+// The delay before the first retry of a function marked with `[Retry=N]`, which doubles after
+// each retry.
+internal const val UNIFFI_RETRY_INITIAL_DELAY_MILLIS = 100L
+
+internal suspend fun<T> uniffiRetryAsync(
+    retries: Int,
+    isRetryable: (Exception) -> Boolean,
+    call: suspend () -> T
+): T {
+    var delayMillis = UNIFFI_RETRY_INITIAL_DELAY_MILLIS
+    repeat(retries) {
+        try {
+            return call()
+        } catch (e: Exception) {
+            if (!isRetryable(e)) {
+                throw e
+            }
+        }
+        kotlinx.coroutines.delay(delayMillis)
+        delayMillis *= 2
+    }
+    return call()
+}
+{%- if ci.has_progress_fns() %}
+
+// Calls an async function with a `[Progress]` argument.  The listener which `call` passes to the
+// function sends its events to a channel, which passes them to `onProgress` in a coroutine on the
+// caller's dispatcher rather than on the thread Rust reports them from.  All of the events have been
+// passed when this returns.
+internal suspend fun<T, E> uniffiCallWithProgress(
+    onProgress: (E) -> Unit,
+    call: suspend (send: (E) -> Unit) -> T
+): T = coroutineScope {
+    val events = Channel<E>(Channel.UNLIMITED)
+    launch {
+        for (event in events) {
+            onProgress(event)
+        }
+    }
+    try {
+        call { event -> events.trySend(event) }
+    } finally {
+        events.close()
+    }
+}
+{%- endif %}
@@ -0,0 +1,52 @@
+// Pass byte segments to Rust without concatenating them, by copying each one onto a handle from
+// Rust.  Rust takes over the handle when it's passed as the argument.
+private fun uniffiLowerByteSegments(segments: List<ByteArray>): Long {
+    val handle = uniffiRustCall { _status ->
+        UniffiLib.INSTANCE.{{ ci.ffi_byte_segments_new().name() }}(_status)
+    }
+    try {
+        for (segment in segments) {
+            val bytes = ForeignBytes.ByValue()
+            bytes.len = segment.size
+            // JNA can't allocate empty memory, and Rust accepts a null pointer for an empty segment.
+            if (segment.isNotEmpty()) {
+                val memory = com.sun.jna.Memory(segment.size.toLong())
+                memory.write(0, segment, 0, segment.size)
+                bytes.data = memory
+            }
+            uniffiRustCall { _status ->
+                UniffiLib.INSTANCE.{{ ci.ffi_byte_segments_push().name() }}(handle, bytes, _status)
+            }
+        }
+    } catch (e: Throwable) {
+        uniffiRustCall { _status ->
+            UniffiLib.INSTANCE.{{ ci.ffi_byte_segments_free().name() }}(handle, _status)
+        }
+        throw e
+    }
+    return handle
+}
+
+// Take the byte segments Rust returns, from the handle returned by the FFI function.  Each segment
+// is returned in its own buffer.
+private fun uniffiLiftByteSegments(handle: Long): List<ByteArray> {
+    try {
+        val count = uniffiRustCall { _status ->
+            UniffiLib.INSTANCE.{{ ci.ffi_byte_segments_len().name() }}(handle, _status)
+        }
+        return List(count.toInt()) {
+            val buf = uniffiRustCall { _status ->
+                UniffiLib.INSTANCE.{{ ci.ffi_byte_segments_next().name() }}(handle, _status)
+            }
+            try {
+                ByteArray(buf.len).also { buf.asByteBuffer()?.get(it) }
+            } finally {
+                RustBuffer.free(buf)
+            }
+        }
+    } finally {
+        uniffiRustCall { _status ->
+            UniffiLib.INSTANCE.{{ ci.ffi_byte_segments_free().name() }}(handle, _status)
+        }
+    }
+}
@@ -4,7 +4,6 @@
 internal class {{ callback_handler_class }} : ForeignCallback {
     @Suppress("TooGenericExceptionCaught")
     override fun invoke(handle: UniffiHandle, method: Int, argsData: Pointer, argsLen: Int, outBuf: RustBufferByReference): Int {
-        val cb = {{ ffi_converter_name }}.handleMap.get(handle)
         return when (method) {
             IDX_CALLBACK_FREE -> {
                 {{ ffi_converter_name }}.handleMap.remove(handle)
@@ -16,6 +15,8 @@ internal class {{ callback_handler_class }} : ForeignCallback {
             {% for meth in methods.iter() -%}
             {% let method_name = format!("invoke_{}", meth.name())|fn_name -%}
             {{ loop.index }} -> {
+                // The handle is missing if it's already been freed, which Rust reports as `CallbackInterfaceGone`.
+                val cb = {{ ffi_converter_name }}.handleMap.getOrNull(handle) ?: return UNIFFI_CALLBACK_INTERFACE_GONE
                 // Call the method, write to outBuf and return a status code
                 // See docs of ForeignCallback in `uniffi_core/src/ffi/foreigncallbacks.rs` for info
                 try {
@@ -86,7 +87,7 @@ internal class {{ callback_handler_class }} : ForeignCallback {
         {%- when Some(error_type) %}
         fun makeCallAndHandleError()  : Int = try {
             makeCall()
-        } catch (e: {{ error_type|type_name(ci) }}) {
+        } catch (e: {{ error_type|type_name(ci, config) }}) {
             // Expected error, serialize it into outBuf
             outBuf.setValue({{ error_type|ffi_converter_name }}.lowerIntoRustBuffer(e))
             UNIFFI_CALLBACK_ERROR
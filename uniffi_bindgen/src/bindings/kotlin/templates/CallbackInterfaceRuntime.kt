@@ -7,7 +7,8 @@ internal class ConcurrentHandleMap<T>(
     private val leftMap: MutableMap<UniffiHandle, T> = mutableMapOf(),
 ) {
     private val lock = java.util.concurrent.locks.ReentrantLock()
-    private val currentHandle = AtomicLong(0L)
+    // Handle 0 is never handed out, so Rust can reject it.
+    private val currentHandle = AtomicLong(1L)
     private val stride = 1L
 
     fun insert(obj: T): UniffiHandle =
@@ -22,6 +23,10 @@ internal class ConcurrentHandleMap<T>(
         leftMap[handle] ?: throw InternalException("No callback in handlemap; this is a Uniffi bug")
     }
 
+    fun getOrNull(handle: UniffiHandle): T? = lock.withLock {
+        leftMap[handle]
+    }
+
     fun delete(handle: UniffiHandle) {
         this.remove(handle)
     }
@@ -43,6 +48,7 @@ internal const val IDX_CALLBACK_FREE = 0
 internal const val UNIFFI_CALLBACK_SUCCESS = 0
 internal const val UNIFFI_CALLBACK_ERROR = 1
 internal const val UNIFFI_CALLBACK_UNEXPECTED_ERROR = 2
+internal const val UNIFFI_CALLBACK_INTERFACE_GONE = 3
 
 public abstract class FfiConverterCallbackInterface<CallbackInterface>: FfiConverter<CallbackInterface, UniffiHandle> {
     internal val handleMap = ConcurrentHandleMap<CallbackInterface>()
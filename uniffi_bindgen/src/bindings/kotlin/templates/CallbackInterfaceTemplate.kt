@@ -2,9 +2,10 @@
 {%- let callback_handler_class = format!("UniffiCallbackInterface{}", name) %}
 {%- let callback_handler_obj = format!("uniffiCallbackInterface{}", name) %}
 {%- let ffi_init_callback = cbi.ffi_init_callback() %}
-{%- let interface_name = cbi|type_name(ci) %}
+{%- let interface_name = cbi|type_name(ci, config) %}
 {%- let methods = cbi.methods() %}
 {%- let interface_docstring = cbi.docstring() %}
+{%- let interface_parent = "" %}
 
 {% include "Interface.kt" %}
 {% include "CallbackInterfaceImpl.kt" %}
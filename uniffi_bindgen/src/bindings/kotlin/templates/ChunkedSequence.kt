@@ -0,0 +1,20 @@
+// Reassemble a sequence that Rust returns in chunks, from the handle returned by the FFI function.
+// An empty chunk means the sequence is exhausted.
+private fun <T> uniffiLiftChunkedSequence(handle: Long, converter: FfiConverterRustBuffer<List<T>>): List<T> {
+    try {
+        val items = ArrayList<T>()
+        while (true) {
+            val chunk = converter.lift(uniffiRustCall { _status ->
+                UniffiLib.INSTANCE.{{ ci.ffi_chunked_sequence_next().name() }}(handle, _status)
+            })
+            if (chunk.isEmpty()) {
+                return items
+            }
+            items.addAll(chunk)
+        }
+    } finally {
+        uniffiRustCall { _status ->
+            UniffiLib.INSTANCE.{{ ci.ffi_chunked_sequence_free().name() }}(handle, _status)
+        }
+    }
+}
@@ -0,0 +1,9 @@
+{%- match func.return_type() %}
+{%- when Some with (return_type) %}
+
+{% call kt::docstring(func, 0) %}
+val {{ func.name()|var_name }}: {{ return_type|type_name(ci, config) }} by lazy {
+    {{ return_type|lift_fn }}({% call kt::to_ffi_call(func) %})
+}
+{%- when None %}
+{%- endmatch %}
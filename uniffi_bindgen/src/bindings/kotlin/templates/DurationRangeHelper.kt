@@ -0,0 +1,28 @@
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: ByteBuffer): {{ type_name }} {
+        val start = FfiConverterDuration.read(buf)
+        val end = FfiConverterDuration.read(buf)
+        if (buf.get() != {% if inclusive %}1{% else %}0{% endif %}.toByte()) {
+            throw InternalException("Unexpected {% if inclusive %}exclusive{% else %}inclusive{% endif %} range")
+        }
+        {%- if inclusive %}
+        return start..end
+        {%- else %}
+        return start.rangeUntil(end)
+        {%- endif %}
+    }
+
+    // Both bounds, then 1 byte for whether the end is included
+    override fun allocationSize(value: {{ type_name }}) = 25
+
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
+        FfiConverterDuration.write(value.start, buf)
+        {%- if inclusive %}
+        FfiConverterDuration.write(value.endInclusive, buf)
+        buf.put(1)
+        {%- else %}
+        FfiConverterDuration.write(value.endExclusive, buf)
+        buf.put(0)
+        {%- endif %}
+    }
+}
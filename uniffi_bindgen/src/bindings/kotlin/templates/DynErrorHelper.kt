@@ -0,0 +1,29 @@
+/**
+ * A dynamically typed error thrown by Rust.
+ *
+ * `message` is the `Display` string of the Rust error, and `causeChain` holds the `Display`
+ * strings of its chain of sources, starting with the immediate source.
+ */
+class DynException(message: String, val causeChain: List<String>) : Exception(message) {
+    companion object ErrorHandler : UniffiRustCallStatusErrorHandler<DynException> {
+        override fun lift(error_buf: RustBuffer.ByValue): DynException = FfiConverterDynError.lift(error_buf)
+    }
+}
+
+public object FfiConverterDynError: FfiConverterRustBuffer<DynException> {
+    override fun read(buf: ByteBuffer): DynException {
+        val message = FfiConverterString.read(buf)
+        val causeChain = FfiConverterSequenceString.read(buf)
+        return DynException(message, causeChain)
+    }
+
+    override fun allocationSize(value: DynException) = (
+        FfiConverterString.allocationSize(value.message ?: "") +
+        FfiConverterSequenceString.allocationSize(value.causeChain)
+    )
+
+    override fun write(value: DynException, buf: ByteBuffer) {
+        FfiConverterString.write(value.message ?: "", buf)
+        FfiConverterSequenceString.write(value.causeChain, buf)
+    }
+}
@@ -2,60 +2,81 @@
 // Kotlin's `enum class` construct doesn't support variants with associated data,
 // but is a little nicer for consumers than its `sealed class` enum pattern.
 // So, we switch here, using `enum class` for enums with no associated data
-// and `sealed class` for the general case.
+// and `sealed class` (or `sealed interface`, if configured) for the general case.
+// Non-exhaustive enums need the latter too, for the `Unknown` variant holding the discriminant.
 #}
 
-{%- if e.is_flat() %}
+{%- if e.is_flat() && !e.has_unknown_variant() %}
 
-{%- call kt::docstring(e, 0) %}
+{%- call kt::type_docstring(e, 0) %}
 enum class {{ type_name }} {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
+    {%- call kt::deprecated(variant, 4) %}
     {{ variant|variant_name }}{% if loop.last %};{% else %},{% endif %}
     {%- endfor %}
+    {% call kt::enum_methods(e) %}
     companion object
 }
 
-public object {{ e|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
-    override fun read(buf: ByteBuffer) = try {
-        {{ type_name }}.values()[buf.getInt() - 1]
+// Enums without associated data are passed across the FFI as the `Int` that identifies their variant.
+public object {{ e|ffi_converter_name }}: FfiConverter<{{ type_name }}, Int> {
+    {%- match e.default_variant() %}
+    {%- when Some with (default) %}
+    override fun lift(value: Int) = {{ type_name }}.values().getOrElse(value - 1) { {{ type_name }}.{{ default|variant_name }} }
+    {%- when None %}
+    override fun lift(value: Int) = try {
+        {{ type_name }}.values()[value - 1]
     } catch (e: IndexOutOfBoundsException) {
         throw RuntimeException("invalid enum value, something is very wrong!!", e)
     }
+    {%- endmatch %}
+
+    override fun lower(value: {{ type_name }}) = value.ordinal + 1
+
+    override fun read(buf: ByteBuffer) = lift({% call kt::read_discriminant(e) %})
 
-    override fun allocationSize(value: {{ type_name }}) = 4
+    override fun allocationSize(value: {{ type_name }}) = {{ e.discriminant_width() }}
 
     override fun write(value: {{ type_name }}, buf: ByteBuffer) {
-        buf.putInt(value.ordinal + 1)
+        {% call kt::write_discriminant(e, "lower(value)") %}
     }
 }
 
 {% else %}
 
-{%- call kt::docstring(e, 0) %}
-sealed class {{ type_name }}{% if contains_object_references %}: Disposable {% endif %} {
+{%- call kt::type_docstring(e, 0) %}
+{% if config.generate_sealed_interfaces() %}sealed interface{% else %}sealed class{% endif %} {{ type_name }}{% if contains_object_references %}: Disposable {% endif %} {
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
+    {%- call kt::deprecated(variant, 4) %}
     {% if !variant.has_fields() -%}
-    object {{ variant|type_name(ci) }} : {{ type_name }}()
+    object {{ variant|type_name(ci, config) }} : {{ type_name }}{% if !config.generate_sealed_interfaces() %}(){% endif %}
     {% else -%}
-    data class {{ variant|type_name(ci) }}(
+    data class {{ variant|type_name(ci, config) }}(
         {% for field in variant.fields() -%}
         {%- call kt::docstring(field, 8) %}
-        val {{ field.name()|var_name }}: {{ field|type_name(ci) }}{% if loop.last %}{% else %}, {% endif %}
+        val {{ field.name()|var_name }}: {{ field|type_name(ci, config) }}{% if loop.last %}{% else %}, {% endif %}
         {% endfor -%}
-    ) : {{ type_name }}() {
+    ) : {{ type_name }}{% if !config.generate_sealed_interfaces() %}(){% endif %} {
         companion object
     }
     {%- endif %}
     {% endfor %}
+    {%- if e.has_unknown_variant() %}
+    /**
+     * A variant which these bindings don't know about, because it was added to the
+     * non-exhaustive enum after they were generated.
+     */
+    data class Unknown(val discriminant: Int) : {{ type_name }}{% if !config.generate_sealed_interfaces() %}(){% endif %}
+    {%- endif %}
 
     {% if contains_object_references %}
     @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
     override fun destroy() {
         when(this) {
             {%- for variant in e.variants() %}
-            is {{ type_name }}.{{ variant|type_name(ci) }} -> {
+            is {{ type_name }}.{{ variant|type_name(ci, config) }} -> {
                 {%- if variant.has_fields() %}
                 {% call kt::destroy_fields(variant) %}
                 {% else -%}
@@ -63,53 +84,101 @@ sealed class {{ type_name }}{% if contains_object_references %}: Disposable {% e
                 {%- endif %}
             }
             {%- endfor %}
+            {%- if e.has_unknown_variant() %}
+            is {{ type_name }}.Unknown -> {
+                // Nothing to destroy
+            }
+            {%- endif %}
         }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
     }
     {% endif %}
+    {% call kt::enum_methods(e) %}
     companion object
 }
 
+{%- if e.is_flat() %}
+
+// Enums without associated data are passed across the FFI as the `Int` that identifies their variant.
+public object {{ e|ffi_converter_name }}: FfiConverter<{{ type_name }}, Int> {
+    override fun lift(value: Int) = when(value) {
+        {%- for variant in e.variants() %}
+        {{ loop.index }} -> {{ type_name }}.{{ variant|type_name(ci, config) }}
+        {%- endfor %}
+        else -> {{ type_name }}.Unknown(value)
+    }
+
+    override fun lower(value: {{ type_name }}) = when(value) {
+        {%- for variant in e.variants() %}
+        is {{ type_name }}.{{ variant|type_name(ci, config) }} -> {{ loop.index }}
+        {%- endfor %}
+        is {{ type_name }}.Unknown -> value.discriminant
+    }
+
+    override fun read(buf: ByteBuffer) = lift({% call kt::read_discriminant(e) %})
+
+    override fun allocationSize(value: {{ type_name }}) = {{ e.discriminant_width() }}
+
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
+        {% call kt::write_discriminant(e, "lower(value)") %}
+    }
+}
+{%- else %}
+
 public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}>{
     override fun read(buf: ByteBuffer): {{ type_name }} {
-        return when(buf.getInt()) {
+        return when({% if e.has_unknown_variant() %}val variant = {% endif %}{% call kt::read_discriminant(e) %}) {
             {%- for variant in e.variants() %}
-            {{ loop.index }} -> {{ type_name }}.{{ variant|type_name(ci) }}{% if variant.has_fields() %}(
+            {{ loop.index }} -> {{ type_name }}.{{ variant|type_name(ci, config) }}{% if variant.has_fields() %}(
                 {% for field in variant.fields() -%}
                 {{ field|read_fn }}(buf),
                 {% endfor -%}
             ){%- endif -%}
             {%- endfor %}
+            {%- if e.has_unknown_variant() %}
+            else -> {{ type_name }}.Unknown(variant)
+            {%- else %}
             else -> throw RuntimeException("invalid enum value, something is very wrong!!")
+            {%- endif %}
         }
     }
 
     override fun allocationSize(value: {{ type_name }}) = when(value) {
         {%- for variant in e.variants() %}
-        is {{ type_name }}.{{ variant|type_name(ci) }} -> {
-            // Add the size for the Int that specifies the variant plus the size needed for all fields
+        is {{ type_name }}.{{ variant|type_name(ci, config) }} -> {
+            // Add the size for the discriminant that specifies the variant plus the size needed for all fields
             (
-                4
+                {{ e.discriminant_width() }}
                 {%- for field in variant.fields() %}
                 + {{ field|allocation_size_fn }}(value.{{ field.name()|var_name }})
                 {%- endfor %}
             )
         }
         {%- endfor %}
+        {%- if e.has_unknown_variant() %}
+        is {{ type_name }}.Unknown -> {{ e.discriminant_width() }}
+        {%- endif %}
     }
 
     override fun write(value: {{ type_name }}, buf: ByteBuffer) {
         when(value) {
             {%- for variant in e.variants() %}
-            is {{ type_name }}.{{ variant|type_name(ci) }} -> {
-                buf.putInt({{ loop.index }})
+            is {{ type_name }}.{{ variant|type_name(ci, config) }} -> {
+                {% call kt::write_discriminant(e, loop.index) %}
                 {%- for field in variant.fields() %}
                 {{ field|write_fn }}(value.{{ field.name()|var_name }}, buf)
                 {%- endfor %}
                 Unit
             }
             {%- endfor %}
+            {%- if e.has_unknown_variant() %}
+            is {{ type_name }}.Unknown -> {
+                {% call kt::write_discriminant(e, "value.discriminant") %}
+                Unit
+            }
+            {%- endif %}
         }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
     }
 }
+{%- endif %}
 
 {% endif %}
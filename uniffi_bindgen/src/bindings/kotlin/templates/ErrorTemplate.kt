@@ -1,12 +1,13 @@
-{%- let type_name = type_|type_name(ci) %}
+{%- let type_name = type_|type_name(ci, config) %}
 {%- let ffi_converter_name = type_|ffi_converter_name %}
 {%- let canonical_type_name = type_|canonical_name %}
 
 {% if e.is_flat() %}
-{%- call kt::docstring(e, 0) %}
+{%- call kt::type_docstring(e, 0) %}
 sealed class {{ type_name }}(message: String): Exception(message){% if contains_object_references %}, Disposable {% endif %} {
         {% for variant in e.variants() -%}
         {%- call kt::docstring(variant, 4) %}
+        {%- call kt::deprecated(variant, 4) %}
         class {{ variant|error_variant_name }}(message: String) : {{ type_name }}(message)
         {% endfor %}
 
@@ -15,15 +16,20 @@ sealed class {{ type_name }}(message: String): Exception(message){% if contains_
     }
 }
 {%- else %}
-{%- call kt::docstring(e, 0) %}
+{%- call kt::type_docstring(e, 0) %}
 sealed class {{ type_name }}: Exception(){% if contains_object_references %}, Disposable {% endif %} {
+    {%- for field in e.common_fields() %}
+    {%- call kt::docstring(field, 4) %}
+    abstract val {{ field.name()|var_name }}: {{ field|type_name(ci, config) }}
+    {%- endfor %}
     {% for variant in e.variants() -%}
     {%- call kt::docstring(variant, 4) %}
+    {%- call kt::deprecated(variant, 4) %}
     {%- let variant_name = variant|error_variant_name %}
     class {{ variant_name }}(
         {% for field in variant.fields() -%}
         {%- call kt::docstring(field, 8) %}
-        val {{ field.name()|var_name }}: {{ field|type_name(ci) }}{% if loop.last %}{% else %}, {% endif %}
+        {% if e.is_common_field(field) %}override {% endif %}val {{ field.name()|var_name }}: {{ field|type_name(ci, config) }}{% if loop.last %}{% else %}, {% endif %}
         {% endfor -%}
     ) : {{ type_name }}() {
         override val message
@@ -81,6 +87,9 @@ public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }
     override fun allocationSize(value: {{ type_name }}): Int {
         {%- if e.is_flat() %}
         return 4
+        {%- else if e.variants().is_empty() %}
+        // There are no variants, so there are no values to lower.
+        throw RuntimeException("{{ type_name }} has no variants")
         {%- else %}
         return when(value) {
             {%- for variant in e.variants() %}
@@ -97,6 +106,9 @@ public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }
     }
 
     override fun write(value: {{ type_name }}, buf: ByteBuffer) {
+        {%- if e.variants().is_empty() %}
+        throw RuntimeException("{{ type_name }} has no variants")
+        {%- else %}
         when(value) {
             {%- for variant in e.variants() %}
             is {{ type_name }}.{{ variant|error_variant_name }} -> {
@@ -108,6 +120,7 @@ public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }
             }
             {%- endfor %}
         }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+        {%- endif %}
     }
 
 }
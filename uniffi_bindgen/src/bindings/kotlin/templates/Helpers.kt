@@ -19,9 +19,37 @@ internal open class UniffiRustCallStatus : Structure() {
     fun isPanic(): Boolean {
         return code == 2.toByte()
     }
+
+    fun isCancelled(): Boolean {
+        return code == 3.toByte()
+    }
+
+    fun isWrongThread(): Boolean {
+        return code == 4.toByte()
+    }
+
+    fun isStatePoisoned(): Boolean {
+        return code == 5.toByte()
+    }
 }
 
 class InternalException(message: String) : Exception(message)
+{%- if ci.has_thread_bound_objects() %}
+
+/**
+ * Thrown when a method of a `[ThreadBound]` object is called on a thread other than the one which
+ * constructed it.
+ */
+class WrongThreadException(message: String) : IllegalStateException(message)
+{%- endif %}
+{%- if ci.has_on_poison_objects() %}
+
+/**
+ * Thrown when a method of an `[OnPoison=error]` object panics on state poisoned by an earlier
+ * call.
+ */
+class StatePoisonedException(message: String) : IllegalStateException(message)
+{%- endif %}
 
 // Each top-level error class has a companion object that can lift the error from the call status's rust buffer
 interface UniffiRustCallStatusErrorHandler<E> {
@@ -55,6 +83,18 @@ private fun<E: Exception> uniffiCheckCallStatus(errorHandler: UniffiRustCallStat
         } else {
             throw InternalException("Rust panic")
         }
+    } else if (status.isCancelled()) {
+        // Only async calls can be cancelled.  This is the exception `kotlinx.coroutines` uses
+        // for cancellation, which doesn't depend on it being available.
+        throw java.util.concurrent.CancellationException("The call was cancelled")
+    {%- if ci.has_thread_bound_objects() %}
+    } else if (status.isWrongThread()) {
+        throw WrongThreadException({{ Type::String.borrow()|lift_fn }}(status.error_buf))
+    {%- endif %}
+    {%- if ci.has_on_poison_objects() %}
+    } else if (status.isStatePoisoned()) {
+        throw StatePoisonedException({{ Type::String.borrow()|lift_fn }}(status.error_buf))
+    {%- endif %}
     } else {
         throw InternalException("Unknown rust call status: $status.code")
     }
@@ -73,6 +113,16 @@ private inline fun <U> uniffiRustCall(callback: (UniffiRustCallStatus) -> U): U
     return uniffiRustCallWithError(UniffiNullRustCallStatusErrorHandler, callback);
 }
 
+// Call a function that throws `E`, returning the error as the failure of a `Result` rather than
+// throwing it.  Any other exception is still thrown.
+private inline fun <U, reified E: Exception> uniffiResultOf(callback: () -> U): Result<U> {
+    return try {
+        Result.success(callback())
+    } catch (e: Exception) {
+        if (e is E) Result.failure(e) else throw e
+    }
+}
+
 // IntegerType that matches Rust's `usize` / C's `size_t`
 public class USize(value: Long = 0) : IntegerType(Native.SIZE_T_SIZE, value, true) {
     // This is needed to fill in the gaps of IntegerType's implementation of Number for Kotlin.
@@ -1,13 +1,29 @@
 {%- call kt::docstring_value(interface_docstring, 0) %}
-public interface {{ interface_name }} {
+public interface {{ interface_name }}{% if !interface_parent.is_empty() %} : {{ interface_parent }}{% endif %} {
     {% for meth in methods.iter() -%}
-    {%- call kt::docstring(meth, 4) %}
+    {%- call kt::callable_docstring(meth, 4) %}
+    {%- if config.java_interop() %}
+    {%- match meth.throws_type() %}
+    {%- when Some with (throwable) %}
+    @Throws({{ throwable|type_name(ci, config) }}::class)
+    {%- else %}
+    {%- endmatch %}
+    {%- endif %}
     {% if meth.is_async() -%}suspend {% endif -%}
     fun {{ meth.name()|fn_name }}({% call kt::arg_list_decl(meth) %})
     {%- match meth.return_type() -%}
-    {%- when Some with (return_type) %}: {{ return_type|type_name(ci) -}}
+    {%- when Some with (return_type) %}: {{ return_type|type_name(ci, config) -}}
     {%- else -%}
     {%- endmatch %}
+    {%- if meth.has_default() %} =
+        {%- call kt::to_ffi_default_call(meth) %}
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}.let {
+            {{ return_type|lift_fn }}(it)
+        }
+        {%- else %}
+        {%- endmatch %}
+    {%- endif %}
     {% endfor %}
     companion object
 }
@@ -0,0 +1,66 @@
+{%- match config.file_header() %}{% when Some with (header) %}{{ header }}
+{% when None %}{% endmatch -%}
+{% if config.hash_header() %}// {{ ci.hash_header() }}
+{% endif -%}
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+{%- call kt::docstring_value(ci.namespace_docstring(), 0) %}
+
+@file:Suppress("NAME_SHADOWING")
+
+package {{ config.package_name() }};
+
+// The `expect` declarations of the public interface, for the common source set of a Kotlin
+// Multiplatform project. The `actual` implementations, which call into the Rust library, are
+// generated separately for each platform.
+
+{%- for type_ in ci.iter_shared_composite_types() %}
+{%- match type_ %}
+{%- when Type::Sequence { inner_type } %}
+
+public typealias {{ type_|canonical_name }} = List<{{ inner_type|type_name(ci, config) }}>
+{%- when Type::Map { key_type, value_type } %}
+
+public typealias {{ type_|canonical_name }} = Map<{{ key_type|type_name(ci, config) }}, {{ value_type|type_name(ci, config) }}>
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+{%- for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Record { name, module_path } %}
+{%- let rec = ci|get_record_definition(name) %}
+{%- let type_name = type_|type_name(ci, config) %}
+{% call kt::type_docstring(rec, 0) %}
+{%- call kt::deprecated(rec, 0) %}
+expect class {{ type_name }}(
+    {%- for field in rec.fields() %}
+    {{ field.name()|var_name }}: {{ field|type_name(ci, config) -}}
+    {%- match field.default_value() %}
+        {%- when Some with(literal) %} = {{ literal|render_literal(field, ci, config) }}
+        {%- else %}
+    {%- endmatch -%}
+    {% if !loop.last %}, {% endif %}
+    {%- endfor %}
+) {
+    {%- for field in rec.fields() %}
+    {%- call kt::docstring(field, 4) %}
+    {% if config.generate_immutable_records() %}val{% else %}var{% endif %} {{ field.name()|var_name }}: {{ field|type_name(ci, config) }}
+    {%- endfor %}
+}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+{%- for func in ci.function_definitions() %}
+{#- The runtime config is only generated for each platform. #}
+{%- if func.target().is_none() && !func.is_runtime_initializer() %}
+{% call kt::callable_docstring(func, 0) %}
+{%- call kt::deprecated(func, 0) %}
+expect {% if func.is_async() %}suspend {% endif %}fun {% call kt::extension_receiver(func) %}{{ func.name()|fn_name }}({% call kt::arg_list_decl(func) %})
+{%- match func.return_type() %}{% when Some with (return_type) %}: {{ return_type|type_name(ci, config) }}{% when None %}{% endmatch %}
+{%- endif %}
+{%- endfor %}
+
+{% import "macros.kt" as kt %}
@@ -22,6 +22,7 @@ internal interface UniffiLib : Library {
             loadIndirect<UniffiLib>(componentName = "{{ ci.namespace() }}")
             .also { lib: UniffiLib ->
                 uniffiCheckContractApiVersion(lib)
+                uniffiCheckComponentVersion(lib)
                 uniffiCheckApiChecksums(lib)
                 {% for fn in self.initialization_fns() -%}
                 {{ fn }}(lib)
@@ -47,6 +48,27 @@ private fun uniffiCheckContractApiVersion(lib: UniffiLib) {
     }
 }
 
+@Suppress("UNUSED_PARAMETER")
+private fun uniffiCheckComponentVersion(lib: UniffiLib) {
+    {%- match ci.component_version() %}
+    {%- when Some(bindings_component_version) %}
+    // Get the bindings component version from our ComponentInterface
+    val bindings_component_version = "{{ bindings_component_version }}"
+    // Get the scaffolding component version by calling the into the dylib.  `UniffiLib.INSTANCE`
+    // isn't initialized yet, so the buffer needs to be freed using `lib` directly.
+    val buf = lib.{{ ci.ffi_uniffi_component_version().name() }}()
+    val scaffolding_component_version = try {
+        (buf.data?.getByteArray(0, buf.len) ?: ByteArray(0)).toString(Charsets.UTF_8)
+    } finally {
+        lib.{{ ci.ffi_rustbuffer_free().name() }}(buf, UniffiRustCallStatus())
+    }
+    if (bindings_component_version != scaffolding_component_version) {
+        throw RuntimeException("UniFFI component version mismatch: the bindings are version \"$bindings_component_version\" but the library is version \"$scaffolding_component_version\"")
+    }
+    {%- when None %}
+    {%- endmatch %}
+}
+
 @Suppress("UNUSED_PARAMETER")
 private fun uniffiCheckApiChecksums(lib: UniffiLib) {
     {%- for (name, expected_checksum) in ci.iter_checksums() %}
@@ -0,0 +1,6 @@
+
+object {{ namespace_object|class_name(ci) }} {
+{%- for func in ci.namespace_object_functions(namespace_object) %}
+{%- include "TopLevelFunctionTemplate.kt" %}
+{%- endfor %}
+}
@@ -0,0 +1,32 @@
+{%- let inner_type_name = inner_type|type_name(ci, config) %}
+{%- let ffi_type_name = inner_type|ffi_type(ci)|ffi_type_name_by_value %}
+
+/**
+ * A `{{ inner_type_name }}` which is never zero.
+ */
+@JvmInline
+value class {{ type_name }}(val value: {{ inner_type_name }}) {
+    init {
+        require(value.toLong() != 0L) { "{{ type_name }} requires a non-zero value" }
+    }
+}
+
+public object {{ ffi_converter_name }}: FfiConverter<{{ type_name }}, {{ ffi_type_name }}> {
+    override fun lift(value: {{ ffi_type_name }}): {{ type_name }} {
+        return {{ type_name }}({{ inner_type|lift_fn }}(value))
+    }
+
+    override fun read(buf: ByteBuffer): {{ type_name }} {
+        return {{ type_name }}({{ inner_type|read_fn }}(buf))
+    }
+
+    override fun lower(value: {{ type_name }}): {{ ffi_type_name }} {
+        return {{ inner_type|lower_fn }}(value.value)
+    }
+
+    override fun allocationSize(value: {{ type_name }}) = {{ inner_type|allocation_size_fn }}(value.value)
+
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
+        {{ inner_type|write_fn }}(value.value, buf)
+    }
+}
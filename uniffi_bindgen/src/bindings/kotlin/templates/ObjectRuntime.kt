@@ -103,6 +103,7 @@ abstract class FFIObject: Disposable, AutoCloseable {
     protected val pointer: Pointer?
 
     private val wasDestroyed = AtomicBoolean(false)
+    private val wasConsumed = AtomicBoolean(false)
     private val callCounter = AtomicLong(1)
 
     open protected fun freeRustArcPtr() {
@@ -132,13 +133,19 @@ abstract class FFIObject: Disposable, AutoCloseable {
         this.destroy()
     }
 
-    internal inline fun <R> callWithPointer(block: (ptr: Pointer) -> R): R {
+    internal inline fun <R> callWithPointer(block: (ptr: Pointer) -> R): R =
+        callWithPointer({ this.uniffiClonePointer() }, block)
+
+    // Objects which inherit from another interface hold a pointer for each level of the hierarchy,
+    // so their methods pass in the function that clones the pointer they need.
+    internal inline fun <R> callWithPointer(clonePointer: () -> Pointer, block: (ptr: Pointer) -> R): R {
         // Check and increment the call counter, to keep the object alive.
         // This needs a compare-and-set retry loop in case of concurrent updates.
         do {
             val c = this.callCounter.get()
             if (c == 0L) {
-                throw IllegalStateException("${this.javaClass.simpleName} object has already been destroyed")
+                val state = if (this.wasConsumed.get()) "consumed" else "destroyed"
+                throw IllegalStateException("${this.javaClass.simpleName} object has already been $state")
             }
             if (c == Long.MAX_VALUE) {
                 throw IllegalStateException("${this.javaClass.simpleName} call counter would overflow")
@@ -146,7 +153,7 @@ abstract class FFIObject: Disposable, AutoCloseable {
         } while (! this.callCounter.compareAndSet(c, c + 1L))
         // Now we can safely do the method call without the pointer being freed concurrently.
         try {
-            return block(this.uniffiClonePointer())
+            return block(clonePointer())
         } finally {
             // This decrement always matches the increment we performed above.
             if (this.callCounter.decrementAndGet() == 0L) {
@@ -154,6 +161,17 @@ abstract class FFIObject: Disposable, AutoCloseable {
             }
         }
     }
+
+    // Methods which consume the object take over its reference to the Rust object, so the object
+    // is destroyed before the call and can't be used afterwards.
+    internal inline fun <R> callWithConsumedPointer(block: (ptr: Pointer) -> R): R {
+        if (!this.wasConsumed.compareAndSet(false, true)) {
+            throw IllegalStateException("${this.javaClass.simpleName} object has already been consumed")
+        }
+        val pointer = callWithPointer { it }
+        this.destroy()
+        return block(pointer)
+    }
 }
 
 /** Used to instantiate a [FFIObject] without an actual pointer, for fakes in tests, mostly. */
@@ -3,13 +3,29 @@
 {%- let (interface_name, impl_class_name) = obj|object_names(ci) %}
 {%- let methods = obj.methods() %}
 {%- let interface_docstring = obj.docstring() %}
+{%- let interface_parent = obj|parent_interface_name(ci) %}
 
 {% include "Interface.kt" %}
 
-{%- call kt::docstring(obj, 0) %}
-open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
+{%- call kt::object_docstring(obj, 0) %}
+open class {{ impl_class_name }} : {% match obj.parent() %}{% when Some(parent) %}{{ parent|class_name(ci) }}{% when None %}FFIObject{% endmatch %}, {{ interface_name }}
+{%- for trait_type in obj.implements() %}, {{ trait_type|type_name(ci, config) }}{% endfor %} {
+
+    {%- match obj.parent() %}
+    {%- when Some(parent) %}
+    // The pointer to this object.  The pointer held by the parent class points to the
+    // `{{ parent|class_name(ci) }}` this object was converted to, which its methods are called with.
+    private val uniffi{{ impl_class_name }}Pointer: Pointer?
+
+    constructor(pointer: Pointer): super(uniffiRustCall() { status ->
+        UniffiLib.INSTANCE.{{ obj.ffi_object_upcast().name() }}(pointer, status)
+    }) {
+        this.uniffi{{ impl_class_name }}Pointer = pointer
+    }
+    {%- when None %}
 
     constructor(pointer: Pointer): super(pointer)
+    {%- endmatch %}
 
     /**
      * This constructor can be used to instantiate a fake object.
@@ -19,21 +35,46 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
      * @param noPointer Placeholder value so we can have a constructor separate from the default empty one that may be
      *   implemented for classes extending [FFIObject].
      */
+    {%- if obj.parent().is_some() %}
+    constructor(noPointer: NoPointer): super(noPointer) {
+        this.uniffi{{ impl_class_name }}Pointer = null
+    }
+    {%- else %}
     constructor(noPointer: NoPointer): super(noPointer)
+    {%- endif %}
 
     {%- match obj.primary_constructor() %}
     {%- when Some with (cons) %}
-    {%- call kt::docstring(cons, 4) %}
+    {%- if cons.is_async() %}
+    // Note no constructor generated for this object as it is async.
+    {%- else %}
+    {%- call kt::constructor_docstring(cons, 4) %}
+    {%- if config.java_interop() %}
+    {%- match cons.throws_type() %}
+    {%- when Some with (throwable) %}
+    @Throws({{ throwable|type_name(ci, config) }}::class)
+    {%- else %}
+    {%- endmatch %}
+    {%- endif %}
     constructor({% call kt::arg_list_decl(cons) -%}) :
         this({% call kt::to_ffi_call(cons) %})
+    {%- endif %}
     {%- when None %}
     {%- endmatch %}
 
+    {%- if obj.parent().is_some() %}
+    fun uniffiClone{{ impl_class_name }}Pointer(): Pointer {
+        return uniffiRustCall() { status ->
+            UniffiLib.INSTANCE.{{ obj.ffi_object_clone().name() }}(uniffi{{ impl_class_name }}Pointer!!, status)
+        }
+    }
+    {%- else %}
     override fun uniffiClonePointer(): Pointer {
         return uniffiRustCall() { status ->
             UniffiLib.INSTANCE.{{ obj.ffi_object_clone().name() }}(pointer!!, status)
         }
     }
+    {%- endif %}
 
     /**
      * Disconnect the object from the underlying Rust object.
@@ -44,27 +85,36 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
      * Clients **must** call this method once done with the object, or cause a memory leak.
      */
     override protected fun freeRustArcPtr() {
+        {%- if obj.parent().is_some() %}
+        this.uniffi{{ impl_class_name }}Pointer?.let { ptr ->
+            uniffiRustCall() { status ->
+                UniffiLib.INSTANCE.{{ obj.ffi_object_free().name() }}(ptr, status)
+            }
+        }
+        super.freeRustArcPtr()
+        {%- else %}
         this.pointer?.let { ptr ->
             uniffiRustCall() { status ->
                 UniffiLib.INSTANCE.{{ obj.ffi_object_free().name() }}(ptr, status)
             }
         }
+        {%- endif %}
     }
 
     {% for meth in obj.methods() -%}
-    {%- call kt::docstring(meth, 4) %}
+    {%- call kt::method_docstring(meth, 4) %}
     {%- match meth.throws_type() -%}
     {%- when Some with (throwable) %}
-    @Throws({{ throwable|type_name(ci) }}::class)
+    @Throws({{ throwable|type_name(ci, config) }}::class)
     {%- else -%}
     {%- endmatch -%}
     {%- if meth.is_async() %}
     @Suppress("ASSIGNED_BUT_NEVER_ACCESSED_VARIABLE")
     override suspend fun {{ meth.name()|fn_name }}(
         {%- call kt::arg_list_decl(meth) -%}
-    ){% match meth.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name(ci) }}{% when None %}{%- endmatch %} {
+    ){% match meth.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name(ci, config) }}{% when None %}{%- endmatch %} {
         return uniffiRustCallAsync(
-            callWithPointer { thisPtr ->
+            {% call kt::call_with_pointer(obj, meth, impl_class_name) %} { thisPtr ->
                 UniffiLib.INSTANCE.{{ meth.ffi_func().name() }}(
                     thisPtr,
                     {% call kt::arg_list_lowered(meth) %}
@@ -83,7 +133,7 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
             // Error FFI converter
             {%- match meth.throws_type() %}
             {%- when Some(e) %}
-            {{ e|type_name(ci) }}.ErrorHandler,
+            {{ e|type_name(ci, config) }}.ErrorHandler,
             {%- when None %}
             UniffiNullRustCallStatusErrorHandler,
             {%- endmatch %}
@@ -94,8 +144,8 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
     {%- when Some with (return_type) -%}
     override fun {{ meth.name()|fn_name }}(
         {%- call kt::arg_list_protocol(meth) -%}
-    ): {{ return_type|type_name(ci) }} =
-        callWithPointer {
+    ): {{ return_type|type_name(ci, config) }} =
+        {% call kt::call_with_pointer(obj, meth, impl_class_name) %} {
             {%- call kt::to_ffi_call_with_prefix("it", meth) %}
         }.let {
             {{ return_type|lift_fn }}(it)
@@ -105,7 +155,7 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
     override fun {{ meth.name()|fn_name }}(
         {%- call kt::arg_list_protocol(meth) -%}
     ) =
-        callWithPointer {
+        {% call kt::call_with_pointer(obj, meth, impl_class_name) %} {
             {%- call kt::to_ffi_call_with_prefix("it", meth) %}
         }
     {% endmatch %}
@@ -143,12 +193,139 @@ open class {{ impl_class_name }} : FFIObject, {{ interface_name }} {
     {%-     endmatch %}
     {%- endfor %}
 
-    {% if !obj.alternate_constructors().is_empty() -%}
+    {%- if obj.has_identity_equality() %}
+
+    /**
+     * The id of the underlying Rust object, which is the same for every handle to it.
+     */
+    fun uniffiHandleId(): Long =
+        callWithPointer { ptr ->
+            uniffiRustCall() { status ->
+                UniffiLib.INSTANCE.{{ obj.ffi_object_handle_id().name() }}(ptr, status)
+            }
+        }
+
+    override fun equals(other: Any?): Boolean {
+        if (this === other) return true
+        if (other !is {{ impl_class_name }}) return false
+        return uniffiHandleId() == other.uniffiHandleId()
+    }
+    {%- endif %}
+    {%- if obj.has_identity_hash() %}
+
+    override fun hashCode(): Int = uniffiHandleId().hashCode()
+    {%- endif %}
+
+    {%- match ci.observable(obj) %}
+    {%- when Some with (observable) %}
+    {{- self.add_import("kotlinx.coroutines.channels.awaitClose") }}
+    {{- self.add_import("kotlinx.coroutines.channels.trySendBlocking") }}
+    {{- self.add_import("kotlinx.coroutines.flow.Flow") }}
+    {{- self.add_import("kotlinx.coroutines.flow.callbackFlow") }}
+    {%- let event_type = observable.event_type()|type_name(ci, config) %}
+    {%- let event_name = observable.event_argument().name()|var_name %}
+    {%- let add_name = observable.add_method().name()|fn_name %}
+    {%- let remove_name = observable.remove_method().name()|fn_name %}
+
+    /**
+     * The events passed to an observer, which is added while the flow is collected and removed
+     * when collection stops.
+     */
+    fun observe(): Flow<{{ event_type }}> = callbackFlow {
+        val observer = object : {{ observable.observer_type()|type_name(ci, config) }} {
+            override fun {{ observable.event_method().name()|fn_name }}({{ event_name }}: {{ event_type }}) {
+                trySendBlocking({{ event_name }})
+            }
+        }
+        {%- if observable.has_token() %}
+        val token = {{ add_name }}(observer)
+        awaitClose { {{ remove_name }}(token) }
+        {%- else %}
+        {{ add_name }}(observer)
+        awaitClose { {{ remove_name }}(observer) }
+        {%- endif %}
+    }
+    {%- else %}
+    {%- endmatch %}
+
+    {%- match obj.cursor() %}
+    {%- when Some with (cursor) %}
+    {%- let next_batch = cursor.next_batch_method() %}
+    {%- let count_type = next_batch.arguments()[0]|type_name(ci, config) %}
+
+    /**
+     * The items of the cursor, fetched with `{{ next_batch.name()|fn_name|unquote }}` in batches of [batchSize] as the
+     * sequence is iterated. A batch with fewer items, including an empty one, ends the sequence.
+     */
+    fun asSequence(batchSize: Int = 100): Sequence<{{ cursor.item_type()|type_name(ci, config) }}> {
+        require(batchSize > 0) { "batchSize must be positive" }
+        return sequence {
+            while (true) {
+                val batch = {{ next_batch.name()|fn_name }}(batchSize.to{{ count_type }}())
+                yieldAll(batch)
+                if (batch.size < batchSize) {
+                    break
+                }
+            }
+        }
+    }
+    {%- else %}
+    {%- endmatch %}
+
+    {% if !obj.alternate_constructors().is_empty() || obj.has_async_constructor() || obj.is_singleton() || !obj.constants().is_empty() -%}
     companion object {
-        {% for cons in obj.alternate_constructors() -%}
-        {%- call kt::docstring(cons, 4) %}
+        {%- for constant in obj.constants() %}
+        {%- call kt::docstring(constant, 8) %}
+        val {{ constant.name()|const_name }}: {{ constant|type_name(ci, config) }} = {{ constant.value()|render_literal(constant, ci, config) }}
+        {%- endfor %}
+        {%- if obj.is_singleton() %}
+        /**
+         * The single instance of `{{ impl_class_name }}`, which is shared by all callers.
+         */
+        val instance: {{ impl_class_name }} by lazy {
+            {{ impl_class_name }}(uniffiRustCall() { status ->
+                UniffiLib.INSTANCE.{{ obj.ffi_object_singleton().name() }}(status)
+            })
+        }
+        {%- endif %}
+        {% for cons in obj.constructors() -%}
+        {%- if cons.is_async() %}
+        {%- call kt::constructor_docstring(cons, 4) %}
+        {%- match cons.throws_type() %}
+        {%- when Some with (throwable) %}
+        @Throws({{ throwable|type_name(ci, config) }}::class)
+        {%- else %}
+        {%- endmatch %}
+        @Suppress("ASSIGNED_BUT_NEVER_ACCESSED_VARIABLE")
+        suspend fun {{ cons.name()|fn_name }}({% call kt::arg_list_decl(cons) %}): {{ impl_class_name }} {
+            return uniffiRustCallAsync(
+                UniffiLib.INSTANCE.{{ cons.ffi_func().name() }}({% call kt::arg_list_lowered(cons) %}),
+                {{ cons|async_poll(ci) }},
+                {{ cons|async_complete(ci) }},
+                {{ cons|async_free(ci) }},
+                // lift function
+                { {{ impl_class_name }}(it) },
+                // Error FFI converter
+                {%- match cons.throws_type() %}
+                {%- when Some(e) %}
+                {{ e|type_name(ci, config) }}.ErrorHandler,
+                {%- when None %}
+                UniffiNullRustCallStatusErrorHandler,
+                {%- endmatch %}
+            )
+        }
+        {%- else if !cons.is_primary_constructor() %}
+        {%- call kt::constructor_docstring(cons, 4) %}
+        {%- if config.java_interop() %}
+        {%- match cons.throws_type() %}
+        {%- when Some with (throwable) %}
+        @Throws({{ throwable|type_name(ci, config) }}::class)
+        {%- else %}
+        {%- endmatch %}
+        {%- endif %}
         fun {{ cons.name()|fn_name }}({% call kt::arg_list_decl(cons) %}): {{ impl_class_name }} =
             {{ impl_class_name }}({% call kt::to_ffi_call(cons) %})
+        {%- endif %}
         {% endfor %}
     }
     {% else %}
@@ -171,7 +348,12 @@ public object {{ obj|ffi_converter_name }}: FfiConverter<{{ type_name }}, Pointe
     override fun lower(value: {{ type_name }}): Pointer {
         {%- match obj.imp() %}
         {%- when ObjectImpl::Struct %}
-        return value.uniffiClonePointer()
+        {%- if obj.has_consuming_methods() %}
+        // Check that the object wasn't consumed, since its pointer is freed then.
+        return value.callWithPointer { it }
+        {%- else %}
+        return value.uniffiClone{% if obj.parent().is_some() %}{{ impl_class_name }}{% endif %}Pointer()
+        {%- endif %}
         {%- when ObjectImpl::Trait %}
         return Pointer(handleMap.insert(value))
         {%- endmatch %}
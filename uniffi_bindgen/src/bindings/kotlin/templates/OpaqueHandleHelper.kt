@@ -0,0 +1,31 @@
+/**
+ * A value from Rust, usually a pointer, which is passed back unchanged.
+ *
+ * Nothing keeps what it points to alive: that's up to the Rust code which handed it out.
+ */
+@JvmInline
+value class OpaqueHandle(val value: Long) {
+    constructor(pointer: Pointer) : this(Pointer.nativeValue(pointer))
+
+    fun toPointer(): Pointer = Pointer(value)
+}
+
+public object FfiConverterOpaqueHandle: FfiConverter<OpaqueHandle, Long> {
+    override fun lift(value: Long): OpaqueHandle {
+        return OpaqueHandle(value)
+    }
+
+    override fun read(buf: ByteBuffer): OpaqueHandle {
+        return lift(buf.getLong())
+    }
+
+    override fun lower(value: OpaqueHandle): Long {
+        return value.value
+    }
+
+    override fun allocationSize(value: OpaqueHandle) = 8
+
+    override fun write(value: OpaqueHandle, buf: ByteBuffer) {
+        buf.putLong(value.value)
+    }
+}
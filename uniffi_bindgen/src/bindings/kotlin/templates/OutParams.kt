@@ -0,0 +1,14 @@
+// Lift the value returned by a function with out-parameters, which is followed by their final
+// values in the same buffer.
+private inline fun <T> uniffiLiftOutParams(rbuf: RustBuffer.ByValue, read: (ByteBuffer) -> T): T {
+    val byteBuf = rbuf.asByteBuffer()!!
+    try {
+        val outputs = read(byteBuf)
+        if (byteBuf.hasRemaining()) {
+            throw RuntimeException("junk remaining in buffer after lifting, something is very wrong!!")
+        }
+        return outputs
+    } finally {
+        RustBuffer.free(rbuf)
+    }
+}
@@ -0,0 +1,27 @@
+public object FfiConverterPath: FfiConverterRustBuffer<java.io.File> {
+    override fun read(buf: ByteBuffer): java.io.File {
+        val len = buf.getInt()
+        val byteArr = ByteArray(len)
+        buf.get(byteArr)
+        return java.io.File(byteArr.toString(Charsets.UTF_8))
+    }
+
+    private fun toUtf8(value: java.io.File): ByteBuffer {
+        // Make sure we don't have invalid UTF-16, check for lone surrogates.
+        return Charsets.UTF_8.newEncoder().run {
+            onMalformedInput(CodingErrorAction.REPORT)
+            encode(CharBuffer.wrap(value.path))
+        }
+    }
+
+    // We aren't sure exactly how many bytes our path will be once it's UTF-8
+    // encoded.  Allocate 3 bytes per UTF-16 code unit which will always be
+    // enough.
+    override fun allocationSize(value: java.io.File) = 4 + value.path.length * 3
+
+    override fun write(value: java.io.File, buf: ByteBuffer) {
+        val byteBuf = toUtf8(value)
+        buf.putInt(byteBuf.limit())
+        buf.put(byteBuf)
+    }
+}
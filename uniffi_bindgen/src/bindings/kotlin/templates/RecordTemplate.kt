@@ -1,13 +1,18 @@
 {%- let rec = ci|get_record_definition(name) %}
 
 {%- if rec.has_fields() %}
-{%- call kt::docstring(rec, 0) %}
+{%- call kt::type_docstring(rec, 0) %}
+{%- call kt::deprecated(rec, 0) %}
+{%- if config.kmp() %}
+actual data class {{ type_name }} actual constructor(
+{%- else %}
 data class {{ type_name }} (
+{%- endif %}
     {%- for field in rec.fields() %}
     {%- call kt::docstring(field, 4) %}
-    {% if config.generate_immutable_records() %}val{% else %}var{% endif %} {{ field.name()|var_name }}: {{ field|type_name(ci) -}}
+    {% if config.kmp() %}actual {% endif %}{% if config.generate_immutable_records() %}val{% else %}var{% endif %} {{ field.name()|var_name }}: {{ field|type_name(ci, config) -}}
     {%- match field.default_value() %}
-        {%- when Some with(literal) %} = {{ literal|render_literal(field, ci) }}
+        {%- when Some with(literal) %}{% if !config.kmp() %} = {{ literal|render_literal(field, ci, config) }}{% endif %}
         {%- else %}
     {%- endmatch -%}
     {% if !loop.last %}, {% endif %}
@@ -19,11 +24,18 @@ data class {{ type_name }} (
         {% call kt::destroy_fields(rec) %}
     }
     {% endif %}
-    companion object
+    {%- if config.derive_display() %}
+
+    override fun toString(): String {
+        return "{{ type_name }}({% for field in rec.fields() %}{{ field.name()|var_name|unquote }}={{ field|display_field }}{% if !loop.last %}, {% endif %}{% endfor %})"
+    }
+    {%- endif %}
+    {%- call kt::record_companion_object(rec, type_name) %}
 }
 {%- else -%}
-{%- call kt::docstring(rec, 0) %}
-class {{ type_name }} {
+{%- call kt::type_docstring(rec, 0) %}
+{%- call kt::deprecated(rec, 0) %}
+{% if config.kmp() %}actual class {{ type_name }} actual constructor() {% else %}class {{ type_name }} {% endif %}{
     override fun equals(other: Any?): Boolean {
         return other is {{ type_name }}
     }
@@ -31,17 +43,46 @@ class {{ type_name }} {
     override fun hashCode(): Int {
         return javaClass.hashCode()
     }
+    {%- if config.derive_display() %}
 
-    companion object
+    override fun toString(): String {
+        return "{{ type_name }}()"
+    }
+    {%- endif %}
+
+    {%- call kt::record_companion_object(rec, type_name) %}
 }
 {%- endif %}
 
 public object {{ rec|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
     override fun read(buf: ByteBuffer): {{ type_name }} {
-        {%- if rec.has_fields() %}
+        {%- if rec.is_packed() %}
+        // Adjacent boolean fields are packed into bytes.
+        {%- for item in rec.wire_items() %}
+        {%- match item %}
+        {%- when WireItem::Field with (field) %}
+        val {{ "uniffi_{}"|format(field.name())|var_name }} = {{ field|read_fn }}(buf)
+        {%- when WireItem::PackedBools with (bools) %}
+        val packed{{ loop.index0 }} = buf.get().toInt()
+        {%- let packed = "packed{}"|format(loop.index0) %}
+        {%- for (field, mask) in bools %}
+        val {{ "uniffi_{}"|format(field.name())|var_name }} = ({{ packed }} and {{ mask }}) != 0
+        {%- endfor %}
+        {%- endmatch %}
+        {%- endfor %}
         return {{ type_name }}(
         {%- for field in rec.fields() %}
+            {{ field.name()|var_name }} = {{ "uniffi_{}"|format(field.name())|var_name }},
+        {%- endfor %}
+        )
+        {%- else if rec.has_fields() %}
+        return {{ type_name }}(
+        {%- for field in rec.wire_fields() %}
+            {%- if rec.has_wire_order() %}
+            {{ field.name()|var_name }} = {{ field|read_fn }}(buf),
+            {%- else %}
             {{ field|read_fn }}(buf),
+            {%- endif %}
         {%- endfor %}
         )
         {%- else %}
@@ -56,8 +97,18 @@ public object {{ rec|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name
     ) {%- else %} 0 {%- endif %}
 
     override fun write(value: {{ type_name }}, buf: ByteBuffer) {
-        {%- for field in rec.fields() %}
+        {%- for item in rec.wire_items() %}
+        {%- match item %}
+        {%- when WireItem::Field with (field) %}
             {{ field|write_fn }}(value.{{ field.name()|var_name }}, buf)
+        {%- when WireItem::PackedBools with (bools) %}
+            var packed{{ loop.index0 }} = 0
+            {%- let packed = "packed{}"|format(loop.index0) %}
+            {%- for (field, mask) in bools %}
+            if (value.{{ field.name()|var_name }}) {{ packed }} = {{ packed }} or {{ mask }}
+            {%- endfor %}
+            buf.put({{ packed }}.toByte())
+        {%- endmatch %}
         {%- endfor %}
     }
 }
@@ -0,0 +1,34 @@
+
+/**
+ * Runtime settings, which [uniffiInitialize] applies together.
+ *
+ * Each method sets one setting and returns this config, so they can be chained.  Settings which
+ * aren't set are left unchanged.
+ */
+class UniffiRuntimeConfig {
+    {%- for arg in func.arguments() %}
+    internal var {{ arg.name()|var_name }}: {{ arg|type_name(ci, config) }} = null
+        private set
+    {%- endfor %}
+    {%- for arg in func.arguments() %}
+    {%- match arg.as_type() %}
+    {%- when Type::Optional { inner_type } %}
+
+    fun {{ arg.name()|fn_name }}(value: {{ inner_type|type_name(ci, config) }}): UniffiRuntimeConfig {
+        this.{{ arg.name()|var_name }} = value
+        return this
+    }
+    {%- else %}
+    {%- endmatch %}
+    {%- endfor %}
+}
+
+/**
+ * Apply the settings in [config] together, with a single call into Rust.
+ */
+fun uniffiInitialize(config: UniffiRuntimeConfig) {
+    {%- for arg in func.arguments() %}
+    val {{ arg.name()|var_name }} = config.{{ arg.name()|var_name }}
+    {%- endfor %}
+    {% call kt::to_ffi_call(func) %}
+}
@@ -0,0 +1,73 @@
+/**
+ * Pass a value of each primitive type to Rust and back, to check that the bindings and the library
+ * agree on how they're passed over the FFI.
+ *
+ * Call this at startup to diagnose mismatched or miscompiled builds.  Every value is checked, and
+ * the report has a message for each one which changed.
+ */
+fun uniffiSelfTest(): UniffiSelfTestReport {
+    // These must match `uniffi_self_test_values()` in the scaffolding.
+    val expected = UniffiSelfTestValues(
+        boolean = true,
+        int8 = Byte.MIN_VALUE,
+        {%- if config.unsigned_as_signed() %}
+        uint8 = -1,
+        {%- else %}
+        uint8 = UByte.MAX_VALUE,
+        {%- endif %}
+        int16 = Short.MIN_VALUE,
+        {%- if config.unsigned_as_signed() %}
+        uint16 = -1,
+        {%- else %}
+        uint16 = UShort.MAX_VALUE,
+        {%- endif %}
+        int32 = Int.MIN_VALUE,
+        {%- if config.unsigned_as_signed() %}
+        uint32 = -1,
+        {%- else %}
+        uint32 = UInt.MAX_VALUE,
+        {%- endif %}
+        int64 = Long.MIN_VALUE,
+        {%- if config.unsigned_as_signed() %}
+        uint64 = -1,
+        {%- else %}
+        uint64 = ULong.MAX_VALUE,
+        {%- endif %}
+        float32 = -0.15625f,
+        float64 = 0.1,
+        // Characters encoded as 1, 2, 3 and 4 bytes of UTF-8.
+        string = "a\u00df\u20ac\uD83E\uDD80",
+        bytes = byteArrayOf(0, 1, 127, -128, -1),
+    )
+    val failures = mutableListOf<String>()
+    try {
+        failures.addAll(uniffiSelfTestCheck(expected).failures)
+    } catch (e: Exception) {
+        failures.add("passing the values to Rust failed: $e")
+    }
+    try {
+        val actual = uniffiSelfTestValues()
+        fun check(name: String, value: Any, expectedValue: Any, matches: Boolean = value == expectedValue) {
+            if (!matches) {
+                failures.add("$name: Kotlin received $value, expected $expectedValue")
+            }
+        }
+        check("boolean", actual.boolean, expected.boolean)
+        check("int8", actual.int8, expected.int8)
+        check("uint8", actual.uint8, expected.uint8)
+        check("int16", actual.int16, expected.int16)
+        check("uint16", actual.uint16, expected.uint16)
+        check("int32", actual.int32, expected.int32)
+        check("uint32", actual.uint32, expected.uint32)
+        check("int64", actual.int64, expected.int64)
+        check("uint64", actual.uint64, expected.uint64)
+        // Compare floats by their bits, to check their encoding rather than just their value.
+        check("float32", actual.float32, expected.float32, actual.float32.toRawBits() == expected.float32.toRawBits())
+        check("float64", actual.float64, expected.float64, actual.float64.toRawBits() == expected.float64.toRawBits())
+        check("string", actual.string, expected.string)
+        check("bytes", actual.bytes.contentToString(), expected.bytes.contentToString())
+    } catch (e: Exception) {
+        failures.add("getting the values from Rust failed: $e")
+    }
+    return UniffiSelfTestReport(failures.isEmpty(), failures)
+}
@@ -1,4 +1,8 @@
-{%- let inner_type_name = inner_type|type_name(ci) %}
+{%- let inner_type_name = inner_type|type_name(ci, config) %}
+{%- if ci.is_shared_composite_type(type_) && !config.kmp() %}
+
+public typealias {{ canonical_type_name }} = List<{{ inner_type_name }}>
+{%- endif %}
 
 public object {{ ffi_converter_name }}: FfiConverterRustBuffer<List<{{ inner_type_name }}>> {
     override fun read(buf: ByteBuffer): List<{{ inner_type_name }}> {
@@ -0,0 +1,60 @@
+{%- match config.file_header() %}{% when Some with (header) %}{{ header }}
+{% when None %}{% endmatch -%}
+{% if config.hash_header() %}// {{ ci.hash_header() }}
+{% endif -%}
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+package {{ config.package_name() }};
+
+// Stub implementations of the callback interfaces in `{{ ci.namespace() }}.kt`, for tests.
+{% for req in imports %}
+{{ req.render() }}
+{%- endfor %}
+
+/**
+ * Thrown by a stub method which has no default value to return.  Override the method to stub it.
+ */
+public class UniffiNotStubbedException(method: String) : Exception("`$method` is not stubbed")
+{%- for cbi in ci.callback_interface_definitions() %}
+{%- let interface_name = cbi|type_name(ci, config) %}
+
+/**
+ * A stub [{{ interface_name }}] for tests, which records each call in [calls].
+ *
+ * Methods return a default value, like `0`, `null` or an empty list, or throw
+ * [UniffiNotStubbedException] if their return type doesn't have one.  Override a method to stub it.
+ */
+public open class {{ interface_name }}Stub : {{ interface_name }} {
+    /** Each call to this stub, in order: the method name and its arguments. */
+    public val calls: MutableList<Pair<String, List<Any?>>> =
+        java.util.Collections.synchronizedList(mutableListOf())
+    {%- for meth in cbi.methods() %}
+    {%- let method_name = meth.name()|fn_name %}
+
+    override {% if meth.is_async() -%}suspend {% endif -%}
+    fun {{ method_name }}({% call kt::arg_list_protocol(meth) %})
+    {%- match meth.return_type() -%}
+    {%- when Some with (return_type) %}: {{ return_type|type_name(ci, config) -}}
+    {%- else -%}
+    {%- endmatch %} {
+        calls.add(Pair("{{ method_name|unquote }}", listOf<Any?>(
+            {%- for arg in meth.arguments() -%}
+            {{ arg.name()|var_name }}{% if !loop.last %}, {% endif -%}
+            {%- endfor %})))
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}
+        {%- match self.default_value(return_type) %}
+        {%- when Some with (value) %}
+        return {{ value }}
+        {%- when None %}
+        throw UniffiNotStubbedException("{{ method_name|unquote }}")
+        {%- endmatch %}
+        {%- else %}
+        {%- endmatch %}
+    }
+    {%- endfor %}
+}
+{%- endfor %}
+
+{% import "macros.kt" as kt %}
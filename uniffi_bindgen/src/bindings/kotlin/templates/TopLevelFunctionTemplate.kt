@@ -1,14 +1,24 @@
-{%- call kt::docstring(func, 8) %}
+{%- if config.generates_target(func.target()) %}
+{%- let result_errors = config.result_errors() && func.throws() %}
+{%- call kt::callable_docstring(func, 8) %}
+{%- call kt::deprecated(func, 0) %}
+{%- match func|jvm_name(ci, config) %}
+{%- when Some with (jvm_name) %}
+@JvmName("{{ jvm_name }}")
+{%- when None %}
+{%- endmatch %}
 {%- if func.is_async() %}
 {%- match func.throws_type() -%}
 {%- when Some with (throwable) %}
-@Throws({{ throwable|type_name(ci) }}::class)
+{%- if !result_errors %}
+@Throws({{ throwable|type_name(ci, config) }}::class)
+{%- endif %}
 {%- else -%}
 {%- endmatch %}
 
 @Suppress("ASSIGNED_BUT_NEVER_ACCESSED_VARIABLE")
-suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% match func.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name(ci) }}{% when None %}{%- endmatch %} {
-    return uniffiRustCallAsync(
+{% if config.kmp() && func.target().is_none() %}actual {% endif %}suspend fun {% call kt::extension_receiver(func) %}{{ func.name()|fn_name }}({%- call kt::fn_arg_list_decl(func) -%}){% if result_errors %} : {{ func|result_type_name(ci, config) }}{% else %}{% match func.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name(ci, config) }}{% when None %}{%- endmatch %}{% endif %} {
+    return {% if result_errors %}{{ func|result_of(ci, config) }} { {% endif %}{% match func.retries() %}{% when Some with (retries) %}uniffiRetryAsync({{ retries }}, { {{ func|retry_predicate(ci, config) }} }) { {% when None %}{% endmatch %}uniffiRustCallAsync(
         UniffiLib.INSTANCE.{{ func.ffi_func().name() }}({% call kt::arg_list_lowered(func) %}),
         {{ func|async_poll(ci) }},
         {{ func|async_complete(ci) }},
@@ -23,30 +33,201 @@ suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% ma
         // Error FFI converter
         {%- match func.throws_type() %}
         {%- when Some(e) %}
-        {{ e|type_name(ci) }}.ErrorHandler,
+        {{ e|type_name(ci, config) }}.ErrorHandler,
+        {%- when None %}
+        UniffiNullRustCallStatusErrorHandler,
+        {%- endmatch %}
+    ){% if func.retries().is_some() %} }{% endif %}{% if result_errors %} }{% endif %}
+}
+{%- if config.generate_blocking_variants() %}
+
+/**
+ * Blocking variant of [{{ func.name()|fn_name }}].
+ *
+ * This blocks the calling thread until the Rust future completes, so it must not be called from a
+ * coroutine or from the main thread.
+ */
+{%- match func.throws_type() -%}
+{%- when Some with (throwable) %}
+{%- if !result_errors %}
+@Throws({{ throwable|type_name(ci, config) }}::class)
+{%- endif %}
+{%- else -%}
+{%- endmatch %}
+fun {% call kt::extension_receiver(func) %}{{ "{}_blocking"|format(func.name())|fn_name }}({%- call kt::arg_list_decl(func) -%}){% if result_errors %} : {{ func|result_type_name(ci, config) }}{% else %}{% match func.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name(ci, config) }}{% when None %}{%- endmatch %}{% endif %} =
+    kotlinx.coroutines.runBlocking { {{ func.name()|fn_name }}({%- for arg in func.arguments() %}{% if !(loop.first && func.is_extension()) %}{% if arg.variadic_type().is_some() %}*{% endif %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endif %}{% endfor %}) }
+{%- endif %}
+{%- if func.is_cancellable() %}
+
+/**
+ * Cancellable variant of [{{ func.name()|fn_name }}].
+ *
+ * Call `await()` on the returned handle for the result, or `cancel()` to cancel the call.
+ */
+fun {% call kt::extension_receiver(func) %}{{ "{}_cancellable"|format(func.name())|fn_name }}({%- call kt::arg_list_decl(func) -%}): UniffiCancellableHandle<{% match func.return_type() %}{% when Some with (return_type) %}{{ return_type|type_name(ci, config) }}{% when None %}Unit{%- endmatch %}> {
+    return uniffiRustCallCancellable(
+        UniffiLib.INSTANCE.{{ func.ffi_func().name() }}({% call kt::arg_list_lowered(func) %}),
+        {{ func|async_poll(ci) }},
+        {{ func|async_complete(ci) }},
+        {{ func|async_free(ci) }},
+        {{ func|async_cancel(ci) }},
+        // lift function
+        {%- match func.return_type() %}
+        {%- when Some(return_type) %}
+        { {{ return_type|lift_fn }}(it) },
+        {%- when None %}
+        { Unit },
+        {% endmatch %}
+        // Error FFI converter
+        {%- match func.throws_type() %}
+        {%- when Some(e) %}
+        {{ e|type_name(ci, config) }}.ErrorHandler,
         {%- when None %}
         UniffiNullRustCallStatusErrorHandler,
         {%- endmatch %}
     )
 }
+{%- endif %}
+{%- match ci.progress(func) %}
+{%- when Some with (progress) %}
+
+/**
+ * Variant of [{{ func.name()|fn_name }}] which passes each progress event to the `{{ progress.argument().name()|var_name|unquote }}` closure.
+ *
+ * The events are passed in order, in a coroutine on the caller's dispatcher, and all of them have
+ * been passed when this returns.
+ */
+{%- match func.throws_type() -%}
+{%- when Some with (throwable) %}
+{%- if !result_errors %}
+@Throws({{ throwable|type_name(ci, config) }}::class)
+{%- endif %}
+{%- else -%}
+{%- endmatch %}
+suspend fun {{ "{}_with_progress"|format(func.name())|fn_name }}(
+    {%- for arg in func.arguments() -%}
+    {%- if arg.is_progress() %}
+    {{- arg.name()|var_name }}: ({{ progress.event_type()|type_name(ci, config) }}) -> Unit
+    {%- else %}
+    {{- arg.name()|var_name }}: {{ arg|type_name(ci, config) }}
+    {%- endif %}
+    {%- if !loop.last %}, {% endif -%}
+    {%- endfor -%}
+){% if result_errors %} : {{ func|result_type_name(ci, config) }}{% else %}{% match func.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name(ci, config) }}{% when None %}{%- endmatch %}{% endif %} =
+    uniffiCallWithProgress({{ progress.argument().name()|var_name }}) { uniffiSend ->
+        {{ func.name()|fn_name }}({%- for arg in func.arguments() %}{% if arg.is_progress() %}object : {{ progress.listener_type()|type_name(ci, config) }} {
+            override fun {{ progress.event_method().name()|fn_name }}({{ progress.event_argument().name()|var_name }}: {{ progress.event_type()|type_name(ci, config) }}) = uniffiSend({{ progress.event_argument().name()|var_name }})
+        }{% else %}{{ arg.name()|var_name }}{% endif %}{% if !loop.last %}, {% endif %}{% endfor %})
+    }
+{%- when None %}
+{%- endmatch %}
 
 {%- else %}
 {%- match func.throws_type() -%}
 {%- when Some with (throwable) %}
-@Throws({{ throwable|type_name(ci) }}::class)
+{%- if !result_errors %}
+@Throws({{ throwable|type_name(ci, config) }}::class)
+{%- endif %}
 {%- else -%}
 {%- endmatch -%}
 
+{%- if func.has_out_args() %}
+{%- let outputs = func.out_params_outputs() %}
+{%- if outputs.len() > 1 %}
+
+/**
+ * The values returned by [{{ func.name()|fn_name }}]: {% if func.return_type().is_some() %}its result, followed by {% endif %}the final values of its out-parameters.
+ */
+data class {{ func|out_params_type_name(ci, config) }}(
+    {%- for (name, type_) in outputs.iter() %}
+    val {{ name|var_name }}: {{ type_|type_name(ci, config) }},
+    {%- endfor %}
+)
+{%- endif %}
+
+{% if config.kmp() && func.target().is_none() %}actual {% endif %}fun {% call kt::extension_receiver(func) %}{{ func.name()|fn_name }}({%- call kt::fn_arg_list_decl(func) -%}): {% if result_errors %}{{ func|result_type_name(ci, config) }}{% else %}{{ func|out_params_type_name(ci, config) }}{% endif %} {
+    {%- call kt::check_main_thread(func) %}
+    return {% if result_errors %}{{ func|result_of(ci, config) }} { {% endif %}uniffiLiftOutParams({% call kt::to_ffi_call(func) %}) { buf ->
+        {%- if outputs.len() > 1 %}
+        {{ func|out_params_type_name(ci, config) }}(
+            {%- for (name, type_) in outputs.iter() %}
+            {{ type_|read_fn }}(buf),
+            {%- endfor %}
+        )
+        {%- else %}
+        {%- for (name, type_) in outputs.iter() %}
+        {{ type_|read_fn }}(buf)
+        {%- endfor %}
+        {%- endif %}
+    }{% if result_errors %} }{% endif %}
+}
+{%- else if result_errors %}
+
+{% if config.kmp() && func.target().is_none() %}actual {% endif %}fun {% call kt::extension_receiver(func) %}{{ func.name()|fn_name }}({%- call kt::fn_arg_list_decl(func) -%}): {{ func|result_type_name(ci, config) }} {
+    {%- call kt::check_main_thread(func) %}
+    return {{ func|result_of(ci, config) }} {
+        {%- match func.return_type() %}
+        {%- when Some with (return_type) %}
+        {%- if func.is_with_warnings() %}
+        uniffiLiftWithWarnings({% call kt::to_ffi_call(func) %}, {{ return_type|ffi_converter_name }})
+        {%- else if func.is_segmented() %}
+        uniffiLiftByteSegments({% call kt::to_ffi_call(func) %})
+        {%- else %}
+        {{ return_type|lift_fn }}({% call kt::to_ffi_call(func) %})
+        {%- endif %}
+        {%- when None %}{% call kt::to_ffi_call(func) %}
+        {%- endmatch %}
+    }
+}
+{%- else %}
+
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
 
-fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}): {{ return_type|type_name(ci) }} {
+{% if config.kmp() && func.target().is_none() %}actual {% endif %}fun {% call kt::extension_receiver(func) %}{{ func.name()|fn_name }}({%- call kt::fn_arg_list_decl(func) -%}): {% if func.is_with_warnings() %}WithWarnings<{{ return_type|type_name(ci, config) }}>{% else %}{{ return_type|type_name(ci, config) }}{% endif %} {
+    {%- call kt::check_main_thread(func) %}
+    {%- if func.is_chunked() %}
+    return uniffiLiftChunkedSequence({% call kt::to_ffi_call(func) %}, {{ return_type|ffi_converter_name }})
+    {%- else if func.is_with_warnings() %}
+    return uniffiLiftWithWarnings({% call kt::to_ffi_call(func) %}, {{ return_type|ffi_converter_name }})
+    {%- else if func.is_segmented() %}
+    return uniffiLiftByteSegments({% call kt::to_ffi_call(func) %})
+    {%- else %}
     return {{ return_type|lift_fn }}({% call kt::to_ffi_call(func) %})
+    {%- endif %}
 }
 {% when None %}
 
-fun {{ func.name()|fn_name }}({% call kt::arg_list_decl(func) %}) =
+{%- if func.is_main_thread() %}
+
+{% if config.kmp() && func.target().is_none() %}actual {% endif %}fun {% call kt::extension_receiver(func) %}{{ func.name()|fn_name }}({% call kt::fn_arg_list_decl(func) %}) {
+    {%- call kt::check_main_thread(func) %}
+    {% call kt::to_ffi_call(func) %}
+}
+{%- else %}
+
+{% if config.kmp() && func.target().is_none() %}actual {% endif %}fun {% call kt::extension_receiver(func) %}{{ func.name()|fn_name }}({% call kt::fn_arg_list_decl(func) %}) =
     {% call kt::to_ffi_call(func) %}
+{%- endif %}
 
 {% endmatch %}
 {%- endif %}
+{%- endif %}
+{%- match func.default_on_none() %}
+{%- when Some with ((default, return_type)) %}
+
+/**
+ * Variant of [{{ func.name()|fn_name }}] which returns `{{ default|render_literal(return_type, ci, config) }}` rather than `null`.
+ */
+{%- match func.throws_type() -%}
+{%- when Some with (throwable) %}
+{%- if !result_errors %}
+@Throws({{ throwable|type_name(ci, config) }}::class)
+{%- endif %}
+{%- else -%}
+{%- endmatch %}
+fun {% call kt::extension_receiver(func) %}{{ "{}_or_default"|format(func.name())|fn_name }}({%- call kt::arg_list_decl(func) -%}): {% if result_errors %}Result<{{ return_type|type_name(ci, config) }}>{% else %}{{ return_type|type_name(ci, config) }}{% endif %} =
+    {{ func.name()|fn_name }}({%- for arg in func.arguments() %}{% if !(loop.first && func.is_extension()) %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endif %}{% endfor %}){% if result_errors %}.map { it ?: {{ default|render_literal(return_type, ci, config) }} }{% else %} ?: {{ default|render_literal(return_type, ci, config) }}{% endif %}
+{%- when None %}
+{%- endmatch %}
+{%- endif %}
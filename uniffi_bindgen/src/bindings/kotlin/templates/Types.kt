@@ -31,7 +31,7 @@ inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
     }
 
 {%- for type_ in ci.iter_types() %}
-{%- let type_name = type_|type_name(ci) %}
+{%- let type_name = type_|type_name(ci, config) %}
 {%- let ffi_converter_name = type_|ffi_converter_name %}
 {%- let canonical_type_name = type_|canonical_name %}
 {%- let contains_object_references = ci.item_contains_object_references(type_) %}
@@ -103,6 +103,9 @@ inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
 {%- when Type::Optional { inner_type } %}
 {% include "OptionalTemplate.kt" %}
 
+{%- when Type::NonZero { inner_type } %}
+{% include "NonZeroTemplate.kt" %}
+
 {%- when Type::Sequence { inner_type } %}
 {% include "SequenceTemplate.kt" %}
 
@@ -118,6 +121,21 @@ inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
 {%- when Type::Duration %}
 {% include "DurationHelper.kt" %}
 
+{%- when Type::DurationRange { inclusive } %}
+{% include "DurationRangeHelper.kt" %}
+
+{%- when Type::Path %}
+{% include "PathHelper.kt" %}
+
+{%- when Type::Json %}
+{% include "JsonHelper.kt" %}
+
+{%- when Type::DynError %}
+{% include "DynErrorHelper.kt" %}
+
+{%- when Type::OpaqueHandle %}
+{% include "OpaqueHandleHelper.kt" %}
+
 {%- when Type::Custom { module_path, name, builtin } %}
 {% include "CustomTypeTemplate.kt" %}
 
@@ -134,3 +152,9 @@ inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
 {{ self.add_import("kotlinx.coroutines.suspendCancellableCoroutine") }}
 {{ self.add_import("kotlinx.coroutines.CancellableContinuation") }}
 {%- endif %}
+
+{%- if ci.has_progress_fns() %}
+{{ self.add_import("kotlinx.coroutines.channels.Channel") }}
+{{ self.add_import("kotlinx.coroutines.coroutineScope") }}
+{{ self.add_import("kotlinx.coroutines.launch") }}
+{%- endif %}
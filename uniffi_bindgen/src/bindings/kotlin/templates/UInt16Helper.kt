@@ -1,19 +1,19 @@
-public object FfiConverterUShort: FfiConverter<UShort, Short> {
-    override fun lift(value: Short): UShort {
-        return value.toUShort()
+public object FfiConverterUShort: FfiConverter<{{ type_name }}, Short> {
+    override fun lift(value: Short): {{ type_name }} {
+        return value.to{{ type_name }}()
     }
 
-    override fun read(buf: ByteBuffer): UShort {
+    override fun read(buf: ByteBuffer): {{ type_name }} {
         return lift(buf.getShort())
     }
 
-    override fun lower(value: UShort): Short {
+    override fun lower(value: {{ type_name }}): Short {
         return value.toShort()
     }
 
-    override fun allocationSize(value: UShort) = 2
+    override fun allocationSize(value: {{ type_name }}) = 2
 
-    override fun write(value: UShort, buf: ByteBuffer) {
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
         buf.putShort(value.toShort())
     }
 }
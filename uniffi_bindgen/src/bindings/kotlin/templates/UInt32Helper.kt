@@ -1,19 +1,19 @@
-public object FfiConverterUInt: FfiConverter<UInt, Int> {
-    override fun lift(value: Int): UInt {
-        return value.toUInt()
+public object FfiConverterUInt: FfiConverter<{{ type_name }}, Int> {
+    override fun lift(value: Int): {{ type_name }} {
+        return value.to{{ type_name }}()
     }
 
-    override fun read(buf: ByteBuffer): UInt {
+    override fun read(buf: ByteBuffer): {{ type_name }} {
         return lift(buf.getInt())
     }
 
-    override fun lower(value: UInt): Int {
+    override fun lower(value: {{ type_name }}): Int {
         return value.toInt()
     }
 
-    override fun allocationSize(value: UInt) = 4
+    override fun allocationSize(value: {{ type_name }}) = 4
 
-    override fun write(value: UInt, buf: ByteBuffer) {
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
         buf.putInt(value.toInt())
     }
 }
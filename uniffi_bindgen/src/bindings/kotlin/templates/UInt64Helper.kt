@@ -1,19 +1,19 @@
-public object FfiConverterULong: FfiConverter<ULong, Long> {
-    override fun lift(value: Long): ULong {
-        return value.toULong()
+public object FfiConverterULong: FfiConverter<{{ type_name }}, Long> {
+    override fun lift(value: Long): {{ type_name }} {
+        return value.to{{ type_name }}()
     }
 
-    override fun read(buf: ByteBuffer): ULong {
+    override fun read(buf: ByteBuffer): {{ type_name }} {
         return lift(buf.getLong())
     }
 
-    override fun lower(value: ULong): Long {
+    override fun lower(value: {{ type_name }}): Long {
         return value.toLong()
     }
 
-    override fun allocationSize(value: ULong) = 8
+    override fun allocationSize(value: {{ type_name }}) = 8
 
-    override fun write(value: ULong, buf: ByteBuffer) {
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
         buf.putLong(value.toLong())
     }
 }
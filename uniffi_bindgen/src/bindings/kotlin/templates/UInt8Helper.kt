@@ -1,19 +1,19 @@
-public object FfiConverterUByte: FfiConverter<UByte, Byte> {
-    override fun lift(value: Byte): UByte {
-        return value.toUByte()
+public object FfiConverterUByte: FfiConverter<{{ type_name }}, Byte> {
+    override fun lift(value: Byte): {{ type_name }} {
+        return value.to{{ type_name }}()
     }
 
-    override fun read(buf: ByteBuffer): UByte {
+    override fun read(buf: ByteBuffer): {{ type_name }} {
         return lift(buf.get())
     }
 
-    override fun lower(value: UByte): Byte {
+    override fun lower(value: {{ type_name }}): Byte {
         return value.toByte()
     }
 
-    override fun allocationSize(value: UByte) = 1
+    override fun allocationSize(value: {{ type_name }}) = 1
 
-    override fun write(value: UByte, buf: ByteBuffer) {
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
         buf.put(value.toByte())
     }
 }
@@ -0,0 +1,23 @@
+/**
+ * A value returned along with a list of non-fatal warnings.
+ */
+data class WithWarnings<T>(
+    val value: T,
+    val warnings: List<String>,
+)
+
+// Lift the value returned by a function with warnings, which is followed by the warnings in the
+// same buffer.
+private fun <T> uniffiLiftWithWarnings(rbuf: RustBuffer.ByValue, converter: FfiConverter<T, *>): WithWarnings<T> {
+    val byteBuf = rbuf.asByteBuffer()!!
+    try {
+        val value = converter.read(byteBuf)
+        val warnings = FfiConverterSequenceString.read(byteBuf)
+        if (byteBuf.hasRemaining()) {
+            throw RuntimeException("junk remaining in buffer after lifting, something is very wrong!!")
+        }
+        return WithWarnings(value, warnings)
+    } finally {
+        RustBuffer.free(rbuf)
+    }
+}
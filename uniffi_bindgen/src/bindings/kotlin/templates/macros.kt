@@ -7,7 +7,7 @@
 {%- macro to_ffi_call(func) -%}
     {%- match func.throws_type() %}
     {%- when Some with (e) %}
-    uniffiRustCallWithError({{ e|type_name(ci) }})
+    uniffiRustCallWithError({{ e|type_name(ci, config) }})
     {%- else %}
     uniffiRustCall()
     {%- endmatch %} { _status ->
@@ -18,7 +18,7 @@
 {%- macro to_ffi_call_with_prefix(prefix, func) %}
     {%- match func.throws_type() %}
     {%- when Some with (e) %}
-    uniffiRustCallWithError({{ e|type_name(ci) }})
+    uniffiRustCallWithError({{ e|type_name(ci, config) }})
     {%- else %}
     uniffiRustCall()
     {%- endmatch %} { _status ->
@@ -29,9 +29,71 @@
 }
 {%- endmacro %}
 
+{#-
+// Call the Rust default implementation of a trait method, passing `this` as the trait object.
+-#}
+{%- macro to_ffi_default_call(meth) %}
+    {%- match meth.throws_type() %}
+    {%- when Some with (e) %}
+    uniffiRustCallWithError({{ e|type_name(ci, config) }})
+    {%- else %}
+    uniffiRustCall()
+    {%- endmatch %} { _status ->
+    UniffiLib.INSTANCE.{{ meth.ffi_default_func().unwrap().name() }}(
+        {{ ffi_converter_name }}.lower(this),
+        {% call arg_list_lowered(meth) %}
+        _status)
+}
+{%- endmacro %}
+
+{#-
+// Methods exported for an enum, which pass the value they're called on to Rust.
+-#}
+{%- macro enum_methods(e) %}
+    {%- let lower_self = "{}.lower(this)"|format(e|ffi_converter_name) %}
+    {%- for meth in e.methods() %}
+    {%- call callable_docstring(meth, 4) %}
+    {%- match meth.throws_type() %}
+    {%- when Some with (throwable) %}
+    @Throws({{ throwable|type_name(ci, config) }}::class)
+    {%- else %}
+    {%- endmatch %}
+    fun {{ meth.name()|fn_name }}(
+        {%- call arg_list_decl(meth) -%}
+    ){% match meth.return_type() %}{% when Some with (return_type) %}: {{ return_type|type_name(ci, config) }} =
+        {%- call to_ffi_call_with_prefix(lower_self, meth) %}.let {
+            {{ return_type|lift_fn }}(it)
+        }
+    {%- when None %} =
+        {%- call to_ffi_call_with_prefix(lower_self, meth) %}
+    {%- endmatch %}
+    {% endfor %}
+{%- endmacro %}
+
+{#-
+// Call `block` with the pointer a method is called with.  Consuming methods take over the object's
+// pointer, and objects with a parent pass the pointer to their own level of the hierarchy.
+-#}
+{%- macro call_with_pointer(obj, meth, impl_class_name) -%}
+    {%- if meth.takes_self_by_value() -%}
+    callWithConsumedPointer
+    {%- else -%}
+    callWithPointer{% if obj.parent().is_some() %}({ uniffiClone{{ impl_class_name }}Pointer() }){% endif %}
+    {%- endif -%}
+{%- endmacro -%}
+
+{#-
+// The receiver of an extension function is its first argument, which the body refers to as `this`.
+-#}
 {%- macro arg_list_lowered(func) %}
     {%- for arg in func.arguments() %}
-        {{- arg|lower_fn }}({{ arg.name()|var_name }}),
+        {%- if loop.first && func.is_extension() %}
+        {{- arg|lower_fn }}(this),
+        {%- else %}
+        {%- if arg.is_segmented() %}uniffiLowerByteSegments({{ arg.name()|var_name }}),
+        {%- else %}{{ arg|lower_fn }}({{ arg.name()|var_name }}{% if arg.variadic_type().is_some() %}.toList(){% endif %}),
+        {%- endif %}
+        {%- endif %}
     {%- endfor %}
 {%- endmacro -%}
 
@@ -42,21 +104,61 @@
 
 {% macro arg_list_decl(func) %}
     {%- for arg in func.arguments() -%}
-        {{ arg.name()|var_name }}: {{ arg|type_name(ci) }}
+        {%- if !(loop.first && func.is_extension()) %}
+        {%- match arg.variadic_type() %}
+        {%- when Some with (item_type) %}
+        {%- call variadic_arg_decl(arg, item_type) %}
+        {%- when None %}
+        {{- arg.name()|var_name }}: {{ arg|type_name(ci, config) }}
         {%- match arg.default_value() %}
-        {%- when Some with(literal) %} = {{ literal|render_literal(arg, ci) }}
+        {%- when Some with(literal) %} = {{ literal|render_literal(arg, ci, config) }}
         {%- else %}
         {%- endmatch %}
+        {%- endmatch %}
         {%- if !loop.last %}, {% endif -%}
+        {%- endif %}
     {%- endfor %}
 {%- endmacro %}
 
+{#-
+// The receiver type of an extension function, like `Client.` in `fun Client.process()`.
+-#}
+{%- macro extension_receiver(func) -%}
+{%- match func.extension_type() %}{% when Some with (receiver_type) %}{{ receiver_type|type_name(ci, config) }}.{% when None %}{% endmatch -%}
+{%- endmacro %}
+
+{#-
+// Arglist of a top-level function. In KMP mode the function is the `actual` of an `expect`
+// declaration, which has the default values instead, unless it's only for one target.
+-#}
+{% macro fn_arg_list_decl(func) %}
+    {%- if config.kmp() && func.target().is_none() %}
+    {%- call arg_list_protocol(func) %}
+    {%- else %}
+    {%- call arg_list_decl(func) %}
+    {%- endif %}
+{%- endmacro %}
+
 {% macro arg_list_protocol(func) %}
     {%- for arg in func.arguments() -%}
-        {{ arg.name()|var_name }}: {{ arg|type_name(ci) }}
+        {%- if !(loop.first && func.is_extension()) %}
+        {%- match arg.variadic_type() %}
+        {%- when Some with (item_type) %}
+        {%- call variadic_arg_decl(arg, item_type) %}
+        {%- when None %}
+        {{- arg.name()|var_name }}: {{ arg|type_name(ci, config) }}
+        {%- endmatch %}
         {%- if !loop.last %}, {% endif -%}
+        {%- endif %}
     {%- endfor %}
 {%- endmacro %}
+
+{#-
+// A variadic argument is an array in the function body, so it's converted to a list to be
+// lowered like any other sequence.
+-#}
+{%- macro variadic_arg_decl(arg, item_type) %}vararg {{ arg.name()|var_name }}: {{ item_type|type_name(ci, config) }}
+{%- endmacro %}
 {#-
 // Arglist as used in the UniffiLib function declarations.
 // Note unfiltered name but ffi_type_name filters.
@@ -87,3 +189,115 @@
 {%- macro docstring(defn, indent_spaces) %}
 {%- call docstring_value(defn.docstring(), indent_spaces) %}
 {%- endmacro %}
+
+{%- macro type_docstring(defn, indent_spaces) %}
+{%- call docstring_value(defn.docstring()|with_since(defn.since()), indent_spaces) %}
+{%- endmacro %}
+
+{%- macro callable_docstring(callable, indent_spaces) %}
+{%- call docstring_value(callable|callable_docstring, indent_spaces) %}
+{%- endmacro %}
+
+{#-
+// Objects, their constructors and their methods document who owns the Rust object when the
+// `ownership_docs` config is set.
+-#}
+{%- macro object_docstring(obj, indent_spaces) %}
+{%- if config.ownership_docs() %}
+{%- call docstring_value(obj|object_ownership_docstring, indent_spaces) %}
+{%- else %}
+{%- call type_docstring(obj, indent_spaces) %}
+{%- endif %}
+{%- endmacro %}
+
+{%- macro constructor_docstring(cons, indent_spaces) %}
+{%- if config.ownership_docs() %}
+{%- call docstring_value(cons|constructor_ownership_docstring, indent_spaces) %}
+{%- else %}
+{%- call callable_docstring(cons, indent_spaces) %}
+{%- endif %}
+{%- endmacro %}
+
+{%- macro method_docstring(meth, indent_spaces) %}
+{%- if config.ownership_docs() && !meth.is_enum_method() %}
+{%- call docstring_value(meth|method_ownership_docstring, indent_spaces) %}
+{%- else %}
+{%- call callable_docstring(meth, indent_spaces) %}
+{%- endif %}
+{%- endmacro %}
+
+{#-
+// The companion object of a record, with a `default()` function for `[HasDefault]` records.
+-#}
+{%- macro record_companion_object(rec, type_name) %}
+{%- if rec.has_default() %}
+    companion object {
+        /**
+         * The Rust `Default::default()` value of `{{ type_name }}`.
+         */
+        fun default(): {{ type_name }} = {{ rec|ffi_converter_name }}.lift(uniffiRustCall() { _status ->
+            UniffiLib.INSTANCE.{{ rec.ffi_func_default().name() }}(_status)
+        })
+    }
+{%- else %}
+    companion object
+{%- endif %}
+{%- endmacro %}
+
+{#-
+// Throws if a `[MainThread]` function is called from another thread.
+-#}
+{%- macro check_main_thread(func) %}
+{%- if func.is_main_thread() %}
+    check(Thread.currentThread().name == "main") { "{{ func.name()|fn_name }} must be called from the main thread" }
+{%- endif %}
+{%- endmacro %}
+
+{%- macro deprecated(defn, indent_spaces) %}
+{%- match defn.deprecated() %}
+{%- when Some(message) %}
+{{ message|deprecated(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
+
+{#-
+// Read or write an enum's discriminant, which is an `Int` unless the enum sets its `[Discriminant]`.
+-#}
+{%- macro read_discriminant(e) -%}
+{%- match e.discriminant_encoding() -%}
+{%- when Some with (encoding) -%}
+{%- if encoding.width() == 1 -%}
+(buf.get().toInt() and 0xff)
+{%- else if encoding.width() == 2 && encoding.is_little_endian() -%}
+(java.lang.Short.reverseBytes(buf.getShort()).toInt() and 0xffff)
+{%- else if encoding.width() == 2 -%}
+(buf.getShort().toInt() and 0xffff)
+{%- else if encoding.is_little_endian() -%}
+Integer.reverseBytes(buf.getInt())
+{%- else -%}
+buf.getInt()
+{%- endif -%}
+{%- when None -%}
+buf.getInt()
+{%- endmatch -%}
+{%- endmacro -%}
+
+{%- macro write_discriminant(e, value) -%}
+{%- match e.discriminant_encoding() -%}
+{%- when Some with (encoding) -%}
+{%- if encoding.width() == 1 -%}
+buf.put(({{ value }}).toByte())
+{%- else if encoding.width() == 2 && encoding.is_little_endian() -%}
+buf.putShort(java.lang.Short.reverseBytes(({{ value }}).toShort()))
+{%- else if encoding.width() == 2 -%}
+buf.putShort(({{ value }}).toShort())
+{%- else if encoding.is_little_endian() -%}
+buf.putInt(Integer.reverseBytes({{ value }}))
+{%- else -%}
+buf.putInt({{ value }})
+{%- endif -%}
+{%- when None -%}
+buf.putInt({{ value }})
+{%- endmatch -%}
+{%- endmacro -%}
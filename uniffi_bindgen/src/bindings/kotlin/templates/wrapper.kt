@@ -1,3 +1,7 @@
+{%- match config.file_header() %}{% when Some with (header) %}{{ header }}
+{% when None %}{% endmatch -%}
+{% if config.hash_header() %}// {{ ci.hash_header() }}
+{% endif -%}
 // This file was autogenerated by some hot garbage in the `uniffi` crate.
 // Trust me, you don't want to mess with it!
 
@@ -18,20 +22,9 @@ package {{ config.package_name() }};
 // now that means coming from the exact some version of `uniffi` that was used to
 // compile the Rust component. The easiest way to ensure this is to bundle the Kotlin
 // helpers directly inline like we're doing here.
-
-import com.sun.jna.Library
-import com.sun.jna.IntegerType
-import com.sun.jna.Native
-import com.sun.jna.Pointer
-import com.sun.jna.Structure
-import com.sun.jna.Callback
-import com.sun.jna.ptr.*
-import java.nio.ByteBuffer
-import java.nio.ByteOrder
-import java.nio.CharBuffer
-import java.nio.charset.CodingErrorAction
-import java.util.concurrent.ConcurrentHashMap
-
+{% for name in self.builtin_imports() %}
+import {{ name }}
+{%- endfor %}
 {%- for req in self.imports() %}
 {{ req.render() }}
 {%- endfor %}
@@ -49,11 +42,42 @@ import java.util.concurrent.ConcurrentHashMap
 {% include "Async.kt" %}
 {%- endif %}
 
+{%- if ci.has_chunked_fns() %}
+{% include "ChunkedSequence.kt" %}
+{%- endif %}
+
+{%- if ci.has_with_warnings_fns() %}
+{% include "WithWarnings.kt" %}
+{%- endif %}
+{%- if ci.has_out_params_fns() %}
+{% include "OutParams.kt" %}
+{%- endif %}
+
+{%- if ci.has_segmented_fns() %}
+{% include "ByteSegments.kt" %}
+{%- endif %}
+
 // Public interface members begin here.
 {{ type_helper_code }}
 
 {%- for func in ci.function_definitions() %}
+{%- if func.is_runtime_initializer() %}
+{%- include "RuntimeConfigTemplate.kt" %}
+{%- else if func.namespace_object().is_none() %}
 {%- include "TopLevelFunctionTemplate.kt" %}
+{%- endif %}
+{%- endfor %}
+
+{%- for func in ci.constant_definitions() %}
+{%- include "ConstantTemplate.kt" %}
 {%- endfor %}
 
+{%- for namespace_object in ci.namespace_objects() %}
+{%- include "NamespaceObjectTemplate.kt" %}
+{%- endfor %}
+
+{%- if ci.has_self_test() %}
+{% include "SelfTest.kt" %}
+{%- endif %}
+
 {% import "macros.kt" as kt %}
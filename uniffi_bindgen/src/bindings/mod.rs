@@ -7,9 +7,11 @@
 //! This module contains all the code for generating foreign language bindings,
 //! along with some helpers for executing foreign language scripts or tests.
 
-use anyhow::{bail, Result};
-use camino::Utf8Path;
+use anyhow::{bail, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use fs_err as fs;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 
 use crate::interface::ComponentInterface;
@@ -25,7 +27,7 @@ pub mod swift;
 /// on the provided `TargetLanguage`. For convenience of calling code we also provide
 /// a few `TryFrom` implementations to help guess the correct target language from
 /// e.g. a file extension of command-line argument.
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum TargetLanguage {
     Kotlin,
@@ -101,6 +103,17 @@ pub struct Config {
     pub(crate) ruby: ruby::Config,
 }
 
+/// A function which transforms generated source code before it's written.
+///
+/// This is the transformation point for running tools like `ktlint`, `black` or `swift-format`
+/// over the bindings.  It's passed the contents of the source file generated for its language,
+/// and returns the contents to write instead.  Only the source file is passed through it, not the
+/// header or modulemap generated for Swift.
+pub type Formatter = dyn Fn(&str) -> Result<String>;
+
+/// The [`Formatter`] to use for each language, if any.
+pub type Formatters = HashMap<TargetLanguage, Box<Formatter>>;
+
 /// Generate foreign language bindings from a compiled `uniffi` library.
 pub fn write_bindings(
     config: &Config,
@@ -108,18 +121,273 @@ pub fn write_bindings(
     out_dir: &Utf8Path,
     language: TargetLanguage,
     try_format_code: bool,
+) -> Result<()> {
+    write_bindings_with_formatter(config, ci, out_dir, language, try_format_code, None)
+}
+
+/// Like [`write_bindings`], but passes the generated source through `formatter` before it's
+/// written, and before the formatter enabled by `try_format_code` is run on the file.
+pub fn write_bindings_with_formatter(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    language: TargetLanguage,
+    try_format_code: bool,
+    formatter: Option<&Formatter>,
 ) -> Result<()> {
     match language {
-        TargetLanguage::Kotlin => {
-            kotlin::write_bindings(&config.kotlin, ci, out_dir, try_format_code)?
-        }
-        TargetLanguage::Swift => {
-            swift::write_bindings(&config.swift, ci, out_dir, try_format_code)?
-        }
-        TargetLanguage::Python => {
-            python::write_bindings(&config.python, ci, out_dir, try_format_code)?
+        TargetLanguage::Kotlin => kotlin::write_bindings_with_formatter(
+            &config.kotlin,
+            ci,
+            out_dir,
+            try_format_code,
+            formatter,
+        )?,
+        TargetLanguage::Swift => swift::write_bindings_with_formatter(
+            &config.swift,
+            ci,
+            out_dir,
+            try_format_code,
+            formatter,
+        )?,
+        TargetLanguage::Python => python::write_bindings_with_formatter(
+            &config.python,
+            ci,
+            out_dir,
+            try_format_code,
+            formatter,
+        )?,
+        TargetLanguage::Ruby => ruby::write_bindings_with_formatter(
+            &config.ruby,
+            ci,
+            out_dir,
+            try_format_code,
+            formatter,
+        )?,
+    }
+    Ok(())
+}
+
+/// Render foreign language bindings without writing them.
+///
+/// Returns the contents of each file which [`write_bindings`] would write, keyed by its path
+/// relative to the output directory.  No formatters are run on the contents.
+pub fn render_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    language: TargetLanguage,
+) -> Result<Vec<(Utf8PathBuf, String)>> {
+    match language {
+        TargetLanguage::Kotlin => kotlin::render_bindings(&config.kotlin, ci),
+        TargetLanguage::Swift => swift::render_bindings(&config.swift, ci),
+        TargetLanguage::Python => python::render_bindings(&config.python, ci),
+        TargetLanguage::Ruby => ruby::render_bindings(&config.ruby, ci),
+    }
+}
+
+/// Write a generated source file, after passing its contents through `formatter`.
+fn write_source_file(
+    path: &Utf8Path,
+    contents: String,
+    formatter: Option<&Formatter>,
+) -> Result<()> {
+    let contents = match formatter {
+        Some(formatter) => {
+            formatter(&contents).with_context(|| format!("Failed to format {path}"))?
         }
-        TargetLanguage::Ruby => ruby::write_bindings(&config.ruby, ci, out_dir, try_format_code)?,
+        None => contents,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
     }
+    fs::write(path, contents)?;
     Ok(())
 }
+
+/// Parse `udl` and generate bindings for it with a backend's `generate` function.
+///
+/// This is for tests which check the generated code.  `config` is the backend's config, as TOML.
+#[cfg(test)]
+pub(crate) fn generate_from_udl<C, T>(
+    udl: &str,
+    config: &str,
+    generate: impl FnOnce(&C, &ComponentInterface) -> Result<T>,
+) -> T
+where
+    C: serde::de::DeserializeOwned,
+{
+    let ci = ComponentInterface::from_webidl(udl, "crate_name").unwrap();
+    generate(&toml::from_str(config).unwrap(), &ci).unwrap()
+}
+
+/// Check whether generated code contains `snippet`, ignoring whitespace.
+///
+/// Checks which span several lines use this, so that they don't depend on how the templates
+/// indent and wrap the code.
+#[cfg(test)]
+pub(crate) fn contains_code(code: &str, snippet: &str) -> bool {
+    let strip = |s: &str| s.split_whitespace().collect::<String>();
+    strip(code).contains(&strip(snippet))
+}
+
+/// Parse `udl` and add `items` to it, as if they'd been exported with proc-macros.
+///
+/// This is for tests of items which can't be declared in UDL.
+#[cfg(test)]
+pub(crate) fn ci_with_metadata(udl: &str, items: Vec<uniffi_meta::Metadata>) -> ComponentInterface {
+    let mut ci = ComponentInterface::from_webidl(udl, "crate_name").unwrap();
+    ci.add_metadata(uniffi_meta::MetadataGroup {
+        namespace: uniffi_meta::NamespaceMetadata {
+            crate_name: "crate_name".into(),
+            name: "test".into(),
+        },
+        namespace_docstring: None,
+        items: items.into_iter().collect(),
+    })
+    .unwrap();
+    ci
+}
+
+/// The metadata of a sync function which doesn't throw, for [ci_with_metadata].
+#[cfg(test)]
+pub(crate) fn fn_metadata(
+    name: &str,
+    inputs: Vec<uniffi_meta::FnParamMetadata>,
+    return_type: Option<uniffi_meta::Type>,
+) -> uniffi_meta::FnMetadata {
+    uniffi_meta::FnMetadata {
+        module_path: "crate_name".into(),
+        name: name.into(),
+        is_async: false,
+        inputs,
+        return_type,
+        throws: None,
+        checksum: None,
+        docstring: None,
+        return_docstring: None,
+        since: None,
+        namespace_object: None,
+        deprecated: None,
+        retries: None,
+        main_thread: false,
+        chunked: false,
+        with_warnings: false,
+        segmented: false,
+        cancellable: false,
+        target: None,
+        extension: None,
+        default_on_none: None,
+        static_return: false,
+        runtime_initializer: false,
+    }
+}
+
+/// The metadata of a sync method which doesn't throw, for [ci_with_metadata].
+#[cfg(test)]
+pub(crate) fn method_metadata(
+    self_name: &str,
+    name: &str,
+    return_type: Option<uniffi_meta::Type>,
+) -> uniffi_meta::MethodMetadata {
+    uniffi_meta::MethodMetadata {
+        module_path: "crate_name".into(),
+        self_name: self_name.into(),
+        name: name.into(),
+        is_async: false,
+        inputs: vec![],
+        return_type,
+        throws: None,
+        takes_self_by_arc: false,
+        takes_self_by_value: false,
+        checksum: None,
+        docstring: None,
+        return_docstring: None,
+        since: None,
+    }
+}
+
+/// Generate bindings for a component with a runtime initializer, like the one
+/// `uniffi::export_runtime_config!()` exports, using the backend's default config.
+///
+/// Only proc-macros can export runtime initializers, so this adds the function's metadata directly.
+#[cfg(test)]
+pub(crate) fn generate_with_runtime_initializer<C, T>(
+    generate: impl FnOnce(&C, &ComponentInterface) -> Result<T>,
+) -> T
+where
+    C: serde::de::DeserializeOwned,
+{
+    use uniffi_meta::{FnParamMetadata, Type};
+
+    let optional = |name: &str, inner_type| {
+        FnParamMetadata::simple(
+            name,
+            Type::Optional {
+                inner_type: Box::new(inner_type),
+            },
+        )
+    };
+    let initializer = uniffi_meta::FnMetadata {
+        runtime_initializer: true,
+        ..fn_metadata(
+            "uniffi_initialize",
+            vec![
+                optional("max_buffer_len", Type::UInt64),
+                optional("max_read_depth", Type::UInt32),
+            ],
+            None,
+        )
+    };
+    let ci = ci_with_metadata("namespace test {};", vec![initializer.into()]);
+    generate(&toml::from_str("").unwrap(), &ci).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_formatter() {
+        let ci = ComponentInterface::from_webidl("namespace test {};", "crate_name").unwrap();
+        let out_dir = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("uniffi-test-formatter-{}", std::process::id()));
+        fs::create_dir_all(&out_dir).unwrap();
+
+        const MARKER: &str =
+            "# This file was autogenerated by some hot garbage in the `uniffi` crate.";
+        let formatter: &Formatter = &|source| Ok(source.replace(MARKER, &MARKER.to_uppercase()));
+        let config = Config::default();
+        write_bindings_with_formatter(
+            &config,
+            &ci,
+            &out_dir,
+            TargetLanguage::Python,
+            false,
+            Some(formatter),
+        )
+        .unwrap();
+        let python = fs::read_to_string(out_dir.join("test.py")).unwrap();
+        assert!(python.contains(&MARKER.to_uppercase()));
+        assert!(!python.contains(MARKER));
+
+        // Errors from the formatter name the file it failed on.
+        let formatter: &Formatter = &|_| anyhow::bail!("formatter failed");
+        let err = write_bindings_with_formatter(
+            &config,
+            &ci,
+            &out_dir,
+            TargetLanguage::Python,
+            false,
+            Some(formatter),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Failed to format {out_dir}/test.py")
+        );
+        assert_eq!(err.root_cause().to_string(), "formatter failed");
+
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+}
@@ -39,6 +39,35 @@ impl CodeType for OptionalCodeType {
     }
 }
 
+// Non-zero integers are plain `int`s, which are checked for zero when they're lowered.
+#[derive(Debug)]
+pub struct NonZeroCodeType {
+    inner: Type,
+}
+
+impl NonZeroCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for NonZeroCodeType {
+    fn type_label(&self) -> String {
+        super::PythonCodeOracle.find(&self.inner).type_label()
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "NonZero{}",
+            super::PythonCodeOracle.find(&self.inner).canonical_name(),
+        )
+    }
+
+    fn literal(&self, literal: &Literal) -> String {
+        super::PythonCodeOracle.find(&self.inner).literal(literal)
+    }
+}
+
 #[derive(Debug)]
 pub struct SequenceCodeType {
     inner: Type,
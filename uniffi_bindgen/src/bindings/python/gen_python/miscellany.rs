@@ -32,3 +32,36 @@ macro_rules! impl_code_type_for_miscellany {
 impl_code_type_for_miscellany!(TimestampCodeType, "Timestamp");
 
 impl_code_type_for_miscellany!(DurationCodeType, "Duration");
+
+impl_code_type_for_miscellany!(PathCodeType, "Path");
+
+impl_code_type_for_miscellany!(JsonCodeType, "Json");
+
+impl_code_type_for_miscellany!(DynErrorCodeType, "DynError");
+
+impl_code_type_for_miscellany!(OpaqueHandleCodeType, "OpaqueHandle");
+
+#[derive(Debug)]
+pub struct DurationRangeCodeType {
+    inclusive: bool,
+}
+
+impl DurationRangeCodeType {
+    pub fn new(inclusive: bool) -> Self {
+        Self { inclusive }
+    }
+}
+
+// Both kinds of range are a `DurationRange`, which knows whether it's inclusive.
+impl CodeType for DurationRangeCodeType {
+    fn type_label(&self) -> String {
+        "DurationRange".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        match self.inclusive {
+            false => "DurationRange".into(),
+            true => "DurationRangeInclusive".into(),
+        }
+    }
+}
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 use std::cell::RefCell;
 use std::collections::{BTreeSet, HashMap, HashSet};
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 use crate::backend::TemplateExpression;
 use crate::interface::*;
@@ -111,12 +111,34 @@ static KEYWORDS: Lazy<HashSet<String>> = Lazy::new(|| {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     cdylib_name: Option<String>,
+    generate_blocking_variants: Option<bool>,
+    enum_style: Option<EnumStyle>,
+    derive_display: Option<bool>,
+    file_header: Option<String>,
+    hash_header: Option<bool>,
+    ownership_docs: Option<bool>,
+    lazy_load: Option<bool>,
+    empty_string_as_none: Option<bool>,
+    split_modules: Option<bool>,
+    #[serde(default)]
+    extra_imports: Vec<String>,
     #[serde(default)]
     custom_types: HashMap<String, CustomTypeConfig>,
     #[serde(default)]
     external_packages: HashMap<String, String>,
 }
 
+/// How enums without associated data are represented in Python.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnumStyle {
+    /// A subclass of the stdlib `enum.Enum`, whose values are the variants' discriminants.
+    #[default]
+    Stdlib,
+    /// A class with a nested class for each variant, like enums with associated data.
+    Class,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CustomTypeConfig {
     // This `CustomTypeConfig` doesn't have a `type_name` like the others -- which is why we have
@@ -139,11 +161,68 @@ impl Config {
     pub fn module_for_namespace(&self, ns: &str) -> String {
         let ns = ns.to_string().to_snake_case();
         match self.external_packages.get(&ns) {
+            // The bindings live one level deeper when they're split into a package.
+            None if self.split_modules() => format!("..{ns}"),
             None => format!(".{ns}"),
             Some(value) if value.is_empty() => ns,
             Some(value) => format!("{value}.{ns}"),
         }
     }
+
+    /// Whether to generate a `_blocking` variant of each async function, which polls the Rust
+    /// future to completion on the calling thread
+    pub fn generate_blocking_variants(&self) -> bool {
+        self.generate_blocking_variants.unwrap_or(false)
+    }
+
+    /// Whether to generate a `__repr__` for records which lists their fields, summarizing bytes
+    /// rather than printing their contents.
+    pub fn derive_display(&self) -> bool {
+        self.derive_display.unwrap_or(false)
+    }
+
+    /// Text to insert verbatim at the top of each generated file, such as a license header.
+    pub fn file_header(&self) -> Option<&str> {
+        self.file_header.as_deref()
+    }
+
+    /// Whether to add a comment at the top of each generated file with the uniffi-bindgen version
+    /// and the interface's stable hash, so a checked-in copy can be checked against its source.
+    pub fn hash_header(&self) -> bool {
+        self.hash_header.unwrap_or(false)
+    }
+
+    /// Whether to document who owns the Rust object behind each object, its constructors and its
+    /// methods.
+    pub fn ownership_docs(&self) -> bool {
+        self.ownership_docs.unwrap_or(false)
+    }
+
+    /// Whether to load the library the first time it's used, rather than when the module is
+    /// imported
+    pub fn lazy_load(&self) -> bool {
+        self.lazy_load.unwrap_or(false)
+    }
+
+    /// Whether an empty string is passed as `None` where the Rust type is `Option<String>`, in
+    /// both directions.
+    pub fn empty_string_as_none(&self) -> bool {
+        self.empty_string_as_none.unwrap_or(false)
+    }
+
+    /// Whether to generate a package with submodules, rather than a single module
+    pub fn split_modules(&self) -> bool {
+        self.split_modules.unwrap_or(false)
+    }
+
+    /// Whether an enum is generated as a subclass of `enum.Enum`, rather than a class with
+    /// a nested class for each variant.  Enums with an `UNKNOWN` variant need the nested classes,
+    /// since `UNKNOWN` holds the discriminant.
+    pub fn is_stdlib_enum(&self, e: &Enum) -> bool {
+        e.is_flat()
+            && !e.has_unknown_variant()
+            && self.enum_style.unwrap_or_default() == EnumStyle::Stdlib
+    }
 }
 
 impl BindingsConfig for Config {
@@ -167,6 +246,54 @@ pub fn generate_python_bindings(config: &Config, ci: &ComponentInterface) -> Res
         .context("failed to render python bindings")
 }
 
+/// The submodules of the package generated with `split_modules = true`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PackageSubmodule {
+    Enums,
+    Records,
+    Interfaces,
+}
+
+impl PackageSubmodule {
+    pub const ALL: [Self; 3] = [Self::Enums, Self::Records, Self::Interfaces];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Enums => "enums",
+            Self::Records => "records",
+            Self::Interfaces => "interfaces",
+        }
+    }
+}
+
+impl fmt::Display for PackageSubmodule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// Generate the `__init__.py` of a split package, as a string.
+pub fn generate_python_package_init(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    PythonPackageInit { ci, config }
+        .render()
+        .context("failed to render python package")
+}
+
+// Generate a submodule of a split package, as a string.
+pub fn generate_python_package_submodule(
+    config: &Config,
+    ci: &ComponentInterface,
+    submodule: PackageSubmodule,
+) -> Result<String> {
+    PythonPackageSubmodule {
+        ci,
+        config,
+        submodule,
+    }
+    .render()
+    .with_context(|| format!("failed to render python submodule {submodule}"))
+}
+
 /// A struct to record a Python import statement.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum ImportRequirement {
@@ -210,7 +337,7 @@ impl ImportRequirement {
 #[derive(Template)]
 #[template(syntax = "py", escape = "none", path = "Types.py")]
 pub struct TypeRenderer<'a> {
-    python_config: &'a Config,
+    config: &'a Config,
     ci: &'a ComponentInterface,
     // Track included modules for the `include_once()` macro
     include_once_names: RefCell<HashSet<String>>,
@@ -219,9 +346,9 @@ pub struct TypeRenderer<'a> {
 }
 
 impl<'a> TypeRenderer<'a> {
-    fn new(python_config: &'a Config, ci: &'a ComponentInterface) -> Self {
+    fn new(config: &'a Config, ci: &'a ComponentInterface) -> Self {
         Self {
-            python_config,
+            config,
             ci,
             include_once_names: RefCell::new(HashSet::new()),
             imports: RefCell::new(BTreeSet::new()),
@@ -240,6 +367,11 @@ impl<'a> TypeRenderer<'a> {
             .insert(name.to_string())
     }
 
+    // Whether the converter for `Option<inner_type>` maps empty strings to `None`.
+    fn empty_string_as_none(&self, inner_type: &Type) -> bool {
+        self.config.empty_string_as_none() && *inner_type == Type::String
+    }
+
     // Helper to add an import statement
     //
     // Call this inside your template to cause an import statement to be added at the top of the
@@ -296,14 +428,154 @@ impl<'a> PythonWrapper<'a> {
         }
     }
 
+    /// The modules which `wrapper.py` always imports, for its helper code.
+    pub fn builtin_imports(&self) -> Vec<&'static str> {
+        let mut imports = vec![
+            "os",
+            "sys",
+            "ctypes",
+            "enum",
+            "struct",
+            "contextlib",
+            "datetime",
+            "json",
+            "pathlib",
+            "typing",
+            "warnings",
+        ];
+        if self.ci.has_async_fns() {
+            imports.push("asyncio");
+        }
+        if (self.ci.has_async_fns() && self.config.generate_blocking_variants())
+            || self.ci.has_cancellable_fns()
+            || self.config.lazy_load()
+        {
+            imports.push("threading");
+        }
+        imports.push("platform");
+        imports
+    }
+
+    /// The imports needed by the types in use and those from the `extra_imports` config, without
+    /// any of the builtin imports.
     pub fn imports(&self) -> Vec<ImportRequirement> {
-        self.type_imports.iter().cloned().collect()
+        let builtin_imports = self.builtin_imports();
+        let extra_imports =
+            self.config
+                .extra_imports
+                .iter()
+                .map(|mod_name| ImportRequirement::Module {
+                    mod_name: mod_name.clone(),
+                });
+        self.type_imports
+            .iter()
+            .cloned()
+            .chain(extra_imports)
+            .filter(|req| match req {
+                ImportRequirement::Module { mod_name } => {
+                    !builtin_imports.contains(&mod_name.as_str())
+                }
+                _ => true,
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+/// The `__init__.py` of a split package.
+///
+/// It re-exports everything from the submodules, along with the names which don't belong in any
+/// of them.
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "PackageInit.py")]
+pub struct PythonPackageInit<'a> {
+    ci: &'a ComponentInterface,
+    config: &'a Config,
+}
+
+impl<'a> PythonPackageInit<'a> {
+    pub fn submodules(&self) -> [PackageSubmodule; 3] {
+        PackageSubmodule::ALL
+    }
+
+    /// The public names which aren't re-exported by a submodule.
+    pub fn names(&self) -> Vec<String> {
+        let mut names = vec!["InternalError".to_string()];
+        for func in self.ci.function_definitions() {
+            if func.namespace_object().is_some() {
+                continue;
+            }
+            let name = PythonCodeOracle.fn_name(func.name());
+            names.push(name.clone());
+            if func.is_async() && self.config.generate_blocking_variants() {
+                names.push(format!("{name}_blocking"));
+            }
+            if func.is_cancellable() {
+                names.push(format!("{name}_cancellable"));
+            }
+        }
+        names.extend(
+            self.ci
+                .namespace_objects()
+                .into_iter()
+                .map(|nm| PythonCodeOracle.class_name(nm)),
+        );
+        if self.ci.has_dyn_error() {
+            names.push("DynError".to_string());
+        }
+        if self.ci.has_cancellable_fns() {
+            names.push("UniffiCancellableHandle".to_string());
+        }
+        names
+    }
+}
+
+/// A submodule of a split package, which re-exports one kind of type from `_uniffi.py`.
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "PackageSubmodule.py")]
+pub struct PythonPackageSubmodule<'a> {
+    ci: &'a ComponentInterface,
+    config: &'a Config,
+    submodule: PackageSubmodule,
+}
+
+impl<'a> PythonPackageSubmodule<'a> {
+    pub fn names(&self) -> Vec<String> {
+        match self.submodule {
+            PackageSubmodule::Enums => self
+                .ci
+                .enum_definitions()
+                .map(|e| e.as_codetype().type_label())
+                .collect(),
+            PackageSubmodule::Records => self
+                .ci
+                .record_definitions()
+                .map(|r| r.as_codetype().type_label())
+                .collect(),
+            PackageSubmodule::Interfaces => self
+                .ci
+                .object_definitions()
+                .iter()
+                .map(|o| o.as_codetype().type_label())
+                .chain(
+                    self.ci
+                        .callback_interface_definitions()
+                        .iter()
+                        .map(|c| PythonCodeOracle.class_name(c.name())),
+                )
+                .collect(),
+        }
     }
 }
 
+/// Escape a name which collides with a Python keyword.
+///
+/// Following PEP 8, we append an underscore rather than prepending one, which would make the
+/// name look private.
 fn fixup_keyword(name: String) -> String {
     if KEYWORDS.contains(&name) {
-        format!("_{name}")
+        format!("{name}_")
     } else {
         name
     }
@@ -409,6 +681,13 @@ impl<T: AsType> AsCodeType for T {
 
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
+            Type::DurationRange { inclusive } => {
+                Box::new(miscellany::DurationRangeCodeType::new(inclusive))
+            }
+            Type::Path => Box::new(miscellany::PathCodeType),
+            Type::Json => Box::new(miscellany::JsonCodeType),
+            Type::DynError => Box::new(miscellany::DynErrorCodeType),
+            Type::OpaqueHandle => Box::new(miscellany::OpaqueHandleCodeType),
 
             Type::Enum { name, .. } => Box::new(enum_::EnumCodeType::new(name)),
             Type::Object { name, .. } => Box::new(object::ObjectCodeType::new(name)),
@@ -422,6 +701,7 @@ impl<T: AsType> AsCodeType for T {
             Type::Sequence { inner_type } => {
                 Box::new(compounds::SequenceCodeType::new(*inner_type))
             }
+            Type::NonZero { inner_type } => Box::new(compounds::NonZeroCodeType::new(*inner_type)),
             Type::Map {
                 key_type,
                 value_type,
@@ -471,8 +751,30 @@ pub mod filters {
     pub(super) fn literal_py(
         literal: &Literal,
         as_ct: &impl AsCodeType,
+        config: &Config,
+        ci: &ComponentInterface,
     ) -> Result<String, askama::Error> {
-        Ok(as_ct.as_codetype().literal(literal))
+        let literal_py = as_ct.as_codetype().literal(literal);
+        Ok(match literal {
+            // Unless the enum is a stdlib `enum.Enum`, each variant is a class which needs to be
+            // instantiated.
+            Literal::Enum(_, type_) => {
+                let stdlib = match type_ {
+                    Type::Enum { name, .. } => ci.get_enum_definition(name),
+                    _ => None,
+                }
+                .map_or(
+                    config.enum_style.unwrap_or_default() == EnumStyle::Stdlib,
+                    |e| config.is_stdlib_enum(e),
+                );
+                if stdlib {
+                    literal_py
+                } else {
+                    format!("{literal_py}()")
+                }
+            }
+            _ => literal_py,
+        })
     }
 
     // Get the idiomatic Python rendering of an individual enum variant's discriminant
@@ -500,6 +802,23 @@ pub mod filters {
         Ok(PythonCodeOracle.var_name(nm))
     }
 
+    /// Get the `repr()` of a record field, with bytes summarized by their size.
+    pub fn display_field(field: &Field) -> Result<String, askama::Error> {
+        let name = format!("self.{}", var_name(field.name())?);
+        Ok(match field.as_type() {
+            Type::Bytes => format!("\"<{{}} bytes>\".format(len({name}))"),
+            Type::Optional { inner_type } if *inner_type == Type::Bytes => {
+                format!("None if {name} is None else \"<{{}} bytes>\".format(len({name}))")
+            }
+            _ => format!("repr({name})"),
+        })
+    }
+
+    /// Get the idiomatic Python rendering of a constant name.
+    pub fn const_name(nm: &str) -> Result<String, askama::Error> {
+        Ok(fixup_keyword(nm.to_string().to_shouty_snake_case()))
+    }
+
     /// Get the idiomatic Python rendering of an individual enum variant.
     pub fn enum_variant_py(nm: &str) -> Result<String, askama::Error> {
         Ok(PythonCodeOracle.enum_variant_name(nm))
@@ -510,6 +829,37 @@ pub mod filters {
         Ok(PythonCodeOracle.object_names(obj))
     }
 
+    /// The protocol that the protocol generated for `obj` extends, or an empty string if it
+    /// doesn't inherit from another interface.
+    pub fn parent_protocol_name(obj: &Object) -> Result<String, askama::Error> {
+        Ok(obj
+            .parent()
+            .map(|parent| format!("{}Protocol", PythonCodeOracle.class_name(parent)))
+            .unwrap_or_default())
+    }
+
+    /// The attribute holding the pointer to `obj`.
+    ///
+    /// Objects which inherit from another interface hold a pointer for each level of the
+    /// hierarchy, so each level has its own attribute.
+    pub fn pointer_attr(obj: &Object) -> Result<String, askama::Error> {
+        Ok(match obj.parent() {
+            Some(_) => format!("_{}_pointer", PythonCodeOracle.var_name(obj.name())),
+            None => "_pointer".to_string(),
+        })
+    }
+
+    /// The method which clones the pointer to `obj` for passing it to Rust.
+    pub fn clone_pointer_fn(obj: &Object) -> Result<String, askama::Error> {
+        Ok(match obj.parent() {
+            Some(_) => format!(
+                "_uniffi_clone_{}_pointer",
+                PythonCodeOracle.var_name(obj.name())
+            ),
+            None => "_uniffi_clone_pointer".to_string(),
+        })
+    }
+
     /// Get the idiomatic Python rendering of docstring
     pub fn docstring(docstring: &str, spaces: &i32) -> Result<String, askama::Error> {
         let docstring = textwrap::dedent(docstring);
@@ -522,4 +872,126 @@ pub mod filters {
         let spaces = usize::try_from(*spaces).unwrap_or_default();
         Ok(textwrap::indent(&wrapped, &" ".repeat(spaces)))
     }
+
+    /// Get the docstring of a function, method or constructor, with `Args:` and `Returns:`
+    /// sections for the argument and return value docs.
+    pub fn callable_docstring<T: Callable>(callable: &T) -> Result<Option<String>, askama::Error> {
+        callable_docstring_with_note(callable, None)
+    }
+
+    /// How the bindings release an object's reference, for the `ownership_docs` notes.
+    const OWNERSHIP_RELEASE: &str = "when the instance is garbage collected";
+
+    /// Get the docstring of an object, with a note on who owns its Rust object.
+    pub fn object_ownership_docstring(obj: &Object) -> Result<Option<String>, askama::Error> {
+        let docstring = join_note(obj.docstring(), obj.ownership_doc(OWNERSHIP_RELEASE));
+        with_since(Some(&docstring), obj.since())
+    }
+
+    /// Get the docstring of a constructor, with a note on who owns the new instance.
+    pub fn constructor_ownership_docstring(
+        cons: &Constructor,
+    ) -> Result<Option<String>, askama::Error> {
+        callable_docstring_with_note(cons, Some(cons.ownership_doc(OWNERSHIP_RELEASE)))
+    }
+
+    /// Get the docstring of a method, with a note on who owns the object during the call.
+    pub fn method_ownership_docstring(meth: &Method) -> Result<Option<String>, askama::Error> {
+        callable_docstring_with_note(meth, Some(meth.ownership_doc(OWNERSHIP_RELEASE)))
+    }
+
+    fn join_note(docstring: Option<&str>, note: String) -> String {
+        match docstring {
+            Some(docstring) => format!("{docstring}\n\n{note}"),
+            None => note,
+        }
+    }
+
+    /// The note is added to the description, before the sections.
+    fn callable_docstring_with_note<T: Callable>(
+        callable: &T,
+        note: Option<String>,
+    ) -> Result<Option<String>, askama::Error> {
+        let args: Vec<String> = callable
+            .arguments()
+            .into_iter()
+            .filter_map(|arg| {
+                let docstring = arg.docstring()?.replace('\n', "\n        ");
+                Some(format!(
+                    "    {}: {docstring}",
+                    PythonCodeOracle.var_name(arg.name())
+                ))
+            })
+            .collect();
+        let mut sections = vec![];
+        if !args.is_empty() {
+            sections.push(format!("Args:\n{}", args.join("\n")));
+        }
+        if let Some(returns) = callable.return_docstring() {
+            sections.push(format!("Returns:\n    {}", returns.replace('\n', "\n    ")));
+        }
+        if let Some(version) = callable.since() {
+            sections.push(format!("Since: {version}"));
+        }
+        let docstring = match note {
+            Some(note) => Some(join_note(callable.docstring(), note)),
+            None => callable.docstring().map(ToOwned::to_owned),
+        };
+        Ok(match (docstring, sections.is_empty()) {
+            (docstring, true) => docstring,
+            (None, false) => Some(sections.join("\n\n")),
+            (Some(docstring), false) => Some(format!("{docstring}\n\n{}", sections.join("\n\n"))),
+        })
+    }
+
+    /// Add a note about the deprecation to a docstring.
+    ///
+    /// This is used for records and enum variants, which can't warn when they're used since the
+    /// bindings construct them too.
+    pub fn with_deprecation_note(
+        docstring: Option<&str>,
+        deprecated: Option<&str>,
+    ) -> Result<Option<String>, askama::Error> {
+        Ok(match (docstring, deprecated) {
+            (docstring, None) => docstring.map(ToOwned::to_owned),
+            (None, Some(message)) => Some(format!("Deprecated: {message}")),
+            (Some(docstring), Some(message)) => {
+                Some(format!("{docstring}\n\nDeprecated: {message}"))
+            }
+        })
+    }
+
+    /// Add the version from a `@since` tag to the docstring of a type.
+    pub fn with_since(
+        docstring: Option<&str>,
+        since: Option<&str>,
+    ) -> Result<Option<String>, askama::Error> {
+        Ok(match (docstring, since) {
+            (docstring, None) => docstring.map(ToOwned::to_owned),
+            (None, Some(version)) => Some(format!("Since: {version}")),
+            (Some(docstring), Some(version)) => Some(format!("{docstring}\n\nSince: {version}")),
+        })
+    }
+
+    /// Get the docstring of a record, with notes about its deprecation and version.
+    pub fn record_docstring(rec: &Record) -> Result<Option<String>, askama::Error> {
+        let docstring = with_deprecation_note(rec.docstring(), rec.deprecated())?;
+        with_since(docstring.as_deref(), rec.since())
+    }
+
+    /// Get a statement which emits a `DeprecationWarning` with the deprecation message.
+    pub fn deprecation_warning(message: &str, spaces: &i32) -> Result<String, askama::Error> {
+        let message = message
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+        let spaces = usize::try_from(*spaces).unwrap_or_default();
+        Ok(format!(
+            "{}warnings.warn(\"{message}\", DeprecationWarning, stacklevel=2)",
+            " ".repeat(spaces)
+        ))
+    }
 }
+
+#[cfg(test)]
+mod tests;
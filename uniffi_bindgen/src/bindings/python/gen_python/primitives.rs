@@ -59,13 +59,15 @@ macro_rules! impl_code_type_for_primitive {
 impl_code_type_for_primitive!(BooleanCodeType, "bool", "Bool");
 impl_code_type_for_primitive!(StringCodeType, "str", "String");
 impl_code_type_for_primitive!(BytesCodeType, "bytes", "Bytes");
-impl_code_type_for_primitive!(Int8CodeType, "int", "Int8");
-impl_code_type_for_primitive!(Int16CodeType, "int", "Int16");
-impl_code_type_for_primitive!(Int32CodeType, "int", "Int32");
-impl_code_type_for_primitive!(Int64CodeType, "int", "Int64");
-impl_code_type_for_primitive!(UInt8CodeType, "int", "UInt8");
-impl_code_type_for_primitive!(UInt16CodeType, "int", "UInt16");
-impl_code_type_for_primitive!(UInt32CodeType, "int", "UInt32");
-impl_code_type_for_primitive!(UInt64CodeType, "int", "UInt64");
+// Python's `int` is unbounded, so the Rust type is recorded in the annotation.  Its range is
+// checked when the value is lowered.
+impl_code_type_for_primitive!(Int8CodeType, "typing.Annotated[int, 'i8']", "Int8");
+impl_code_type_for_primitive!(Int16CodeType, "typing.Annotated[int, 'i16']", "Int16");
+impl_code_type_for_primitive!(Int32CodeType, "typing.Annotated[int, 'i32']", "Int32");
+impl_code_type_for_primitive!(Int64CodeType, "typing.Annotated[int, 'i64']", "Int64");
+impl_code_type_for_primitive!(UInt8CodeType, "typing.Annotated[int, 'u8']", "UInt8");
+impl_code_type_for_primitive!(UInt16CodeType, "typing.Annotated[int, 'u16']", "UInt16");
+impl_code_type_for_primitive!(UInt32CodeType, "typing.Annotated[int, 'u32']", "UInt32");
+impl_code_type_for_primitive!(UInt64CodeType, "typing.Annotated[int, 'u64']", "UInt64");
 impl_code_type_for_primitive!(Float32CodeType, "float", "Float");
 impl_code_type_for_primitive!(Float64CodeType, "float", "Double");
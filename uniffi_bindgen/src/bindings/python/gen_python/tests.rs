@@ -0,0 +1,1023 @@
+use super::{
+    generate_python_bindings, generate_python_package_init, generate_python_package_submodule,
+    Config, PackageSubmodule,
+};
+use crate::bindings::{
+    ci_with_metadata, contains_code, fn_metadata, generate_from_udl,
+    generate_with_runtime_initializer, method_metadata,
+};
+use crate::interface::ComponentInterface;
+
+const NAMESPACE_UDL: &str = r#"
+    namespace test {
+        u32 top_level();
+        [Namespace=MyApi]
+        u32 grouped_one();
+        [Namespace=MyApi, Async]
+        u32 grouped_two(u32 value);
+    };
+"#;
+
+#[test]
+fn test_namespace_object_functions() {
+    let python = generate_from_udl(NAMESPACE_UDL, "", generate_python_bindings);
+    assert!(python.contains("\nclass MyApi:\n"));
+    assert!(python.contains("grouped_one = staticmethod(grouped_one)\n"));
+    assert!(python.contains("\ndel grouped_one\n"));
+    assert!(python.contains("\"MyApi\",\n"));
+    assert!(!python.contains("\"grouped_one\",\n"));
+}
+
+#[test]
+fn test_blocking_variants() {
+    // Blocking variants are only generated when they're asked for.
+    let python = generate_from_udl(NAMESPACE_UDL, "", generate_python_bindings);
+    assert!(!python.contains("_blocking("));
+
+    let python = generate_from_udl(
+        NAMESPACE_UDL,
+        "generate_blocking_variants = true",
+        generate_python_bindings,
+    );
+    assert!(
+        python.contains("\ndef grouped_two_blocking(value: \"typing.Annotated[int, 'u32']\"):\n")
+    );
+    assert!(python.contains("return _uniffi_rust_call_blocking(\n"));
+    assert!(python.contains("grouped_two_blocking = staticmethod(grouped_two_blocking)\n"));
+    assert!(!python.contains("grouped_one_blocking"));
+}
+
+#[test]
+fn test_deprecated() {
+    const UDL: &str = r#"
+        namespace test {
+            [Deprecated="Use new_thing instead"]
+            u32 old_thing();
+        };
+
+        enum Shape {
+            "Circle",
+            [Deprecated="Squares are going away"]
+            "Square",
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python
+        .contains("warnings.warn(\"Use new_thing instead\", DeprecationWarning, stacklevel=2)\n"));
+    assert!(contains_code(
+        &python,
+        "    SQUARE = 1\n    \"\"\"Deprecated: Squares are going away\"\"\""
+    ));
+}
+
+#[test]
+fn test_enum_style() {
+    const UDL: &str = r#"
+        namespace test {
+            Shape default_shape(optional Shape shape = "Square");
+        };
+
+        enum Shape { "Circle", "Square" };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(contains_code(
+        &python,
+        "\nclass Shape(enum.Enum):\n    CIRCLE = 0\n    \n    SQUARE = 1\n"
+    ));
+    assert!(python.contains("shape = Shape.SQUARE\n"));
+    assert!(python.contains("return Shape.CIRCLE\n"));
+
+    let python_class = generate_from_udl(UDL, "enum_style = \"class\"", generate_python_bindings);
+    assert!(python_class.contains("\nclass Shape:\n"));
+    assert!(python_class.contains("class CIRCLE:\n"));
+    assert!(python_class.contains("def is_circle(self) -> bool:\n"));
+    assert!(python_class.contains("shape = Shape.SQUARE()\n"));
+    assert!(python_class.contains("return Shape.CIRCLE()\n"));
+    assert!(contains_code(
+        &python_class,
+        "        if value.is_square():\n            return 2\n"
+    ));
+
+    // Either way, the enum is passed across the FFI as the same scalar.
+    let lower_argument = "_UniffiConverterTypeShape.lower(shape))";
+    assert!(python.contains(lower_argument));
+    assert!(python_class.contains(lower_argument));
+}
+
+#[test]
+fn test_empty_string_as_none() {
+    const UDL: &str = r#"
+        namespace test {
+            string? echo_string(string? value);
+            i32? echo_i32(i32? value);
+            bytes? echo_bytes(bytes? value);
+        };
+    "#;
+    let write_empty = "if value is None or value == \"\":\n";
+    let read_empty = "return _UniffiConverterString.read(buf) or None\n";
+
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(!python.contains(write_empty));
+    assert!(!python.contains(read_empty));
+
+    let python = generate_from_udl(UDL, "empty_string_as_none = true", generate_python_bindings);
+    assert_eq!(python.matches(write_empty).count(), 1);
+    assert!(python.contains(read_empty));
+    // Other optional types are unaffected.
+    assert!(python.contains("return _UniffiConverterInt32.read(buf)\n"));
+    // Empty bytes are a value, not an absent one.
+    assert!(python.contains("return _UniffiConverterBytes.read(buf)\n"));
+}
+
+#[test]
+fn test_split_modules() {
+    const UDL: &str = r#"
+        namespace test {
+            Point origin();
+        };
+        enum Axis { "X", "Y" };
+        dictionary Point { i32 x; i32 y; };
+        interface Canvas {
+            void draw(Point point);
+        };
+    "#;
+    let paths = |config: &str| -> Vec<String> {
+        generate_from_udl(UDL, config, crate::bindings::python::render_bindings)
+            .into_iter()
+            .map(|(path, _)| path.to_string())
+            .collect()
+    };
+    assert_eq!(paths(""), ["test.py"]);
+
+    const CONFIG: &str = "split_modules = true";
+    assert_eq!(
+        paths(CONFIG),
+        [
+            "test/__init__.py",
+            "test/_uniffi.py",
+            "test/enums.py",
+            "test/records.py",
+            "test/interfaces.py",
+        ]
+    );
+    let init = generate_from_udl(UDL, CONFIG, generate_python_package_init);
+    assert!(init.contains("from .records import *\n"));
+    assert!(init.contains("from ._uniffi import origin\n"));
+    let submodule = |submodule| {
+        generate_from_udl(UDL, CONFIG, |config: &Config, ci: &ComponentInterface| {
+            generate_python_package_submodule(config, ci, submodule)
+        })
+    };
+    let records = submodule(PackageSubmodule::Records);
+    assert!(records.contains("from ._uniffi import Point\n"));
+    assert!(!records.contains("Axis"));
+    let interfaces = submodule(PackageSubmodule::Interfaces);
+    assert!(interfaces.contains("from ._uniffi import Canvas\n"));
+}
+
+#[test]
+fn test_optional_throwing_return() {
+    const UDL: &str = r#"
+        namespace test {
+            [Throws=Failure]
+            string? find(u8 index);
+        };
+
+        [Error]
+        enum Failure { "Oops" };
+    "#;
+    // The optional is lifted from the return value of the call that checks for errors.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains(
+        "def find(index: \"typing.Annotated[int, 'u8']\") -> \"typing.Optional[str]\":\n"
+    ));
+    assert!(python.contains(
+        "return _UniffiConverterOptionalString.lift(\
+         _rust_call_with_error(_UniffiConverterTypeFailure,"
+    ));
+}
+
+#[test]
+fn test_shared_error_generated_once() {
+    const UDL: &str = r#"
+        namespace test {
+            [Throws=StoreError]
+            void open();
+            [Throws=StoreError]
+            string read(u32 key);
+            [Throws=StoreError]
+            void write(u32 key, string value);
+        };
+
+        [Error]
+        enum StoreError { "NotFound", "ReadOnly" };
+    "#;
+    // However many functions throw it, the exception hierarchy is only generated once.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert_eq!(python.matches("class StoreError(Exception):").count(), 1);
+    assert_eq!(python.matches("class NotFound(").count(), 1);
+    assert_eq!(
+        python
+            .matches("class _UniffiConverterTypeStoreError(")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_cancelled_call_status() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async, Cancellable]
+            u32 download();
+        };
+    "#;
+    // The `Cancelled` call status is raised as a cancellation, rather than as an error.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(contains_code(
+        &python,
+        "    elif call_status.code == _UniffiRustCallStatus.CALL_CANCELLED:\n        \
+        # Only async calls can be cancelled.\n        raise asyncio.CancelledError()"
+    ));
+
+    // `asyncio` is only imported for async functions.
+    let python = generate_from_udl(
+        "namespace test { u32 get(); };",
+        "",
+        generate_python_bindings,
+    );
+    assert!(!python.contains("CancelledError"));
+}
+
+#[test]
+fn test_wrong_thread_call_status() {
+    const UDL: &str = r#"
+        namespace test {};
+        [ThreadBound]
+        interface Context {
+            constructor();
+            void draw();
+        };
+    "#;
+    // The `WrongThread` call status is raised as its own error type.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(contains_code(
+        &python,
+        "    elif call_status.code == _UniffiRustCallStatus.CALL_WRONG_THREAD:\n        \
+        raise WrongThreadError(_UniffiConverterString.lift(call_status.error_buf))"
+    ));
+    assert!(python.contains("\"WrongThreadError\",\n"));
+
+    // The error type is only generated for components with thread-bound objects.
+    let python = generate_from_udl(
+        "namespace test {}; interface Context { void draw(); };",
+        "",
+        generate_python_bindings,
+    );
+    assert!(!python.contains("WrongThreadError"));
+}
+
+#[test]
+fn test_state_poisoned_call_status() {
+    const UDL: &str = r#"
+        namespace test {};
+        [OnPoison=error]
+        interface Counter {
+            constructor();
+            u32 increment();
+        };
+    "#;
+    // The `StatePoisoned` call status is raised as its own error type.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains("class StatePoisonedError(Exception):"));
+    assert!(python.contains("elif call_status.code == _UniffiRustCallStatus.CALL_STATE_POISONED:"));
+    assert!(python
+        .contains("raise StatePoisonedError(_UniffiConverterString.lift(call_status.error_buf))"));
+    assert!(python.contains("\"StatePoisonedError\","));
+
+    // The error type is only generated for components with `[OnPoison]` objects.
+    let python = generate_from_udl(
+        "namespace test {}; interface Counter { u32 increment(); };",
+        "",
+        generate_python_bindings,
+    );
+    assert!(!python.contains("StatePoisonedError"));
+}
+
+#[test]
+fn test_runtime_config() {
+    // The runtime initializer is wrapped in a builder, rather than exposed as a function.
+    let python = generate_with_runtime_initializer(generate_python_bindings);
+    assert!(python.contains("class UniffiRuntimeConfig:"));
+    assert!(python.contains(
+        "def max_read_depth(self, value: \"typing.Annotated[int, 'u32']\") -> \"UniffiRuntimeConfig\":"
+    ));
+    assert!(python.contains("def uniffi_initialize(config: UniffiRuntimeConfig) -> None:"));
+    assert!(python.contains("max_read_depth = config._max_read_depth"));
+    assert!(python.contains("\"UniffiRuntimeConfig\","));
+    assert!(python.contains("\"uniffi_initialize\","));
+}
+
+#[test]
+fn test_constants() {
+    let ci = ci_with_metadata(
+        "namespace test {};",
+        vec![uniffi_meta::ConstFnMetadata {
+            module_path: "crate_name".into(),
+            name: "app_version".into(),
+            return_type: uniffi_meta::Type::String,
+            checksum: None,
+            docstring: None,
+        }
+        .into()],
+    );
+    // Constants are read lazily, through the module's `__getattr__`.
+    let python = generate_python_bindings(&Default::default(), &ci).unwrap();
+    assert!(contains_code(
+        &python,
+        "def __getattr__(name): if name == \"app_version\":"
+    ));
+    assert!(!python.contains("\"app_version\","));
+}
+
+#[test]
+fn test_non_zero() {
+    let non_zero_u32 = uniffi_meta::Type::NonZero {
+        inner_type: Box::new(uniffi_meta::Type::UInt32),
+    };
+    let ci = ci_with_metadata(
+        "namespace test {};",
+        vec![fn_metadata(
+            "next_id",
+            vec![uniffi_meta::FnParamMetadata::simple(
+                "id",
+                non_zero_u32.clone(),
+            )],
+            Some(non_zero_u32),
+        )
+        .into()],
+    );
+    // Zero is checked for when lowering.
+    let python = generate_python_bindings(&Default::default(), &ci).unwrap();
+    assert!(python.contains("class _UniffiConverterNonZeroUInt32(_UniffiConverterUInt32):\n"));
+    assert!(python.contains("_UniffiConverterNonZeroUInt32.check_lower(id)\n"));
+}
+
+#[test]
+fn test_opaque_handle() {
+    const UDL: &str = r#"
+        namespace test {
+            opaque_handle echo_handle(opaque_handle handle);
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains("class OpaqueHandle:\n"));
+    assert!(python.contains("\"OpaqueHandle\",\n"));
+    assert!(python.contains("_UniffiConverterOpaqueHandle.check_lower(handle)\n"));
+}
+
+#[test]
+fn test_duration_ranges() {
+    const UDL: &str = r#"
+        namespace test {
+            duration_range shift(duration_range window, duration by);
+            duration_range_inclusive shift_closed(duration_range_inclusive window, duration by);
+        };
+    "#;
+    // There's a single `DurationRange` type, which knows whether it's inclusive.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert_eq!(
+        python
+            .matches("class DurationRange(typing.NamedTuple):\n")
+            .count(),
+        1
+    );
+    assert!(python.contains("\"DurationRange\",\n"));
+    assert!(python.contains(
+        "def shift_closed(window: \"DurationRange\",by: \"Duration\") -> \"DurationRange\":\n"
+    ));
+    assert!(python.contains("return DurationRange(start, end, True)\n"));
+}
+
+#[test]
+fn test_keyword_escaping() {
+    const UDL: &str = r#"
+        namespace test {
+            Keywords make_keywords(i32 class);
+        };
+        dictionary Keywords {
+            i32 class;
+            i32 in;
+            i32 None;
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains("class_: \"typing.Annotated[int, 'i32']\"\n"));
+    assert!(python.contains("in_: \"typing.Annotated[int, 'i32']\"\n"));
+    assert!(python.contains("none: \"typing.Annotated[int, 'i32']\"\n"));
+    assert!(python.contains("self.in_ = in_\n"));
+    assert!(python.contains(
+        "\ndef make_keywords(class_: \"typing.Annotated[int, 'i32']\") -> \"Keywords\":\n"
+    ));
+}
+
+#[test]
+fn test_record_wire_order() {
+    const UDL: &str = r#"
+        namespace test {};
+        dictionary Reordered {
+            [Order=1] string first;
+            [Order=0] u32 second;
+        };
+    "#;
+    // Fields are read and written in wire order.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(contains_code(
+        &python,
+        "            second=_UniffiConverterUInt32.read(buf),\n            \
+         first=_UniffiConverterString.read(buf),\n"
+    ));
+    assert!(contains_code(
+        &python,
+        "        _UniffiConverterUInt32.write(value.second, buf)\n        \
+         _UniffiConverterString.write(value.first, buf)\n"
+    ));
+}
+
+#[test]
+fn test_associated_constants() {
+    const UDL: &str = r#"
+        namespace test {};
+        interface Color {
+            /// Pure red
+            const u32 RED = 0xFF0000;
+            const boolean IS_OPAQUE = true;
+            const f64 SCALE = 1.5;
+            constructor();
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(contains_code(
+        &python,
+        "    RED = 0xff0000\n    \"\"\"Pure red\"\"\"\n"
+    ));
+    assert!(contains_code(
+        &python,
+        "    IS_OPAQUE = True\n    SCALE = 1.5\n"
+    ));
+}
+
+#[test]
+fn test_file_header_and_extra_imports() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async]
+            u32 wait();
+        };
+        interface Counter {
+            constructor();
+        };
+    "#;
+    // Extra imports which are already generated are only imported once.
+    const CONFIG: &str = r##"
+        file_header = "# Copyright Example Corp."
+        extra_imports = ["asyncio", "json", "extra", "extra"]
+    "##;
+    let python = generate_from_udl(UDL, CONFIG, generate_python_bindings);
+    let count = |needle: &str| python.matches(needle).count();
+    assert!(python.starts_with("# Copyright Example Corp.\n"));
+    assert_eq!(count("# Copyright Example Corp."), 1);
+    assert_eq!(count("\nimport asyncio\n"), 1);
+    assert_eq!(count("\nimport json\n"), 1);
+    assert_eq!(count("\nimport extra\n"), 1);
+}
+
+#[test]
+fn test_hash_header() {
+    const UDL: &str = r#"
+        namespace test {
+            u32 add(u32 a, u32 b);
+        };
+    "#;
+    let hash = ComponentInterface::from_webidl(UDL, "crate_name")
+        .unwrap()
+        .stable_hash();
+    let python = generate_from_udl(UDL, "hash_header = true", generate_python_bindings);
+    let plain = generate_from_udl(UDL, "", generate_python_bindings);
+    // The header is the first line, and the rest of the file is the same as without it.
+    assert_eq!(
+        python,
+        format!(
+            "# Generated by uniffi-bindgen {} from an interface with hash {hash:016x}\n{plain}",
+            env!("CARGO_PKG_VERSION"),
+        )
+    );
+}
+
+#[test]
+fn test_observable_interfaces() {
+    const UDL: &str = r#"
+        namespace test {};
+        callback interface Listener {
+            void on_tick(u32 count);
+        };
+        [Observable]
+        interface Clock {
+            void add_observer(Listener listener);
+            void remove_observer(Listener listener);
+        };
+        interface Plain {};
+    "#;
+    // Without a token from `add_observer`, the observer itself is removed.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(contains_code(
+        &python,
+        "        class _Observer(Listener):\n            \
+         def on_tick(self, count):\n                \
+         events.put(count)\n\n        \
+         observer = _Observer()\n        \
+         self.add_observer(observer)\n        \
+         token = observer\n"
+    ));
+    assert!(python.contains("self.remove_observer(token)\n"));
+    assert_eq!(python.matches("def observe(self)").count(), 1);
+}
+
+#[test]
+fn test_cursor_interfaces() {
+    const UDL: &str = r#"
+        namespace test {};
+        [Cursor]
+        interface Rows {
+            [Throws=QueryError]
+            sequence<string> next_batch(u32 count);
+        };
+        interface Plain {};
+        [Error]
+        enum QueryError { "Closed" };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(contains_code(
+        &python,
+        "        while True:\n            \
+         batch = self.next_batch(batch_size)\n            \
+         yield from batch\n            \
+         if len(batch) < batch_size:\n                return\n\n    \
+         def __iter__(self):\n        return self.iter_items()\n"
+    ));
+    assert_eq!(python.matches("def iter_items(self").count(), 1);
+}
+
+#[test]
+fn test_extension_functions() {
+    const UDL: &str = r#"
+        namespace test {
+            [Extension=Client]
+            string process(Client client, u32 count);
+        };
+        interface Client {};
+    "#;
+    // Python still generates a plain function.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains("def process(client: \"Client\",count: "));
+}
+
+#[test]
+fn test_variadic_arguments() {
+    const UDL: &str = r#"
+        namespace test {
+            string join_words(string separator, [Variadic] sequence<string> words);
+        };
+    "#;
+    // The sequence is taken as a list.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains("def join_words(separator: \"str\",words: \"typing.List[str]\")"));
+}
+
+#[test]
+fn test_derive_display() {
+    const UDL: &str = r#"
+        namespace test {};
+        dictionary Point {
+            i32 x;
+            i32 y;
+        };
+        dictionary Shape {
+            Point origin;
+            string? label;
+            bytes data;
+            bytes? thumbnail;
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(!python.contains("def __repr__(self):"));
+    let python = generate_from_udl(UDL, "derive_display = true", generate_python_bindings);
+    assert!(python.contains("return \"Point(x={}, y={})\".format(repr(self.x), repr(self.y))"));
+    assert!(python.contains(
+        "return \"Shape(origin={}, label={}, data={}, thumbnail={})\".format(repr(self.origin), repr(self.label), \"<{} bytes>\".format(len(self.data)), None if self.thumbnail is None else \"<{} bytes>\".format(len(self.thumbnail)))"
+    ));
+}
+
+#[test]
+fn test_since() {
+    const UDL: &str = r#"
+        namespace test {};
+        /// A point.
+        /// @since 1.2.0
+        dictionary Point {
+            i32 x;
+        };
+        /// @since 1.3.0
+        interface Counter {
+            constructor();
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(contains_code(
+        &python,
+        "class Point:\n    \"\"\"\n    A point.\n\n    Since: 1.2.0\n    \"\"\""
+    ));
+    assert!(contains_code(
+        &python,
+        "class Counter:\n    \"\"\"Since: 1.3.0\"\"\""
+    ));
+}
+
+#[test]
+fn test_shared_composite_types() {
+    const UDL: &str = r#"
+        namespace test {
+            u32 count(record<string, sequence<u32>> groups);
+            record<string, sequence<u32>> merge(record<string, sequence<u32>> groups);
+            sequence<string> names();
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert_eq!(
+        python
+            .matches("class _UniffiConverterMapStringSequenceUInt32")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_enum_default_variant() {
+    const UDL: &str = r#"
+        namespace test {};
+        [NonExhaustive]
+        enum Color { "Red", [Default] "Unknown" };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains("return Color.UNKNOWN\n"));
+    assert!(!python.contains("Raw enum value doesn't match any cases"));
+}
+
+#[test]
+fn test_enum_unknown_variant() {
+    const UDL: &str = r#"
+        namespace test {};
+        [NonExhaustive]
+        enum Color { "Red", "Green" };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains("class UNKNOWN:\n"));
+    assert!(python.contains("return Color.UNKNOWN(value)\n"));
+    assert!(!python.contains("class Color(enum.Enum):"));
+}
+
+#[test]
+fn test_enum_discriminant_encoding() {
+    const UDL: &str = r#"
+        namespace test {};
+        [Discriminant=u16_le]
+        enum Opcode { "Read", "Write" };
+        [Enum, Discriminant=u8]
+        interface Shape {
+            Circle(double radius);
+            Point();
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains("variant = buf._unpack_from(2, \"<H\")"));
+    assert!(python.contains("buf._pack_into(2, \"<H\", 1)"));
+    assert!(python.contains("variant = buf._unpack_from(1, \">B\")"));
+    assert!(python.contains("buf._pack_into(1, \">B\", 2)"));
+}
+
+#[test]
+fn test_object_identity_equality() {
+    const UDL: &str = r#"
+        namespace test {};
+        interface Counter {
+            constructor();
+        };
+        [Traits=(Eq)]
+        interface Point {
+            constructor();
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python
+        .contains("_rust_call(_UniffiLib.uniffi_crate_name_fn_handle_id_counter, self._pointer)"));
+    assert!(python.contains("return self._uniffi_handle_id() == other._uniffi_handle_id()"));
+    assert_eq!(python.matches("def _uniffi_handle_id(").count(), 1);
+}
+
+#[test]
+fn test_chunked_sequences() {
+    const UDL: &str = r#"
+        namespace test {
+            [Chunked]
+            sequence<string> get_names();
+            sequence<string> get_names_unchunked();
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains(
+        "return _uniffi_lift_chunked_sequence(_rust_call(_UniffiLib.uniffi_crate_name_fn_func_get_names,), _UniffiConverterSequenceString)"
+    ));
+    assert_eq!(python.matches("_uniffi_lift_chunked_sequence(").count(), 2);
+}
+
+#[test]
+fn test_with_warnings() {
+    const UDL: &str = r#"
+        namespace test {
+            [WithWarnings]
+            u32 parse(string input);
+            [WithWarnings, Throws=Error]
+            string load();
+        };
+        [Error]
+        enum Error { "Oops" };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains("def parse(input: \"str\") -> \"WithWarnings\":"));
+    assert!(python.contains("_UniffiLib.uniffi_crate_name_fn_func_load,), _UniffiConverterString)"));
+}
+
+#[test]
+fn test_segmented() {
+    const UDL: &str = r#"
+        namespace test {
+            [Segmented]
+            sequence<bytes> split(bytes data, u32 size);
+            [Throws=Error]
+            u64 total_len([Segmented] sequence<bytes> segments);
+        };
+        [Error]
+        enum Error { "Oops" };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains(
+        "return _uniffi_lift_byte_segments(_rust_call(_UniffiLib.uniffi_crate_name_fn_func_split,"
+    ));
+    assert!(python.contains("_uniffi_lower_byte_segments(segments)))"));
+}
+
+#[test]
+fn test_has_default() {
+    const UDL: &str = r#"
+        namespace test {};
+        [HasDefault]
+        dictionary Settings {
+            string name;
+            u32 retries;
+        };
+        dictionary Point {
+            i32 x;
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains(
+        "return _UniffiConverterTypeSettings.lift(_rust_call(_UniffiLib.uniffi_crate_name_fn_default_settings))"
+    ));
+    assert_eq!(python.matches("def default(cls)").count(), 1);
+}
+
+#[test]
+fn test_progress() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async]
+            u32 count_steps(u32 steps, [Progress] ProgressListener listener);
+        };
+        callback interface ProgressListener {
+            void on_progress(u32 step);
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains(
+        "async def count_steps_with_progress(steps: \"typing.Annotated[int, 'u32']\",listener: \"typing.Callable[[typing.Annotated[int, 'u32']], None]\"):"
+    ));
+    assert!(python.contains("\"count_steps_with_progress\","));
+    assert!(python.contains("return await count_steps(steps, _UniffiProgress())"));
+    assert!(python.contains("return await _uniffi_call_with_progress(listener, call)"));
+}
+
+#[test]
+fn test_enum_methods() {
+    const UDL: &str = r#"
+        namespace test {};
+        [Enum]
+        interface Shape {
+            Circle(double radius);
+            Point();
+        };
+    "#;
+    let ci = ci_with_metadata(
+        UDL,
+        vec![method_metadata("Shape", "area", Some(uniffi_meta::Type::Float64)).into()],
+    );
+    let python = generate_python_bindings(&Default::default(), &ci).unwrap();
+    assert!(python.contains("def area(self, ) -> \"float\":"));
+    assert!(python.contains(
+        "_rust_call(_UniffiLib.uniffi_crate_name_fn_method_shape_area,_UniffiConverterTypeShape.lower(self),)"
+    ));
+}
+
+#[test]
+fn test_consuming_methods() {
+    const UDL: &str = r#"
+        namespace test {};
+        interface Builder {
+            constructor();
+            void append(string part);
+        };
+    "#;
+    let ci = ci_with_metadata(
+        UDL,
+        vec![uniffi_meta::MethodMetadata {
+            takes_self_by_value: true,
+            ..method_metadata("Builder", "build", Some(uniffi_meta::Type::String))
+        }
+        .into()],
+    );
+    let python = generate_python_bindings(&Default::default(), &ci).unwrap();
+    assert!(python.contains(
+        "_rust_call(_UniffiLib.uniffi_crate_name_fn_method_builder_build,self._uniffi_consume_pointer(),)"
+    ));
+    assert!(python.contains("raise ValueError(\"`Builder` object has already been consumed\")"));
+}
+
+#[test]
+fn test_lazy_load() {
+    const UDL: &str = r#"
+        namespace test {
+            void register(Listener listener);
+        };
+        callback interface Listener {
+            void notify();
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(
+        python.contains("\n_UniffiLib = _uniffi_load_indirect()\n_uniffi_init_lib(_UniffiLib)\n")
+    );
+    assert!(!python.contains("_UniffiLazyLib"));
+
+    let python = generate_from_udl(UDL, "lazy_load = true", generate_python_bindings);
+    assert!(python.contains("\n_UniffiLib = _UniffiLazyLib()\n"));
+    assert!(!python.contains("_UniffiLib = _uniffi_load_indirect()"));
+    // Callback interfaces are registered once the library is loaded.
+    assert!(python.contains(
+        "_UniffiLib._uniffi_on_load(lambda lib: lib.uniffi_crate_name_fn_init_callback_listener("
+    ));
+}
+
+#[test]
+fn test_cancellable() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async, Cancellable]
+            u32 download(string url);
+            [Async]
+            u32 upload(string url);
+        };
+    "#;
+    // The handle type is generated, along with a variant of the function returning it.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains("class UniffiCancellableHandle:\n"));
+    assert!(python.contains("def download_cancellable(url: \"str\") -> UniffiCancellableHandle:\n"));
+    assert!(python.contains("_UniffiLib.ffi_crate_name_rust_future_cancel_u32,\n"));
+    assert!(!python.contains("def upload_cancellable("));
+    assert!(python.contains("\"download_cancellable\",\n"));
+    assert!(python.contains("\nimport threading\n"));
+
+    // Without a cancellable function, the handle type isn't generated.
+    let udl = UDL.replace("[Async, Cancellable]", "[Async]");
+    let python = generate_from_udl(&udl, "", generate_python_bindings);
+    assert!(!python.contains("UniffiCancellableHandle"));
+}
+
+#[test]
+fn test_default_on_none() {
+    const UDL: &str = r#"
+        namespace test {
+            [DefaultOnNone="7"]
+            u32? find_count(string key);
+            [DefaultOnNone="unknown"]
+            string? find_name();
+        };
+    "#;
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains(
+        "def find_count_or_default(key: \"str\") -> \"typing.Annotated[int, 'u32']\":\n"
+    ));
+    assert!(python.contains("result = find_count(key)\n"));
+    assert!(python.contains("return 7\n"));
+    assert!(python.contains("return \"unknown\"\n"));
+    assert!(python.contains("\"find_count_or_default\",\n"));
+}
+
+#[test]
+fn test_ownership_docs() {
+    const UDL: &str = r#"
+        namespace test {};
+        /// A node in a tree.
+        interface Node {
+            /// Create a root node.
+            constructor(string name);
+            /// The parent of this node.
+            Node? parent();
+        };
+    "#;
+    let python = generate_from_udl(UDL, "ownership_docs = true", generate_python_bindings);
+    assert!(python.contains("def __init__(self, name: \"str\"):
+        \"\"\"
+        Create a root node.
+
+        The new instance owns a reference to the Rust object, which is released when the instance is garbage collected.
+        \"\"\""));
+    assert!(python.contains("def parent(self, ) -> \"typing.Optional[Node]\":
+        \"\"\"
+        The parent of this node.
+
+        Rust borrows this object for the duration of the call. Returned objects own a reference to their Rust object, which is released when the instance is garbage collected.
+        \"\"\""));
+
+    // Without the config, only the docstrings are there.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(!python.contains("owns a reference"));
+}
+
+#[test]
+fn test_out_params() {
+    let out_param = |name: &str, ty| uniffi_meta::FnParamMetadata {
+        out: true,
+        ..uniffi_meta::FnParamMetadata::simple(name, ty)
+    };
+    let ci = ci_with_metadata(
+        "namespace test {};",
+        vec![
+            fn_metadata(
+                "divide",
+                vec![
+                    uniffi_meta::FnParamMetadata::simple("dividend", uniffi_meta::Type::UInt32),
+                    uniffi_meta::FnParamMetadata::simple("divisor", uniffi_meta::Type::UInt32),
+                    out_param("remainder", uniffi_meta::Type::UInt32),
+                ],
+                Some(uniffi_meta::Type::UInt32),
+            )
+            .into(),
+            fn_metadata(
+                "trim",
+                vec![out_param("text", uniffi_meta::Type::String)],
+                None,
+            )
+            .into(),
+        ],
+    );
+    let python = generate_python_bindings(&Default::default(), &ci).unwrap();
+    // Several outputs are returned as a tuple, a single one is returned directly.
+    assert!(python.contains(
+        "-> \"typing.Tuple[typing.Annotated[int, 'u32'], typing.Annotated[int, 'u32']]\":"
+    ));
+    assert!(contains_code(
+        &python,
+        "_UniffiConverterUInt32.lower(remainder)), _UniffiConverterUInt32, _UniffiConverterUInt32, )"
+    ));
+    assert!(python.contains("def trim(text: \"str\") -> \"str\":"));
+}
+
+#[test]
+fn test_callback_interface_gone() {
+    const UDL: &str = r#"
+        namespace test {
+            void register(Listener listener);
+        };
+        callback interface Listener {
+            void notify();
+        };
+    "#;
+    // A handle which isn't in the handle map is reported to Rust, rather than raised.
+    let python = generate_from_udl(UDL, "", generate_python_bindings);
+    assert!(python.contains("_UNIFFI_CALLBACK_INTERFACE_GONE = 3\n"));
+    assert!(contains_code(
+        &python,
+        "cb = _UniffiConverterCallbackInterfaceListener._handle_map.get_or_none(handle)
+        if cb is None:
+            return _UNIFFI_CALLBACK_INTERFACE_GONE"
+    ));
+}
@@ -5,13 +5,15 @@
 use std::process::Command;
 
 use anyhow::Result;
-use camino::Utf8Path;
-use fs_err as fs;
+use camino::{Utf8Path, Utf8PathBuf};
 
 pub mod gen_python;
 mod test;
 use super::super::interface::ComponentInterface;
-pub use gen_python::{generate_python_bindings, Config};
+pub use gen_python::{
+    generate_python_bindings, generate_python_package_init, generate_python_package_submodule,
+    Config, PackageSubmodule,
+};
 pub use test::{run_script, run_test};
 
 // Generate python bindings for the given ComponentInterface, in the given output directory.
@@ -21,17 +23,68 @@ pub fn write_bindings(
     out_dir: &Utf8Path,
     try_format_code: bool,
 ) -> Result<()> {
-    let py_file = out_dir.join(format!("{}.py", ci.namespace()));
-    fs::write(&py_file, generate_python_bindings(config, ci)?)?;
+    write_bindings_with_formatter(config, ci, out_dir, try_format_code, None)
+}
+
+/// Like [`write_bindings`], but passes the generated source through `formatter` before it's
+/// written.
+pub fn write_bindings_with_formatter(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+    formatter: Option<&super::Formatter>,
+) -> Result<()> {
+    let mut py_files = Vec::new();
+    for (path, contents) in render_bindings(config, ci)? {
+        let py_file = out_dir.join(path);
+        super::write_source_file(&py_file, contents, formatter)?;
+        py_files.push(py_file);
+    }
 
     if try_format_code {
-        if let Err(e) = Command::new("yapf").arg(&py_file).output() {
-            println!(
-                "Warning: Unable to auto-format {} using yapf: {e:?}",
-                py_file.file_name().unwrap(),
-            )
+        for py_file in py_files {
+            if let Err(e) = Command::new("yapf").arg(&py_file).output() {
+                println!(
+                    "Warning: Unable to auto-format {} using yapf: {e:?}",
+                    py_file.file_name().unwrap(),
+                )
+            }
         }
     }
 
     Ok(())
 }
+
+/// Render the Python bindings, keyed by their paths relative to the output directory.
+pub fn render_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<Vec<(Utf8PathBuf, String)>> {
+    if !config.split_modules() {
+        return Ok(vec![(
+            format!("{}.py", ci.namespace()).into(),
+            generate_python_bindings(config, ci)?,
+        )]);
+    }
+    // A package, whose `__init__.py` re-exports the submodules.  Everything is defined in
+    // `_uniffi.py`, which the submodules import from.
+    let package_dir = Utf8PathBuf::from(ci.namespace());
+    let mut files = vec![
+        (
+            package_dir.join("__init__.py"),
+            generate_python_package_init(config, ci)?,
+        ),
+        (
+            package_dir.join("_uniffi.py"),
+            generate_python_bindings(config, ci)?,
+        ),
+    ];
+    for submodule in PackageSubmodule::ALL {
+        files.push((
+            package_dir.join(format!("{submodule}.py")),
+            generate_python_package_submodule(config, ci, submodule)?,
+        ));
+    }
+    Ok(files)
+}
@@ -16,20 +16,119 @@ def _uniffi_set_future_result(future, poll_code):
     if not future.cancelled():
         future.set_result(poll_code)
 
-async def _uniffi_rust_call_async(rust_future, ffi_poll, ffi_complete, ffi_free, lift_func, error_ffi_converter):
+async def _uniffi_poll_rust_future(rust_future, ffi_poll):
+    eventloop = asyncio.get_running_loop()
+
+    # Loop and poll until we see a _UNIFFI_RUST_FUTURE_POLL_READY value
+    while True:
+        future = eventloop.create_future()
+        ffi_poll(
+            rust_future,
+            _uniffi_continuation_callback,
+            _UniffiContinuationPointerManager.new_pointer((eventloop, future)),
+        )
+        poll_code = await future
+        if poll_code == _UNIFFI_RUST_FUTURE_POLL_READY:
+            break
+
+async def _uniffi_rust_call_async(rust_future, ffi_poll, ffi_complete, ffi_free, ffi_cancel, lift_func, error_ffi_converter):
     try:
-        eventloop = asyncio.get_running_loop()
+        try:
+            await _uniffi_poll_rust_future(rust_future, ffi_poll)
+        except asyncio.CancelledError:
+            # The awaiting task was cancelled, so cancel the Rust future too.  This wakes up any
+            # pending continuation before the future is freed.
+            ffi_cancel(rust_future)
+            raise
+        return lift_func(
+            _rust_call_with_error(error_ffi_converter, ffi_complete, rust_future)
+        )
+    finally:
+        ffi_free(rust_future)
+{%- if ci.has_cancellable_fns() %}
+
+class UniffiCancellableHandle:
+    """
+    A call to an async function marked with `[Cancellable]`.
+
+    Awaiting the handle waits for the call to complete, and it can only be awaited once.  Calling
+    `cancel()`, from any thread, cancels the Rust future, which makes the awaiting coroutine raise
+    `asyncio.CancelledError`.
+    """
+
+    def __init__(self, rust_future, ffi_poll, ffi_complete, ffi_free, ffi_cancel, lift_func, error_ffi_converter):
+        self._rust_future = rust_future
+        self._ffi_poll = ffi_poll
+        self._ffi_complete = ffi_complete
+        self._ffi_free = ffi_free
+        self._ffi_cancel = ffi_cancel
+        self._lift_func = lift_func
+        self._error_ffi_converter = error_ffi_converter
+        # Guards the Rust future, so that it's never cancelled after it's been freed.
+        self._lock = threading.Lock()
+        self._awaited = False
+        self._cancelled = False
+        self._freed = False
+
+    def cancel(self):
+        with self._lock:
+            if not self._cancelled and not self._freed:
+                self._cancelled = True
+                self._ffi_cancel(self._rust_future)
 
-        # Loop and poll until we see a _UNIFFI_RUST_FUTURE_POLL_READY value
+    def __await__(self):
+        return self._uniffi_call().__await__()
+
+    async def _uniffi_call(self):
+        if self._awaited:
+            raise RuntimeError("UniffiCancellableHandle can only be awaited once")
+        self._awaited = True
+        try:
+            await _uniffi_poll_rust_future(self._rust_future, self._ffi_poll)
+            # A cancelled future completes with the `CALL_CANCELLED` status, which raises
+            # `asyncio.CancelledError`.
+            return self._lift_func(
+                _rust_call_with_error(self._error_ffi_converter, self._ffi_complete, self._rust_future)
+            )
+        finally:
+            with self._lock:
+                self._freed = True
+                self._ffi_free(self._rust_future)
+{%- endif %}
+{%- if config.generate_blocking_variants() %}
+
+# Continuation callback for the blocking variants of async functions
+# store the poll code and wake up the thread that's waiting for it.
+@_UNIFFI_FUTURE_CONTINUATION_T
+def _uniffi_blocking_continuation_callback(waiter_ptr, poll_code):
+    waiter = _UniffiContinuationPointerManager.release_pointer(waiter_ptr)
+    waiter.set_poll_code(poll_code)
+
+class _UniffiBlockingWaiter:
+    def __init__(self):
+        self._event = threading.Event()
+        self._poll_code = None
+
+    def set_poll_code(self, poll_code):
+        self._poll_code = poll_code
+        self._event.set()
+
+    def wait(self):
+        self._event.wait()
+        return self._poll_code
+
+def _uniffi_rust_call_blocking(rust_future, ffi_poll, ffi_complete, ffi_free, lift_func, error_ffi_converter):
+    try:
+        # Loop and poll until we see a _UNIFFI_RUST_FUTURE_POLL_READY value, blocking the calling
+        # thread while Rust makes progress.
         while True:
-            future = eventloop.create_future()
+            waiter = _UniffiBlockingWaiter()
             ffi_poll(
                 rust_future,
-                _uniffi_continuation_callback,
-                _UniffiContinuationPointerManager.new_pointer((eventloop, future)),
+                _uniffi_blocking_continuation_callback,
+                _UniffiContinuationPointerManager.new_pointer(waiter),
             )
-            poll_code = await future
-            if poll_code == _UNIFFI_RUST_FUTURE_POLL_READY:
+            if waiter.wait() == _UNIFFI_RUST_FUTURE_POLL_READY:
                 break
 
         return lift_func(
@@ -37,3 +136,18 @@ async def _uniffi_rust_call_async(rust_future, ffi_poll, ffi_complete, ffi_free,
         )
     finally:
         ffi_free(rust_future)
+{%- endif %}
+{%- if ci.has_progress_fns() %}
+
+async def _uniffi_call_with_progress(on_progress, call):
+    # Calls an async function with a `[Progress]` argument.  The listener which `call` passes to
+    # the function schedules each event on the event loop, which passes them to `on_progress` in
+    # order rather than on the thread Rust reports them from.  The function's completion is
+    # scheduled the same way, so all of the events have been passed when this returns.
+    eventloop = asyncio.get_running_loop()
+
+    def send(event):
+        eventloop.call_soon_threadsafe(on_progress, event)
+
+    return await call(send)
+{%- endif %}
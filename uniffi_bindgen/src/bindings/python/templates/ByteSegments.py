@@ -0,0 +1,30 @@
+# Pass byte segments to Rust without concatenating them, by copying each one onto a handle from
+# Rust.  Rust takes over the handle when it's passed as the argument.
+def _uniffi_lower_byte_segments(segments):
+    handle = _rust_call(_UniffiLib.{{ ci.ffi_byte_segments_new().name() }})
+    try:
+        for segment in segments:
+            if not isinstance(segment, bytes):
+                segment = bytes(segment)
+            # Rust copies the segment straight out of the `bytes` object.
+            data = ctypes.cast(ctypes.c_char_p(segment), ctypes.POINTER(ctypes.c_char))
+            _rust_call(_UniffiLib.{{ ci.ffi_byte_segments_push().name() }}, handle, _UniffiForeignBytes(len(segment), data))
+    except:
+        _rust_call(_UniffiLib.{{ ci.ffi_byte_segments_free().name() }}, handle)
+        raise
+    return handle
+
+# Take the byte segments Rust returns, from the handle returned by the FFI function.  Each segment
+# is returned in its own buffer.
+def _uniffi_lift_byte_segments(handle):
+    try:
+        segments = []
+        for _ in range(_rust_call(_UniffiLib.{{ ci.ffi_byte_segments_len().name() }}, handle)):
+            buf = _rust_call(_UniffiLib.{{ ci.ffi_byte_segments_next().name() }}, handle)
+            try:
+                segments.append(ctypes.string_at(buf.data, buf.len))
+            finally:
+                buf.free()
+        return segments
+    finally:
+        _rust_call(_UniffiLib.{{ ci.ffi_byte_segments_free().name() }}, handle)
@@ -48,8 +48,6 @@ def {{ callback_handler_class }}(handle, method, args_data, args_len, buf_ptr):
 
     {% endfor %}
 
-    cb = {{ ffi_converter_name }}._handle_map.get(handle)
-
     if method == IDX_CALLBACK_FREE:
         {{ ffi_converter_name }}._handle_map.remove(handle)
 
@@ -60,6 +58,10 @@ def {{ callback_handler_class }}(handle, method, args_data, args_len, buf_ptr):
     {% for meth in methods.iter() -%}
     {% let method_name = format!("invoke_{}", meth.name())|fn_name -%}
     if method == {{ loop.index }}:
+        # The handle is missing if it's already been freed, which Rust reports as `CallbackInterfaceGone`.
+        cb = {{ ffi_converter_name }}._handle_map.get_or_none(handle)
+        if cb is None:
+            return _UNIFFI_CALLBACK_INTERFACE_GONE
         # Call the method and handle any errors
         # See docs of ForeignCallback in `uniffi_core/src/ffi/foreigncallbacks.rs` for details
         try:
@@ -88,4 +90,8 @@ def {{ callback_handler_class }}(handle, method, args_data, args_len, buf_ptr):
 # that is in freed memory.
 # That would be...uh...bad. Yeah, that's the word. Bad.
 {{ callback_handler_obj }} = _UNIFFI_FOREIGN_CALLBACK_T({{ callback_handler_class }})
+{%- if config.lazy_load() %}
+_UniffiLib._uniffi_on_load(lambda lib: lib.{{ ffi_init_callback.name() }}({{ callback_handler_obj }}))
+{%- else %}
 _UniffiLib.{{ ffi_init_callback.name() }}({{ callback_handler_obj }})
+{%- endif %}
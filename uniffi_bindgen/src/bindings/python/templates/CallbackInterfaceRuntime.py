@@ -10,7 +10,8 @@ class ConcurrentHandleMap:
         self._left_map = {}  # type: Dict[Handle, Any]
 
         self._lock = threading.Lock()
-        self._current_handle = 0
+        # Handle 0 is never handed out, so Rust can reject it.
+        self._current_handle = 1
         self._stride = 1
 
     def insert(self, obj):
@@ -27,6 +28,10 @@ class ConcurrentHandleMap:
             raise InternalError("No callback in handlemap; this is a uniffi bug")
         return obj
 
+    def get_or_none(self, handle):
+        with self._lock:
+            return self._left_map.get(handle)
+
     def remove(self, handle):
         with self._lock:
             if handle in self._left_map:
@@ -40,6 +45,7 @@ IDX_CALLBACK_FREE = 0
 _UNIFFI_CALLBACK_SUCCESS = 0
 _UNIFFI_CALLBACK_ERROR = 1
 _UNIFFI_CALLBACK_UNEXPECTED_ERROR = 2
+_UNIFFI_CALLBACK_INTERFACE_GONE = 3
 
 class UniffiCallbackInterfaceFfiConverter:
     _handle_map = ConcurrentHandleMap()
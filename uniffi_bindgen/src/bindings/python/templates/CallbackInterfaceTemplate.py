@@ -5,6 +5,7 @@
 {%- let protocol_name = type_name.clone() %}
 {%- let methods = cbi.methods() %}
 {%- let protocol_docstring = cbi.docstring() %}
+{%- let protocol_parent = "" %}
 
 {% include "Protocol.py" %}
 {% include "CallbackInterfaceImpl.py" %}
@@ -0,0 +1,12 @@
+# Reassemble a sequence that Rust returns in chunks, from the handle returned by the FFI function.
+# An empty chunk means the sequence is exhausted.
+def _uniffi_lift_chunked_sequence(handle, ffi_converter):
+    try:
+        items = []
+        while True:
+            chunk = ffi_converter.lift(_rust_call(_UniffiLib.{{ ci.ffi_chunked_sequence_next().name() }}, handle))
+            if not chunk:
+                return items
+            items.extend(chunk)
+    finally:
+        _rust_call(_UniffiLib.{{ ci.ffi_chunked_sequence_free().name() }}, handle)
@@ -0,0 +1,18 @@
+{%- if !ci.constant_definitions().is_empty() %}
+
+# Constants are computed by calling into Rust the first time they're accessed, via the module
+# `__getattr__` hook.  The value is then stored as a module global, so later accesses don't get
+# here.  They're left out of `__all__`, since `import *` would compute them all.
+def __getattr__(name):
+    {%- for func in ci.constant_definitions() %}
+    {%- match func.return_type() %}
+    {%- when Some with (return_type) %}
+    if name == "{{ func.name()|var_name }}":
+        value = {{ return_type|lift_fn }}({% call py::to_ffi_call(func) %})
+        globals()[name] = value
+        return value
+    {%- when None %}
+    {%- endmatch %}
+    {%- endfor %}
+    raise AttributeError(f"module {__name__!r} has no attribute {name!r}")
+{%- endif %}
@@ -0,0 +1,45 @@
+{%- if !inclusive || !ci.has_duration_range(false) %}
+class DurationRange(typing.NamedTuple):
+    """
+    A range of durations.
+
+    `end` is part of the range if `inclusive` is true, like Rust's `RangeInclusive`, and it isn't
+    otherwise, like Rust's `Range`.  Ranges whose `end` is before their `start` are empty.
+    """
+
+    start: datetime.timedelta
+    end: datetime.timedelta
+    inclusive: bool = False
+
+    def __contains__(self, value):
+        if self.inclusive:
+            return self.start <= value <= self.end
+        return self.start <= value < self.end
+
+    def is_empty(self):
+        if self.inclusive:
+            return self.end < self.start
+        return self.end <= self.start
+{%- endif %}
+
+class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        start = _UniffiConverterDuration.read(buf)
+        end = _UniffiConverterDuration.read(buf)
+        if buf.read_u8() != {% if inclusive %}1{% else %}0{% endif %}:
+            raise InternalError("Unexpected {% if inclusive %}exclusive{% else %}inclusive{% endif %} range")
+        return DurationRange(start, end, {% if inclusive %}True{% else %}False{% endif %})
+
+    @staticmethod
+    def check_lower(value):
+        if {% if inclusive %}not {% endif %}value.inclusive:
+            raise ValueError("expected {% if inclusive %}an inclusive{% else %}an exclusive{% endif %} DurationRange")
+        _UniffiConverterDuration.check_lower(value.start)
+        _UniffiConverterDuration.check_lower(value.end)
+
+    @staticmethod
+    def write(value, buf):
+        _UniffiConverterDuration.write(value.start, buf)
+        _UniffiConverterDuration.write(value.end, buf)
+        buf.write_u8(1 if value.inclusive else 0)
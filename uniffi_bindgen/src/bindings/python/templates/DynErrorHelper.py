@@ -0,0 +1,32 @@
+class DynError(Exception):
+    """
+    A dynamically typed error raised by Rust.
+
+    `message` is the `Display` string of the Rust error, and `cause_chain` holds the `Display`
+    strings of its chain of sources, starting with the immediate source.
+    """
+
+    def __init__(self, message, cause_chain):
+        super().__init__(message)
+        self.message = message
+        self.cause_chain = cause_chain
+
+    def __repr__(self):
+        return "DynError({!r}, cause_chain={!r})".format(self.message, self.cause_chain)
+
+class _UniffiConverterDynError(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        message = _UniffiConverterString.read(buf)
+        cause_chain = _UniffiConverterSequenceString.read(buf)
+        return DynError(message, cause_chain)
+
+    @staticmethod
+    def check_lower(value):
+        _UniffiConverterString.check_lower(value.message)
+        _UniffiConverterSequenceString.check_lower(value.cause_chain)
+
+    @staticmethod
+    def write(value, buf):
+        _UniffiConverterString.write(value.message, buf)
+        _UniffiConverterSequenceString.write(value.cause_chain, buf)
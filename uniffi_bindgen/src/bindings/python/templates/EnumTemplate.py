@@ -2,27 +2,32 @@
 # Python has a built-in `enum` module which is nice to use, but doesn't support
 # variants with associated data. So, we switch here, and generate a stdlib `enum`
 # when none of the variants have associated data, or a generic nested-class
-# construct when they do.
+# construct when they do, or when the `enum_style` config asks for it.
 #}
-{% if e.is_flat() %}
+{%- let stdlib = config.is_stdlib_enum(e) %}
+{%- let lower_self = "{}.lower(self)"|format(ffi_converter_name) %}
+{% if stdlib %}
 
 class {{ type_name }}(enum.Enum):
-    {%- call py::docstring(e, 4) %}
+    {%- call py::type_docstring(e, 4) %}
     {%- for variant in e.variants() %}
     {{ variant.name()|enum_variant_py }} = {{ e|variant_discr_literal(loop.index0) }}
-    {%- call py::docstring(variant, 4) %}
+    {%- call py::docstring_with_deprecation(variant, 4) %}
     {% endfor %}
+    {%- for meth in e.methods() %}
+    {%- call py::method_decl(meth.name()|fn_name, meth, lower_self) %}
+    {%- endfor %}
 {% else %}
 
 class {{ type_name }}:
-    {%- call py::docstring(e, 4) %}
+    {%- call py::type_docstring(e, 4) %}
     def __init__(self):
         raise RuntimeError("{{ type_name }} cannot be instantiated directly")
 
     # Each enum variant is a nested class of the enum itself.
     {% for variant in e.variants() -%}
     class {{ variant.name()|enum_variant_py }}:
-        {%- call py::docstring(variant, 8) %}
+        {%- call py::docstring_with_deprecation(variant, 8) %}
 
         {%- for field in variant.fields() %}
         {{ field.name()|var_name }}: "{{ field|type_name }}"
@@ -51,6 +56,24 @@ class {{ type_name }}:
             {%- endfor %}
             return True
     {% endfor %}
+    {%- if e.has_unknown_variant() %}
+    class UNKNOWN:
+        """
+        A variant which these bindings don't know about, because it was added to the
+        non-exhaustive enum after they were generated.
+        """
+
+        discriminant: int
+
+        def __init__(self, discriminant: int):
+            self.discriminant = discriminant
+
+        def __str__(self):
+            return "{{ type_name }}.UNKNOWN(discriminant={})".format(self.discriminant)
+
+        def __eq__(self, other):
+            return other.is_unknown() and self.discriminant == other.discriminant
+    {% endif %}
 
     # For each variant, we have an `is_NAME` method for easily checking
     # whether an instance is that variant.
@@ -58,6 +81,13 @@ class {{ type_name }}:
     def is_{{ variant.name()|var_name }}(self) -> bool:
         return isinstance(self, {{ type_name }}.{{ variant.name()|enum_variant_py }})
     {% endfor %}
+    {%- if e.has_unknown_variant() %}
+    def is_unknown(self) -> bool:
+        return isinstance(self, {{ type_name }}.UNKNOWN)
+    {% endif %}
+    {%- for meth in e.methods() %}
+    {%- call py::method_decl(meth.name()|fn_name, meth, lower_self) %}
+    {%- endfor %}
 
 # Now, a little trick - we make each nested variant class be a subclass of the main
 # enum class, so that method calls and instance checks etc will work intuitively.
@@ -65,17 +95,20 @@ class {{ type_name }}:
 {% for variant in e.variants() -%}
 {{ type_name }}.{{ variant.name()|enum_variant_py }} = type("{{ type_name }}.{{ variant.name()|enum_variant_py }}", ({{ type_name }}.{{variant.name()|enum_variant_py}}, {{ type_name }},), {})  # type: ignore
 {% endfor %}
+{%- if e.has_unknown_variant() %}
+{{ type_name }}.UNKNOWN = type("{{ type_name }}.UNKNOWN", ({{ type_name }}.UNKNOWN, {{ type_name }},), {})  # type: ignore
+{% endif %}
 
 {% endif %}
 
-class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
+class {{ ffi_converter_name }}{% if !e.is_flat() %}(_UniffiConverterRustBuffer){% endif %}:
     @staticmethod
     def read(buf):
-        variant = buf.read_i32()
+        variant = {% call py::read_discriminant(e) %}
 
         {%- for variant in e.variants() %}
         if variant == {{ loop.index }}:
-            {%- if e.is_flat() %}
+            {%- if stdlib %}
             return {{ type_name }}.{{variant.name()|enum_variant_py}}
             {%- else %}
             return {{ type_name }}.{{variant.name()|enum_variant_py}}(
@@ -85,7 +118,16 @@ class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
             )
             {%- endif %}
         {%- endfor %}
+        {%- match e.default_variant() %}
+        {%- when Some with (default) %}
+        return {{ type_name }}.{{ default.name()|enum_variant_py }}{% if !stdlib %}(){% endif %}
+        {%- when None %}
+        {%- if e.has_unknown_variant() %}
+        return {{ type_name }}.UNKNOWN(variant)
+        {%- else %}
         raise InternalError("Raw enum value doesn't match any cases")
+        {%- endif %}
+        {%- endmatch %}
 
     @staticmethod
     def check_lower(value):
@@ -93,7 +135,7 @@ class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
         pass
         {%- else %}
         {%- for variant in e.variants() %}
-        {%- if e.is_flat() %}
+        {%- if stdlib %}
         if value == {{ type_name }}.{{ variant.name()|enum_variant_py }}:
         {%- else %}
         if value.is_{{ variant.name()|var_name }}():
@@ -103,20 +145,64 @@ class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
             {%- endfor %}
             return
         {%- endfor %}
+        {%- if e.has_unknown_variant() %}
+        if value.is_unknown():
+            return
+        {%- endif %}
         {%- endif %}
 
     @staticmethod
     def write(value, buf):
         {%- for variant in e.variants() %}
-        {%- if e.is_flat() %}
+        {%- if stdlib %}
         if value == {{ type_name }}.{{ variant.name()|enum_variant_py }}:
-            buf.write_i32({{ loop.index }})
+            {% call py::write_discriminant(e, loop.index) %}
         {%- else %}
         if value.is_{{ variant.name()|var_name }}():
-            buf.write_i32({{ loop.index }})
+            {% call py::write_discriminant(e, loop.index) %}
             {%- for field in variant.fields() %}
             {{ field|write_fn }}(value.{{ field.name()|var_name }}, buf)
             {%- endfor %}
         {%- endif %}
         {%- endfor %}
+        {%- if e.has_unknown_variant() %}
+        if value.is_unknown():
+            {% call py::write_discriminant(e, "value.discriminant") %}
+        {%- endif %}
+
+    {%- if e.is_flat() %}
+
+    # Enums without associated data are passed across the FFI as the `int` that identifies their variant.
+    @staticmethod
+    def lift(value):
+        {%- for variant in e.variants() %}
+        if value == {{ loop.index }}:
+            return {{ type_name }}.{{variant.name()|enum_variant_py}}{% if !stdlib %}(){% endif %}
+        {%- endfor %}
+        {%- match e.default_variant() %}
+        {%- when Some with (default) %}
+        return {{ type_name }}.{{ default.name()|enum_variant_py }}{% if !stdlib %}(){% endif %}
+        {%- when None %}
+        {%- if e.has_unknown_variant() %}
+        return {{ type_name }}.UNKNOWN(value)
+        {%- else %}
+        raise InternalError("Raw enum value doesn't match any cases")
+        {%- endif %}
+        {%- endmatch %}
 
+    @staticmethod
+    def lower(value):
+        {%- for variant in e.variants() %}
+        {%- if stdlib %}
+        if value == {{ type_name }}.{{ variant.name()|enum_variant_py }}:
+        {%- else %}
+        if value.is_{{ variant.name()|var_name }}():
+        {%- endif %}
+            return {{ loop.index }}
+        {%- endfor %}
+        {%- if e.has_unknown_variant() %}
+        if value.is_unknown():
+            return value.discriminant
+        {%- endif %}
+        raise InternalError("Enum value doesn't match any cases")
+    {%- endif %}
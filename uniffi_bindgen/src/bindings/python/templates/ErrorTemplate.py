@@ -5,23 +5,26 @@
 # __dict__.  All of this happens in dummy class to avoid polluting the module
 # namespace.
 class {{ type_name }}(Exception):
-    {%- call py::docstring(e, 4) %}
+    {%- call py::type_docstring(e, 4) %}
     pass
 
 _UniffiTemp{{ type_name }} = {{ type_name }}
 
 class {{ type_name }}:  # type: ignore
+    {%- if e.variants().is_empty() %}
+    pass
+    {%- endif %}
     {%- for variant in e.variants() -%}
     {%- let variant_type_name = variant.name()|class_name -%}
     {%- if e.is_flat() %}
     class {{ variant_type_name }}(_UniffiTemp{{ type_name }}):
-        {%- call py::docstring(variant, 8) %}
+        {%- call py::docstring_with_deprecation(variant, 8) %}
 
         def __repr__(self):
             return "{{ type_name }}.{{ variant_type_name }}({})".format(repr(str(self)))
     {%- else %}
     class {{ variant_type_name }}(_UniffiTemp{{ type_name }}):
-        {%- call py::docstring(variant, 8) %}
+        {%- call py::docstring_with_deprecation(variant, 8) %}
 
         def __init__(self{% for field in variant.fields() %}, {{ field.name()|var_name }}{% endfor %}):
             {%- if variant.has_fields() %}
@@ -80,6 +83,9 @@ class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
 
     @staticmethod
     def write(value, buf):
+        {%- if e.variants().is_empty() %}
+        pass
+        {%- endif %}
         {%- for variant in e.variants() %}
         if isinstance(value, {{ type_name }}.{{ variant.name()|class_name }}):
             buf.write_i32({{ loop.index }})
@@ -1,4 +1,4 @@
-{%- let module = python_config.module_for_namespace(namespace) -%}
+{%- let module = config.module_for_namespace(namespace) -%}
 
 # External type {{name}} is in namespace "{{namespace}}", crate {{module_path}}
 {%- let ffi_converter_name = "_UniffiConverterType{}"|format(name) %}
@@ -3,6 +3,22 @@
 
 class InternalError(Exception):
     pass
+{%- if ci.has_thread_bound_objects() %}
+
+class WrongThreadError(Exception):
+    """
+    Raised when a method of a `[ThreadBound]` object is called on a thread other than the one which
+    constructed it.
+    """
+{%- endif %}
+{%- if ci.has_on_poison_objects() %}
+
+class StatePoisonedError(Exception):
+    """
+    Raised when a method of an `[OnPoison=error]` object panics on state poisoned by an earlier
+    call.
+    """
+{%- endif %}
 
 class _UniffiRustCallStatus(ctypes.Structure):
     """
@@ -17,6 +33,9 @@ class _UniffiRustCallStatus(ctypes.Structure):
     CALL_SUCCESS = 0
     CALL_ERROR = 1
     CALL_PANIC = 2
+    CALL_CANCELLED = 3
+    CALL_WRONG_THREAD = 4
+    CALL_STATE_POISONED = 5
 
     def __str__(self):
         if self.code == _UniffiRustCallStatus.CALL_SUCCESS:
@@ -25,6 +44,12 @@ class _UniffiRustCallStatus(ctypes.Structure):
             return "_UniffiRustCallStatus(CALL_ERROR)"
         elif self.code == _UniffiRustCallStatus.CALL_PANIC:
             return "_UniffiRustCallStatus(CALL_PANIC)"
+        elif self.code == _UniffiRustCallStatus.CALL_CANCELLED:
+            return "_UniffiRustCallStatus(CALL_CANCELLED)"
+        elif self.code == _UniffiRustCallStatus.CALL_WRONG_THREAD:
+            return "_UniffiRustCallStatus(CALL_WRONG_THREAD)"
+        elif self.code == _UniffiRustCallStatus.CALL_STATE_POISONED:
+            return "_UniffiRustCallStatus(CALL_STATE_POISONED)"
         else:
             return "_UniffiRustCallStatus(<invalid code>)"
 
@@ -62,6 +87,19 @@ def _uniffi_check_call_status(error_ffi_converter, call_status):
         else:
             msg = "Unknown rust panic"
         raise InternalError(msg)
+    {%- if ci.has_async_fns() %}
+    elif call_status.code == _UniffiRustCallStatus.CALL_CANCELLED:
+        # Only async calls can be cancelled.
+        raise asyncio.CancelledError()
+    {%- endif %}
+    {%- if ci.has_thread_bound_objects() %}
+    elif call_status.code == _UniffiRustCallStatus.CALL_WRONG_THREAD:
+        raise WrongThreadError(_UniffiConverterString.lift(call_status.error_buf))
+    {%- endif %}
+    {%- if ci.has_on_poison_objects() %}
+    elif call_status.code == _UniffiRustCallStatus.CALL_STATE_POISONED:
+        raise StatePoisonedError(_UniffiConverterString.lift(call_status.error_buf))
+    {%- endif %}
     else:
         raise InternalError("Invalid _UniffiRustCallStatus code: {}".format(
             call_status.code))
@@ -0,0 +1,24 @@
+# The Json type, which is any value that the `json` module can encode.
+Json = typing.Any
+
+# JSON values are passed as their JSON text, and are lifted as `dict`, `list`,
+# `str`, `int`, `float`, `bool` or `None`.
+class _UniffiConverterJson(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        size = buf.read_i32()
+        if size < 0:
+            raise InternalError("Unexpected negative JSON length")
+        return json.loads(buf.read(size).decode("utf-8"))
+
+    @staticmethod
+    def check_lower(value):
+        # Raises `TypeError` for values that can't be encoded, and `ValueError`
+        # for non-finite floats, which aren't valid JSON.
+        json.dumps(value, allow_nan=False)
+
+    @staticmethod
+    def write(value, buf):
+        utf8_bytes = json.dumps(value, allow_nan=False).encode("utf-8")
+        buf.write_i32(len(utf8_bytes))
+        buf.write(utf8_bytes)
@@ -32,7 +32,15 @@ def _uniffi_load_indirect():
         libname = "lib{}.so"
 
     libname = libname.format("{{ config.cdylib_name() }}")
+    {%- if config.split_modules() %}
+    # The library either lives in the package or next to it.
+    package_dir = os.path.dirname(__file__)
+    path = os.path.join(package_dir, libname)
+    if not os.path.exists(path):
+        path = os.path.join(os.path.dirname(package_dir), os.path.basename(libname))
+    {%- else %}
     path = os.path.join(os.path.dirname(__file__), libname)
+    {%- endif %}
     lib = ctypes.cdll.LoadLibrary(path)
     return lib
 
@@ -44,6 +52,20 @@ def _uniffi_check_contract_api_version(lib):
     if bindings_contract_version != scaffolding_contract_version:
         raise InternalError("UniFFI contract version mismatch: try cleaning and rebuilding your project")
 
+def _uniffi_check_component_version(lib):
+    {%- match ci.component_version() %}
+    {%- when Some(bindings_component_version) %}
+    # Get the bindings component version from our ComponentInterface
+    bindings_component_version = "{{ bindings_component_version }}"
+    # Get the scaffolding component version by calling the into the dylib
+    with lib.{{ ci.ffi_uniffi_component_version().name() }}().consume_with_stream() as stream:
+        scaffolding_component_version = stream.read(stream.remaining()).decode("utf-8")
+    if bindings_component_version != scaffolding_component_version:
+        raise InternalError(f"UniFFI component version mismatch: the bindings are version \"{bindings_component_version}\" but the library is version \"{scaffolding_component_version}\"")
+    {%- when None %}
+    pass
+    {%- endmatch %}
+
 def _uniffi_check_api_checksums(lib):
     {%- for (name, expected_checksum) in ci.iter_checksums() %}
     if lib.{{ name }}() != {{ expected_checksum }}:
@@ -52,16 +74,62 @@ def _uniffi_check_api_checksums(lib):
     pass
     {%- endfor %}
 
+def _uniffi_init_lib(lib):
+    {%- for func in ci.iter_ffi_function_definitions() %}
+    lib.{{ func.name() }}.argtypes = (
+        {%- call py::arg_list_ffi_decl(func) -%}
+    )
+    lib.{{ func.name() }}.restype = {% match func.return_type() %}{% when Some with (type_) %}{{ type_|ffi_type_name }}{% when None %}None{% endmatch %}
+    {%- endfor %}
+    {#- Ensure to call the contract verification only after we defined all functions. #}
+    _uniffi_check_contract_api_version(lib)
+    _uniffi_check_component_version(lib)
+    _uniffi_check_api_checksums(lib)
+
 # A ctypes library to expose the extern-C FFI definitions.
 # This is an implementation detail which will be called internally by the public API.
+{%- if config.lazy_load() %}
+
+class _UniffiLazyLib:
+    """
+    Loads the library the first time one of its functions is used, rather than when the module
+    is imported.  Errors loading the library are raised by that first call.
+    """
+
+    def __init__(self):
+        self._uniffi_lib = None
+        self._uniffi_lock = threading.Lock()
+        self._uniffi_on_load_fns = []
+
+    def _uniffi_on_load(self, fn):
+        """
+        Call `fn` with the library once it's loaded.
+        """
+        with self._uniffi_lock:
+            if self._uniffi_lib is None:
+                self._uniffi_on_load_fns.append(fn)
+                return
+        fn(self._uniffi_lib)
+
+    def _uniffi_load(self):
+        with self._uniffi_lock:
+            if self._uniffi_lib is None:
+                lib = _uniffi_load_indirect()
+                _uniffi_init_lib(lib)
+                for fn in self._uniffi_on_load_fns:
+                    fn(lib)
+                self._uniffi_lib = lib
+            return self._uniffi_lib
+
+    def __getattr__(self, name):
+        lib = self._uniffi_lib
+        if lib is None:
+            lib = self._uniffi_load()
+        return getattr(lib, name)
+
+_UniffiLib = _UniffiLazyLib()
+{%- else %}
 
 _UniffiLib = _uniffi_load_indirect()
-{%- for func in ci.iter_ffi_function_definitions() %}
-_UniffiLib.{{ func.name() }}.argtypes = (
-    {%- call py::arg_list_ffi_decl(func) -%}
-)
-_UniffiLib.{{ func.name() }}.restype = {% match func.return_type() %}{% when Some with (type_) %}{{ type_|ffi_type_name }}{% when None %}None{% endmatch %}
-{%- endfor %}
-{# Ensure to call the contract verification only after we defined all functions. -#}
-_uniffi_check_contract_api_version(_UniffiLib)
-_uniffi_check_api_checksums(_UniffiLib)
+_uniffi_init_lib(_UniffiLib)
+{%- endif %}
@@ -0,0 +1,28 @@
+
+# The functions in the `{{ namespace_object }}` namespace are defined at the top level like any other,
+# then moved into a class so that they're only reachable via the namespace.
+class {{ namespace_object|class_name }}:
+    {%- for func in ci.namespace_object_functions(namespace_object) %}
+    {{ func.name()|fn_name }} = staticmethod({{ func.name()|fn_name }})
+    {%- if func.is_async() && config.generate_blocking_variants() %}
+    {{ func.name()|fn_name }}_blocking = staticmethod({{ func.name()|fn_name }}_blocking)
+    {%- endif %}
+    {%- if func.is_cancellable() %}
+    {{ func.name()|fn_name }}_cancellable = staticmethod({{ func.name()|fn_name }}_cancellable)
+    {%- endif %}
+    {%- if func.default_on_none().is_some() %}
+    {{ func.name()|fn_name }}_or_default = staticmethod({{ func.name()|fn_name }}_or_default)
+    {%- endif %}
+    {%- endfor %}
+{% for func in ci.namespace_object_functions(namespace_object) %}
+del {{ func.name()|fn_name }}
+{%- if func.is_async() && config.generate_blocking_variants() %}
+del {{ func.name()|fn_name }}_blocking
+{%- endif %}
+{%- if func.is_cancellable() %}
+del {{ func.name()|fn_name }}_cancellable
+{%- endif %}
+{%- if func.default_on_none().is_some() %}
+del {{ func.name()|fn_name }}_or_default
+{%- endif %}
+{%- endfor %}
@@ -0,0 +1,20 @@
+{%- let inner_ffi_converter = inner_type|ffi_converter_name %}
+
+class {{ ffi_converter_name }}({{ inner_ffi_converter }}):
+    CLASS_NAME = "{{ canonical_type_name }}"
+
+    @classmethod
+    def check_lower(cls, value):
+        super().check_lower(value)
+        if value == 0:
+            raise ValueError("{} requires a non-zero value".format(cls.CLASS_NAME))
+
+    @classmethod
+    def lift(cls, value):
+        if value == 0:
+            raise InternalError("Unexpected zero value for {}".format(cls.CLASS_NAME))
+        return value
+
+    @classmethod
+    def read(cls, buf):
+        return cls.lift({{ inner_ffi_converter }}.read(buf))
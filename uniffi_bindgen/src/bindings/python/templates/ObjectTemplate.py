@@ -2,31 +2,72 @@
 {%- let (protocol_name, impl_name) = obj|object_names %}
 {%- let methods = obj.methods() %}
 {%- let protocol_docstring = obj.docstring() %}
+{%- let protocol_parent = obj|parent_protocol_name %}
+{%- let pointer_attr = obj|pointer_attr %}
+{%- let clone_pointer_fn = obj|clone_pointer_fn %}
+{%- let clone_pointer_call = format!("self.{}()", clone_pointer_fn) %}
 
 {% include "Protocol.py" %}
 
-class {{ impl_name }}:
-    {%- call py::docstring(obj, 4) %}
+class {{ impl_name }}{% match obj.parent() %}{% when Some(parent) %}({{ parent|class_name }}){% when None %}{% endmatch %}:
+    {%- call py::object_docstring(obj, 4) %}
 
-    _pointer: ctypes.c_void_p
+    {{ pointer_attr }}: ctypes.c_void_p
+
+{%- for constant in obj.constants() %}
+    {{ constant.name()|const_name }} = {{ constant.value()|literal_py(constant, config, ci) }}
+    {%- call py::docstring(constant, 4) %}
+{%- endfor %}
 
 {%- match obj.primary_constructor() %}
 {%-     when Some with (cons) %}
+{%-         if cons.is_async() %}
+    def __init__(self, *args, **kwargs):
+        raise ValueError("async constructors not supported, use `{{ impl_name }}.{{ cons.name()|fn_name }}()` instead.")
+{%-         else %}
     def __init__(self, {% call py::arg_list_decl(cons) -%}):
-        {%- call py::docstring(cons, 8) %}
+        {%- call py::constructor_docstring(cons, 8) %}
         {%- call py::setup_args_extra_indent(cons) %}
+{%-             if obj.parent().is_some() %}
+        self._uniffi_init_pointers({% call py::to_ffi_call(cons) %})
+{%-             else %}
         self._pointer = {% call py::to_ffi_call(cons) %}
+{%-             endif %}
+{%-         endif %}
 {%-     when None %}
+{%-         if obj.parent().is_some() %}
+    def __init__(self, *args, **kwargs):
+        raise ValueError("`{{ impl_name }}` has no primary constructor.")
+{%-         endif %}
 {%- endmatch %}
 
     def __del__(self):
         # In case of partial initialization of instances.
-        pointer = getattr(self, "_pointer", None)
+        pointer = getattr(self, "{{ pointer_attr }}", None)
         if pointer is not None:
             _rust_call(_UniffiLib.{{ obj.ffi_object_free().name() }}, pointer)
+        {%- if obj.parent().is_some() %}
+        super().__del__()
+        {%- endif %}
+
+    def {{ clone_pointer_fn }}(self):
+        {%- if obj.has_consuming_methods() %}
+        self._uniffi_check_not_consumed()
+        {%- endif %}
+        return _rust_call(_UniffiLib.{{ obj.ffi_object_clone().name() }}, self.{{ pointer_attr }})
+{%- if obj.has_consuming_methods() %}
+
+    def _uniffi_consume_pointer(self):
+        # Methods which consume the object take over its pointer, so it can't be used afterwards.
+        self._uniffi_check_not_consumed()
+        pointer = self.{{ pointer_attr }}
+        self.{{ pointer_attr }} = None
+        return pointer
 
-    def _uniffi_clone_pointer(self):
-        return _rust_call(_UniffiLib.{{ obj.ffi_object_clone().name() }}, self._pointer)
+    def _uniffi_check_not_consumed(self):
+        if self.{{ pointer_attr }} is None:
+            raise ValueError("`{{ impl_name }}` object has already been consumed")
+{%- endif %}
 
     # Used by alternative constructors or any methods which return this type.
     @classmethod
@@ -34,30 +75,93 @@ class {{ impl_name }}:
         # Lightly yucky way to bypass the usual __init__ logic
         # and just create a new instance with the required pointer.
         inst = cls.__new__(cls)
+        {%- if obj.parent().is_some() %}
+        inst._uniffi_init_pointers(pointer)
+        {%- else %}
         inst._pointer = pointer
+        {%- endif %}
         return inst
 
-{%- for cons in obj.alternate_constructors() %}
+{%- match obj.parent() %}
+{%- when Some(parent) %}
+{%- let parent_obj = ci|get_object_definition(parent) %}
+
+    def _uniffi_init_pointers(self, pointer):
+        # The parent class holds a pointer to the `{{ parent|class_name }}` this object was
+        # converted to, which its methods are called with.
+        self.{{ pointer_attr }} = pointer
+        parent_pointer = _rust_call(_UniffiLib.{{ obj.ffi_object_upcast().name() }}, pointer)
+        {%- if parent_obj.parent().is_some() %}
+        super()._uniffi_init_pointers(parent_pointer)
+        {%- else %}
+        self._pointer = parent_pointer
+        {%- endif %}
+{%- when None %}
+{%- endmatch %}
+
+{%- if obj.is_singleton() %}
+
+    _instance = None
+
+    @classmethod
+    def instance(cls):
+        """
+        The single instance of `{{ impl_name }}`, which is shared by all callers.
+        """
+        if cls._instance is None:
+            cls._instance = cls._make_instance_(_rust_call(_UniffiLib.{{ obj.ffi_object_singleton().name() }}))
+        return cls._instance
+{%- endif %}
+
+{%- for cons in obj.constructors() %}
+{%-     if cons.is_async() %}
+
+    @classmethod
+    async def {{ cons.name()|fn_name }}(cls, {% call py::arg_list_decl(cons) %}):
+        {%- call py::constructor_docstring(cons, 8) %}
+        {%- call py::setup_args_extra_indent(cons) %}
+        return await _uniffi_rust_call_async(
+            _UniffiLib.{{ cons.ffi_func().name() }}({% call py::arg_list_lowered(cons) %}),
+            _UniffiLib.{{ cons.ffi_rust_future_poll(ci) }},
+            _UniffiLib.{{ cons.ffi_rust_future_complete(ci) }},
+            _UniffiLib.{{ cons.ffi_rust_future_free(ci) }},
+            _UniffiLib.{{ cons.ffi_rust_future_cancel(ci) }},
+            # lift function
+            cls._make_instance_,
+            # Error FFI converter
+            {%- match cons.throws_type() %}
+            {%- when Some(e) %}
+            {{ e|ffi_converter_name }},
+            {%- when None %}
+            None,
+            {%- endmatch %}
+        )
+{%-     else if !cons.is_primary_constructor() %}
 
     @classmethod
     def {{ cons.name()|fn_name }}(cls, {% call py::arg_list_decl(cons) %}):
-        {%- call py::docstring(cons, 8) %}
+        {%- call py::constructor_docstring(cons, 8) %}
         {%- call py::setup_args_extra_indent(cons) %}
         # Call the (fallible) function before creating any half-baked object instances.
         pointer = {% call py::to_ffi_call(cons) %}
         return cls._make_instance_(pointer)
+{%-     endif %}
 {% endfor %}
 
 {%- for meth in obj.methods() -%}
-    {%- call py::method_decl(meth.name()|fn_name, meth) %}
+{%-     if meth.takes_self_by_value() %}
+    {%- call py::method_decl(meth.name()|fn_name, meth, "self._uniffi_consume_pointer()") %}
+{%-     else %}
+    {%- call py::method_decl(meth.name()|fn_name, meth, clone_pointer_call) %}
+{%-     endif %}
 {% endfor %}
 
 {%- for tm in obj.uniffi_traits() -%}
 {%-     match tm %}
 {%-         when UniffiTrait::Debug { fmt } %}
-            {%- call py::method_decl("__repr__", fmt) %}
+            {%- call py::method_decl("__repr__", fmt, clone_pointer_call) %}
 {%-         when UniffiTrait::Display { fmt } %}
-            {%- call py::method_decl("__str__", fmt) %}
+            {%- call py::method_decl("__str__", fmt, clone_pointer_call) %}
 {%-         when UniffiTrait::Eq { eq, ne } %}
     def __eq__(self, other: object) -> {{ eq.return_type().unwrap()|type_name }}:
         if not isinstance(other, {{ type_name }}):
@@ -71,10 +175,96 @@ class {{ impl_name }}:
 
         return {{ ne.return_type().unwrap()|lift_fn }}({% call py::to_ffi_call_with_prefix("self._uniffi_clone_pointer()", ne) %})
 {%-         when UniffiTrait::Hash { hash } %}
-            {%- call py::method_decl("__hash__", hash) %}
+            {%- call py::method_decl("__hash__", hash, clone_pointer_call) %}
 {%      endmatch %}
 {% endfor %}
 
+{%- if obj.has_identity_equality() %}
+    def _uniffi_handle_id(self) -> int:
+        # The id of the underlying Rust object, which is the same for every handle to it.
+        {%- if obj.has_consuming_methods() %}
+        self._uniffi_check_not_consumed()
+        {%- endif %}
+        return _rust_call(_UniffiLib.{{ obj.ffi_object_handle_id().name() }}, self.{{ pointer_attr }})
+
+    def __eq__(self, other: object) -> bool:
+        if not isinstance(other, {{ impl_name }}):
+            return NotImplemented
+
+        return self._uniffi_handle_id() == other._uniffi_handle_id()
+{%- endif %}
+{%- if obj.has_identity_hash() %}
+
+    def __hash__(self) -> int:
+        return hash(self._uniffi_handle_id())
+{%- endif %}
+
+{%- match ci.observable(obj) %}
+{%- when Some with (observable) %}
+{{- self.add_import("queue") }}
+{%- let event_name = observable.event_argument().name()|var_name %}
+{%- let add_name = observable.add_method().name()|fn_name %}
+{%- let remove_name = observable.remove_method().name()|fn_name %}
+
+    def observe(self):
+        """
+        Yields the events passed to an observer's `{{ observable.event_method().name()|fn_name }}` method.
+
+        The observer is added with `{{ add_name }}` when this is called, and removed with
+        `{{ remove_name }}` when the generator is closed.
+        """
+        events = queue.Queue()
+
+        class _Observer({{ observable.observer_type()|type_name }}):
+            def {{ observable.event_method().name()|fn_name }}(self, {{ event_name }}):
+                events.put({{ event_name }})
+
+        observer = _Observer()
+        {%- if observable.has_token() %}
+        token = self.{{ add_name }}(observer)
+        {%- else %}
+        self.{{ add_name }}(observer)
+        token = observer
+        {%- endif %}
+
+        def _events():
+            try:
+                # Paused here until the first event is requested, so closing the generator
+                # always removes the observer.
+                yield
+                while True:
+                    yield events.get()
+            finally:
+                self.{{ remove_name }}(token)
+
+        generator = _events()
+        next(generator)
+        return generator
+{%- else %}
+{%- endmatch %}
+
+{%- match obj.cursor() %}
+{%- when Some with (cursor) %}
+{%- let next_batch_name = cursor.next_batch_method().name()|fn_name %}
+
+    def iter_items(self, batch_size=100):
+        """
+        Yields the items of the cursor, fetched with `{{ next_batch_name }}` in batches of `batch_size`
+        as they're needed.  A batch with fewer items, including an empty one, ends the iteration.
+        """
+        if batch_size <= 0:
+            raise ValueError("batch_size must be positive")
+        while True:
+            batch = self.{{ next_batch_name }}(batch_size)
+            yield from batch
+            if len(batch) < batch_size:
+                return
+
+    def __iter__(self):
+        return self.iter_items()
+{%- else %}
+{%- endmatch %}
+
 {%- if obj.is_trait_interface() %}
 {%- let callback_handler_class = format!("UniffiCallbackInterface{}", name) %}
 {%- let callback_handler_obj = format!("uniffiCallbackInterface{}", name) %}
@@ -106,7 +296,7 @@ class {{ ffi_converter_name }}:
         {%- when ObjectImpl::Struct %}
         if not isinstance(value, {{ impl_name }}):
             raise TypeError("Expected {{ impl_name }} instance, {} found".format(type(value).__name__))
-        return value._uniffi_clone_pointer()
+        return value.{{ clone_pointer_fn }}()
         {%- when ObjectImpl::Trait %}
         return {{ ffi_converter_name }}._handle_map.insert(value)
         {%- endmatch %}
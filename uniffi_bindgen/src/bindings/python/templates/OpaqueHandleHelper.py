@@ -0,0 +1,41 @@
+# A value from Rust, usually a pointer, which is passed back unchanged.
+# Nothing keeps what it points to alive: that's up to the Rust code which handed it out.
+class OpaqueHandle:
+    def __init__(self, value):
+        self.value = value
+
+    def __int__(self):
+        return self.value
+
+    def __eq__(self, other):
+        if not isinstance(other, OpaqueHandle):
+            return NotImplemented
+        return self.value == other.value
+
+    def __hash__(self):
+        return hash(self.value)
+
+    def __repr__(self):
+        return "OpaqueHandle({:#x})".format(self.value)
+
+class _UniffiConverterOpaqueHandle(_UniffiConverterPrimitive):
+    @staticmethod
+    def check_lower(value):
+        if not isinstance(value, OpaqueHandle):
+            raise TypeError("expected OpaqueHandle, not {}".format(type(value).__name__))
+
+    @staticmethod
+    def lift(value):
+        return OpaqueHandle(value)
+
+    @staticmethod
+    def lower(value):
+        return value.value
+
+    @staticmethod
+    def read(buf):
+        return OpaqueHandle(buf.read_u64())
+
+    @staticmethod
+    def write(value, buf):
+        buf.write_u64(value.value)
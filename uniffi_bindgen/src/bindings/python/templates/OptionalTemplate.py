@@ -1,4 +1,5 @@
 {%- let inner_ffi_converter = inner_type|ffi_converter_name %}
+{%- let empty_string_as_none = self.empty_string_as_none(inner_type) %}
 
 class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
     @classmethod
@@ -8,7 +9,7 @@ class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
 
     @classmethod
     def write(cls, value, buf):
-        if value is None:
+        if value is None{% if empty_string_as_none %} or value == ""{% endif %}:
             buf.write_u8(0)
             return
 
@@ -21,6 +22,6 @@ class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
         if flag == 0:
             return None
         elif flag == 1:
-            return {{ inner_ffi_converter }}.read(buf)
+            return {{ inner_ffi_converter }}.read(buf){% if empty_string_as_none %} or None{% endif %}
         else:
             raise InternalError("Unexpected flag byte for optional type")
@@ -0,0 +1,7 @@
+# Lift the value returned by a function with out-parameters, which is followed by their final
+# values in the same buffer.  A single output is returned as it is, otherwise they're returned as a
+# tuple.
+def _uniffi_lift_out_params(rbuf, *ffi_converters):
+    with rbuf.consume_with_stream() as stream:
+        outputs = tuple(ffi_converter.read(stream) for ffi_converter in ffi_converters)
+    return outputs[0] if len(outputs) == 1 else outputs
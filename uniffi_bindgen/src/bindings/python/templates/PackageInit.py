@@ -0,0 +1,28 @@
+{%- match config.file_header() %}{% when Some with (header) %}{{ header }}
+{% when None %}{% endmatch -%}
+{% if config.hash_header() %}# {{ ci.hash_header() }}
+{% endif -%}
+{%- call py::docstring_value(ci.namespace_docstring(), 0) %}
+
+# This file was autogenerated by some hot garbage in the `uniffi` crate.
+# Trust me, you don't want to mess with it!
+{% for submodule in self.submodules() %}
+from .{{ submodule }} import *
+{%- endfor %}
+{%- for name in self.names() %}
+from ._uniffi import {{ name }}
+{%- endfor %}
+{% for submodule in self.submodules() %}
+from . import {{ submodule }}
+{%- endfor %}
+
+__all__ = [
+    {%- for name in self.names() %}
+    "{{ name }}",
+    {%- endfor %}
+]
+{%- for submodule in self.submodules() %}
+__all__ += {{ submodule }}.__all__
+{%- endfor %}
+
+{% import "macros.py" as py %}
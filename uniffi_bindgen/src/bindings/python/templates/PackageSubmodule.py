@@ -0,0 +1,18 @@
+{%- match config.file_header() %}{% when Some with (header) %}{{ header }}
+{% when None %}{% endmatch -%}
+{% if config.hash_header() %}# {{ ci.hash_header() }}
+{% endif -%}
+# This file was autogenerated by some hot garbage in the `uniffi` crate.
+# Trust me, you don't want to mess with it!
+
+# Re-exports the {{ submodule }} of the `{{ ci.namespace() }}` package.  They're all defined in
+# `_uniffi.py`, so that the FFI converters can refer to each other.
+{%- for name in self.names() %}
+from ._uniffi import {{ name }}
+{%- endfor %}
+
+__all__ = [
+    {%- for name in self.names() %}
+    "{{ name }}",
+    {%- endfor %}
+]
@@ -0,0 +1,23 @@
+# The Path type.
+Path = pathlib.Path
+
+# Paths are lowered from anything `os.fspath()` accepts, which includes `str`
+# and `pathlib.Path`, and are always lifted as `pathlib.Path`.
+class _UniffiConverterPath(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        size = buf.read_i32()
+        if size < 0:
+            raise InternalError("Unexpected negative path length")
+        return pathlib.Path(buf.read(size).decode("utf-8"))
+
+    @staticmethod
+    def check_lower(value):
+        if not isinstance(os.fspath(value), str):
+            raise TypeError("argument must be a str path, not {}".format(type(value).__name__))
+
+    @staticmethod
+    def write(value, buf):
+        utf8_bytes = os.fspath(value).encode("utf-8")
+        buf.write_i32(len(utf8_bytes))
+        buf.write(utf8_bytes)
@@ -1,9 +1,21 @@
-class {{ protocol_name }}(typing.Protocol):
+class {{ protocol_name }}({% if !protocol_parent.is_empty() %}{{ protocol_parent }}, {% endif %}typing.Protocol):
     {%- call py::docstring_value(protocol_docstring, 4) %}
     {%- for meth in methods.iter() %}
     def {{ meth.name()|fn_name }}(self, {% call py::arg_list_decl(meth) %}):
-        {%- call py::docstring(meth, 8) %}
+        {%- call py::callable_docstring(meth, 8) %}
+        {%- if meth.has_default() %}
+        {%- call py::setup_args_extra_indent(meth) %}
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}
+        return {{ return_type|lift_fn }}(
+            {% call py::to_ffi_default_call(meth) %}
+        )
+        {%- when None %}
+        {% call py::to_ffi_default_call(meth) %}
+        {%- endmatch %}
+        {%- else %}
         raise NotImplementedError
+        {%- endif %}
     {%- else %}
     pass
     {%- endfor %}
@@ -1,6 +1,6 @@
 {%- let rec = ci|get_record_definition(name) %}
 class {{ type_name }}:
-    {%- call py::docstring(rec, 4) %}
+    {%- call py::docstring_value(rec|record_docstring, 4) %}
     {%- for field in rec.fields() %}
     {{ field.name()|var_name }}: "{{ field|type_name }}"
     {%- call py::docstring(field, 4) %}
@@ -20,7 +20,7 @@ class {{ type_name }}:
         self.{{ field_name }} = {{ field_name }}
         {%- when Some with(literal) %}
         if {{ field_name }} is _DEFAULT:
-            self.{{ field_name }} = {{ literal|literal_py(field) }}
+            self.{{ field_name }} = {{ literal|literal_py(field, config, ci) }}
         else:
             self.{{ field_name }} = {{ field_name }}
         {%- endmatch %}
@@ -29,22 +29,63 @@ class {{ type_name }}:
 
     def __str__(self):
         return "{{ type_name }}({% for field in rec.fields() %}{{ field.name()|var_name }}={}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in rec.fields() %}self.{{ field.name()|var_name }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
+    {%- if config.derive_display() %}
+
+    def __repr__(self):
+        return "{{ type_name }}({% for field in rec.fields() %}{{ field.name()|var_name }}={}{% if !loop.last %}, {% endif %}{% endfor %})".format({% for field in rec.fields() %}{{ field|display_field }}{% if !loop.last %}, {% endif %}{% endfor %})
+    {%- endif %}
+
+    # `__eq__` and `__hash__` are both derived from this tuple, so that equal records always have
+    # equal hashes.  Like tuples, records are only hashable if all of their fields are.
+    def _uniffi_fields(self):
+        return ({% for field in rec.fields() %}self.{{ field.name()|var_name }}, {% endfor %})
 
     def __eq__(self, other):
-        {%- for field in rec.fields() %}
-        if self.{{ field.name()|var_name }} != other.{{ field.name()|var_name }}:
-            return False
-        {%- endfor %}
-        return True
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_fields() == other._uniffi_fields()
+
+    def __hash__(self):
+        return hash(self._uniffi_fields())
+    {%- if rec.has_default() %}
+
+    @classmethod
+    def default(cls):
+        """
+        The Rust `Default::default()` value of `{{ type_name }}`.
+        """
+        return {{ ffi_converter_name }}.lift(_rust_call(_UniffiLib.{{ rec.ffi_func_default().name() }}))
+    {%- endif %}
 
 class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
     @staticmethod
     def read(buf):
+        {%- if rec.is_packed() %}
+        # Adjacent boolean fields are packed into bytes.
+        {%- for item in rec.wire_items() %}
+        {%- match item %}
+        {%- when WireItem::Field with (field) %}
+        {{ "uniffi_{}"|format(field.name())|var_name }} = {{ field|read_fn }}(buf)
+        {%- when WireItem::PackedBools with (bools) %}
+        packed{{ loop.index0 }} = buf.read_u8()
+        {%- let packed = "packed{}"|format(loop.index0) %}
+        {%- for (field, mask) in bools %}
+        {{ "uniffi_{}"|format(field.name())|var_name }} = bool({{ packed }} & {{ mask }})
+        {%- endfor %}
+        {%- endmatch %}
+        {%- endfor %}
         return {{ type_name }}(
             {%- for field in rec.fields() %}
+            {{ field.name()|var_name }}={{ "uniffi_{}"|format(field.name())|var_name }},
+            {%- endfor %}
+        )
+        {%- else %}
+        return {{ type_name }}(
+            {%- for field in rec.wire_fields() %}
             {{ field.name()|var_name }}={{ field|read_fn }}(buf),
             {%- endfor %}
         )
+        {%- endif %}
 
     @staticmethod
     def check_lower(value):
@@ -59,8 +100,18 @@ class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
     @staticmethod
     def write(value, buf):
         {%- if rec.has_fields() %}
-        {%- for field in rec.fields() %}
+        {%- for item in rec.wire_items() %}
+        {%- match item %}
+        {%- when WireItem::Field with (field) %}
         {{ field|write_fn }}(value.{{ field.name()|var_name }}, buf)
+        {%- when WireItem::PackedBools with (bools) %}
+        packed = 0
+        {%- for (field, mask) in bools %}
+        if value.{{ field.name()|var_name }}:
+            packed |= {{ mask }}
+        {%- endfor %}
+        buf.write_u8(packed)
+        {%- endmatch %}
         {%- endfor %}
         {%- else %}
         pass
@@ -0,0 +1,33 @@
+
+class UniffiRuntimeConfig:
+    """
+    Runtime settings, which `uniffi_initialize()` applies together.
+
+    Each method sets one setting and returns this config, so they can be chained.  Settings which
+    aren't set are left unchanged.
+    """
+
+    def __init__(self):
+        {%- for arg in func.arguments() %}
+        self._{{ arg.name()|var_name }} = None
+        {%- endfor %}
+    {%- for arg in func.arguments() %}
+    {%- match arg.as_type() %}
+    {%- when Type::Optional { inner_type } %}
+
+    def {{ arg.name()|fn_name }}(self, value: "{{ inner_type|type_name }}") -> "UniffiRuntimeConfig":
+        self._{{ arg.name()|var_name }} = value
+        return self
+    {%- else %}
+    {%- endmatch %}
+    {%- endfor %}
+
+def {{ func.name()|fn_name }}(config: UniffiRuntimeConfig) -> None:
+    """
+    Apply the settings in `config` together, with a single call into Rust.
+    """
+    {%- for arg in func.arguments() %}
+    {{ arg.name()|var_name }} = config._{{ arg.name()|var_name }}
+    {%- endfor %}
+    {%- call py::setup_args(func) %}
+    {% call py::to_ffi_call(func) %}
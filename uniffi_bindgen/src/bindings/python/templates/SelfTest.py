@@ -0,0 +1,43 @@
+def uniffi_self_test() -> UniffiSelfTestReport:
+    """
+    Pass a value of each primitive type to Rust and back, to check that the bindings and the
+    library agree on how they're passed over the FFI.
+
+    Call this at startup to diagnose mismatched or miscompiled builds.  Every value is checked,
+    and the report has a message for each one which changed.
+    """
+
+    # These must match `uniffi_self_test_values()` in the scaffolding.
+    expected = UniffiSelfTestValues(
+        boolean=True,
+        int8=-2**7,
+        uint8=2**8 - 1,
+        int16=-2**15,
+        uint16=2**16 - 1,
+        int32=-2**31,
+        uint32=2**32 - 1,
+        int64=-2**63,
+        uint64=2**64 - 1,
+        float32=-0.15625,
+        float64=0.1,
+        # Characters encoded as 1, 2, 3 and 4 bytes of UTF-8.
+        string="a\u00df\u20ac\U0001f980",
+        bytes=b"\x00\x01\x7f\x80\xff",
+    )
+    failures = []
+    try:
+        failures.extend(uniffi_self_test_check(expected).failures)
+    except Exception as e:
+        failures.append(f"passing the values to Rust failed: {e!r}")
+    try:
+        actual = uniffi_self_test_values()
+        # Python floats are doubles, and `float32` is exactly representable as one, so comparing
+        # the values also checks its encoding.
+        for name in ("boolean", "int8", "uint8", "int16", "uint16", "int32", "uint32", "int64",
+                     "uint64", "float32", "float64", "string", "bytes"):
+            value, expected_value = getattr(actual, name), getattr(expected, name)
+            if type(value) != type(expected_value) or value != expected_value:
+                failures.append(f"{name}: Python received {value!r}, expected {expected_value!r}")
+    except Exception as e:
+        failures.append(f"getting the values from Rust failed: {e!r}")
+    return UniffiSelfTestReport(passed=not failures, failures=failures)
@@ -1,9 +1,42 @@
 {%- if func.is_async() %}
 
-def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
-    {%- call py::docstring(func, 4) %}
+async def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
+    {%- call py::callable_docstring(func, 4) %}
+    {%- call py::deprecation_warning(func, 4) %}
+    {%- call py::setup_args(func) %}
+    return await _uniffi_rust_call_async(
+        _UniffiLib.{{ func.ffi_func().name() }}({% call py::arg_list_lowered(func) %}),
+        _UniffiLib.{{func.ffi_rust_future_poll(ci) }},
+        _UniffiLib.{{func.ffi_rust_future_complete(ci) }},
+        _UniffiLib.{{func.ffi_rust_future_free(ci) }},
+        _UniffiLib.{{func.ffi_rust_future_cancel(ci) }},
+        # lift function
+        {%- match func.return_type() %}
+        {%- when Some(return_type) %}
+        {{ return_type|lift_fn }},
+        {%- when None %}
+        lambda val: None,
+        {% endmatch %}
+        # Error FFI converter
+        {%- match func.throws_type() %}
+        {%- when Some(e) %}
+        {{ e|ffi_converter_name }},
+        {%- when None %}
+        None,
+        {%- endmatch %}
+    )
+{%- if config.generate_blocking_variants() %}
+
+def {{ func.name()|fn_name }}_blocking({%- call py::arg_list_decl(func) -%}):
+    """
+    Blocking variant of `{{ func.name()|fn_name }}`.
+
+    This blocks the calling thread until the Rust future completes, so it must not be called from
+    a coroutine or from a thread which runs an event loop.
+    """
+    {%- call py::deprecation_warning(func, 4) %}
     {%- call py::setup_args(func) %}
-    return _uniffi_rust_call_async(
+    return _uniffi_rust_call_blocking(
         _UniffiLib.{{ func.ffi_func().name() }}({% call py::arg_list_lowered(func) %}),
         _UniffiLib.{{func.ffi_rust_future_poll(ci) }},
         _UniffiLib.{{func.ffi_rust_future_complete(ci) }},
@@ -23,20 +56,124 @@ def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
         None,
         {%- endmatch %}
     )
+{%- endif %}
+{%- if func.is_cancellable() %}
+
+def {{ func.name()|fn_name }}_cancellable({%- call py::arg_list_decl(func) -%}) -> UniffiCancellableHandle:
+    """
+    Cancellable variant of `{{ func.name()|fn_name }}`.
 
+    Await the returned handle for the result, or call its `cancel()` method to cancel the call.
+    """
+    {%- call py::deprecation_warning(func, 4) %}
+    {%- call py::setup_args(func) %}
+    return UniffiCancellableHandle(
+        _UniffiLib.{{ func.ffi_func().name() }}({% call py::arg_list_lowered(func) %}),
+        _UniffiLib.{{func.ffi_rust_future_poll(ci) }},
+        _UniffiLib.{{func.ffi_rust_future_complete(ci) }},
+        _UniffiLib.{{func.ffi_rust_future_free(ci) }},
+        _UniffiLib.{{func.ffi_rust_future_cancel(ci) }},
+        # lift function
+        {%- match func.return_type() %}
+        {%- when Some(return_type) %}
+        {{ return_type|lift_fn }},
+        {%- when None %}
+        lambda val: None,
+        {% endmatch %}
+        # Error FFI converter
+        {%- match func.throws_type() %}
+        {%- when Some(e) %}
+        {{ e|ffi_converter_name }},
+        {%- when None %}
+        None,
+        {%- endmatch %}
+    )
+{%- endif %}
+{%- match ci.progress(func) %}
+{%- when Some with (progress) %}
+
+async def {{ func.name()|fn_name }}_with_progress(
+    {%- for arg in func.arguments() -%}
+    {%- if arg.is_progress() %}
+    {{- arg.name()|var_name }}: "typing.Callable[[{{ progress.event_type()|type_name }}], None]"
+    {%- else %}
+    {{- arg.name()|var_name }}
+    {%- match arg.default_value() %}
+    {%- when Some with(literal) %}: "typing.Union[object, {{ arg|type_name -}}]" = _DEFAULT
+    {%- else %}: "{{ arg|type_name -}}"
+    {%- endmatch %}
+    {%- endif %}
+    {%- if !loop.last %},{% endif -%}
+    {%- endfor -%}
+):
+    """
+    Variant of `{{ func.name()|fn_name }}` which passes each progress event to the `{{ progress.argument().name()|var_name }}` function.
+
+    The events are passed in order, on the event loop which awaits the call, and all of them have
+    been passed when this returns.
+    """
+    {#- Functions in a namespace object are only reachable through it once it's defined. #}
+    async def call(send):
+        class _UniffiProgress({{ progress.listener_type()|type_name }}):
+            def {{ progress.event_method().name()|fn_name }}(self, {{ progress.event_argument().name()|var_name }}):
+                send({{ progress.event_argument().name()|var_name }})
+
+        return await {% match func.namespace_object() %}{% when Some with (namespace_object) %}{{ namespace_object|class_name }}.{% when None %}{% endmatch %}{{ func.name()|fn_name }}({% for arg in func.arguments() %}{% if arg.is_progress() %}_UniffiProgress(){% else %}{{ arg.name()|var_name }}{% endif %}{% if !loop.last %}, {% endif %}{% endfor %})
+
+    return await _uniffi_call_with_progress({{ progress.argument().name()|var_name }}, call)
+{%- when None %}
+{%- endmatch %}
+
+{%- else if func.has_out_args() %}
+{%- let outputs = func.out_params_outputs() %}
+
+def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}) -> "{% if outputs.len() > 1 %}typing.Tuple[{% for (name, type_) in outputs.iter() %}{{ type_|type_name }}{% if !loop.last %}, {% endif %}{% endfor %}]{% else %}{% for (name, type_) in outputs.iter() %}{{ type_|type_name }}{% endfor %}{% endif %}":
+    {%- call py::callable_docstring(func, 4) %}
+    {%- call py::deprecation_warning(func, 4) %}
+    {%- call py::setup_args(func) %}
+    return _uniffi_lift_out_params(
+        {% call py::to_ffi_call(func) %},
+        {%- for (name, type_) in outputs.iter() %}
+        {{ type_|ffi_converter_name }},
+        {%- endfor %}
+    )
 {%- else %}
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
 
-def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}) -> "{{ return_type|type_name }}":
-    {%- call py::docstring(func, 4) %}
+def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}) -> "{% if func.is_with_warnings() %}WithWarnings{% else %}{{ return_type|type_name }}{% endif %}":
+    {%- call py::callable_docstring(func, 4) %}
+    {%- call py::deprecation_warning(func, 4) %}
     {%- call py::setup_args(func) %}
+    {%- if func.is_chunked() %}
+    return _uniffi_lift_chunked_sequence({% call py::to_ffi_call(func) %}, {{ return_type|ffi_converter_name }})
+    {%- else if func.is_with_warnings() %}
+    return _uniffi_lift_with_warnings({% call py::to_ffi_call(func) %}, {{ return_type|ffi_converter_name }})
+    {%- else if func.is_segmented() %}
+    return _uniffi_lift_byte_segments({% call py::to_ffi_call(func) %})
+    {%- else %}
     return {{ return_type|lift_fn }}({% call py::to_ffi_call(func) %})
+    {%- endif %}
 {% when None %}
 
 def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
-    {%- call py::docstring(func, 4) %}
+    {%- call py::callable_docstring(func, 4) %}
+    {%- call py::deprecation_warning(func, 4) %}
     {%- call py::setup_args(func) %}
     {% call py::to_ffi_call(func) %}
 {% endmatch %}
 {%- endif %}
+{%- match func.default_on_none() %}
+{%- when Some with ((default, return_type)) %}
+
+def {{ func.name()|fn_name }}_or_default({%- call py::arg_list_decl(func) -%}) -> "{{ return_type|type_name }}":
+    """
+    Variant of `{{ func.name()|fn_name }}` which returns `{{ default|literal_py(return_type, config, ci) }}` rather than `None`.
+    """
+    {#- Functions in a namespace object are only reachable through it once it's defined. #}
+    result = {% match func.namespace_object() %}{% when Some with (namespace_object) %}{{ namespace_object|class_name }}.{% when None %}{% endmatch %}{{ func.name()|fn_name }}({% for arg in func.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %})
+    if result is None:
+        return {{ default|literal_py(return_type, config, ci) }}
+    return result
+{%- when None %}
+{%- endmatch %}
@@ -1,6 +1,6 @@
 {%- import "macros.py" as py %}
 
-{%- for type_ in ci.iter_types() %}
+{%- for type_ in ci.iter_types_in_inheritance_order() %}
 {%- let type_name = type_|type_name %}
 {%- let ffi_converter_name = type_|ffi_converter_name %}
 {%- let canonical_type_name = type_|canonical_name %}
@@ -76,9 +76,27 @@
 {%- when Type::Duration %}
 {%- include "DurationHelper.py" %}
 
+{%- when Type::DurationRange { inclusive } %}
+{%- include "DurationRangeHelper.py" %}
+
+{%- when Type::Path %}
+{%- include "PathHelper.py" %}
+
+{%- when Type::Json %}
+{%- include "JsonHelper.py" %}
+
+{%- when Type::DynError %}
+{%- include "DynErrorHelper.py" %}
+
+{%- when Type::OpaqueHandle %}
+{%- include "OpaqueHandleHelper.py" %}
+
 {%- when Type::Optional { inner_type } %}
 {%- include "OptionalTemplate.py" %}
 
+{%- when Type::NonZero { inner_type } %}
+{%- include "NonZeroTemplate.py" %}
+
 {%- when Type::Sequence { inner_type } %}
 {%- include "SequenceTemplate.py" %}
 
@@ -0,0 +1,24 @@
+class WithWarnings:
+    """
+    A value returned along with a list of non-fatal warnings.
+    """
+
+    def __init__(self, value, warnings):
+        self.value = value
+        self.warnings = warnings
+
+    def __repr__(self):
+        return "WithWarnings({!r}, warnings={!r})".format(self.value, self.warnings)
+
+    def __eq__(self, other):
+        if not isinstance(other, WithWarnings):
+            return NotImplemented
+        return self.value == other.value and self.warnings == other.warnings
+
+# Lift the value returned by a function with warnings, which is followed by the warnings in the
+# same buffer.
+def _uniffi_lift_with_warnings(rbuf, ffi_converter):
+    with rbuf.consume_with_stream() as stream:
+        value = ffi_converter.read(stream)
+        warnings = _UniffiConverterSequenceString.read(stream)
+        return WithWarnings(value, warnings)
@@ -30,9 +30,30 @@ _rust_call(
 )
 {%- endmacro -%}
 
+{#-
+// Call the Rust default implementation of a trait method, passing `self` as the trait object.
+-#}
+{%- macro to_ffi_default_call(meth) -%}
+    {%- match meth.throws_type() -%}
+    {%- when Some with (e) -%}
+_rust_call_with_error(
+    {{ e|ffi_converter_name }},
+    {%- else -%}
+_rust_call(
+    {%- endmatch -%}
+    _UniffiLib.{{ meth.ffi_default_func().unwrap().name() }},
+    {{- ffi_converter_name }}.lower(self),
+    {%- call arg_list_lowered(meth) -%}
+)
+{%- endmacro -%}
+
 {%- macro arg_list_lowered(func) %}
     {%- for arg in func.arguments() %}
+        {%- if arg.is_segmented() %}
+        _uniffi_lower_byte_segments({{ arg.name()|var_name }})
+        {%- else %}
         {{ arg|lower_fn }}({{ arg.name()|var_name }})
+        {%- endif %}
         {%- if !loop.last %},{% endif %}
     {%- endfor %}
 {%- endmacro -%}
@@ -50,6 +71,58 @@ _rust_call(
 {%- call docstring_value(defn.docstring(), indent_spaces) %}
 {%- endmacro %}
 
+{%- macro type_docstring(defn, indent_spaces) %}
+{%- call docstring_value(defn.docstring()|with_since(defn.since()), indent_spaces) %}
+{%- endmacro %}
+
+{%- macro callable_docstring(callable, indent_spaces) %}
+{%- call docstring_value(callable|callable_docstring, indent_spaces) %}
+{%- endmacro %}
+
+{#-
+// Objects, their constructors and their methods document who owns the Rust object when the
+// `ownership_docs` config is set.
+-#}
+{%- macro object_docstring(obj, indent_spaces) %}
+{%- if config.ownership_docs() %}
+{%- call docstring_value(obj|object_ownership_docstring, indent_spaces) %}
+{%- else %}
+{%- call type_docstring(obj, indent_spaces) %}
+{%- endif %}
+{%- endmacro %}
+
+{%- macro constructor_docstring(cons, indent_spaces) %}
+{%- if config.ownership_docs() %}
+{%- call docstring_value(cons|constructor_ownership_docstring, indent_spaces) %}
+{%- else %}
+{%- call callable_docstring(cons, indent_spaces) %}
+{%- endif %}
+{%- endmacro %}
+
+{%- macro method_docstring(meth, indent_spaces) %}
+{%- if config.ownership_docs() && !meth.is_enum_method() %}
+{%- call docstring_value(meth|method_ownership_docstring, indent_spaces) %}
+{%- else %}
+{%- call callable_docstring(meth, indent_spaces) %}
+{%- endif %}
+{%- endmacro %}
+
+{#-
+// Records and enum variants just mention the deprecation in their docstring, since the
+// bindings create instances of them too and they'd warn every time.
+-#}
+{%- macro docstring_with_deprecation(defn, indent_spaces) %}
+{%- call docstring_value(defn.docstring()|with_deprecation_note(defn.deprecated()), indent_spaces) %}
+{%- endmacro %}
+
+{%- macro deprecation_warning(defn, indent_spaces) %}
+{%- match defn.deprecated() %}
+{%- when Some(message) %}
+{{ message|deprecation_warning(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
+
 {#-
 // Arglist as used in Python declarations of methods, functions and constructors.
 // Note the var_name and type_name filters.
@@ -88,7 +161,7 @@ _rust_call(
     {%- when None %}
     {%- when Some with(literal) %}
     if {{ arg.name()|var_name }} is _DEFAULT:
-        {{ arg.name()|var_name }} = {{ literal|literal_py(arg.as_type().borrow()) }}
+        {{ arg.name()|var_name }} = {{ literal|literal_py(arg.as_type().borrow(), config, ci) }}
     {%- endmatch %}
     {% endfor -%}
 {%- endmacro -%}
@@ -104,7 +177,7 @@ _rust_call(
         {%- when None %}
         {%- when Some with(literal) %}
         if {{ arg.name()|var_name }} is _DEFAULT:
-            {{ arg.name()|var_name }} = {{ literal|literal_py(arg.as_type().borrow()) }}
+            {{ arg.name()|var_name }} = {{ literal|literal_py(arg.as_type().borrow(), config, ci) }}
         {%- endmatch %}
         {% endfor -%}
 {%- endmacro -%}
@@ -112,19 +185,20 @@ _rust_call(
 {#
  # Macro to call methods
  #}
-{%- macro method_decl(py_method_name, meth) %}
+{%- macro method_decl(py_method_name, meth, clone_pointer_call) %}
 {%  if meth.is_async() %}
 
-    def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}):
-        {%- call docstring(meth, 8) %}
+    async def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}):
+        {%- call method_docstring(meth, 8) %}
         {%- call setup_args_extra_indent(meth) %}
-        return _uniffi_rust_call_async(
+        return await _uniffi_rust_call_async(
             _UniffiLib.{{ meth.ffi_func().name() }}(
-                self._uniffi_clone_pointer(), {% call arg_list_lowered(meth) %}
+                {{ clone_pointer_call }}, {% call arg_list_lowered(meth) %}
             ),
             _UniffiLib.{{ meth.ffi_rust_future_poll(ci) }},
             _UniffiLib.{{ meth.ffi_rust_future_complete(ci) }},
             _UniffiLib.{{ meth.ffi_rust_future_free(ci) }},
+            _UniffiLib.{{ meth.ffi_rust_future_cancel(ci) }},
             # lift function
             {%- match meth.return_type() %}
             {%- when Some(return_type) %}
@@ -147,19 +221,45 @@ _rust_call(
 {%-         when Some with (return_type) %}
 
     def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}) -> "{{ return_type|type_name }}":
-        {%- call docstring(meth, 8) %}
+        {%- call method_docstring(meth, 8) %}
         {%- call setup_args_extra_indent(meth) %}
         return {{ return_type|lift_fn }}(
-            {% call to_ffi_call_with_prefix("self._uniffi_clone_pointer()", meth) %}
+            {% call to_ffi_call_with_prefix(clone_pointer_call, meth) %}
         )
 
 {%-         when None %}
 
     def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}):
-        {%- call docstring(meth, 8) %}
+        {%- call method_docstring(meth, 8) %}
         {%- call setup_args_extra_indent(meth) %}
-        {% call to_ffi_call_with_prefix("self._uniffi_clone_pointer()", meth) %}
+        {% call to_ffi_call_with_prefix(clone_pointer_call, meth) %}
 {%      endmatch %}
 {%  endif %}
 
 {% endmacro %}
+
+{#-
+// Read or write an enum's discriminant, which is an `i32` unless the enum sets its `[Discriminant]`.
+-#}
+{%- macro discriminant_format(encoding) -%}
+{% if encoding.is_little_endian() %}<{% else %}>{% endif -%}
+{% if encoding.width() == 1 %}B{% else if encoding.width() == 2 %}H{% else %}I{% endif -%}
+{%- endmacro -%}
+
+{%- macro read_discriminant(e) -%}
+{%- match e.discriminant_encoding() -%}
+{%- when Some with (encoding) -%}
+buf._unpack_from({{ encoding.width() }}, "{% call discriminant_format(encoding) %}")
+{%- when None -%}
+buf.read_i32()
+{%- endmatch -%}
+{%- endmacro -%}
+
+{%- macro write_discriminant(e, value) -%}
+{%- match e.discriminant_encoding() -%}
+{%- when Some with (encoding) -%}
+buf._pack_into({{ encoding.width() }}, "{% call discriminant_format(encoding) %}", {{ value }})
+{%- when None -%}
+buf.write_i32({{ value }})
+{%- endmatch -%}
+{%- endmacro -%}
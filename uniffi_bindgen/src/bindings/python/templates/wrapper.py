@@ -1,3 +1,7 @@
+{%- match config.file_header() %}{% when Some with (header) %}{{ header }}
+{% when None %}{% endmatch -%}
+{% if config.hash_header() %}# {{ ci.hash_header() }}
+{% endif -%}
 {%- call py::docstring_value(ci.namespace_docstring(), 0) %}
 
 # This file was autogenerated by some hot garbage in the `uniffi` crate.
@@ -14,19 +18,9 @@
 # now that means coming from the exact some version of `uniffi` that was used to
 # compile the rust component. The easiest way to ensure this is to bundle the Python
 # helpers directly inline like we're doing here.
-
-import os
-import sys
-import ctypes
-import enum
-import struct
-import contextlib
-import datetime
-import typing
-{%- if ci.has_async_fns() %}
-import asyncio
-{%- endif %}
-import platform
+{% for name in self.builtin_imports() %}
+import {{ name }}
+{%- endfor %}
 {%- for req in self.imports() %}
 {{ req.render() }}
 {%- endfor %}
@@ -47,13 +41,42 @@ _DEFAULT = object()
 {%- include "Async.py" %}
 {%- endif %}
 
+{%- if ci.has_chunked_fns() %}
+{% include "ChunkedSequence.py" %}
+{%- endif %}
+
+{%- if ci.has_with_warnings_fns() %}
+{% include "WithWarnings.py" %}
+{%- endif %}
+{%- if ci.has_out_params_fns() %}
+{% include "OutParams.py" %}
+{%- endif %}
+
+{%- if ci.has_segmented_fns() %}
+{% include "ByteSegments.py" %}
+{%- endif %}
+
 # Public interface members begin here.
 {{ type_helper_code }}
 
 {%- for func in ci.function_definitions() %}
+{%- if func.is_runtime_initializer() %}
+{%- include "RuntimeConfigTemplate.py" %}
+{%- else %}
 {%- include "TopLevelFunctionTemplate.py" %}
+{%- endif %}
 {%- endfor %}
 
+{%- include "ConstantTemplate.py" %}
+
+{%- for namespace_object in ci.namespace_objects() %}
+{%- include "NamespaceObjectTemplate.py" %}
+{%- endfor %}
+
+{%- if ci.has_self_test() %}
+{% include "SelfTest.py" %}
+{%- endif %}
+
 __all__ = [
     "InternalError",
     {%- for e in ci.enum_definitions() %}
@@ -63,7 +86,27 @@ __all__ = [
     "{{ record|type_name }}",
     {%- endfor %}
     {%- for func in ci.function_definitions() %}
+    {%- if func.is_runtime_initializer() %}
+    "UniffiRuntimeConfig",
+    {%- endif %}
+    {%- if func.namespace_object().is_none() %}
     "{{ func.name()|fn_name }}",
+    {%- if func.is_async() && config.generate_blocking_variants() %}
+    "{{ func.name()|fn_name }}_blocking",
+    {%- endif %}
+    {%- if func.is_cancellable() %}
+    "{{ func.name()|fn_name }}_cancellable",
+    {%- endif %}
+    {%- if ci.progress(func).is_some() %}
+    "{{ func.name()|fn_name }}_with_progress",
+    {%- endif %}
+    {%- if func.default_on_none().is_some() %}
+    "{{ func.name()|fn_name }}_or_default",
+    {%- endif %}
+    {%- endif %}
+    {%- endfor %}
+    {%- for namespace_object in ci.namespace_objects() %}
+    "{{ namespace_object|class_name }}",
     {%- endfor %}
     {%- for obj in ci.object_definitions() %}
     "{{ obj|type_name }}",
@@ -71,6 +114,30 @@ __all__ = [
     {%- for c in ci.callback_interface_definitions() %}
     "{{ c.name()|class_name }}",
     {%- endfor %}
+    {%- if ci.has_dyn_error() %}
+    "DynError",
+    {%- endif %}
+    {%- if ci.has_opaque_handle() %}
+    "OpaqueHandle",
+    {%- endif %}
+    {%- if ci.has_duration_range(false) || ci.has_duration_range(true) %}
+    "DurationRange",
+    {%- endif %}
+    {%- if ci.has_cancellable_fns() %}
+    "UniffiCancellableHandle",
+    {%- endif %}
+    {%- if ci.has_self_test() %}
+    "uniffi_self_test",
+    {%- endif %}
+    {%- if ci.has_with_warnings_fns() %}
+    "WithWarnings",
+    {%- endif %}
+    {%- if ci.has_thread_bound_objects() %}
+    "WrongThreadError",
+    {%- endif %}
+    {%- if ci.has_on_poison_objects() %}
+    "StatePoisonedError",
+    {%- endif %}
 ]
 
 {% import "macros.py" as py %}
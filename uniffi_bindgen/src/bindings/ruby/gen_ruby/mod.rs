@@ -7,7 +7,7 @@ use askama::Template;
 use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use crate::interface::*;
 use crate::BindingsConfig;
@@ -57,6 +57,12 @@ pub fn canonical_name(t: &Type) -> String {
         Type::CallbackInterface { name, .. } => format!("CallbackInterface{name}"),
         Type::Timestamp => "Timestamp".into(),
         Type::Duration => "Duration".into(),
+        Type::DurationRange { inclusive: false } => "DurationRange".into(),
+        Type::DurationRange { inclusive: true } => "DurationRangeInclusive".into(),
+        Type::Path => "Path".into(),
+        Type::Json => "Json".into(),
+        Type::DynError => "DynError".into(),
+        Type::OpaqueHandle => "OpaqueHandle".into(),
         // Recursive types.
         // These add a prefix to the name of the underlying type.
         // The component API definition cannot give names to recursive types, so as long as the
@@ -64,6 +70,10 @@ pub fn canonical_name(t: &Type) -> String {
         // acccidentally generating name collisions.
         Type::Optional { inner_type } => format!("Optional{}", canonical_name(inner_type)),
         Type::Sequence { inner_type } => format!("Sequence{}", canonical_name(inner_type)),
+        Type::NonZero { inner_type } => format!(
+            "NonZero{}",
+            canonical_name(inner_type).to_upper_camel_case()
+        ),
         Type::Map {
             key_type,
             value_type,
@@ -84,6 +94,10 @@ pub fn canonical_name(t: &Type) -> String {
 pub struct Config {
     cdylib_name: Option<String>,
     cdylib_path: Option<String>,
+    file_header: Option<String>,
+    hash_header: Option<bool>,
+    #[serde(default)]
+    extra_imports: Vec<String>,
 }
 
 impl Config {
@@ -100,6 +114,17 @@ impl Config {
     pub fn cdylib_path(&self) -> String {
         self.cdylib_path.clone().unwrap_or_default()
     }
+
+    /// Text to insert verbatim at the top of each generated file, such as a license header.
+    pub fn file_header(&self) -> Option<&str> {
+        self.file_header.as_deref()
+    }
+
+    /// Whether to add a comment at the top of each generated file with the uniffi-bindgen version
+    /// and the interface's stable hash, so a checked-in copy can be checked against its source.
+    pub fn hash_header(&self) -> bool {
+        self.hash_header.unwrap_or(false)
+    }
 }
 
 impl BindingsConfig for Config {
@@ -131,6 +156,19 @@ impl<'a> RubyWrapper<'a> {
             canonical_name: &canonical_name,
         }
     }
+
+    /// The libraries from the `extra_imports` config to `require`, except for those which are
+    /// always required.
+    pub fn imports(&self) -> Vec<String> {
+        self.config
+            .extra_imports
+            .iter()
+            .filter(|name| !["ffi", "json"].contains(&name.as_str()))
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
 }
 
 mod filters {
@@ -217,6 +255,27 @@ mod filters {
         Ok(nm.to_string().to_shouty_snake_case())
     }
 
+    /// The width and `pack` format of an enum's discriminant, which is an `i32` unless the enum
+    /// sets its `[Discriminant]`.
+    pub fn discriminant_pack_rb(e: &Enum) -> Result<String, askama::Error> {
+        Ok(match e.discriminant_encoding() {
+            None => "4, 'l>'".to_string(),
+            Some(encoding) => {
+                let format = match encoding.width() {
+                    1 => "C",
+                    2 => "S",
+                    _ => "L",
+                };
+                let order = match (encoding.width(), encoding.is_little_endian()) {
+                    (1, _) => "",
+                    (_, true) => "<",
+                    (_, false) => ">",
+                };
+                format!("{}, '{format}{order}'", encoding.width())
+            }
+        })
+    }
+
     pub fn coerce_rb(nm: &str, ns: &str, type_: &Type) -> Result<String, askama::Error> {
         Ok(match type_ {
             Type::Int8 => format!("{ns}::uniffi_in_range({nm}, \"i8\", -2**7, 2**7)"),
@@ -232,10 +291,20 @@ mod filters {
             Type::Object { .. } | Type::Enum { .. } | Type::Record { .. } => nm.to_string(),
             Type::String => format!("{ns}::uniffi_utf8({nm})"),
             Type::Bytes => format!("{ns}::uniffi_bytes({nm})"),
-            Type::Timestamp | Type::Duration => nm.to_string(),
+            Type::Timestamp | Type::Duration | Type::DurationRange { .. } => nm.to_string(),
+            // Accept a `Pathname` as well as a `String`.
+            Type::Path => format!("{ns}::uniffi_utf8({nm}.to_s)"),
+            // Any value that `JSON.generate` accepts.
+            Type::Json => nm.to_string(),
+            Type::OpaqueHandle => format!("{ns}::uniffi_opaque_handle({nm})"),
             Type::CallbackInterface { .. } => {
                 panic!("No support for coercing callback interfaces yet")
             }
+            Type::NonZero { inner_type: t } => format!(
+                "{ns}::uniffi_non_zero({}, \"{}\")",
+                coerce_rb(nm, ns, t)?,
+                canonical_name(type_)
+            ),
             Type::Optional { inner_type: t } => format!("({nm} ? {} : nil)", coerce_rb(nm, ns, t)?),
             Type::Sequence { inner_type: t } => {
                 let coerce_code = coerce_rb("v", ns, t)?;
@@ -259,6 +328,7 @@ mod filters {
             }
             Type::External { .. } => panic!("No support for external types, yet"),
             Type::Custom { .. } => panic!("No support for custom types, yet"),
+            Type::DynError => panic!("No support for dynamic errors, yet"),
         })
     }
 
@@ -280,7 +350,15 @@ mod filters {
         })
     }
 
-    pub fn lower_rb(nm: &str, type_: &Type) -> Result<String, askama::Error> {
+    fn is_scalar_enum(ci: &ComponentInterface, name: &str) -> bool {
+        ci.get_enum_definition(name).map_or(false, Enum::is_scalar)
+    }
+
+    pub fn lower_rb(
+        nm: &str,
+        type_: &Type,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
         Ok(match type_ {
             Type::Int8
             | Type::UInt8
@@ -292,6 +370,8 @@ mod filters {
             | Type::UInt64
             | Type::Float32
             | Type::Float64 => nm.to_string(),
+            Type::NonZero { inner_type } => lower_rb(nm, inner_type, ci)?,
+            Type::OpaqueHandle => format!("{nm}.value"),
             Type::Boolean => format!("({nm} ? 1 : 0)"),
             Type::String => format!("RustBuffer.allocFromString({nm})"),
             Type::Bytes => format!("RustBuffer.allocFromBytes({nm})"),
@@ -299,12 +379,17 @@ mod filters {
             Type::CallbackInterface { .. } => {
                 panic!("No support for lowering callback interfaces yet")
             }
+            // Flat enums are already represented by the integer that's passed across the FFI.
+            Type::Enum { name, .. } if is_scalar_enum(ci, name) => nm.to_string(),
             Type::Enum { .. }
             | Type::Record { .. }
             | Type::Optional { .. }
             | Type::Sequence { .. }
             | Type::Timestamp
             | Type::Duration
+            | Type::DurationRange { .. }
+            | Type::Path
+            | Type::Json
             | Type::Map { .. } => format!(
                 "RustBuffer.alloc_from_{}({})",
                 class_name_rb(&canonical_name(type_))?,
@@ -312,10 +397,15 @@ mod filters {
             ),
             Type::External { .. } => panic!("No support for lowering external types, yet"),
             Type::Custom { .. } => panic!("No support for lowering custom types, yet"),
+            Type::DynError => panic!("No support for lowering dynamic errors, yet"),
         })
     }
 
-    pub fn lift_rb(nm: &str, type_: &Type) -> Result<String, askama::Error> {
+    pub fn lift_rb(
+        nm: &str,
+        type_: &Type,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
         Ok(match type_ {
             Type::Int8
             | Type::UInt8
@@ -325,6 +415,8 @@ mod filters {
             | Type::UInt32
             | Type::Int64
             | Type::UInt64 => format!("{nm}.to_i"),
+            Type::NonZero { inner_type } => lift_rb(nm, inner_type, ci)?,
+            Type::OpaqueHandle => format!("OpaqueHandle.new({nm}.to_i)"),
             Type::Float32 | Type::Float64 => format!("{nm}.to_f"),
             Type::Boolean => format!("1 == {nm}"),
             Type::String => format!("{nm}.consumeIntoString"),
@@ -333,6 +425,7 @@ mod filters {
             Type::CallbackInterface { .. } => {
                 panic!("No support for lifting callback interfaces, yet")
             }
+            Type::Enum { name, .. } if is_scalar_enum(ci, name) => format!("{nm}.to_i"),
             Type::Enum { .. } => {
                 format!(
                     "{}.consumeInto{}",
@@ -345,6 +438,9 @@ mod filters {
             | Type::Sequence { .. }
             | Type::Timestamp
             | Type::Duration
+            | Type::DurationRange { .. }
+            | Type::Path
+            | Type::Json
             | Type::Map { .. } => format!(
                 "{}.consumeInto{}",
                 nm,
@@ -352,6 +448,7 @@ mod filters {
             ),
             Type::External { .. } => panic!("No support for lifting external types, yet"),
             Type::Custom { .. } => panic!("No support for lifting custom types, yet"),
+            Type::DynError => panic!("No support for lifting dynamic errors, yet"),
         })
     }
 }
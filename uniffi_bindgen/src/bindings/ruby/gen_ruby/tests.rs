@@ -1,4 +1,9 @@
 use super::{is_reserved_word, Config};
+use crate::bindings::ruby::generate_ruby_bindings;
+use crate::bindings::{
+    ci_with_metadata, contains_code, fn_metadata, generate_from_udl, method_metadata,
+};
+use crate::interface::ComponentInterface;
 
 #[test]
 fn when_reserved_word() {
@@ -15,6 +20,7 @@ fn cdylib_name() {
     let config = Config {
         cdylib_name: None,
         cdylib_path: None,
+        ..Default::default()
     };
 
     assert_eq!("uniffi", config.cdylib_name());
@@ -22,6 +28,7 @@ fn cdylib_name() {
     let config = Config {
         cdylib_name: Some("todolist".to_string()),
         cdylib_path: None,
+        ..Default::default()
     };
 
     assert_eq!("todolist", config.cdylib_name());
@@ -32,6 +39,7 @@ fn cdylib_path() {
     let config = Config {
         cdylib_name: None,
         cdylib_path: None,
+        ..Default::default()
     };
 
     assert_eq!("", config.cdylib_path());
@@ -40,8 +48,346 @@ fn cdylib_path() {
     let config = Config {
         cdylib_name: None,
         cdylib_path: Some("/foo/bar".to_string()),
+        ..Default::default()
     };
 
     assert_eq!("/foo/bar", config.cdylib_path());
     assert!(config.custom_cdylib_path());
 }
+
+#[test]
+fn optional_throwing_return() {
+    const UDL: &str = r#"
+        namespace test {
+            [Throws=Failure]
+            string? find(u8 index);
+        };
+
+        [Error]
+        enum Failure { "Oops" };
+    "#;
+    let ruby = generate_from_udl(UDL, "", generate_ruby_bindings);
+    assert!(contains_code(
+        &ruby,
+        "  result = Test.rust_call_with_error(Failure,:uniffi_crate_name_fn_func_find,index)\n  \
+         return result.consumeIntoOptionalstring\n"
+    ));
+}
+
+#[test]
+fn shared_error_generated_once() {
+    const UDL: &str = r#"
+        namespace test {
+            [Throws=StoreError]
+            void open();
+            [Throws=StoreError]
+            string read(u32 key);
+            [Throws=StoreError]
+            void write(u32 key, string value);
+        };
+
+        [Error]
+        enum StoreError { "NotFound", "ReadOnly" };
+    "#;
+    let ruby = generate_from_udl(UDL, "", generate_ruby_bindings);
+    assert_eq!(ruby.matches("class StoreError\n").count(), 1);
+    assert_eq!(ruby.matches("def readTypeStoreError\n").count(), 1);
+}
+
+#[test]
+fn wrong_thread_call_status() {
+    const UDL: &str = r#"
+        namespace test {};
+        [ThreadBound]
+        interface Context {
+            constructor();
+            void draw();
+        };
+    "#;
+    let ruby = generate_from_udl(UDL, "", generate_ruby_bindings);
+    assert!(contains_code(
+        &ruby,
+        "  when CALL_WRONG_THREAD\n    \
+        raise WrongThreadError, status.error_buf.consumeIntoString()"
+    ));
+}
+
+#[test]
+fn constants() {
+    let ci = ci_with_metadata(
+        "namespace test {};",
+        vec![uniffi_meta::ConstFnMetadata {
+            module_path: "crate_name".into(),
+            name: "app_version".into(),
+            return_type: uniffi_meta::Type::String,
+            checksum: None,
+            docstring: None,
+        }
+        .into()],
+    );
+    let ruby = generate_ruby_bindings(&Default::default(), &ci).unwrap();
+    assert!(ruby.contains("return @app_version if defined?(@app_version)\n"));
+}
+
+#[test]
+fn non_zero() {
+    let non_zero_u32 = uniffi_meta::Type::NonZero {
+        inner_type: Box::new(uniffi_meta::Type::UInt32),
+    };
+    let ci = ci_with_metadata(
+        "namespace test {};",
+        vec![fn_metadata(
+            "next_id",
+            vec![uniffi_meta::FnParamMetadata::simple(
+                "id",
+                non_zero_u32.clone(),
+            )],
+            Some(non_zero_u32),
+        )
+        .into()],
+    );
+    // Zero is checked for when lowering.
+    let ruby = generate_ruby_bindings(&Default::default(), &ci).unwrap();
+    assert!(ruby.contains(
+        "id = Test::uniffi_non_zero(Test::uniffi_in_range(id, \"u32\", 0, 2**32), \"NonZeroU32\")"
+    ));
+}
+
+#[test]
+fn opaque_handle() {
+    const UDL: &str = r#"
+        namespace test {
+            opaque_handle echo_handle(opaque_handle handle);
+        };
+    "#;
+    let ruby = generate_from_udl(UDL, "", generate_ruby_bindings);
+    assert!(ruby.contains("\nOpaqueHandle = Struct.new(:value) do\n"));
+    assert!(ruby.contains("handle = Test::uniffi_opaque_handle(handle)"));
+    assert!(ruby.contains("OpaqueHandle.new(result.to_i)"));
+}
+
+#[test]
+fn duration_ranges() {
+    const UDL: &str = r#"
+        namespace test {
+            duration_range shift(duration_range window, duration by);
+            duration_range_inclusive shift_closed(duration_range_inclusive window, duration by);
+        };
+    "#;
+    let ruby = generate_from_udl(UDL, "", generate_ruby_bindings);
+    assert!(ruby.contains("Range.new(first, last, true)\n"));
+    assert!(ruby.contains("raise ArgumentError, 'Expected an inclusive range' if v.exclude_end?\n"));
+}
+
+#[test]
+fn record_wire_order() {
+    const UDL: &str = r#"
+        namespace test {};
+        dictionary Reordered {
+            [Order=1] string first;
+            [Order=0] u32 second;
+        };
+    "#;
+    // Fields are read in wire order, but constructed in declaration order.
+    let ruby = generate_from_udl(UDL, "", generate_ruby_bindings);
+    assert!(contains_code(
+        &ruby,
+        "    second = readU32\n    first = readString\n    Reordered.new(first, second)\n"
+    ));
+}
+
+#[test]
+fn associated_constants() {
+    const UDL: &str = r#"
+        namespace test {};
+        interface Color {
+            /// Pure red
+            const u32 RED = 0xFF0000;
+            const boolean IS_OPAQUE = true;
+            const f64 SCALE = 1.5;
+            constructor();
+        };
+    "#;
+    let ruby = generate_from_udl(UDL, "", generate_ruby_bindings);
+    assert!(contains_code(
+        &ruby,
+        "  class Color\n  RED = 0xff0000\n  IS_OPAQUE = true\n  SCALE = 1.5\n"
+    ));
+}
+
+#[test]
+fn file_header_and_extra_imports() {
+    // Extra imports which are already generated are only imported once.
+    const CONFIG: &str = r##"
+        file_header = "# Copyright Example Corp."
+        extra_imports = ["ffi", "set", "set"]
+    "##;
+    let ruby = generate_from_udl("namespace test {};", CONFIG, generate_ruby_bindings);
+    let count = |needle: &str| ruby.matches(needle).count();
+    assert!(ruby.starts_with("# Copyright Example Corp.\n"));
+    assert_eq!(count("# Copyright Example Corp."), 1);
+    assert_eq!(count("\nrequire 'ffi'\n"), 1);
+    assert_eq!(count("\nrequire 'set'\n"), 1);
+}
+
+#[test]
+fn hash_header() {
+    const UDL: &str = r#"
+        namespace test {
+            u32 add(u32 a, u32 b);
+        };
+    "#;
+    let hash = ComponentInterface::from_webidl(UDL, "crate_name")
+        .unwrap()
+        .stable_hash();
+    let ruby = generate_from_udl(UDL, "hash_header = true", generate_ruby_bindings);
+    let plain = generate_from_udl(UDL, "", generate_ruby_bindings);
+    // The header is the first line, and the rest of the file is the same as without it.
+    assert_eq!(
+        ruby,
+        format!(
+            "# Generated by uniffi-bindgen {} from an interface with hash {hash:016x}\n{plain}",
+            env!("CARGO_PKG_VERSION"),
+        )
+    );
+}
+
+#[test]
+fn enum_unknown_variant() {
+    const UDL: &str = r#"
+        namespace test {};
+        [NonExhaustive]
+        enum Color { "Red", "Green" };
+    "#;
+    let ruby = generate_from_udl(UDL, "", generate_ruby_bindings);
+    assert!(!ruby.contains("Unexpected variant tag for TypeColor"));
+}
+
+#[test]
+fn enum_discriminant_encoding() {
+    const UDL: &str = r#"
+        namespace test {};
+        [Discriminant=u16_le]
+        enum Opcode { "Read", "Write" };
+        [Enum, Discriminant=u8]
+        interface Shape {
+            Circle(double radius);
+            Point();
+        };
+    "#;
+    let ruby = generate_from_udl(UDL, "", generate_ruby_bindings);
+    assert!(ruby.contains("variant = unpack_from 2, 'S<'"));
+    assert!(ruby.contains("pack_into(2, 'S<', v)"));
+    assert!(ruby.contains("variant = unpack_from 1, 'C'"));
+    assert!(ruby.contains("pack_into(1, 'C', 2)"));
+}
+
+#[test]
+fn with_warnings_unsupported() {
+    const UDL: &str = r#"
+        namespace test {
+            [WithWarnings, Throws=Error]
+            string load();
+        };
+        [Error]
+        enum Error { "Oops" };
+    "#;
+    let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+    let err = generate_ruby_bindings(&Default::default(), &ci).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Ruby bindings don't support functions with warnings: \"load\""
+    );
+}
+
+#[test]
+fn segmented_unsupported() {
+    const UDL: &str = r#"
+        namespace test {
+            [Segmented]
+            sequence<bytes> split(bytes data, u32 size);
+        };
+    "#;
+    let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+    let err = generate_ruby_bindings(&Default::default(), &ci).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Ruby bindings don't support [Segmented] byte segments"
+    );
+}
+
+#[test]
+fn has_default() {
+    const UDL: &str = r#"
+        namespace test {};
+        [HasDefault]
+        dictionary Settings {
+            string name;
+            u32 retries;
+        };
+    "#;
+    let ruby = generate_from_udl(UDL, "", generate_ruby_bindings);
+    assert!(ruby.contains("result = Test.rust_call(:uniffi_crate_name_fn_default_settings)"));
+}
+
+#[test]
+fn consuming_methods() {
+    const UDL: &str = r#"
+        namespace test {};
+        interface Builder {
+            constructor();
+            void append(string part);
+        };
+    "#;
+    let ci = ci_with_metadata(
+        UDL,
+        vec![uniffi_meta::MethodMetadata {
+            takes_self_by_value: true,
+            ..method_metadata("Builder", "build", Some(uniffi_meta::Type::String))
+        }
+        .into()],
+    );
+    let ruby = generate_ruby_bindings(&Default::default(), &ci).unwrap();
+    assert!(ruby.contains(":uniffi_crate_name_fn_method_builder_build,uniffi_consume_pointer()"));
+}
+
+#[test]
+fn default_on_none() {
+    const UDL: &str = r#"
+        namespace test {
+            [DefaultOnNone="7"]
+            u32? find_count(string key);
+        };
+    "#;
+    let ruby = generate_from_udl(UDL, "", generate_ruby_bindings);
+    assert!(contains_code(
+        &ruby,
+        "def self.find_count_or_default(key)\n  result = find_count(key)\n"
+    ));
+}
+
+#[test]
+fn out_params_unsupported() {
+    let out_param = uniffi_meta::FnParamMetadata {
+        out: true,
+        ..uniffi_meta::FnParamMetadata::simple("remainder", uniffi_meta::Type::UInt32)
+    };
+    let ci = ci_with_metadata(
+        "namespace test {};",
+        vec![fn_metadata(
+            "divide",
+            vec![
+                uniffi_meta::FnParamMetadata::simple("dividend", uniffi_meta::Type::UInt32),
+                uniffi_meta::FnParamMetadata::simple("divisor", uniffi_meta::Type::UInt32),
+                out_param,
+            ],
+            Some(uniffi_meta::Type::UInt32),
+        )
+        .into()],
+    );
+    let err = generate_ruby_bindings(&Default::default(), &ci).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Ruby bindings don't support functions with out-parameters: \"divide\""
+    );
+}
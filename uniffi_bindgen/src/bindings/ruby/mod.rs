@@ -4,9 +4,8 @@
 
 use std::process::Command;
 
-use anyhow::{Context, Result};
-use camino::Utf8Path;
-use fs_err as fs;
+use anyhow::{bail, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
 
 pub mod gen_ruby;
 mod test;
@@ -23,8 +22,20 @@ pub fn write_bindings(
     out_dir: &Utf8Path,
     try_format_code: bool,
 ) -> Result<()> {
-    let rb_file = out_dir.join(format!("{}.rb", ci.namespace()));
-    fs::write(&rb_file, generate_ruby_bindings(config, ci)?)?;
+    write_bindings_with_formatter(config, ci, out_dir, try_format_code, None)
+}
+
+/// Like [`write_bindings`], but passes the generated source through `formatter` before it's
+/// written.
+pub fn write_bindings_with_formatter(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+    formatter: Option<&super::Formatter>,
+) -> Result<()> {
+    let rb_file = out_dir.join(bindings_filename(ci));
+    super::write_source_file(&rb_file, generate_ruby_bindings(config, ci)?, formatter)?;
 
     if try_format_code {
         if let Err(e) = Command::new("rubocop").arg("-A").arg(&rb_file).output() {
@@ -38,10 +49,54 @@ pub fn write_bindings(
     Ok(())
 }
 
+/// Render the Ruby bindings, keyed by their paths relative to the output directory.
+pub fn render_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<Vec<(Utf8PathBuf, String)>> {
+    Ok(vec![(
+        bindings_filename(ci),
+        generate_ruby_bindings(config, ci)?,
+    )])
+}
+
+fn bindings_filename(ci: &ComponentInterface) -> Utf8PathBuf {
+    format!("{}.rb", ci.namespace()).into()
+}
+
 // Generate ruby bindings for the given ComponentInterface, as a string.
 
 pub fn generate_ruby_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
     use askama::Template;
+    if let Some(obj) = ci
+        .object_definitions()
+        .iter()
+        .find(|o| o.parent().is_some())
+    {
+        bail!(
+            "Ruby bindings don't support interface inheritance: \"{}\"",
+            obj.name()
+        );
+    }
+    if let Some(func) = ci
+        .function_definitions()
+        .iter()
+        .find(|f| f.is_with_warnings())
+    {
+        bail!(
+            "Ruby bindings don't support functions with warnings: \"{}\"",
+            func.name()
+        );
+    }
+    if ci.has_segmented_fns() {
+        bail!("Ruby bindings don't support [Segmented] byte segments");
+    }
+    if let Some(func) = ci.function_definitions().iter().find(|f| f.has_out_args()) {
+        bail!(
+            "Ruby bindings don't support functions with out-parameters: \"{}\"",
+            func.name()
+        );
+    }
     RubyWrapper::new(config.clone(), ci)
         .render()
         .context("failed to render ruby bindings")
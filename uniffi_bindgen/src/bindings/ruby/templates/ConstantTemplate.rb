@@ -0,0 +1,10 @@
+{%- match func.return_type() %}
+{%- when Some with (return_type) %}
+
+def self.{{ func.name()|fn_name_rb }}
+  return @{{ func.name()|fn_name_rb }} if defined?(@{{ func.name()|fn_name_rb }})
+  result = {% call rb::to_ffi_call(func) %}
+  @{{ func.name()|fn_name_rb }} = {{ "result"|lift_rb(return_type, ci) }}
+end
+{%- when None %}
+{%- endmatch %}
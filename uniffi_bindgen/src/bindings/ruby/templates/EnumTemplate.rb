@@ -52,8 +52,42 @@ class {{ e.name()|class_name_rb }}
       instance_of? {{ e.name()|class_name_rb }}::{{ variant.name()|enum_name_rb }}
     end
     {% endfor %}
+    {%- if e.has_unknown_variant() %}
+    def unknown?
+      false
+    end
+    {%- endif %}
   end
   {% endfor %}
+  {%- if e.has_unknown_variant() %}
+
+  # A variant which these bindings don't know about, because it was added to the
+  # non-exhaustive enum after they were generated.
+  class Unknown
+    attr_reader :discriminant
+
+    def initialize(discriminant)
+      @discriminant = discriminant
+    end
+
+    def to_s
+      "{{ e.name()|class_name_rb }}::Unknown(#{@discriminant})"
+    end
+
+    def ==(other)
+      other.unknown? && @discriminant == other.discriminant
+    end
+
+    def unknown?
+      true
+    end
+    {% for variant in e.variants() %}
+    def {{ variant.name()|var_name_rb }}?
+      false
+    end
+    {% endfor %}
+  end
+  {%- endif %}
 end
 
 {% endif %}
@@ -19,6 +19,8 @@ end
 CALL_SUCCESS = 0
 CALL_ERROR = 1
 CALL_PANIC = 2
+CALL_WRONG_THREAD = 4
+CALL_STATE_POISONED = 5
 {%- for e in ci.enum_definitions() %}
 {% if ci.is_name_used_as_error(e.name()) %}
 {% if e.is_flat() %}
@@ -50,6 +52,26 @@ module {{ e.name()|class_name_rb }}
 end
 {% endif %}
 {%- endfor %}
+{%- if ci.has_dyn_error() %}
+
+# A dynamically typed error raised by Rust.
+#
+# `message` is the `Display` string of the Rust error, and `cause_chain` holds the `Display`
+# strings of its chain of sources, starting with the immediate source.
+class DynError < StandardError
+  def initialize(message, cause_chain)
+    @message = message
+    @cause_chain = cause_chain
+    super(message)
+  end
+
+  attr_reader :message, :cause_chain
+
+  def inspect
+    "#{self.class.name}(#{@message.inspect}, cause_chain=#{@cause_chain.inspect})"
+  end
+end
+{%- endif %}
 
 # Map error modules to the RustBuffer method name that reads them
 ERROR_MODULE_TO_READER_METHOD = {
@@ -60,10 +82,13 @@ ERROR_MODULE_TO_READER_METHOD = {
   {{ e.name()|class_name_rb }} => :read{{ canonical_type_name }},
 {% endif %}
 {%- endfor %}
+{%- if ci.has_dyn_error() %}
+  DynError => :readDynError,
+{%- endif %}
 }
 
 private_constant :ERROR_MODULE_TO_READER_METHOD, :CALL_SUCCESS, :CALL_ERROR, :CALL_PANIC,
-                 :RustCallStatus
+                 :CALL_WRONG_THREAD, :CALL_STATE_POISONED, :RustCallStatus
 
 def self.consume_buffer_into_error(error_module, rust_buffer)
   rust_buffer.consumeWithStream do |stream|
@@ -74,6 +99,19 @@ end
 
 class InternalError < StandardError
 end
+{%- if ci.has_thread_bound_objects() %}
+
+# Raised when a method of a `[ThreadBound]` object is called on a thread other than the one which
+# constructed it.
+class WrongThreadError < StandardError
+end
+{%- endif %}
+{%- if ci.has_on_poison_objects() %}
+
+# Raised when a method of an `[OnPoison=error]` object panics on state poisoned by an earlier call.
+class StatePoisonedError < StandardError
+end
+{%- endif %}
 
 def self.rust_call(fn_name, *args)
   # Call a rust function
@@ -113,6 +151,14 @@ def self.rust_call_with_error(error_module, fn_name, *args)
     else
       raise InternalError, "Rust panic"
     end
+  {%- if ci.has_thread_bound_objects() %}
+  when CALL_WRONG_THREAD
+    raise WrongThreadError, status.error_buf.consumeIntoString()
+  {%- endif %}
+  {%- if ci.has_on_poison_objects() %}
+  when CALL_STATE_POISONED
+    raise StatePoisonedError, status.error_buf.consumeIntoString()
+  {%- endif %}
   else
     raise InternalError, "Unknown call status: #{status.code}"
   end
@@ -5,6 +5,11 @@ def self.uniffi_in_range(i, type_name, min, max)
   i
 end
 
+def self.uniffi_non_zero(i, type_name)
+  raise RangeError, "#{type_name} requires a non-zero value" if i.zero?
+  i
+end
+
 def self.uniffi_utf8(v)
   raise TypeError, "no implicit conversion of #{v} into String" unless v.respond_to?(:to_str)
   v = v.to_str.encode(Encoding::UTF_8)
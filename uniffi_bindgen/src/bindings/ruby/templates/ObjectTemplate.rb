@@ -1,4 +1,7 @@
 class {{ obj.name()|class_name_rb }}
+  {%- for constant in obj.constants() %}
+  {{ constant.name()|enum_name_rb }} = {{ constant.value()|literal_rb }}
+  {%- endfor %}
 
   # A private helper for initializing instances of the class from a raw pointer,
   # bypassing any initialization logic and ensuring they are GC'd properly.
@@ -32,16 +35,61 @@ class {{ obj.name()|class_name_rb }}
   end
 
   def uniffi_clone_pointer()
+    {%- if obj.has_consuming_methods() %}
+    uniffi_check_not_consumed()
+    {%- endif %}
     return {{ ci.namespace()|class_name_rb }}.rust_call(
       :{{ obj.ffi_object_clone().name() }},
       @pointer
     )
   end
+  {%- if obj.has_consuming_methods() %}
+
+  # Methods which consume the object take over its pointer, so it can't be used afterwards.
+  def uniffi_consume_pointer()
+    uniffi_check_not_consumed()
+    pointer = @pointer
+    @pointer = nil
+    ObjectSpace.undefine_finalizer(self)
+    return pointer
+  end
+
+  def uniffi_check_not_consumed()
+    if @pointer.nil?
+      raise RuntimeError.new "{{ obj.name()|class_name_rb }} object has already been consumed"
+    end
+  end
+  {%- endif %}
 
   def self.uniffi_lower(inst)
     return inst.uniffi_clone_pointer()
   end
 
+  {%- if obj.has_identity_equality() %}
+
+  # The id of the underlying Rust object, which is the same for every handle to it.
+  def uniffi_handle_id()
+    {%- if obj.has_consuming_methods() %}
+    uniffi_check_not_consumed()
+    {%- endif %}
+    return {{ ci.namespace()|class_name_rb }}.rust_call(
+      :{{ obj.ffi_object_handle_id().name() }},
+      @pointer
+    )
+  end
+
+  def ==(other)
+    return other.is_a?({{ obj.name()|class_name_rb }}) && uniffi_handle_id == other.uniffi_handle_id
+  end
+  alias eql? ==
+  {%- endif %}
+  {%- if obj.has_identity_hash() %}
+
+  def hash
+    return uniffi_handle_id.hash
+  end
+  {%- endif %}
+
   {%- match obj.primary_constructor() %}
   {%- when Some with (cons) %}
   def initialize({% call rb::arg_list_decl(cons) -%})
@@ -53,6 +101,16 @@ class {{ obj.name()|class_name_rb }}
   {%- when None %}
   {%- endmatch %}
 
+  {%- if obj.is_singleton() %}
+
+  # The single instance of the class, which is shared by all callers.
+  def self.instance
+    @instance ||= uniffi_allocate({{ ci.namespace()|class_name_rb }}.rust_call(
+      :{{ obj.ffi_object_singleton().name() }}
+    ))
+  end
+  {%- endif %}
+
   {% for cons in obj.alternate_constructors() -%}
   def self.{{ cons.name()|fn_name_rb }}({% call rb::arg_list_decl(cons) %})
     {%- call rb::setup_args_extra_indent(cons) %}
@@ -64,19 +122,25 @@ class {{ obj.name()|class_name_rb }}
   {% endfor %}
 
   {% for meth in obj.methods() -%}
+  {%- let self_pointer_call %}
+  {%- if meth.takes_self_by_value() %}
+  {%- let self_pointer_call = "uniffi_consume_pointer()" %}
+  {%- else %}
+  {%- let self_pointer_call = "uniffi_clone_pointer()" %}
+  {%- endif %}
   {%- match meth.return_type() -%}
 
   {%- when Some with (return_type) -%}
   def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
     {%- call rb::setup_args_extra_indent(meth) %}
-    result = {% call rb::to_ffi_call_with_prefix("uniffi_clone_pointer()", meth) %}
-    return {{ "result"|lift_rb(return_type) }}
+    result = {% call rb::to_ffi_call_with_prefix(self_pointer_call, meth) %}
+    return {{ "result"|lift_rb(return_type, ci) }}
   end
 
   {%- when None -%}
   def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
       {%- call rb::setup_args_extra_indent(meth) %}
-      {% call rb::to_ffi_call_with_prefix("uniffi_clone_pointer()", meth) %}
+      {% call rb::to_ffi_call_with_prefix(self_pointer_call, meth) %}
   end
   {% endmatch %}
   {% endfor %}
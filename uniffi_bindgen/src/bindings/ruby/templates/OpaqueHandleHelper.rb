@@ -0,0 +1,12 @@
+# A value from Rust, usually a pointer, which is passed back unchanged.
+# Nothing keeps what it points to alive: that's up to the Rust code which handed it out.
+OpaqueHandle = Struct.new(:value) do
+  def to_i
+    value
+  end
+end
+
+def self.uniffi_opaque_handle(v)
+  raise TypeError, "no implicit conversion of #{v} into OpaqueHandle" unless v.is_a?(OpaqueHandle)
+  v
+end
@@ -17,4 +17,12 @@ class {{ rec.name()|class_name_rb }}
 
     true
   end
+  {%- if rec.has_default() %}
+
+  # The Rust `Default::default()` value of the record.
+  def self.default
+    result = {{ ci.namespace()|class_name_rb }}.rust_call(:{{ rec.ffi_func_default().name() }})
+    return {{ "result"|lift_rb(rec.as_type().borrow(), ci) }}
+  end
+  {%- endif %}
 end
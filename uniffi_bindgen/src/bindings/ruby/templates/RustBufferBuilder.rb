@@ -87,6 +87,13 @@ class RustBufferBuilder
     pack_into(8, 'Q>', v)
   end
 
+  {% when Type::OpaqueHandle -%}
+
+  def write_{{ canonical_type_name }}(v)
+    v = {{ ci.namespace()|class_name_rb }}::uniffi_opaque_handle(v)
+    pack_into(8, 'Q>', v.value)
+  end
+
   {% when Type::Float32 -%}
 
   def write_F32(v)
@@ -146,6 +153,24 @@ class RustBufferBuilder
     pack_into 4, 'L>', nanoseconds
   end
 
+  {% when Type::Path -%}
+  # The Path type, which is a String in Ruby.
+
+  def write_{{ canonical_type_name }}(v)
+    v = {{ ci.namespace()|class_name_rb }}::uniffi_utf8(v)
+    pack_into 4, 'l>', v.bytes.size
+    write v
+  end
+
+  {% when Type::Json -%}
+  # The Json type, which is any value that `JSON.generate` accepts in Ruby.
+
+  def write_{{ canonical_type_name }}(v)
+    v = {{ ci.namespace()|class_name_rb }}::uniffi_utf8(JSON.generate(v))
+    pack_into 4, 'l>', v.bytes.size
+    write v
+  end
+
   {% when Type::Duration -%}
   # The Duration type.
 
@@ -159,6 +184,21 @@ class RustBufferBuilder
     pack_into 4, 'L>', nanoseconds
   end
 
+  {% when Type::DurationRange { inclusive } -%}
+  # A range of durations, where the end is {% if !inclusive %}not {% endif %}part of the range.
+
+  def write_{{ canonical_type_name }}(v)
+    {%- if inclusive %}
+    raise ArgumentError, 'Expected an inclusive range' if v.exclude_end?
+    {%- else %}
+    raise ArgumentError, 'Expected an exclusive range' unless v.exclude_end?
+    {%- endif %}
+
+    write_Duration v.begin
+    write_Duration v.end
+    pack_into 1, 'c', {% if inclusive %}1{% else %}0{% endif %}
+  end
+
   {% when Type::Object with { name: object_name, module_path, imp } -%}
   # The Object type {{ object_name }}.
 
@@ -174,16 +214,19 @@ class RustBufferBuilder
 
   def write_{{ canonical_type_name }}(v)
     {%- if e.is_flat() %}
-    pack_into(4, 'l>', v)
+    pack_into({{ e|discriminant_pack_rb }}, v)
     {%- else -%}
     {%- for variant in e.variants() %}
     if v.{{ variant.name()|var_name_rb }}?
-      pack_into(4, 'l>', {{ loop.index }})
+      pack_into({{ e|discriminant_pack_rb }}, {{ loop.index }})
       {%- for field in variant.fields() %}
       self.write_{{ canonical_name(field.as_type().borrow()).borrow()|class_name_rb }}(v.{{ field.name() }})
       {%- endfor %}
     end
     {%- endfor %}
+    {%- if e.has_unknown_variant() %}
+    pack_into({{ e|discriminant_pack_rb }}, v.discriminant) if v.unknown?
+    {%- endif %}
     {%- endif %}
  end
    {% endif %}
@@ -193,9 +236,26 @@ class RustBufferBuilder
   # The Record type {{ record_name }}.
 
   def write_{{ canonical_type_name }}(v)
-    {%- for field in rec.fields() %}
+    {%- for item in rec.wire_items() %}
+    {%- match item %}
+    {%- when WireItem::Field with (field) %}
     self.write_{{ canonical_name(field.as_type().borrow()).borrow()|class_name_rb }}(v.{{ field.name()|var_name_rb }})
+    {%- when WireItem::PackedBools with (bools) %}
+    packed = 0
+    {%- for (field, mask) in bools %}
+    packed |= {{ mask }} if v.{{ field.name()|var_name_rb }}
     {%- endfor %}
+    pack_into(1, 'C', packed)
+    {%- endmatch %}
+    {%- endfor %}
+  end
+
+  {% when Type::NonZero { inner_type } -%}
+  # The NonZero<T> type for {{ canonical_name(inner_type) }}.
+
+  def write_{{ canonical_type_name }}(v)
+    v = {{ ci.namespace()|class_name_rb }}::uniffi_non_zero(v, "{{ canonical_type_name }}")
+    self.write_{{ canonical_name(inner_type).borrow()|class_name_rb }}(v)
   end
 
   {% when Type::Optional { inner_type } -%}
@@ -73,6 +73,12 @@ class RustBufferStream
     unpack_from 8, 'Q>'
   end
 
+  {% when Type::OpaqueHandle -%}
+
+  def read{{ canonical_type_name }}
+    OpaqueHandle.new(unpack_from(8, 'Q>'))
+  end
+
   {% when Type::Float32 -%}
 
   def readF32
@@ -140,6 +146,28 @@ class RustBufferStream
     Time.at(seconds, nanoseconds, :nanosecond, in: '+00:00').utc
   end
 
+  {% when Type::Path -%}
+  # The Path type, which is a String in Ruby.
+
+  def read{{ canonical_type_name }}
+    size = unpack_from 4, 'l>'
+
+    raise InternalError, 'Unexpected negative string length' if size.negative?
+
+    read(size).force_encoding(Encoding::UTF_8)
+  end
+
+  {% when Type::Json -%}
+  # The Json type, which is parsed with `JSON.parse` in Ruby.
+
+  def read{{ canonical_type_name }}
+    size = unpack_from 4, 'l>'
+
+    raise InternalError, 'Unexpected negative JSON length' if size.negative?
+
+    JSON.parse(read(size).force_encoding(Encoding::UTF_8))
+  end
+
   {% when Type::Duration -%}
   # The Duration type.
 
@@ -150,6 +178,28 @@ class RustBufferStream
     Time.at(seconds, nanoseconds, :nanosecond, in: '+00:00').utc
   end
 
+  {% when Type::DurationRange { inclusive } -%}
+  # A range of durations, where the end is {% if !inclusive %}not {% endif %}part of the range.
+
+  def read{{ canonical_type_name }}
+    first = readDuration
+    last = readDuration
+
+    raise InternalError, 'Unexpected {% if inclusive %}exclusive{% else %}inclusive{% endif %} range' unless unpack_from(1, 'c') == {% if inclusive %}1{% else %}0{% endif %}
+
+    Range.new(first, last, {% if inclusive %}false{% else %}true{% endif %})
+  end
+
+  {% when Type::DynError -%}
+  # The DynError type, which is the error's message followed by its cause chain.
+
+  def read{{ canonical_type_name }}
+    message = readString
+    cause_chain = readSequencestring
+
+    DynError.new(message, cause_chain)
+  end
+
   {% when Type::Object with { name: object_name, module_path, imp } -%}
   # The Object type {{ object_name }}.
 
@@ -165,7 +215,7 @@ class RustBufferStream
   # The Enum type {{ enum_name }}.
 
   def read{{ canonical_type_name }}
-    variant = unpack_from 4, 'l>'
+    variant = unpack_from {{ e|discriminant_pack_rb }}
     {% if e.is_flat() -%}
     {%- for variant in e.variants() %}
     if variant == {{ loop.index }}
@@ -173,7 +223,16 @@ class RustBufferStream
     end
     {%- endfor %}
 
+    {% match e.default_variant() -%}
+    {%- when Some with (default) -%}
+    {{ enum_name|class_name_rb }}::{{ default.name()|enum_name_rb }}
+    {%- when None -%}
+    {%- if e.has_unknown_variant() -%}
+    variant
+    {%- else -%}
     raise InternalError, 'Unexpected variant tag for {{ canonical_type_name }}'
+    {%- endif -%}
+    {%- endmatch %}
     {%- else -%}
     {%- for variant in e.variants() %}
     if variant == {{ loop.index }}
@@ -188,8 +247,12 @@ class RustBufferStream
         {% endif %}
     end
     {%- endfor %}
+    {%- if e.has_unknown_variant() %}
+    {{ enum_name|class_name_rb }}::Unknown.new(variant)
+    {%- else %}
     raise InternalError, 'Unexpected variant tag for {{ canonical_type_name }}'
     {%- endif %}
+    {%- endif %}
   end
 
   {% else %}
@@ -235,11 +298,44 @@ class RustBufferStream
   # The Record type {{ record_name }}.
 
   def read{{ canonical_type_name }}
+    {%- if rec.is_packed() %}
+    # Adjacent boolean fields are packed into bytes.
+    {%- for item in rec.wire_items() %}
+    {%- match item %}
+    {%- when WireItem::Field with (field) %}
+    {{ field.name()|var_name_rb }} = read{{ canonical_name(field.as_type().borrow()).borrow()|class_name_rb }}
+    {%- when WireItem::PackedBools with (bools) %}
+    uniffi_packed{{ loop.index0 }} = unpack_from 1, 'C'
+    {%- let packed = "uniffi_packed{}"|format(loop.index0) %}
+    {%- for (field, mask) in bools %}
+    {{ field.name()|var_name_rb }} = ({{ packed }} & {{ mask }}) != 0
+    {%- endfor %}
+    {%- endmatch %}
+    {%- endfor %}
+    {{ rec.name()|class_name_rb }}.new({% for field in rec.fields() %}{{ field.name()|var_name_rb }}{% if !loop.last %}, {% endif %}{% endfor %})
+    {%- else if rec.has_wire_order() %}
+    # The fields are serialized in a different order to their declaration.
+    {%- for field in rec.wire_fields() %}
+    {{ field.name()|var_name_rb }} = read{{ canonical_name(field.as_type().borrow()).borrow()|class_name_rb }}
+    {%- endfor %}
+    {{ rec.name()|class_name_rb }}.new({% for field in rec.fields() %}{{ field.name()|var_name_rb }}{% if !loop.last %}, {% endif %}{% endfor %})
+    {%- else %}
     {{ rec.name()|class_name_rb }}.new(
       {%- for field in rec.fields() %}
       read{{ canonical_name(field.as_type().borrow()).borrow()|class_name_rb }}{% if loop.last %}{% else %},{% endif %}
       {%- endfor %}
     )
+    {%- endif %}
+  end
+
+  {% when Type::NonZero { inner_type } -%}
+  # The NonZero<T> type for {{ canonical_name(inner_type) }}.
+
+  def read{{ canonical_type_name }}
+    v = read{{ canonical_name(inner_type).borrow()|class_name_rb }}
+    raise InternalError, 'Unexpected zero value for {{ canonical_type_name }}' if v.zero?
+
+    v
   end
 
   {% when Type::Optional { inner_type } -%}
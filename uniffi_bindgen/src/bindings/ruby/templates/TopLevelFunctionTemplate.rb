@@ -3,8 +3,24 @@
 
 def self.{{ func.name()|fn_name_rb }}({%- call rb::arg_list_decl(func) -%})
   {%- call rb::setup_args(func) %}
+  {%- if func.is_chunked() %}
+  # The sequence is returned in chunks, until an empty one.
+  handle = {% call rb::to_ffi_call(func) %}
+  begin
+    result = []
+    loop do
+      chunk = {{ ci.namespace()|class_name_rb }}.rust_call(:{{ ci.ffi_chunked_sequence_next().name() }}, handle)
+      chunk = {{ "chunk"|lift_rb(return_type, ci) }}
+      return result if chunk.empty?
+      result.concat(chunk)
+    end
+  ensure
+    {{ ci.namespace()|class_name_rb }}.rust_call(:{{ ci.ffi_chunked_sequence_free().name() }}, handle)
+  end
+  {%- else %}
   result = {% call rb::to_ffi_call(func) %}
-  return {{ "result"|lift_rb(return_type) }}
+  return {{ "result"|lift_rb(return_type, ci) }}
+  {%- endif %}
 end
 
 {% when None %}
@@ -14,3 +30,13 @@ def self.{{ func.name()|fn_name_rb }}({%- call rb::arg_list_decl(func) -%})
   {% call rb::to_ffi_call(func) %}
 end
 {% endmatch %}
+{%- match func.default_on_none() %}
+{%- when Some with ((default, return_type)) %}
+
+# Variant of `{{ func.name()|fn_name_rb }}` which returns `{{ default|literal_rb }}` rather than `nil`.
+def self.{{ func.name()|fn_name_rb }}_or_default({%- call rb::arg_list_decl(func) -%})
+  result = {{ func.name()|fn_name_rb }}({% for arg in func.arguments() %}{{ arg.name()|var_name_rb }}{% if !loop.last %}, {% endif %}{% endfor %})
+  result.nil? ? {{ default|literal_rb }} : result
+end
+{%- when None %}
+{%- endmatch %}
@@ -31,7 +31,7 @@
 
 {%- macro _arg_list_ffi_call(func) %}
     {%- for arg in func.arguments() %}
-        {{- arg.name()|lower_rb(arg.as_type().borrow()) }}
+        {{- arg.name()|lower_rb(arg.as_type().borrow(), ci) }}
         {%- if !loop.last %},{% endif %}
     {%- endfor %}
 {%- endmacro -%}
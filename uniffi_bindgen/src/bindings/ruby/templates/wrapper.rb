@@ -1,3 +1,7 @@
+{%- match config.file_header() %}{% when Some with (header) %}{{ header }}
+{% when None %}{% endmatch -%}
+{% if config.hash_header() %}# {{ ci.hash_header() }}
+{% endif -%}
 # This file was autogenerated by some hot garbage in the `uniffi` crate.
 # Trust me, you don't want to mess with it!
 
@@ -14,11 +18,19 @@
 # helpers directly inline like we're doing here.
 
 require 'ffi'
+require 'json'
+{%- for name in self.imports() %}
+require '{{ name }}'
+{%- endfor %}
 
 
 module {{ ci.namespace()|class_name_rb }}
   {% include "Helpers.rb" %}
 
+  {%- if ci.has_opaque_handle() %}
+  {% include "OpaqueHandleHelper.rb" %}
+  {%- endif %}
+
   {% include "RustBufferTemplate.rb" %}
   {% include "RustBufferStream.rb" %}
   {% include "RustBufferBuilder.rb" %}
@@ -44,6 +56,10 @@ module {{ ci.namespace()|class_name_rb }}
   {% include "TopLevelFunctionTemplate.rb" %}
   {% endfor %}
 
+  {% for func in ci.constant_definitions() %}
+  {% include "ConstantTemplate.rb" %}
+  {% endfor %}
+
   {% for obj in ci.object_definitions() %}
   {% include "ObjectTemplate.rb" %}
   {% endfor %}
@@ -36,6 +36,35 @@ impl CodeType for OptionalCodeType {
     }
 }
 
+// Non-zero integers use the plain integer type, and only check for zero when lifting.
+#[derive(Debug)]
+pub struct NonZeroCodeType {
+    inner: Type,
+}
+
+impl NonZeroCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for NonZeroCodeType {
+    fn type_label(&self) -> String {
+        super::SwiftCodeOracle.find(&self.inner).type_label()
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "NonZero{}",
+            super::SwiftCodeOracle.find(&self.inner).canonical_name()
+        )
+    }
+
+    fn literal(&self, literal: &Literal) -> String {
+        super::SwiftCodeOracle.find(&self.inner).literal(literal)
+    }
+}
+
 #[derive(Debug)]
 pub struct SequenceCodeType {
     inner: Type,
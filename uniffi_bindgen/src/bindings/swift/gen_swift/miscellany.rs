@@ -29,3 +29,82 @@ impl CodeType for DurationCodeType {
         "Duration".into()
     }
 }
+
+#[derive(Debug)]
+pub struct DurationRangeCodeType {
+    inclusive: bool,
+}
+
+impl DurationRangeCodeType {
+    pub fn new(inclusive: bool) -> Self {
+        Self { inclusive }
+    }
+}
+
+impl CodeType for DurationRangeCodeType {
+    fn type_label(&self) -> String {
+        match self.inclusive {
+            false => "Range<TimeInterval>".into(),
+            true => "ClosedRange<TimeInterval>".into(),
+        }
+    }
+
+    fn canonical_name(&self) -> String {
+        match self.inclusive {
+            false => "DurationRange".into(),
+            true => "DurationRangeInclusive".into(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PathCodeType;
+
+impl CodeType for PathCodeType {
+    fn type_label(&self) -> String {
+        "URL".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Path".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct JsonCodeType;
+
+impl CodeType for JsonCodeType {
+    fn type_label(&self) -> String {
+        "String".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Json".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct DynErrorCodeType;
+
+impl CodeType for DynErrorCodeType {
+    fn type_label(&self) -> String {
+        "DynError".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "DynError".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct OpaqueHandleCodeType;
+
+impl CodeType for OpaqueHandleCodeType {
+    fn type_label(&self) -> String {
+        "OpaqueHandle".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "OpaqueHandle".into()
+    }
+}
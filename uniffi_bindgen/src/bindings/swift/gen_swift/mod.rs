@@ -195,11 +195,31 @@ pub struct Config {
     ffi_module_filename: Option<String>,
     generate_module_map: Option<bool>,
     omit_argument_labels: Option<bool>,
+    omit_first_argument_label: Option<bool>,
     generate_immutable_records: Option<bool>,
+    derive_display: Option<bool>,
+    generate_codable: Option<bool>,
+    file_header: Option<String>,
+    hash_header: Option<bool>,
+    ownership_docs: Option<bool>,
+    #[serde(default)]
+    extra_imports: Vec<String>,
     #[serde(default)]
     custom_types: HashMap<String, CustomTypeConfig>,
+    #[serde(default)]
+    target_conditions: HashMap<String, String>,
 }
 
+/// The compilation conditions for the Apple platforms, which are used for `[Target]` functions
+/// unless they're overridden by `target_conditions`.
+const DEFAULT_TARGET_CONDITIONS: &[(&str, &str)] = &[
+    ("ios", "os(iOS)"),
+    ("macos", "os(macOS)"),
+    ("tvos", "os(tvOS)"),
+    ("watchos", "os(watchOS)"),
+    ("visionos", "os(visionOS)"),
+];
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct CustomTypeConfig {
     imports: Option<Vec<String>>,
@@ -262,10 +282,65 @@ impl Config {
         self.omit_argument_labels.unwrap_or(false)
     }
 
+    /// Whether to omit the label of the first argument of functions and methods, following the
+    /// Swift convention of `func greet(_ name: String)`.  Initializers always keep their labels.
+    pub fn omit_first_argument_label(&self) -> bool {
+        self.omit_first_argument_label.unwrap_or(false)
+    }
+
     /// Whether to generate immutable records (`let` instead of `var`)
     pub fn generate_immutable_records(&self) -> bool {
         self.generate_immutable_records.unwrap_or(false)
     }
+
+    /// Whether to make records `CustomStringConvertible`, with a `description` which lists their
+    /// fields and summarizes `Data` rather than printing its contents.
+    pub fn derive_display(&self) -> bool {
+        self.derive_display.unwrap_or(false)
+    }
+
+    /// Whether to conform records and enums without fields to `Codable`, where all of their
+    /// fields can be encoded.
+    pub fn generate_codable(&self) -> bool {
+        self.generate_codable.unwrap_or(false)
+    }
+
+    /// The compilation condition which a function for the given target variant is wrapped in,
+    /// such as `os(iOS)` for `ios`.
+    ///
+    /// Functions without a target aren't wrapped, and functions for a target without a condition
+    /// aren't generated at all.
+    pub fn target_condition(&self, target: Option<&str>) -> Option<String> {
+        let target = target?;
+        self.target_conditions.get(target).cloned().or_else(|| {
+            DEFAULT_TARGET_CONDITIONS
+                .iter()
+                .find(|(name, _)| *name == target)
+                .map(|(_, condition)| condition.to_string())
+        })
+    }
+
+    /// Whether to generate a function for the given target variant.
+    pub fn generates_target(&self, target: Option<&str>) -> bool {
+        target.is_none() || self.target_condition(target).is_some()
+    }
+
+    /// Text to insert verbatim at the top of each generated file, such as a license header.
+    pub fn file_header(&self) -> Option<&str> {
+        self.file_header.as_deref()
+    }
+
+    /// Whether to add a comment at the top of each generated file with the uniffi-bindgen version
+    /// and the interface's stable hash, so a checked-in copy can be checked against its source.
+    pub fn hash_header(&self) -> bool {
+        self.hash_header.unwrap_or(false)
+    }
+
+    /// Whether to document who owns the Rust object behind each object, its constructors and its
+    /// methods.
+    pub fn ownership_docs(&self) -> bool {
+        self.ownership_docs.unwrap_or(false)
+    }
 }
 
 impl BindingsConfig for Config {
@@ -356,6 +431,49 @@ impl<'a> TypeRenderer<'a> {
         self.imports.borrow_mut().insert(name.to_owned());
         ""
     }
+
+    // Whether the Swift type is `Codable`, when `generate_codable` is set.
+    //
+    // Objects, callback interfaces and enums with fields have no `Codable` conformance, and
+    // neither does anything containing them.  Nor do external types, or custom types which
+    // are converted to a type of the user's choosing.
+    fn is_codable(&self, type_: &Type) -> bool {
+        if !self.config.generate_codable() {
+            return false;
+        }
+        match type_ {
+            Type::Object { .. }
+            | Type::CallbackInterface { .. }
+            | Type::External { .. }
+            | Type::DynError => false,
+            Type::Optional { inner_type }
+            | Type::Sequence { inner_type }
+            | Type::NonZero { inner_type } => self.is_codable(inner_type),
+            Type::Map {
+                key_type,
+                value_type,
+            } => self.is_codable(key_type) && self.is_codable(value_type),
+            Type::Record { name, .. } => self.ci.get_record_definition(name).map_or(false, |rec| {
+                rec.fields().iter().all(|f| self.is_codable(&f.as_type()))
+            }),
+            Type::Enum { name, .. } => {
+                !self.ci.is_name_used_as_error(name)
+                    && self
+                        .ci
+                        .get_enum_definition(name)
+                        .map_or(false, Enum::is_flat)
+            }
+            Type::Custom { name, builtin, .. } => {
+                !self
+                    .config
+                    .custom_types
+                    .get(name)
+                    .map_or(false, |custom| custom.type_name.is_some())
+                    && self.is_codable(builtin)
+            }
+            _ => true,
+        }
+    }
 }
 
 /// Template for generating the `.h` file that defines the low-level C FFI.
@@ -366,16 +484,13 @@ impl<'a> TypeRenderer<'a> {
 #[derive(Template)]
 #[template(syntax = "c", escape = "none", path = "BridgingHeaderTemplate.h")]
 pub struct BridgingHeader<'config, 'ci> {
-    _config: &'config Config,
+    config: &'config Config,
     ci: &'ci ComponentInterface,
 }
 
 impl<'config, 'ci> BridgingHeader<'config, 'ci> {
     pub fn new(config: &'config Config, ci: &'ci ComponentInterface) -> Self {
-        Self {
-            _config: config,
-            ci,
-        }
+        Self { config, ci }
     }
 }
 
@@ -389,12 +504,12 @@ impl<'config, 'ci> BridgingHeader<'config, 'ci> {
 #[template(syntax = "c", escape = "none", path = "ModuleMapTemplate.modulemap")]
 pub struct ModuleMap<'config, 'ci> {
     config: &'config Config,
-    _ci: &'ci ComponentInterface,
+    ci: &'ci ComponentInterface,
 }
 
 impl<'config, 'ci> ModuleMap<'config, 'ci> {
-    pub fn new(config: &'config Config, _ci: &'ci ComponentInterface) -> Self {
-        Self { config, _ci }
+    pub fn new(config: &'config Config, ci: &'ci ComponentInterface) -> Self {
+        Self { config, ci }
     }
 }
 
@@ -419,8 +534,17 @@ impl<'a> SwiftWrapper<'a> {
         }
     }
 
+    /// The modules imported by the types in use and those from the `extra_imports` config,
+    /// except `Foundation` which is always imported.
     pub fn imports(&self) -> Vec<String> {
-        self.type_imports.iter().cloned().collect()
+        self.type_imports
+            .iter()
+            .chain(&self.config.extra_imports)
+            .filter(|name| name.as_str() != "Foundation")
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
     }
 
     pub fn initialization_fns(&self) -> Vec<String> {
@@ -461,6 +585,13 @@ impl SwiftCodeOracle {
 
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
+            Type::DurationRange { inclusive } => {
+                Box::new(miscellany::DurationRangeCodeType::new(inclusive))
+            }
+            Type::Path => Box::new(miscellany::PathCodeType),
+            Type::Json => Box::new(miscellany::JsonCodeType),
+            Type::DynError => Box::new(miscellany::DynErrorCodeType),
+            Type::OpaqueHandle => Box::new(miscellany::OpaqueHandleCodeType),
 
             Type::Enum { name, .. } => Box::new(enum_::EnumCodeType::new(name)),
             Type::Object { name, imp, .. } => Box::new(object::ObjectCodeType::new(name, imp)),
@@ -474,6 +605,7 @@ impl SwiftCodeOracle {
             Type::Sequence { inner_type } => {
                 Box::new(compounds::SequenceCodeType::new(*inner_type))
             }
+            Type::NonZero { inner_type } => Box::new(compounds::NonZeroCodeType::new(*inner_type)),
             Type::Map {
                 key_type,
                 value_type,
@@ -660,6 +792,27 @@ pub mod filters {
         Ok(quote_general_keyword(oracle().var_name(nm)))
     }
 
+    /// Get the value of a record field in a `description` string, with `Data` summarized by its
+    /// size and optionals unwrapped.
+    pub fn display_field(field: &Field) -> Result<String, askama::Error> {
+        let name = var_name(field.name())?;
+        Ok(match field.as_type() {
+            Type::Bytes => format!("<\\({name}.count) bytes>"),
+            Type::Optional { inner_type } if *inner_type == Type::Bytes => {
+                format!("\\({name}.map {{ \"<\\($0.count) bytes>\" }} ?? \"nil\")")
+            }
+            Type::Optional { .. } => {
+                format!("\\({name}.map {{ String(describing: $0) }} ?? \"nil\")")
+            }
+            _ => format!("\\({name})"),
+        })
+    }
+
+    /// Remove the backticks from a name quoted with `var_name`.
+    pub fn unquote(nm: &str) -> Result<String, askama::Error> {
+        Ok(nm.trim_matches('`').to_string())
+    }
+
     /// Get the idiomatic Swift rendering of an arguments name.
     /// This is the same as the var name but quoting is not required.
     pub fn arg_name(nm: &str) -> Result<String, askama::Error> {
@@ -685,6 +838,108 @@ pub mod filters {
         Ok(textwrap::indent(&wrapped, &" ".repeat(spaces)))
     }
 
+    /// Get an `@available` attribute which marks a declaration as deprecated, with the
+    /// deprecation message.
+    pub fn deprecated(message: &str, spaces: &i32) -> Result<String, askama::Error> {
+        let message = message
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+        let spaces = usize::try_from(*spaces).unwrap_or_default();
+        Ok(format!(
+            "{}@available(*, deprecated, message: \"{message}\")",
+            " ".repeat(spaces)
+        ))
+    }
+
+    /// Get the docstring of a function, method or constructor, with DocC `- Parameter` and
+    /// `- Returns` entries for the argument and return value docs.
+    /// Add a `Since` callout to the docstring of a type, if it has a version.
+    ///
+    /// `@available` only takes platform and Swift versions, so the version of the library is only
+    /// documented.
+    pub fn with_since(
+        docstring: Option<&str>,
+        since: Option<&str>,
+    ) -> Result<Option<String>, askama::Error> {
+        Ok(match (docstring, since) {
+            (docstring, None) => docstring.map(ToOwned::to_owned),
+            (None, Some(version)) => Some(format!("- Since: {version}")),
+            (Some(docstring), Some(version)) => Some(format!("{docstring}\n\n- Since: {version}")),
+        })
+    }
+
+    pub fn callable_docstring<T: Callable>(callable: &T) -> Result<Option<String>, askama::Error> {
+        callable_docstring_with_note(callable, None)
+    }
+
+    /// How the bindings release an object's reference, for the `ownership_docs` notes.
+    const OWNERSHIP_RELEASE: &str = "when the instance is deinitialized";
+
+    /// Get the docstring of an object, with a note on who owns its Rust object.
+    pub fn object_ownership_docstring(obj: &Object) -> Result<Option<String>, askama::Error> {
+        let docstring = join_note(obj.docstring(), obj.ownership_doc(OWNERSHIP_RELEASE));
+        with_since(Some(&docstring), obj.since())
+    }
+
+    /// Get the docstring of a constructor, with a note on who owns the new instance.
+    pub fn constructor_ownership_docstring(
+        cons: &Constructor,
+    ) -> Result<Option<String>, askama::Error> {
+        callable_docstring_with_note(cons, Some(cons.ownership_doc(OWNERSHIP_RELEASE)))
+    }
+
+    /// Get the docstring of a method, with a note on who owns the object during the call.
+    pub fn method_ownership_docstring(meth: &Method) -> Result<Option<String>, askama::Error> {
+        callable_docstring_with_note(meth, Some(meth.ownership_doc(OWNERSHIP_RELEASE)))
+    }
+
+    fn join_note(docstring: Option<&str>, note: String) -> String {
+        match docstring {
+            Some(docstring) => format!("{docstring}\n\n{note}"),
+            None => note,
+        }
+    }
+
+    /// The note is added to the description, before the callouts.
+    fn callable_docstring_with_note<T: Callable>(
+        callable: &T,
+        note: Option<String>,
+    ) -> Result<Option<String>, askama::Error> {
+        let mut tags: Vec<String> = callable
+            .arguments()
+            .into_iter()
+            .filter_map(|arg| {
+                let docstring = arg.docstring()?.replace('\n', "\n  ");
+                Some(format!(
+                    "- Parameter {}: {docstring}",
+                    oracle().var_name(arg.name())
+                ))
+            })
+            .collect();
+        tags.extend(
+            callable
+                .return_docstring()
+                .map(|d| format!("- Returns: {}", d.replace('\n', "\n  "))),
+        );
+        tags.extend(callable.since().map(|v| format!("- Since: {v}")));
+        if callable.is_main_thread() {
+            tags.insert(
+                0,
+                "- Precondition: Must be called from the main thread.".to_string(),
+            );
+        }
+        let docstring = match note {
+            Some(note) => Some(join_note(callable.docstring(), note)),
+            None => callable.docstring().map(ToOwned::to_owned),
+        };
+        Ok(match (docstring, tags.is_empty()) {
+            (docstring, true) => docstring,
+            (None, false) => Some(tags.join("\n")),
+            (Some(docstring), false) => Some(format!("{docstring}\n\n{}", tags.join("\n"))),
+        })
+    }
+
     pub fn error_handler(result: &ResultType) -> Result<String, askama::Error> {
         Ok(match &result.throws_type {
             Some(t) => format!("{}.lift", ffi_converter_name(t)?),
@@ -710,4 +965,57 @@ pub mod filters {
     pub fn object_names(obj: &Object) -> Result<(String, String), askama::Error> {
         Ok(SwiftCodeOracle.object_names(obj))
     }
+
+    /// The protocol that the protocol generated for `obj` refines.
+    pub fn parent_protocol_name(obj: &Object) -> Result<String, askama::Error> {
+        Ok(match obj.parent() {
+            Some(parent) => format!("{}Protocol", SwiftCodeOracle.class_name(parent)),
+            None => "AnyObject".to_string(),
+        })
+    }
+
+    /// The method which clones the pointer to `obj` for passing it to Rust.
+    ///
+    /// Objects which inherit from another interface hold a pointer for each level of the
+    /// hierarchy, so each level has its own method.
+    pub fn clone_pointer_fn(obj: &Object) -> Result<String, askama::Error> {
+        Ok(match obj.parent() {
+            Some(_) => format!(
+                "uniffiClone{}Pointer",
+                SwiftCodeOracle.class_name(obj.name())
+            ),
+            None => "uniffiClonePointer".to_string(),
+        })
+    }
+
+    /// The primary constructor of the parent of `obj`, if the class generated for `obj` would
+    /// otherwise inherit it.
+    ///
+    /// Swift classes inherit the convenience initializers of their superclass, but calling the
+    /// parent's constructor can't create a child object, so the child needs to hide it.
+    pub fn inherited_constructor(
+        obj: &Object,
+        ci: &ComponentInterface,
+    ) -> Result<Option<Constructor>, askama::Error> {
+        let Some(parent_cons) = obj
+            .parent()
+            .and_then(|parent| ci.get_object_definition(parent))
+            .and_then(Object::primary_constructor)
+        else {
+            return Ok(None);
+        };
+        let signature = |cons: &Constructor| -> Vec<(String, Type)> {
+            cons.arguments()
+                .into_iter()
+                .map(|arg| (arg.name().to_string(), arg.as_type()))
+                .collect()
+        };
+        let redeclared = obj
+            .primary_constructor()
+            .map_or(false, |cons| signature(cons) == signature(parent_cons));
+        Ok((!redeclared).then(|| parent_cons.clone()))
+    }
 }
+
+#[cfg(test)]
+mod tests;
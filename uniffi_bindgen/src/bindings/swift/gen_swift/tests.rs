@@ -0,0 +1,1064 @@
+use super::generate_bindings;
+use crate::bindings::{
+    ci_with_metadata, contains_code, fn_metadata, generate_from_udl,
+    generate_with_runtime_initializer, method_metadata,
+};
+use crate::interface::ComponentInterface;
+
+#[test]
+fn test_namespace_object_functions() {
+    const UDL: &str = r#"
+        namespace test {
+            u32 top_level();
+            [Namespace=MyApi]
+            u32 grouped_one();
+            [Namespace=MyApi, Async]
+            u32 grouped_two(u32 value);
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("public func topLevel()"));
+    let (_, swift_enum) = swift.split_once("\npublic enum MyApi {\n").unwrap();
+    assert!(swift_enum.contains("public static func groupedOne()"));
+    assert!(swift_enum.contains("public static func groupedTwo(value: UInt32) async"));
+}
+
+#[test]
+fn test_deprecated() {
+    const UDL: &str = r#"
+        namespace test {
+            [Deprecated="Use new_thing instead"]
+            u32 old_thing();
+        };
+
+        enum Shape {
+            "Circle",
+            [Deprecated="Squares are going away"]
+            "Square",
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains(
+        "@available(*, deprecated, message: \"Use new_thing instead\")\npublic func oldThing()"
+    ));
+    assert!(contains_code(
+        &swift,
+        "    @available(*, deprecated, message: \"Squares are going away\")\n    case square"
+    ));
+}
+
+#[test]
+fn test_optional_throwing_return() {
+    const UDL: &str = r#"
+        namespace test {
+            [Throws=Failure]
+            string? find(u8 index);
+        };
+
+        [Error]
+        enum Failure { "Oops" };
+    "#;
+    // The optional is lifted from the return value of the call that checks for errors.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(contains_code(
+        &swift,
+        "public func find(index: UInt8) throws  -> String? {\n    \
+         return try  FfiConverterOptionString.lift(\n        \
+         try rustCallWithError(FfiConverterTypeFailure.lift) {"
+    ));
+}
+
+#[test]
+fn test_common_error_fields() {
+    const UDL: &str = r#"
+        namespace test {};
+
+        [Error]
+        interface CodedError {
+            NotFound(i32 code, string path);
+            Timeout(i32 code);
+        };
+    "#;
+    // Swift errors are enums, so the shared field is a computed property over the variants.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(contains_code(
+        &swift,
+        "extension CodedError {\n    \
+         public var code: Int32 {\n        \
+         switch self {\n        \
+         case let .NotFound(code, _):\n            \
+         return code\n        \
+         case let .Timeout(code):\n            \
+         return code\n"
+    ));
+    assert!(!swift.contains("public var path: String"));
+}
+
+#[test]
+fn test_shared_error_generated_once() {
+    const UDL: &str = r#"
+        namespace test {
+            [Throws=StoreError]
+            void open();
+            [Throws=StoreError]
+            string read(u32 key);
+            [Throws=StoreError]
+            void write(u32 key, string value);
+        };
+
+        [Error]
+        enum StoreError { "NotFound", "ReadOnly" };
+    "#;
+    // However many functions throw it, the error is only generated once.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert_eq!(swift.matches("public enum StoreError {").count(), 1);
+    assert_eq!(swift.matches("extension StoreError: Error { }").count(), 1);
+    assert_eq!(
+        swift
+            .matches("rustCallWithError(FfiConverterTypeStoreError.lift)")
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn test_wrong_thread_call_status() {
+    const UDL: &str = r#"
+        namespace test {};
+        [ThreadBound]
+        interface Context {
+            constructor();
+            void draw();
+        };
+    "#;
+    // The `WrongThread` call status is raised as its own error type.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(contains_code(
+        &swift,
+        "        case CALL_WRONG_THREAD:\n            \
+        throw WrongThreadError(message: try FfiConverterString.lift(callStatus.errorBuf))"
+    ));
+
+    // The error type is only generated for components with thread-bound objects.
+    let swift = generate_from_udl(
+        "namespace test {}; interface Context { void draw(); };",
+        "",
+        generate_bindings,
+    )
+    .library;
+    assert!(!swift.contains("WrongThreadError"));
+}
+
+#[test]
+fn test_state_poisoned_call_status() {
+    const UDL: &str = r#"
+        namespace test {};
+        [OnPoison=error]
+        interface Counter {
+            constructor();
+            u32 increment();
+        };
+    "#;
+    // The `StatePoisoned` call status is raised as its own error type.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("public struct StatePoisonedError: Swift.Error, Equatable, Hashable {"));
+    assert!(swift.contains("case CALL_STATE_POISONED:"));
+    assert!(swift.contains(
+        "throw StatePoisonedError(message: try FfiConverterString.lift(callStatus.errorBuf))"
+    ));
+
+    // The error type is only generated for components with `[OnPoison]` objects.
+    let swift = generate_from_udl(
+        "namespace test {}; interface Counter { u32 increment(); };",
+        "",
+        generate_bindings,
+    )
+    .library;
+    assert!(!swift.contains("StatePoisonedError"));
+}
+
+#[test]
+fn test_runtime_config() {
+    // The runtime initializer is wrapped in a builder, rather than exposed as a function.
+    let swift = generate_with_runtime_initializer(generate_bindings).library;
+    assert!(swift.contains("public class UniffiRuntimeConfig {"));
+    assert!(swift.contains("public func maxBufferLen(_ value: UInt64) -> UniffiRuntimeConfig {"));
+    assert!(swift.contains("public func maxReadDepth(_ value: UInt32) -> UniffiRuntimeConfig {"));
+    assert!(swift.contains("public func uniffiInitialize(_ config: UniffiRuntimeConfig) {"));
+    assert!(swift.contains("let maxReadDepth = config.maxReadDepth"));
+    assert!(!swift.contains("public func uniffiInitialize(maxBufferLen"));
+}
+
+#[test]
+fn test_async_cancellation() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async, Throws=Failure]
+            u32 cancellable();
+            [Async]
+            u32 not_cancellable();
+        };
+        [Error]
+        enum Failure { "Oops" };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+
+    // Cancelling the task cancels the Rust future, and cancelled calls throw
+    // `CancellationError`.
+    assert!(contains_code(
+        &swift,
+        "        } onCancel: {\n            cancelFunc?(rustFuture)\n"
+    ));
+    assert!(contains_code(
+        &swift,
+        "        case CALL_CANCELLED:\n            throw CancellationError()"
+    ));
+
+    // Only functions which can throw are cancellable.
+    let function = |name: &str| {
+        let (_, func) = swift
+            .split_once(&format!("public func {name}() async"))
+            .unwrap();
+        func.split_once("\n}\n").unwrap().0.to_owned()
+    };
+    assert!(function("cancellable").contains("cancelFunc: ffi_crate_name_rust_future_cancel_u32\n"));
+    assert!(function("notCancellable").contains("cancelFunc: nil\n"));
+}
+
+#[test]
+fn test_constants() {
+    let ci = ci_with_metadata(
+        "namespace test {};",
+        vec![uniffi_meta::ConstFnMetadata {
+            module_path: "crate_name".into(),
+            name: "app_version".into(),
+            return_type: uniffi_meta::Type::String,
+            checksum: None,
+            docstring: None,
+        }
+        .into()],
+    );
+    let swift = generate_bindings(&Default::default(), &ci).unwrap().library;
+    assert!(swift.contains("public let appVersion: String = {\n"));
+}
+
+#[test]
+fn test_non_zero() {
+    let non_zero_u32 = uniffi_meta::Type::NonZero {
+        inner_type: Box::new(uniffi_meta::Type::UInt32),
+    };
+    let ci = ci_with_metadata(
+        "namespace test {};",
+        vec![fn_metadata(
+            "next_id",
+            vec![uniffi_meta::FnParamMetadata::simple(
+                "id",
+                non_zero_u32.clone(),
+            )],
+            Some(non_zero_u32),
+        )
+        .into()],
+    );
+    // The plain integer is used, and zero is checked for when lifting.
+    let swift = generate_bindings(&Default::default(), &ci).unwrap().library;
+    assert!(contains_code(
+        &swift,
+        "public func nextId(id: UInt32) -> UInt32 {"
+    ));
+    assert!(swift.contains("throw UniffiInternalError.unexpectedZero\n"));
+}
+
+#[test]
+fn test_opaque_handle() {
+    const UDL: &str = r#"
+        namespace test {
+            opaque_handle echo_handle(opaque_handle handle);
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("public struct OpaqueHandle: Hashable {\n"));
+    assert!(swift.contains("public func echoHandle(handle: OpaqueHandle)  -> OpaqueHandle {\n"));
+}
+
+#[test]
+fn test_duration_ranges() {
+    const UDL: &str = r#"
+        namespace test {
+            duration_range shift(duration_range window, duration by);
+            duration_range_inclusive shift_closed(duration_range_inclusive window, duration by);
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("public func shift(window: Range<TimeInterval>, by: TimeInterval)  -> Range<TimeInterval> {\n"));
+    assert!(swift.contains("public func shiftClosed(window: ClosedRange<TimeInterval>, by: TimeInterval)  -> ClosedRange<TimeInterval> {\n"));
+    assert!(swift.contains("return start..<end\n"));
+    assert!(swift.contains("return start...end\n"));
+}
+
+#[test]
+fn test_keyword_escaping() {
+    const UDL: &str = r#"
+        namespace test {
+            Keywords make_keywords(i32 class);
+        };
+        dictionary Keywords {
+            i32 class;
+            i32 in;
+            i32 None;
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("public var `class`: Int32\n"));
+    assert!(swift.contains("public var `in`: Int32\n"));
+    assert!(swift.contains("public var none: Int32\n"));
+    assert!(swift.contains("self.`in` = `in`\n"));
+    assert!(swift.contains("public func makeKeywords(`class`: Int32)"));
+}
+
+#[test]
+fn test_record_wire_order() {
+    const UDL: &str = r#"
+        namespace test {};
+        dictionary Reordered {
+            [Order=1] string first;
+            [Order=0] u32 second;
+        };
+    "#;
+    // Fields are read and written in wire order, but constructed in declaration order.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(contains_code(
+        &swift,
+        "        let uniffiSecond = try FfiConverterUInt32.read(from: &buf)\n        \
+         let uniffiFirst = try FfiConverterString.read(from: &buf)\n"
+    ));
+    assert!(contains_code(
+        &swift,
+        "            first: uniffiFirst, \n            second: uniffiSecond\n"
+    ));
+    assert!(contains_code(
+        &swift,
+        "        FfiConverterUInt32.write(value.second, into: &buf)\n        \
+         FfiConverterString.write(value.first, into: &buf)\n"
+    ));
+}
+
+#[test]
+fn test_associated_constants() {
+    const UDL: &str = r#"
+        namespace test {};
+        interface Color {
+            /// Pure red
+            const u32 RED = 0xFF0000;
+            const boolean IS_OPAQUE = true;
+            const f64 SCALE = 1.5;
+            constructor();
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("public static let red: UInt32 = UInt32(0xff0000)\n"));
+    assert!(swift.contains("public static let isOpaque: Bool = true\n"));
+    assert!(swift.contains("public static let scale: Double = Double(1.5)\n"));
+}
+
+#[test]
+fn test_file_header_and_extra_imports() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async]
+            u32 wait();
+        };
+        interface Counter {
+            constructor();
+        };
+    "#;
+    // Extra imports which are already generated are only imported once, and only into the
+    // library.
+    const CONFIG: &str = r#"
+        file_header = "// Copyright Example Corp."
+        extra_imports = ["Foundation", "Extra", "Extra"]
+    "#;
+    let swift = generate_from_udl(UDL, CONFIG, generate_bindings);
+    let count = |source: &str, needle: &str| source.matches(needle).count();
+    let header = "// Copyright Example Corp.";
+    assert!(swift.library.starts_with("// Copyright Example Corp.\n"));
+    assert_eq!(count(&swift.library, header), 1);
+    assert_eq!(count(&swift.library, "\nimport Foundation\n"), 1);
+    assert_eq!(count(&swift.library, "\nimport Extra\n"), 1);
+    assert!(swift.header.starts_with("// Copyright Example Corp.\n"));
+    assert_eq!(count(&swift.header, header), 1);
+    assert!(!swift.header.contains("import Extra"));
+}
+
+#[test]
+fn test_hash_header() {
+    const UDL: &str = r#"
+        namespace test {
+            u32 add(u32 a, u32 b);
+        };
+    "#;
+    let hash = ComponentInterface::from_webidl(UDL, "crate_name")
+        .unwrap()
+        .stable_hash();
+    const CONFIG: &str = r#"
+        file_header = "// Copyright Example Corp."
+        hash_header = true
+    "#;
+    let swift = generate_from_udl(UDL, CONFIG, generate_bindings);
+    let plain = generate_from_udl(UDL, "", generate_bindings);
+    // Every file gets the header after `file_header`, and is otherwise the same as without it.
+    let check = |with: &str, without: &str| {
+        let expected = format!(
+            "// Copyright Example Corp.\n\
+             // Generated by uniffi-bindgen {} from an interface with hash {hash:016x}\n{without}",
+            env!("CARGO_PKG_VERSION"),
+        );
+        assert_eq!(with, expected);
+    };
+    check(&swift.library, &plain.library);
+    check(&swift.header, &plain.header);
+    check(
+        swift.modulemap.as_deref().unwrap(),
+        plain.modulemap.as_deref().unwrap(),
+    );
+}
+
+#[test]
+fn test_retry() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async, Retry=3, Throws=NetworkError]
+            string fetch();
+        };
+        [Error]
+        enum NetworkError {
+            [Retryable] "Timeout",
+            [Retryable] "Unavailable",
+            "NotFound",
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(contains_code(
+        &swift,
+        "    return try await uniffiRetryAsync(\n        retries: 3,\n        \
+         isRetryable: { error in\n            \
+         switch error as? NetworkError {\n            \
+         case .Timeout?, .Unavailable?: return true\n"
+    ));
+}
+
+#[test]
+fn test_case_iterable() {
+    const UDL: &str = r#"
+        namespace test {};
+        enum Shape { "Circle", "Square" };
+        [Enum]
+        interface Event {
+            Click(u32 x, u32 y);
+            Close();
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("extension Shape: CaseIterable {}"));
+    assert!(!swift.contains("extension Event: CaseIterable"));
+}
+
+#[test]
+fn test_codable() {
+    const UDL: &str = r#"
+        namespace test {};
+        enum Shape { "Circle", "Square" };
+        [Enum]
+        interface Event {
+            Click(u32 x, u32 y);
+            Close();
+        };
+        dictionary Point { i32 x; i32 y; };
+        dictionary Drawing {
+            string? title;
+            sequence<Point> points;
+            record<string, Shape> shapes;
+            timestamp created;
+        };
+        dictionary Owned { Canvas canvas; };
+        dictionary Logged { sequence<Event> events; };
+        interface Canvas {};
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(!swift.contains("Codable"));
+
+    let swift = generate_from_udl(UDL, "generate_codable = true", generate_bindings).library;
+    assert!(swift.contains("extension Shape: Codable {}"));
+    assert!(swift.contains("extension Point: Codable {}"));
+    assert!(swift.contains("extension Drawing: Codable {}"));
+    // Objects can't be encoded, and neither can enums with fields or anything containing
+    // them.
+    assert!(!swift.contains("extension Canvas: Codable"));
+    assert!(!swift.contains("extension Owned: Codable"));
+    assert!(!swift.contains("extension Event: Codable"));
+    assert!(!swift.contains("extension Logged: Codable"));
+}
+
+#[test]
+fn test_target_functions() {
+    const UDL: &str = r#"
+        namespace test {
+            u32 everywhere();
+            [Target="ios"]
+            void request_review(string prompt);
+            [Target="android"]
+            void show_toast(string message);
+        };
+    "#;
+    // The functions for Apple platforms are wrapped in their compilation condition, and the
+    // others are left out.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("public func everywhere() "));
+    let start = swift
+        .find("#if os(iOS)\npublic func requestReview(prompt: String) ")
+        .unwrap();
+    let end = start + swift[start..].find("#endif").unwrap();
+    assert!(swift[start..end].contains("uniffi_crate_name_fn_func_request_review("));
+    assert!(!swift.contains("showToast"));
+
+    let config = "target_conditions = { android = \"false\" }";
+    let swift = generate_from_udl(UDL, config, generate_bindings).library;
+    assert!(swift.contains("#if false\npublic func showToast("));
+}
+
+#[test]
+fn test_observable_interfaces() {
+    const UDL: &str = r#"
+        namespace test {};
+        callback interface Listener {
+            void on_tick(u32 count);
+        };
+        [Observable]
+        interface Clock {
+            void add_observer(Listener listener);
+            void remove_observer(Listener listener);
+        };
+        interface Plain {};
+    "#;
+    // Without a token from `add_observer`, the observer itself is removed.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(contains_code(
+        &swift,
+        "    public func observe() -> AsyncStream<UInt32> {\n        \
+         return AsyncStream { continuation in\n            \
+         let observer = UniffiClockObserver(continuation)\n            \
+         self.addObserver(listener: observer)\n            \
+         continuation.onTermination = { _ in\n                \
+         self.removeObserver(listener: observer)\n            }\n        }\n    }\n"
+    ));
+    assert!(swift.contains("fileprivate final class UniffiClockObserver: Listener {\n"));
+    assert!(contains_code(
+        &swift,
+        "    func onTick(count: UInt32) {\n        continuation.yield(count)\n    }\n"
+    ));
+    assert_eq!(swift.matches("func observe()").count(), 1);
+}
+
+#[test]
+fn test_extension_functions() {
+    const UDL: &str = r#"
+        namespace test {
+            [Extension=Client]
+            string process(Client client, u32 count);
+            [Extension=Client, Async]
+            void upload(Client client, bytes data);
+            [Extension=Point]
+            double length(Point point);
+        };
+        interface Client {};
+        dictionary Point { double x; double y; };
+    "#;
+    // The receiver isn't declared, and is lowered as `self`.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(contains_code(
+        &swift,
+        "extension Client {\n\
+         public func process(count: UInt32)  -> String {\n    \
+         return try!  FfiConverterString.lift(\n        try! rustCall() {\n    \
+         uniffi_crate_name_fn_func_process(\n        \
+         FfiConverterTypeClient.lower(self),\n        \
+         FfiConverterUInt32.lower(count),$0)\n}\n    )\n}\n}\n"
+    ));
+    assert!(swift.contains("extension Client {\npublic func upload(data: Data) async  {"));
+    assert!(swift.contains("extension Point {\npublic func length()  -> Double {"));
+}
+
+#[test]
+fn test_variadic_arguments() {
+    const UDL: &str = r#"
+        namespace test {
+            string join_words(string separator, [Variadic] sequence<string> words);
+            [Async]
+            u32 sum([Variadic] sequence<u32> values);
+        };
+        interface Counter {
+            constructor([Variadic] sequence<u32> steps);
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("public func joinWords(separator: String, _ words: String...)"));
+    assert!(swift.contains("FfiConverterSequenceString.lower(words)"));
+    assert!(swift.contains("public convenience init(_ steps: UInt32...)"));
+}
+
+#[test]
+fn test_argument_labels() {
+    const UDL: &str = r#"
+        namespace test {
+            void enter(string in);
+            u32 add(u32 left, u32 right);
+            void listen(Listener listener);
+        };
+        interface Counter {
+            constructor(u32 start);
+            void step(u32 by, u32 times);
+        };
+        callback interface Listener {
+            void on_event(string class, u32 count);
+        };
+    "#;
+    // Keywords are escaped with backticks, and every argument is labelled by default.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("public func enter(`in`: String)"));
+    assert!(swift.contains("public func add(left: UInt32, right: UInt32)"));
+    assert!(swift.contains("func onEvent(`class`: String, count: UInt32)"));
+    assert!(swift.contains("class:  try FfiConverterString.read(from: &reader)"));
+
+    // With `omit_first_argument_label`, the first argument is declared with `_`, except for
+    // initializers.
+    let config = "omit_first_argument_label = true";
+    let swift = generate_from_udl(UDL, config, generate_bindings).library;
+    assert!(swift.contains("public func enter(_ `in`: String)"));
+    assert!(swift.contains("public func add(_ left: UInt32, right: UInt32)"));
+    assert!(swift.contains("public func step(_ by: UInt32, times: UInt32)"));
+    assert!(swift.contains("public convenience init(start: UInt32)"));
+    assert!(swift.contains("func onEvent(_ `class`: String, count: UInt32)"));
+    assert!(!swift.contains("class:  try FfiConverterString.read(from: &reader)"));
+    assert!(swift.contains("count:  try FfiConverterUInt32.read(from: &reader)"));
+}
+
+#[test]
+fn test_main_thread() {
+    const UDL: &str = r#"
+        namespace test {
+            [MainThread]
+            void update_ui(string text);
+            [MainThread]
+            u32 read_ui();
+            u32 anywhere();
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    let guard = "dispatchPrecondition(condition: .onQueue(.main))";
+    assert!(contains_code(
+        &swift,
+        &format!("public func updateUi(text: String)  {{\n    {guard}\n")
+    ));
+    assert!(contains_code(
+        &swift,
+        &format!("public func readUi()  -> UInt32 {{\n    {guard}\n")
+    ));
+    assert_eq!(swift.matches(guard).count(), 2);
+    assert!(contains_code(
+        &swift,
+        " * - Precondition: Must be called from the main thread.\n */\npublic func updateUi"
+    ));
+}
+
+#[test]
+fn test_derive_display() {
+    const UDL: &str = r#"
+        namespace test {};
+        dictionary Point {
+            i32 x;
+            i32 y;
+        };
+        dictionary Shape {
+            Point origin;
+            string? label;
+            bytes data;
+            bytes? thumbnail;
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(!swift.contains("CustomStringConvertible"));
+    let swift = generate_from_udl(UDL, "derive_display = true", generate_bindings).library;
+    assert!(swift.contains("extension Point: CustomStringConvertible {"));
+    assert!(swift.contains("return \"Point(x: \\(x), y: \\(y))\""));
+    assert!(swift.contains(
+        "return \"Shape(origin: \\(origin), label: \\(label.map { String(describing: $0) } ?? \"nil\"), data: <\\(data.count) bytes>, thumbnail: \\(thumbnail.map { \"<\\($0.count) bytes>\" } ?? \"nil\"))\""
+    ));
+}
+
+#[test]
+fn test_since() {
+    const UDL: &str = r#"
+        namespace test {};
+        /// A point.
+        /// @since 1.2.0
+        dictionary Point {
+            i32 x;
+        };
+        /// @since 1.3.0
+        interface Counter {
+            constructor();
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(contains_code(
+        &swift,
+        "/**\n * A point.\n *\n * - Since: 1.2.0\n */\npublic struct Point"
+    ));
+    assert!(contains_code(
+        &swift,
+        "/**\n * - Since: 1.3.0\n */\npublic class Counter"
+    ));
+}
+
+#[test]
+fn test_shared_composite_types() {
+    const UDL: &str = r#"
+        namespace test {
+            u32 count(record<string, sequence<u32>> groups);
+            record<string, sequence<u32>> merge(record<string, sequence<u32>> groups);
+            sequence<string> names();
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert_eq!(
+        swift
+            .matches("fileprivate struct FfiConverterDictionaryStringSequenceUInt32")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_enum_default_variant() {
+    const UDL: &str = r#"
+        namespace test {};
+        [NonExhaustive]
+        enum Color { "Red", [Default] "Unknown" };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("default: return .unknown\n"));
+}
+
+#[test]
+fn test_enum_unknown_variant() {
+    const UDL: &str = r#"
+        namespace test {};
+        [NonExhaustive]
+        enum Color { "Red", "Green" };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("case unknown(discriminant: Int32)\n"));
+    assert!(swift.contains("default: return .unknown(discriminant: value)\n"));
+    assert!(!swift.contains("extension Color: CaseIterable {}"));
+}
+
+#[test]
+fn test_enum_discriminant_encoding() {
+    const UDL: &str = r#"
+        namespace test {};
+        [Discriminant=u16_le]
+        enum Opcode { "Read", "Write" };
+        [Enum, Discriminant=u8]
+        interface Shape {
+            Circle(double radius);
+            Point();
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("return try lift(Int32((readInt(&buf) as UInt16).byteSwapped))"));
+    assert!(swift.contains("writeInt(&buf, UInt16(truncatingIfNeeded: lower(value)).byteSwapped)"));
+    assert!(swift.contains("let variant: Int32 = try Int32(readInt(&buf) as UInt8)"));
+    assert!(swift.contains("writeInt(&buf, UInt8(truncatingIfNeeded: 2))"));
+}
+
+#[test]
+fn test_object_identity_equality() {
+    const UDL: &str = r#"
+        namespace test {};
+        interface Counter {
+            constructor();
+        };
+        [Traits=(Eq)]
+        interface Point {
+            constructor();
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(contains_code(
+        &swift,
+        "public class Counter:\n    Equatable,\n    Hashable,"
+    ));
+    assert!(swift.contains("return lhs.uniffiHandleId() == other.uniffiHandleId()"));
+    assert_eq!(swift.matches("func uniffiHandleId()").count(), 1);
+}
+
+#[test]
+fn test_chunked_sequences() {
+    const UDL: &str = r#"
+        namespace test {
+            [Chunked]
+            sequence<string> get_names();
+            sequence<string> get_names_unchunked();
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("try! rustCall { ffi_crate_name_chunked_sequence_free(handle, $0) }"));
+    assert!(contains_code(
+        &swift,
+        "FfiConverterSequenceString.self\n    )"
+    ));
+}
+
+#[test]
+fn test_with_warnings() {
+    const UDL: &str = r#"
+        namespace test {
+            [WithWarnings]
+            u32 parse(string input);
+            [WithWarnings, Throws=Error]
+            string load();
+        };
+        [Error]
+        enum Error { "Oops" };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("public func load() throws  -> WithWarnings<String> {"));
+    assert!(contains_code(&swift, "FfiConverterUInt32.self\n    )"));
+}
+
+#[test]
+fn test_segmented() {
+    const UDL: &str = r#"
+        namespace test {
+            [Segmented]
+            sequence<bytes> split(bytes data, u32 size);
+            [Throws=Error]
+            u64 total_len([Segmented] sequence<bytes> segments);
+        };
+        [Error]
+        enum Error { "Oops" };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("return try!  uniffiLiftByteSegments(\n"));
+    assert!(swift.contains("uniffiLowerByteSegments(segments),$0)"));
+}
+
+#[test]
+fn test_has_default() {
+    const UDL: &str = r#"
+        namespace test {};
+        [HasDefault]
+        dictionary Settings {
+            string name;
+            u32 retries;
+        };
+        dictionary Point {
+            i32 x;
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("public static func `default`() -> Settings {"));
+    assert!(swift.contains("return try! FfiConverterTypeSettings.lift(try! rustCall {"));
+}
+
+#[test]
+fn test_progress() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async]
+            u32 count_steps(u32 steps, [Progress] ProgressListener listener);
+        };
+        callback interface ProgressListener {
+            void on_progress(u32 step);
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains(
+        "public func countStepsWithProgress(steps: UInt32, listener: @escaping (UInt32) -> Void) async  -> UInt32 {"
+    ));
+    assert!(swift.contains(
+        "await countSteps(steps: steps, listener: UniffiCountStepsProgress(continuation))"
+    ));
+    assert!(swift.contains("fileprivate final class UniffiCountStepsProgress: ProgressListener {"));
+}
+
+#[test]
+fn test_enum_methods() {
+    const UDL: &str = r#"
+        namespace test {};
+        [Enum]
+        interface Shape {
+            Circle(double radius);
+            Point();
+        };
+    "#;
+    let ci = ci_with_metadata(
+        UDL,
+        vec![method_metadata("Shape", "area", Some(uniffi_meta::Type::Float64)).into()],
+    );
+    let swift = generate_bindings(&Default::default(), &ci).unwrap().library;
+    assert!(contains_code(
+        &swift,
+        "extension Shape { public func area() -> Double {"
+    ));
+    assert!(swift
+        .contains("uniffi_crate_name_fn_method_shape_area(FfiConverterTypeShape.lower(self), $0"));
+}
+
+#[test]
+fn test_consuming_methods() {
+    const UDL: &str = r#"
+        namespace test {};
+        interface Builder {
+            constructor();
+            void append(string part);
+        };
+    "#;
+    let ci = ci_with_metadata(
+        UDL,
+        vec![uniffi_meta::MethodMetadata {
+            takes_self_by_value: true,
+            ..method_metadata("Builder", "build", Some(uniffi_meta::Type::String))
+        }
+        .into()],
+    );
+    let swift = generate_bindings(&Default::default(), &ci).unwrap().library;
+    assert!(swift.contains("uniffi_crate_name_fn_method_builder_build(self.uniffiConsumePointer()"));
+    assert!(swift
+        .contains("precondition(!uniffiConsumed, \"Builder object has already been consumed\")"));
+}
+
+#[test]
+fn test_cancellable() {
+    const UDL: &str = r#"
+        namespace test {
+            [Async, Cancellable]
+            u32 download(string url);
+            [Async]
+            u32 upload(string url);
+        };
+    "#;
+    // The handle type is generated, along with a variant of the function returning it.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("public final class UniffiCancellableHandle<T> {\n"));
+    assert!(swift.contains(
+        "public func downloadCancellable(url: String) -> UniffiCancellableHandle<UInt32> {\n"
+    ));
+    assert!(swift.contains("cancelFunc: ffi_crate_name_rust_future_cancel_u32,\n"));
+    assert!(!swift.contains("uploadCancellable"));
+}
+
+#[test]
+fn test_default_on_none() {
+    const UDL: &str = r#"
+        namespace test {
+            [DefaultOnNone="7"]
+            u32? find_count(string key);
+            [DefaultOnNone="unknown"]
+            string? find_name();
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(contains_code(&swift, "public func findCountOrDefault(key: String)  -> UInt32 {\n    return try!  findCount(key: key) ?? UInt32(7)\n}"));
+}
+
+#[test]
+fn test_ownership_docs() {
+    const UDL: &str = r#"
+        namespace test {};
+        /// A node in a tree.
+        interface Node {
+            /// Create a root node.
+            constructor(string name);
+            /// The parent of this node.
+            Node? parent();
+        };
+    "#;
+    let swift = generate_from_udl(UDL, "ownership_docs = true", generate_bindings).library;
+    assert!(swift.contains("/**
+     * Create a root node.
+     *
+     * The new instance owns a reference to the Rust object, which is released when the instance is deinitialized.
+     */
+    public convenience init(name: String)"));
+    assert!(swift.contains("/**
+     * The parent of this node.
+     *
+     * Rust borrows this object for the duration of the call. Returned objects own a reference to their Rust object, which is released when the instance is deinitialized.
+     */
+    public func parent()"));
+
+    // Without the config, only the docstrings are there.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(!swift.contains("owns a reference"));
+}
+
+#[test]
+fn test_out_params() {
+    let out_param = |name: &str, ty| uniffi_meta::FnParamMetadata {
+        out: true,
+        ..uniffi_meta::FnParamMetadata::simple(name, ty)
+    };
+    let ci = ci_with_metadata(
+        "namespace test {};",
+        vec![
+            fn_metadata(
+                "divide",
+                vec![
+                    uniffi_meta::FnParamMetadata::simple("dividend", uniffi_meta::Type::UInt32),
+                    uniffi_meta::FnParamMetadata::simple("divisor", uniffi_meta::Type::UInt32),
+                    out_param("remainder", uniffi_meta::Type::UInt32),
+                ],
+                Some(uniffi_meta::Type::UInt32),
+            )
+            .into(),
+            fn_metadata(
+                "trim",
+                vec![out_param("text", uniffi_meta::Type::String)],
+                None,
+            )
+            .into(),
+        ],
+    );
+    let swift = generate_bindings(&Default::default(), &ci).unwrap().library;
+    // Several outputs are returned as a tuple, a single one is returned directly.
+    assert!(contains_code(
+        &swift,
+        "public func divide(dividend: UInt32, divisor: UInt32, remainder: UInt32) -> (value: UInt32, remainder: UInt32) {"
+    ));
+    assert!(swift.contains("remainder: try FfiConverterUInt32.read(from: &reader)\n"));
+    assert!(contains_code(
+        &swift,
+        "public func trim(text: String) -> String {"
+    ));
+}
+
+#[test]
+fn test_callback_interface_gone() {
+    const UDL: &str = r#"
+        namespace test {
+            void register(Listener listener);
+        };
+        callback interface Listener {
+            void notify();
+        };
+    "#;
+    // A handle which isn't in the handle map is reported to Rust as its own code.
+    let swift = generate_from_udl(UDL, "", generate_bindings).library;
+    assert!(swift.contains("private let UNIFFI_CALLBACK_INTERFACE_GONE: Int32 = 3"));
+    assert!(contains_code(
+        &swift,
+        "guard let cb = FfiConverterCallbackInterfaceListener.handleMap.get(handle: handle) else {
+            return UNIFFI_CALLBACK_INTERFACE_GONE
+        }"
+    ));
+}
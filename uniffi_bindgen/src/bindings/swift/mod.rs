@@ -32,7 +32,7 @@
 use std::process::Command;
 
 use anyhow::Result;
-use camino::Utf8Path;
+use camino::{Utf8Path, Utf8PathBuf};
 use fs_err as fs;
 
 pub mod gen_swift;
@@ -46,11 +46,11 @@ pub use test::{run_script, run_test};
 ///
 pub struct Bindings {
     /// The contents of the generated `.swift` file, as a string.
-    library: String,
+    pub(crate) library: String,
     /// The contents of the generated `.h` file, as a string.
-    header: String,
+    pub(crate) header: String,
     /// The contents of the generated `.modulemap` file, as a string.
-    modulemap: Option<String>,
+    pub(crate) modulemap: Option<String>,
 }
 
 /// Write UniFFI component bindings for Swift as files on disk.
@@ -64,21 +64,27 @@ pub fn write_bindings(
     out_dir: &Utf8Path,
     try_format_code: bool,
 ) -> Result<()> {
-    let Bindings {
-        header,
-        library,
-        modulemap,
-    } = generate_bindings(config, ci)?;
+    write_bindings_with_formatter(config, ci, out_dir, try_format_code, None)
+}
 
-    let source_file = out_dir.join(format!("{}.swift", config.module_name()));
-    fs::write(&source_file, library)?;
+/// Like [`write_bindings`], but passes the generated source through `formatter` before it's
+/// written.
+pub fn write_bindings_with_formatter(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+    formatter: Option<&super::Formatter>,
+) -> Result<()> {
+    let mut files = render_bindings(config, ci)?.into_iter();
 
-    let header_file = out_dir.join(config.header_filename());
-    fs::write(header_file, header)?;
+    // The Swift source always comes first, and it's the only file which is formatted.
+    let (source_path, library) = files.next().expect("Swift bindings have a source file");
+    let source_file = out_dir.join(source_path);
+    super::write_source_file(&source_file, library, formatter)?;
 
-    if let Some(modulemap) = modulemap {
-        let modulemap_file = out_dir.join(config.modulemap_filename());
-        fs::write(modulemap_file, modulemap)?;
+    for (path, contents) in files {
+        fs::write(out_dir.join(path), contents)?;
     }
 
     if try_format_code {
@@ -95,3 +101,27 @@ pub fn write_bindings(
 
     Ok(())
 }
+
+/// Render the Swift bindings, keyed by their paths relative to the output directory.
+///
+/// The `.swift` source file comes first, followed by the header and the modulemap, if one is
+/// generated.
+pub fn render_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<Vec<(Utf8PathBuf, String)>> {
+    let Bindings {
+        header,
+        library,
+        modulemap,
+    } = generate_bindings(config, ci)?;
+
+    let mut files = vec![
+        (format!("{}.swift", config.module_name()).into(), library),
+        (config.header_filename().into(), header),
+    ];
+    if let Some(modulemap) = modulemap {
+        files.push((config.modulemap_filename().into(), modulemap));
+    }
+    Ok(files)
+}
@@ -7,8 +7,15 @@ fileprivate func uniffiRustCallAsync<F, T>(
     completeFunc: (UnsafeMutableRawPointer, UnsafeMutablePointer<RustCallStatus>) -> F,
     freeFunc: (UnsafeMutableRawPointer) -> (),
     liftFunc: (F) throws -> T,
-    errorHandler: ((RustBuffer) throws -> Error)?
+    errorHandler: ((RustBuffer) throws -> Error)?,
+    cancelFunc: ((UnsafeMutableRawPointer) -> ())?
 ) async throws -> T {
+    // Only functions which can throw get a `cancelFunc`, since cancelling them throws
+    // `CancellationError`.  Other functions always run to completion, even if their task is
+    // cancelled.
+    if cancelFunc != nil {
+        try Task.checkCancellation()
+    }
     // Make sure to call uniffiEnsureInitialized() since future creation doesn't have a
     // RustCallStatus param, so doesn't use makeRustCall()
     uniffiEnsureInitialized()
@@ -18,8 +25,14 @@ fileprivate func uniffiRustCallAsync<F, T>(
     }
     var pollResult: Int8;
     repeat {
-        pollResult = await withUnsafeContinuation {
-            pollFunc(rustFuture, uniffiFutureContinuationCallback, ContinuationHolder($0).toOpaque())
+        // If the task is cancelled, cancelling the Rust future wakes us up with a ready poll
+        // result, then `completeFunc` reports the cancellation, which throws `CancellationError`.
+        pollResult = await withTaskCancellationHandler {
+            await withUnsafeContinuation {
+                pollFunc(rustFuture, uniffiFutureContinuationCallback, ContinuationHolder($0).toOpaque())
+            }
+        } onCancel: {
+            cancelFunc?(rustFuture)
         }
     } while pollResult != UNIFFI_RUST_FUTURE_POLL_READY
 
@@ -29,6 +42,94 @@ fileprivate func uniffiRustCallAsync<F, T>(
     ))
 }
 
+{%- if ci.has_cancellable_fns() %}
+/// A call to an async function marked with `[Cancellable]`.
+///
+/// `value()` waits for the call to complete, and can only be called once.  Calling `cancel()`, from
+/// any thread, cancels the Rust future, which makes `value()` throw `CancellationError`.
+public final class UniffiCancellableHandle<T> {
+    private let rustFuture: UnsafeMutableRawPointer
+    private let pollFunc: (UnsafeMutableRawPointer, @escaping UniFfiRustFutureContinuation, UnsafeMutableRawPointer) -> ()
+    private let completeFunc: (UnsafeMutableRawPointer) throws -> T
+    private let freeFunc: (UnsafeMutableRawPointer) -> ()
+    private let cancelFunc: (UnsafeMutableRawPointer) -> ()
+    // Guards the Rust future, so that it's never cancelled after it's been freed.
+    private let lock = NSLock()
+    private var awaited = false
+    private var cancelled = false
+    private var freed = false
+
+    fileprivate init(
+        rustFuture: UnsafeMutableRawPointer,
+        pollFunc: @escaping (UnsafeMutableRawPointer, @escaping UniFfiRustFutureContinuation, UnsafeMutableRawPointer) -> (),
+        completeFunc: @escaping (UnsafeMutableRawPointer) throws -> T,
+        freeFunc: @escaping (UnsafeMutableRawPointer) -> (),
+        cancelFunc: @escaping (UnsafeMutableRawPointer) -> ()
+    ) {
+        self.rustFuture = rustFuture
+        self.pollFunc = pollFunc
+        self.completeFunc = completeFunc
+        self.freeFunc = freeFunc
+        self.cancelFunc = cancelFunc
+    }
+
+    public func cancel() {
+        lock.lock()
+        defer { lock.unlock() }
+        if !cancelled && !freed {
+            cancelled = true
+            cancelFunc(rustFuture)
+        }
+    }
+
+    public func value() async throws -> T {
+        lock.lock()
+        precondition(!awaited, "UniffiCancellableHandle can only be awaited once")
+        awaited = true
+        lock.unlock()
+        defer {
+            lock.lock()
+            freed = true
+            freeFunc(rustFuture)
+            lock.unlock()
+        }
+        var pollResult: Int8;
+        repeat {
+            pollResult = await withUnsafeContinuation {
+                pollFunc(rustFuture, uniffiFutureContinuationCallback, ContinuationHolder($0).toOpaque())
+            }
+        } while pollResult != UNIFFI_RUST_FUTURE_POLL_READY
+
+        // A cancelled future completes with the `CALL_CANCELLED` status, which throws
+        // `CancellationError`.
+        return try completeFunc(rustFuture)
+    }
+}
+
+fileprivate func uniffiRustCallCancellable<F, T>(
+    rustFutureFunc: () -> UnsafeMutableRawPointer,
+    pollFunc: @escaping (UnsafeMutableRawPointer, @escaping UniFfiRustFutureContinuation, UnsafeMutableRawPointer) -> (),
+    completeFunc: @escaping (UnsafeMutableRawPointer, UnsafeMutablePointer<RustCallStatus>) -> F,
+    freeFunc: @escaping (UnsafeMutableRawPointer) -> (),
+    cancelFunc: @escaping (UnsafeMutableRawPointer) -> (),
+    liftFunc: @escaping (F) throws -> T,
+    errorHandler: ((RustBuffer) throws -> Error)?
+) -> UniffiCancellableHandle<T> {
+    // Make sure to call uniffiEnsureInitialized() since future creation doesn't have a
+    // RustCallStatus param, so doesn't use makeRustCall()
+    uniffiEnsureInitialized()
+    return UniffiCancellableHandle(
+        rustFuture: rustFutureFunc(),
+        pollFunc: pollFunc,
+        completeFunc: { rustFuture in
+            try liftFunc(makeRustCall({ completeFunc(rustFuture, $0) }, errorHandler: errorHandler))
+        },
+        freeFunc: freeFunc,
+        cancelFunc: cancelFunc
+    )
+}
+{%- endif %}
+
 // Callback handlers for an async calls.  These are invoked by Rust when the future is ready.  They
 // lift the return value or error and resume the suspended function.
 fileprivate func uniffiFutureContinuationCallback(ptr: UnsafeMutableRawPointer, pollResult: Int8) {
@@ -56,3 +157,47 @@ fileprivate class ContinuationHolder {
         return Unmanaged<ContinuationHolder>.fromOpaque(ptr).takeRetainedValue()
     }
 }
+
+// The delay before the first retry of a function marked with `[Retry=N]`, which doubles after
+// each retry.
+private let UNIFFI_RETRY_INITIAL_DELAY_NANOS: UInt64 = 100_000_000
+
+fileprivate func uniffiRetryAsync<T>(
+    retries: Int,
+    isRetryable: (Error) -> Bool,
+    _ call: () async throws -> T
+) async throws -> T {
+    var delayNanos = UNIFFI_RETRY_INITIAL_DELAY_NANOS
+    for _ in 0..<retries {
+        do {
+            return try await call()
+        } catch let error where isRetryable(error) {
+            try await Task.sleep(nanoseconds: delayNanos)
+            delayNanos *= 2
+        }
+    }
+    return try await call()
+}
+{%- if ci.has_progress_fns() %}
+
+// Calls an async function with a `[Progress]` argument.  The listener which `call` passes to the
+// function yields its events to a stream, which this task reads to pass them to `onProgress`
+// rather than passing them on the thread Rust reports them from.  All of the events have been
+// passed when this returns.
+fileprivate func uniffiCallWithProgress<T, Event>(
+    _ onProgress: @escaping (Event) -> Void,
+    _ call: @escaping (AsyncStream<Event>.Continuation) async throws -> T
+) async throws -> T {
+    var streamContinuation: AsyncStream<Event>.Continuation? = nil
+    let events = AsyncStream<Event> { streamContinuation = $0 }
+    let continuation = streamContinuation!
+    async let result: T = {
+        defer { continuation.finish() }
+        return try await call(continuation)
+    }()
+    for await event in events {
+        onProgress(event)
+    }
+    return try await result
+}
+{%- endif %}
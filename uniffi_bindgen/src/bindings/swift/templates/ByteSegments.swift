@@ -0,0 +1,31 @@
+// Pass byte segments to Rust without concatenating them, by copying each one onto a handle from
+// Rust.  Rust takes over the handle when it's passed as the argument.
+private func uniffiLowerByteSegments(_ segments: [Data]) -> UInt64 {
+    let handle = try! rustCall { {{ ci.ffi_byte_segments_new().name() }}($0) }
+    for segment in segments {
+        segment.withUnsafeBytes { ptr in
+            try! rustCall {
+                {{ ci.ffi_byte_segments_push().name() }}(
+                    handle,
+                    ForeignBytes(bufferPointer: ptr.bindMemory(to: UInt8.self)),
+                    $0
+                )
+            }
+        }
+    }
+    return handle
+}
+
+// Take the byte segments Rust returns, from the handle returned by the FFI function.  Each segment
+// is returned in its own buffer.
+private func uniffiLiftByteSegments(_ handle: UInt64) -> [Data] {
+    defer {
+        try! rustCall { {{ ci.ffi_byte_segments_free().name() }}(handle, $0) }
+    }
+    let count = try! rustCall { {{ ci.ffi_byte_segments_len().name() }}(handle, $0) }
+    return (0..<count).map { _ in
+        let buf = try! rustCall { {{ ci.ffi_byte_segments_next().name() }}(handle, $0) }
+        defer { buf.deallocate() }
+        return buf.data.map { Data(bytes: $0, count: Int(buf.len)) } ?? Data()
+    }
+}
@@ -17,7 +17,7 @@ fileprivate let {{ callback_handler }} : ForeignCallback =
         func makeCall() throws -> Int32 {
             let result = {% if meth.throws() %} try{% endif %} swiftCallbackInterface.{{ meth.name()|fn_name }}(
                     {% for arg in meth.arguments() -%}
-                    {% if !config.omit_argument_labels() %}{{ arg.name()|var_name }}: {% endif %} try {{ arg|read_fn }}(from: &reader)
+                    {% call swift::arg_label_call(arg, loop.first && config.omit_first_argument_label()) %} try {{ arg|read_fn }}(from: &reader)
                     {%- if !loop.last %}, {% endif %}
                     {% endfor -%}
                 )
@@ -30,7 +30,7 @@ fileprivate let {{ callback_handler }} : ForeignCallback =
         func makeCall() throws -> Int32 {
             {% if meth.throws() %}try {% endif %}swiftCallbackInterface.{{ meth.name()|fn_name }}(
                     {% for arg in meth.arguments() -%}
-                    {% if !config.omit_argument_labels() %}{{ arg.name()|var_name }}: {% endif %} try {{ arg|read_fn }}(from: &reader)
+                    {% call swift::arg_label_call(arg, loop.first && config.omit_first_argument_label()) %} try {{ arg|read_fn }}(from: &reader)
                     {%- if !loop.last %}, {% endif %}
                     {% endfor -%}
                 )
@@ -62,9 +62,9 @@ fileprivate let {{ callback_handler }} : ForeignCallback =
         {% for meth in methods.iter() -%}
         {% let method_name = format!("invoke_{}", meth.name())|fn_name -%}
         case {{ loop.index }}:
+            // The handle is missing if it's already been freed, which Rust reports as `CallbackInterfaceGone`.
             guard let cb = {{ ffi_converter_name }}.handleMap.get(handle: handle) else {
-                out_buf.pointee = {{ Type::String.borrow()|lower_fn }}("No callback in handlemap; this is a Uniffi bug")
-                return UNIFFI_CALLBACK_UNEXPECTED_ERROR
+                return UNIFFI_CALLBACK_INTERFACE_GONE
             }
             do {
                 return try {{ method_name }}(cb, argsData, argsLen, out_buf)
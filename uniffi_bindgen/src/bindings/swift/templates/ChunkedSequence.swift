@@ -0,0 +1,20 @@
+// Reassemble a sequence that Rust returns in chunks, from the handle returned by the FFI function.
+// An empty chunk means the sequence is exhausted.
+private func uniffiLiftChunkedSequence<Converter: FfiConverterRustBuffer, Element>(
+    _ handle: UInt64,
+    _ converter: Converter.Type
+) throws -> [Element] where Converter.SwiftType == [Element] {
+    defer {
+        try! rustCall { {{ ci.ffi_chunked_sequence_free().name() }}(handle, $0) }
+    }
+    var items: [Element] = []
+    while true {
+        let chunk = try converter.lift(
+            try rustCall { {{ ci.ffi_chunked_sequence_next().name() }}(handle, $0) }
+        )
+        if chunk.isEmpty {
+            return items
+        }
+        items.append(contentsOf: chunk)
+    }
+}
@@ -0,0 +1,9 @@
+{%- match func.return_type() %}
+{%- when Some with (return_type) %}
+{%- call swift::docstring(func, 0) %}
+// Swift initializes global constants lazily, the first time they're accessed.
+public let {{ func.name()|var_name }}: {{ return_type|type_name }} = {
+    return {{ return_type|lift_fn }}({% call swift::to_ffi_call(func) %})
+}()
+{%- when None %}
+{%- endmatch %}
@@ -1,4 +1,4 @@
-{%- let ffi_type_name=builtin|ffi_type|ffi_type_name %}
+{%- let ffi_type_name=builtin|ffi_type(ci)|ffi_type_name %}
 {%- match config.custom_types.get(name.as_str())  %}
 {%- when None %}
 {#- No config, just forward all methods to our builtin type #}
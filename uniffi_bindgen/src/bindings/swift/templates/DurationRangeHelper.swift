@@ -0,0 +1,21 @@
+fileprivate struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
+    typealias SwiftType = {{ type_name }}
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
+        let start = try FfiConverterDuration.read(from: &buf)
+        let end = try FfiConverterDuration.read(from: &buf)
+        let inclusive: Int8 = try readInt(&buf)
+        // Swift ranges can't have their bounds out of order, so empty Rust ranges like `5..1` can't
+        // be lifted.
+        guard inclusive == {% if inclusive %}1{% else %}0{% endif %}, start <= end else {
+            throw UniffiInternalError.unexpectedRange
+        }
+        return start{% if inclusive %}...{% else %}..<{% endif %}end
+    }
+
+    public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
+        FfiConverterDuration.write(value.lowerBound, into: &buf)
+        FfiConverterDuration.write(value.upperBound, into: &buf)
+        writeInt(&buf, Int8({% if inclusive %}1{% else %}0{% endif %}))
+    }
+}
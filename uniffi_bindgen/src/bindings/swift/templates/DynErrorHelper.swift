@@ -0,0 +1,37 @@
+/**
+ * A dynamically typed error thrown by Rust.
+ *
+ * `message` is the `Display` string of the Rust error, and `causeChain` holds the `Display`
+ * strings of its chain of sources, starting with the immediate source.
+ */
+public struct DynError: Swift.Error, Equatable, Hashable {
+    public let message: String
+    public let causeChain: [String]
+
+    public init(message: String, causeChain: [String]) {
+        self.message = message
+        self.causeChain = causeChain
+    }
+}
+
+extension DynError: CustomStringConvertible {
+    public var description: String {
+        return message
+    }
+}
+
+fileprivate struct FfiConverterDynError: FfiConverterRustBuffer {
+    typealias SwiftType = DynError
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> DynError {
+        return DynError(
+            message: try FfiConverterString.read(from: &buf),
+            causeChain: try FfiConverterSequenceString.read(from: &buf)
+        )
+    }
+
+    public static func write(_ value: DynError, into buf: inout [UInt8]) {
+        FfiConverterString.write(value.message, into: &buf)
+        FfiConverterSequenceString.write(value.causeChain, into: &buf)
+    }
+}
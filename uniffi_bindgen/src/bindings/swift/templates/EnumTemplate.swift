@@ -1,20 +1,72 @@
 // Note that we don't yet support `indirect` for enums.
 // See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
-{%- call swift::docstring(e, 0) %}
+{%- call swift::type_docstring(e, 0) %}
 public enum {{ type_name }} {
     {% for variant in e.variants() %}
     {%- call swift::docstring(variant, 4) %}
+    {%- call swift::deprecated(variant, 4) %}
     case {{ variant.name()|enum_variant_swift_quoted }}{% if variant.fields().len() > 0 %}(
         {%- call swift::field_list_decl(variant) %}
     ){% endif -%}
     {% endfor %}
+    {%- if e.has_unknown_variant() %}
+    /**
+     * A variant which these bindings don't know about, because it was added to the
+     * non-exhaustive enum after they were generated.
+     */
+    case unknown(discriminant: Int32)
+    {%- endif %}
 }
 
+{%- if e.is_flat() %}
+// Enums without associated data are passed across the FFI as the `Int32` that identifies their variant.
+public struct {{ ffi_converter_name }}: FfiConverter {
+    typealias FfiType = Int32
+    typealias SwiftType = {{ type_name }}
+
+    public static func lift(_ value: Int32) throws -> {{ type_name }} {
+        switch value {
+        {% for variant in e.variants() %}
+        case {{ loop.index }}: return .{{ variant.name()|enum_variant_swift_quoted }}
+        {% endfor %}
+        {%- match e.default_variant() %}
+        {%- when Some with (default) %}
+        default: return .{{ default.name()|enum_variant_swift_quoted }}
+        {%- when None %}
+        {%- if e.has_unknown_variant() %}
+        default: return .unknown(discriminant: value)
+        {%- else %}
+        default: throw UniffiInternalError.unexpectedEnumCase
+        {%- endif %}
+        {%- endmatch %}
+        }
+    }
+
+    public static func lower(_ value: {{ type_name }}) -> Int32 {
+        switch value {
+        {% for variant in e.variants() %}
+        case .{{ variant.name()|enum_variant_swift_quoted }}: return {{ loop.index }}
+        {% endfor %}
+        {%- if e.has_unknown_variant() %}
+        case let .unknown(discriminant): return discriminant
+        {%- endif %}
+        }
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
+        return try lift({% call swift::read_discriminant(e) %})
+    }
+
+    public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
+        {% call swift::write_discriminant(e, "lower(value)") %}
+    }
+}
+{%- else %}
 public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
     typealias SwiftType = {{ type_name }}
 
     public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
-        let variant: Int32 = try readInt(&buf)
+        let variant: Int32 = try {% call swift::read_discriminant(e) %}
         switch variant {
         {% for variant in e.variants() %}
         case {{ loop.index }}: return .{{ variant.name()|enum_variant_swift_quoted }}{% if variant.has_fields() %}(
@@ -24,7 +76,11 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
             {%- endfor %}
         ){%- endif %}
         {% endfor %}
+        {%- if e.has_unknown_variant() %}
+        default: return .unknown(discriminant: variant)
+        {%- else %}
         default: throw UniffiInternalError.unexpectedEnumCase
+        {%- endif %}
         }
     }
 
@@ -33,31 +89,67 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
         {% for variant in e.variants() %}
         {% if variant.has_fields() %}
         case let .{{ variant.name()|enum_variant_swift_quoted }}({% for field in variant.fields() %}{{ field.name()|var_name }}{%- if loop.last -%}{%- else -%},{%- endif -%}{% endfor %}):
-            writeInt(&buf, Int32({{ loop.index }}))
+            {% call swift::write_discriminant(e, loop.index) %}
             {% for field in variant.fields() -%}
             {{ field|write_fn }}({{ field.name()|var_name }}, into: &buf)
             {% endfor -%}
         {% else %}
         case .{{ variant.name()|enum_variant_swift_quoted }}:
-            writeInt(&buf, Int32({{ loop.index }}))
+            {% call swift::write_discriminant(e, loop.index) %}
         {% endif %}
         {%- endfor %}
+        {%- if e.has_unknown_variant() %}
+        case let .unknown(discriminant):
+            {% call swift::write_discriminant(e, "discriminant") %}
+        {%- endif %}
         }
     }
 }
+{%- endif %}
 
 {#
 We always write these public functions just in case the enum is used as
 an external type by another crate.
 #}
-public func {{ ffi_converter_name }}_lift(_ buf: RustBuffer) throws -> {{ type_name }} {
-    return try {{ ffi_converter_name }}.lift(buf)
+{%- let ffi_type_name = e|ffi_type(ci)|ffi_type_name %}
+public func {{ ffi_converter_name }}_lift(_ value: {{ ffi_type_name }}) throws -> {{ type_name }} {
+    return try {{ ffi_converter_name }}.lift(value)
 }
 
-public func {{ ffi_converter_name }}_lower(_ value: {{ type_name }}) -> RustBuffer {
+public func {{ ffi_converter_name }}_lower(_ value: {{ type_name }}) -> {{ ffi_type_name }} {
     return {{ ffi_converter_name }}.lower(value)
 }
 
 {% if !contains_object_references %}
 extension {{ type_name }}: Equatable, Hashable {}
 {% endif %}
+{%- if e.is_flat() && !e.has_unknown_variant() %}
+
+extension {{ type_name }}: CaseIterable {}
+{%- endif %}
+{%- if self.is_codable(e.as_type().borrow()) %}
+
+extension {{ type_name }}: Codable {}
+{%- endif %}
+{%- if !e.methods().is_empty() %}
+{%- let lower_self = "{}.lower(self)"|format(ffi_converter_name) %}
+
+extension {{ type_name }} {
+    {%- for meth in e.methods() %}
+    {%- match meth.return_type() %}
+    {%- when Some with (return_type) %}
+    {%- call swift::callable_docstring(meth, 4) %}
+    public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_name }} {
+        return {% call swift::try(meth) %} {{ return_type|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix(lower_self, meth) %}
+        )
+    }
+    {%- when None %}
+    {%- call swift::callable_docstring(meth, 4) %}
+    public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
+        {% call swift::to_ffi_call_with_prefix(lower_self, meth) %}
+    }
+    {%- endmatch %}
+    {% endfor %}
+}
+{%- endif %}
@@ -1,15 +1,17 @@
-{%- call swift::docstring(e, 0) %}
+{%- call swift::type_docstring(e, 0) %}
 public enum {{ type_name }} {
 
     {% if e.is_flat() %}
     {% for variant in e.variants() %}
     {%- call swift::docstring(variant, 4) %}
+    {%- call swift::deprecated(variant, 4) %}
     case {{ variant.name()|class_name }}(message: String)
     {% endfor %}
 
     {%- else %}
     {% for variant in e.variants() %}
     {%- call swift::docstring(variant, 4) %}
+    {%- call swift::deprecated(variant, 4) %}
     case {{ variant.name()|class_name }}{% if variant.fields().len() > 0 %}(
         {%- call swift::field_list_decl(variant) %}
     ){% endif -%}
@@ -22,6 +24,31 @@ public enum {{ type_name }} {
     }
 }
 
+{%- if !e.is_flat() %}
+{%- let common_fields = e.common_fields() %}
+{%- if !common_fields.is_empty() %}
+
+// Fields which every variant of `{{ type_name }}` carries, accessible without matching.
+extension {{ type_name }} {
+    {%- for field in common_fields %}
+    {%- call swift::docstring(field, 4) %}
+    public var {{ field.name()|var_name }}: {{ field|type_name }} {
+        switch self {
+        {%- for variant in e.variants() %}
+        case let .{{ variant.name()|class_name }}(
+            {%- for variant_field in variant.fields() %}
+            {%- if variant_field.name() == field.name() %}{{ field.name()|var_name }}{% else %}_{% endif %}
+            {%- if !loop.last %}, {% endif %}
+            {%- endfor %}):
+            return {{ field.name()|var_name }}
+        {%- endfor %}
+        }
+    }
+    {%- endfor %}
+}
+{%- endif %}
+{%- endif %}
+
 
 public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
     typealias SwiftType = {{ type_name }}
@@ -55,6 +82,9 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
     }
 
     public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
+        {%- if e.variants().is_empty() %}
+        // There are no variants, so there are no values to write.
+        {%- else %}
         switch value {
 
         {% if e.is_flat() %}
@@ -81,6 +111,7 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
 
         {%- endif %}
         }
+        {%- endif %}
     }
 }
 
@@ -6,6 +6,8 @@ fileprivate enum UniffiInternalError: LocalizedError {
     case unexpectedOptionalTag
     case unexpectedEnumCase
     case unexpectedNullPointer
+    case unexpectedZero
+    case unexpectedRange
     case unexpectedRustCallStatusCode
     case unexpectedRustCallError
     case unexpectedStaleHandle
@@ -18,6 +20,8 @@ fileprivate enum UniffiInternalError: LocalizedError {
         case .unexpectedOptionalTag: return "Unexpected optional tag; should be 0 or 1"
         case .unexpectedEnumCase: return "Raw enum value doesn't match any cases"
         case .unexpectedNullPointer: return "Raw pointer value was null"
+        case .unexpectedZero: return "Non-zero integer value was zero"
+        case .unexpectedRange: return "Range bounds are out of order, or the range is the wrong kind"
         case .unexpectedRustCallStatusCode: return "Unexpected RustCallStatus code"
         case .unexpectedRustCallError: return "CALL_ERROR but no errorClass specified"
         case .unexpectedStaleHandle: return "The object in the handle map has been dropped already"
@@ -30,6 +34,40 @@ fileprivate let CALL_SUCCESS: Int8 = 0
 fileprivate let CALL_ERROR: Int8 = 1
 fileprivate let CALL_PANIC: Int8 = 2
 fileprivate let CALL_CANCELLED: Int8 = 3
+fileprivate let CALL_WRONG_THREAD: Int8 = 4
+fileprivate let CALL_STATE_POISONED: Int8 = 5
+{%- if ci.has_thread_bound_objects() %}
+
+/**
+ * Thrown when a method of a `[ThreadBound]` object is called on a thread other than the one which
+ * constructed it.
+ */
+public struct WrongThreadError: Swift.Error, Equatable, Hashable {
+    public let message: String
+}
+
+extension WrongThreadError: CustomStringConvertible {
+    public var description: String {
+        return message
+    }
+}
+{%- endif %}
+{%- if ci.has_on_poison_objects() %}
+
+/**
+ * Thrown when a method of an `[OnPoison=error]` object panics on state poisoned by an earlier
+ * call.
+ */
+public struct StatePoisonedError: Swift.Error, Equatable, Hashable {
+    public let message: String
+}
+
+extension StatePoisonedError: CustomStringConvertible {
+    public var description: String {
+        return message
+    }
+}
+{%- endif %}
 
 fileprivate extension RustCallStatus {
     init() {
@@ -93,7 +131,17 @@ private func uniffiCheckCallStatus(
             }
 
         case CALL_CANCELLED:
-            fatalError("Cancellation not supported yet")
+            throw CancellationError()
+        {%- if ci.has_thread_bound_objects() %}
+
+        case CALL_WRONG_THREAD:
+            throw WrongThreadError(message: try {{ Type::String.borrow()|lift_fn }}(callStatus.errorBuf))
+        {%- endif %}
+        {%- if ci.has_on_poison_objects() %}
+
+        case CALL_STATE_POISONED:
+            throw StatePoisonedError(message: try {{ Type::String.borrow()|lift_fn }}(callStatus.errorBuf))
+        {%- endif %}
 
         default:
             throw UniffiInternalError.unexpectedRustCallStatusCode
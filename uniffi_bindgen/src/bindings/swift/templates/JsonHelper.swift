@@ -0,0 +1,15 @@
+// JSON values are passed as their JSON text, which Rust checks is valid when it's lifted.
+fileprivate struct FfiConverterJson: FfiConverterRustBuffer {
+    typealias SwiftType = String
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> String {
+        let len: Int32 = try readInt(&buf)
+        return String(bytes: try readBytes(&buf, count: Int(len)), encoding: String.Encoding.utf8)!
+    }
+
+    public static func write(_ value: String, into buf: inout [UInt8]) {
+        let len = Int32(value.utf8.count)
+        writeInt(&buf, len)
+        writeBytes(&buf, value.utf8)
+    }
+}
@@ -0,0 +1,6 @@
+
+public enum {{ namespace_object|class_name }} {
+{%- for func in ci.namespace_object_functions(namespace_object) %}
+{%- include "TopLevelFunctionTemplate.swift" %}
+{%- endfor %}
+}
@@ -0,0 +1,19 @@
+fileprivate struct {{ ffi_converter_name }}: FfiConverterPrimitive {
+    typealias FfiType = {{ type_name }}
+    typealias SwiftType = {{ type_name }}
+
+    public static func lift(_ value: FfiType) throws -> SwiftType {
+        if value == 0 {
+            throw UniffiInternalError.unexpectedZero
+        }
+        return value
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        return try lift(readInt(&buf))
+    }
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
@@ -2,11 +2,40 @@
 {%- let (protocol_name, impl_class_name) = obj|object_names %}
 {%- let methods = obj.methods() %}
 {%- let protocol_docstring = obj.docstring() %}
+{%- let protocol_parent = obj|parent_protocol_name %}
+{%- let clone_pointer_fn = obj|clone_pointer_fn %}
+{%- let clone_pointer_call = format!("self.{}()", clone_pointer_fn) %}
 
 {% include "Protocol.swift" %}
+{%- if obj.has_default_methods() %}
 
-{%- call swift::docstring(obj, 0) %}
+extension {{ protocol_name }} {
+    {%- for meth in methods.iter() %}
+    {%- if meth.has_default() %}
+    {%- match meth.return_type() %}
+    {%- when Some with (return_type) %}
+    public func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_name }} {
+        return {% call swift::try(meth) %} {{ return_type|lift_fn }}(
+            {% call swift::to_ffi_default_call(meth) %}
+        )
+    }
+    {%- when None %}
+    public func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) %} {
+        {% call swift::to_ffi_default_call(meth) %}
+    }
+    {%- endmatch %}
+    {%- endif %}
+    {%- endfor %}
+}
+{%- endif %}
+
+{%- call swift::object_docstring(obj, 0) %}
 public class {{ impl_class_name }}:
+    {%- match obj.parent() %}
+    {%- when Some(parent) %}
+    {{ parent|class_name }},
+    {%- when None %}
+    {%- endmatch %}
     {%- for tm in obj.uniffi_traits() %}
     {%-     match tm %}
     {%-         when UniffiTrait::Display { fmt } %}
@@ -20,8 +49,44 @@ public class {{ impl_class_name }}:
     {%-         else %}
     {%-    endmatch %}
     {%- endfor %}
+    {%- if obj.has_identity_equality() %}
+    Equatable,
+    {%- endif %}
+    {%- if obj.has_identity_hash() %}
+    Hashable,
+    {%- endif %}
+    {%- for trait_type in obj.implements() %}
+    {{ trait_type|type_name }},
+    {%- endfor %}
     {{ protocol_name }} {
+    {%- for constant in obj.constants() %}
+    {%- call swift::docstring(constant, 4) %}
+    public static let {{ constant.name()|var_name }}: {{ constant|type_name }} = {{ constant.value()|literal_swift(constant) }}
+    {%- endfor %}
+    {%- match obj.parent() %}
+    {%- when Some(parent) %}
+    // The pointer to this object.  The pointer held by the superclass points to the
+    // `{{ parent|class_name }}` this object was converted to, which its methods are called with.
+    fileprivate let uniffi{{ impl_class_name }}Pointer: UnsafeMutableRawPointer
+
+    // TODO: We'd like this to be `private` but for Swifty reasons,
+    // we can't implement `FfiConverter` without making this `required` and we can't
+    // make it `required` without making it `public`.
+    required init(unsafeFromRawPointer pointer: UnsafeMutableRawPointer) {
+        self.uniffi{{ impl_class_name }}Pointer = pointer
+        super.init(unsafeFromRawPointer: try! rustCall { {{ obj.ffi_object_upcast().name() }}(pointer, $0) })
+    }
+
+    public func {{ clone_pointer_fn }}() -> UnsafeMutableRawPointer {
+        return try! rustCall { {{ obj.ffi_object_clone().name() }}(self.uniffi{{ impl_class_name }}Pointer, $0) }
+    }
+    {%- when None %}
     fileprivate let pointer: UnsafeMutableRawPointer
+    {%- if obj.has_consuming_methods() %}
+    // Set when a method consumes the object, which takes over `pointer`.
+    private var uniffiConsumed = false
+    private let uniffiConsumedLock = NSLock()
+    {%- endif %}
 
     // TODO: We'd like this to be `private` but for Swifty reasons,
     // we can't implement `FfiConverter` without making this `required` and we can't
@@ -31,39 +96,101 @@ public class {{ impl_class_name }}:
     }
 
     public func uniffiClonePointer() -> UnsafeMutableRawPointer {
+        {%- if obj.has_consuming_methods() %}
+        uniffiConsumedLock.lock()
+        defer { uniffiConsumedLock.unlock() }
+        precondition(!uniffiConsumed, "{{ impl_class_name }} object has already been consumed")
+        {%- endif %}
         return try! rustCall { {{ obj.ffi_object_clone().name() }}(self.pointer, $0) }
     }
+    {%- if obj.has_consuming_methods() %}
+
+    fileprivate func uniffiConsumePointer() -> UnsafeMutableRawPointer {
+        uniffiConsumedLock.lock()
+        defer { uniffiConsumedLock.unlock() }
+        precondition(!uniffiConsumed, "{{ impl_class_name }} object has already been consumed")
+        uniffiConsumed = true
+        return pointer
+    }
+    {%- endif %}
+    {%- endmatch %}
 
     {%- match obj.primary_constructor() %}
     {%- when Some with (cons) %}
-    {%- call swift::docstring(cons, 4) %}
-    public convenience init({% call swift::arg_list_decl(cons) -%}) {% call swift::throws(cons) %} {
+    {%- if cons.is_async() %}
+    {%- call swift::constructor_docstring(cons, 4) %}
+    public convenience init({% call swift::arg_list_init_decl(cons) -%}) async {% call swift::throws(cons) %} {
+        let pointer = {% call swift::try(cons) %} await {% call swift::async_constructor_call(cons) %}
+        self.init(unsafeFromRawPointer: pointer)
+    }
+    {%- else %}
+    {%- call swift::constructor_docstring(cons, 4) %}
+    public convenience init({% call swift::arg_list_init_decl(cons) -%}) {% call swift::throws(cons) %} {
         self.init(unsafeFromRawPointer: {% call swift::to_ffi_call(cons) %})
     }
+    {%- endif %}
+    {%- when None %}
+    {%- endmatch %}
+
+    {%- match obj|inherited_constructor(ci) %}
+    {%- when Some with (cons) %}
+
+    @available(*, unavailable, message: "Use the constructors of `{{ impl_class_name }}` instead")
+    public convenience init({% call swift::arg_list_init_decl(cons) -%}) {% if cons.is_async() %}async {% endif %}{% call swift::throws(cons) %} {
+        fatalError("Unavailable")
+    }
     {%- when None %}
     {%- endmatch %}
 
     deinit {
-        try! rustCall { {{ obj.ffi_object_free().name() }}(pointer, $0) }
+        {%- if obj.has_consuming_methods() %}
+        if uniffiConsumed {
+            return
+        }
+        {%- endif %}
+        try! rustCall { {{ obj.ffi_object_free().name() }}({% if obj.parent().is_some() %}uniffi{{ impl_class_name }}Pointer{% else %}pointer{% endif %}, $0) }
     }
 
+    {%- if obj.is_singleton() %}
+
+    /**
+     * The single instance of `{{ impl_class_name }}`, which is shared by all callers.
+     */
+    public static let instance = {{ impl_class_name }}(unsafeFromRawPointer: try! rustCall {
+        {{ obj.ffi_object_singleton().name() }}($0)
+    })
+    {%- endif %}
+
     {% for cons in obj.alternate_constructors() %}
-    {%- call swift::docstring(cons, 4) %}
+    {%- call swift::constructor_docstring(cons, 4) %}
+    {%- if cons.is_async() %}
+    public static func {{ cons.name()|fn_name }}({% call swift::arg_list_decl(cons) %}) async {% call swift::throws(cons) %} -> {{ impl_class_name }} {
+        let pointer = {% call swift::try(cons) %} await {% call swift::async_constructor_call(cons) %}
+        return {{ impl_class_name }}(unsafeFromRawPointer: pointer)
+    }
+    {%- else %}
     public static func {{ cons.name()|fn_name }}({% call swift::arg_list_decl(cons) %}) {% call swift::throws(cons) %} -> {{ impl_class_name }} {
         return {{ impl_class_name }}(unsafeFromRawPointer: {% call swift::to_ffi_call(cons) %})
     }
+    {%- endif %}
 
     {% endfor %}
 
     {# // TODO: Maybe merge the two templates (i.e the one with a return type and the one without) #}
     {% for meth in obj.methods() -%}
+    {%- let self_pointer_call %}
+    {%- if meth.takes_self_by_value() %}
+    {%- let self_pointer_call = "self.uniffiConsumePointer()".to_string() %}
+    {%- else %}
+    {%- let self_pointer_call = clone_pointer_call.clone() %}
+    {%- endif %}
     {%- if meth.is_async() %}
-    {%- call swift::docstring(meth, 4) %}
+    {%- call swift::method_docstring(meth, 4) %}
     public func {{ meth.name()|fn_name }}({%- call swift::arg_list_decl(meth) -%}) async {% call swift::throws(meth) %}{% match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
         return {% call swift::try(meth) %} await uniffiRustCallAsync(
             rustFutureFunc: {
                 {{ meth.ffi_func().name() }}(
-                    self.uniffiClonePointer()
+                    {{ self_pointer_call }}
                     {%- for arg in meth.arguments() -%}
                     ,
                     {{ arg|lower_fn }}({{ arg.name()|var_name }})
@@ -81,9 +208,11 @@ public class {{ impl_class_name }}:
             {%- endmatch %}
             {%- match meth.throws_type() %}
             {%- when Some with (e) %}
-            errorHandler: {{ e|ffi_converter_name }}.lift
+            errorHandler: {{ e|ffi_converter_name }}.lift,
+            cancelFunc: {{ meth.ffi_rust_future_cancel(ci) }}
             {%- else %}
-            errorHandler: nil
+            errorHandler: nil,
+            cancelFunc: nil
             {% endmatch %}
         )
     }
@@ -93,17 +222,17 @@ public class {{ impl_class_name }}:
     {%- match meth.return_type() -%}
 
     {%- when Some with (return_type) %}
-    {%- call swift::docstring(meth, 4) %}
+    {%- call swift::method_docstring(meth, 4) %}
     public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_name }} {
         return {% call swift::try(meth) %} {{ return_type|lift_fn }}(
-            {% call swift::to_ffi_call_with_prefix("self.uniffiClonePointer()", meth) %}
+            {% call swift::to_ffi_call_with_prefix(self_pointer_call, meth) %}
         )
     }
 
     {%- when None %}
-    {%- call swift::docstring(meth, 4) %}
+    {%- call swift::method_docstring(meth, 4) %}
     public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
-        {% call swift::to_ffi_call_with_prefix("self.uniffiClonePointer()", meth) %}
+        {% call swift::to_ffi_call_with_prefix(self_pointer_call, meth) %}
     }
 
     {%- endmatch -%}
@@ -141,7 +270,71 @@ public class {{ impl_class_name }}:
     {%-    endmatch %}
     {%- endfor %}
 
+    {%- if obj.has_identity_equality() %}
+
+    /// The id of the underlying Rust object, which is the same for every handle to it.
+    public func uniffiHandleId() -> UInt64 {
+        return try! rustCall { {{ obj.ffi_object_handle_id().name() }}(self.pointer, $0) }
+    }
+
+    public static func == (lhs: {{ impl_class_name }}, other: {{ impl_class_name }}) -> Bool {
+        return lhs.uniffiHandleId() == other.uniffiHandleId()
+    }
+    {%- endif %}
+    {%- if obj.has_identity_hash() %}
+
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(uniffiHandleId())
+    }
+    {%- endif %}
+
+    {%- match ci.observable(obj) %}
+    {%- when Some with (observable) %}
+    {%- let add = observable.add_method() %}
+    {%- let remove = observable.remove_method() %}
+
+    /// The events passed to the observer's `{{ observable.event_method().name()|fn_name }}` method.
+    /// The observer is added with `{{ add.name()|fn_name }}` when the stream is created, and removed
+    /// with `{{ remove.name()|fn_name }}` when it's finished or cancelled.
+    public func observe() -> AsyncStream<{{ observable.event_type()|type_name }}> {
+        return AsyncStream { continuation in
+            let observer = Uniffi{{ impl_class_name }}Observer(continuation)
+            {%- if observable.has_token() %}
+            let token = self.{{ add.name()|fn_name }}({% call swift::arg_label_call(add.arguments()[0], config.omit_first_argument_label()) %}observer)
+            continuation.onTermination = { _ in
+                self.{{ remove.name()|fn_name }}({% call swift::arg_label_call(remove.arguments()[0], config.omit_first_argument_label()) %}token)
+            }
+            {%- else %}
+            self.{{ add.name()|fn_name }}({% call swift::arg_label_call(add.arguments()[0], config.omit_first_argument_label()) %}observer)
+            continuation.onTermination = { _ in
+                self.{{ remove.name()|fn_name }}({% call swift::arg_label_call(remove.arguments()[0], config.omit_first_argument_label()) %}observer)
+            }
+            {%- endif %}
+        }
+    }
+    {%- else %}
+    {%- endmatch %}
+
+}
+
+{%- match ci.observable(obj) %}
+{%- when Some with (observable) %}
+{%- let event_method = observable.event_method() %}
+
+// Passes the events of `{{ impl_class_name }}.observe()` to its stream.
+fileprivate final class Uniffi{{ impl_class_name }}Observer: {{ observable.observer_type()|type_name }} {
+    private let continuation: AsyncStream<{{ observable.event_type()|type_name }}>.Continuation
+
+    init(_ continuation: AsyncStream<{{ observable.event_type()|type_name }}>.Continuation) {
+        self.continuation = continuation
+    }
+
+    func {{ event_method.name()|fn_name }}({% call swift::arg_list_protocol(event_method) %}) {
+        continuation.yield({{ observable.event_argument().name()|var_name }})
+    }
 }
+{%- else %}
+{%- endmatch %}
 
 {%- if obj.is_trait_interface() %}
 {%- let callback_handler = format!("uniffiCallbackInterface{}", name) %}
@@ -165,7 +358,7 @@ public struct {{ ffi_converter_name }}: FfiConverter {
     public static func lower(_ value: {{ type_name }}) -> UnsafeMutableRawPointer {
         {%- match obj.imp() %}
         {%- when ObjectImpl::Struct %}
-        return value.uniffiClonePointer()
+        return value.{{ clone_pointer_fn }}()
         {%- when ObjectImpl::Trait %}
         guard let ptr = UnsafeMutableRawPointer(bitPattern: UInt(truncatingIfNeeded: handleMap.insert(obj: value))) else {
             fatalError("Cast to UnsafeMutableRawPointer failed")
@@ -0,0 +1,41 @@
+/**
+ * A value from Rust, usually a pointer, which is passed back unchanged.
+ *
+ * Nothing keeps what it points to alive: that's up to the Rust code which handed it out.
+ */
+public struct OpaqueHandle: Hashable {
+    public let rawValue: UInt64
+
+    public init(rawValue: UInt64) {
+        self.rawValue = rawValue
+    }
+
+    public init(_ pointer: UnsafeMutableRawPointer) {
+        self.rawValue = UInt64(UInt(bitPattern: pointer))
+    }
+
+    public var pointer: UnsafeMutableRawPointer? {
+        return UnsafeMutableRawPointer(bitPattern: UInt(rawValue))
+    }
+}
+
+fileprivate struct FfiConverterOpaqueHandle: FfiConverter {
+    typealias FfiType = UInt64
+    typealias SwiftType = OpaqueHandle
+
+    public static func lift(_ value: UInt64) throws -> OpaqueHandle {
+        return OpaqueHandle(rawValue: value)
+    }
+
+    public static func lower(_ value: OpaqueHandle) -> UInt64 {
+        return value.rawValue
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> OpaqueHandle {
+        return try lift(readInt(&buf))
+    }
+
+    public static func write(_ value: OpaqueHandle, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
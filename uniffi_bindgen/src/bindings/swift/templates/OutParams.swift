@@ -0,0 +1,14 @@
+// Lift the value returned by a function with out-parameters, which is followed by their final
+// values in the same buffer.
+private func uniffiLiftOutParams<T>(
+    _ buf: RustBuffer,
+    _ read: (inout (data: Data, offset: Data.Index)) throws -> T
+) throws -> T {
+    var reader = createReader(data: Data(rustBuffer: buf))
+    let outputs = try read(&reader)
+    if hasRemaining(reader) {
+        throw UniffiInternalError.incompleteData
+    }
+    buf.deallocate()
+    return outputs
+}
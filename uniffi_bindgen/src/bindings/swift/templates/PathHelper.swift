@@ -0,0 +1,17 @@
+// Paths are passed as file URLs.
+fileprivate struct FfiConverterPath: FfiConverterRustBuffer {
+    typealias SwiftType = URL
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> URL {
+        let len: Int32 = try readInt(&buf)
+        let path = String(bytes: try readBytes(&buf, count: Int(len)), encoding: String.Encoding.utf8)!
+        return URL(fileURLWithPath: path)
+    }
+
+    public static func write(_ value: URL, into buf: inout [UInt8]) {
+        let path = value.path
+        let len = Int32(path.utf8.count)
+        writeInt(&buf, len)
+        writeBytes(&buf, path.utf8)
+    }
+}
@@ -1,5 +1,6 @@
 {%- let rec = ci|get_record_definition(name) %}
-{%- call swift::docstring(rec, 0) %}
+{%- call swift::type_docstring(rec, 0) %}
+{%- call swift::deprecated(rec, 0) %}
 public struct {{ type_name }} {
     {%- for field in rec.fields() %}
     {%- call swift::docstring(field, 4) %}
@@ -14,6 +15,17 @@ public struct {{ type_name }} {
         {%- endfor %}
     }
 }
+{%- if rec.has_default() %}
+
+extension {{ type_name }} {
+    /// The Rust `Default::default()` value of `{{ type_name }}`.
+    public static func `default`() -> {{ type_name }} {
+        return try! {{ ffi_converter_name }}.lift(try! rustCall {
+            {{ rec.ffi_func_default().name() }}($0)
+        })
+    }
+}
+{%- endif %}
 
 {% if !contains_object_references %}
 extension {{ type_name }}: Equatable, Hashable {
@@ -33,9 +45,53 @@ extension {{ type_name }}: Equatable, Hashable {
     }
 }
 {% endif %}
+{%- if self.is_codable(rec.as_type().borrow()) %}
+
+extension {{ type_name }}: Codable {}
+{%- endif %}
+{%- if config.derive_display() %}
+
+extension {{ type_name }}: CustomStringConvertible {
+    public var description: String {
+        return "{{ type_name }}({% for field in rec.fields() %}{{ field.name()|var_name|unquote }}: {{ field|display_field }}{% if !loop.last %}, {% endif %}{% endfor %})"
+    }
+}
+{%- endif %}
 
 public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
     public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
+        {%- if rec.is_packed() %}
+        // Adjacent boolean fields are packed into bytes.
+        {%- for item in rec.wire_items() %}
+        {%- match item %}
+        {%- when WireItem::Field with (field) %}
+        let {{ "uniffi_{}"|format(field.name())|var_name }} = try {{ field|read_fn }}(from: &buf)
+        {%- when WireItem::PackedBools with (bools) %}
+        let packed{{ loop.index0 }}: UInt8 = try readInt(&buf)
+        {%- let packed = "packed{}"|format(loop.index0) %}
+        {%- for (field, mask) in bools %}
+        let {{ "uniffi_{}"|format(field.name())|var_name }} = {{ packed }} & {{ mask }} != 0
+        {%- endfor %}
+        {%- endmatch %}
+        {%- endfor %}
+        return {{ type_name }}(
+            {%- for field in rec.fields() %}
+            {{ field.name()|arg_name }}: {{ "uniffi_{}"|format(field.name())|var_name }}
+            {%- if !loop.last %}, {% endif %}
+            {%- endfor %}
+        )
+        {%- else if rec.has_wire_order() %}
+        // The fields are serialized in a different order to their declaration.
+        {%- for field in rec.wire_fields() %}
+        let {{ "uniffi_{}"|format(field.name())|var_name }} = try {{ field|read_fn }}(from: &buf)
+        {%- endfor %}
+        return {{ type_name }}(
+            {%- for field in rec.fields() %}
+            {{ field.name()|arg_name }}: {{ "uniffi_{}"|format(field.name())|var_name }}
+            {%- if !loop.last %}, {% endif %}
+            {%- endfor %}
+        )
+        {%- else %}
         return {%- if rec.has_fields() %}
             try {{ type_name }}(
             {%- for field in rec.fields() %}
@@ -46,11 +102,22 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
         {%- else %}
             {{ type_name }}()
         {%- endif %}
+        {%- endif %}
     }
 
     public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
-        {%- for field in rec.fields() %}
+        {%- for item in rec.wire_items() %}
+        {%- match item %}
+        {%- when WireItem::Field with (field) %}
         {{ field|write_fn }}(value.{{ field.name()|var_name }}, into: &buf)
+        {%- when WireItem::PackedBools with (bools) %}
+        var packed{{ loop.index0 }}: UInt8 = 0
+        {%- let packed = "packed{}"|format(loop.index0) %}
+        {%- for (field, mask) in bools %}
+        if value.{{ field.name()|var_name }} { {{ packed }} |= {{ mask }} }
+        {%- endfor %}
+        writeInt(&buf, {{ packed }})
+        {%- endmatch %}
         {%- endfor %}
     }
 }
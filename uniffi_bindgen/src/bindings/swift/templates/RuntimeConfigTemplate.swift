@@ -0,0 +1,36 @@
+
+/**
+ * Runtime settings, which `uniffiInitialize(_:)` applies together.
+ *
+ * Each method sets one setting and returns this config, so they can be chained.  Settings which
+ * aren't set are left unchanged.
+ */
+public class UniffiRuntimeConfig {
+    {%- for arg in func.arguments() %}
+    fileprivate var {{ arg.name()|var_name }}: {{ arg|type_name }} = nil
+    {%- endfor %}
+
+    public init() {}
+    {%- for arg in func.arguments() %}
+    {%- match arg.as_type() %}
+    {%- when Type::Optional { inner_type } %}
+
+    @discardableResult
+    public func {{ arg.name()|fn_name }}(_ value: {{ inner_type|type_name }}) -> UniffiRuntimeConfig {
+        self.{{ arg.name()|var_name }} = value
+        return self
+    }
+    {%- else %}
+    {%- endmatch %}
+    {%- endfor %}
+}
+
+/**
+ * Apply the settings in `config` together, with a single call into Rust.
+ */
+public func uniffiInitialize(_ config: UniffiRuntimeConfig) {
+    {%- for arg in func.arguments() %}
+    let {{ arg.name()|var_name }} = config.{{ arg.name()|var_name }}
+    {%- endfor %}
+    {% call swift::to_ffi_call(func) %}
+}
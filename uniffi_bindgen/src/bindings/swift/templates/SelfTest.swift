@@ -0,0 +1,48 @@
+/**
+ * Pass a value of each primitive type to Rust and back, to check that the bindings and the library
+ * agree on how they're passed over the FFI.
+ *
+ * Call this at startup to diagnose mismatched or miscompiled builds.  Every value is checked, and
+ * the report has a message for each one which changed.
+ */
+public func uniffiSelfTest() -> UniffiSelfTestReport {
+    // These must match `uniffi_self_test_values()` in the scaffolding.
+    let expected = UniffiSelfTestValues(
+        boolean: true,
+        int8: Int8.min,
+        uint8: UInt8.max,
+        int16: Int16.min,
+        uint16: UInt16.max,
+        int32: Int32.min,
+        uint32: UInt32.max,
+        int64: Int64.min,
+        uint64: UInt64.max,
+        float32: -0.15625,
+        float64: 0.1,
+        // Characters encoded as 1, 2, 3 and 4 bytes of UTF-8.
+        string: "a\u{df}\u{20ac}\u{1f980}",
+        bytes: Data([0, 1, 127, 128, 255])
+    )
+    var failures = uniffiSelfTestCheck(values: expected).failures
+    let actual = uniffiSelfTestValues()
+    func check<T: Equatable>(_ name: String, _ value: T, _ expectedValue: T) {
+        if value != expectedValue {
+            failures.append("\(name): Swift received \(value), expected \(expectedValue)")
+        }
+    }
+    check("boolean", actual.boolean, expected.boolean)
+    check("int8", actual.int8, expected.int8)
+    check("uint8", actual.uint8, expected.uint8)
+    check("int16", actual.int16, expected.int16)
+    check("uint16", actual.uint16, expected.uint16)
+    check("int32", actual.int32, expected.int32)
+    check("uint32", actual.uint32, expected.uint32)
+    check("int64", actual.int64, expected.int64)
+    check("uint64", actual.uint64, expected.uint64)
+    // Compare floats by their bits, to check their encoding rather than just their value.
+    check("float32", actual.float32.bitPattern, expected.float32.bitPattern)
+    check("float64", actual.float64.bitPattern, expected.float64.bitPattern)
+    check("string", actual.string, expected.string)
+    check("bytes", actual.bytes, expected.bytes)
+    return UniffiSelfTestReport(passed: failures.isEmpty, failures: failures)
+}
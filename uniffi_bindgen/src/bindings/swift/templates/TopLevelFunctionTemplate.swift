@@ -1,18 +1,85 @@
+{%- if config.generates_target(func.target()) %}
+{%- match config.target_condition(func.target()) %}
+{%- when Some with (condition) %}
+
+#if {{ condition }}
+{%- when None %}
+{%- endmatch %}
+{%- match func.extension_type() %}
+{%- when Some with (receiver_type) %}
+
+extension {{ receiver_type|type_name }} {
+{%- when None %}
+{%- endmatch %}
 {%- if func.is_async() %}
 
-{%- call swift::docstring(func, 0) %}
-public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) async {% call swift::throws(func) %}{% match func.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+{%- call swift::callable_docstring(func, 0) %}
+{%- call swift::deprecated(func, 0) %}
+public {% if func.namespace_object().is_some() %}static {% endif %}func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) async {% call swift::throws(func) %}{% match func.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+    {%- match func.retries() %}
+    {%- when Some with (retries) %}
+    return try await uniffiRetryAsync(
+        retries: {{ retries }},
+        isRetryable: { error in
+            switch error as? {{ func.throws_type().unwrap()|type_name }} {
+            case {% for variant in ci.retryable_variants(func) %}.{{ variant.name()|class_name }}?{% if !loop.last %}, {% endif %}{% endfor %}: return true
+            default: return false
+            }
+        }
+    ) {
+    {%- when None %}
+    {%- endmatch %}
     return {% call swift::try(func) %} await uniffiRustCallAsync(
         rustFutureFunc: {
             {{ func.ffi_func().name() }}(
                 {%- for arg in func.arguments() %}
-                {{ arg|lower_fn }}({{ arg.name()|var_name }}){% if !loop.last %},{% endif %}
+                {{ arg|lower_fn }}({% call swift::arg_name_lowered(func, arg, loop.first) %}){% if !loop.last %},{% endif %}
+                {%- endfor %}
+            )
+        },
+        pollFunc: {{ func.ffi_rust_future_poll(ci) }},
+        completeFunc: {{ func.ffi_rust_future_complete(ci) }},
+        freeFunc: {{ func.ffi_rust_future_free(ci) }},
+        {%- match func.return_type() %}
+        {%- when Some(return_type) %}
+        liftFunc: {{ return_type|lift_fn }},
+        {%- when None %}
+        liftFunc: { $0 },
+        {%- endmatch %}
+        {%- match func.throws_type() %}
+        {%- when Some with (e) %}
+        errorHandler: {{ e|ffi_converter_name }}.lift,
+        cancelFunc: {{ func.ffi_rust_future_cancel(ci) }}
+        {%- else %}
+        errorHandler: nil,
+        cancelFunc: nil
+        {% endmatch %}
+    )
+    {%- if func.retries().is_some() %}
+    }
+    {%- endif %}
+}
+{%- if func.is_cancellable() %}
+
+/**
+ * Cancellable variant of `{{ func.name()|fn_name }}`.
+ *
+ * Call `value()` on the returned handle for the result, or `cancel()` to cancel the call.
+ */
+{%- call swift::deprecated(func, 0) %}
+public {% if func.namespace_object().is_some() %}static {% endif %}func {{ "{}_cancellable"|format(func.name())|fn_name }}({%- call swift::arg_list_decl(func) -%}) -> UniffiCancellableHandle<{% match func.return_type() %}{% when Some with (return_type) %}{{ return_type|type_name }}{% when None %}Void{% endmatch %}> {
+    return uniffiRustCallCancellable(
+        rustFutureFunc: {
+            {{ func.ffi_func().name() }}(
+                {%- for arg in func.arguments() %}
+                {{ arg|lower_fn }}({% call swift::arg_name_lowered(func, arg, loop.first) %}){% if !loop.last %},{% endif %}
                 {%- endfor %}
             )
         },
         pollFunc: {{ func.ffi_rust_future_poll(ci) }},
         completeFunc: {{ func.ffi_rust_future_complete(ci) }},
         freeFunc: {{ func.ffi_rust_future_free(ci) }},
+        cancelFunc: {{ func.ffi_rust_future_cancel(ci) }},
         {%- match func.return_type() %}
         {%- when Some(return_type) %}
         liftFunc: {{ return_type|lift_fn }},
@@ -27,25 +94,131 @@ public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) a
         {% endmatch %}
     )
 }
+{%- endif %}
+{%- match ci.progress(func) %}
+{%- when Some with (progress) %}
+{%- let progress_class = "Uniffi{}Progress"|format(func.name()|class_name) %}
+
+/**
+ * Variant of `{{ func.name()|fn_name }}` which passes each progress event to the `{{ progress.argument().name()|var_name }}` closure.
+ *
+ * The events are passed in order, from the task which awaits the call, and all of them have been
+ * passed when this returns.
+ */
+{%- call swift::deprecated(func, 0) %}
+public {% if func.namespace_object().is_some() %}static {% endif %}func {{ "{}_with_progress"|format(func.name())|fn_name }}(
+    {%- for arg in func.arguments() -%}
+    {%- call swift::arg_label_decl(loop.first && config.omit_first_argument_label()) %}{{ arg.name()|var_name }}: {% if arg.is_progress() %}@escaping ({{ progress.event_type()|type_name }}) -> Void{% else %}{{ arg|type_name }}{% endif %}
+    {%- if !loop.last %}, {% endif -%}
+    {%- endfor -%}
+) async {% call swift::throws(func) %}{% match func.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+    return {% call swift::try(func) %} await uniffiCallWithProgress({{ progress.argument().name()|var_name }}) { continuation in
+        {% if func.throws() %}try {% endif %}await {{ func.name()|fn_name }}(
+            {%- for arg in func.arguments() -%}
+            {%- call swift::arg_label_call(arg, loop.first && config.omit_first_argument_label()) %}{% if arg.is_progress() %}{{ progress_class }}(continuation){% else %}{{ arg.name()|var_name }}{% endif %}
+            {%- if !loop.last %}, {% endif -%}
+            {%- endfor -%}
+        )
+    }
+}
+
+// Passes the progress events of `{{ "{}_with_progress"|format(func.name())|fn_name }}` to its stream.
+fileprivate final class {{ progress_class }}: {{ progress.listener_type()|type_name }} {
+    private let continuation: AsyncStream<{{ progress.event_type()|type_name }}>.Continuation
+
+    init(_ continuation: AsyncStream<{{ progress.event_type()|type_name }}>.Continuation) {
+        self.continuation = continuation
+    }
+
+    func {{ progress.event_method().name()|fn_name }}({% call swift::arg_list_protocol(progress.event_method()) %}) {
+        continuation.yield({{ progress.event_argument().name()|var_name }})
+    }
+}
+{%- when None %}
+{%- endmatch %}
 
 {% else %}
 
+{%- if func.has_out_args() %}
+{%- let outputs = func.out_params_outputs() %}
+
+{%- call swift::callable_docstring(func, 0) %}
+{%- call swift::deprecated(func, 0) %}
+public {% if func.namespace_object().is_some() %}static {% endif %}func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) {% call swift::throws(func) %} -> {% if outputs.len() > 1 %}({% for (name, type_) in outputs.iter() %}{{ name|var_name }}: {{ type_|type_name }}{% if !loop.last %}, {% endif %}{% endfor %}){% else %}{% for (name, type_) in outputs.iter() %}{{ type_|type_name }}{% endfor %}{% endif %} {
+    {%- call swift::check_main_thread(func) %}
+    return {% call swift::try(func) %} uniffiLiftOutParams(
+        {% call swift::to_ffi_call(func) %}
+    ) { reader in
+        {%- if outputs.len() > 1 %}
+        (
+            {%- for (name, type_) in outputs.iter() %}
+            {{ name|var_name }}: try {{ type_|read_fn }}(from: &reader){% if !loop.last %},{% endif %}
+            {%- endfor %}
+        )
+        {%- else %}
+        {%- for (name, type_) in outputs.iter() %}
+        try {{ type_|read_fn }}(from: &reader)
+        {%- endfor %}
+        {%- endif %}
+    }
+}
+{%- else %}
+
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
 
-{%- call swift::docstring(func, 0) %}
-public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) {% call swift::throws(func) %} -> {{ return_type|type_name }} {
+{%- call swift::callable_docstring(func, 0) %}
+{%- call swift::deprecated(func, 0) %}
+public {% if func.namespace_object().is_some() %}static {% endif %}func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) {% call swift::throws(func) %} -> {% if func.is_with_warnings() %}WithWarnings<{{ return_type|type_name }}>{% else %}{{ return_type|type_name }}{% endif %} {
+    {%- call swift::check_main_thread(func) %}
+    {%- if func.is_chunked() %}
+    return {% call swift::try(func) %} uniffiLiftChunkedSequence(
+        {% call swift::to_ffi_call(func) %},
+        {{ return_type|ffi_converter_name }}.self
+    )
+    {%- else if func.is_with_warnings() %}
+    return {% call swift::try(func) %} uniffiLiftWithWarnings(
+        {% call swift::to_ffi_call(func) %},
+        {{ return_type|ffi_converter_name }}.self
+    )
+    {%- else if func.is_segmented() %}
+    return {% call swift::try(func) %} uniffiLiftByteSegments(
+        {% call swift::to_ffi_call(func) %}
+    )
+    {%- else %}
     return {% call swift::try(func) %} {{ return_type|lift_fn }}(
         {% call swift::to_ffi_call(func) %}
     )
+    {%- endif %}
 }
 
 {%- when None %}
 
-{%- call swift::docstring(func, 0) %}
-public func {{ func.name()|fn_name }}({% call swift::arg_list_decl(func) %}) {% call swift::throws(func) %} {
+{%- call swift::callable_docstring(func, 0) %}
+{%- call swift::deprecated(func, 0) %}
+public {% if func.namespace_object().is_some() %}static {% endif %}func {{ func.name()|fn_name }}({% call swift::arg_list_decl(func) %}) {% call swift::throws(func) %} {
+    {%- call swift::check_main_thread(func) %}
     {% call swift::to_ffi_call(func) %}
 }
 
 {% endmatch %}
 {%- endif %}
+{%- endif %}
+{%- match func.default_on_none() %}
+{%- when Some with ((default, return_type)) %}
+
+/**
+ * Variant of `{{ func.name()|fn_name }}` which returns `{{ default|literal_swift(return_type) }}` rather than `nil`.
+ */
+public {% if func.namespace_object().is_some() %}static {% endif %}func {{ "{}_or_default"|format(func.name())|fn_name }}({%- call swift::arg_list_decl(func) -%}) {% call swift::throws(func) %} -> {{ return_type|type_name }} {
+    return {% call swift::try(func) %} {{ func.name()|fn_name }}({% call swift::arg_list_forwarded(func) %}) ?? {{ default|literal_swift(return_type) }}
+}
+{%- when None %}
+{%- endmatch %}
+{%- if func.is_extension() %}
+}
+{%- endif %}
+{%- if func.target().is_some() %}
+#endif
+{%- endif %}
+{%- endif %}
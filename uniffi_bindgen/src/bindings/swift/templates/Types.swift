@@ -61,6 +61,21 @@
 {%- when Type::Duration %}
 {%- include "DurationHelper.swift" %}
 
+{%- when Type::DurationRange { inclusive } %}
+{%- include "DurationRangeHelper.swift" %}
+
+{%- when Type::Path %}
+{%- include "PathHelper.swift" %}
+
+{%- when Type::Json %}
+{%- include "JsonHelper.swift" %}
+
+{%- when Type::DynError %}
+{%- include "DynErrorHelper.swift" %}
+
+{%- when Type::OpaqueHandle %}
+{%- include "OpaqueHandleHelper.swift" %}
+
 {%- when Type::CallbackInterface { name, module_path } %}
 {%- include "CallbackInterfaceTemplate.swift" %}
 
@@ -84,6 +99,9 @@
 {%- when Type::Optional { inner_type } %}
 {%- include "OptionalTemplate.swift" %}
 
+{%- when Type::NonZero { inner_type } %}
+{%- include "NonZeroTemplate.swift" %}
+
 {%- when Type::Sequence { inner_type } %}
 {%- include "SequenceTemplate.swift" %}
 
@@ -0,0 +1,30 @@
+/**
+ * A value returned along with a list of non-fatal warnings.
+ */
+public struct WithWarnings<Value> {
+    public let value: Value
+    public let warnings: [String]
+
+    public init(value: Value, warnings: [String]) {
+        self.value = value
+        self.warnings = warnings
+    }
+}
+
+extension WithWarnings: Equatable where Value: Equatable {}
+
+// Lift the value returned by a function with warnings, which is followed by the warnings in the
+// same buffer.
+private func uniffiLiftWithWarnings<Converter: FfiConverter>(
+    _ buf: RustBuffer,
+    _ converter: Converter.Type
+) throws -> WithWarnings<Converter.SwiftType> {
+    var reader = createReader(data: Data(rustBuffer: buf))
+    let value = try converter.read(from: &reader)
+    let warnings = try FfiConverterSequenceString.read(from: &reader)
+    if hasRemaining(reader) {
+        throw UniffiInternalError.incompleteData
+    }
+    buf.deallocate()
+    return WithWarnings(value: value, warnings: warnings)
+}
@@ -30,28 +30,126 @@
 }
 {%- endmacro %}
 
+{#-
+// Call the Rust default implementation of a trait method, passing `self` as the trait object.
+-#}
+{%- macro to_ffi_default_call(meth) -%}
+{% call try(meth) %}
+    {%- match meth.throws_type() %}
+    {%- when Some with (e) %}
+    rustCallWithError({{ e|ffi_converter_name }}.lift) {
+    {%- else %}
+    rustCall() {
+    {% endmatch %}
+    {{ meth.ffi_default_func().unwrap().name() }}(
+        {{- ffi_converter_name }}.lower(self), {% call arg_list_lowered(meth) -%} $0
+    )
+}
+{%- endmacro %}
+
+{#-
+// Call an async constructor, producing the raw object pointer.
+-#}
+{%- macro async_constructor_call(cons) -%}
+uniffiRustCallAsync(
+            rustFutureFunc: {
+                {{ cons.ffi_func().name() }}(
+                    {%- for arg in cons.arguments() %}
+                    {{ arg|lower_fn }}({{ arg.name()|var_name }}){% if !loop.last %},{% endif %}
+                    {%- endfor %}
+                )
+            },
+            pollFunc: {{ cons.ffi_rust_future_poll(ci) }},
+            completeFunc: {{ cons.ffi_rust_future_complete(ci) }},
+            freeFunc: {{ cons.ffi_rust_future_free(ci) }},
+            liftFunc: { $0 },
+            {%- match cons.throws_type() %}
+            {%- when Some with (e) %}
+            errorHandler: {{ e|ffi_converter_name }}.lift,
+            cancelFunc: {{ cons.ffi_rust_future_cancel(ci) }}
+            {%- else %}
+            errorHandler: nil,
+            cancelFunc: nil
+            {% endmatch %}
+        )
+{%- endmacro %}
+
+{#-
+// The receiver of an extension function is its first argument, which the body refers to as `self`.
+-#}
 {%- macro arg_list_lowered(func) %}
     {%- for arg in func.arguments() %}
-        {{ arg|lower_fn }}({{ arg.name()|var_name }}),
+        {%- if arg.is_segmented() %}
+        uniffiLowerByteSegments({% call arg_name_lowered(func, arg, loop.first) %}),
+        {%- else %}
+        {{ arg|lower_fn }}({% call arg_name_lowered(func, arg, loop.first) %}),
+        {%- endif %}
     {%- endfor %}
 {%- endmacro -%}
 
+{%- macro arg_name_lowered(func, arg, first) -%}
+{%- if first && func.is_extension() %}self{% else %}{{ arg.name()|var_name }}{% endif -%}
+{%- endmacro -%}
+
 {#-
 // Arglist as used in Swift declarations of methods, functions and constructors.
 // Note the var_name and type_name filters.
 -#}
 
 {% macro arg_list_decl(func) %}
+    {%- call _arg_list_decl(func, config.omit_first_argument_label()) %}
+{%- endmacro %}
+
+{#-
+// Initializers always label their first argument, like `init(name:)`.
+-#}
+{% macro arg_list_init_decl(func) %}
+    {%- call _arg_list_decl(func, false) %}
+{%- endmacro %}
+
+{#-
+// An extension function doesn't declare its first argument, which is the receiver, so the first
+// declared argument is the second one.
+-#}
+{% macro _arg_list_decl(func, omit_first_label) %}
     {%- for arg in func.arguments() -%}
-        {% if config.omit_argument_labels() %}_ {% endif %}{{ arg.name()|var_name }}: {{ arg|type_name -}}
+        {%- if !(loop.first && func.is_extension()) %}
+        {%- let first_declared = loop.first || (loop.index == 2 && func.is_extension()) %}
+        {%- match arg.variadic_type() %}
+        {%- when Some with (item_type) %}
+        {%- call variadic_arg_decl(arg, item_type) %}
+        {%- when None %}
+        {%- call arg_label_decl(first_declared && omit_first_label) %}{{ arg.name()|var_name }}: {{ arg|type_name -}}
         {%- match arg.default_value() %}
         {%- when Some with(literal) %} = {{ literal|literal_swift(arg) }}
         {%- else %}
         {%- endmatch %}
+        {%- endmatch %}
         {%- if !loop.last %}, {% endif -%}
+        {%- endif %}
     {%- endfor %}
 {%- endmacro %}
 
+{#-
+// The arguments declared by `arg_list_decl`, passed on to another function with the same ones.
+-#}
+{% macro arg_list_forwarded(func) %}
+    {%- for arg in func.arguments() -%}
+        {%- if !(loop.first && func.is_extension()) %}
+        {%- let first_declared = loop.first || (loop.index == 2 && func.is_extension()) %}
+        {%- if !(first_declared && config.omit_first_argument_label()) %}{{ arg.name()|var_name }}: {% endif %}{{ arg.name()|var_name }}
+        {%- if !loop.last %}, {% endif -%}
+        {%- endif %}
+    {%- endfor %}
+{%- endmacro %}
+
+{#-
+// A variadic argument is always unlabelled. The function body sees it as an array, which is
+// lowered like any other sequence.
+-#}
+{%- macro variadic_arg_decl(arg, item_type) %}_ {{ arg.name()|var_name }}: {{ item_type|type_name }}...
+{%- endmacro %}
+
 {#-
 // Field lists as used in Swift declarations of Records and Enums.
 // Note the var_name and type_name filters.
@@ -71,12 +169,35 @@
 
 {% macro arg_list_protocol(func) %}
     {%- for arg in func.arguments() -%}
-        {% if config.omit_argument_labels() %}_ {% endif %}{{ arg.name()|var_name }}: {{ arg|type_name -}}
+        {%- if !(loop.first && func.is_extension()) %}
+        {%- let first_declared = loop.first || (loop.index == 2 && func.is_extension()) %}
+        {%- match arg.variadic_type() %}
+        {%- when Some with (item_type) %}
+        {%- call variadic_arg_decl(arg, item_type) %}
+        {%- when None %}
+        {%- call arg_label_decl(first_declared && config.omit_first_argument_label()) %}{{ arg.name()|var_name }}: {{ arg|type_name -}}
+        {%- endmatch %}
         {%- if !loop.last %}, {% endif -%}
+        {%- endif %}
     {%- endfor %}
 {%- endmacro %}
 
 
+{#-
+// The argument label in a declaration, which is `_` if labels are omitted.  Otherwise the
+// argument name is also used as its label.
+-#}
+{%- macro arg_label_decl(omit_label) %}
+{%- if config.omit_argument_labels() || omit_label %}_ {% endif %}
+{%- endmacro -%}
+
+{#-
+// The argument label when calling a function with `arg_list_protocol`.
+-#}
+{%- macro arg_label_call(arg, omit_label) %}
+{%- if !config.omit_argument_labels() && !omit_label %}{{ arg.name()|arg_name }}: {% endif %}
+{%- endmacro -%}
+
 {%- macro async(func) %}
 {%- if func.is_async() %}async {% endif %}
 {%- endmacro -%}
@@ -100,3 +221,98 @@
 {%- macro docstring(defn, indent_spaces) %}
 {%- call docstring_value(defn.docstring(), indent_spaces) %}
 {%- endmacro %}
+
+{%- macro type_docstring(defn, indent_spaces) %}
+{%- call docstring_value(defn.docstring()|with_since(defn.since()), indent_spaces) %}
+{%- endmacro %}
+
+{%- macro callable_docstring(callable, indent_spaces) %}
+{%- call docstring_value(callable|callable_docstring, indent_spaces) %}
+{%- endmacro %}
+
+{#-
+// Objects, their constructors and their methods document who owns the Rust object when the
+// `ownership_docs` config is set.
+-#}
+{%- macro object_docstring(obj, indent_spaces) %}
+{%- if config.ownership_docs() %}
+{%- call docstring_value(obj|object_ownership_docstring, indent_spaces) %}
+{%- else %}
+{%- call type_docstring(obj, indent_spaces) %}
+{%- endif %}
+{%- endmacro %}
+
+{%- macro constructor_docstring(cons, indent_spaces) %}
+{%- if config.ownership_docs() %}
+{%- call docstring_value(cons|constructor_ownership_docstring, indent_spaces) %}
+{%- else %}
+{%- call callable_docstring(cons, indent_spaces) %}
+{%- endif %}
+{%- endmacro %}
+
+{%- macro method_docstring(meth, indent_spaces) %}
+{%- if config.ownership_docs() && !meth.is_enum_method() %}
+{%- call docstring_value(meth|method_ownership_docstring, indent_spaces) %}
+{%- else %}
+{%- call callable_docstring(meth, indent_spaces) %}
+{%- endif %}
+{%- endmacro %}
+
+{#-
+// Traps if a `[MainThread]` function is called from another thread.
+-#}
+{%- macro check_main_thread(func) %}
+{%- if func.is_main_thread() %}
+    dispatchPrecondition(condition: .onQueue(.main))
+{%- endif %}
+{%- endmacro %}
+
+{%- macro deprecated(defn, indent_spaces) %}
+{%- match defn.deprecated() %}
+{%- when Some(message) %}
+{{ message|deprecated(indent_spaces) }}
+{%- else %}
+{%- endmatch %}
+{%- endmacro %}
+
+{#-
+// Read or write an enum's discriminant, which is an `Int32` unless the enum sets its
+// `[Discriminant]`.  `readInt()` is big-endian, so little-endian discriminants are byte-swapped.
+-#}
+{%- macro read_discriminant(e) -%}
+{%- match e.discriminant_encoding() -%}
+{%- when Some with (encoding) -%}
+{%- if encoding.width() == 1 -%}
+Int32(readInt(&buf) as UInt8)
+{%- else if encoding.width() == 2 && encoding.is_little_endian() -%}
+Int32((readInt(&buf) as UInt16).byteSwapped)
+{%- else if encoding.width() == 2 -%}
+Int32(readInt(&buf) as UInt16)
+{%- else if encoding.is_little_endian() -%}
+(readInt(&buf) as Int32).byteSwapped
+{%- else -%}
+readInt(&buf) as Int32
+{%- endif -%}
+{%- when None -%}
+readInt(&buf) as Int32
+{%- endmatch -%}
+{%- endmacro -%}
+
+{%- macro write_discriminant(e, value) -%}
+{%- match e.discriminant_encoding() -%}
+{%- when Some with (encoding) -%}
+{%- if encoding.width() == 1 -%}
+writeInt(&buf, UInt8(truncatingIfNeeded: {{ value }}))
+{%- else if encoding.width() == 2 && encoding.is_little_endian() -%}
+writeInt(&buf, UInt16(truncatingIfNeeded: {{ value }}).byteSwapped)
+{%- else if encoding.width() == 2 -%}
+writeInt(&buf, UInt16(truncatingIfNeeded: {{ value }}))
+{%- else if encoding.is_little_endian() -%}
+writeInt(&buf, Int32({{ value }}).byteSwapped)
+{%- else -%}
+writeInt(&buf, Int32({{ value }}))
+{%- endif -%}
+{%- when None -%}
+writeInt(&buf, Int32({{ value }}))
+{%- endmatch -%}
+{%- endmacro -%}
@@ -1,3 +1,7 @@
+{%- match config.file_header() %}{% when Some with (header) %}{{ header }}
+{% when None %}{% endmatch -%}
+{% if config.hash_header() %}// {{ ci.hash_header() }}
+{% endif -%}
 // This file was autogenerated by some hot garbage in the `uniffi` crate.
 // Trust me, you don't want to mess with it!
 
@@ -26,13 +30,46 @@ import {{ config.ffi_module_name() }}
 {% include "Async.swift" %}
 {%- endif %}
 
+{%- if ci.has_chunked_fns() %}
+{% include "ChunkedSequence.swift" %}
+{%- endif %}
+
+{%- if ci.has_with_warnings_fns() %}
+{% include "WithWarnings.swift" %}
+{%- endif %}
+
+{%- if ci.has_out_params_fns() %}
+{% include "OutParams.swift" %}
+{%- endif %}
+
+{%- if ci.has_segmented_fns() %}
+{% include "ByteSegments.swift" %}
+{%- endif %}
+
 {%- for func in ci.function_definitions() %}
+{%- if func.is_runtime_initializer() %}
+{%- include "RuntimeConfigTemplate.swift" %}
+{%- else if func.namespace_object().is_none() %}
 {%- include "TopLevelFunctionTemplate.swift" %}
+{%- endif %}
 {%- endfor %}
 
+{%- for func in ci.constant_definitions() %}
+{%- include "ConstantTemplate.swift" %}
+{%- endfor %}
+
+{%- for namespace_object in ci.namespace_objects() %}
+{%- include "NamespaceObjectTemplate.swift" %}
+{%- endfor %}
+
+{%- if ci.has_self_test() %}
+{% include "SelfTest.swift" %}
+{%- endif %}
+
 private enum InitializationResult {
     case ok
     case contractVersionMismatch
+    case componentVersionMismatch(bindings: String, scaffolding: String)
     case apiChecksumMismatch
 }
 // Use a global variables to perform the versioning checks. Swift ensures that
@@ -46,6 +83,26 @@ private var initializationResult: InitializationResult {
         return InitializationResult.contractVersionMismatch
     }
 
+    {%- match ci.component_version() %}
+    {%- when Some(bindings_component_version) %}
+    // Get the bindings component version from our ComponentInterface
+    let bindings_component_version = "{{ bindings_component_version }}"
+    // Get the scaffolding component version by calling the into the dylib
+    let component_version_buffer = {{ ci.ffi_uniffi_component_version().name() }}()
+    let scaffolding_component_version = String(
+        decoding: UnsafeBufferPointer(start: component_version_buffer.data, count: Int(component_version_buffer.len)),
+        as: UTF8.self
+    )
+    component_version_buffer.deallocate()
+    if bindings_component_version != scaffolding_component_version {
+        return InitializationResult.componentVersionMismatch(
+            bindings: bindings_component_version,
+            scaffolding: scaffolding_component_version
+        )
+    }
+    {%- when None %}
+    {%- endmatch %}
+
     {%- for (name, expected_checksum) in ci.iter_checksums() %}
     if ({{ name }}() != {{ expected_checksum }}) {
         return InitializationResult.apiChecksumMismatch
@@ -65,6 +122,8 @@ private func uniffiEnsureInitialized() {
         break
     case .contractVersionMismatch:
         fatalError("UniFFI contract version mismatch: try cleaning and rebuilding your project")
+    case let .componentVersionMismatch(bindings, scaffolding):
+        fatalError("UniFFI component version mismatch: the bindings are version \"\(bindings)\" but the library is version \"\(scaffolding)\"")
     case .apiChecksumMismatch:
         fatalError("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
     }
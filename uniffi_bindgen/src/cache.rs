@@ -0,0 +1,230 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Caching of parsed [`ComponentInterface`]s.
+//!
+//! Parsing a UDL file isn't free, and large workspaces can end up parsing the same files over
+//! and over, e.g. once for the scaffolding and once per bindings language. The functions here
+//! let the parsed `ComponentInterface` be reused when the input hasn't changed.
+//!
+//! The cache is pluggable via the [`ComponentInterfaceCache`] trait.  Caching is opt-in: the
+//! functions which don't take a cache use [`NoCache`], and callers which parse the same files
+//! repeatedly can pass a [`MemoryCache`] instead, which lives as long as they keep it.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use anyhow::Result;
+
+use crate::interface::ComponentInterface;
+
+/// Identifies a parsed `ComponentInterface` in a cache.
+///
+/// This includes the uniffi version, so that a `ComponentInterface` is never reused by a
+/// different version of `uniffi_bindgen` which might parse the UDL differently.  Keys compare the
+/// UDL itself, not just its hash, so a hash collision can't return the wrong `ComponentInterface`.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CacheKey {
+    udl_hash: u64,
+    udl: String,
+    crate_name: String,
+    uniffi_version: &'static str,
+}
+
+impl CacheKey {
+    pub fn new(udl: &str, crate_name: &str) -> Self {
+        let uniffi_version = env!("CARGO_PKG_VERSION");
+        Self {
+            udl_hash: uniffi_meta::checksum_u64(&(uniffi_version, udl)),
+            udl: udl.to_string(),
+            crate_name: crate_name.to_string(),
+            uniffi_version,
+        }
+    }
+
+    /// Hash of the UDL source and the uniffi version.
+    ///
+    /// This uses the same hasher as the API checksums, so it's the same in every process and
+    /// every build, which lets a persistent cache key its entries by it.  Such a cache must still
+    /// check [Self::udl] on a hit, since different UDL can have the same hash.
+    pub fn udl_hash(&self) -> u64 {
+        self.udl_hash
+    }
+
+    pub fn udl(&self) -> &str {
+        &self.udl
+    }
+
+    pub fn crate_name(&self) -> &str {
+        &self.crate_name
+    }
+
+    pub fn uniffi_version(&self) -> &str {
+        self.uniffi_version
+    }
+}
+
+/// A store for parsed `ComponentInterface`s.
+pub trait ComponentInterfaceCache: Send + Sync {
+    /// Get the `ComponentInterface` stored for `key`, if any.
+    fn get(&self, key: &CacheKey) -> Option<ComponentInterface>;
+
+    /// Store a `ComponentInterface` for `key`.
+    fn insert(&self, key: CacheKey, ci: &ComponentInterface);
+}
+
+/// In-memory `ComponentInterfaceCache`.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<CacheKey, ComponentInterface>>,
+}
+
+impl MemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of `ComponentInterface`s stored in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ComponentInterfaceCache for MemoryCache {
+    fn get(&self, key: &CacheKey) -> Option<ComponentInterface> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    fn insert(&self, key: CacheKey, ci: &ComponentInterface) {
+        self.entries.lock().unwrap().insert(key, ci.clone());
+    }
+}
+
+/// `ComponentInterfaceCache` which doesn't store anything, used when no other cache is specified.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoCache;
+
+impl ComponentInterfaceCache for NoCache {
+    fn get(&self, _key: &CacheKey) -> Option<ComponentInterface> {
+        None
+    }
+
+    fn insert(&self, _key: CacheKey, _ci: &ComponentInterface) {}
+}
+
+/// Parse a `ComponentInterface` from a string containing a UDL definition, reusing a previously
+/// parsed `ComponentInterface` from `cache` if there is one.
+pub fn parse_udl(
+    udl: &str,
+    crate_name: &str,
+    cache: &dyn ComponentInterfaceCache,
+) -> Result<ComponentInterface> {
+    let key = CacheKey::new(udl, crate_name);
+    if let Some(ci) = cache.get(&key) {
+        return Ok(ci);
+    }
+    let ci = ComponentInterface::from_webidl(udl, crate_name)?;
+    cache.insert(key, &ci);
+    Ok(ci)
+}
+
+/// A `MemoryCache` which counts its hits, for tests.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct CountingCache {
+    pub(crate) inner: MemoryCache,
+    pub(crate) hits: std::sync::atomic::AtomicUsize,
+}
+
+#[cfg(test)]
+impl ComponentInterfaceCache for CountingCache {
+    fn get(&self, key: &CacheKey) -> Option<ComponentInterface> {
+        let ci = self.inner.get(key);
+        if ci.is_some() {
+            self.hits.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+        ci
+    }
+
+    fn insert(&self, key: CacheKey, ci: &ComponentInterface) {
+        self.inner.insert(key, ci)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::Ordering;
+
+    const UDL: &str = r#"
+        namespace test {
+            u32 get_u32();
+        };
+    "#;
+
+    #[test]
+    fn test_second_parse_hits_cache() {
+        let cache = CountingCache::default();
+        let ci = parse_udl(UDL, "crate_name", &cache).unwrap();
+        assert_eq!(cache.hits.load(Ordering::Relaxed), 0);
+        assert_eq!(cache.inner.len(), 1);
+
+        let cached_ci = parse_udl(UDL, "crate_name", &cache).unwrap();
+        assert_eq!(cache.hits.load(Ordering::Relaxed), 1);
+        assert_eq!(cache.inner.len(), 1);
+        assert_eq!(
+            ci.function_definitions().len(),
+            cached_ci.function_definitions().len()
+        );
+        assert!(cached_ci.get_function_definition("get_u32").is_some());
+    }
+
+    #[test]
+    fn test_cache_key() {
+        let key = CacheKey::new(UDL, "crate_name");
+        assert_eq!(key, CacheKey::new(UDL, "crate_name"));
+        assert_ne!(key, CacheKey::new(UDL, "other_crate"));
+        assert_ne!(key.udl_hash(), CacheKey::new("", "crate_name").udl_hash());
+        assert_eq!(key.uniffi_version(), env!("CARGO_PKG_VERSION"));
+
+        // Changing the UDL means a different `ComponentInterface` gets parsed.
+        let cache = MemoryCache::new();
+        parse_udl(UDL, "crate_name", &cache).unwrap();
+        let ci = parse_udl(
+            "namespace test { u32 get_other_u32(); };",
+            "crate_name",
+            &cache,
+        )
+        .unwrap();
+        assert_eq!(cache.len(), 2);
+        assert!(ci.get_function_definition("get_u32").is_none());
+    }
+
+    #[test]
+    fn test_hash_collision_misses() {
+        let cache = MemoryCache::new();
+        let key = CacheKey::new(UDL, "crate_name");
+        cache.insert(
+            key.clone(),
+            &parse_udl(UDL, "crate_name", &NoCache).unwrap(),
+        );
+        // A key for different UDL which happens to have the same hash.
+        let colliding_key = CacheKey {
+            udl: "namespace test {};".to_string(),
+            ..key.clone()
+        };
+        assert_ne!(key, colliding_key);
+        assert!(cache.get(&colliding_key).is_none());
+        assert!(cache.get(&key).is_some());
+    }
+
+    #[test]
+    fn test_no_cache() {
+        parse_udl(UDL, "crate_name", &NoCache).unwrap();
+        assert!(NoCache.get(&CacheKey::new(UDL, "crate_name")).is_none());
+    }
+}
@@ -0,0 +1,450 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Report which Rust items ended up in the generated bindings.
+//!
+//! This is intended to help audit what a component exports.  Each exported item is listed with the
+//! Rust module path it was defined in, which comes from the UDL crate name or the proc-macro
+//! metadata, and whether it can be reached from the namespace.
+//!
+//! Functions, constants and objects with a constructor are the entry points of the API, so they're
+//! always reachable.  Any other item is reachable if it's used, possibly indirectly, in the
+//! signature of an entry point.  An unreachable item still gets bindings generated for it, but
+//! foreign code has no way to get hold of a value of that type.
+//!
+//! External types aren't exported by this component, they're listed separately so it's clear
+//! which other crates the bindings depend on.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use anyhow::Result;
+use camino::Utf8Path;
+use uniffi_meta::{AsType, ExternalKind, Type};
+
+use crate::interface::ComponentInterface;
+use crate::metadata_artifact::MetadataArtifact;
+
+/// The kind of an exported item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ItemKind {
+    Function,
+    Constant,
+    Object,
+    Record,
+    Enum,
+    CallbackInterface,
+    CustomType,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ItemKind::Function => "function",
+            ItemKind::Constant => "constant",
+            ItemKind::Object => "object",
+            ItemKind::Record => "record",
+            ItemKind::Enum => "enum",
+            ItemKind::CallbackInterface => "callback interface",
+            ItemKind::CustomType => "custom type",
+        })
+    }
+}
+
+/// An item exported by the component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedItem {
+    pub kind: ItemKind,
+    pub name: String,
+    /// The Rust module path the item was defined in.
+    pub module_path: String,
+    /// Can foreign code get to this item from the namespace?
+    pub reachable: bool,
+}
+
+/// A type from another crate which is used by the component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalTypeItem {
+    pub name: String,
+    /// The Rust module path of the crate which exports the type.
+    pub module_path: String,
+    pub kind: ExternalKind,
+}
+
+/// The items exported by a component, and the external types it uses.
+#[derive(Debug, Clone)]
+pub struct CoverageReport {
+    pub namespace: String,
+    /// The exported items, sorted by module path.
+    pub items: Vec<ExportedItem>,
+    pub external_types: Vec<ExternalTypeItem>,
+}
+
+impl CoverageReport {
+    pub fn item(&self, kind: ItemKind, name: &str) -> Option<&ExportedItem> {
+        self.items
+            .iter()
+            .find(|item| item.kind == kind && item.name == name)
+    }
+
+    /// The exported items which can't be reached from the namespace.
+    pub fn unreachable_items(&self) -> impl Iterator<Item = &ExportedItem> {
+        self.items.iter().filter(|item| !item.reachable)
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Coverage report for `{}`", self.namespace)?;
+        writeln!(f)?;
+        writeln!(f, "Exported items:")?;
+        if self.items.is_empty() {
+            writeln!(f, "  (none)")?;
+        }
+        let kind_width = self
+            .items
+            .iter()
+            .map(|item| item.kind.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let name_width = self
+            .items
+            .iter()
+            .map(|item| item.name.len())
+            .max()
+            .unwrap_or(0);
+        for item in &self.items {
+            writeln!(
+                f,
+                "  {:kind_width$}  {:name_width$}  {}{}",
+                item.kind.to_string(),
+                item.name,
+                item.module_path,
+                if item.reachable { "" } else { " (unreachable)" },
+            )?;
+        }
+        if !self.external_types.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "External types:")?;
+            let name_width = self
+                .external_types
+                .iter()
+                .map(|ty| ty.name.len())
+                .max()
+                .unwrap_or(0);
+            for ty in &self.external_types {
+                let kind = match ty.kind {
+                    ExternalKind::Interface => "interface",
+                    ExternalKind::Trait => "trait",
+                    ExternalKind::DataClass => "data class",
+                    ExternalKind::ScalarEnum => "enum",
+                };
+                writeln!(f, "  {:name_width$}  {} ({kind})", ty.name, ty.module_path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Build a coverage report for `ci`.
+pub fn coverage_report(ci: &ComponentInterface) -> CoverageReport {
+    let reachable = reachable_types(ci);
+    let is_reachable = |name: &str| reachable.contains(name);
+
+    let mut items = Vec::new();
+    for func in ci.function_definitions() {
+        items.push(item(
+            ItemKind::Function,
+            func.name(),
+            func.module_path(),
+            true,
+        ));
+    }
+    for constant in ci.constant_definitions() {
+        items.push(item(
+            ItemKind::Constant,
+            constant.name(),
+            constant.module_path(),
+            true,
+        ));
+    }
+    for obj in ci.object_definitions() {
+        let reachable = is_reachable(obj.name());
+        items.push(item(
+            ItemKind::Object,
+            obj.name(),
+            obj.module_path(),
+            reachable,
+        ));
+    }
+    for rec in ci.record_definitions() {
+        let reachable = is_reachable(rec.name());
+        items.push(item(
+            ItemKind::Record,
+            rec.name(),
+            rec.module_path(),
+            reachable,
+        ));
+    }
+    for e in ci.enum_definitions() {
+        items.push(item(
+            ItemKind::Enum,
+            e.name(),
+            e.module_path(),
+            is_reachable(e.name()),
+        ));
+    }
+    for cbi in ci.callback_interface_definitions() {
+        let reachable = is_reachable(cbi.name());
+        items.push(item(
+            ItemKind::CallbackInterface,
+            cbi.name(),
+            cbi.module_path(),
+            reachable,
+        ));
+    }
+
+    let mut external_types = Vec::new();
+    for ty in ci.iter_types() {
+        match ty {
+            Type::Custom {
+                name, module_path, ..
+            } => items.push(item(
+                ItemKind::CustomType,
+                name,
+                module_path,
+                is_reachable(name),
+            )),
+            Type::External {
+                name,
+                module_path,
+                kind,
+                ..
+            } => external_types.push(ExternalTypeItem {
+                name: name.clone(),
+                module_path: module_path.clone(),
+                kind: *kind,
+            }),
+            _ => (),
+        }
+    }
+
+    items.sort_by(|a, b| (&a.module_path, a.kind, &a.name).cmp(&(&b.module_path, b.kind, &b.name)));
+    external_types.sort_by(|a, b| (&a.module_path, &a.name).cmp(&(&b.module_path, &b.name)));
+    CoverageReport {
+        namespace: ci.namespace().to_string(),
+        items,
+        external_types,
+    }
+}
+
+/// Parse a UDL file, along with any proc-macro metadata from `library_file`, and print its
+/// coverage report.
+///
+/// `library_file` and `crate_name` work the same way as for [`crate::generate_bindings`].
+pub fn print_coverage_report(
+    udl_file: &Utf8Path,
+    library_file: Option<&Utf8Path>,
+    crate_name: Option<&str>,
+) -> Result<()> {
+    let artifact = MetadataArtifact::from_udl_file(udl_file, library_file, crate_name)?;
+    print!("{}", coverage_report(&artifact.component_interface()?));
+    Ok(())
+}
+
+fn item(kind: ItemKind, name: &str, module_path: &str, reachable: bool) -> ExportedItem {
+    ExportedItem {
+        kind,
+        name: name.to_string(),
+        module_path: module_path.to_string(),
+        reachable,
+    }
+}
+
+/// Find the names of the user-defined types that can be reached from the API entry points.
+fn reachable_types(ci: &ComponentInterface) -> HashSet<String> {
+    let mut pending: Vec<Type> = ci
+        .function_definitions()
+        .iter()
+        .chain(ci.constant_definitions())
+        .flat_map(|f| f.iter_types())
+        .cloned()
+        .collect();
+    pending.extend(
+        ci.object_definitions()
+            .iter()
+            .filter(|obj| !obj.constructors().is_empty())
+            .map(AsType::as_type),
+    );
+
+    let mut seen = HashSet::new();
+    while let Some(ty) = pending.pop() {
+        let nested = match &ty {
+            Type::Record { name, .. }
+            | Type::Enum { name, .. }
+            | Type::Object { name, .. }
+            | Type::CallbackInterface { name, .. }
+            | Type::Custom { name, .. } => {
+                if !seen.insert(name.clone()) {
+                    continue;
+                }
+                match &ty {
+                    Type::Record { .. } => ci.get_record_definition(name).map(|r| r.iter_types()),
+                    Type::Enum { .. } => ci.get_enum_definition(name).map(|e| e.iter_types()),
+                    Type::Object { .. } => ci.get_object_definition(name).map(|o| o.iter_types()),
+                    Type::CallbackInterface { .. } => ci
+                        .get_callback_interface_definition(name)
+                        .map(|cbi| cbi.iter_types()),
+                    Type::Custom { builtin, .. } => Some(builtin.iter_types()),
+                    _ => unreachable!(),
+                }
+            }
+            _ => None,
+        };
+        if let Some(nested) = nested {
+            pending.extend(nested.cloned());
+        }
+    }
+    seen
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uniffi_meta::{FieldMetadata, FnMetadata, FnParamMetadata, RecordMetadata};
+
+    const UDL: &str = r#"
+        namespace test {
+            u32 add(u32 a, u32 b);
+            Remote fetch_remote();
+        };
+
+        [External="other_crate"]
+        typedef extern Remote;
+    "#;
+
+    fn record(module_path: &str, name: &str) -> RecordMetadata {
+        RecordMetadata {
+            module_path: module_path.into(),
+            name: name.into(),
+            fields: vec![FieldMetadata {
+                name: "x".into(),
+                ty: Type::Int32,
+                default: None,
+                docstring: None,
+                order: None,
+            }],
+            packed: false,
+            has_default: false,
+            docstring: None,
+            since: None,
+            deprecated: None,
+        }
+    }
+
+    #[test]
+    fn test_coverage_report() {
+        // Put the proc-macro items in different modules from the UDL items.
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let point = Type::Record {
+            module_path: "crate_name::geometry".into(),
+            name: "Point".into(),
+        };
+        ci.add_metadata(uniffi_meta::MetadataGroup {
+            namespace: uniffi_meta::NamespaceMetadata {
+                crate_name: "crate_name".into(),
+                name: "test".into(),
+            },
+            namespace_docstring: None,
+            items: [
+                record("crate_name::geometry", "Point").into(),
+                record("crate_name::unused", "Orphan").into(),
+                FnMetadata {
+                    module_path: "crate_name::geometry".into(),
+                    name: "origin".into(),
+                    is_async: false,
+                    inputs: vec![FnParamMetadata::simple("scale", Type::Int32)],
+                    return_type: Some(point),
+                    throws: None,
+                    checksum: None,
+                    docstring: None,
+                    return_docstring: None,
+                    since: None,
+                    namespace_object: None,
+                    deprecated: None,
+                    retries: None,
+                    main_thread: false,
+                    chunked: false,
+                    with_warnings: false,
+                    segmented: false,
+                    cancellable: false,
+                    target: None,
+                    extension: None,
+                    default_on_none: None,
+                    static_return: false,
+                    runtime_initializer: false,
+                }
+                .into(),
+            ]
+            .into(),
+        })
+        .unwrap();
+
+        let report = coverage_report(&ci);
+        assert_eq!(report.namespace, "test");
+        let add = report.item(ItemKind::Function, "add").unwrap();
+        assert_eq!(add.module_path, "crate_name");
+        assert!(add.reachable);
+        let origin = report.item(ItemKind::Function, "origin").unwrap();
+        assert_eq!(origin.module_path, "crate_name::geometry");
+        let point = report.item(ItemKind::Record, "Point").unwrap();
+        assert_eq!(point.module_path, "crate_name::geometry");
+        assert!(point.reachable);
+        let orphan = report.item(ItemKind::Record, "Orphan").unwrap();
+        assert_eq!(orphan.module_path, "crate_name::unused");
+        assert!(!orphan.reachable);
+        assert_eq!(report.unreachable_items().collect::<Vec<_>>(), [orphan]);
+
+        // Items are grouped by module.
+        let module_paths: Vec<_> = report.items.iter().map(|i| &i.module_path).collect();
+        assert_eq!(
+            module_paths,
+            [
+                "crate_name",
+                "crate_name",
+                "crate_name::geometry",
+                "crate_name::geometry",
+                "crate_name::unused"
+            ]
+        );
+
+        // External types aren't exported, so they get their own section.
+        assert!(report.item(ItemKind::Record, "Remote").is_none());
+        assert_eq!(
+            report.external_types,
+            [ExternalTypeItem {
+                name: "Remote".into(),
+                module_path: "other_crate".into(),
+                kind: ExternalKind::DataClass,
+            }]
+        );
+
+        assert_eq!(
+            report.to_string(),
+            "\
+Coverage report for `test`
+
+Exported items:
+  function  add           crate_name
+  function  fetch_remote  crate_name
+  function  origin        crate_name::geometry
+  record    Point         crate_name::geometry
+  record    Orphan        crate_name::unused (unreachable)
+
+External types:
+  Remote  other_crate (data class)
+"
+        );
+    }
+}
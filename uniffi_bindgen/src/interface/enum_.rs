@@ -156,8 +156,9 @@
 //! ```
 
 use anyhow::Result;
-use uniffi_meta::Checksum;
+use uniffi_meta::{Checksum, DiscriminantEncoding};
 
+use super::object::Method;
 use super::record::Field;
 use super::{AsType, Literal, Type, TypeIterator};
 
@@ -166,6 +167,8 @@ use super::{AsType, Literal, Type, TypeIterator};
 ///
 /// Enums are passed across the FFI by serializing to a bytebuffer, with a
 /// i32 indicating the variant followed by the serialization of each field.
+/// Fieldless enums that aren't errors skip the bytebuffer and are passed as
+/// that i32 on its own.
 #[derive(Debug, Clone, PartialEq, Eq, Checksum)]
 pub struct Enum {
     pub(super) name: String,
@@ -189,9 +192,20 @@ pub struct Enum {
     // * For an Enum not used as an error but which has no variants with data, `flat` will be
     //   false when generating the scaffolding but `true` when generating bindings.
     pub(super) flat: bool,
+    // Is this enum passed across the FFI as the i32 indicating its variant, rather than
+    // in a bytebuffer? This is true for fieldless enums, unless they were declared as errors.
+    pub(super) scalar: bool,
     pub(super) non_exhaustive: bool,
+    // The variant which unknown values are lifted as, rather than failing.
+    pub(super) default_variant: Option<String>,
+    // How the discriminant is serialized, if it's not the usual big-endian `i32`.
+    pub(super) discriminant: Option<DiscriminantEncoding>,
+    // Methods exported from an `impl` block for the enum.
+    pub(super) methods: Vec<Method>,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    #[checksum_ignore]
+    pub(super) since: Option<String>,
 }
 
 impl Enum {
@@ -199,6 +213,10 @@ impl Enum {
         &self.name
     }
 
+    pub fn module_path(&self) -> &str {
+        &self.module_path
+    }
+
     pub fn variants(&self) -> &[Variant] {
         &self.variants
     }
@@ -234,20 +252,94 @@ impl Enum {
         self.flat
     }
 
+    pub fn is_scalar(&self) -> bool {
+        self.scalar
+    }
+
     pub fn is_non_exhaustive(&self) -> bool {
         self.non_exhaustive
     }
 
+    /// The variant which unknown values are lifted as, if the enum has a `[Default]` variant.
+    pub fn default_variant(&self) -> Option<&Variant> {
+        let name = self.default_variant.as_ref()?;
+        self.variants.iter().find(|v| &v.name == name)
+    }
+
+    /// The fixed width and byte order which the discriminant is serialized with, from
+    /// `[Discriminant=...]`, or `None` if it's serialized as the usual big-endian `i32`.
+    ///
+    /// Fieldless enums are still lowered as the `i32` when they're passed directly.
+    pub fn discriminant_encoding(&self) -> Option<DiscriminantEncoding> {
+        self.discriminant
+    }
+
+    /// The number of bytes the discriminant is serialized as.
+    pub fn discriminant_width(&self) -> usize {
+        self.discriminant.map_or(4, |encoding| encoding.width())
+    }
+
+    /// Do the bindings lift unknown values as an extra `Unknown` variant, which holds the
+    /// discriminant?  That's the case for non-exhaustive enums without a `[Default]` variant, so
+    /// bindings generated before a variant was added can still lift it.
+    pub fn has_unknown_variant(&self) -> bool {
+        self.non_exhaustive && self.default_variant.is_none()
+    }
+
+    /// Methods which the bindings call on a value of the enum, by passing the value to Rust.
+    pub fn methods(&self) -> Vec<&Method> {
+        self.methods.iter().collect()
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
-        Box::new(self.variants.iter().flat_map(Variant::iter_types))
+        Box::new(
+            self.variants
+                .iter()
+                .flat_map(Variant::iter_types)
+                .chain(self.methods.iter().flat_map(Method::iter_types)),
+        )
+    }
+
+    /// The fields which every variant has, with the same name and type, in the order they're
+    /// declared in the first variant.
+    ///
+    /// The bindings for errors expose these on the error type itself, so they can be read
+    /// without matching on the variant.
+    pub fn common_fields(&self) -> Vec<&Field> {
+        match self.variants.split_first() {
+            Some((first, rest)) => first
+                .fields()
+                .iter()
+                .filter(|field| {
+                    rest.iter()
+                        .all(|v| v.field(field.name()) == Some(field.as_type()))
+                })
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    pub fn is_common_field(&self, field: &Field) -> bool {
+        self.common_fields()
+            .iter()
+            .any(|common| common.name() == field.name())
     }
 
     pub fn docstring(&self) -> Option<&str> {
         self.docstring.as_deref()
     }
 
+    /// The version the enum was added in, from a `@since` docstring tag.
+    pub fn since(&self) -> Option<&str> {
+        self.since.as_deref()
+    }
+
     // Sadly can't use TryFrom due to the 'is_flat' complication.
-    pub fn try_from_meta(meta: uniffi_meta::EnumMetadata, flat: bool) -> Result<Self> {
+    pub fn try_from_meta(
+        meta: uniffi_meta::EnumMetadata,
+        flat: bool,
+        scalar: bool,
+    ) -> Result<Self> {
         // This is messy - error enums are considered "flat" if the user
         // opted in via a special attribute, regardless of whether the enum
         // is actually flat.
@@ -262,8 +354,13 @@ impl Enum {
                 .map(TryInto::try_into)
                 .collect::<Result<_>>()?,
             flat,
+            scalar,
             non_exhaustive: meta.non_exhaustive,
+            default_variant: meta.default_variant,
+            discriminant: meta.discriminant,
+            methods: vec![],
             docstring: meta.docstring.clone(),
+            since: meta.since,
         })
     }
 }
@@ -287,6 +384,10 @@ pub struct Variant {
     pub(super) fields: Vec<Field>,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
+    #[checksum_ignore]
+    pub(super) retryable: bool,
 }
 
 impl Variant {
@@ -298,6 +399,14 @@ impl Variant {
         &self.fields
     }
 
+    // The type of the field with the given name, if the variant has one.
+    fn field(&self, name: &str) -> Option<Type> {
+        self.fields
+            .iter()
+            .find(|f| f.name() == name)
+            .map(|f| f.as_type())
+    }
+
     pub fn has_fields(&self) -> bool {
         !self.fields.is_empty()
     }
@@ -306,6 +415,16 @@ impl Variant {
         self.docstring.as_deref()
     }
 
+    /// The deprecation message, if the variant is deprecated.
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    /// Whether functions which are retried are retried when they throw this variant.
+    pub fn is_retryable(&self) -> bool {
+        self.retryable
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(self.fields.iter().flat_map(Field::iter_types))
     }
@@ -324,28 +443,27 @@ impl TryFrom<uniffi_meta::VariantMetadata> for Variant {
                 .map(TryInto::try_into)
                 .collect::<Result<_>>()?,
             docstring: meta.docstring.clone(),
+            deprecated: meta.deprecated,
+            retryable: meta.retryable,
         })
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::super::{ComponentInterface, FfiType};
+    use super::super::{Callable, ComponentInterface, ExternalKind, FfiType};
     use super::*;
 
     #[test]
     fn test_duplicate_variants() {
         const UDL: &str = r#"
             namespace test{};
-            // Weird, but currently allowed!
-            // We should probably disallow this...
             enum Testing { "one", "two", "one" };
         "#;
-        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
-        assert_eq!(ci.enum_definitions().count(), 1);
+        let err = ComponentInterface::from_webidl(UDL, "crate_name").unwrap_err();
         assert_eq!(
-            ci.get_enum_definition("Testing").unwrap().variants().len(),
-            3
+            err.to_string(),
+            "Duplicate variant `one` in enum `Testing` at 3:43"
         );
     }
 
@@ -441,9 +559,9 @@ mod test {
         assert_eq!(ewd.variants()[0].fields().len(), 0);
         assert_eq!(ewd.variants()[1].fields().len(), 0);
 
-        // Flat enums pass over the FFI as bytebuffers.
-        // (It might be nice to optimize these to pass as plain integers, but that's
-        // difficult atop the current factoring of `ComponentInterface` and friends).
+        // Flat enums pass over the FFI as the i32 indicating their variant.
+        assert!(e.is_scalar());
+        assert!(ewd.is_scalar());
         let farg = ci.get_function_definition("takes_an_enum").unwrap();
         assert_eq!(
             farg.arguments()[0].as_type(),
@@ -452,17 +570,14 @@ mod test {
                 module_path: "crate_name".into()
             }
         );
-        assert_eq!(
-            farg.ffi_func().arguments()[0].type_(),
-            FfiType::RustBuffer(None)
-        );
+        assert_eq!(farg.ffi_func().arguments()[0].type_(), FfiType::Int32);
         let fret = ci.get_function_definition("returns_an_enum").unwrap();
         assert!(
             matches!(fret.return_type(), Some(Type::Enum { name, .. }) if name == "TestEnum" && !ci.is_name_used_as_error(name))
         );
         assert!(matches!(
             fret.ffi_func().return_type(),
-            Some(FfiType::RustBuffer(None))
+            Some(FfiType::Int32)
         ));
 
         // Enums with associated data pass over the FFI as bytebuffers.
@@ -490,6 +605,187 @@ mod test {
             fret.ffi_func().return_type(),
             Some(FfiType::RustBuffer(None))
         ));
+        assert!(!ed.is_scalar());
+    }
+
+    #[test]
+    fn test_common_fields() {
+        const UDL: &str = r#"
+            namespace test {};
+
+            [Error]
+            interface CodedError {
+                NotFound(i32 code, string path);
+                Timeout(i32 code);
+                Invalid(string path, i32 code);
+            };
+
+            [Enum]
+            interface Mismatched {
+                One(i32 code, string path);
+                Two(u32 code, string path);
+                Three();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+
+        // The field can be anywhere in each variant.
+        let e = ci.get_enum_definition("CodedError").unwrap();
+        let common: Vec<_> = e.common_fields().iter().map(|f| f.name()).collect();
+        assert_eq!(common, vec!["code"]);
+        assert!(e.is_common_field(&e.variants()[2].fields()[1]));
+        assert!(!e.is_common_field(&e.variants()[2].fields()[0]));
+
+        // Fields must have the same type in every variant, and a variant without fields means
+        // there can't be any.
+        let e = ci.get_enum_definition("Mismatched").unwrap();
+        assert!(e.common_fields().is_empty());
+    }
+
+    #[test]
+    fn test_scalar_enums() {
+        const UDL: &str = r#"
+            namespace test {
+                [Async]
+                TestEnum returns_an_enum_async();
+                [Throws=TestError]
+                void takes_an_enum(TestEnum e, sequence<TestEnum> es);
+            };
+
+            enum TestEnum { "one", "two" };
+
+            [Error]
+            enum TestError { "oops" };
+
+            interface TestObject {
+                constructor(TestEnum e);
+                TestEnum method(TestEnum e);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        // Flat errors keep being passed in bytebuffers.
+        assert!(!ci.get_enum_definition("TestError").unwrap().is_scalar());
+        assert_eq!(
+            ci.ffi_type(
+                &ci.get_function_definition("takes_an_enum")
+                    .unwrap()
+                    .throws_type()
+                    .unwrap()
+                    .clone()
+            ),
+            FfiType::RustBuffer(None)
+        );
+
+        // Only the enum itself is a scalar, not compound types that contain it.
+        let f = ci.get_function_definition("takes_an_enum").unwrap();
+        assert_eq!(
+            f.ffi_func()
+                .arguments()
+                .iter()
+                .map(|a| a.type_())
+                .collect::<Vec<_>>(),
+            vec![FfiType::Int32, FfiType::RustBuffer(None)]
+        );
+
+        // Async functions complete with the scalar.
+        let f = ci.get_function_definition("returns_an_enum_async").unwrap();
+        assert_eq!(
+            f.ffi_rust_future_complete(&ci),
+            "ffi_crate_name_rust_future_complete_i32"
+        );
+
+        let obj = ci.get_object_definition("TestObject").unwrap();
+        assert_eq!(
+            obj.primary_constructor().unwrap().ffi_func().arguments()[0].type_(),
+            FfiType::Int32
+        );
+        let meth = obj.get_method("method");
+        assert_eq!(meth.ffi_func().arguments()[1].type_(), FfiType::Int32);
+        assert_eq!(meth.ffi_func().return_type(), Some(&FfiType::Int32));
+    }
+
+    #[test]
+    fn test_scalar_enum_used_as_error() {
+        // UDL doesn't allow this, but proc-macros can use any enum as an error.
+        const UDL: &str = r#"
+            namespace test {
+                TestEnum returns_an_enum();
+            };
+            enum TestEnum { "one", "two" };
+            enum OtherEnum { "one", "two" };
+        "#;
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        // Errors are returned in a `RustBuffer`, which holds the same i32 the enum is lowered into.
+        ci.note_name_used_as_error("OtherEnum");
+        ci.check_consistency().unwrap();
+        assert!(ci.get_enum_definition("OtherEnum").unwrap().is_scalar());
+
+        // But the bindings can't pass an error directly as that i32.
+        ci.note_name_used_as_error("TestEnum");
+        let err = ci.check_consistency().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"TestEnum\" has no fields so Rust passes it as an i32, but it's used as an error so the bindings pass it in a RustBuffer. Derive `uniffi::Error` rather than `uniffi::Enum` for it, or don't pass it directly as an argument or return value"
+        );
+    }
+
+    #[test]
+    fn test_external_scalar_enums() {
+        const UDL: &str = r#"
+            namespace test {
+                void takes_external(ExternalEnum e);
+                void takes_declared(DeclaredEnum e);
+                void takes_record(ExternalRecord r);
+            };
+            [External="other_crate"]
+            typedef extern ExternalEnum;
+            [External="other_crate", Enum]
+            typedef extern DeclaredEnum;
+            [External="other_crate"]
+            typedef extern ExternalRecord;
+        "#;
+        let arg_type = |ci: &ComponentInterface, name: &str| {
+            ci.get_function_definition(name)
+                .unwrap()
+                .ffi_func()
+                .arguments()[0]
+                .type_()
+        };
+        // From the UDL alone, only enums declared with `Enum` are passed as an i32.
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert_eq!(
+            arg_type(&ci, "takes_external"),
+            FfiType::RustBuffer(Some("ExternalEnum".to_string()))
+        );
+        assert_eq!(arg_type(&ci, "takes_declared"), FfiType::Int32);
+        assert_eq!(
+            arg_type(&ci, "takes_record"),
+            FfiType::RustBuffer(Some("ExternalRecord".to_string()))
+        );
+        // The scaffolding checks these against their `FfiConverter`.
+        assert_eq!(
+            ci.iter_external_types_passed_directly().collect::<Vec<_>>(),
+            vec![
+                ("DeclaredEnum".to_string(), ExternalKind::ScalarEnum),
+                ("ExternalEnum".to_string(), ExternalKind::DataClass),
+                ("ExternalRecord".to_string(), ExternalKind::DataClass),
+            ]
+        );
+
+        // Library mode finds the fieldless enums which weren't declared.
+        let scalar_enums = [
+            ("other_crate".to_string(), "ExternalEnum".to_string()),
+            ("other_crate".to_string(), "DeclaredEnum".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        ci.note_external_scalar_enums(&scalar_enums).unwrap();
+        assert_eq!(arg_type(&ci, "takes_external"), FfiType::Int32);
+        assert_eq!(arg_type(&ci, "takes_declared"), FfiType::Int32);
+        assert_eq!(
+            arg_type(&ci, "takes_record"),
+            FfiType::RustBuffer(Some("ExternalRecord".to_string()))
+        );
     }
 
     // Tests for [Error], which are represented as `Enum`
@@ -519,16 +815,13 @@ mod test {
     fn test_duplicate_error_variants() {
         const UDL: &str = r#"
             namespace test{};
-            // Weird, but currently allowed!
-            // We should probably disallow this...
             [Error]
             enum Testing { "one", "two", "one" };
         "#;
-        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
-        assert_eq!(ci.enum_definitions().count(), 1);
+        let err = ComponentInterface::from_webidl(UDL, "crate_name").unwrap_err();
         assert_eq!(
-            ci.get_enum_definition("Testing").unwrap().variants().len(),
-            3
+            err.to_string(),
+            "Duplicate variant `one` in enum `Testing` at 4:43"
         );
     }
 
@@ -558,6 +851,42 @@ mod test {
         assert!(ci.is_name_used_as_error(&error.name));
     }
 
+    #[test]
+    fn test_variant_fields_with_records_declared_later() {
+        const UDL: &str = r#"
+            namespace test{
+                Event get_event();
+            };
+
+            [Enum]
+            interface Event {
+                Created(User user);
+                Tagged(User user, sequence<Tag> tags);
+            };
+
+            dictionary User { string name; Tag? tag; };
+            dictionary Tag { string label; };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let event = ci.get_enum_definition("Event").unwrap();
+        assert_eq!(
+            event.variants()[0].fields()[0].as_type(),
+            Type::Record {
+                module_path: "crate_name".to_string(),
+                name: "User".to_string()
+            }
+        );
+        // The records are generated along with the enum.
+        assert!(ci.get_record_definition("User").is_some());
+        assert!(ci.get_record_definition("Tag").is_some());
+        let types: Vec<_> = ci.iter_types().collect();
+        for name in ["User", "Tag"] {
+            assert!(types
+                .iter()
+                .any(|t| matches!(t, Type::Record { name: n, .. } if n == name)));
+        }
+    }
+
     #[test]
     fn test_enum_variant_named_error() {
         const UDL: &str = r#"
@@ -620,6 +949,8 @@ mod test {
             discr: val.map(Literal::new_uint),
             fields: vec![],
             docstring: None,
+            deprecated: None,
+            retryable: false,
         }
     }
 
@@ -641,8 +972,13 @@ mod test {
             name: "test".to_string(),
             variants: vec![],
             flat: false,
+            scalar: false,
             non_exhaustive: false,
+            default_variant: None,
+            discriminant: None,
+            methods: vec![],
             docstring: None,
+            since: None,
         };
 
         assert!(e.variant_discr(0).is_err());
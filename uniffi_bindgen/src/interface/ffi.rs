@@ -20,6 +20,9 @@
 /// "borrowed" types (the sender must keep it alive for the duration of the call).
 use uniffi_meta::{ExternalKind, Type};
 
+use super::{Argument, Enum};
+use std::collections::{BTreeMap, BTreeSet};
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum FfiType {
     // N.B. there are no booleans at this layer, since they cause problems for JNA.
@@ -89,6 +92,8 @@ impl From<&Type> for FfiType {
             Type::Object { name, .. } => FfiType::RustArcPtr(name.to_owned()),
             // Callback interfaces are passed as opaque integer handles.
             Type::CallbackInterface { .. } => FfiType::UInt64,
+            // So are the handles which the user passes through.
+            Type::OpaqueHandle => FfiType::UInt64,
             // Other types are serialized into a bytebuffer and deserialized on the other side.
             Type::Enum { .. }
             | Type::Record { .. }
@@ -96,7 +101,11 @@ impl From<&Type> for FfiType {
             | Type::Sequence { .. }
             | Type::Map { .. }
             | Type::Timestamp
-            | Type::Duration => FfiType::RustBuffer(None),
+            | Type::Duration
+            | Type::DurationRange { .. }
+            | Type::Path
+            | Type::Json
+            | Type::DynError => FfiType::RustBuffer(None),
             Type::External {
                 name,
                 kind: ExternalKind::Interface,
@@ -112,7 +121,76 @@ impl From<&Type> for FfiType {
                 kind: ExternalKind::DataClass,
                 ..
             } => FfiType::RustBuffer(Some(name.clone())),
+            Type::External {
+                kind: ExternalKind::ScalarEnum,
+                ..
+            } => FfiType::Int32,
             Type::Custom { builtin, .. } => FfiType::from(builtin.as_ref()),
+            // Non-zero integers are passed exactly like the integer.
+            Type::NonZero { inner_type } => FfiType::from(inner_type.as_ref()),
+        }
+    }
+}
+
+/// Maps `Type`s to the `FfiType` that they're lowered into.
+///
+/// This is the same as `FfiType::from()`, except for fieldless enums: they're lowered into the
+/// `i32` that identifies their variant rather than into a `RustBuffer`, and telling whether an enum
+/// is fieldless needs its definition.
+pub(super) struct FfiTypeMap<'a> {
+    pub(super) enums: &'a BTreeMap<String, Enum>,
+    pub(super) external_scalar_enums: &'a BTreeSet<String>,
+}
+
+impl FfiTypeMap<'_> {
+    pub(super) fn ffi_type(&self, t: &Type) -> FfiType {
+        match t {
+            Type::Enum { name, .. } if self.enums.get(name).map_or(false, Enum::is_scalar) => {
+                FfiType::Int32
+            }
+            Type::External {
+                name,
+                kind: ExternalKind::DataClass,
+                ..
+            } if self.external_scalar_enums.contains(name) => FfiType::Int32,
+            Type::Custom { builtin, .. } => self.ffi_type(builtin),
+            _ => t.into(),
+        }
+    }
+
+    pub(super) fn ffi_argument(&self, a: &Argument) -> FfiArgument {
+        FfiArgument {
+            name: a.name.clone(),
+            // Segmented arguments are passed as a handle to the byte segments.
+            type_: if a.segmented {
+                FfiType::UInt64
+            } else {
+                self.ffi_type(&a.type_)
+            },
+        }
+    }
+}
+
+impl FfiType {
+    /// The C type used to pass this type, as used in [FfiFunction::c_declaration].
+    pub fn c_type_name(&self) -> &'static str {
+        match self {
+            FfiType::UInt8 => "uint8_t",
+            FfiType::Int8 => "int8_t",
+            FfiType::UInt16 => "uint16_t",
+            FfiType::Int16 => "int16_t",
+            FfiType::UInt32 => "uint32_t",
+            FfiType::Int32 => "int32_t",
+            FfiType::UInt64 => "uint64_t",
+            FfiType::Int64 => "int64_t",
+            FfiType::Float32 => "float",
+            FfiType::Float64 => "double",
+            FfiType::RustArcPtr(_) => "void*",
+            FfiType::RustBuffer(_) => "RustBuffer",
+            FfiType::ForeignBytes => "ForeignBytes",
+            FfiType::ForeignCallback => "ForeignCallback",
+            FfiType::RustFutureHandle | FfiType::RustFutureContinuationData => "void*",
+            FfiType::RustFutureContinuationCallback => "UniFfiRustFutureContinuation",
         }
     }
 }
@@ -136,7 +214,7 @@ impl From<&&Type> for FfiType {
 /// from the high-level interface. Each callable thing in the component API will have a
 /// corresponding `FfiFunction` through which it can be invoked, and UniFFI also provides
 /// some built-in `FfiFunction` helpers for use in the foreign language bindings.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FfiFunction {
     pub(super) name: String,
     pub(super) is_async: bool,
@@ -186,6 +264,41 @@ impl FfiFunction {
         self.is_object_free_function
     }
 
+    /// Whether any argument or the return value is passed as a `RustBuffer`.
+    pub fn uses_rust_buffer(&self) -> bool {
+        self.arguments
+            .iter()
+            .map(|arg| &arg.type_)
+            .chain(self.return_type.as_ref())
+            .any(|type_| matches!(type_, FfiType::RustBuffer(_)))
+    }
+
+    /// Render the C declaration of this function.
+    ///
+    /// Functions which have a `RustCallStatus` argument take it last, as a `RustCallStatus*`
+    /// named `out_status`.  The function writes the outcome of the call to it, and the return
+    /// value is only meaningful if the call succeeded.
+    pub fn c_declaration(&self) -> String {
+        let mut args = self
+            .arguments
+            .iter()
+            .map(|arg| format!("{} {}", arg.type_.c_type_name(), arg.name))
+            .collect::<Vec<_>>();
+        if self.has_rust_call_status_arg {
+            args.push("RustCallStatus* out_status".to_string());
+        }
+        let args = if args.is_empty() {
+            "void".to_string()
+        } else {
+            args.join(", ")
+        };
+        let return_type = self
+            .return_type
+            .as_ref()
+            .map_or("void", FfiType::c_type_name);
+        format!("{return_type} {}({args});", self.name)
+    }
+
     pub fn init(
         &mut self,
         return_type: Option<FfiType>,
@@ -217,7 +330,7 @@ impl Default for FfiFunction {
 /// Represents an argument to an FFI function.
 ///
 /// Each argument has a name and a type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FfiArgument {
     pub(super) name: String,
     pub(super) type_: FfiType,
@@ -34,8 +34,8 @@
 
 use anyhow::Result;
 
-use super::ffi::{FfiArgument, FfiFunction, FfiType};
-use super::{AsType, ComponentInterface, Literal, ObjectImpl, Type, TypeIterator};
+use super::ffi::{FfiFunction, FfiType, FfiTypeMap};
+use super::{AsType, ComponentInterface, Literal, Method, ObjectImpl, Type, TypeIterator};
 use uniffi_meta::Checksum;
 
 /// Represents a standalone function.
@@ -61,6 +61,46 @@ pub struct Function {
     pub(super) ffi_func: FfiFunction,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    #[checksum_ignore]
+    pub(super) return_docstring: Option<String>,
+    #[checksum_ignore]
+    pub(super) since: Option<String>,
+    // Only affects how the bindings present the function, not the FFI.
+    #[checksum_ignore]
+    pub(super) namespace_object: Option<String>,
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
+    // Only affects the bindings, which retry the call.
+    #[checksum_ignore]
+    pub(super) retries: Option<u8>,
+    // Only affects the bindings, which check the calling thread.
+    #[checksum_ignore]
+    pub(super) main_thread: bool,
+    // The FFI function returns a handle to the chunks instead of the sequence.
+    pub(super) chunked: bool,
+    // The FFI function returns the value followed by the warnings, in a `RustBuffer`.
+    pub(super) with_warnings: bool,
+    // The FFI function returns a handle to the byte segments instead of the sequence.
+    pub(super) segmented: bool,
+    // Only affects the bindings, which add a variant returning a cancellable handle.
+    #[checksum_ignore]
+    pub(super) cancellable: bool,
+    // Only affects which bindings the function is generated in.
+    #[checksum_ignore]
+    pub(super) target: Option<String>,
+    // Only affects how the bindings present the function, not the FFI.
+    #[checksum_ignore]
+    pub(super) extension: Option<String>,
+    // Only affects the bindings, which add a variant returning this instead of `None`.
+    #[checksum_ignore]
+    pub(super) default_on_none: Option<Literal>,
+    // Only affects whether the bindings can cache the result.
+    #[checksum_ignore]
+    pub(super) static_return: bool,
+    // Only affects the bindings, which call it from `uniffiInitialize(config)` rather than
+    // generating it as a function.
+    #[checksum_ignore]
+    pub(super) runtime_initializer: bool,
     pub(super) throws: Option<Type>,
     pub(super) checksum_fn_name: String,
     // Force a checksum value, or we'll fallback to the trait.
@@ -73,6 +113,10 @@ impl Function {
         &self.name
     }
 
+    pub fn module_path(&self) -> &str {
+        &self.module_path
+    }
+
     pub fn is_async(&self) -> bool {
         self.is_async
     }
@@ -97,6 +141,11 @@ impl Function {
         &self.checksum_fn_name
     }
 
+    /// The namespace object the bindings should group this function under, if any.
+    pub fn namespace_object(&self) -> Option<&str> {
+        self.namespace_object.as_deref()
+    }
+
     pub fn checksum(&self) -> u16 {
         self.checksum.unwrap_or_else(|| uniffi_meta::checksum(self))
     }
@@ -113,11 +162,18 @@ impl Function {
         self.throws.as_ref()
     }
 
-    pub fn derive_ffi_func(&mut self) -> Result<()> {
+    pub(super) fn derive_ffi_func(&mut self, ffi_types: &FfiTypeMap<'_>) -> Result<()> {
         assert!(!self.ffi_func.name.is_empty());
+        let return_ffi_type = if self.chunked || self.segmented {
+            Some(FfiType::UInt64)
+        } else if self.with_warnings || self.has_out_args() {
+            Some(FfiType::RustBuffer(None))
+        } else {
+            self.return_type.as_ref().map(|t| ffi_types.ffi_type(t))
+        };
         self.ffi_func.init(
-            self.return_type.as_ref().map(Into::into),
-            self.arguments.iter().map(Into::into),
+            return_ffi_type,
+            self.arguments.iter().map(|a| ffi_types.ffi_argument(a)),
         );
         Ok(())
     }
@@ -127,13 +183,129 @@ impl Function {
             self.arguments
                 .iter()
                 .flat_map(Argument::iter_types)
-                .chain(self.return_type.iter().flat_map(Type::iter_types)),
+                .chain(self.return_type.iter().flat_map(Type::iter_types))
+                .chain(self.throws.iter().flat_map(Type::iter_types)),
         )
     }
 
     pub fn docstring(&self) -> Option<&str> {
         self.docstring.as_deref()
     }
+
+    pub fn return_docstring(&self) -> Option<&str> {
+        self.return_docstring.as_deref()
+    }
+
+    /// The version the function was added in, from a `@since` docstring tag.
+    pub fn since(&self) -> Option<&str> {
+        self.since.as_deref()
+    }
+
+    /// The deprecation message, if the function is deprecated.
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    /// How many times the bindings retry the function when it throws a retryable error, if they
+    /// retry it at all.
+    pub fn retries(&self) -> Option<u8> {
+        self.retries
+    }
+
+    /// Whether the function must only be called from the main thread.
+    pub fn is_main_thread(&self) -> bool {
+        self.main_thread
+    }
+
+    /// Whether the returned sequence is passed over the FFI in chunks.
+    ///
+    /// The FFI function returns a handle, which the bindings pass to the
+    /// `chunked_sequence_next` FFI function to get each chunk.
+    pub fn is_chunked(&self) -> bool {
+        self.chunked
+    }
+
+    /// Whether the function returns its value along with a list of non-fatal warnings.
+    ///
+    /// The FFI function returns a `RustBuffer` with the value serialized as usual, followed by
+    /// the warnings serialized as a `sequence<string>`.
+    pub fn is_with_warnings(&self) -> bool {
+        self.with_warnings
+    }
+
+    /// Whether the function has out-parameters, from `&mut T` arguments of an exported function.
+    ///
+    /// The FFI function returns a `RustBuffer` with the value serialized as usual, unless it's
+    /// `()`, followed by the final value of each out-parameter.
+    pub fn has_out_args(&self) -> bool {
+        self.arguments.iter().any(|arg| arg.out)
+    }
+
+    /// The out-parameters, in the order their final values are returned.
+    pub fn out_arguments(&self) -> Vec<&Argument> {
+        self.arguments.iter().filter(|arg| arg.out).collect()
+    }
+
+    /// What the bindings return for a function with out-parameters, as names and types: the
+    /// function's value, named `value`, unless it returns nothing, followed by the final value of
+    /// each out-parameter.
+    pub fn out_params_outputs(&self) -> Vec<(&str, &Type)> {
+        self.return_type
+            .iter()
+            .map(|t| ("value", t))
+            .chain(
+                self.out_arguments()
+                    .into_iter()
+                    .map(|a| (a.name(), &a.type_)),
+            )
+            .collect()
+    }
+
+    /// Whether the returned byte segments are passed over the FFI without concatenating them.
+    ///
+    /// The FFI function returns a handle, which the bindings pass to the `byte_segments_next`
+    /// FFI function to get each segment.
+    pub fn is_segmented(&self) -> bool {
+        self.segmented
+    }
+
+    /// Whether the bindings also generate a variant of this async function which returns a
+    /// handle with a `cancel()` method, rather than a future.
+    pub fn is_cancellable(&self) -> bool {
+        self.cancellable
+    }
+
+    /// The target variant, such as `ios`, that the bindings only generate this function for.
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// The type which the bindings generate this function as an extension of, if any.
+    ///
+    /// This is the type of the first argument, which the extension is called on.
+    pub fn extension_type(&self) -> Option<&Type> {
+        self.extension.as_ref()?;
+        self.arguments.first().map(|arg| &arg.type_)
+    }
+
+    /// The value which the `_or_default` variant of this function returns when the function
+    /// returns `None`, and the non-optional type that the variant returns.
+    pub fn default_on_none(&self) -> Option<(&Literal, &Type)> {
+        let default = self.default_on_none.as_ref()?;
+        match self.return_type.as_ref()? {
+            Type::Optional { inner_type } => Some((default, inner_type)),
+            _ => None,
+        }
+    }
+
+    /// Whether this is the function exported by `uniffi::export_runtime_config!()`.
+    ///
+    /// The bindings don't generate it as a function.  Instead, they generate a
+    /// `UniffiRuntimeConfig` builder with a setter for each argument, and a
+    /// `uniffiInitialize(config)` function which calls this with the settings.
+    pub fn is_runtime_initializer(&self) -> bool {
+        self.runtime_initializer
+    }
 }
 
 impl From<uniffi_meta::FnParamMetadata> for Argument {
@@ -144,6 +316,11 @@ impl From<uniffi_meta::FnParamMetadata> for Argument {
             by_ref: meta.by_ref,
             optional: meta.optional,
             default: meta.default,
+            variadic: meta.variadic,
+            segmented: meta.segmented,
+            progress: meta.progress,
+            out: meta.out,
+            docstring: meta.docstring,
         }
     }
 }
@@ -170,6 +347,21 @@ impl From<uniffi_meta::FnMetadata> for Function {
             return_type,
             ffi_func,
             docstring: meta.docstring.clone(),
+            return_docstring: meta.return_docstring,
+            since: meta.since,
+            namespace_object: meta.namespace_object,
+            deprecated: meta.deprecated,
+            retries: meta.retries,
+            main_thread: meta.main_thread,
+            chunked: meta.chunked,
+            with_warnings: meta.with_warnings,
+            segmented: meta.segmented,
+            cancellable: meta.cancellable,
+            target: meta.target,
+            extension: meta.extension,
+            default_on_none: meta.default_on_none,
+            static_return: meta.static_return,
+            runtime_initializer: meta.runtime_initializer,
             throws: meta.throws,
             checksum_fn_name,
             checksum: meta.checksum,
@@ -177,16 +369,65 @@ impl From<uniffi_meta::FnMetadata> for Function {
     }
 }
 
+// Constant functions are ordinary zero-argument functions at the FFI level, only the bindings
+// present them differently.
+impl From<uniffi_meta::ConstFnMetadata> for Function {
+    fn from(meta: uniffi_meta::ConstFnMetadata) -> Self {
+        let ffi_func = FfiFunction {
+            name: meta.ffi_symbol_name(),
+            ..FfiFunction::default()
+        };
+
+        Self {
+            checksum_fn_name: meta.checksum_symbol_name(),
+            name: meta.name,
+            module_path: meta.module_path,
+            is_async: false,
+            arguments: vec![],
+            return_type: Some(meta.return_type),
+            ffi_func,
+            docstring: meta.docstring,
+            return_docstring: None,
+            since: None,
+            namespace_object: None,
+            deprecated: None,
+            retries: None,
+            main_thread: false,
+            chunked: false,
+            with_warnings: false,
+            segmented: false,
+            cancellable: false,
+            target: None,
+            extension: None,
+            default_on_none: None,
+            static_return: false,
+            runtime_initializer: false,
+            throws: None,
+            checksum: meta.checksum,
+        }
+    }
+}
+
 /// Represents an argument to a function/constructor/method call.
 ///
 /// Each argument has a name and a type, along with some optional metadata.
-#[derive(Debug, Clone, Checksum)]
+#[derive(Debug, Clone, PartialEq, Eq, Checksum)]
 pub struct Argument {
     pub(super) name: String,
     pub(super) type_: Type,
     pub(super) by_ref: bool,
     pub(super) optional: bool,
     pub(super) default: Option<Literal>,
+    pub(super) variadic: bool,
+    pub(super) segmented: bool,
+    // The progress events are passed to the callback interface as usual, so this only changes
+    // the bindings.
+    #[checksum_ignore]
+    pub(super) progress: bool,
+    // The final value is returned along with the function's, which changes its FFI return type.
+    pub(super) out: bool,
+    #[checksum_ignore]
+    pub(super) docstring: Option<String>,
 }
 
 impl Argument {
@@ -206,23 +447,82 @@ impl Argument {
         self.default.as_ref()
     }
 
+    /// The type of each item, if the bindings take this trailing sequence argument as variadic
+    /// arguments.
+    pub fn variadic_type(&self) -> Option<&Type> {
+        match &self.type_ {
+            Type::Sequence { inner_type } if self.variadic => Some(inner_type),
+            _ => None,
+        }
+    }
+
+    /// Whether these byte segments are passed over the FFI without concatenating them.
+    ///
+    /// The bindings push each segment onto a handle from the `byte_segments_new` FFI function,
+    /// and pass that instead of the sequence.
+    pub fn is_segmented(&self) -> bool {
+        self.segmented
+    }
+
+    /// Whether this callback interface argument receives the progress events of an async
+    /// function, from `[Progress]`.
+    pub fn is_progress(&self) -> bool {
+        self.progress
+    }
+
+    /// Whether this is an out-parameter, whose final value the function returns along with its
+    /// own.
+    pub fn is_out(&self) -> bool {
+        self.out
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         self.type_.iter_types()
     }
 }
 
-impl AsType for Argument {
-    fn as_type(&self) -> Type {
-        self.type_.clone()
+/// The `[Progress]` argument of an async function, which the bindings' `_with_progress` variant
+/// replaces with a closure.
+///
+/// The argument is a callback interface with a single method, which is passed each event.
+#[derive(Debug, Clone, Copy)]
+pub struct Progress<'a> {
+    pub(super) argument: &'a Argument,
+    pub(super) on_progress: &'a Method,
+}
+
+impl<'a> Progress<'a> {
+    pub fn argument(&self) -> &'a Argument {
+        self.argument
+    }
+
+    /// The type of the callback interface which receives the events.
+    pub fn listener_type(&self) -> &'a Type {
+        &self.argument.type_
+    }
+
+    /// The listener's method which is passed each event.
+    pub fn event_method(&self) -> &'a Method {
+        self.on_progress
+    }
+
+    /// The argument of the listener's method which is the event.
+    pub fn event_argument(&self) -> &'a Argument {
+        &self.on_progress.arguments[0]
+    }
+
+    pub fn event_type(&self) -> &'a Type {
+        &self.event_argument().type_
     }
 }
 
-impl From<&Argument> for FfiArgument {
-    fn from(a: &Argument) -> FfiArgument {
-        FfiArgument {
-            name: a.name.clone(),
-            type_: (&a.type_).into(),
-        }
+impl AsType for Argument {
+    fn as_type(&self) -> Type {
+        self.type_.clone()
     }
 }
 
@@ -235,9 +535,9 @@ pub struct ResultType {
 
 impl ResultType {
     /// Get the `T` parameters for the `FutureCallback<T>` for this ResultType
-    pub fn future_callback_param(&self) -> FfiType {
+    pub fn future_callback_param(&self, ci: &ComponentInterface) -> FfiType {
         match &self.return_type {
-            Some(t) => t.into(),
+            Some(t) => ci.ffi_type(t),
             None => FfiType::UInt8,
         }
     }
@@ -249,6 +549,18 @@ pub trait Callable {
     fn return_type(&self) -> Option<Type>;
     fn throws_type(&self) -> Option<Type>;
     fn is_async(&self) -> bool;
+    fn docstring(&self) -> Option<&str>;
+    fn return_docstring(&self) -> Option<&str>;
+    fn since(&self) -> Option<&str>;
+    // Only top-level functions can be restricted to the main thread.
+    fn is_main_thread(&self) -> bool {
+        false
+    }
+    // Only top-level functions can be extensions, which take their first argument as the
+    // receiver.
+    fn is_extension(&self) -> bool {
+        false
+    }
     fn result_type(&self) -> ResultType {
         ResultType {
             return_type: self.return_type(),
@@ -259,25 +571,25 @@ pub trait Callable {
     // Quick way to get the rust future scaffolding function that corresponds to our return type.
 
     fn ffi_rust_future_poll(&self, ci: &ComponentInterface) -> String {
-        ci.ffi_rust_future_poll(self.return_type().map(Into::into))
+        ci.ffi_rust_future_poll(self.return_type().map(|t| ci.ffi_type(&t)))
             .name()
             .to_owned()
     }
 
     fn ffi_rust_future_cancel(&self, ci: &ComponentInterface) -> String {
-        ci.ffi_rust_future_cancel(self.return_type().map(Into::into))
+        ci.ffi_rust_future_cancel(self.return_type().map(|t| ci.ffi_type(&t)))
             .name()
             .to_owned()
     }
 
     fn ffi_rust_future_complete(&self, ci: &ComponentInterface) -> String {
-        ci.ffi_rust_future_complete(self.return_type().map(Into::into))
+        ci.ffi_rust_future_complete(self.return_type().map(|t| ci.ffi_type(&t)))
             .name()
             .to_owned()
     }
 
     fn ffi_rust_future_free(&self, ci: &ComponentInterface) -> String {
-        ci.ffi_rust_future_free(self.return_type().map(Into::into))
+        ci.ffi_rust_future_free(self.return_type().map(|t| ci.ffi_type(&t)))
             .name()
             .to_owned()
     }
@@ -299,6 +611,26 @@ impl Callable for Function {
     fn is_async(&self) -> bool {
         self.is_async
     }
+
+    fn docstring(&self) -> Option<&str> {
+        self.docstring()
+    }
+
+    fn return_docstring(&self) -> Option<&str> {
+        self.return_docstring()
+    }
+
+    fn since(&self) -> Option<&str> {
+        self.since()
+    }
+
+    fn is_main_thread(&self) -> bool {
+        self.main_thread
+    }
+
+    fn is_extension(&self) -> bool {
+        self.extension.is_some()
+    }
 }
 
 // Needed because Askama likes to add extra refs to variables
@@ -318,6 +650,22 @@ impl<T: Callable> Callable for &T {
     fn is_async(&self) -> bool {
         (*self).is_async()
     }
+
+    fn docstring(&self) -> Option<&str> {
+        (*self).docstring()
+    }
+
+    fn return_docstring(&self) -> Option<&str> {
+        (*self).return_docstring()
+    }
+
+    fn since(&self) -> Option<&str> {
+        (*self).since()
+    }
+
+    fn is_main_thread(&self) -> bool {
+        (*self).is_main_thread()
+    }
 }
 
 #[cfg(test)]
@@ -372,6 +720,27 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_dyn_error() {
+        const UDL: &str = r#"
+            namespace test {
+                [Throws=DynError]
+                void testing();
+            };
+            [Error]
+            typedef any DynError;
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let func = ci.get_function_definition("testing").unwrap();
+        assert_eq!(func.throws_type(), Some(&Type::DynError));
+        assert!(ci.has_dyn_error());
+        // The message and cause chain are serialized as strings.
+        assert!(ci.iter_types().any(|t| t
+            == &Type::Sequence {
+                inner_type: Box::new(Type::String)
+            }));
+    }
+
     #[test]
     fn test_docstring_function() {
         const UDL: &str = r#"
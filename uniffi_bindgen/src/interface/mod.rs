@@ -60,13 +60,16 @@ pub use callbacks::CallbackInterface;
 mod enum_;
 pub use enum_::{Enum, Variant};
 mod function;
-pub use function::{Argument, Callable, Function, ResultType};
+pub use function::{Argument, Callable, Function, Progress, ResultType};
 mod object;
-pub use object::{Constructor, Method, Object, UniffiTrait};
+pub use object::{
+    AssociatedConstant, Constructor, Cursor, Method, Object, Observable, UniffiTrait,
+};
 mod record;
-pub use record::{Field, Record};
+pub use record::{Field, Record, WireItem};
 
 pub mod ffi;
+use ffi::FfiTypeMap;
 pub use ffi::{FfiArgument, FfiFunction, FfiType};
 pub use uniffi_meta::Radix;
 use uniffi_meta::{
@@ -75,9 +78,16 @@ use uniffi_meta::{
 };
 pub type Literal = LiteralMetadata;
 
+#[cfg(test)]
+thread_local! {
+    /// The number of times UDL has been parsed on this thread, for tests which check that it's
+    /// only parsed once.
+    pub(crate) static UDL_PARSES: std::cell::Cell<usize> = std::cell::Cell::new(0);
+}
+
 /// The main public interface for this module, representing the complete details of an interface exposed
 /// by a rust component and the details of consuming it via an extern-C FFI layer.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct ComponentInterface {
     /// All of the types used in the interface.
     // We can't checksum `self.types`, but its contents are implied by the other fields
@@ -87,12 +97,19 @@ pub struct ComponentInterface {
     enums: BTreeMap<String, Enum>,
     records: BTreeMap<String, Record>,
     functions: Vec<Function>,
+    // Zero-argument functions which the bindings expose as lazily-initialized constants.
+    constants: Vec<Function>,
     objects: Vec<Object>,
     callback_interfaces: Vec<CallbackInterface>,
     // Type names which were seen used as an error.
     errors: HashSet<String>,
     // Types which were seen used as callback interface error.
     callback_interface_throws_types: BTreeSet<Type>,
+    // Names of the external types which library mode found to be fieldless enums, and so are
+    // lowered into a scalar.
+    external_scalar_enums: BTreeSet<String>,
+    // The user-supplied version string which the bindings check against the scaffolding's.
+    component_version: Option<String>,
 }
 
 impl ComponentInterface {
@@ -113,10 +130,27 @@ impl ComponentInterface {
             !module_path.is_empty(),
             "you must specify a valid crate name"
         );
+        #[cfg(test)]
+        UDL_PARSES.with(|parses| parses.set(parses.get() + 1));
         let group = uniffi_udl::parse_udl(idl, module_path)?;
         Self::from_metadata(group)
     }
 
+    /// Parse a `ComponentInterface` from several UDL files which share a namespace.
+    ///
+    /// `files` are `(file_name, udl)` pairs; the file names are used to report which file
+    /// a conflicting definition came from.
+    pub fn from_webidl_multi(files: &[(&str, &str)], module_path: &str) -> Result<Self> {
+        ensure!(
+            !module_path.is_empty(),
+            "you must specify a valid crate name"
+        );
+        #[cfg(test)]
+        UDL_PARSES.with(|parses| parses.set(parses.get() + 1));
+        let group = uniffi_udl::parse_udl_multi(files, module_path)?;
+        Self::from_metadata(group)
+    }
+
     /// Create a `ComponentInterface` from a `MetadataGroup`
     /// Public so that external binding generators can use it.
     pub fn from_metadata(group: uniffi_meta::MetadataGroup) -> Result<Self> {
@@ -171,6 +205,49 @@ impl ComponentInterface {
         }
     }
 
+    /// The version string set by `component_version` in the `[bindings]` section of `uniffi.toml`.
+    ///
+    /// The scaffolding reads the same setting from the crate's `uniffi.toml`, and the bindings
+    /// check that the two are equal when they're initialized.
+    pub fn component_version(&self) -> Option<&str> {
+        self.component_version.as_deref()
+    }
+
+    /// Set the version string the bindings expect the scaffolding to report.
+    ///
+    /// The version is embedded in the generated code, so it may only contain ASCII letters, digits
+    /// and the characters `.`, `-` and `+`, as in a semantic version.
+    pub fn set_component_version(&mut self, version: Option<String>) -> Result<()> {
+        if let Some(v) = &version {
+            ensure!(
+                !v.is_empty()
+                    && v
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+')),
+                "Invalid component version {v:?}: it must be non-empty and only contain ASCII letters, digits, '.', '-' and '+'"
+            );
+        }
+        self.component_version = version;
+        Ok(())
+    }
+
+    /// Expose functions without arguments which return a `'static` borrow, like
+    /// `fn version() -> &'static str`, as constants.
+    ///
+    /// These always return the same value, so the bindings can call them once and cache the
+    /// result.  This is set by `static_functions_as_constants` in the `[bindings]` section of
+    /// `uniffi.toml`.
+    pub fn expose_static_functions_as_constants(&mut self) {
+        let (constants, functions) =
+            std::mem::take(&mut self.functions)
+                .into_iter()
+                .partition(|f| {
+                    f.static_return && f.arguments.is_empty() && !f.is_async && f.throws.is_none()
+                });
+        self.functions = functions;
+        self.constants.extend(constants);
+    }
+
     /// Get the definitions for every Enum type in the interface.
     pub fn enum_definitions(&self) -> impl Iterator<Item = &Enum> {
         self.enums.values()
@@ -181,6 +258,17 @@ impl ComponentInterface {
         self.enums.get(name)
     }
 
+    /// Get the variants of the error thrown by a function which the bindings retry it on.
+    pub fn retryable_variants(&self, func: &Function) -> Vec<&Variant> {
+        match func.throws_type() {
+            Some(Type::Enum { name, .. }) => self
+                .get_enum_definition(name)
+                .map(|e| e.variants().iter().filter(|v| v.is_retryable()).collect())
+                .unwrap_or_default(),
+            _ => vec![],
+        }
+    }
+
     /// Get the definitions for every Record type in the interface.
     pub fn record_definitions(&self) -> impl Iterator<Item = &Record> {
         self.records.values()
@@ -196,6 +284,32 @@ impl ComponentInterface {
         &self.functions
     }
 
+    /// Get the definitions for every constant in the interface.
+    ///
+    /// Each constant is a function without arguments, which the bindings call the first time the
+    /// constant is accessed.
+    pub fn constant_definitions(&self) -> &[Function] {
+        &self.constants
+    }
+
+    /// Get the names of all the namespace objects that functions are grouped under.
+    pub fn namespace_objects(&self) -> Vec<&str> {
+        self.functions
+            .iter()
+            .filter_map(|f| f.namespace_object())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Get the functions which are grouped under the given namespace object.
+    pub fn namespace_object_functions(&self, name: &str) -> Vec<&Function> {
+        self.functions
+            .iter()
+            .filter(|f| f.namespace_object() == Some(name))
+            .collect()
+    }
+
     /// Get a Function definition by name, or None if no such Function is defined.
     pub fn get_function_definition(&self, name: &str) -> Option<&Function> {
         // TODO: probably we could store these internally in a HashMap to make this easier?
@@ -213,6 +327,132 @@ impl ComponentInterface {
         self.objects.iter().find(|o| o.name == name)
     }
 
+    /// Get the methods the bindings subscribe to the events of an `[Observable]` object with, or
+    /// None if the object isn't observable.
+    pub fn observable<'a>(&'a self, obj: &'a Object) -> Option<Observable<'a>> {
+        if obj.is_observable() {
+            self.resolve_observable(obj).ok()
+        } else {
+            None
+        }
+    }
+
+    fn resolve_observable<'a>(&'a self, obj: &'a Object) -> Result<Observable<'a>> {
+        let find_method = |name: &str| {
+            obj.methods
+                .iter()
+                .find(|m| m.name() == name)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "\"{}\" is observable, so it needs a `{name}` method",
+                        obj.name()
+                    )
+                })
+        };
+        let add = find_method("add_observer")?;
+        let remove = find_method("remove_observer")?;
+        for meth in [add, remove] {
+            if meth.is_async() || meth.throws() {
+                bail!(
+                    "\"{}.{}\" is used to observe events, so it can't be async or throw",
+                    obj.name(),
+                    meth.name()
+                );
+            }
+        }
+        let observer = match add.arguments().as_slice() {
+            [arg] => match arg.as_type() {
+                Type::CallbackInterface { name, .. } => {
+                    self.get_callback_interface_definition(&name)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+        .ok_or_else(|| {
+            anyhow!(
+                "\"{}.add_observer\" must take a single callback interface argument",
+                obj.name()
+            )
+        })?;
+        let token_type = add
+            .return_type()
+            .cloned()
+            .unwrap_or_else(|| add.arguments()[0].as_type());
+        if !matches!(remove.arguments().as_slice(), [arg] if arg.as_type() == token_type)
+            || remove.return_type().is_some()
+        {
+            bail!(
+                "\"{}.remove_observer\" must take the value returned by `add_observer`, or the observer if it doesn't return anything",
+                obj.name()
+            );
+        }
+        let on_event = match observer.methods().as_slice() {
+            [meth]
+                if meth.arguments().len() == 1
+                    && meth.return_type().is_none()
+                    && !meth.throws()
+                    && !meth.is_async() =>
+            {
+                *meth
+            }
+            _ => bail!(
+                "\"{}\" observes \"{}\", so it must have a single method which takes the event and returns nothing",
+                observer.name(),
+                obj.name()
+            ),
+        };
+        Ok(Observable {
+            add,
+            remove,
+            on_event,
+        })
+    }
+
+    /// The `[Progress]` argument of an async function, if it has one.
+    pub fn progress<'a>(&'a self, func: &'a Function) -> Option<Progress<'a>> {
+        func.arguments
+            .iter()
+            .find(|arg| arg.is_progress())
+            .and_then(|arg| self.resolve_progress(func, arg).ok())
+    }
+
+    fn resolve_progress<'a>(
+        &'a self,
+        func: &'a Function,
+        argument: &'a Argument,
+    ) -> Result<Progress<'a>> {
+        let listener = match &argument.type_ {
+            Type::CallbackInterface { name, .. } => self.get_callback_interface_definition(name),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            anyhow!(
+                "argument `{}` must be a callback interface to be [Progress]",
+                argument.name()
+            )
+        })?;
+        let on_progress = match listener.methods().as_slice() {
+            [meth]
+                if meth.arguments().len() == 1
+                    && meth.return_type().is_none()
+                    && !meth.throws()
+                    && !meth.is_async() =>
+            {
+                *meth
+            }
+            _ => bail!(
+                "\"{}\" receives the progress of \"{}\", so it must have a single method which takes the event and returns nothing",
+                listener.name(),
+                func.name()
+            ),
+        };
+        Ok(Progress {
+            argument,
+            on_progress,
+        })
+    }
+
     /// Get the definitions for every Callback Interface type in the interface.
     pub fn callback_interface_definitions(&self) -> &[CallbackInterface] {
         &self.callback_interfaces
@@ -238,6 +478,11 @@ impl ComponentInterface {
                     .map(|c| c as &dyn Callable)
                     .chain(o.methods().into_iter().map(|m| m as &dyn Callable))
             }))
+            .chain(
+                self.enums
+                    .values()
+                    .flat_map(|e| e.methods.iter().map(|m| m as &dyn Callable)),
+            )
     }
 
     /// Should we generate read (and lift) functions for errors?
@@ -287,6 +532,62 @@ impl ComponentInterface {
         })
     }
 
+    /// Get the external records, enums and custom types which are passed directly as an argument
+    /// or return value of an exported function, with their kind.
+    ///
+    /// How these are lowered is part of the FFI signature, so the scaffolding checks that their
+    /// `FfiConverter` agrees with the kind declared in the UDL.  Types which are only nested in
+    /// other types are always serialized into a buffer, so their kind doesn't matter.
+    pub fn iter_external_types_passed_directly(
+        &self,
+    ) -> impl Iterator<Item = (String, ExternalKind)> {
+        let mut types = BTreeMap::new();
+        let callables = self
+            .iter_callables()
+            .chain(self.constants.iter().map(|c| c as &dyn Callable));
+        for callable in callables {
+            let passed = callable
+                .arguments()
+                .into_iter()
+                .map(|a| a.as_type())
+                .chain(callable.return_type());
+            for t in passed {
+                if let Type::External {
+                    name,
+                    kind: kind @ (ExternalKind::DataClass | ExternalKind::ScalarEnum),
+                    ..
+                } = t
+                {
+                    types.insert(name, kind);
+                }
+            }
+        }
+        types.into_iter()
+    }
+
+    /// Note which of the external types are enums that are lowered into a scalar.
+    ///
+    /// Fieldless enums are passed across the FFI as the `i32` that identifies their variant, but
+    /// only the crate defining an enum knows whether it's fieldless.  UDL declares them with
+    /// `[External="crate_name", Enum]`, and other external types are assumed to be passed in a
+    /// `RustBuffer`.  `scalar_enums` holds the `(crate_name, enum_name)` of every fieldless enum,
+    /// which library mode collects from all the crates it loads, so it also finds the undeclared
+    /// ones.  This re-derives the FFI functions, since their signatures may change.
+    pub fn note_external_scalar_enums(
+        &mut self,
+        scalar_enums: &HashSet<(String, String)>,
+    ) -> Result<()> {
+        self.external_scalar_enums = self
+            .iter_external_types()
+            .filter(|(name, crate_name, kind, _)| {
+                *kind == ExternalKind::DataClass
+                    && scalar_enums.contains(&(crate_name.clone(), name.to_string()))
+            })
+            .map(|(name, ..)| name.clone())
+            .collect();
+        self.derive_ffi_funcs()
+    }
+
     /// Get details about all `Type::Custom` types
     pub fn iter_custom_types(&self) -> impl Iterator<Item = (&String, &Type)> {
         self.types.iter_known_types().filter_map(|t| match t {
@@ -300,6 +601,78 @@ impl ComponentInterface {
         self.types.iter_known_types()
     }
 
+    /// Iterate over all known types in the interface, with each object after the interface it
+    /// inherits from.
+    ///
+    /// This is for bindings where a class must be defined before any of its subclasses.
+    pub fn iter_types_in_inheritance_order(&self) -> impl Iterator<Item = &Type> {
+        let depth = |type_: &Type| {
+            let mut depth = 0;
+            let mut parent = match type_ {
+                Type::Object { name, .. } => {
+                    self.get_object_definition(name).and_then(Object::parent)
+                }
+                _ => None,
+            };
+            while let Some(name) = parent {
+                depth += 1;
+                parent = self.get_object_definition(name).and_then(Object::parent);
+            }
+            depth
+        };
+        let mut types: Vec<_> = self.iter_types().collect();
+        types.sort_by_key(|t| depth(t));
+        types.into_iter()
+    }
+
+    /// Iterate over the sequence and map types which are used more than once in the interface.
+    ///
+    /// Bindings can declare a single named alias for each of these, rather than spelling out the
+    /// full type everywhere it's used.
+    pub fn iter_shared_composite_types(&self) -> impl Iterator<Item = &Type> {
+        let mut counts = BTreeMap::<&Type, usize>::new();
+        let usages = self
+            .functions
+            .iter()
+            .flat_map(Function::iter_types)
+            .chain(self.objects.iter().flat_map(Object::iter_types))
+            .chain(self.records.values().flat_map(Record::iter_types))
+            .chain(self.enums.values().flat_map(Enum::iter_types))
+            .chain(
+                self.callback_interfaces
+                    .iter()
+                    .flat_map(CallbackInterface::iter_types),
+            );
+        for type_ in usages {
+            if matches!(type_, Type::Sequence { .. } | Type::Map { .. }) {
+                *counts.entry(type_).or_default() += 1;
+            }
+        }
+        self.iter_types()
+            .filter(move |type_| counts.get(type_).map_or(false, |count| *count > 1))
+    }
+
+    /// Is `type_` a sequence or map type which is used more than once in the interface?
+    pub fn is_shared_composite_type(&self, type_: &Type) -> bool {
+        self.iter_shared_composite_types().any(|t| t == type_)
+    }
+
+    /// Does the interface throw dynamically typed errors, i.e. `uniffi::DynError`?
+    pub fn has_dyn_error(&self) -> bool {
+        self.types.contains(&Type::DynError)
+    }
+
+    /// Does the interface pass opaque handles, i.e. `uniffi::OpaqueHandle`?
+    pub fn has_opaque_handle(&self) -> bool {
+        self.types.contains(&Type::OpaqueHandle)
+    }
+
+    /// Does the interface pass ranges of durations, i.e. `RangeInclusive<Duration>` if `inclusive`
+    /// and `Range<Duration>` otherwise?
+    pub fn has_duration_range(&self, inclusive: bool) -> bool {
+        self.types.contains(&Type::DurationRange { inclusive })
+    }
+
     /// Get a specific type
     pub fn get_type(&self, name: &str) -> Option<Type> {
         self.types.get_type_definition(name)
@@ -379,6 +752,20 @@ impl ComponentInterface {
         }
     }
 
+    /// Builtin FFI function to get the component version the scaffolding was built with.
+    /// This returns a `RustBuffer` holding the UTF-8 version string, which is empty if the crate's
+    /// `uniffi.toml` doesn't set one.
+    pub fn ffi_uniffi_component_version(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_uniffi_component_version", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![],
+            return_type: Some(FfiType::RustBuffer(None)),
+            has_rust_call_status_arg: false,
+            is_object_free_function: false,
+        }
+    }
+
     /// Builtin FFI function for allocating a new `RustBuffer`.
     /// This is needed so that the foreign language bindings can create buffers in which to pass
     /// complex data types across the FFI.
@@ -453,6 +840,119 @@ impl ComponentInterface {
         }
     }
 
+    /// Builtin FFI function to get the next chunk of a chunked sequence.
+    /// The chunk is serialized like the sequence, and is empty once the sequence is exhausted.
+    pub fn ffi_chunked_sequence_next(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_chunked_sequence_next", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::UInt64,
+            }],
+            return_type: Some(FfiType::RustBuffer(None)),
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function for freeing a chunked sequence, along with any chunks which weren't
+    /// returned yet.
+    pub fn ffi_chunked_sequence_free(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_chunked_sequence_free", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::UInt64,
+            }],
+            return_type: None,
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to create an empty list of byte segments, to pass as a segmented
+    /// argument.
+    pub fn ffi_byte_segments_new(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_byte_segments_new", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![],
+            return_type: Some(FfiType::UInt64),
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to copy a segment onto the end of a list of byte segments.
+    pub fn ffi_byte_segments_push(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_byte_segments_push", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![
+                FfiArgument {
+                    name: "handle".to_string(),
+                    type_: FfiType::UInt64,
+                },
+                FfiArgument {
+                    name: "segment".to_string(),
+                    type_: FfiType::ForeignBytes,
+                },
+            ],
+            return_type: None,
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to get the number of segments left in a list of byte segments.
+    pub fn ffi_byte_segments_len(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_byte_segments_len", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::UInt64,
+            }],
+            return_type: Some(FfiType::UInt64),
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function to take the next segment from a list of byte segments.
+    /// The `RustBuffer` contains the segment's raw bytes, rather than a serialized value.
+    pub fn ffi_byte_segments_next(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_byte_segments_next", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::UInt64,
+            }],
+            return_type: Some(FfiType::RustBuffer(None)),
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function for freeing a list of byte segments, along with any segments which
+    /// weren't taken yet.
+    pub fn ffi_byte_segments_free(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_byte_segments_free", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::UInt64,
+            }],
+            return_type: None,
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
     /// Builtin FFI function to poll a Rust future.
     pub fn ffi_rust_future_poll(&self, return_ffi_type: Option<FfiType>) -> FfiFunction {
         FfiFunction {
@@ -552,11 +1052,73 @@ impl ComponentInterface {
         self.iter_ffi_function_definitions().any(|f| f.is_async())
     }
 
+    /// Does this interface contain async functions which can be called with a cancellable handle?
+    pub fn has_cancellable_fns(&self) -> bool {
+        self.functions.iter().any(|f| f.is_cancellable())
+    }
+
+    /// Does this interface contain async functions which pass their progress events to a closure?
+    pub fn has_progress_fns(&self) -> bool {
+        self.functions
+            .iter()
+            .any(|f| f.arguments().iter().any(|arg| arg.is_progress()))
+    }
+
+    /// Does this interface contain functions which return their sequence in chunks?
+    pub fn has_chunked_fns(&self) -> bool {
+        self.functions.iter().any(|f| f.is_chunked())
+    }
+
+    /// Does this interface contain functions which pass byte segments without concatenating
+    /// them, either as an argument or as their return value?
+    pub fn has_segmented_fns(&self) -> bool {
+        self.functions
+            .iter()
+            .any(|f| f.is_segmented() || f.arguments().iter().any(|arg| arg.is_segmented()))
+    }
+
+    /// The function the bindings call from `uniffiInitialize(config)`, if the component exports a
+    /// runtime config with `uniffi::export_runtime_config!()`.
+    pub fn runtime_initializer(&self) -> Option<&Function> {
+        self.functions.iter().find(|f| f.is_runtime_initializer())
+    }
+
+    /// Does this interface contain objects whose methods must be called on the thread which
+    /// constructed them?
+    pub fn has_thread_bound_objects(&self) -> bool {
+        self.objects.iter().any(|o| o.is_thread_bound())
+    }
+
+    /// Does this interface contain objects with an `[OnPoison]` policy, whose methods can fail
+    /// with a `StatePoisoned` error?
+    pub fn has_on_poison_objects(&self) -> bool {
+        self.objects.iter().any(|o| o.on_poison().is_some())
+    }
+
+    /// Does this interface contain functions which return the final values of out-parameters?
+    pub fn has_out_params_fns(&self) -> bool {
+        self.functions.iter().any(|f| f.has_out_args())
+    }
+
+    /// Does this interface contain functions which return their value with warnings?
+    pub fn has_with_warnings_fns(&self) -> bool {
+        self.functions.iter().any(|f| f.is_with_warnings())
+    }
+
+    /// Does the scaffolding expose the functions behind the bindings' self-test?
+    ///
+    /// These are generated by `setup_scaffolding!()` when `expose_self_test` is set.
+    pub fn has_self_test(&self) -> bool {
+        ["uniffi_self_test_values", "uniffi_self_test_check"]
+            .into_iter()
+            .all(|name| self.get_function_definition(name).is_some())
+    }
+
     /// Iterate over `T` parameters of the `FutureCallback<T>` callbacks in this interface
     pub fn iter_future_callback_params(&self) -> impl Iterator<Item = FfiType> {
         let unique_results = self
             .iter_callables()
-            .map(|c| c.result_type().future_callback_param())
+            .map(|c| c.result_type().future_callback_param(self))
             .collect::<BTreeSet<_>>();
         unique_results.into_iter()
     }
@@ -578,9 +1140,56 @@ impl ComponentInterface {
         self.iter_user_ffi_function_definitions()
             .cloned()
             .chain(self.iter_rust_buffer_ffi_function_definitions())
+            .chain(self.iter_chunked_sequence_ffi_function_definitions())
+            .chain(self.iter_byte_segments_ffi_function_definitions())
             .chain(self.iter_futures_ffi_function_definitions())
             .chain(self.iter_checksum_ffi_functions())
             .chain([self.ffi_uniffi_contract_version()])
+            .chain(
+                self.component_version
+                    .as_ref()
+                    .map(|_| self.ffi_uniffi_component_version()),
+            )
+    }
+
+    /// Describe the C signature of every FFI function in the interface.
+    ///
+    /// This is intended for tooling which validates hand-written FFI calls.  Each line is the
+    /// [C declaration](FfiFunction::c_declaration) of a function, followed by a comment listing
+    /// the conventions it uses:
+    ///   - `async`: the function returns a Rust future handle, to drive with the
+    ///     `rust_future_*` functions.
+    ///   - `rust_buffer`: an argument or the return value is a `RustBuffer`.
+    ///   - `rust_call_status`: the function takes a trailing `RustCallStatus*` out-parameter.
+    pub fn ffi_manifest(&self) -> String {
+        self.iter_ffi_function_definitions()
+            .map(|func| {
+                let conventions = [
+                    (func.is_async(), "async"),
+                    (func.uses_rust_buffer(), "rust_buffer"),
+                    (func.has_rust_call_status_arg(), "rust_call_status"),
+                ]
+                .into_iter()
+                .filter_map(|(uses, name)| uses.then_some(name))
+                .collect::<Vec<_>>();
+                if conventions.is_empty() {
+                    format!("{}\n", func.c_declaration())
+                } else {
+                    format!("{} // {}\n", func.c_declaration(), conventions.join(", "))
+                }
+            })
+            .collect()
+    }
+
+    /// The names of all the C symbols the scaffolding exports for this interface.
+    ///
+    /// These are the symbols to keep when stripping a static library, for example in a linker
+    /// version script or an `-exported_symbols_list` file.  The names are as declared in C, so
+    /// platforms which prefix C symbols with an underscore, like Apple's, need it added.
+    pub fn ffi_symbols(&self) -> BTreeSet<String> {
+        self.iter_ffi_function_definitions()
+            .map(|func| func.name().to_string())
+            .collect()
     }
 
     /// Alternate version of iter_ffi_function_definitions for languages that don't support async
@@ -590,8 +1199,15 @@ impl ComponentInterface {
         self.iter_user_ffi_function_definitions()
             .cloned()
             .chain(self.iter_rust_buffer_ffi_function_definitions())
+            .chain(self.iter_chunked_sequence_ffi_function_definitions())
+            .chain(self.iter_byte_segments_ffi_function_definitions())
             .chain(self.iter_checksum_ffi_functions())
             .chain([self.ffi_uniffi_contract_version()])
+            .chain(
+                self.component_version
+                    .as_ref()
+                    .map(|_| self.ffi_uniffi_component_version()),
+            )
     }
 
     /// List all FFI functions definitions for user-defined interfaces
@@ -600,6 +1216,7 @@ impl ComponentInterface {
     ///   - Top-level functions
     ///   - Object methods
     ///   - Callback interfaces
+    ///   - Record defaults
     pub fn iter_user_ffi_function_definitions(&self) -> impl Iterator<Item = &FfiFunction> + '_ {
         iter::empty()
             .chain(
@@ -613,6 +1230,17 @@ impl ComponentInterface {
                     .map(|cb| cb.ffi_init_callback()),
             )
             .chain(self.functions.iter().map(|f| &f.ffi_func))
+            .chain(self.constants.iter().map(|f| &f.ffi_func))
+            .chain(
+                self.enums
+                    .values()
+                    .flat_map(|e| e.methods.iter().map(|m| &m.ffi_func)),
+            )
+            .chain(
+                self.records
+                    .values()
+                    .filter_map(|rec| rec.ffi_func_default.as_ref()),
+            )
     }
 
     /// List all FFI functions definitions for RustBuffer functionality.
@@ -626,6 +1254,37 @@ impl ComponentInterface {
         .into_iter()
     }
 
+    /// List all FFI functions definitions for chunked sequences, if any function uses them.
+    pub fn iter_chunked_sequence_ffi_function_definitions(
+        &self,
+    ) -> impl Iterator<Item = FfiFunction> {
+        self.has_chunked_fns()
+            .then(|| {
+                [
+                    self.ffi_chunked_sequence_next(),
+                    self.ffi_chunked_sequence_free(),
+                ]
+            })
+            .into_iter()
+            .flatten()
+    }
+
+    /// List all FFI functions definitions for byte segments, if any function uses them.
+    pub fn iter_byte_segments_ffi_function_definitions(&self) -> impl Iterator<Item = FfiFunction> {
+        self.has_segmented_fns()
+            .then(|| {
+                [
+                    self.ffi_byte_segments_new(),
+                    self.ffi_byte_segments_push(),
+                    self.ffi_byte_segments_len(),
+                    self.ffi_byte_segments_next(),
+                    self.ffi_byte_segments_free(),
+                ]
+            })
+            .into_iter()
+            .flatten()
+    }
+
     /// List all FFI functions definitions for async functionality.
     pub fn iter_futures_ffi_function_definitions(&self) -> impl Iterator<Item = FfiFunction> + '_ {
         let all_possible_return_ffi_types = [
@@ -658,19 +1317,62 @@ impl ComponentInterface {
             })
     }
 
-    /// List all API checksums to check
+    /// A hash of the whole interface, for use as a cache key.
     ///
-    /// Returns a list of (export_symbol_name, checksum) items
-    pub fn iter_checksums(&self) -> impl Iterator<Item = (String, u16)> + '_ {
+    /// Each item is hashed with the same [`uniffi_meta::Checksum`] implementation as the API checksums, and the
+    /// item hashes are sorted before they're combined, so the hash doesn't depend on the order the
+    /// items were declared in.  It's the same on every platform and in every run.  Like the API
+    /// checksums, it doesn't cover docstrings or the other fields which only affect how the
+    /// bindings present an item.
+    pub fn stable_hash(&self) -> u64 {
+        fn item_hash<T: uniffi_meta::Checksum>(kind: &str, item: &T) -> u64 {
+            uniffi_meta::checksum_u64(&(kind, item))
+        }
+        let mut item_hashes: Vec<u64> = std::iter::once(item_hash("namespace", &self.namespace()))
+            .chain(self.enums.values().map(|e| item_hash("enum", e)))
+            .chain(self.records.values().map(|r| item_hash("record", r)))
+            .chain(self.functions.iter().map(|f| item_hash("function", f)))
+            .chain(self.constants.iter().map(|c| item_hash("constant", c)))
+            .chain(self.objects.iter().map(|o| item_hash("object", o)))
+            .chain(
+                self.callback_interfaces
+                    .iter()
+                    .map(|cbi| item_hash("callback_interface", cbi)),
+            )
+            .chain(self.errors.iter().map(|name| item_hash("error", name)))
+            .collect();
+        item_hashes.sort_unstable();
+        uniffi_meta::checksum_u64(&item_hashes)
+    }
+
+    /// The text of the header comment added by the `hash_header` bindings config, without the
+    /// comment marker.
+    ///
+    /// The hash is [`Self::stable_hash`], so it's computed from the interface rather than the
+    /// generated file and the header doesn't change it.
+    pub fn hash_header(&self) -> String {
+        format!(
+            "Generated by uniffi-bindgen {} from an interface with hash {:016x}",
+            env!("CARGO_PKG_VERSION"),
+            self.stable_hash()
+        )
+    }
+
+    /// List all API checksums to check
+    ///
+    /// Returns a list of (export_symbol_name, checksum) items
+    pub fn iter_checksums(&self) -> impl Iterator<Item = (String, u16)> + '_ {
         let func_checksums = self
             .functions
             .iter()
+            .chain(self.constants.iter())
             .map(|f| (f.checksum_fn_name(), f.checksum()));
-        let method_checksums = self.objects.iter().flat_map(|o| {
-            o.methods()
-                .into_iter()
-                .map(|m| (m.checksum_fn_name(), m.checksum()))
-        });
+        let method_checksums = self
+            .objects
+            .iter()
+            .flat_map(|o| o.methods())
+            .chain(self.enums.values().flat_map(|e| e.methods()))
+            .map(|m| (m.checksum_fn_name(), m.checksum()));
         let constructor_checksums = self.objects.iter().flat_map(|o| {
             o.constructors()
                 .into_iter()
@@ -756,18 +1458,70 @@ impl ComponentInterface {
     pub(super) fn add_function_definition(&mut self, defn: Function) -> Result<()> {
         // Since functions are not a first-class type, we have to check for duplicates here
         // rather than relying on the type-finding pass to catch them.
-        if self.functions.iter().any(|f| f.name == defn.name) {
+        if self
+            .functions
+            .iter()
+            .chain(self.constants.iter())
+            .any(|f| f.name == defn.name)
+        {
             bail!("duplicate function definition: \"{}\"", defn.name);
         }
         if self.types.get_type_definition(defn.name()).is_some() {
             bail!("Conflicting type definition for \"{}\"", defn.name());
         }
+        if defn.is_runtime_initializer() {
+            if let Some(other) = self.runtime_initializer() {
+                bail!(
+                    "\"{}\" and \"{}\" are both runtime initializers",
+                    other.name(),
+                    defn.name()
+                );
+            }
+            // Settings which aren't set in the config are passed as `None`.
+            if defn.is_async()
+                || defn.return_type().is_some()
+                || defn.throws()
+                || !defn
+                    .arguments()
+                    .iter()
+                    .all(|arg| matches!(arg.as_type(), Type::Optional { .. }))
+            {
+                bail!(
+                    "runtime initializer \"{}\" must be a sync function with only optional arguments, which returns nothing",
+                    defn.name()
+                );
+            }
+        }
         self.types.add_known_types(defn.iter_types())?;
+        if defn.is_with_warnings() {
+            // The bindings lift the warnings with the `sequence<string>` FFI converter.
+            let warnings_type = Type::Sequence {
+                inner_type: Box::new(Type::String),
+            };
+            self.types.add_known_types(warnings_type.iter_types())?;
+        }
         self.functions.push(defn);
 
         Ok(())
     }
 
+    /// Called by `APIBuilder` impls to add a constant definition to the `ComponentInterface`.
+    pub(super) fn add_constant_definition(&mut self, defn: Function) -> Result<()> {
+        // Constants share the namespace of functions in the bindings.
+        if self
+            .functions
+            .iter()
+            .chain(self.constants.iter())
+            .any(|f| f.name == defn.name)
+        {
+            bail!("duplicate constant definition: \"{}\"", defn.name);
+        }
+        self.types.add_known_types(defn.iter_types())?;
+        self.constants.push(defn);
+
+        Ok(())
+    }
+
     pub(super) fn add_constructor_meta(&mut self, meta: ConstructorMetadata) -> Result<()> {
         let object = get_object(&mut self.objects, &meta.self_name)
             .ok_or_else(|| anyhow!("add_constructor_meta: object {} not found", &meta.self_name))?;
@@ -781,10 +1535,15 @@ impl ComponentInterface {
 
     pub(super) fn add_method_meta(&mut self, meta: impl Into<Method>) -> Result<()> {
         let mut method: Method = meta.into();
+        self.types.add_known_types(method.iter_types())?;
+        if let Some(enum_) = self.enums.get_mut(&method.object_name) {
+            method.enum_receiver = true;
+            enum_.methods.push(method);
+            return Ok(());
+        }
         let object = get_object(&mut self.objects, &method.object_name)
             .ok_or_else(|| anyhow!("add_method_meta: object {} not found", &method.object_name))?;
 
-        self.types.add_known_types(method.iter_types())?;
         method.object_impl = object.imp;
         object.methods.push(method);
         Ok(())
@@ -793,6 +1552,7 @@ impl ComponentInterface {
     pub(super) fn add_uniffitrait_meta(&mut self, meta: UniffiTraitMetadata) -> Result<()> {
         let object = get_object(&mut self.objects, meta.self_name())
             .ok_or_else(|| anyhow!("add_uniffitrait_meta: object not found"))?;
+        object.has_stringifier |= meta.is_stringifier();
         let ut: UniffiTrait = meta.into();
         self.types.add_known_types(ut.iter_types())?;
         object.uniffi_traits.push(ut);
@@ -860,30 +1620,509 @@ impl ComponentInterface {
 
         // Because functions aren't first class types, we need to check here that
         // a function name hasn't already been used as a type name.
-        for f in self.functions.iter() {
+        for f in self.functions.iter().chain(self.constants.iter()) {
             if self.types.get_type_definition(f.name()).is_some() {
                 bail!("Conflicting type definition for \"{}\"", f.name());
             }
         }
+        // Namespace objects are generated as types in the bindings, so the same goes for them.
+        for name in self.namespace_objects() {
+            if self.types.get_type_definition(name).is_some() {
+                bail!("Conflicting type definition for namespace object \"{name}\"");
+            }
+        }
+        // The bindings generate enum methods alongside the enum's variants, which error enums
+        // and async calls don't fit into.
+        for e in self.enums.values() {
+            if let Some(meth) = e.methods.first() {
+                if self.is_name_used_as_error(e.name()) {
+                    bail!(
+                        "Error enum \"{}\" can't have methods, but \"{}\" was exported",
+                        e.name(),
+                        meth.name(),
+                    );
+                }
+            }
+            if let Some(meth) = e.methods.iter().find(|m| m.is_async()) {
+                bail!(
+                    "Enum method \"{}.{}\" can't be async",
+                    e.name(),
+                    meth.name()
+                );
+            }
+        }
+        // Objects implement the foreign interfaces of their traits with their own methods, so
+        // they need a matching method for every trait method.
+        for obj in self.objects.iter() {
+            for ty in obj.implements() {
+                let trait_obj = match ty {
+                    Type::Object { name, .. } => self
+                        .get_object_definition(name)
+                        .filter(|t| t.is_trait_interface()),
+                    _ => None,
+                }
+                .ok_or_else(|| anyhow!("\"{}\" can only implement trait interfaces", obj.name()))?;
+                for trait_meth in trait_obj.methods() {
+                    if !obj
+                        .methods()
+                        .into_iter()
+                        .any(|m| m.has_same_signature(trait_meth))
+                    {
+                        bail!(
+                            "\"{}\" implements \"{}\" but has no matching \"{}\" method",
+                            obj.name(),
+                            trait_obj.name(),
+                            trait_meth.name(),
+                        );
+                    }
+                }
+            }
+        }
+        // The bindings add their own `Unknown` variant to non-exhaustive enums.
+        for e in self.enums.values() {
+            if e.has_unknown_variant()
+                && !self.is_name_used_as_error(e.name())
+                && e.variants().iter().any(|v| v.name() == "Unknown")
+            {
+                bail!(
+                    "\"{}\" is non-exhaustive, so the bindings add an \"Unknown\" variant; mark its own \"Unknown\" variant as the default instead",
+                    e.name()
+                );
+            }
+        }
+        // Objects are generated as subclasses of their parent, so they inherit all of its methods.
+        for obj in self.objects.iter() {
+            let mut ancestors = vec![obj.name()];
+            let mut current = obj;
+            while let Some(parent_name) = current.parent() {
+                let parent = self
+                    .get_object_definition(parent_name)
+                    .filter(|p| !p.is_trait_interface())
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "\"{}\" can only inherit from interfaces, not \"{parent_name}\"",
+                            current.name()
+                        )
+                    })?;
+                if ancestors.contains(&parent.name()) {
+                    bail!(
+                        "Interface inheritance cycle: {} -> {}",
+                        ancestors.join(" -> "),
+                        parent.name()
+                    );
+                }
+                for meth in obj.methods() {
+                    if parent.methods.iter().any(|m| m.name() == meth.name()) {
+                        bail!(
+                            "\"{}\" can't redefine \"{}\", which it inherits from \"{}\"",
+                            obj.name(),
+                            meth.name(),
+                            parent.name()
+                        );
+                    }
+                }
+                ancestors.push(parent.name());
+                current = parent;
+            }
+            if obj.parent().is_some() && !obj.uniffi_traits().is_empty() {
+                bail!(
+                    "\"{}\" inherits its traits from \"{}\", so it can't declare its own",
+                    obj.name(),
+                    ancestors[1]
+                );
+            }
+            // Each level of the hierarchy holds its own reference, which a consuming method
+            // can't hand over all of.
+            if obj.has_consuming_methods()
+                && (obj.parent().is_some()
+                    || self.objects.iter().any(|o| o.parent() == Some(obj.name())))
+            {
+                bail!(
+                    "\"{}\" has methods which consume it, so it can't be part of an interface hierarchy",
+                    obj.name()
+                );
+            }
+        }
+        // The bindings subscribe to the events of an observable object with its observer methods.
+        for obj in self.objects.iter().filter(|o| o.is_observable()) {
+            self.resolve_observable(obj)?;
+        }
+        // The bindings iterate over the items of a cursor with its `next_batch` method.
+        for obj in self.objects.iter().filter(|o| o.cursor) {
+            obj.resolve_cursor()?;
+        }
+        // The bindings retry a function by awaiting it again, when it throws a variant marked as
+        // retryable.
+        for f in self.functions.iter().filter(|f| f.retries().is_some()) {
+            if !f.is_async() {
+                bail!("\"{}\" can only be retried if it's async", f.name());
+            }
+            if self.retryable_variants(f).is_empty() {
+                bail!(
+                    "\"{}\" is retried, so it must throw an error with a `[Retryable]` variant",
+                    f.name()
+                );
+            }
+        }
+        // The bindings reassemble chunks into a sequence, and only do so for the plain result of
+        // a sync call.
+        for f in self.functions.iter().filter(|f| f.is_chunked()) {
+            if !matches!(f.return_type(), Some(Type::Sequence { .. })) {
+                bail!("\"{}\" is chunked, so it must return a sequence", f.name());
+            }
+            if f.is_async() || f.throws() {
+                bail!("\"{}\" is chunked, so it can't be async or throw", f.name());
+            }
+        }
+        // The bindings lift the warnings along with the plain result of a sync call.
+        for f in self.functions.iter().filter(|f| f.is_with_warnings()) {
+            if f.return_type().is_none() {
+                bail!(
+                    "\"{}\" returns warnings, so it must return a value",
+                    f.name()
+                );
+            }
+            if f.is_async() || f.is_chunked() || f.default_on_none().is_some() {
+                bail!(
+                    "\"{}\" returns warnings, so it can't be async, chunked or have a default",
+                    f.name()
+                );
+            }
+        }
+        // Byte segments are passed with the `byte_segments_*` FFI functions, which the bindings
+        // only call for the plain arguments and result of a sync top-level function.
+        let is_bytes_sequence =
+            |t: &Type| matches!(t, Type::Sequence { inner_type } if **inner_type == Type::Bytes);
+        for f in self.functions.iter() {
+            let args: Vec<_> = f
+                .arguments()
+                .into_iter()
+                .filter(|arg| arg.is_segmented())
+                .collect();
+            if !f.is_segmented() && args.is_empty() {
+                continue;
+            }
+            if f.is_async() {
+                bail!("\"{}\" can't use [Segmented], since it's async", f.name());
+            }
+            if f.is_segmented() {
+                if !f.return_type().map_or(false, is_bytes_sequence) {
+                    bail!(
+                        "\"{}\" is [Segmented], so it must return a sequence of bytes",
+                        f.name()
+                    );
+                }
+                if f.is_chunked() || f.is_with_warnings() || f.default_on_none().is_some() {
+                    bail!(
+                        "\"{}\" is [Segmented], so it can't be chunked, return warnings or have a default",
+                        f.name()
+                    );
+                }
+            }
+            for arg in args {
+                if !is_bytes_sequence(&arg.type_) {
+                    bail!(
+                        "argument `{}` must be a sequence of bytes to be [Segmented]",
+                        arg.name()
+                    );
+                }
+                if arg.by_ref() || arg.variadic_type().is_some() || arg.default_value().is_some() {
+                    bail!(
+                        "argument `{}` can't be [ByRef], [Variadic] or have a default, since it's [Segmented]",
+                        arg.name()
+                    );
+                }
+            }
+        }
+        let methods = self
+            .objects
+            .iter()
+            .flat_map(|o| o.methods())
+            .chain(self.enums.values().flat_map(|e| e.methods()))
+            .chain(self.callback_interfaces.iter().flat_map(|cb| cb.methods()));
+        let constructors = self.objects.iter().flat_map(|o| o.constructors());
+        if let Some(arg) = methods
+            .flat_map(|m| m.arguments())
+            .chain(constructors.flat_map(|c| c.arguments()))
+            .find(|arg| arg.is_segmented())
+        {
+            bail!(
+                "argument `{}` can't be [Segmented], only top-level functions support it",
+                arg.name()
+            );
+        }
+        // The `_with_progress` variant passes the progress events to a closure while awaiting the
+        // function, and forwards the other arguments to it as they are.
+        for f in self.functions.iter() {
+            let args: Vec<_> = f
+                .arguments()
+                .into_iter()
+                .filter(|arg| arg.is_progress())
+                .collect();
+            let arg = match args.as_slice() {
+                [] => continue,
+                [arg] => *arg,
+                _ => bail!("\"{}\" can only have one [Progress] argument", f.name()),
+            };
+            if !f.is_async() {
+                bail!(
+                    "\"{}\" can only have a [Progress] argument if it's async",
+                    f.name()
+                );
+            }
+            if f.is_extension() || f.arguments().iter().any(|a| a.variadic_type().is_some()) {
+                bail!(
+                    "\"{}\" has a [Progress] argument, so it can't be an extension or have variadic arguments",
+                    f.name()
+                );
+            }
+            self.resolve_progress(f, arg)?;
+        }
+        let methods = self
+            .objects
+            .iter()
+            .flat_map(|o| o.methods())
+            .chain(self.enums.values().flat_map(|e| e.methods()))
+            .chain(self.callback_interfaces.iter().flat_map(|cb| cb.methods()));
+        let constructors = self.objects.iter().flat_map(|o| o.constructors());
+        if let Some(arg) = methods
+            .flat_map(|m| m.arguments())
+            .chain(constructors.flat_map(|c| c.arguments()))
+            .find(|arg| arg.is_progress())
+        {
+            bail!(
+                "argument `{}` can't be [Progress], only top-level functions support it",
+                arg.name()
+            );
+        }
+        // The bindings lift the out-parameters along with the plain result of a sync call, and
+        // return them in place of it.
+        for f in self.functions.iter().filter(|f| f.has_out_args()) {
+            if f.is_async()
+                || f.is_chunked()
+                || f.is_with_warnings()
+                || f.is_segmented()
+                || f.default_on_none().is_some()
+            {
+                bail!(
+                    "\"{}\" has out-parameters, so it can't be async, chunked, return warnings, be [Segmented] or have a default",
+                    f.name()
+                );
+            }
+            if f.is_extension() || f.arguments().iter().any(|a| a.variadic_type().is_some()) {
+                bail!(
+                    "\"{}\" has out-parameters, so it can't be an extension or have variadic arguments",
+                    f.name()
+                );
+            }
+            if f.return_type().is_some() && f.out_arguments().iter().any(|a| a.name() == "value") {
+                bail!(
+                    "\"{}\" returns a value, so its out-parameter can't be named `value`",
+                    f.name()
+                );
+            }
+        }
+        // Cancelling the handle cancels the Rust future, so only async functions have one.
+        for f in self.functions.iter().filter(|f| f.is_cancellable()) {
+            if !f.is_async() {
+                bail!("\"{}\" can only be cancellable if it's async", f.name());
+            }
+        }
+        // The `_or_default` variant forwards its arguments to the function and returns its result
+        // directly.
+        for f in self
+            .functions
+            .iter()
+            .filter(|f| f.default_on_none().is_some())
+        {
+            if f.is_async() {
+                bail!(
+                    "\"{}\" can't be async, since it has [DefaultOnNone]",
+                    f.name()
+                );
+            }
+            if f.arguments()
+                .iter()
+                .any(|arg| arg.variadic_type().is_some())
+            {
+                bail!(
+                    "\"{}\" can't have a [Variadic] argument, since it has [DefaultOnNone]",
+                    f.name()
+                );
+            }
+        }
+        // Async functions can resume on any thread, so the calling thread can't be checked.
+        for f in self.functions.iter().filter(|f| f.is_main_thread()) {
+            if f.is_async() {
+                bail!(
+                    "\"{}\" must be called from the main thread, so it can't be async",
+                    f.name()
+                );
+            }
+        }
+        // Errors are always returned in a `RustBuffer`, which works for enums lowered into a scalar
+        // too since they're serialized as that scalar. The bindings only generate the `RustBuffer`
+        // converter for errors though, so such an enum can't also be passed directly.
+        for c in self.iter_callables() {
+            let direct_types = c
+                .arguments()
+                .into_iter()
+                .map(|a| a.as_type())
+                .chain(c.return_type());
+            for t in direct_types {
+                if let Type::Enum { name, .. } = &t {
+                    if self.is_scalar_enum_used_as_error(name) {
+                        bail!(
+                            "\"{name}\" has no fields so Rust passes it as an i32, but it's used as an error so the bindings pass it in a RustBuffer. Derive `uniffi::Error` rather than `uniffi::Enum` for it, or don't pass it directly as an argument or return value"
+                        );
+                    }
+                }
+            }
+        }
         Ok(())
     }
 
+    /// Problems with the interface which don't stop the bindings from being generated, but which
+    /// are probably mistakes.
+    pub fn warnings(&self) -> Vec<String> {
+        self.unused_types()
+            .into_iter()
+            .filter_map(|t| match t {
+                Type::Record { name, .. }
+                | Type::Enum { name, .. }
+                | Type::CallbackInterface { name, .. }
+                | Type::Custom { name, .. } => {
+                    Some(format!("\"{name}\" is declared but never used"))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get the user-defined types which can't be reached from any function, constant, or
+    /// interface member, and so can never be passed across the FFI.
+    ///
+    /// Interfaces are always reachable, since they're how their members are exposed. Errors are
+    /// reachable through the functions and methods which throw them.
+    pub fn unused_types(&self) -> Vec<&Type> {
+        let mut reachable = BTreeSet::new();
+        let mut pending: Vec<&Type> = self
+            .functions
+            .iter()
+            .chain(self.constants.iter())
+            .flat_map(Function::iter_types)
+            .chain(
+                self.iter_types()
+                    .filter(|t| matches!(t, Type::Object { .. })),
+            )
+            .collect();
+        while let Some(type_) = pending.pop() {
+            for t in self.iter_types_in_item(type_) {
+                // The recursive iterator doesn't look through custom types to their builtin.
+                if reachable.insert(t) {
+                    if let Type::Custom { builtin, .. } = t {
+                        pending.push(builtin);
+                    }
+                }
+            }
+        }
+        self.iter_types()
+            .filter(|t| {
+                matches!(
+                    t,
+                    Type::Record { .. }
+                        | Type::Enum { .. }
+                        | Type::CallbackInterface { .. }
+                        | Type::Custom { .. }
+                )
+            })
+            .filter(|t| !reachable.contains(t))
+            .collect()
+    }
+
+    fn is_scalar_enum_used_as_error(&self, name: &str) -> bool {
+        self.enums.get(name).map_or(false, Enum::is_scalar) && self.is_name_used_as_error(name)
+    }
+
+    /// Get the `FfiType` that a `Type` is lowered into.
+    pub fn ffi_type(&self, t: &Type) -> FfiType {
+        self.ffi_type_map().ffi_type(t)
+    }
+
+    fn ffi_type_map(&self) -> FfiTypeMap<'_> {
+        FfiTypeMap {
+            enums: &self.enums,
+            external_scalar_enums: &self.external_scalar_enums,
+        }
+    }
+
     /// Automatically derive the low-level FFI functions from the high-level types in the interface.
     ///
     /// This should only be called after the high-level types have been completed defined, otherwise
     /// the resulting set will be missing some entries.
     pub fn derive_ffi_funcs(&mut self) -> Result<()> {
-        for func in self.functions.iter_mut() {
-            func.derive_ffi_func()?;
+        // Borrow the fields directly, rather than with `ffi_type_map()`, so that the functions
+        // can be borrowed mutably at the same time.
+        let ffi_types = FfiTypeMap {
+            enums: &self.enums,
+            external_scalar_enums: &self.external_scalar_enums,
+        };
+        for func in self.functions.iter_mut().chain(self.constants.iter_mut()) {
+            func.derive_ffi_func(&ffi_types)?;
         }
         for obj in self.objects.iter_mut() {
-            obj.derive_ffi_funcs()?;
+            obj.derive_ffi_funcs(&ffi_types)?;
         }
         for callback in self.callback_interfaces.iter_mut() {
             callback.derive_ffi_funcs();
         }
+        // Enum methods are taken out of their enums while their FFI functions are derived, since
+        // that needs the enums themselves.
+        let mut enum_methods: Vec<(String, Vec<Method>)> = self
+            .enums
+            .iter_mut()
+            .map(|(name, e)| (name.clone(), std::mem::take(&mut e.methods)))
+            .collect();
+        let ffi_types = FfiTypeMap {
+            enums: &self.enums,
+            external_scalar_enums: &self.external_scalar_enums,
+        };
+        for meth in enum_methods.iter_mut().flat_map(|(_, methods)| methods) {
+            meth.derive_ffi_func(&ffi_types)?;
+        }
+        for (name, methods) in enum_methods {
+            self.enums.get_mut(&name).unwrap().methods = methods;
+        }
         Ok(())
     }
+
+    /// A `ComponentInterface` with the same namespace, but without any items.
+    ///
+    /// This and the `remove_*` methods below are used by the size report to measure how much
+    /// each kind of item contributes to the generated bindings.
+    pub(crate) fn empty_copy(&self) -> Self {
+        Self {
+            types: TypeUniverse::new(self.types.namespace.clone()),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn remove_records(&mut self) {
+        self.records.clear();
+        self.types
+            .retain_known_types(|t| !matches!(t, Type::Record { .. }));
+    }
+
+    pub(crate) fn remove_enums(&mut self) {
+        self.enums.clear();
+        self.types
+            .retain_known_types(|t| !matches!(t, Type::Enum { .. }));
+    }
+
+    pub(crate) fn remove_functions(&mut self) {
+        self.functions.clear();
+        self.constants.clear();
+    }
 }
 
 fn get_object<'a>(objects: &'a mut [Object], name: &str) -> Option<&'a mut Object> {
@@ -1008,6 +2247,7 @@ fn throws_name(throws: &Option<Type>) -> Option<&str> {
     match throws {
         None => None,
         Some(Type::Enum { name, .. }) => Some(name),
+        Some(Type::DynError) => Some("DynError"),
         _ => panic!("unknown throw type: {throws:?}"),
     }
 }
@@ -1019,6 +2259,42 @@ mod test {
     // Note that much of the functionality of `ComponentInterface` is tested via its interactions
     // with specific member types, in the sub-modules defining those member types.
 
+    #[test]
+    fn test_stable_hash() {
+        const UDL: &str = r#"
+            namespace test {
+                u32 add(u32 a, u32 b);
+                Point origin();
+            };
+            dictionary Point { i32 x; i32 y; };
+            enum Shape { "Circle", "Square" };
+            interface Canvas { void draw(Shape shape); };
+        "#;
+        // The same items, declared in a different order.
+        const REORDERED_UDL: &str = r#"
+            namespace test {
+                Point origin();
+                u32 add(u32 a, u32 b);
+            };
+            interface Canvas { void draw(Shape shape); };
+            enum Shape { "Circle", "Square" };
+            dictionary Point { i32 x; i32 y; };
+        "#;
+        let hash = ComponentInterface::from_webidl(UDL, "crate_name")
+            .unwrap()
+            .stable_hash();
+        let reordered_hash = ComponentInterface::from_webidl(REORDERED_UDL, "crate_name")
+            .unwrap()
+            .stable_hash();
+        assert_eq!(hash, reordered_hash);
+
+        let changed_field_udl = UDL.replace("i32 y;", "i64 y;");
+        let changed_hash = ComponentInterface::from_webidl(&changed_field_udl, "crate_name")
+            .unwrap()
+            .stable_hash();
+        assert_ne!(hash, changed_hash);
+    }
+
     #[test]
     fn test_duplicate_type_names_are_an_error() {
         const UDL: &str = r#"
@@ -1059,17 +2335,26 @@ existing definition: Enum {
             discr: None,
             fields: [],
             docstring: None,
+            deprecated: None,
+            retryable: false,
         },
         Variant {
             name: \"two\",
             discr: None,
             fields: [],
             docstring: None,
+            deprecated: None,
+            retryable: false,
         },
     ],
     flat: true,
+    scalar: true,
     non_exhaustive: false,
+    default_variant: None,
+    discriminant: None,
+    methods: [],
     docstring: None,
+    since: None,
 },
 new definition: Enum {
     name: \"Testing\",
@@ -1080,17 +2365,26 @@ new definition: Enum {
             discr: None,
             fields: [],
             docstring: None,
+            deprecated: None,
+            retryable: false,
         },
         Variant {
             name: \"four\",
             discr: None,
             fields: [],
             docstring: None,
+            deprecated: None,
+            retryable: false,
         },
     ],
     flat: true,
+    scalar: false,
     non_exhaustive: false,
+    default_variant: None,
+    discriminant: None,
+    methods: [],
     docstring: None,
+    since: None,
 }",
         );
 
@@ -1106,6 +2400,22 @@ new definition: Enum {
         assert!(format!("{err:#}").contains("Conflicting type definition for \"Testing\""));
     }
 
+    #[test]
+    fn test_namespace_object_conflicts_with_type() {
+        const UDL: &str = r#"
+            namespace test{
+                [Namespace=Testing]
+                u32 grouped();
+            };
+            dictionary Testing {
+                u32 field;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL, "crate_name").unwrap_err();
+        assert!(format!("{err:#}")
+            .contains("Conflicting type definition for namespace object \"Testing\""));
+    }
+
     #[test]
     fn test_contains_optional_types() {
         let mut ci = ComponentInterface {
@@ -1225,4 +2535,971 @@ new definition: Enum {
         let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
         assert_eq!(ci.namespace_docstring().unwrap(), "informative\ndocstring");
     }
+
+    #[test]
+    fn test_ffi_manifest() {
+        const UDL: &str = r#"
+            namespace test {
+                [Throws=Failure]
+                string throwing(u32 value);
+                [Async]
+                u64 waiting(Item item);
+            };
+            [Error]
+            enum Failure { "Oops" };
+            interface Item {};
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let manifest = ci.ffi_manifest();
+        let lines = manifest
+            .lines()
+            .filter(|line| line.contains("_fn_func_") || line.contains("rust_future_poll_u64"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "RustBuffer uniffi_crate_name_fn_func_throwing(uint32_t value, RustCallStatus* out_status); \
+                 // rust_buffer, rust_call_status",
+                "void* uniffi_crate_name_fn_func_waiting(void* item); // async",
+                "void ffi_crate_name_rust_future_poll_u64(void* handle, \
+                 UniFfiRustFutureContinuation callback, void* callback_data);",
+            ]
+        );
+        assert!(manifest.contains(
+            "uint64_t ffi_crate_name_rust_future_complete_u64(void* handle, \
+             RustCallStatus* out_status); // rust_call_status\n"
+        ));
+        assert!(manifest.contains("uint32_t ffi_crate_name_uniffi_contract_version(void);\n"));
+    }
+
+    #[test]
+    fn test_ffi_symbols() {
+        const UDL: &str = r#"
+            namespace test {
+                u32 add(u32 a, u32 b);
+                [Async]
+                void wait();
+            };
+            interface Counter {
+                constructor();
+                void increment();
+            };
+            callback interface Listener {
+                void on_event(string name);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let symbols = ci.ffi_symbols();
+        for symbol in [
+            "ffi_crate_name_uniffi_contract_version",
+            "uniffi_crate_name_fn_init_callback_listener",
+            "uniffi_crate_name_fn_func_add",
+            "uniffi_crate_name_fn_func_wait",
+            "uniffi_crate_name_fn_constructor_counter_new",
+            "uniffi_crate_name_fn_method_counter_increment",
+            "uniffi_crate_name_fn_free_counter",
+            "uniffi_crate_name_checksum_func_add",
+            "ffi_crate_name_rustbuffer_free",
+            "ffi_crate_name_rust_future_poll_void",
+            "ffi_crate_name_rust_future_cancel_void",
+        ] {
+            assert!(symbols.contains(symbol), "missing {symbol}");
+        }
+        // Each FFI function is exported once.
+        assert_eq!(symbols.len(), ci.iter_ffi_function_definitions().count());
+    }
+
+    #[test]
+    fn test_component_version() {
+        let mut ci = ComponentInterface::from_webidl("namespace test {};", "crate_name").unwrap();
+        assert!(!ci.ffi_manifest().contains("uniffi_component_version"));
+
+        ci.set_component_version(Some("1.2.3-beta.1+build".to_string()))
+            .unwrap();
+        assert_eq!(ci.component_version(), Some("1.2.3-beta.1+build"));
+        assert!(ci.ffi_manifest().contains(
+            "RustBuffer ffi_crate_name_uniffi_component_version(void); // rust_buffer\n"
+        ));
+
+        // The version is embedded in string literals, so anything which might need escaping is
+        // rejected.
+        for version in ["", "1.2.3\"", "1.2 3", "${version}"] {
+            let err = ci
+                .set_component_version(Some(version.to_string()))
+                .unwrap_err();
+            assert!(err.to_string().starts_with("Invalid component version"));
+        }
+        assert_eq!(ci.component_version(), Some("1.2.3-beta.1+build"));
+    }
+
+    #[test]
+    fn test_static_functions_as_constants() {
+        let func = |name: &str, static_return: bool, inputs| uniffi_meta::FnMetadata {
+            module_path: "crate_name".into(),
+            name: name.into(),
+            is_async: false,
+            inputs,
+            return_type: Some(Type::String),
+            throws: None,
+            checksum: None,
+            docstring: None,
+            return_docstring: None,
+            since: None,
+            namespace_object: None,
+            deprecated: None,
+            retries: None,
+            main_thread: false,
+            chunked: false,
+            with_warnings: false,
+            segmented: false,
+            cancellable: false,
+            target: None,
+            extension: None,
+            default_on_none: None,
+            static_return,
+            runtime_initializer: false,
+        };
+        let mut ci = ComponentInterface::from_webidl("namespace test {};", "crate_name").unwrap();
+        ci.add_metadata(uniffi_meta::MetadataGroup {
+            namespace: NamespaceMetadata {
+                crate_name: "crate_name".into(),
+                name: "test".into(),
+            },
+            namespace_docstring: None,
+            items: [
+                func("version", true, vec![]).into(),
+                func(
+                    "greeting",
+                    true,
+                    vec![uniffi_meta::FnParamMetadata::simple("name", Type::String)],
+                )
+                .into(),
+                func("build_id", false, vec![]).into(),
+            ]
+            .into(),
+        })
+        .unwrap();
+        assert!(ci.constant_definitions().is_empty());
+
+        // Only the functions without arguments which return a `'static` borrow become constants.
+        ci.expose_static_functions_as_constants();
+        let names = |fns: &[Function]| fns.iter().map(|f| f.name().to_owned()).collect::<Vec<_>>();
+        assert_eq!(names(ci.constant_definitions()), ["version"]);
+        assert_eq!(names(ci.function_definitions()), ["build_id", "greeting"]);
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .any(|f| f.name() == "uniffi_crate_name_fn_func_version"));
+    }
+
+    #[test]
+    fn test_runtime_initializer() {
+        let initializer = |name: &str, inputs, return_type| uniffi_meta::FnMetadata {
+            module_path: "crate_name".into(),
+            name: name.into(),
+            is_async: false,
+            inputs,
+            return_type,
+            throws: None,
+            checksum: None,
+            docstring: None,
+            return_docstring: None,
+            since: None,
+            namespace_object: None,
+            deprecated: None,
+            retries: None,
+            main_thread: false,
+            chunked: false,
+            with_warnings: false,
+            segmented: false,
+            cancellable: false,
+            target: None,
+            extension: None,
+            default_on_none: None,
+            static_return: false,
+            runtime_initializer: true,
+        };
+        let optional = |name: &str| {
+            uniffi_meta::FnParamMetadata::simple(
+                name,
+                Type::Optional {
+                    inner_type: Box::new(Type::UInt32),
+                },
+            )
+        };
+        let add = |items: Vec<uniffi_meta::FnMetadata>| {
+            let mut ci =
+                ComponentInterface::from_webidl("namespace test {};", "crate_name").unwrap();
+            ci.add_metadata(uniffi_meta::MetadataGroup {
+                namespace: NamespaceMetadata {
+                    crate_name: "crate_name".into(),
+                    name: "test".into(),
+                },
+                namespace_docstring: None,
+                items: items.into_iter().map(Into::into).collect(),
+            })
+            .map(|_| ci)
+        };
+
+        let ci = add(vec![initializer(
+            "uniffi_initialize",
+            vec![optional("max_read_depth")],
+            None,
+        )])
+        .unwrap();
+        assert_eq!(
+            ci.runtime_initializer().unwrap().name(),
+            "uniffi_initialize"
+        );
+        assert!(
+            ComponentInterface::from_webidl("namespace test {};", "crate_name")
+                .unwrap()
+                .runtime_initializer()
+                .is_none()
+        );
+
+        // The bindings pass `None` for each setting which isn't set.
+        let err = add(vec![initializer(
+            "uniffi_initialize",
+            vec![uniffi_meta::FnParamMetadata::simple("depth", Type::UInt32)],
+            None,
+        )])
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "runtime initializer \"uniffi_initialize\" must be a sync function with only optional arguments, which returns nothing"
+        );
+        let err = add(vec![initializer(
+            "uniffi_initialize",
+            vec![],
+            Some(Type::String),
+        )])
+        .unwrap_err();
+        assert!(err.to_string().starts_with("runtime initializer"), "{err}");
+        let err = add(vec![
+            initializer("first", vec![], None),
+            initializer("second", vec![], None),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"first\" and \"second\" are both runtime initializers"
+        );
+    }
+
+    #[test]
+    fn test_unused_types() {
+        const UDL: &str = r#"
+            namespace test {
+                [Throws=Failure]
+                Outer outer();
+            };
+            dictionary Outer {
+                Inner inner;
+                Handle handle;
+            };
+            dictionary Inner {
+                u32 value;
+            };
+            dictionary Wrapped {
+                u32 value;
+            };
+            [Custom]
+            typedef Wrapped Handle;
+            [Error]
+            enum Failure { "Oops" };
+            interface Counter {
+                constructor(Step step);
+            };
+            enum Step { "One", "Two" };
+            callback interface Listener {
+                void notify(Event event);
+            };
+            dictionary Event {
+                u32 value;
+            };
+            dictionary Orphan {
+                u32 value;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert_eq!(
+            ci.warnings(),
+            vec![
+                "\"Event\" is declared but never used",
+                "\"Orphan\" is declared but never used",
+                "\"Listener\" is declared but never used",
+            ]
+        );
+    }
+
+    /// A function's metadata, as if it had been exported with proc-macros.
+    fn fn_metadata(
+        name: &str,
+        inputs: Vec<uniffi_meta::FnParamMetadata>,
+        return_type: Option<Type>,
+    ) -> uniffi_meta::FnMetadata {
+        uniffi_meta::FnMetadata {
+            module_path: "crate_name".into(),
+            name: name.into(),
+            is_async: false,
+            inputs,
+            return_type,
+            throws: None,
+            checksum: None,
+            docstring: None,
+            return_docstring: None,
+            since: None,
+            namespace_object: None,
+            deprecated: None,
+            retries: None,
+            main_thread: false,
+            chunked: false,
+            with_warnings: false,
+            segmented: false,
+            cancellable: false,
+            target: None,
+            extension: None,
+            default_on_none: None,
+            static_return: false,
+            runtime_initializer: false,
+        }
+    }
+
+    /// A method's metadata, as if it had been exported with proc-macros.
+    fn method_metadata(
+        self_name: &str,
+        name: &str,
+        return_type: Option<Type>,
+    ) -> uniffi_meta::MethodMetadata {
+        uniffi_meta::MethodMetadata {
+            module_path: "crate_name".into(),
+            self_name: self_name.into(),
+            name: name.into(),
+            is_async: false,
+            inputs: vec![],
+            return_type,
+            throws: None,
+            takes_self_by_arc: false,
+            takes_self_by_value: false,
+            checksum: None,
+            docstring: None,
+            return_docstring: None,
+            since: None,
+        }
+    }
+
+    fn metadata_group(items: Vec<uniffi_meta::Metadata>) -> uniffi_meta::MetadataGroup {
+        uniffi_meta::MetadataGroup {
+            namespace: NamespaceMetadata {
+                crate_name: "crate_name".into(),
+                name: "test".into(),
+            },
+            namespace_docstring: None,
+            items: items.into_iter().collect(),
+        }
+    }
+
+    /// Check that `udl` fails the consistency checks with an error ending in `message`.
+    fn check_udl_error(udl: &str, message: &str) {
+        let err = ComponentInterface::from_webidl(udl, "crate_name").unwrap_err();
+        assert!(format!("{err:#}").ends_with(message), "{err:#}");
+    }
+
+    #[test]
+    fn test_namespace_objects() {
+        const UDL: &str = r#"
+            namespace test {
+                u32 top_level();
+                [Namespace=MyApi]
+                u32 grouped_one();
+                [Namespace=MyApi, Async]
+                u32 grouped_two(u32 value);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert_eq!(ci.namespace_objects(), vec!["MyApi"]);
+        assert_eq!(
+            ci.namespace_object_functions("MyApi")
+                .iter()
+                .map(|f| f.name())
+                .collect::<Vec<_>>(),
+            vec!["grouped_one", "grouped_two"]
+        );
+
+        // The FFI symbols are the same as for any other function.
+        let grouped = ci.get_function_definition("grouped_one").unwrap();
+        assert_eq!(
+            grouped.ffi_func().name(),
+            "uniffi_crate_name_fn_func_grouped_one"
+        );
+    }
+
+    #[test]
+    fn test_optional_throwing_return() {
+        const UDL: &str = r#"
+            namespace test {
+                [Throws=Failure]
+                string? find(u8 index);
+            };
+
+            [Error]
+            enum Failure { "Oops" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let func = ci.get_function_definition("find").unwrap();
+        // The optional is lowered into the return value, and the error is returned through the
+        // call status, so each can be set independently of the other.
+        assert_eq!(
+            func.ffi_func().return_type(),
+            Some(&FfiType::RustBuffer(None))
+        );
+        assert!(func.ffi_func().has_rust_call_status_arg());
+    }
+
+    #[test]
+    fn test_thread_bound_objects() {
+        const UDL: &str = r#"
+            namespace test {};
+            [ThreadBound]
+            interface Context {
+                constructor();
+                void draw();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert!(ci.has_thread_bound_objects());
+
+        let ci = ComponentInterface::from_webidl(
+            "namespace test {}; interface Context { void draw(); };",
+            "crate_name",
+        )
+        .unwrap();
+        assert!(!ci.has_thread_bound_objects());
+    }
+
+    #[test]
+    fn test_on_poison_objects() {
+        const UDL: &str = r#"
+            namespace test {};
+            [OnPoison=error]
+            interface Counter {
+                constructor();
+                u32 increment();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert!(ci.has_on_poison_objects());
+
+        let ci = ComponentInterface::from_webidl(
+            "namespace test {}; interface Counter { u32 increment(); };",
+            "crate_name",
+        )
+        .unwrap();
+        assert!(!ci.has_on_poison_objects());
+    }
+
+    #[test]
+    fn test_constants() {
+        // Constants can only be exported with proc-macros, so add the metadata by hand.
+        let mut ci = ComponentInterface::from_webidl("namespace test {};", "crate_name").unwrap();
+        ci.add_metadata(metadata_group(vec![uniffi_meta::ConstFnMetadata {
+            module_path: "crate_name".into(),
+            name: "app_version".into(),
+            return_type: Type::String,
+            checksum: None,
+            docstring: None,
+        }
+        .into()]))
+            .unwrap();
+        assert!(ci.function_definitions().is_empty());
+        assert_eq!(
+            ci.constant_definitions()[0].ffi_func().name(),
+            "uniffi_crate_name_fn_func_app_version"
+        );
+    }
+
+    #[test]
+    fn test_non_zero() {
+        // Non-zero integers can only be exported with proc-macros, so add the metadata by hand.
+        let non_zero_u32 = Type::NonZero {
+            inner_type: Box::new(Type::UInt32),
+        };
+        let mut ci = ComponentInterface::from_webidl("namespace test {};", "crate_name").unwrap();
+        ci.add_metadata(metadata_group(vec![fn_metadata(
+            "next_id",
+            vec![uniffi_meta::FnParamMetadata::simple(
+                "id",
+                non_zero_u32.clone(),
+            )],
+            Some(non_zero_u32),
+        )
+        .into()]))
+            .unwrap();
+        // They're passed exactly like the integer.
+        let func = ci.get_function_definition("next_id").unwrap();
+        assert_eq!(func.ffi_func().return_type(), Some(&FfiType::UInt32));
+    }
+
+    #[test]
+    fn test_opaque_handle() {
+        const UDL: &str = r#"
+            namespace test {
+                opaque_handle echo_handle(opaque_handle handle);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert!(ci.has_opaque_handle());
+        // Handles are passed as a plain integer.
+        let func = ci.get_function_definition("echo_handle").unwrap();
+        assert_eq!(func.ffi_func().return_type(), Some(&FfiType::UInt64));
+        assert_eq!(func.ffi_func().arguments()[0].type_(), FfiType::UInt64);
+    }
+
+    #[test]
+    fn test_duration_ranges() {
+        const UDL: &str = r#"
+            namespace test {
+                duration_range shift(duration_range window, duration by);
+                duration_range_inclusive shift_closed(duration_range_inclusive window, duration by);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert!(ci.has_duration_range(false) && ci.has_duration_range(true));
+        let func = ci.get_function_definition("shift").unwrap();
+        assert_eq!(
+            func.ffi_func().return_type(),
+            Some(&FfiType::RustBuffer(None))
+        );
+    }
+
+    #[test]
+    fn test_retry() {
+        const UDL: &str = r#"
+            namespace test {
+                [Async, Retry=3, Throws=NetworkError]
+                string fetch();
+            };
+            [Error]
+            enum NetworkError {
+                [Retryable] "Timeout",
+                [Retryable] "Unavailable",
+                "NotFound",
+            };
+        "#;
+        ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+
+        // Functions are only retried if they're async and throw a retryable error.
+        let udl = UDL.replace("[Async, Retry=3", "[Retry=3");
+        let err = ComponentInterface::from_webidl(&udl, "crate_name").unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "ComponentInterface consistency error: \"fetch\" can only be retried if it's async"
+        );
+        check_udl_error(
+            &UDL.replace("[Retryable] ", ""),
+            "\"fetch\" is retried, so it must throw an error with a `[Retryable]` variant",
+        );
+    }
+
+    #[test]
+    fn test_main_thread_async() {
+        // Async functions can't check the calling thread.
+        check_udl_error(
+            r#"
+            namespace test {
+                [Async, MainThread]
+                void update_ui();
+            };
+            "#,
+            "\"update_ui\" must be called from the main thread, so it can't be async",
+        );
+    }
+
+    #[test]
+    fn test_enum_unknown_variant() {
+        const UDL: &str = r#"
+            namespace test {};
+            [NonExhaustive]
+            enum Color { "Red", "Green" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert!(ci
+            .get_enum_definition("Color")
+            .unwrap()
+            .has_unknown_variant());
+
+        // A variant of its own called "Unknown" would clash with the generated one.
+        const CLASH: &str = r#"
+            namespace test {};
+            [NonExhaustive]
+            enum Color { "Red", "Unknown" };
+        "#;
+        let err = ComponentInterface::from_webidl(CLASH, "crate_name").unwrap_err();
+        assert!(format!("{err:#}").contains("mark its own \"Unknown\" variant as the default"));
+    }
+
+    #[test]
+    fn test_object_identity_equality() {
+        const UDL: &str = r#"
+            namespace test {};
+            interface Counter {
+                constructor();
+            };
+            [Traits=(Eq)]
+            interface Point {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let counter = ci.get_object_definition("Counter").unwrap();
+        assert!(counter.has_identity_equality());
+        assert!(counter.has_identity_hash());
+        let point = ci.get_object_definition("Point").unwrap();
+        assert!(!point.has_identity_equality());
+        assert!(!point.has_identity_hash());
+    }
+
+    #[test]
+    fn test_chunked_sequences() {
+        const UDL: &str = r#"
+            namespace test {
+                [Chunked]
+                sequence<string> get_names();
+                sequence<string> get_names_unchunked();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert!(ci.has_chunked_fns());
+        let names = ci.get_function_definition("get_names").unwrap();
+        assert_eq!(names.ffi_func().return_type(), Some(&FfiType::UInt64));
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .any(|f| f.name() == "ffi_crate_name_chunked_sequence_next"));
+
+        // The bindings only reassemble the plain result of a sync call.
+        check_udl_error(
+            r#"
+            namespace test {
+                [Chunked, Throws=Error]
+                sequence<string> get_names();
+            };
+            [Error]
+            enum Error { "Oops" };
+            "#,
+            "\"get_names\" is chunked, so it can't be async or throw",
+        );
+        check_udl_error(
+            "namespace test { [Chunked] string get_name(); };",
+            "\"get_name\" is chunked, so it must return a sequence",
+        );
+    }
+
+    #[test]
+    fn test_with_warnings() {
+        const UDL: &str = r#"
+            namespace test {
+                [WithWarnings]
+                u32 parse(string input);
+                [WithWarnings, Throws=Error]
+                string load();
+            };
+            [Error]
+            enum Error { "Oops" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert!(ci.has_with_warnings_fns());
+        let parse = ci.get_function_definition("parse").unwrap();
+        assert_eq!(
+            parse.ffi_func().return_type(),
+            Some(&FfiType::RustBuffer(None))
+        );
+
+        // The bindings only lift the warnings along with the plain result of a sync call.
+        check_udl_error(
+            "namespace test { [Async, WithWarnings] u32 parse(string input); };",
+            "\"parse\" returns warnings, so it can't be async, chunked or have a default",
+        );
+        check_udl_error(
+            "namespace test { [WithWarnings] void parse(string input); };",
+            "\"parse\" returns warnings, so it must return a value",
+        );
+    }
+
+    #[test]
+    fn test_segmented() {
+        const UDL: &str = r#"
+            namespace test {
+                [Segmented]
+                sequence<bytes> split(bytes data, u32 size);
+                [Throws=Error]
+                u64 total_len([Segmented] sequence<bytes> segments);
+            };
+            [Error]
+            enum Error { "Oops" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert!(ci.has_segmented_fns());
+        let split = ci.get_function_definition("split").unwrap();
+        assert_eq!(split.ffi_func().return_type(), Some(&FfiType::UInt64));
+        let total_len = ci.get_function_definition("total_len").unwrap();
+        assert_eq!(total_len.ffi_func().arguments()[0].type_(), FfiType::UInt64);
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .any(|f| f.name() == "ffi_crate_name_byte_segments_push"));
+
+        check_udl_error(
+            "namespace test { [Segmented] bytes read(); };",
+            "\"read\" is [Segmented], so it must return a sequence of bytes",
+        );
+        check_udl_error(
+            "namespace test { void write([Segmented] sequence<string> lines); };",
+            "argument `lines` must be a sequence of bytes to be [Segmented]",
+        );
+        check_udl_error(
+            "namespace test { [Async] void write([Segmented] sequence<bytes> segments); };",
+            "\"write\" can't use [Segmented], since it's async",
+        );
+        check_udl_error(
+            r#"
+            namespace test {};
+            interface Writer {
+                void write([Segmented] sequence<bytes> segments);
+            };
+            "#,
+            "argument `segments` can't be [Segmented], only top-level functions support it",
+        );
+    }
+
+    #[test]
+    fn test_has_default() {
+        const UDL: &str = r#"
+            namespace test {};
+            [HasDefault]
+            dictionary Settings {
+                string name;
+                u32 retries;
+            };
+            dictionary Point {
+                i32 x;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let settings = ci.get_record_definition("Settings").unwrap();
+        assert!(settings.has_default());
+        assert!(!ci.get_record_definition("Point").unwrap().has_default());
+        let ffi_func = settings.ffi_func_default();
+        assert_eq!(ffi_func.name(), "uniffi_crate_name_fn_default_settings");
+        assert_eq!(ffi_func.return_type(), Some(&FfiType::RustBuffer(None)));
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .any(|f| f.name() == "uniffi_crate_name_fn_default_settings"));
+    }
+
+    #[test]
+    fn test_progress() {
+        const UDL: &str = r#"
+            namespace test {
+                [Async]
+                u32 count_steps(u32 steps, [Progress] ProgressListener listener);
+            };
+            callback interface ProgressListener {
+                void on_progress(u32 step);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert!(ci.has_progress_fns());
+        let func = ci.get_function_definition("count_steps").unwrap();
+        let progress = ci.progress(func).unwrap();
+        assert_eq!(progress.argument().name(), "listener");
+        assert_eq!(progress.event_method().name(), "on_progress");
+        assert_eq!(progress.event_type(), &Type::UInt32);
+
+        check_udl_error(
+            &UDL.replace("[Async]\n", ""),
+            "\"count_steps\" can only have a [Progress] argument if it's async",
+        );
+        check_udl_error(
+            &UDL.replace("void on_progress", "boolean on_progress"),
+            "\"ProgressListener\" receives the progress of \"count_steps\", so it must have a single method which takes the event and returns nothing",
+        );
+        check_udl_error(
+            r#"
+            namespace test {
+                [Async]
+                u32 count_steps([Progress] u32 steps);
+            };
+            "#,
+            "argument `steps` must be a callback interface to be [Progress]",
+        );
+    }
+
+    #[test]
+    fn test_enum_methods() {
+        // Methods can only be exported for enums with proc-macros, so add the metadata by hand.
+        const UDL: &str = r#"
+            namespace test {};
+            [Enum]
+            interface Shape {
+                Circle(double radius);
+                Point();
+            };
+            [Error]
+            enum ShapeError { "Oops" };
+        "#;
+        let area = |self_name: &str, is_async: bool| {
+            metadata_group(vec![uniffi_meta::MethodMetadata {
+                is_async,
+                ..method_metadata(self_name, "area", Some(Type::Float64))
+            }
+            .into()])
+        };
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        ci.add_metadata(area("Shape", false)).unwrap();
+        let shape = ci.get_enum_definition("Shape").unwrap();
+        assert_eq!(shape.methods().len(), 1);
+        // The enum is passed by value, in a `RustBuffer` like any other argument.
+        let ffi_func = shape.methods()[0].ffi_func();
+        assert_eq!(ffi_func.arguments()[0].name(), "value");
+        assert_eq!(ffi_func.arguments()[0].type_(), FfiType::RustBuffer(None));
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .any(|f| f.name() == "uniffi_crate_name_fn_method_shape_area"));
+
+        // The bindings can't generate methods for errors or async methods for enums.
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let err = ci.add_metadata(area("ShapeError", false)).unwrap_err();
+        assert!(format!("{err:#}")
+            .ends_with("Error enum \"ShapeError\" can't have methods, but \"area\" was exported"));
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let err = ci.add_metadata(area("Shape", true)).unwrap_err();
+        assert!(format!("{err:#}").ends_with("Enum method \"Shape.area\" can't be async"));
+    }
+
+    #[test]
+    fn test_consuming_methods() {
+        // Methods can only take `self` by value with proc-macros, so add the metadata by hand.
+        const UDL: &str = r#"
+            namespace test {};
+            interface Builder {
+                constructor();
+                void append(string part);
+            };
+        "#;
+        let build = |self_name: &str| {
+            metadata_group(vec![uniffi_meta::MethodMetadata {
+                takes_self_by_value: true,
+                ..method_metadata(self_name, "build", Some(Type::String))
+            }
+            .into()])
+        };
+        let mut ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        ci.add_metadata(build("Builder")).unwrap();
+        let builder = ci.get_object_definition("Builder").unwrap();
+        assert!(builder.has_consuming_methods());
+        assert!(builder.get_method("build").takes_self_by_value());
+        assert!(!builder.get_method("append").takes_self_by_value());
+
+        // Objects in a hierarchy hold a reference for each level, which can't all be handed over.
+        const HIERARCHY_UDL: &str = r#"
+            namespace test {};
+            interface Parent {};
+            interface Child : Parent {};
+        "#;
+        for self_name in ["Parent", "Child"] {
+            let mut ci = ComponentInterface::from_webidl(HIERARCHY_UDL, "crate_name").unwrap();
+            let err = ci.add_metadata(build(self_name)).unwrap_err();
+            assert!(format!("{err:#}").ends_with(&format!(
+                "\"{self_name}\" has methods which consume it, so it can't be part of an interface hierarchy"
+            )));
+        }
+    }
+
+    #[test]
+    fn test_cancellable() {
+        // Only async functions can be cancelled.
+        check_udl_error(
+            "namespace test { [Cancellable] u32 download(string url); };",
+            "\"download\" can only be cancellable if it's async",
+        );
+    }
+
+    #[test]
+    fn test_default_on_none() {
+        const UDL: &str = r#"
+            namespace test {
+                [DefaultOnNone="7"]
+                u32? find_count(string key);
+                [DefaultOnNone="unknown"]
+                string? find_name();
+            };
+        "#;
+        ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+
+        // The default must be a valid value of the type, and the function must return an optional.
+        let udl = UDL.replace("[DefaultOnNone=\"7\"]", "[DefaultOnNone=\"seven\"]");
+        let err = ComponentInterface::from_webidl(&udl, "crate_name").unwrap_err();
+        assert!(format!("{err:#}")
+            .starts_with("[DefaultOnNone=\"seven\"] isn't a valid default for \"find_count\""));
+        let udl = UDL.replace("u32? find_count", "u32 find_count");
+        let err = ComponentInterface::from_webidl(&udl, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"find_count\" must return an optional to have [DefaultOnNone]"
+        );
+        check_udl_error(
+            &UDL.replace("[DefaultOnNone=\"7\"]", "[Async, DefaultOnNone=\"7\"]"),
+            "\"find_count\" can't be async, since it has [DefaultOnNone]",
+        );
+    }
+
+    #[test]
+    fn test_out_params() {
+        // Out-parameters can only be exported with proc-macros, so add the metadata by hand.
+        let out_param = |name: &str, ty| uniffi_meta::FnParamMetadata {
+            out: true,
+            ..uniffi_meta::FnParamMetadata::simple(name, ty)
+        };
+        let mut ci = ComponentInterface::from_webidl("namespace test {};", "crate_name").unwrap();
+        ci.add_metadata(metadata_group(vec![
+            fn_metadata(
+                "divide",
+                vec![
+                    uniffi_meta::FnParamMetadata::simple("dividend", Type::UInt32),
+                    uniffi_meta::FnParamMetadata::simple("divisor", Type::UInt32),
+                    out_param("remainder", Type::UInt32),
+                ],
+                Some(Type::UInt32),
+            )
+            .into(),
+            fn_metadata("trim", vec![out_param("text", Type::String)], None).into(),
+        ]))
+        .unwrap();
+        assert!(ci.has_out_params_fns());
+        // The values are returned in a buffer, even when the function returns nothing.
+        for name in ["divide", "trim"] {
+            let func = ci.get_function_definition(name).unwrap();
+            assert_eq!(
+                func.ffi_func().return_type(),
+                Some(&FfiType::RustBuffer(None))
+            );
+        }
+
+        // The bindings only lift the out-parameters along with the plain result of a sync call.
+        let mut ci = ComponentInterface::from_webidl("namespace test {};", "crate_name").unwrap();
+        let err = ci
+            .add_metadata(metadata_group(vec![uniffi_meta::FnMetadata {
+                with_warnings: true,
+                ..fn_metadata(
+                    "trim",
+                    vec![out_param("text", Type::String)],
+                    Some(Type::UInt32),
+                )
+            }
+            .into()]))
+            .and_then(|_| ci.check_consistency())
+            .unwrap_err();
+        assert!(format!("{err:#}").ends_with(
+            "\"trim\" has out-parameters, so it can't be async, chunked, return warnings, be [Segmented] or have a default"
+        ));
+    }
 }
@@ -57,12 +57,12 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
-use anyhow::Result;
-use uniffi_meta::Checksum;
+use anyhow::{bail, Result};
+use uniffi_meta::{Checksum, OnPoison};
 
-use super::ffi::{FfiArgument, FfiFunction, FfiType};
+use super::ffi::{FfiArgument, FfiFunction, FfiType, FfiTypeMap};
 use super::function::{Argument, Callable};
-use super::{AsType, ObjectImpl, Type, TypeIterator};
+use super::{AsType, Literal, ObjectImpl, Type, TypeIterator};
 
 /// An "object" is an opaque type that is passed around by reference, can
 /// have methods called on it, and so on - basically your classic Object Oriented Programming
@@ -90,6 +90,28 @@ pub struct Object {
     // a regular method (albeit with a generated name)
     // XXX - this should really be a HashSet, but not enough transient types support hash to make it worthwhile now.
     pub(super) uniffi_traits: Vec<UniffiTrait>,
+    // Whether the `Display` trait above is a UDL `stringifier` method.
+    #[checksum_ignore]
+    pub(super) has_stringifier: bool,
+    // The trait interfaces this object can be used as.
+    pub(super) implements: Vec<Type>,
+    // The interface this object inherits from.
+    pub(super) parent: Option<String>,
+    pub(super) constants: Vec<AssociatedConstant>,
+    // Whether the bindings generate a stream of the events passed to this object's observers.
+    #[checksum_ignore]
+    pub(super) observable: bool,
+    // Whether the bindings generate helpers iterating over this object's batches of items.
+    #[checksum_ignore]
+    pub(super) cursor: bool,
+    // Whether the scaffolding checks that this object's methods are called on the thread which
+    // constructed it.
+    #[checksum_ignore]
+    pub(super) thread_bound: bool,
+    // What the scaffolding does when this object's methods panic on state poisoned by an
+    // earlier call.
+    #[checksum_ignore]
+    pub(super) on_poison: Option<OnPoison>,
     // We don't include the FfiFuncs in the hash calculation, because:
     //  - it is entirely determined by the other fields,
     //    so excluding it is safe.
@@ -103,11 +125,22 @@ pub struct Object {
     // FFI function to free a pointer for this object
     #[checksum_ignore]
     pub(super) ffi_func_free: FfiFunction,
+    // FFI function to get the id of the object behind a pointer
+    #[checksum_ignore]
+    pub(super) ffi_func_handle_id: FfiFunction,
     // Ffi function to initialize the foreign callback for trait interfaces
     #[checksum_ignore]
     pub(super) ffi_init_callback: Option<FfiFunction>,
+    // FFI function to get the instance of a singleton object
+    #[checksum_ignore]
+    pub(super) ffi_func_singleton: Option<FfiFunction>,
+    // FFI function to convert the object to its parent interface
+    #[checksum_ignore]
+    pub(super) ffi_func_upcast: Option<FfiFunction>,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    #[checksum_ignore]
+    pub(super) since: Option<String>,
 }
 
 impl Object {
@@ -115,6 +148,10 @@ impl Object {
         &self.name
     }
 
+    pub fn module_path(&self) -> &str {
+        &self.module_path
+    }
+
     /// Returns the fully qualified name that should be used by Rust code for this object.
     /// Includes `r#`, traits get a leading `dyn`. If we ever supported associated types, then
     /// this would also include them.
@@ -126,10 +163,65 @@ impl Object {
         &self.imp
     }
 
+    /// Is there only one instance of this object, which is shared by all callers?
+    pub fn is_singleton(&self) -> bool {
+        self.ffi_func_singleton.is_some()
+    }
+
     pub fn is_trait_interface(&self) -> bool {
         matches!(self.imp, ObjectImpl::Trait)
     }
 
+    /// Do the bindings generate a stream of the events passed to this object's observers?
+    pub fn is_observable(&self) -> bool {
+        self.observable
+    }
+
+    /// Must this object's methods be called on the thread which constructed it?
+    pub fn is_thread_bound(&self) -> bool {
+        self.thread_bound
+    }
+
+    /// The `[OnPoison]` policy of this object's methods, if it has one.
+    pub fn on_poison(&self) -> Option<OnPoison> {
+        self.on_poison
+    }
+
+    /// Get the method the bindings fetch the items of a `[Cursor]` object with, or None if the
+    /// object isn't a cursor.
+    pub fn cursor(&self) -> Option<Cursor<'_>> {
+        if self.cursor {
+            self.resolve_cursor().ok()
+        } else {
+            None
+        }
+    }
+
+    pub(super) fn resolve_cursor(&self) -> Result<Cursor<'_>> {
+        let Some(next_batch) = self.methods.iter().find(|m| m.name() == "next_batch") else {
+            bail!(
+                "\"{}\" is a cursor, so it needs a `next_batch` method",
+                self.name
+            );
+        };
+        let item_type = match (next_batch.arguments.as_slice(), &next_batch.return_type) {
+            ([arg], Some(Type::Sequence { inner_type })) if arg.type_ == Type::UInt32 => {
+                inner_type.as_ref()
+            }
+            _ => bail!(
+                "\"{}.next_batch\" must take the number of items to fetch as a `u32` and return a sequence of them",
+                self.name
+            ),
+        };
+        if next_batch.is_async() {
+            bail!("\"{}.next_batch\" can't be async", self.name);
+        }
+        Ok(Cursor {
+            next_batch,
+            item_type,
+        })
+    }
+
     pub fn constructors(&self) -> Vec<&Constructor> {
         self.constructors.iter().collect()
     }
@@ -147,10 +239,45 @@ impl Object {
             .collect()
     }
 
+    pub fn has_async_constructor(&self) -> bool {
+        self.constructors.iter().any(Constructor::is_async)
+    }
+
     pub fn methods(&self) -> Vec<&Method> {
         self.methods.iter().collect()
     }
 
+    /// Do any of the methods consume the object?
+    pub fn has_consuming_methods(&self) -> bool {
+        self.methods.iter().any(Method::takes_self_by_value)
+    }
+
+    /// Describe who owns the Rust object behind an instance, for the `ownership_docs` config.
+    ///
+    /// `release` says how the bindings release an instance's reference, like "when the instance
+    /// is deinitialized".
+    pub fn ownership_doc(&self, release: &str) -> String {
+        let dyn_prefix = if self.is_trait_interface() {
+            "dyn "
+        } else {
+            ""
+        };
+        let mut doc = format!(
+            "Each instance owns a reference to a Rust `Arc<{dyn_prefix}{}>`, which is released \
+            {release}. The Rust object is freed when its last reference is released.",
+            self.name
+        );
+        if self.has_consuming_methods() {
+            doc.push_str(" Methods which consume the instance hand its reference over to Rust.");
+        }
+        doc
+    }
+
+    /// Does the trait provide default implementations for any of its methods?
+    pub fn has_default_methods(&self) -> bool {
+        self.methods.iter().any(Method::has_default)
+    }
+
     pub fn get_method(&self, name: &str) -> Method {
         let matches: Vec<_> = self.methods.iter().filter(|m| m.name() == name).collect();
         match matches.len() {
@@ -163,6 +290,36 @@ impl Object {
         self.uniffi_traits.iter().collect()
     }
 
+    /// The trait interfaces this object can be used as.
+    ///
+    /// The object has a method matching each method of these traits, so the bindings can declare
+    /// the object as implementing the foreign interfaces generated for them.
+    pub fn implements(&self) -> Vec<&Type> {
+        self.implements.iter().collect()
+    }
+
+    /// The name of the interface this object inherits from, declared in UDL as
+    /// `interface Child : Parent`.
+    ///
+    /// The bindings generate the object as a subclass of its parent, so it has all of the parent's
+    /// methods and can be passed wherever the parent is expected.
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
+
+    /// Constants associated with this object, for example `Color.RED`.
+    pub fn constants(&self) -> Vec<&AssociatedConstant> {
+        self.constants.iter().collect()
+    }
+
+    /// Is `ut` implemented by a `stringifier` method of this object, rather than by the Rust
+    /// `Display` trait?
+    ///
+    /// A stringifier uses the FFI function of the method, so no separate FFI function is needed.
+    pub fn is_stringifier(&self, ut: &UniffiTrait) -> bool {
+        self.has_stringifier && matches!(ut, UniffiTrait::Display { .. })
+    }
+
     pub fn ffi_object_clone(&self) -> &FfiFunction {
         &self.ffi_func_clone
     }
@@ -171,36 +328,93 @@ impl Object {
         &self.ffi_func_free
     }
 
+    /// The FFI function which returns the id of the object behind a pointer, which is the same
+    /// for every pointer to that object.
+    pub fn ffi_object_handle_id(&self) -> &FfiFunction {
+        &self.ffi_func_handle_id
+    }
+
+    /// Whether the bindings compare instances by the identity of the Rust object, because the
+    /// object doesn't export `Eq`.  Objects with a parent interface inherit its equality.
+    pub fn has_identity_equality(&self) -> bool {
+        self.parent.is_none()
+            && !self
+                .uniffi_traits
+                .iter()
+                .any(|t| matches!(t, UniffiTrait::Eq { .. }))
+    }
+
+    /// Whether the bindings hash instances by the identity of the Rust object, because the object
+    /// uses identity equality and doesn't export `Hash`.
+    pub fn has_identity_hash(&self) -> bool {
+        self.has_identity_equality()
+            && !self
+                .uniffi_traits
+                .iter()
+                .any(|t| matches!(t, UniffiTrait::Hash { .. }))
+    }
+
     pub fn ffi_init_callback(&self) -> &FfiFunction {
         self.ffi_init_callback
             .as_ref()
             .unwrap_or_else(|| panic!("No ffi_init_callback set for {}", &self.name))
     }
 
+    pub fn ffi_object_singleton(&self) -> &FfiFunction {
+        self.ffi_func_singleton
+            .as_ref()
+            .unwrap_or_else(|| panic!("{} is not a singleton", &self.name))
+    }
+
+    /// The FFI function which takes a borrowed pointer to this object and returns a new pointer
+    /// to its parent interface.
+    pub fn ffi_object_upcast(&self) -> &FfiFunction {
+        self.ffi_func_upcast
+            .as_ref()
+            .unwrap_or_else(|| panic!("{} doesn't inherit from another interface", &self.name))
+    }
+
     pub fn docstring(&self) -> Option<&str> {
         self.docstring.as_deref()
     }
 
+    /// The version the interface was added in, from a `@since` docstring tag.
+    pub fn since(&self) -> Option<&str> {
+        self.since.as_deref()
+    }
+
     pub fn iter_ffi_function_definitions(&self) -> impl Iterator<Item = &FfiFunction> {
-        [&self.ffi_func_clone, &self.ffi_func_free]
-            .into_iter()
-            .chain(&self.ffi_init_callback)
-            .chain(self.constructors.iter().map(|f| &f.ffi_func))
-            .chain(self.methods.iter().map(|f| &f.ffi_func))
-            .chain(
-                self.uniffi_traits
-                    .iter()
-                    .flat_map(|ut| match ut {
-                        UniffiTrait::Display { fmt: m }
-                        | UniffiTrait::Debug { fmt: m }
-                        | UniffiTrait::Hash { hash: m } => vec![m],
-                        UniffiTrait::Eq { eq, ne } => vec![eq, ne],
-                    })
-                    .map(|m| &m.ffi_func),
-            )
-    }
-
-    pub fn derive_ffi_funcs(&mut self) -> Result<()> {
+        [
+            &self.ffi_func_clone,
+            &self.ffi_func_free,
+            &self.ffi_func_handle_id,
+        ]
+        .into_iter()
+        .chain(&self.ffi_init_callback)
+        .chain(&self.ffi_func_singleton)
+        .chain(&self.ffi_func_upcast)
+        .chain(self.constructors.iter().map(|f| &f.ffi_func))
+        .chain(self.methods.iter().map(|f| &f.ffi_func))
+        .chain(
+            self.methods
+                .iter()
+                .filter_map(|f| f.ffi_default_func.as_ref()),
+        )
+        .chain(
+            self.uniffi_traits
+                .iter()
+                .filter(|ut| !self.is_stringifier(ut))
+                .flat_map(|ut| match ut {
+                    UniffiTrait::Display { fmt: m }
+                    | UniffiTrait::Debug { fmt: m }
+                    | UniffiTrait::Hash { hash: m } => vec![m],
+                    UniffiTrait::Eq { eq, ne } => vec![eq, ne],
+                })
+                .map(|m| &m.ffi_func),
+        )
+    }
+
+    pub(super) fn derive_ffi_funcs(&mut self, ffi_types: &FfiTypeMap<'_>) -> Result<()> {
         assert!(!self.ffi_func_clone.name().is_empty());
         assert!(!self.ffi_func_free.name().is_empty());
         self.ffi_func_clone.arguments = vec![FfiArgument {
@@ -214,19 +428,35 @@ impl Object {
         }];
         self.ffi_func_free.return_type = None;
         self.ffi_func_free.is_object_free_function = true;
+        self.ffi_func_handle_id.arguments = vec![FfiArgument {
+            name: "ptr".to_string(),
+            type_: FfiType::RustArcPtr(self.name.to_string()),
+        }];
+        self.ffi_func_handle_id.return_type = Some(FfiType::UInt64);
         if self.is_trait_interface() {
             self.ffi_init_callback =
                 Some(FfiFunction::callback_init(&self.module_path, &self.name));
         }
+        if let Some(ffi_func_singleton) = &mut self.ffi_func_singleton {
+            ffi_func_singleton.arguments = vec![];
+            ffi_func_singleton.return_type = Some(FfiType::RustArcPtr(self.name.to_string()));
+        }
+        if let (Some(ffi_func_upcast), Some(parent)) = (&mut self.ffi_func_upcast, &self.parent) {
+            ffi_func_upcast.arguments = vec![FfiArgument {
+                name: "ptr".to_string(),
+                type_: FfiType::RustArcPtr(self.name.to_string()),
+            }];
+            ffi_func_upcast.return_type = Some(FfiType::RustArcPtr(parent.to_string()));
+        }
 
         for cons in self.constructors.iter_mut() {
-            cons.derive_ffi_func();
+            cons.derive_ffi_func(ffi_types);
         }
         for meth in self.methods.iter_mut() {
-            meth.derive_ffi_func()?;
+            meth.derive_ffi_func(ffi_types)?;
         }
         for ut in self.uniffi_traits.iter_mut() {
-            ut.derive_ffi_func()?;
+            ut.derive_ffi_func(ffi_types)?;
         }
 
         Ok(())
@@ -258,6 +488,15 @@ impl From<uniffi_meta::ObjectMetadata> for Object {
     fn from(meta: uniffi_meta::ObjectMetadata) -> Self {
         let ffi_clone_name = meta.clone_ffi_symbol_name();
         let ffi_free_name = meta.free_ffi_symbol_name();
+        let ffi_handle_id_name = meta.handle_id_ffi_symbol_name();
+        let ffi_func_singleton = meta.is_singleton.then(|| FfiFunction {
+            name: meta.singleton_ffi_symbol_name(),
+            ..Default::default()
+        });
+        let ffi_func_upcast = meta.parent.is_some().then(|| FfiFunction {
+            name: meta.upcast_ffi_symbol_name(),
+            ..Default::default()
+        });
         Object {
             module_path: meta.module_path,
             name: meta.name,
@@ -265,6 +504,14 @@ impl From<uniffi_meta::ObjectMetadata> for Object {
             constructors: Default::default(),
             methods: Default::default(),
             uniffi_traits: Default::default(),
+            has_stringifier: false,
+            implements: meta.implements,
+            parent: meta.parent,
+            constants: meta.constants.into_iter().map(Into::into).collect(),
+            observable: meta.observable,
+            cursor: meta.cursor,
+            thread_bound: meta.thread_bound,
+            on_poison: meta.on_poison,
             ffi_func_clone: FfiFunction {
                 name: ffi_clone_name,
                 ..Default::default()
@@ -273,19 +520,94 @@ impl From<uniffi_meta::ObjectMetadata> for Object {
                 name: ffi_free_name,
                 ..Default::default()
             },
+            ffi_func_handle_id: FfiFunction {
+                name: ffi_handle_id_name,
+                ..Default::default()
+            },
             ffi_init_callback: None,
+            ffi_func_singleton,
+            ffi_func_upcast,
             docstring: meta.docstring.clone(),
+            since: meta.since,
         }
     }
 }
 
+/// The methods of an `[Observable]` object which the bindings subscribe to its events with.
+///
+/// `add_observer` takes the observer, a callback interface with a single method which is passed
+/// each event.  `remove_observer` takes the value `add_observer` returns, or the observer itself if
+/// it doesn't return anything.
+#[derive(Debug, Clone, Copy)]
+pub struct Observable<'a> {
+    pub(super) add: &'a Method,
+    pub(super) remove: &'a Method,
+    pub(super) on_event: &'a Method,
+}
+
+impl<'a> Observable<'a> {
+    pub fn add_method(&self) -> &'a Method {
+        self.add
+    }
+
+    pub fn remove_method(&self) -> &'a Method {
+        self.remove
+    }
+
+    /// The type of the observer passed to `add_observer`.
+    pub fn observer_type(&self) -> &'a Type {
+        &self.add.arguments[0].type_
+    }
+
+    /// The observer's method which is passed each event.
+    pub fn event_method(&self) -> &'a Method {
+        self.on_event
+    }
+
+    /// The argument of the observer's method which is the event.
+    pub fn event_argument(&self) -> &'a Argument {
+        &self.on_event.arguments[0]
+    }
+
+    pub fn event_type(&self) -> &'a Type {
+        &self.event_argument().type_
+    }
+
+    /// Does `add_observer` return a token which is passed to `remove_observer`, rather than
+    /// `remove_observer` taking the observer?
+    pub fn has_token(&self) -> bool {
+        self.add.return_type().is_some()
+    }
+}
+
+/// The method of a `[Cursor]` object which the bindings fetch its items with.
+///
+/// `next_batch` takes the maximum number of items to return.  Returning fewer items, including
+/// none, ends the iteration.
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    pub(super) next_batch: &'a Method,
+    pub(super) item_type: &'a Type,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn next_batch_method(&self) -> &'a Method {
+        self.next_batch
+    }
+
+    pub fn item_type(&self) -> &'a Type {
+        self.item_type
+    }
+}
+
 impl From<uniffi_meta::UniffiTraitMetadata> for UniffiTrait {
     fn from(meta: uniffi_meta::UniffiTraitMetadata) -> Self {
         match meta {
             uniffi_meta::UniffiTraitMetadata::Debug { fmt } => {
                 UniffiTrait::Debug { fmt: fmt.into() }
             }
-            uniffi_meta::UniffiTraitMetadata::Display { fmt } => {
+            uniffi_meta::UniffiTraitMetadata::Display { fmt }
+            | uniffi_meta::UniffiTraitMetadata::Stringifier { fmt } => {
                 UniffiTrait::Display { fmt: fmt.into() }
             }
             uniffi_meta::UniffiTraitMetadata::Eq { eq, ne } => UniffiTrait::Eq {
@@ -301,6 +623,49 @@ impl From<uniffi_meta::UniffiTraitMetadata> for UniffiTrait {
 
 // Represents a constructor for an object type.
 //
+/// A constant associated with an object, declared in UDL as `const type NAME = value;`.
+///
+/// Constants don't cross the FFI, the bindings declare them with the literal value.
+#[derive(Debug, Clone, Checksum)]
+pub struct AssociatedConstant {
+    pub(super) name: String,
+    pub(super) type_: Type,
+    pub(super) value: Literal,
+    #[checksum_ignore]
+    pub(super) docstring: Option<String>,
+}
+
+impl AssociatedConstant {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &Literal {
+        &self.value
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+}
+
+impl AsType for AssociatedConstant {
+    fn as_type(&self) -> Type {
+        self.type_.clone()
+    }
+}
+
+impl From<uniffi_meta::ConstantMetadata> for AssociatedConstant {
+    fn from(meta: uniffi_meta::ConstantMetadata) -> Self {
+        Self {
+            name: meta.name,
+            type_: meta.ty,
+            value: meta.value,
+            docstring: meta.docstring,
+        }
+    }
+}
+
 // In the FFI, this will be a function that returns a pointer to an instance
 // of the corresponding object type.
 #[derive(Debug, Clone, Checksum)]
@@ -308,6 +673,7 @@ pub struct Constructor {
     pub(super) name: String,
     pub(super) object_name: String,
     pub(super) object_module_path: String,
+    pub(super) is_async: bool,
     pub(super) arguments: Vec<Argument>,
     // We don't include the FFIFunc in the hash calculation, because:
     //  - it is entirely determined by the other fields,
@@ -319,6 +685,8 @@ pub struct Constructor {
     pub(super) ffi_func: FfiFunction,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    #[checksum_ignore]
+    pub(super) since: Option<String>,
     pub(super) throws: Option<Type>,
     pub(super) checksum_fn_name: String,
     // Force a checksum value, or we'll fallback to the trait.
@@ -331,6 +699,10 @@ impl Constructor {
         &self.name
     }
 
+    pub fn is_async(&self) -> bool {
+        self.is_async
+    }
+
     pub fn arguments(&self) -> Vec<&Argument> {
         self.arguments.iter().collect()
     }
@@ -367,18 +739,38 @@ impl Constructor {
         self.docstring.as_deref()
     }
 
+    pub fn since(&self) -> Option<&str> {
+        self.since.as_deref()
+    }
+
     pub fn is_primary_constructor(&self) -> bool {
         self.name == "new"
     }
 
-    fn derive_ffi_func(&mut self) {
+    /// Describe who owns the constructed object, for the `ownership_docs` config.
+    ///
+    /// See [Object::ownership_doc] for `release`.
+    pub fn ownership_doc(&self, release: &str) -> String {
+        format!(
+            "The new instance owns a reference to the Rust object, which is released {release}."
+        )
+    }
+
+    fn derive_ffi_func(&mut self, ffi_types: &FfiTypeMap<'_>) {
         assert!(!self.ffi_func.name().is_empty());
-        self.ffi_func.arguments = self.arguments.iter().map(Into::into).collect();
-        self.ffi_func.return_type = Some(FfiType::RustArcPtr(self.object_name.clone()));
+        self.ffi_func.init(
+            Some(FfiType::RustArcPtr(self.object_name.clone())),
+            self.arguments.iter().map(|a| ffi_types.ffi_argument(a)),
+        );
     }
 
     pub fn iter_types(&self) -> TypeIterator<'_> {
-        Box::new(self.arguments.iter().flat_map(Argument::iter_types))
+        Box::new(
+            self.arguments
+                .iter()
+                .flat_map(Argument::iter_types)
+                .chain(self.throws.iter().flat_map(Type::iter_types)),
+        )
     }
 }
 
@@ -386,19 +778,23 @@ impl From<uniffi_meta::ConstructorMetadata> for Constructor {
     fn from(meta: uniffi_meta::ConstructorMetadata) -> Self {
         let ffi_name = meta.ffi_symbol_name();
         let checksum_fn_name = meta.checksum_symbol_name();
+        let is_async = meta.is_async;
         let arguments = meta.inputs.into_iter().map(Into::into).collect();
 
         let ffi_func = FfiFunction {
             name: ffi_name,
+            is_async,
             ..FfiFunction::default()
         };
         Self {
             name: meta.name,
             object_name: meta.self_name,
             object_module_path: meta.module_path,
+            is_async,
             arguments,
             ffi_func,
             docstring: meta.docstring.clone(),
+            since: meta.since,
             throws: meta.throws.map(Into::into),
             checksum_fn_name,
             checksum: meta.checksum,
@@ -410,7 +806,7 @@ impl From<uniffi_meta::ConstructorMetadata> for Constructor {
 //
 // The FFI will represent this as a function whose first/self argument is a
 // `FfiType::RustArcPtr` to the instance.
-#[derive(Debug, Clone, Checksum)]
+#[derive(Debug, Clone, PartialEq, Eq, Checksum)]
 pub struct Method {
     pub(super) name: String,
     pub(super) object_name: String,
@@ -427,10 +823,22 @@ pub struct Method {
     //    avoids a weird circular dependency in the calculation.
     #[checksum_ignore]
     pub(super) ffi_func: FfiFunction,
+    // Trait methods with a default implementation also have an FFI function to call the Rust
+    // default, which foreign implementations use if they don't override the method.
+    #[checksum_ignore]
+    pub(super) ffi_default_func: Option<FfiFunction>,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    #[checksum_ignore]
+    pub(super) return_docstring: Option<String>,
+    #[checksum_ignore]
+    pub(super) since: Option<String>,
     pub(super) throws: Option<Type>,
     pub(super) takes_self_by_arc: bool,
+    pub(super) takes_self_by_value: bool,
+    // Is this a method of an enum, which is passed by value rather than as a pointer?
+    #[checksum_ignore]
+    pub(super) enum_receiver: bool,
     pub(super) checksum_fn_name: String,
     // Force a checksum value, or we'll fallback to the trait.
     #[checksum_ignore]
@@ -454,23 +862,45 @@ impl Method {
     // hence `arguments` and `full_arguments` are different.
     pub fn full_arguments(&self) -> Vec<Argument> {
         vec![Argument {
-            name: "ptr".to_string(),
-            // TODO: ideally we'd get this via `ci.resolve_type_expression` so that it
-            // is contained in the proper `TypeUniverse`, but this works for now.
-            type_: Type::Object {
-                name: self.object_name.clone(),
-                module_path: self.object_module_path.clone(),
-                imp: self.object_impl,
-            },
+            name: if self.enum_receiver { "value" } else { "ptr" }.to_string(),
+            type_: self.self_type(),
             by_ref: !self.takes_self_by_arc,
             optional: false,
             default: None,
+            variadic: false,
+            segmented: false,
+            progress: false,
+            out: false,
+            docstring: None,
         }]
         .into_iter()
         .chain(self.arguments.iter().cloned())
         .collect()
     }
 
+    /// Is the method called on an enum, rather than an object?
+    pub fn is_enum_method(&self) -> bool {
+        self.enum_receiver
+    }
+
+    /// The type of the value the method is called on: an object, or an enum.
+    pub fn self_type(&self) -> Type {
+        // TODO: ideally we'd get this via `ci.resolve_type_expression` so that it
+        // is contained in the proper `TypeUniverse`, but this works for now.
+        if self.enum_receiver {
+            Type::Enum {
+                name: self.object_name.clone(),
+                module_path: self.object_module_path.clone(),
+            }
+        } else {
+            Type::Object {
+                name: self.object_name.clone(),
+                module_path: self.object_module_path.clone(),
+                imp: self.object_impl,
+            }
+        }
+    }
+
     pub fn return_type(&self) -> Option<&Type> {
         self.return_type.as_ref()
     }
@@ -479,6 +909,14 @@ impl Method {
         &self.ffi_func
     }
 
+    pub fn has_default(&self) -> bool {
+        self.ffi_default_func.is_some()
+    }
+
+    pub fn ffi_default_func(&self) -> Option<&FfiFunction> {
+        self.ffi_default_func.as_ref()
+    }
+
     pub fn checksum_fn_name(&self) -> &str {
         &self.checksum_fn_name
     }
@@ -503,16 +941,85 @@ impl Method {
         self.docstring.as_deref()
     }
 
+    pub fn return_docstring(&self) -> Option<&str> {
+        self.return_docstring.as_deref()
+    }
+
+    pub fn since(&self) -> Option<&str> {
+        self.since.as_deref()
+    }
+
     pub fn takes_self_by_arc(&self) -> bool {
         self.takes_self_by_arc
     }
 
-    pub fn derive_ffi_func(&mut self) -> Result<()> {
+    /// Does this method consume the object?
+    ///
+    /// The bindings hand their reference to the Rust object over to the call, so the object can't
+    /// be used afterwards.
+    pub fn takes_self_by_value(&self) -> bool {
+        self.takes_self_by_value
+    }
+
+    /// Describe who owns the object during the call, and any objects it returns, for the
+    /// `ownership_docs` config.
+    ///
+    /// See [Object::ownership_doc] for `release`.
+    pub fn ownership_doc(&self, release: &str) -> String {
+        let mut doc = if self.takes_self_by_value {
+            "Consumes this instance: its reference is handed over to Rust, and the instance \
+            can't be used after the call."
+        } else if self.takes_self_by_arc {
+            "Rust gets its own reference to this object, which it can keep after the call."
+        } else {
+            "Rust borrows this object for the duration of the call."
+        }
+        .to_string();
+        let returns_objects = self
+            .return_type
+            .iter()
+            .flat_map(Type::iter_types)
+            .any(|t| matches!(t, Type::Object { .. }));
+        if returns_objects {
+            doc.push_str(&format!(
+                " Returned objects own a reference to their Rust object, which is released \
+                {release}."
+            ));
+        }
+        doc
+    }
+
+    /// Can this method implement `other` in the foreign bindings?
+    ///
+    /// This compares everything which ends up in the foreign method signature.
+    pub fn has_same_signature(&self, other: &Method) -> bool {
+        self.name == other.name
+            && self.is_async == other.is_async
+            && self.return_type == other.return_type
+            && self.throws == other.throws
+            && self.arguments.len() == other.arguments.len()
+            && self
+                .arguments
+                .iter()
+                .zip(&other.arguments)
+                .all(|(a, b)| a.name == b.name && a.type_ == b.type_)
+    }
+
+    pub(super) fn derive_ffi_func(&mut self, ffi_types: &FfiTypeMap<'_>) -> Result<()> {
         assert!(!self.ffi_func.name().is_empty());
         self.ffi_func.init(
-            self.return_type.as_ref().map(Into::into),
-            self.full_arguments().iter().map(Into::into),
+            self.return_type.as_ref().map(|t| ffi_types.ffi_type(t)),
+            self.full_arguments()
+                .iter()
+                .map(|a| ffi_types.ffi_argument(a)),
         );
+        // The default function has the same signature as the method itself.
+        if let Some(ffi_default_func) = &mut self.ffi_default_func {
+            *ffi_default_func = FfiFunction {
+                name: std::mem::take(&mut ffi_default_func.name),
+                ..self.ffi_func.clone()
+            };
+        }
         Ok(())
     }
 
@@ -521,7 +1028,8 @@ impl Method {
             self.arguments
                 .iter()
                 .flat_map(Argument::iter_types)
-                .chain(self.return_type.iter().flat_map(Type::iter_types)),
+                .chain(self.return_type.iter().flat_map(Type::iter_types))
+                .chain(self.throws.iter().flat_map(Type::iter_types)),
         )
     }
 }
@@ -549,9 +1057,14 @@ impl From<uniffi_meta::MethodMetadata> for Method {
             arguments,
             return_type,
             ffi_func,
+            ffi_default_func: None,
             docstring: meta.docstring.clone(),
+            return_docstring: meta.return_docstring,
+            since: meta.since,
             throws: meta.throws.map(Into::into),
             takes_self_by_arc: meta.takes_self_by_arc,
+            takes_self_by_value: meta.takes_self_by_value,
+            enum_receiver: false, // will be filled in later
             checksum_fn_name,
             checksum: meta.checksum,
         }
@@ -562,6 +1075,10 @@ impl From<uniffi_meta::TraitMethodMetadata> for Method {
     fn from(meta: uniffi_meta::TraitMethodMetadata) -> Self {
         let ffi_name = meta.ffi_symbol_name();
         let checksum_fn_name = meta.checksum_symbol_name();
+        let ffi_default_func = meta.has_default.then(|| FfiFunction {
+            name: meta.default_ffi_symbol_name(),
+            ..FfiFunction::default()
+        });
         let return_type = meta.return_type.map(Into::into);
         let arguments = meta.inputs.into_iter().map(Into::into).collect();
         let ffi_func = FfiFunction {
@@ -576,11 +1093,16 @@ impl From<uniffi_meta::TraitMethodMetadata> for Method {
             arguments,
             return_type,
             docstring: meta.docstring.clone(),
+            return_docstring: meta.return_docstring,
+            since: meta.since,
             throws: meta.throws.map(Into::into),
             takes_self_by_arc: meta.takes_self_by_arc,
+            takes_self_by_value: false,
+            enum_receiver: false,
             checksum_fn_name,
             checksum: meta.checksum,
             ffi_func,
+            ffi_default_func,
             object_impl: ObjectImpl::Struct,
         }
     }
@@ -609,16 +1131,16 @@ impl UniffiTrait {
         )
     }
 
-    pub fn derive_ffi_func(&mut self) -> Result<()> {
+    pub(super) fn derive_ffi_func(&mut self, ffi_types: &FfiTypeMap<'_>) -> Result<()> {
         match self {
             UniffiTrait::Display { fmt: m }
             | UniffiTrait::Debug { fmt: m }
             | UniffiTrait::Hash { hash: m } => {
-                m.derive_ffi_func()?;
+                m.derive_ffi_func(ffi_types)?;
             }
             UniffiTrait::Eq { eq, ne } => {
-                eq.derive_ffi_func()?;
-                ne.derive_ffi_func()?;
+                eq.derive_ffi_func(ffi_types)?;
+                ne.derive_ffi_func(ffi_types)?;
             }
         }
         Ok(())
@@ -643,7 +1165,19 @@ impl Callable for Constructor {
     }
 
     fn is_async(&self) -> bool {
-        false
+        self.is_async
+    }
+
+    fn docstring(&self) -> Option<&str> {
+        self.docstring()
+    }
+
+    fn return_docstring(&self) -> Option<&str> {
+        None
+    }
+
+    fn since(&self) -> Option<&str> {
+        self.since()
     }
 }
 
@@ -663,11 +1197,23 @@ impl Callable for Method {
     fn is_async(&self) -> bool {
         self.is_async
     }
+
+    fn docstring(&self) -> Option<&str> {
+        self.docstring()
+    }
+
+    fn return_docstring(&self) -> Option<&str> {
+        self.return_docstring()
+    }
+
+    fn since(&self) -> Option<&str> {
+        self.since()
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::super::ComponentInterface;
+    use super::super::{ComponentInterface, Radix};
     use super::*;
 
     #[test]
@@ -832,60 +1378,763 @@ mod test {
     }
 
     #[test]
-    fn test_docstring_object() {
+    fn test_singleton() {
         const UDL: &str = r#"
             namespace test{};
-            /// informative docstring
-            interface Testing { };
+            [Singleton]
+            interface Testing {
+                u32 value();
+            };
+            interface NotASingleton {
+            };
         "#;
         let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        assert!(obj.is_singleton());
+        let ffi_func = obj.ffi_object_singleton();
+        assert_eq!(ffi_func.name(), "uniffi_crate_name_fn_singleton_testing");
+        assert!(ffi_func.arguments().is_empty());
         assert_eq!(
-            ci.get_object_definition("Testing")
-                .unwrap()
-                .docstring()
-                .unwrap(),
-            "informative docstring"
+            ffi_func.return_type(),
+            Some(&FfiType::RustArcPtr("Testing".to_string()))
         );
-    }
+        assert!(obj
+            .iter_ffi_function_definitions()
+            .any(|f| f.name() == ffi_func.name()));
+        assert!(!ci
+            .get_object_definition("NotASingleton")
+            .unwrap()
+            .is_singleton());
 
-    #[test]
-    fn test_docstring_constructor() {
-        const UDL: &str = r#"
+        const UDL2: &str = r#"
             namespace test{};
+            [Singleton]
             interface Testing {
-                /// informative docstring
                 constructor();
             };
         "#;
-        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let err = ComponentInterface::from_webidl(UDL2, "crate_name").unwrap_err();
         assert_eq!(
-            ci.get_object_definition("Testing")
-                .unwrap()
-                .primary_constructor()
-                .unwrap()
-                .docstring()
-                .unwrap(),
-            "informative docstring"
+            err.to_string(),
+            "Singleton interfaces can not have constructors: \"new\""
+        );
+
+        const UDL3: &str = r#"
+            namespace test{};
+            [Trait, Singleton]
+            interface Testing {
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Trait interfaces can not be singletons: \"Testing\""
         );
     }
 
     #[test]
-    fn test_docstring_method() {
+    fn test_thread_bound() {
         const UDL: &str = r#"
             namespace test{};
+            [ThreadBound]
             interface Testing {
-                /// informative docstring
-                void testing();
+                constructor();
+                u32 value();
+            };
+            interface NotThreadBound {
             };
         "#;
         let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
-        assert_eq!(
-            ci.get_object_definition("Testing")
-                .unwrap()
-                .get_method("testing")
-                .docstring()
-                .unwrap(),
-            "informative docstring"
+        assert!(ci
+            .get_object_definition("Testing")
+            .unwrap()
+            .is_thread_bound());
+        assert!(!ci
+            .get_object_definition("NotThreadBound")
+            .unwrap()
+            .is_thread_bound());
+
+        let check_err = |udl: &str, message: &str| {
+            let err = ComponentInterface::from_webidl(udl, "crate_name").unwrap_err();
+            assert_eq!(err.to_string(), message);
+        };
+        check_err(
+            "namespace test{}; [Trait, ThreadBound] interface Testing {};",
+            "Trait and singleton interfaces can not be thread-bound: \"Testing\"",
+        );
+        check_err(
+            "namespace test{}; [Singleton, ThreadBound] interface Testing {};",
+            "Trait and singleton interfaces can not be thread-bound: \"Testing\"",
+        );
+        check_err(
+            "namespace test{}; [ThreadBound] interface Testing { [Async] u32 value(); };",
+            "Thread-bound interfaces can not have async methods: \"value\"",
+        );
+        check_err(
+            "namespace test{}; interface Parent {}; [ThreadBound] interface Testing : Parent {};",
+            "Thread-bound interfaces can not inherit from other interfaces: \"Testing\"",
+        );
+    }
+
+    #[test]
+    fn test_on_poison() {
+        const UDL: &str = r#"
+            namespace test{};
+            [OnPoison=recover]
+            interface Testing {
+                constructor();
+                u32 value();
+            };
+            interface NoPolicy {
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert_eq!(
+            ci.get_object_definition("Testing").unwrap().on_poison(),
+            Some(OnPoison::Recover)
+        );
+        assert_eq!(
+            ci.get_object_definition("NoPolicy").unwrap().on_poison(),
+            None
+        );
+
+        let check_err = |udl: &str, message: &str| {
+            let err = ComponentInterface::from_webidl(udl, "crate_name").unwrap_err();
+            assert_eq!(err.to_string(), message);
+        };
+        check_err(
+            "namespace test{}; [Trait, OnPoison=error] interface Testing {};",
+            "Trait interfaces can not have an OnPoison policy: \"Testing\"",
+        );
+        check_err(
+            "namespace test{}; [OnPoison=error] interface Testing { [Async] u32 value(); };",
+            "Interfaces with an OnPoison policy can not have async methods: \"value\"",
+        );
+    }
+
+    #[test]
+    fn test_constants() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                const u32 RED = 0xFF0000;
+                const i8 OFFSET = -1;
+                const boolean ENABLED = false;
+                const string? NAME = null;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        let constants = obj.constants();
+        assert_eq!(constants.len(), 4);
+        assert_eq!(constants[0].name(), "RED");
+        assert_eq!(constants[0].as_type(), Type::UInt32);
+        assert!(matches!(
+            constants[0].value(),
+            Literal::UInt(0xFF0000, Radix::Hexadecimal, Type::UInt32)
+        ));
+        assert!(matches!(
+            constants[1].value(),
+            Literal::Int(-1, Radix::Decimal, Type::Int8)
+        ));
+        assert!(matches!(constants[2].value(), Literal::Boolean(false)));
+        assert!(matches!(constants[3].value(), Literal::Null));
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [Trait]
+            interface Testing {
+                const u32 RED = 0xFF0000;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Trait interfaces can not have constants: \"RED\""
+        );
+
+        const UDL3: &str = r#"
+            namespace test{};
+            interface Testing {
+                const u32 RED = 0xFF0000;
+                string RED();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3, "crate_name").unwrap_err();
+        assert_eq!(err.to_string(), "Duplicate interface member name: \"RED\"");
+
+        const UDL4: &str = r#"
+            namespace test{};
+            interface Testing {
+                const u32 RED = 1.5;
+            };
+        "#;
+        assert!(ComponentInterface::from_webidl(UDL4, "crate_name").is_err());
+    }
+
+    #[test]
+    fn test_computed_constants() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                const u32 BUFFER_SIZE = PAGE_SIZE * 4 + HEADER_SIZE;
+                const u32 PAGE_SIZE = 1 << 12;
+                const u32 HEADER_SIZE = 0x10;
+                const i8 OFFSET = -1 - 2 * 3;
+                const boolean ENABLED = false;
+                const boolean DEFAULT_ENABLED = ENABLED;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        let constants = obj.constants();
+        assert_eq!(constants[0].name(), "BUFFER_SIZE");
+        assert!(matches!(
+            constants[0].value(),
+            Literal::UInt(16400, Radix::Decimal, Type::UInt32)
+        ));
+        assert!(matches!(
+            constants[1].value(),
+            Literal::UInt(4096, Radix::Decimal, Type::UInt32)
+        ));
+        // Plain literals keep their radix.
+        assert!(matches!(
+            constants[2].value(),
+            Literal::UInt(0x10, Radix::Hexadecimal, Type::UInt32)
+        ));
+        assert!(matches!(
+            constants[3].value(),
+            Literal::Int(-7, Radix::Decimal, Type::Int8)
+        ));
+        assert!(matches!(constants[5].value(), Literal::Boolean(false)));
+
+        let check_err = |consts: &str, expected: &str| {
+            let udl = format!("namespace test{{}}; interface Testing {{ {consts} }};");
+            let err = ComponentInterface::from_webidl(&udl, "crate_name").unwrap_err();
+            assert_eq!(err.to_string(), expected);
+        };
+        check_err(
+            "const u32 A = 4 / ZERO; const u32 ZERO = 0;",
+            "Division by zero in the value of `A`",
+        );
+        check_err("const u32 A = B * 2;", "Unknown constant `B` at 1:53");
+        check_err(
+            "const u32 A = B + 1; const u32 B = A;",
+            "Constant `A` refers to itself: `A` -> `B` -> `A` at 1:74",
+        );
+        check_err(
+            "const u32 A = 1 - 2;",
+            "The value of `A` (-1) is out of range for its type",
+        );
+        check_err(
+            "const u8 A = 1 << 64;",
+            "The value of `A` overflows at `1 << 64`",
+        );
+        check_err(
+            "const double A = 1.5 * 2;",
+            "Only integer constants can be computed from expressions: `A`",
+        );
+    }
+
+    #[test]
+    fn test_implements() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Trait]
+            interface Named {
+                string name();
+            };
+            [Implements=(Named)]
+            interface Testing {
+                string name();
+                u32 other();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        assert_eq!(
+            obj.implements(),
+            vec![&ci.get_object_definition("Named").unwrap().as_type()]
+        );
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [Trait]
+            interface Named {
+                string name();
+            };
+            [Implements=(Named)]
+            interface Testing {
+                string name(u32 v);
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2, "crate_name").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "ComponentInterface consistency error: \"Testing\" implements \"Named\" but has no matching \"name\" method"
+        );
+
+        const UDL3: &str = r#"
+            namespace test{};
+            interface Named {
+                string name();
+            };
+            [Implements=(Named)]
+            interface Testing {
+                string name();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"Testing\" can only implement trait interfaces, not \"Named\""
+        );
+    }
+
+    #[test]
+    fn test_inheritance() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Animal {
+                string name();
+            };
+            interface Dog : Animal {
+                string fetch();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert_eq!(ci.get_object_definition("Animal").unwrap().parent(), None);
+        let obj = ci.get_object_definition("Dog").unwrap();
+        assert_eq!(obj.parent(), Some("Animal"));
+        let ffi_func = obj.ffi_object_upcast();
+        assert_eq!(ffi_func.name(), "uniffi_crate_name_fn_upcast_dog");
+        assert_eq!(
+            ffi_func.arguments()[0].type_(),
+            FfiType::RustArcPtr("Dog".to_string())
+        );
+        assert_eq!(
+            ffi_func.return_type(),
+            Some(&FfiType::RustArcPtr("Animal".to_string()))
+        );
+        assert!(obj
+            .iter_ffi_function_definitions()
+            .any(|f| f.name() == ffi_func.name()));
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [Trait]
+            interface Animal {
+                string name();
+            };
+            interface Dog : Animal {};
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"Dog\" can only inherit from interfaces, not \"Animal\""
+        );
+
+        const UDL3: &str = r#"
+            namespace test{};
+            interface Animal : Dog {};
+            interface Dog : Animal {};
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3, "crate_name").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "ComponentInterface consistency error: Interface inheritance cycle: Animal -> Dog -> Animal"
+        );
+
+        const UDL4: &str = r#"
+            namespace test{};
+            interface Animal {
+                string name();
+            };
+            interface Dog : Animal {
+                string name();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL4, "crate_name").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "ComponentInterface consistency error: \"Dog\" can't redefine \"name\", which it inherits from \"Animal\""
+        );
+
+        const UDL5: &str = r#"
+            namespace test{};
+            interface Animal {};
+            [Traits=(Debug)]
+            interface Dog : Animal {};
+        "#;
+        let err = ComponentInterface::from_webidl(UDL5, "crate_name").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "ComponentInterface consistency error: \"Dog\" inherits its traits from \"Animal\", so it can't declare its own"
+        );
+    }
+
+    #[test]
+    fn test_stringifier() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                stringifier string describe();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        assert_eq!(obj.methods().len(), 1);
+        let ut = obj.uniffi_traits()[0];
+        assert!(obj.is_stringifier(ut));
+        match ut {
+            UniffiTrait::Display { fmt } => {
+                assert_eq!(
+                    fmt.ffi_func().name(),
+                    obj.get_method("describe").ffi_func().name()
+                )
+            }
+            _ => panic!("stringifier should be a Display trait"),
+        }
+        // The method's FFI function is only declared once.
+        let names: Vec<_> = obj
+            .iter_ffi_function_definitions()
+            .map(|f| f.name())
+            .filter(|name| name.ends_with("describe"))
+            .collect();
+        assert_eq!(names.len(), 1);
+
+        // The `Display` trait is only a stringifier when it's declared as one.
+        const UDL2: &str = r#"
+            namespace test{};
+            [Traits=(Display)]
+            interface Testing {
+                string describe();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL2, "crate_name").unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        assert!(!obj.is_stringifier(obj.uniffi_traits()[0]));
+    }
+
+    #[test]
+    fn test_invalid_stringifiers() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                stringifier string describe(u32 v);
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Stringifiers must take no arguments and return a string: \"describe\""
+        );
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [Traits=(Display)]
+            interface Testing {
+                stringifier string describe();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Interfaces with a stringifier can't also have the Display trait"
+        );
+
+        const UDL3: &str = r#"
+            namespace test{};
+            [Trait]
+            interface Testing {
+                stringifier string describe();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Trait interfaces can not have stringifiers: \"describe\""
+        );
+    }
+
+    #[test]
+    fn test_async_constructors() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                [Async]
+                constructor();
+                [Name=new_with_u32, Async]
+                constructor(u32 v);
+                [Name=new_sync]
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+
+        let cons = obj.primary_constructor().unwrap();
+        assert!(cons.is_async());
+        assert!(cons.ffi_func().is_async());
+        assert_eq!(
+            cons.ffi_func().return_type(),
+            Some(&FfiType::RustFutureHandle)
+        );
+        assert!(!cons.ffi_func().has_rust_call_status_arg());
+
+        let cons = obj
+            .alternate_constructors()
+            .into_iter()
+            .find(|c| c.name() == "new_with_u32")
+            .unwrap();
+        assert!(cons.is_async());
+        assert_eq!(cons.ffi_func().arguments().len(), 1);
+        assert_eq!(
+            cons.ffi_func().return_type(),
+            Some(&FfiType::RustFutureHandle)
+        );
+
+        let cons = obj
+            .alternate_constructors()
+            .into_iter()
+            .find(|c| c.name() == "new_sync")
+            .unwrap();
+        assert!(!cons.is_async());
+        assert_eq!(
+            cons.ffi_func().return_type(),
+            Some(&FfiType::RustArcPtr("Testing".to_string()))
+        );
+        assert!(ci.has_async_fns());
+    }
+
+    #[test]
+    fn test_docstring_object() {
+        const UDL: &str = r#"
+            namespace test{};
+            /// informative docstring
+            interface Testing { };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert_eq!(
+            ci.get_object_definition("Testing")
+                .unwrap()
+                .docstring()
+                .unwrap(),
+            "informative docstring"
+        );
+    }
+
+    #[test]
+    fn test_docstring_constructor() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                /// informative docstring
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert_eq!(
+            ci.get_object_definition("Testing")
+                .unwrap()
+                .primary_constructor()
+                .unwrap()
+                .docstring()
+                .unwrap(),
+            "informative docstring"
+        );
+    }
+
+    #[test]
+    fn test_docstring_method() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                /// informative docstring
+                void testing();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        assert_eq!(
+            ci.get_object_definition("Testing")
+                .unwrap()
+                .get_method("testing")
+                .docstring()
+                .unwrap(),
+            "informative docstring"
+        );
+    }
+
+    #[test]
+    fn test_observable() {
+        const UDL: &str = r#"
+            namespace test{};
+            callback interface Listener {
+                void on_event(u32 value);
+            };
+            [Observable]
+            interface Counter {
+                u64 add_observer(Listener listener);
+                void remove_observer(u64 token);
+            };
+            [Observable]
+            interface Timer {
+                void add_observer(Listener listener);
+                void remove_observer(Listener listener);
+            };
+            interface Plain {
+                void add_observer(Listener listener);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let obj = ci.get_object_definition("Counter").unwrap();
+        let observable = ci.observable(obj).unwrap();
+        assert_eq!(observable.add_method().name(), "add_observer");
+        assert_eq!(observable.remove_method().name(), "remove_observer");
+        assert_eq!(observable.event_method().name(), "on_event");
+        assert_eq!(observable.event_argument().name(), "value");
+        assert_eq!(observable.event_type(), &Type::UInt32);
+        assert!(matches!(
+            observable.observer_type(),
+            Type::CallbackInterface { name, .. } if name == "Listener"
+        ));
+        assert!(observable.has_token());
+
+        let obj = ci.get_object_definition("Timer").unwrap();
+        assert!(!ci.observable(obj).unwrap().has_token());
+        let obj = ci.get_object_definition("Plain").unwrap();
+        assert!(ci.observable(obj).is_none());
+
+        let check_err = |udl: &str, message: &str| {
+            let err = ComponentInterface::from_webidl(udl, "crate_name").unwrap_err();
+            assert_eq!(
+                format!("{err:#}"),
+                format!("ComponentInterface consistency error: {message}")
+            );
+        };
+        check_err(
+            r#"
+            namespace test{};
+            callback interface Listener {
+                void on_event(u32 value);
+            };
+            [Observable]
+            interface Counter {
+                void add_observer(Listener listener);
+            };
+            "#,
+            "\"Counter\" is observable, so it needs a `remove_observer` method",
+        );
+        check_err(
+            r#"
+            namespace test{};
+            [Observable]
+            interface Counter {
+                void add_observer(u32 listener);
+                void remove_observer(u32 listener);
+            };
+            "#,
+            "\"Counter.add_observer\" must take a single callback interface argument",
+        );
+        check_err(
+            r#"
+            namespace test{};
+            callback interface Listener {
+                void on_event(u32 value);
+            };
+            [Observable]
+            interface Counter {
+                u64 add_observer(Listener listener);
+                void remove_observer(Listener listener);
+            };
+            "#,
+            "\"Counter.remove_observer\" must take the value returned by `add_observer`, or the observer if it doesn't return anything",
+        );
+        check_err(
+            r#"
+            namespace test{};
+            callback interface Listener {
+                void on_event(u32 value, u32 other);
+            };
+            [Observable]
+            interface Counter {
+                void add_observer(Listener listener);
+                void remove_observer(Listener listener);
+            };
+            "#,
+            "\"Listener\" observes \"Counter\", so it must have a single method which takes the event and returns nothing",
+        );
+    }
+
+    #[test]
+    fn test_cursor() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Cursor]
+            interface Rows {
+                sequence<string> next_batch(u32 count);
+            };
+            interface Plain {
+                sequence<string> next_batch(u32 count);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let cursor = ci.get_object_definition("Rows").unwrap().cursor().unwrap();
+        assert_eq!(cursor.next_batch_method().name(), "next_batch");
+        assert_eq!(cursor.item_type(), &Type::String);
+        assert!(ci
+            .get_object_definition("Plain")
+            .unwrap()
+            .cursor()
+            .is_none());
+
+        let check_err = |udl: &str, message: &str| {
+            let err = ComponentInterface::from_webidl(udl, "crate_name").unwrap_err();
+            assert_eq!(
+                format!("{err:#}"),
+                format!("ComponentInterface consistency error: {message}")
+            );
+        };
+        check_err(
+            r#"
+            namespace test{};
+            [Cursor]
+            interface Rows {
+                sequence<string> next(u32 count);
+            };
+            "#,
+            "\"Rows\" is a cursor, so it needs a `next_batch` method",
+        );
+        check_err(
+            r#"
+            namespace test{};
+            [Cursor]
+            interface Rows {
+                string next_batch(u32 count);
+            };
+            "#,
+            "\"Rows.next_batch\" must take the number of items to fetch as a `u32` and return a sequence of them",
+        );
+        check_err(
+            r#"
+            namespace test{};
+            [Cursor]
+            interface Rows {
+                sequence<string> next_batch(u64 count);
+            };
+            "#,
+            "\"Rows.next_batch\" must take the number of items to fetch as a `u32` and return a sequence of them",
+        );
+        check_err(
+            r#"
+            namespace test{};
+            [Cursor]
+            interface Rows {
+                [Async]
+                sequence<string> next_batch(u32 count);
+            };
+            "#,
+            "\"Rows.next_batch\" can't be async",
         );
     }
 }
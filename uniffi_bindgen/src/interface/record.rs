@@ -44,9 +44,10 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use uniffi_meta::Checksum;
 
+use super::ffi::{FfiFunction, FfiType};
 use super::Literal;
 use super::{AsType, Type, TypeIterator};
 
@@ -60,8 +61,17 @@ pub struct Record {
     pub(super) name: String,
     pub(super) module_path: String,
     pub(super) fields: Vec<Field>,
+    pub(super) packed: bool,
+    // FFI function to get the record's `Default::default()` value, from `[HasDefault]`.  Like the
+    // other FFI functions, this isn't included in the checksum.
+    #[checksum_ignore]
+    pub(super) ffi_func_default: Option<FfiFunction>,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    #[checksum_ignore]
+    pub(super) since: Option<String>,
+    #[checksum_ignore]
+    pub(super) deprecated: Option<String>,
 }
 
 impl Record {
@@ -69,14 +79,80 @@ impl Record {
         &self.name
     }
 
+    pub fn module_path(&self) -> &str {
+        &self.module_path
+    }
+
     pub fn fields(&self) -> &[Field] {
         &self.fields
     }
 
+    /// The fields in the order they're serialized, which is the declaration order unless the
+    /// fields set `[Order=N]`.
+    pub fn wire_fields(&self) -> Vec<&Field> {
+        let mut fields: Vec<_> = self.fields.iter().collect();
+        fields.sort_by_key(|field| field.order);
+        fields
+    }
+
+    /// Whether the fields are serialized in a different order to the one they're declared in.
+    pub fn has_wire_order(&self) -> bool {
+        self.fields.iter().any(|field| field.order.is_some())
+    }
+
+    /// Whether adjacent `bool` fields are packed into bytes when serialized, from `[Packed]`.
+    pub fn is_packed(&self) -> bool {
+        self.packed
+    }
+
+    /// The fields in the order they're serialized, with runs of adjacent `bool` fields grouped
+    /// into the bytes they're packed into when the record is `[Packed]`.
+    pub fn wire_items(&self) -> Vec<WireItem<'_>> {
+        let mut items = Vec::new();
+        for field in self.wire_fields() {
+            if !self.packed || field.type_ != Type::Boolean {
+                items.push(WireItem::Field(field));
+                continue;
+            }
+            match items.last_mut() {
+                Some(WireItem::PackedBools(bools)) if bools.len() < 8 => {
+                    let mask = 1 << bools.len();
+                    bools.push((field, mask));
+                }
+                _ => items.push(WireItem::PackedBools(vec![(field, 1)])),
+            }
+        }
+        items
+    }
+
+    /// Whether the bindings generate a constructor returning the record's Rust
+    /// `Default::default()` value, from `[HasDefault]`.
+    pub fn has_default(&self) -> bool {
+        self.ffi_func_default.is_some()
+    }
+
+    /// The FFI function which returns the record's `Default::default()` value, serialized into a
+    /// `RustBuffer`.
+    pub fn ffi_func_default(&self) -> &FfiFunction {
+        self.ffi_func_default
+            .as_ref()
+            .unwrap_or_else(|| panic!("{} doesn't have a default", &self.name))
+    }
+
     pub fn docstring(&self) -> Option<&str> {
         self.docstring.as_deref()
     }
 
+    /// The version the record was added in, from a `@since` docstring tag.
+    pub fn since(&self) -> Option<&str> {
+        self.since.as_deref()
+    }
+
+    /// The deprecation message, if the record is deprecated.
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(self.fields.iter().flat_map(Field::iter_types))
     }
@@ -86,6 +162,14 @@ impl Record {
     }
 }
 
+/// An item serialized by a record: either a single field, or up to 8 `bool` fields packed into a
+/// byte, along with the mask for each one's bit.
+#[derive(Debug, Clone)]
+pub enum WireItem<'a> {
+    Field(&'a Field),
+    PackedBools(Vec<(&'a Field, u8)>),
+}
+
 impl AsType for Record {
     fn as_type(&self) -> Type {
         Type::Record {
@@ -99,6 +183,32 @@ impl TryFrom<uniffi_meta::RecordMetadata> for Record {
     type Error = anyhow::Error;
 
     fn try_from(meta: uniffi_meta::RecordMetadata) -> Result<Self> {
+        if meta.fields.iter().any(|field| field.order.is_some()) {
+            let mut orders = meta
+                .fields
+                .iter()
+                .map(|field| field.order.map(usize::from))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Record `{}` must set `[Order=N]` on all of its fields or none of them",
+                        meta.name
+                    )
+                })?;
+            orders.sort_unstable();
+            if orders.into_iter().ne(0..meta.fields.len()) {
+                bail!(
+                    "The `[Order=N]` values of record `{}` must be 0 to {}, each used once",
+                    meta.name,
+                    meta.fields.len() - 1
+                );
+            }
+        }
+        let ffi_func_default = meta.has_default.then(|| FfiFunction {
+            name: meta.default_ffi_symbol_name(),
+            return_type: Some(FfiType::RustBuffer(None)),
+            ..Default::default()
+        });
         Ok(Self {
             name: meta.name,
             module_path: meta.module_path,
@@ -107,7 +217,11 @@ impl TryFrom<uniffi_meta::RecordMetadata> for Record {
                 .into_iter()
                 .map(TryInto::try_into)
                 .collect::<Result<_>>()?,
+            packed: meta.packed,
+            ffi_func_default,
             docstring: meta.docstring.clone(),
+            since: meta.since,
+            deprecated: meta.deprecated,
         })
     }
 }
@@ -120,6 +234,7 @@ pub struct Field {
     pub(super) default: Option<Literal>,
     #[checksum_ignore]
     pub(super) docstring: Option<String>,
+    pub(super) order: Option<u8>,
 }
 
 impl Field {
@@ -135,6 +250,11 @@ impl Field {
         self.docstring.as_deref()
     }
 
+    /// The position of the field when serialized, if it's been set with `[Order=N]`.
+    pub fn order(&self) -> Option<u8> {
+        self.order
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         self.type_.iter_types()
     }
@@ -158,6 +278,7 @@ impl TryFrom<uniffi_meta::FieldMetadata> for Field {
             type_,
             default,
             docstring: meta.docstring.clone(),
+            order: meta.order,
         })
     }
 }
@@ -246,6 +367,118 @@ mod test {
             .any(|t| matches!(t, Type::Record { name, .. } if name == "Testing")));
     }
 
+    #[test]
+    fn test_field_order() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Reordered {
+                [Order=1] string first;
+                [Order=2] u32 second;
+                [Order=0] boolean third;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let record = ci.get_record_definition("Reordered").unwrap();
+        assert!(record.has_wire_order());
+        assert_eq!(
+            record.fields().iter().map(Field::name).collect::<Vec<_>>(),
+            vec!["first", "second", "third"]
+        );
+        assert_eq!(
+            record
+                .wire_fields()
+                .into_iter()
+                .map(Field::name)
+                .collect::<Vec<_>>(),
+            vec!["third", "first", "second"]
+        );
+
+        const MISSING_UDL: &str = r#"
+            namespace test{};
+            dictionary Reordered {
+                [Order=1] string first;
+                u32 second;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(MISSING_UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Record `Reordered` must set `[Order=N]` on all of its fields or none of them"
+        );
+
+        const DUPLICATE_UDL: &str = r#"
+            namespace test{};
+            dictionary Reordered {
+                [Order=1] string first;
+                [Order=1] u32 second;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(DUPLICATE_UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The `[Order=N]` values of record `Reordered` must be 0 to 1, each used once"
+        );
+    }
+
+    #[test]
+    fn test_packed_wire_items() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Packed]
+            dictionary Flags {
+                boolean a;
+                boolean b;
+                u32 count;
+                boolean c;
+                boolean d;
+                boolean e;
+                boolean f;
+                boolean g;
+                boolean h;
+                boolean i;
+                boolean j;
+                boolean k;
+                boolean? l;
+            };
+            dictionary Unpacked {
+                boolean a;
+                boolean b;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let describe = |record: &Record| -> Vec<String> {
+            record
+                .wire_items()
+                .into_iter()
+                .map(|item| match item {
+                    WireItem::Field(field) => field.name().to_string(),
+                    WireItem::PackedBools(bools) => bools
+                        .into_iter()
+                        .map(|(field, mask)| format!("{}:{mask}", field.name()))
+                        .collect::<Vec<_>>()
+                        .join(","),
+                })
+                .collect()
+        };
+
+        let record = ci.get_record_definition("Flags").unwrap();
+        assert!(record.is_packed());
+        assert_eq!(
+            describe(record),
+            vec![
+                "a:1,b:2",
+                "count",
+                "c:1,d:2,e:4,f:8,g:16,h:32,i:64,j:128",
+                "k:1",
+                "l",
+            ]
+        );
+
+        let record = ci.get_record_definition("Unpacked").unwrap();
+        assert!(!record.is_packed());
+        assert_eq!(describe(record), vec!["a", "b"]);
+    }
+
     #[test]
     fn test_docstring_record() {
         const UDL: &str = r#"
@@ -21,7 +21,7 @@ pub use uniffi_meta::{AsType, ExternalKind, NamespaceMetadata, ObjectImpl, Type,
 /// You could imagine this struct doing some clever interning of names and so-on in future,
 /// to reduce the overhead of passing around [Type] instances. For now we just do a whole
 /// lot of cloning.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct TypeUniverse {
     /// The unique prefixes that we'll use for namespacing when exposing this component's API.
     pub namespace: NamespaceMetadata,
@@ -84,6 +84,25 @@ impl TypeUniverse {
             Type::Bytes => self.add_type_definition("bytes", type_)?,
             Type::Timestamp => self.add_type_definition("timestamp", type_)?,
             Type::Duration => self.add_type_definition("duration", type_)?,
+            Type::DurationRange { inclusive } => {
+                let name = match inclusive {
+                    false => "duration_range",
+                    true => "duration_range_inclusive",
+                };
+                self.add_type_definition(name, type_)?;
+                // The bindings read and write the bounds as durations.
+                self.add_known_type(&Type::Duration)?;
+            }
+            Type::Path => self.add_type_definition("path", type_)?,
+            Type::Json => self.add_type_definition("json", type_)?,
+            Type::OpaqueHandle => self.add_type_definition("opaque_handle", type_)?,
+            Type::DynError => {
+                self.add_type_definition("DynError", type_)?;
+                // The error message and its cause chain are serialized as strings.
+                self.add_known_type(&Type::Sequence {
+                    inner_type: Box::new(Type::String),
+                })?;
+            }
             Type::Object { name, .. }
             | Type::Record { name, .. }
             | Type::Enum { name, .. }
@@ -94,7 +113,9 @@ impl TypeUniverse {
                 self.add_known_type(builtin)?;
             }
             // Structurally recursive types.
-            Type::Optional { inner_type, .. } | Type::Sequence { inner_type, .. } => {
+            Type::Optional { inner_type, .. }
+            | Type::Sequence { inner_type, .. }
+            | Type::NonZero { inner_type } => {
                 self.add_known_type(inner_type)?;
             }
             Type::Map {
@@ -116,7 +137,6 @@ impl TypeUniverse {
         Ok(())
     }
 
-    #[cfg(test)]
     /// Check if a [Type] is present
     pub fn contains(&self, type_: &Type) -> bool {
         self.all_known_types.contains(type_)
@@ -126,6 +146,13 @@ impl TypeUniverse {
     pub fn iter_known_types(&self) -> impl Iterator<Item = &Type> {
         self.all_known_types.iter()
     }
+
+    /// Remove the known types for which `f` returns `false`.
+    ///
+    /// The type definitions are kept, so names still resolve to the removed types.
+    pub fn retain_known_types(&mut self, f: impl FnMut(&Type) -> bool) {
+        self.all_known_types.retain(f)
+    }
 }
 
 #[cfg(test)]
@@ -102,12 +102,18 @@ use std::{collections::HashMap, process::Command};
 
 pub mod backend;
 pub mod bindings;
+pub mod cache;
+pub mod coverage_report;
 pub mod interface;
 pub mod library_mode;
+pub mod lint;
 pub mod macro_metadata;
+pub mod metadata_artifact;
 pub mod scaffolding;
+pub mod size_report;
 
 use bindings::TargetLanguage;
+use cache::ComponentInterfaceCache;
 pub use interface::ComponentInterface;
 use scaffolding::RustScaffolding;
 
@@ -169,6 +175,7 @@ pub trait BindingGenerator: Sized {
 struct BindingGeneratorDefault {
     target_languages: Vec<TargetLanguage>,
     try_format_code: bool,
+    formatters: bindings::Formatters,
 }
 
 impl BindingGenerator for BindingGeneratorDefault {
@@ -181,12 +188,13 @@ impl BindingGenerator for BindingGeneratorDefault {
         out_dir: &Utf8Path,
     ) -> Result<()> {
         for &language in &self.target_languages {
-            bindings::write_bindings(
+            bindings::write_bindings_with_formatter(
                 &config.bindings,
                 ci,
                 out_dir,
                 language,
                 self.try_format_code,
+                self.formatters.get(&language).map(|f| f.as_ref()),
             )?;
         }
         Ok(())
@@ -226,34 +234,76 @@ pub fn generate_external_bindings<T: BindingGenerator>(
     library_file: Option<impl AsRef<Utf8Path>>,
     crate_name: Option<&str>,
 ) -> Result<()> {
+    generate_external_bindings_with_cache(
+        binding_generator,
+        udl_file,
+        config_file_override,
+        out_dir_override,
+        library_file,
+        crate_name,
+        &cache::NoCache,
+    )
+}
+
+/// Like [`generate_external_bindings`], but parsed UDL files are cached in `cache`, for example a
+/// [`cache::MemoryCache`] kept across calls.  The other functions don't cache anything.
+pub fn generate_external_bindings_with_cache<T: BindingGenerator>(
+    binding_generator: T,
+    udl_file: impl AsRef<Utf8Path>,
+    config_file_override: Option<impl AsRef<Utf8Path>>,
+    out_dir_override: Option<impl AsRef<Utf8Path>>,
+    library_file: Option<impl AsRef<Utf8Path>>,
+    crate_name: Option<&str>,
+    cache: &dyn ComponentInterfaceCache,
+) -> Result<()> {
+    let (component, config) = load_component_and_config::<T::Config>(
+        udl_file.as_ref(),
+        config_file_override.as_ref().map(|p| p.as_ref()),
+        library_file.as_ref().map(|p| p.as_ref()),
+        crate_name,
+        cache,
+    )?;
+    for warning in component.warnings() {
+        eprintln!("Warning: {warning}");
+    }
+
+    let out_dir = get_out_dir(
+        udl_file.as_ref(),
+        out_dir_override.as_ref().map(|p| p.as_ref()),
+    )?;
+    binding_generator.write_bindings(&component, &config, &out_dir)
+}
+
+/// Parse the `ComponentInterface` from a UDL file and load its bindings config.
+fn load_component_and_config<C: BindingsConfig>(
+    udl_file: &Utf8Path,
+    config_file_override: Option<&Utf8Path>,
+    library_file: Option<&Utf8Path>,
+    crate_name: Option<&str>,
+    cache: &dyn ComponentInterfaceCache,
+) -> Result<(ComponentInterface, C)> {
     let crate_name = crate_name
         .map(|c| Ok(c.to_string()))
-        .unwrap_or_else(|| crate_name_from_cargo_toml(udl_file.as_ref()))?;
-    let mut component = parse_udl(udl_file.as_ref(), &crate_name)?;
-    if let Some(ref library_file) = library_file {
-        macro_metadata::add_to_ci_from_library(&mut component, library_file.as_ref())?;
+        .unwrap_or_else(|| crate_name_from_cargo_toml(udl_file))?;
+    let mut component = parse_udl(udl_file, &crate_name, cache)?;
+    if let Some(library_file) = library_file {
+        macro_metadata::add_to_ci_from_library(&mut component, library_file)?;
     }
-    let crate_root = &guess_crate_root(udl_file.as_ref()).context("Failed to guess crate root")?;
+    let crate_root = &guess_crate_root(udl_file).context("Failed to guess crate root")?;
 
-    let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
+    load_common_bindings_config(crate_root, config_file_override)?.apply(&mut component)?;
 
     let config = {
-        let mut config = load_initial_config::<T::Config>(crate_root, config_file_override)?;
+        let mut config = load_initial_config::<C>(crate_root, config_file_override)?;
         config.update_from_ci(&component);
-        if let Some(ref library_file) = library_file {
-            if let Some(cdylib_name) = crate::library_mode::calc_cdylib_name(library_file.as_ref())
-            {
+        if let Some(library_file) = library_file {
+            if let Some(cdylib_name) = crate::library_mode::calc_cdylib_name(library_file) {
                 config.update_from_cdylib_name(cdylib_name)
             }
         };
         config
     };
-
-    let out_dir = get_out_dir(
-        udl_file.as_ref(),
-        out_dir_override.as_ref().map(|p| p.as_ref()),
-    )?;
-    binding_generator.write_bindings(&component, &config, &out_dir)
+    Ok((component, config))
 }
 
 // Generate the infrastructural Rust code for implementing the UDL interface,
@@ -264,7 +314,11 @@ pub fn generate_component_scaffolding(
     out_dir_override: Option<&Utf8Path>,
     format_code: bool,
 ) -> Result<()> {
-    let component = parse_udl(udl_file, &crate_name_from_cargo_toml(udl_file)?)?;
+    let component = parse_udl(
+        udl_file,
+        &crate_name_from_cargo_toml(udl_file)?,
+        &cache::NoCache,
+    )?;
     generate_component_scaffolding_inner(component, udl_file, out_dir_override, format_code)
 }
 
@@ -277,7 +331,7 @@ pub fn generate_component_scaffolding_for_crate(
     out_dir_override: Option<&Utf8Path>,
     format_code: bool,
 ) -> Result<()> {
-    let component = parse_udl(udl_file, crate_name)?;
+    let component = parse_udl(udl_file, crate_name, &cache::NoCache)?;
     generate_component_scaffolding_inner(component, udl_file, out_dir_override, format_code)
 }
 
@@ -300,7 +354,8 @@ fn generate_component_scaffolding_inner(
 }
 
 // Generate the bindings in the target languages that call the scaffolding
-// Rust code.
+// Rust code.  The UDL is only parsed once, and the same `ComponentInterface`
+// is used for each language.
 pub fn generate_bindings(
     udl_file: &Utf8Path,
     config_file_override: Option<&Utf8Path>,
@@ -314,6 +369,7 @@ pub fn generate_bindings(
         BindingGeneratorDefault {
             target_languages,
             try_format_code,
+            formatters: Default::default(),
         },
         udl_file,
         config_file_override,
@@ -323,6 +379,51 @@ pub fn generate_bindings(
     )
 }
 
+/// Generate the bindings in the target languages without writing anything to disk.
+///
+/// This is for tools which embed the binding generator and handle the output themselves.  It
+/// returns the contents of each file which [`generate_bindings`] would write, keyed by its path
+/// relative to the output directory.  The generated code isn't formatted.
+pub fn generate_to_memory(
+    udl_file: &Utf8Path,
+    config_file_override: Option<&Utf8Path>,
+    target_languages: &[TargetLanguage],
+    library_file: Option<&Utf8Path>,
+    crate_name: Option<&str>,
+) -> Result<HashMap<Utf8PathBuf, String>> {
+    let (component, config) = load_component_and_config::<Config>(
+        udl_file,
+        config_file_override,
+        library_file,
+        crate_name,
+        &cache::NoCache,
+    )?;
+    let mut files = HashMap::new();
+    for &language in target_languages {
+        files.extend(bindings::render_bindings(
+            &config.bindings,
+            &component,
+            language,
+        )?);
+    }
+    Ok(files)
+}
+
+/// Generate the bindings in the target languages for every component in a compiled library.
+///
+/// The `ComponentInterface` for each component is built from the metadata embedded in the library,
+/// so no UDL file needs to be passed.  This uses [library_mode] with the default settings: each
+/// crate's `uniffi.toml` is used for its config and the generated code isn't formatted.
+///
+/// Returns the list of sources used to generate the bindings, in no particular order.
+pub fn generate_bindings_from_library(
+    library_path: &Utf8Path,
+    target_languages: &[TargetLanguage],
+    out_dir: &Utf8Path,
+) -> Result<Vec<library_mode::Source<Config>>> {
+    library_mode::generate_bindings(library_path, None, target_languages, None, out_dir, false)
+}
+
 pub fn print_repr(library_path: &Utf8Path) -> Result<()> {
     let metadata = macro_metadata::extract_from_library(library_path)?;
     println!("{metadata:#?}");
@@ -396,11 +497,14 @@ fn get_out_dir(udl_file: &Utf8Path, out_dir_override: Option<&Utf8Path>) -> Resu
     })
 }
 
-fn parse_udl(udl_file: &Utf8Path, crate_name: &str) -> Result<ComponentInterface> {
+fn parse_udl(
+    udl_file: &Utf8Path,
+    crate_name: &str,
+    cache: &dyn ComponentInterfaceCache,
+) -> Result<ComponentInterface> {
     let udl = fs::read_to_string(udl_file)
         .with_context(|| format!("Failed to read UDL from {udl_file}"))?;
-    let group = uniffi_udl::parse_udl(&udl, crate_name)?;
-    ComponentInterface::from_metadata(group)
+    cache::parse_udl(&udl, crate_name, cache)
 }
 
 fn format_code_with_rustfmt(path: &Utf8Path) -> Result<()> {
@@ -438,10 +542,54 @@ fn load_initial_config<Config: DeserializeOwned>(
     crate_root: &Utf8Path,
     config_file_override: Option<&Utf8Path>,
 ) -> Result<Config> {
-    let mut config = load_toml_file(Some(crate_root.join("uniffi.toml").as_path()))
+    let config = load_toml_file(Some(crate_root.join("uniffi.toml").as_path()))
         .context("default config")?
         .unwrap_or(toml::value::Table::default());
+    merge_config_override(config, config_file_override)
+}
+
+/// The settings from the `[bindings]` section of the config which apply to every binding
+/// generator.
+///
+/// These are loaded separately from the bindings config, since they're applied to the
+/// `ComponentInterface` itself.  `component_version` is also read by the scaffolding.
+#[derive(Default, Deserialize)]
+struct CommonBindingsConfig {
+    component_version: Option<String>,
+    #[serde(default)]
+    static_functions_as_constants: bool,
+}
+
+impl CommonBindingsConfig {
+    fn apply(self, ci: &mut ComponentInterface) -> Result<()> {
+        ci.set_component_version(self.component_version)?;
+        if self.static_functions_as_constants {
+            ci.expose_static_functions_as_constants();
+        }
+        Ok(())
+    }
+}
 
+/// Load the [`CommonBindingsConfig`] from the `[bindings]` section of the config.
+fn load_common_bindings_config(
+    crate_root: &Utf8Path,
+    config_file_override: Option<&Utf8Path>,
+) -> Result<CommonBindingsConfig> {
+    #[derive(Deserialize)]
+    struct CommonConfig {
+        #[serde(default)]
+        bindings: CommonBindingsConfig,
+    }
+
+    let config: CommonConfig = load_initial_config(crate_root, config_file_override)?;
+    Ok(config.bindings)
+}
+
+/// Merge a TOML config tree with `config_file_override` if specified.
+fn merge_config_override<Config: DeserializeOwned>(
+    mut config: toml::value::Table,
+    config_file_override: Option<&Utf8Path>,
+) -> Result<Config> {
     let override_config = load_toml_file(config_file_override).context("override config")?;
     if let Some(override_config) = override_config {
         merge_toml(&mut config, override_config);
@@ -529,6 +677,7 @@ mod __unused {
 #[cfg(test)]
 mod test {
     use super::*;
+    use std::sync::atomic::Ordering;
 
     #[test]
     fn test_guessing_of_crate_root_directory_from_udl_file() {
@@ -548,6 +697,126 @@ mod test {
         assert!(guess_crate_root(&not_a_crate_root.join("src/example.udl")).is_err());
     }
 
+    #[test]
+    fn test_generate_to_memory() {
+        let example_crate_root = Utf8PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .parent()
+            .expect("should have a parent directory")
+            .join("examples/arithmetic");
+        let files = generate_to_memory(
+            &example_crate_root.join("src/arithmetic.udl"),
+            None,
+            &[TargetLanguage::Python, TargetLanguage::Kotlin],
+            None,
+            Some("arithmetic"),
+        )
+        .unwrap();
+
+        let mut paths = files.keys().map(|path| path.as_str()).collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "arithmetic.py",
+                "org/mozilla/uniffi/example/arithmetic/arithmetic.kt"
+            ]
+        );
+        assert!(files.values().all(|contents| !contents.is_empty()));
+    }
+
+    #[test]
+    fn test_generate_udl_with_external_types() {
+        // The UDL uses external records, enums and custom types, and bindings can still be
+        // generated from it without library mode.
+        let fixture_root = Utf8PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .parent()
+            .expect("should have a parent directory")
+            .join("fixtures/ext-types/lib");
+        let udl_file = fixture_root.join("src/ext-types-lib.udl");
+        let (component, _) = load_component_and_config::<Config>(
+            &udl_file,
+            None,
+            None,
+            Some("uniffi_ext_types_lib"),
+            &cache::NoCache,
+        )
+        .unwrap();
+        let arg_type = |name: &str| {
+            component
+                .get_function_definition(name)
+                .unwrap()
+                .ffi_func()
+                .arguments()[0]
+                .type_()
+        };
+        assert_eq!(
+            arg_type("get_uniffi_one_type"),
+            interface::FfiType::RustBuffer(Some("UniffiOneType".to_string()))
+        );
+        assert_eq!(arg_type("get_uniffi_one_enum"), interface::FfiType::Int32);
+
+        let files = generate_to_memory(
+            &udl_file,
+            None,
+            &[TargetLanguage::Python, TargetLanguage::Kotlin],
+            None,
+            Some("uniffi_ext_types_lib"),
+        )
+        .unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files.values().all(|contents| !contents.is_empty()));
+    }
+
+    #[test]
+    fn test_generate_bindings_parses_once() {
+        let example_crate_root = Utf8PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap())
+            .parent()
+            .expect("should have a parent directory")
+            .join("examples/arithmetic");
+        let out_dir = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("uniffi-test-parses-once-{}", std::process::id()));
+        let cache = cache::CountingCache::default();
+        let parses_before = interface::UDL_PARSES.with(|parses| parses.get());
+        generate_external_bindings_with_cache(
+            BindingGeneratorDefault {
+                target_languages: vec![
+                    TargetLanguage::Kotlin,
+                    TargetLanguage::Swift,
+                    TargetLanguage::Python,
+                    TargetLanguage::Ruby,
+                ],
+                try_format_code: false,
+                formatters: Default::default(),
+            },
+            example_crate_root.join("src/arithmetic.udl"),
+            None::<&Utf8Path>,
+            Some(&out_dir),
+            None::<&Utf8Path>,
+            Some("arithmetic"),
+            &cache,
+        )
+        .unwrap();
+        // The UDL is parsed once and shared by every language, rather than looked up again for
+        // each of them.
+        let parses = interface::UDL_PARSES.with(|parses| parses.get()) - parses_before;
+        assert_eq!(parses, 1);
+        assert_eq!(cache.hits.load(Ordering::Relaxed), 0);
+        assert_eq!(cache.inner.len(), 1);
+
+        for path in [
+            "org/mozilla/uniffi/example/arithmetic/arithmetic.kt",
+            "arithmetic.swift",
+            "arithmeticFFI.h",
+            "arithmeticFFI.modulemap",
+            "arithmetic.py",
+            "arithmetic.rb",
+        ] {
+            assert!(out_dir.join(path).is_file(), "{path} wasn't generated");
+        }
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
     #[test]
     fn test_merge_toml() {
         let default = r#"
@@ -16,7 +16,8 @@
 ///   - UniFFI can figure out the package/module names for each crate, eliminating the external
 ///     package maps.
 use crate::{
-    bindings::TargetLanguage, load_initial_config, macro_metadata, BindingGenerator,
+    bindings::{Formatters, TargetLanguage},
+    load_common_bindings_config, load_initial_config, macro_metadata, BindingGenerator,
     BindingGeneratorDefault, BindingsConfig, ComponentInterface, Result,
 };
 use anyhow::{bail, Context};
@@ -40,11 +41,34 @@ pub fn generate_bindings(
     config_file_override: Option<&Utf8Path>,
     out_dir: &Utf8Path,
     try_format_code: bool,
+) -> Result<Vec<Source<crate::Config>>> {
+    generate_bindings_with_formatters(
+        library_path,
+        crate_name,
+        target_languages,
+        config_file_override,
+        out_dir,
+        try_format_code,
+        Formatters::new(),
+    )
+}
+
+/// Like [`generate_bindings`], but the generated source for each language in `formatters` is
+/// passed through its [`Formatter`](crate::bindings::Formatter) before it's written.
+pub fn generate_bindings_with_formatters(
+    library_path: &Utf8Path,
+    crate_name: Option<String>,
+    target_languages: &[TargetLanguage],
+    config_file_override: Option<&Utf8Path>,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+    formatters: Formatters,
 ) -> Result<Vec<Source<crate::Config>>> {
     generate_external_bindings(
         BindingGeneratorDefault {
             target_languages: target_languages.into(),
             try_format_code,
+            formatters,
         },
         library_path,
         crate_name,
@@ -109,6 +133,9 @@ pub fn generate_external_bindings<T: BindingGenerator>(
     }
 
     for source in sources.iter() {
+        for warning in source.ci.warnings() {
+            eprintln!("Warning: {warning}");
+        }
         binding_generator.write_bindings(&source.ci, &source.config, out_dir)?;
     }
 
@@ -166,14 +193,15 @@ fn find_sources<Config: BindingsConfig>(
                 .map(|item| fixup_external_type(item, &metadata_groups))
                 // some items are both in UDL and library metadata. For many that's fine but
                 // uniffi-traits aren't trivial to compare meaning we end up with dupes.
-                // We filter out such problematic items here.
-                .filter(|item| !matches!(item, Metadata::UniffiTrait { .. }))
+                // We filter out such problematic items here.  Stringifiers are the exception,
+                // since they only exist in the UDL.
+                .filter(|item| !matches!(item, Metadata::UniffiTrait(ut) if !ut.is_stringifier()))
                 .collect();
             udl_items.insert(crate_name, metadata_group);
         };
     }
 
-    metadata_groups
+    let mut sources = metadata_groups
         .into_values()
         .map(|group| {
             let package = find_package_by_crate_name(cargo_metadata, &group.namespace.crate_name)?;
@@ -187,6 +215,7 @@ fn find_sources<Config: BindingsConfig>(
                 ci.add_metadata(metadata)?;
             };
             ci.add_metadata(group)?;
+            load_common_bindings_config(crate_root, config_file_override)?.apply(&mut ci)?;
             let mut config = load_initial_config::<Config>(crate_root, config_file_override)?;
             if let Some(cdylib_name) = cdylib_name {
                 config.update_from_cdylib_name(cdylib_name);
@@ -199,7 +228,22 @@ fn find_sources<Config: BindingsConfig>(
                 package,
             })
         })
-        .collect()
+        .collect::<Result<Vec<Source<Config>>>>()?;
+
+    // Fieldless enums are lowered into a scalar, which crates using them as external types can
+    // only find out from the crate that defines them.
+    let scalar_enums: HashSet<(String, String)> = sources
+        .iter()
+        .flat_map(|s| {
+            s.ci.enum_definitions()
+                .filter(|e| e.is_scalar())
+                .map(|e| (s.crate_name.clone(), e.name().to_string()))
+        })
+        .collect();
+    for source in sources.iter_mut() {
+        source.ci.note_external_scalar_enums(&scalar_enums)?;
+    }
+    Ok(sources)
 }
 
 fn find_package_by_crate_name(
@@ -0,0 +1,371 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Check a UDL file for likely mistakes and style problems.
+//!
+//! The UDL is parsed and validated in the same way as when generating bindings, and a UDL which
+//! fails that is reported as a single [`Lint::Invalid`] finding.  Otherwise, the interface is
+//! checked for things which are accepted but probably wrong, such as types which are never used,
+//! and for deviations from the Rust naming conventions which the scaffolding code relies on.
+
+use std::fmt;
+use std::fs;
+
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use uniffi_meta::Type;
+
+use crate::interface::{Argument, ComponentInterface, Field};
+
+/// Callables with more arguments than this, not counting those with default values, are
+/// reported by [`Lint::TooManyArguments`].
+pub const MAX_POSITIONAL_ARGUMENTS: usize = 5;
+
+/// How serious a finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The UDL works, but doesn't follow the usual conventions.
+    Style,
+    /// The UDL works, but probably doesn't do what was intended.
+    Warning,
+    /// The UDL is invalid, or will fail once the scaffolding is compiled.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Style => "style",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// The check which produced a finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Lint {
+    /// The UDL can't be parsed, or fails validation.
+    Invalid,
+    /// A type can't be reached from any function, constant or interface.
+    UnusedType,
+    /// A `[NonExhaustive]` error has no `[Default]` variant to lift unknown variants as.
+    NonExhaustiveWithoutDefault,
+    /// A callable has more than [`MAX_POSITIONAL_ARGUMENTS`] arguments without default values.
+    TooManyArguments,
+    /// A name doesn't follow the Rust naming conventions.
+    Naming,
+}
+
+impl Lint {
+    pub fn severity(self) -> Severity {
+        match self {
+            Lint::Invalid => Severity::Error,
+            Lint::UnusedType | Lint::NonExhaustiveWithoutDefault => Severity::Warning,
+            Lint::TooManyArguments | Lint::Naming => Severity::Style,
+        }
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Lint::Invalid => "invalid",
+            Lint::UnusedType => "unused-type",
+            Lint::NonExhaustiveWithoutDefault => "non-exhaustive-without-default",
+            Lint::TooManyArguments => "too-many-arguments",
+            Lint::Naming => "naming",
+        })
+    }
+}
+
+/// A problem found in the UDL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub lint: Lint,
+    pub severity: Severity,
+    /// The item the finding is about, like `Client.connect`, or empty for the whole UDL.
+    pub item: String,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]", self.severity, self.lint)?;
+        if !self.item.is_empty() {
+            write!(f, " {}", self.item)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Parse and lint a UDL string, returning the findings with the most severe first.
+pub fn lint_udl(udl: &str, crate_name: &str) -> Vec<Finding> {
+    match ComponentInterface::from_webidl(udl, crate_name) {
+        Ok(ci) => lint(&ci),
+        Err(e) => vec![finding(Lint::Invalid, "", format!("{e:#}"))],
+    }
+}
+
+/// Lint a parsed interface, returning the findings with the most severe first.
+pub fn lint(ci: &ComponentInterface) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    check_enums(ci, &mut findings);
+    check_unused_types(ci, &mut findings);
+    check_callables(ci, &mut findings);
+    check_type_names(ci, &mut findings);
+    // Sorting is stable, so findings of the same severity stay in the order they were found.
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity));
+    findings
+}
+
+/// Lint a UDL file and print the findings.  Fails if any of them are errors.
+///
+/// `crate_name` works the same way as for [`crate::generate_bindings`].
+pub fn print_lint_report(udl_file: &Utf8Path, crate_name: Option<&str>) -> Result<()> {
+    let crate_name = crate_name
+        .map(|c| Ok(c.to_string()))
+        .unwrap_or_else(|| crate::crate_name_from_cargo_toml(udl_file))?;
+    let udl = fs::read_to_string(udl_file)
+        .with_context(|| format!("Failed to read UDL from {udl_file}"))?;
+    let findings = lint_udl(&udl, &crate_name);
+    for finding in &findings {
+        println!("{finding}");
+    }
+    let errors = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Error)
+        .count();
+    if errors > 0 {
+        anyhow::bail!("{udl_file} has {errors} lint error(s)");
+    }
+    Ok(())
+}
+
+fn finding(lint: Lint, item: impl Into<String>, message: impl Into<String>) -> Finding {
+    Finding {
+        lint,
+        severity: lint.severity(),
+        item: item.into(),
+        message: message.into(),
+    }
+}
+
+fn check_enums(ci: &ComponentInterface, findings: &mut Vec<Finding>) {
+    for e in ci.enum_definitions() {
+        for v in e.variants() {
+            check_name(findings, Case::UpperCamel, e.name(), "variant", v.name());
+            check_fields(findings, &format!("{}.{}", e.name(), v.name()), v.fields());
+        }
+        // Other enums get an `Unknown` variant in the bindings, but errors don't.
+        if e.is_non_exhaustive()
+            && e.default_variant().is_none()
+            && ci.is_name_used_as_error(e.name())
+        {
+            findings.push(finding(
+                Lint::NonExhaustiveWithoutDefault,
+                e.name(),
+                "the error is non-exhaustive but has no `[Default]` variant, so older bindings \
+                 fail to lift variants which are added later",
+            ));
+        }
+    }
+}
+
+fn check_unused_types(ci: &ComponentInterface, findings: &mut Vec<Finding>) {
+    for t in ci.unused_types() {
+        if let Type::Record { name, .. }
+        | Type::Enum { name, .. }
+        | Type::CallbackInterface { name, .. }
+        | Type::Custom { name, .. } = t
+        {
+            findings.push(finding(
+                Lint::UnusedType,
+                name,
+                "the type is declared but can't be reached from any function, constant or \
+                 interface",
+            ));
+        }
+    }
+}
+
+fn check_callables(ci: &ComponentInterface, findings: &mut Vec<Finding>) {
+    for func in ci.function_definitions() {
+        check_name(findings, Case::Snake, "", "function", func.name());
+        check_arguments(findings, func.name(), func.arguments());
+    }
+    for constant in ci.constant_definitions() {
+        check_name(findings, Case::ShoutySnake, "", "constant", constant.name());
+    }
+    for obj in ci.object_definitions() {
+        for cons in obj.constructors() {
+            let item = format!("{}.{}", obj.name(), cons.name());
+            check_name(
+                findings,
+                Case::Snake,
+                obj.name(),
+                "constructor",
+                cons.name(),
+            );
+            check_arguments(findings, &item, cons.arguments());
+        }
+        for meth in obj.methods() {
+            let item = format!("{}.{}", obj.name(), meth.name());
+            check_name(findings, Case::Snake, obj.name(), "method", meth.name());
+            check_arguments(findings, &item, meth.arguments());
+        }
+    }
+    for cbi in ci.callback_interface_definitions() {
+        for meth in cbi.methods() {
+            let item = format!("{}.{}", cbi.name(), meth.name());
+            check_name(findings, Case::Snake, cbi.name(), "method", meth.name());
+            check_arguments(findings, &item, meth.arguments());
+        }
+    }
+    for rec in ci.record_definitions() {
+        check_fields(findings, rec.name(), rec.fields());
+    }
+}
+
+fn check_arguments(findings: &mut Vec<Finding>, item: &str, args: Vec<&Argument>) {
+    let positional = args.iter().filter(|a| a.default_value().is_none()).count();
+    if positional > MAX_POSITIONAL_ARGUMENTS {
+        findings.push(finding(
+            Lint::TooManyArguments,
+            item,
+            format!(
+                "{positional} arguments without default values, more than \
+                 {MAX_POSITIONAL_ARGUMENTS}; consider passing a record instead"
+            ),
+        ));
+    }
+    for arg in args {
+        check_name(findings, Case::Snake, item, "argument", arg.name());
+    }
+}
+
+fn check_fields(findings: &mut Vec<Finding>, item: &str, fields: &[Field]) {
+    for field in fields {
+        check_name(findings, Case::Snake, item, "field", field.name());
+    }
+}
+
+fn check_type_names(ci: &ComponentInterface, findings: &mut Vec<Finding>) {
+    for t in ci.iter_types() {
+        match t {
+            Type::Record { name, .. }
+            | Type::Enum { name, .. }
+            | Type::Object { name, .. }
+            | Type::CallbackInterface { name, .. }
+            | Type::Custom { name, .. } => check_name(findings, Case::UpperCamel, "", "type", name),
+            _ => (),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Case {
+    UpperCamel,
+    Snake,
+    ShoutySnake,
+}
+
+/// Check that a `kind` name, declared in `item`, follows the `case` convention.
+fn check_name(findings: &mut Vec<Finding>, case: Case, item: &str, kind: &str, name: &str) {
+    let (expected, convention) = match case {
+        Case::UpperCamel => (name.to_upper_camel_case(), "UpperCamelCase"),
+        Case::Snake => (name.to_snake_case(), "snake_case"),
+        Case::ShoutySnake => (name.to_shouty_snake_case(), "SCREAMING_SNAKE_CASE"),
+    };
+    if name != expected {
+        let item = if item.is_empty() {
+            name.to_string()
+        } else {
+            format!("{item}.{name}")
+        };
+        findings.push(finding(
+            Lint::Naming,
+            item,
+            format!("{kind} names should be {convention}, like `{expected}`"),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lint() {
+        const UDL: &str = r#"
+            namespace test {
+                Shape make_shape(u32 a, u32 b, u32 c, u32 d, u32 e, u32 f);
+                Shape make_small_shape(u32 a, u32 b, u32 c, u32 d, u32 e, optional u32 f = 0);
+                [Throws=Status]
+                void fetchAll();
+                Shade get_shade();
+            };
+            enum Shape { "Circle", "Square" };
+            [Error, NonExhaustive]
+            enum Status { "Ok", "failed" };
+            [NonExhaustive]
+            enum Shade { "Light", "Dark" };
+            dictionary Orphan { u32 x; };
+            interface Client {
+                constructor();
+                void Connect(string hostName);
+            };
+        "#;
+        let findings: Vec<_> = lint_udl(UDL, "crate_name")
+            .into_iter()
+            .map(|f| f.to_string())
+            .collect();
+        assert_eq!(
+            findings,
+            [
+                "warning[non-exhaustive-without-default] Status: the error is non-exhaustive but \
+                 has no `[Default]` variant, so older bindings fail to lift variants which are \
+                 added later",
+                "warning[unused-type] Orphan: the type is declared but can't be reached from any \
+                 function, constant or interface",
+                "style[naming] Status.failed: variant names should be UpperCamelCase, like `Failed`",
+                "style[naming] fetchAll: function names should be snake_case, like `fetch_all`",
+                "style[too-many-arguments] make_shape: 6 arguments without default values, more \
+                 than 5; consider passing a record instead",
+                "style[naming] Client.Connect: method names should be snake_case, like `connect`",
+                "style[naming] Client.Connect.hostName: argument names should be snake_case, \
+                 like `host_name`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_invalid() {
+        let findings = lint_udl("namespace test { Missing get(); };", "crate_name");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].lint, Lint::Invalid);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert!(findings[0].message.contains("Missing"), "{}", findings[0]);
+
+        // Errors about a specific part of the UDL say where it is.
+        let findings = lint_udl(
+            "namespace test {};\nenum Shape { \"Circle\", \"Circle\" };",
+            "crate_name",
+        );
+        assert_eq!(
+            findings[0].to_string(),
+            "error[invalid]: Duplicate variant `Circle` in enum `Shape` at 2:25"
+        );
+
+        // A clean UDL has no findings.
+        const UDL: &str = r#"
+            namespace test {
+                Point origin();
+            };
+            dictionary Point { i32 x; i32 y; };
+        "#;
+        assert_eq!(lint_udl(UDL, "crate_name"), []);
+    }
+}
@@ -63,6 +63,7 @@ fn add_enum_to_ci(
     iface: &mut ComponentInterface,
     meta: EnumMetadata,
     is_flat: bool,
+    is_scalar: bool,
 ) -> anyhow::Result<()> {
     let ty = Type::Enum {
         name: meta.name.clone(),
@@ -70,7 +71,7 @@ fn add_enum_to_ci(
     };
     iface.types.add_known_type(&ty)?;
 
-    let enum_ = Enum::try_from_meta(meta, is_flat)?;
+    let enum_ = Enum::try_from_meta(meta, is_flat, is_scalar)?;
     iface.add_enum_definition(enum_)?;
     Ok(())
 }
@@ -82,6 +83,9 @@ fn add_item_to_ci(iface: &mut ComponentInterface, item: Metadata) -> anyhow::Res
         Metadata::Func(meta) => {
             iface.add_function_definition(meta.into())?;
         }
+        Metadata::ConstFn(meta) => {
+            iface.add_constant_definition(meta.into())?;
+        }
         Metadata::Constructor(meta) => {
             iface.add_constructor_meta(meta)?;
         }
@@ -99,7 +103,8 @@ fn add_item_to_ci(iface: &mut ComponentInterface, item: Metadata) -> anyhow::Res
         }
         Metadata::Enum(meta) => {
             let flat = meta.variants.iter().all(|v| v.fields.is_empty());
-            add_enum_to_ci(iface, meta, flat)?;
+            // Fieldless enums are lowered into a scalar, unless they were declared as errors.
+            add_enum_to_ci(iface, meta, flat, flat)?;
         }
         Metadata::Object(meta) => {
             iface.types.add_known_type(&Type::Object {
@@ -126,7 +131,7 @@ fn add_item_to_ci(iface: &mut ComponentInterface, item: Metadata) -> anyhow::Res
             iface.note_name_used_as_error(meta.name());
             match meta {
                 ErrorMetadata::Enum { enum_, is_flat } => {
-                    add_enum_to_ci(iface, enum_, is_flat)?;
+                    add_enum_to_ci(iface, enum_, is_flat, false)?;
                 }
             };
         }
@@ -0,0 +1,306 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Serialized metadata artifacts.
+//!
+//! These let the bindings be generated in a different step, or on a different machine, from the
+//! one that parsed the UDL.  [`write_metadata_artifact`] parses the UDL file, along with any
+//! proc-macro metadata from the library, and writes everything needed to generate the bindings to
+//! a JSON file.  [`generate_bindings_from_artifact`] then generates the bindings from that file,
+//! without needing the UDL file, the library or the crate sources.
+//!
+//! The metadata types aren't stable between uniffi versions, so artifacts are tagged with the
+//! version of uniffi that wrote them and can only be read by that same version.
+
+use anyhow::{bail, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+use uniffi_meta::{Metadata, MetadataGroup};
+
+use crate::{
+    bindings::TargetLanguage, crate_name_from_cargo_toml, get_out_dir, guess_crate_root,
+    library_mode::calc_cdylib_name, macro_metadata, merge_config_override, BindingGenerator,
+    BindingGeneratorDefault, BindingsConfig, ComponentInterface,
+};
+
+const UNIFFI_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Everything needed to generate the bindings for a component.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MetadataArtifact {
+    /// The version of uniffi that wrote the artifact.
+    uniffi_version: String,
+    /// The metadata parsed from the UDL file.
+    udl: MetadataGroup,
+    /// The proc-macro metadata extracted from the library, if one was given.
+    library_items: Vec<Metadata>,
+    /// The contents of the crate's `uniffi.toml`, if it has one.
+    config: Option<String>,
+    /// The cdylib name, if it could be calculated from the library path.
+    cdylib_name: Option<String>,
+}
+
+impl MetadataArtifact {
+    fn new(
+        udl: MetadataGroup,
+        library_items: Vec<Metadata>,
+        config: Option<String>,
+        cdylib_name: Option<String>,
+    ) -> Self {
+        Self {
+            uniffi_version: UNIFFI_VERSION.to_string(),
+            udl,
+            library_items,
+            config,
+            cdylib_name,
+        }
+    }
+
+    /// Parse the UDL file and proc-macro metadata for a component.
+    ///
+    /// `library_file` and `crate_name` work the same way as for [`crate::generate_bindings`].
+    pub fn from_udl_file(
+        udl_file: &Utf8Path,
+        library_file: Option<&Utf8Path>,
+        crate_name: Option<&str>,
+    ) -> Result<Self> {
+        let crate_name = crate_name
+            .map(|c| Ok(c.to_string()))
+            .unwrap_or_else(|| crate_name_from_cargo_toml(udl_file))?;
+        let udl = fs::read_to_string(udl_file)
+            .with_context(|| format!("Failed to read UDL from {udl_file}"))?;
+        let udl = uniffi_udl::parse_udl(&udl, &crate_name)?;
+        let (library_items, cdylib_name) = match library_file {
+            Some(library_file) => (
+                macro_metadata::extract_from_library(library_file)
+                    .context("Failed to extract proc-macro metadata")?,
+                calc_cdylib_name(library_file).map(str::to_string),
+            ),
+            None => (vec![], None),
+        };
+        let crate_root = guess_crate_root(udl_file).context("Failed to guess crate root")?;
+        let config_file = crate_root.join("uniffi.toml");
+        let config = if config_file.exists() {
+            Some(fs::read_to_string(config_file)?)
+        } else {
+            None
+        };
+        Ok(Self::new(udl, library_items, config, cdylib_name))
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        // Check the version before anything else, since the rest of the artifact may not match
+        // the current metadata types.
+        #[derive(Deserialize)]
+        struct Header {
+            uniffi_version: String,
+        }
+
+        let header: Header =
+            serde_json::from_str(json).context("Failed to read metadata artifact version")?;
+        if header.uniffi_version != UNIFFI_VERSION {
+            bail!(
+                "Metadata artifact was written by uniffi {}, but this is uniffi {UNIFFI_VERSION}",
+                header.uniffi_version
+            );
+        }
+        serde_json::from_str(json).context("Failed to read metadata artifact")
+    }
+
+    pub fn write(&self, path: &Utf8Path) -> Result<()> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    pub fn read(path: &Utf8Path) -> Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?).with_context(|| format!("Reading {path}"))
+    }
+
+    /// Build the `ComponentInterface` for the component.
+    pub fn component_interface(&self) -> Result<ComponentInterface> {
+        let mut ci = ComponentInterface::from_metadata(self.udl.clone())?;
+        macro_metadata::add_to_ci(&mut ci, self.library_items.clone())
+            .context("Failed to add proc-macro metadata to ComponentInterface")?;
+        Ok(ci)
+    }
+
+    /// Load the bindings config for the component, from the `uniffi.toml` stored in the artifact
+    /// merged with `config_file_override`.
+    pub fn load_config<Config: BindingsConfig>(
+        &self,
+        ci: &ComponentInterface,
+        config_file_override: Option<&Utf8Path>,
+    ) -> Result<Config> {
+        let initial = match &self.config {
+            Some(config) => toml::de::from_str(config).context("default config")?,
+            None => toml::value::Table::default(),
+        };
+        let mut config: Config = merge_config_override(initial, config_file_override)?;
+        config.update_from_ci(ci);
+        if let Some(cdylib_name) = &self.cdylib_name {
+            config.update_from_cdylib_name(cdylib_name);
+        }
+        Ok(config)
+    }
+}
+
+/// Parse a UDL file and write the result to a metadata artifact.
+///
+/// The artifact is named after the UDL file, for example `example.uniffi.json` for
+/// `example.udl`, and written to `out_dir_override` or the directory of the UDL file.
+/// Returns the path to the artifact.
+pub fn write_metadata_artifact(
+    udl_file: &Utf8Path,
+    out_dir_override: Option<&Utf8Path>,
+    library_file: Option<&Utf8Path>,
+    crate_name: Option<&str>,
+) -> Result<Utf8PathBuf> {
+    let artifact = MetadataArtifact::from_udl_file(udl_file, library_file, crate_name)?;
+    let file_stem = udl_file.file_stem().context("not a file")?;
+    let out_path =
+        get_out_dir(udl_file, out_dir_override)?.join(format!("{file_stem}.uniffi.json"));
+    artifact.write(&out_path)?;
+    Ok(out_path)
+}
+
+/// Generate bindings for an external binding generator from a metadata artifact.
+///
+/// This is the equivalent of [`crate::generate_external_bindings`], except the bindings are
+/// written to `out_dir_override` or the directory of the artifact.
+pub fn generate_external_bindings_from_artifact<T: BindingGenerator>(
+    binding_generator: T,
+    artifact_file: &Utf8Path,
+    config_file_override: Option<&Utf8Path>,
+    out_dir_override: Option<&Utf8Path>,
+) -> Result<()> {
+    let artifact = MetadataArtifact::read(artifact_file)?;
+    let component = artifact.component_interface()?;
+    let config = artifact.load_config::<T::Config>(&component, config_file_override)?;
+    let out_dir = get_out_dir(artifact_file, out_dir_override)?;
+    binding_generator.write_bindings(&component, &config, &out_dir)
+}
+
+/// Generate the bindings in the target languages from a metadata artifact.
+pub fn generate_bindings_from_artifact(
+    artifact_file: &Utf8Path,
+    config_file_override: Option<&Utf8Path>,
+    target_languages: Vec<TargetLanguage>,
+    out_dir_override: Option<&Utf8Path>,
+    try_format_code: bool,
+) -> Result<()> {
+    generate_external_bindings_from_artifact(
+        BindingGeneratorDefault {
+            target_languages,
+            try_format_code,
+            formatters: Default::default(),
+        },
+        artifact_file,
+        config_file_override,
+        out_dir_override,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bindings::{kotlin, python, swift};
+    use crate::Config;
+
+    const UDL: &str = r#"
+        namespace test {
+            Point make_point(i32 x, i32 y);
+            [Throws=ArithmeticError]
+            u64 add(u64 a, u64 b);
+        };
+
+        dictionary Point {
+            i32 x;
+            i32 y = 0;
+            string? label = null;
+        };
+
+        enum Direction {
+            "North",
+            "South",
+        };
+
+        [Error]
+        enum ArithmeticError {
+            "IntegerOverflow",
+        };
+
+        interface Counter {
+            constructor(u32 start);
+            [Name=with_default]
+            constructor();
+            u32 increment(Direction direction);
+            sequence<Point> history();
+            record<string, u32> totals();
+        };
+
+        callback interface Listener {
+            void on_change(u32 value);
+        };
+    "#;
+
+    const CONFIG: &str = r#"
+        [bindings.kotlin]
+        package_name = "com.example.test"
+    "#;
+
+    fn render(ci: &ComponentInterface, config: &Config) -> Vec<String> {
+        vec![
+            kotlin::generate_bindings(&config.bindings.kotlin, ci).unwrap(),
+            swift::generate_bindings(&config.bindings.swift, ci)
+                .unwrap()
+                .library,
+            python::generate_python_bindings(&config.bindings.python, ci).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let group = uniffi_udl::parse_udl(UDL, "crate_name").unwrap();
+        let artifact = MetadataArtifact::new(
+            group,
+            vec![],
+            Some(CONFIG.to_string()),
+            Some("test_cdylib".to_string()),
+        );
+        let json = artifact.to_json().unwrap();
+        let round_tripped = MetadataArtifact::from_json(&json).unwrap();
+
+        let direct_ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let mut direct_config: Config = toml::de::from_str(CONFIG).unwrap();
+        direct_config.update_from_ci(&direct_ci);
+        direct_config.update_from_cdylib_name("test_cdylib");
+
+        let ci = round_tripped.component_interface().unwrap();
+        let config: Config = round_tripped.load_config(&ci, None).unwrap();
+        assert_eq!(render(&ci, &config), render(&direct_ci, &direct_config));
+        assert!(render(&ci, &config)[0].contains("package com.example.test"));
+    }
+
+    #[test]
+    fn test_version_mismatch() {
+        let group = uniffi_udl::parse_udl(UDL, "crate_name").unwrap();
+        let mut artifact = MetadataArtifact::new(group, vec![], None, None);
+        artifact.uniffi_version = "0.0.1".to_string();
+        let err = MetadataArtifact::from_json(&artifact.to_json().unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Metadata artifact was written by uniffi 0.0.1, but this is uniffi {UNIFFI_VERSION}")
+        );
+
+        // The version is checked before the rest of the artifact is read.
+        let err =
+            MetadataArtifact::from_json(r#"{"uniffi_version": "0.0.1", "udl": 1}"#).unwrap_err();
+        assert!(err.to_string().contains("0.0.1"));
+    }
+}
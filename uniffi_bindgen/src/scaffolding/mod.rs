@@ -40,6 +40,16 @@ mod filters {
             Type::Bytes => "Vec<u8>".into(),
             Type::Timestamp => "std::time::SystemTime".into(),
             Type::Duration => "std::time::Duration".into(),
+            Type::DurationRange { inclusive: false } => {
+                "std::ops::Range<std::time::Duration>".into()
+            }
+            Type::DurationRange { inclusive: true } => {
+                "std::ops::RangeInclusive<std::time::Duration>".into()
+            }
+            Type::Path => "std::path::PathBuf".into(),
+            Type::Json => "::uniffi::deps::serde_json::Value".into(),
+            Type::DynError => "::uniffi::DynError".into(),
+            Type::OpaqueHandle => "::uniffi::OpaqueHandle".into(),
             Type::Enum { name, .. } | Type::Record { name, .. } => format!("r#{name}"),
             Type::Object { name, imp, .. } => {
                 format!("std::sync::Arc<{}>", imp.rust_name_for(name))
@@ -49,6 +59,9 @@ mod filters {
                 format!("std::option::Option<{}>", type_rs(inner_type)?)
             }
             Type::Sequence { inner_type } => format!("std::vec::Vec<{}>", type_rs(inner_type)?),
+            Type::NonZero { inner_type } => {
+                format!("std::num::NonZero{}", type_rs(inner_type)?.to_uppercase())
+            }
             Type::Map {
                 key_type,
                 value_type,
@@ -6,6 +6,16 @@
     {%- if e.is_non_exhaustive() -%}
     non_exhaustive,
     {%- endif %}
+    {%- match e.default_variant() %}
+    {%- when Some with (default) %}
+    default = r#{{ default.name() }},
+    {%- when None %}
+    {%- endmatch %}
+    {%- match e.discriminant_encoding() %}
+    {%- when Some with (encoding) %}
+    discriminant = {{ encoding.name() }},
+    {%- when None %}
+    {%- endmatch %}
 )]
 enum r#{{ e.name() }} {
     {%- for variant in e.variants() %}
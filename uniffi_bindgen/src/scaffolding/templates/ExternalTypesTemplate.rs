@@ -8,6 +8,8 @@
 {%- match kind %}
 {%- when ExternalKind::DataClass %}
 ::uniffi::ffi_converter_forward!(r#{{ name }}, ::{{ crate_name|crate_name_rs }}::UniFfiTag, crate::UniFfiTag);
+{%- when ExternalKind::ScalarEnum %}
+::uniffi::ffi_converter_forward!(r#{{ name }}, ::{{ crate_name|crate_name_rs }}::UniFfiTag, crate::UniFfiTag);
 {%- when ExternalKind::Interface %}
 ::uniffi::ffi_converter_arc_forward!(r#{{ name }}, ::{{ crate_name|crate_name_rs }}::UniFfiTag, crate::UniFfiTag);
 {%- when ExternalKind::Trait %}
@@ -16,6 +18,22 @@
 {% endif %}
 {%- endfor %}
 
+// External records and enums passed directly across the FFI are lowered the way the UDL declares
+// them, which must match their `FfiConverter`.  Enums without fields are passed as an `i32`, and
+// are declared with `[External="crate_name", Enum]`.
+{% for (name, kind) in ci.iter_external_types_passed_directly() %}
+const _: fn() = || {
+{%- match kind %}
+{%- when ExternalKind::ScalarEnum %}
+    fn only_declare_enums_without_fields_with_enum<T: ::uniffi::Lift<crate::UniFfiTag, FfiType = i32>>() {}
+    only_declare_enums_without_fields_with_enum::<r#{{ name }}>();
+{%- else %}
+    fn declare_enums_without_fields_with_enum<T: ::uniffi::Lift<crate::UniFfiTag, FfiType = ::uniffi::RustBuffer>>() {}
+    declare_enums_without_fields_with_enum::<r#{{ name }}>();
+{%- endmatch %}
+};
+{%- endfor %}
+
 // We generate support for each Custom Type and the builtin type it uses.
 {%- for (name, builtin) in ci.iter_custom_types() %}
 ::uniffi::custom_type!(r#{{ name }}, {{builtin|type_rs}});
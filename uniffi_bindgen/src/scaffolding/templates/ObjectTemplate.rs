@@ -27,20 +27,75 @@ pub trait r#{{ obj.name() }} {
 {%          when UniffiTrait::Debug { fmt }%}
 #[uniffi::export(Debug)]
 {%          when UniffiTrait::Display { fmt }%}
+{%-             if !obj.is_stringifier(tm) %}
 #[uniffi::export(Display)]
+{%-             endif %}
 {%          when UniffiTrait::Hash { hash }%}
 #[uniffi::export(Hash)]
 {%          when UniffiTrait::Eq { eq, ne }%}
 #[uniffi::export(Eq)]
 {%      endmatch %}
 {% endfor %}
-#[::uniffi::derive_object_for_udl]
+#[::uniffi::derive_object_for_udl{% if obj.is_thread_bound() %}(thread_bound){% endif %}]
 struct {{ obj.rust_name() }} { }
 
+{%- for trait_type in obj.implements() %}
+{%-     match trait_type %}
+{%-         when Type::Object { name, module_path, imp } %}
+// `[Implements]` means the object can be used as a `{{ name }}`, so check that it really is one.
+::uniffi::deps::static_assertions::assert_impl_all!({{ obj.rust_name() }}: r#{{ name }});
+{%-         else %}
+{%-     endmatch %}
+{%- endfor %}
+
+{%- if obj.is_singleton() %}
+// `[Singleton]` means there's only one instance of the object, which is created with `Default`
+// the first time it's asked for.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ obj.ffi_object_singleton().name() }}(
+    call_status: &mut ::uniffi::RustCallStatus
+) -> *const ::std::ffi::c_void {
+    static INSTANCE: ::uniffi::deps::once_cell::sync::OnceCell<::std::sync::Arc<{{ obj.rust_name() }}>> = ::uniffi::deps::once_cell::sync::OnceCell::new();
+    ::uniffi::rust_call(call_status, || {
+        let instance = INSTANCE.get_or_init(|| ::std::sync::Arc::new(<{{ obj.rust_name() }} as ::std::default::Default>::default()));
+        Ok(<::std::sync::Arc<{{ obj.rust_name() }}> as ::uniffi::Lower<crate::UniFfiTag>>::lower(::std::sync::Arc::clone(instance)))
+    })
+}
+{%- endif %}
+
+{%- match obj.parent() %}
+{%- when Some(parent) %}
+// `interface {{ obj.name() }} : {{ parent }}` means the object can be converted to a `{{ parent }}`,
+// which the bindings use to call the methods it inherits.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ obj.ffi_object_upcast().name() }}(
+    ptr: *const ::std::ffi::c_void,
+    call_status: &mut ::uniffi::RustCallStatus
+) -> *const ::std::ffi::c_void {
+    ::uniffi::rust_call(call_status, || {
+        {%- if obj.is_thread_bound() %}
+        // The handle is borrowed, so take a new reference to the object it points to.
+        let obj = ::std::sync::Arc::clone(unsafe { ::uniffi::ThreadBound::<{{ obj.rust_name() }}>::borrow(ptr) }.object());
+        {%- else %}
+        // The pointer is borrowed, so take a new reference before reconstructing the `Arc`.
+        let obj = unsafe {
+            ::std::sync::Arc::increment_strong_count(ptr as *const {{ obj.rust_name() }});
+            ::std::sync::Arc::from_raw(ptr as *const {{ obj.rust_name() }})
+        };
+        {%- endif %}
+        let parent = <{{ obj.rust_name() }} as ::uniffi::Upcast<r#{{ parent }}>>::upcast(obj);
+        Ok(<::std::sync::Arc<r#{{ parent }}> as ::uniffi::Lower<crate::UniFfiTag>>::lower(parent))
+    })
+}
+{%- when None %}
+{%- endmatch %}
+
 {%- for cons in obj.constructors() %}
-#[::uniffi::export_for_udl(constructor)]
+#[::uniffi::export_for_udl(constructor{% if obj.is_thread_bound() %}, thread_bound{% endif %})]
 impl {{ obj.rust_name() }} {
-    pub fn r#{{ cons.name() }}(
+    pub {% if cons.is_async() %}async {% endif %}fn r#{{ cons.name() }}(
         {%- for arg in cons.arguments() %}
         r#{{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.as_type().borrow()|type_rs }},
         {%- endfor %}
@@ -58,7 +113,14 @@ impl {{ obj.rust_name() }} {
 {%- endfor %}
 
 {%- for meth in obj.methods() %}
-#[::uniffi::export_for_udl]
+#[::uniffi::export_for_udl
+{%- if obj.is_thread_bound() || obj.on_poison().is_some() -%}
+(
+    {%- if obj.is_thread_bound() %}thread_bound{% endif %}
+    {%- match obj.on_poison() %}{% when Some(on_poison) %}{% if obj.is_thread_bound() %}, {% endif %}on_poison = {{ on_poison.name() }}{% when None %}{% endmatch -%}
+)
+{%- endif -%}
+]
 impl {{ obj.rust_name() }} {
     pub {% if meth.is_async() %}async {% endif %}fn r#{{ meth.name() }}(
         {% if meth.takes_self_by_arc()%}self: Arc<Self>{% else %}&self{% endif %},
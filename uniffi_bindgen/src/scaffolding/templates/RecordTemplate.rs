@@ -3,8 +3,30 @@
 #}
 
 #[::uniffi::derive_record_for_udl]
+{%- if rec.is_packed() %}
+#[uniffi(packed)]
+{%- endif %}
 struct r#{{ rec.name() }} {
     {%- for field in rec.fields() %}
+    {%- match field.order() %}
+    {%- when Some(order) %}
+    #[uniffi(order = {{ order }})]
+    {%- when None %}
+    {%- endmatch %}
     r#{{ field.name() }}: {{ field.as_type().borrow()|type_rs }},
     {%- endfor %}
 }
+
+{%- if rec.has_default() %}
+
+// `[HasDefault]` means the bindings can construct the record's `Default` value.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ rec.ffi_func_default().name() }}(
+    call_status: &mut ::uniffi::RustCallStatus
+) -> ::uniffi::RustBuffer {
+    ::uniffi::rust_call(call_status, || {
+        Ok(<r#{{ rec.name() }} as ::uniffi::Lower<crate::UniFfiTag>>::lower(<r#{{ rec.name() }} as ::std::default::Default>::default()))
+    })
+}
+{%- endif %}
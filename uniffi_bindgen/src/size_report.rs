@@ -0,0 +1,210 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Estimate the size of the generated bindings.
+//!
+//! This is intended to help track how the size of the bindings grows over time.  The report is
+//! built by rendering the bindings with the normal templates and measuring the output.
+//!
+//! To break the size down by category, the bindings are rendered again with each category of item
+//! removed from the `ComponentInterface` and the difference is attributed to that category.  The
+//! "runtime helpers" category is the size of the bindings for an interface with no items at all,
+//! which is the code every component gets regardless of what it exports.  These numbers are
+//! estimates: helper code that's only generated when a category is used counts towards that
+//! category, and the categories don't add up exactly to the total.
+//!
+//! Ruby isn't included, since its bindings don't support all the features of the other languages.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use crate::bindings::{kotlin, python, swift, TargetLanguage};
+use crate::interface::ComponentInterface;
+use crate::BindingsConfig;
+
+/// A category of generated code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SizeCategory {
+    Records,
+    Enums,
+    Functions,
+    /// Code which is generated for every component, regardless of what it exports.
+    RuntimeHelpers,
+}
+
+impl SizeCategory {
+    pub const ALL: [SizeCategory; 4] = [
+        SizeCategory::Records,
+        SizeCategory::Enums,
+        SizeCategory::Functions,
+        SizeCategory::RuntimeHelpers,
+    ];
+}
+
+/// The size of some generated code.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Size {
+    pub lines: usize,
+    pub bytes: usize,
+}
+
+impl Size {
+    pub fn of(source: &str) -> Self {
+        Self {
+            lines: source.lines().count(),
+            bytes: source.len(),
+        }
+    }
+
+    fn saturating_sub(self, other: Self) -> Self {
+        Self {
+            lines: self.lines.saturating_sub(other.lines),
+            bytes: self.bytes.saturating_sub(other.bytes),
+        }
+    }
+}
+
+/// The estimated size of the bindings for a single language.
+#[derive(Debug, Clone)]
+pub struct LanguageSizeReport {
+    pub language: TargetLanguage,
+    /// The size of all the generated code.
+    pub total: Size,
+    pub categories: BTreeMap<SizeCategory, Size>,
+}
+
+impl LanguageSizeReport {
+    pub fn category(&self, category: SizeCategory) -> Size {
+        self.categories.get(&category).copied().unwrap_or_default()
+    }
+}
+
+/// The estimated size of the bindings for each supported language.
+#[derive(Debug, Clone)]
+pub struct SizeReport {
+    pub languages: Vec<LanguageSizeReport>,
+}
+
+impl SizeReport {
+    pub fn language(&self, language: TargetLanguage) -> Option<&LanguageSizeReport> {
+        self.languages.iter().find(|l| l.language == language)
+    }
+}
+
+/// Build a size report for the bindings generated from `ci`, using the default config for each
+/// language.
+pub fn size_report(ci: &ComponentInterface) -> Result<SizeReport> {
+    let languages = [
+        TargetLanguage::Kotlin,
+        TargetLanguage::Swift,
+        TargetLanguage::Python,
+    ]
+    .into_iter()
+    .map(|language| language_size_report(language, ci))
+    .collect::<Result<_>>()?;
+    Ok(SizeReport { languages })
+}
+
+fn language_size_report(
+    language: TargetLanguage,
+    ci: &ComponentInterface,
+) -> Result<LanguageSizeReport> {
+    let total = render(language, ci)?;
+    let mut categories = BTreeMap::new();
+    for category in SizeCategory::ALL {
+        let size = match category {
+            SizeCategory::RuntimeHelpers => render(language, &ci.empty_copy())?,
+            _ => {
+                let mut reduced = ci.clone();
+                match category {
+                    SizeCategory::Records => reduced.remove_records(),
+                    SizeCategory::Enums => reduced.remove_enums(),
+                    SizeCategory::Functions => reduced.remove_functions(),
+                    SizeCategory::RuntimeHelpers => unreachable!(),
+                }
+                total.saturating_sub(render(language, &reduced)?)
+            }
+        };
+        categories.insert(category, size);
+    }
+    Ok(LanguageSizeReport {
+        language,
+        total,
+        categories,
+    })
+}
+
+fn render(language: TargetLanguage, ci: &ComponentInterface) -> Result<Size> {
+    Ok(match language {
+        TargetLanguage::Kotlin => {
+            let mut config = kotlin::Config::default();
+            config.update_from_ci(ci);
+            Size::of(&kotlin::generate_bindings(&config, ci)?)
+        }
+        TargetLanguage::Swift => {
+            let mut config = swift::Config::default();
+            config.update_from_ci(ci);
+            let bindings = swift::generate_bindings(&config, ci)?;
+            let library = Size::of(&bindings.library);
+            let header = Size::of(&bindings.header);
+            Size {
+                lines: library.lines + header.lines,
+                bytes: library.bytes + header.bytes,
+            }
+        }
+        TargetLanguage::Python => {
+            let mut config = python::Config::default();
+            config.update_from_ci(ci);
+            Size::of(&python::generate_python_bindings(&config, ci)?)
+        }
+        TargetLanguage::Ruby => anyhow::bail!("size reports aren't supported for Ruby"),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const UDL: &str = r#"
+        namespace test {
+            Point make_point(i32 x, i32 y);
+            Direction opposite(Direction direction);
+        };
+
+        dictionary Point {
+            i32 x;
+            i32 y;
+        };
+
+        enum Direction {
+            "North",
+            "East",
+            "South",
+            "West",
+        };
+    "#;
+
+    #[test]
+    fn test_size_report() {
+        let ci = ComponentInterface::from_webidl(UDL, "crate_name").unwrap();
+        let report = size_report(&ci).unwrap();
+        assert_eq!(report.languages.len(), 3);
+        for language in &report.languages {
+            assert!(language.total.lines > 0);
+            assert!(language.total.bytes > 0);
+            assert_eq!(language.categories.len(), SizeCategory::ALL.len());
+            for category in SizeCategory::ALL {
+                let size = language.category(category);
+                assert!(
+                    size.lines > 0 && size.bytes > 0,
+                    "{} {category:?} is empty",
+                    language.language
+                );
+                assert!(size.bytes < language.total.bytes);
+            }
+        }
+        assert!(report.language(TargetLanguage::Ruby).is_none());
+    }
+}
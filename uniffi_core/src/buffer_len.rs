@@ -0,0 +1,87 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::Result;
+use anyhow::bail;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The default value for [max_buffer_len] (1GiB).
+pub const DEFAULT_MAX_BUFFER_LEN: usize = 1 << 30;
+
+static MAX_BUFFER_LEN: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_BUFFER_LEN);
+
+/// The maximum length that we'll accept when lifting data from a buffer.
+///
+/// This applies both to the total size of a lifted `RustBuffer` and to the number of items in a
+/// serialized collection.
+pub fn max_buffer_len() -> usize {
+    MAX_BUFFER_LEN.load(Ordering::Relaxed)
+}
+
+/// Set the maximum length that we'll accept when lifting data from a buffer.
+///
+/// Foreign code is not trusted to send sensible lengths over the FFI.  A corrupted length could
+/// otherwise cause a huge allocation while lifting, so any length exceeding this value results in
+/// a lift error instead.
+pub fn set_max_buffer_len(len: usize) {
+    MAX_BUFFER_LEN.store(len, Ordering::Relaxed)
+}
+
+/// A helper function to ensure a length read from foreign code is within [max_buffer_len].
+///
+/// This should be called before allocating based on a length from a buffer.
+pub fn check_max_buffer_len(len: usize) -> Result<()> {
+    let max_len = max_buffer_len();
+    if len > max_len {
+        bail!("buffer length exceeds the maximum allowed ({len} > {max_len})");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, Lower, RustBuffer, UniFfiTag};
+    use std::collections::HashMap;
+
+    #[test]
+    fn lift_under_max_buffer_len() {
+        assert!(check_max_buffer_len(DEFAULT_MAX_BUFFER_LEN).is_ok());
+        let expected = vec![1u8, 2, 3];
+        let result = <Vec<u8> as Lift<UniFfiTag>>::try_lift(<Vec<u8> as Lower<UniFfiTag>>::lower(
+            expected.clone(),
+        ))
+        .expect("Failed to lift!");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn lift_over_max_buffer_len() {
+        assert!(check_max_buffer_len(DEFAULT_MAX_BUFFER_LEN + 1).is_err());
+        // A buffer claiming to contain `i32::MAX` items.  Lifting this must fail before trying to
+        // allocate space for them all, which would need 16GiB.
+        let buf = RustBuffer::from_vec(i32::MAX.to_be_bytes().to_vec());
+        let err = <Vec<u64> as Lift<UniFfiTag>>::try_lift(buf).unwrap_err();
+        assert!(
+            err.to_string().contains("exceeds the maximum allowed"),
+            "unexpected error: {err}"
+        );
+
+        // A count just under the limit is accepted, but mustn't reserve space for that many items
+        // either, since the buffer is too short to hold them.
+        let len = i32::try_from(DEFAULT_MAX_BUFFER_LEN - 1).unwrap();
+        let buf = RustBuffer::from_vec(len.to_be_bytes().to_vec());
+        let err = <Vec<u64> as Lift<UniFfiTag>>::try_lift(buf).unwrap_err();
+        assert!(
+            err.to_string().contains("not enough bytes remaining"),
+            "unexpected error: {err}"
+        );
+        let buf = RustBuffer::from_vec(len.to_be_bytes().to_vec());
+        let err = <HashMap<u64, u64> as Lift<UniFfiTag>>::try_lift(buf).unwrap_err();
+        assert!(
+            err.to_string().contains("not enough bytes remaining"),
+            "unexpected error: {err}"
+        );
+    }
+}
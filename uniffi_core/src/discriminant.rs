@@ -0,0 +1,143 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::{check_remaining, Result};
+use anyhow::bail;
+use bytes::{Buf, BufMut};
+
+/// A fixed width and byte order for an enum's discriminant, set with `[Discriminant=...]`.
+///
+/// Enums are normally serialized with their discriminant, the 1-based index of the variant, as a
+/// big-endian `i32`.  Enums which are synced with an external protocol can use one of these
+/// encodings instead, whatever the byte order of the platform is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiscriminantEncoding {
+    U8,
+    U16Le,
+    U16Be,
+    U32Le,
+    U32Be,
+}
+
+impl DiscriminantEncoding {
+    /// The number of bytes the discriminant is serialized as.
+    pub const fn width(self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::U16Le | Self::U16Be => 2,
+            Self::U32Le | Self::U32Be => 4,
+        }
+    }
+
+    /// Write a discriminant, which must fit in the encoding's width.
+    pub fn write(self, discriminant: i32, buf: &mut Vec<u8>) {
+        match self {
+            Self::U8 => buf.put_u8(discriminant as u8),
+            Self::U16Le => buf.put_u16_le(discriminant as u16),
+            Self::U16Be => buf.put_u16(discriminant as u16),
+            Self::U32Le => buf.put_u32_le(discriminant as u32),
+            Self::U32Be => buf.put_u32(discriminant as u32),
+        }
+    }
+
+    /// Read a discriminant written by [Self::write].
+    pub fn read(self, buf: &mut &[u8]) -> Result<i32> {
+        check_remaining(buf, self.width())?;
+        let discriminant = match self {
+            Self::U8 => buf.get_u8().into(),
+            Self::U16Le => buf.get_u16_le().into(),
+            Self::U16Be => buf.get_u16().into(),
+            Self::U32Le => buf.get_u32_le(),
+            Self::U32Be => buf.get_u32(),
+        };
+        match i32::try_from(discriminant) {
+            Ok(discriminant) => Ok(discriminant),
+            Err(_) => bail!("enum discriminant out of range ({discriminant})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{FfiConverter, Lift, Lower, MetadataBuffer, UniFfiTag};
+
+    #[test]
+    fn discriminant_encoding_roundtrip() {
+        let cases: [(DiscriminantEncoding, &[u8]); 5] = [
+            (DiscriminantEncoding::U8, &[0x12]),
+            (DiscriminantEncoding::U16Le, &[0x12, 0x00]),
+            (DiscriminantEncoding::U16Be, &[0x00, 0x12]),
+            (DiscriminantEncoding::U32Le, &[0x12, 0x00, 0x00, 0x00]),
+            (DiscriminantEncoding::U32Be, &[0x00, 0x00, 0x00, 0x12]),
+        ];
+        for (encoding, bytes) in cases {
+            let mut buf = Vec::new();
+            encoding.write(0x12, &mut buf);
+            assert_eq!(buf, bytes, "{encoding:?}");
+            assert_eq!(buf.len(), encoding.width());
+            assert_eq!(encoding.read(&mut buf.as_slice()).unwrap(), 0x12);
+            // A truncated discriminant is an error rather than a panic.
+            assert!(encoding.read(&mut &buf[1..]).is_err());
+        }
+        let mut buf = Vec::new();
+        DiscriminantEncoding::U16Be.write(0x0102, &mut buf);
+        DiscriminantEncoding::U16Le.write(0x0102, &mut buf);
+        assert_eq!(buf, [0x01, 0x02, 0x02, 0x01]);
+        // Discriminants are at most `i32::MAX`.
+        let err = DiscriminantEncoding::U32Le
+            .read(&mut [0xff, 0xff, 0xff, 0xff].as_slice())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "enum discriminant out of range (4294967295)"
+        );
+    }
+
+    // A fieldless enum with a little-endian `u16` discriminant, implemented the way
+    // `#[derive(uniffi::Enum)]` implements `#[uniffi(discriminant = u16_le)]` enums.
+    #[derive(Debug, PartialEq, Eq)]
+    enum Opcode {
+        Read,
+        Write,
+    }
+
+    unsafe impl FfiConverter<UniFfiTag> for Opcode {
+        crate::ffi_converter_scalar_enum_read_and_write!(UniFfiTag, DiscriminantEncoding::U16Le);
+
+        fn lower(obj: Opcode) -> i32 {
+            match obj {
+                Opcode::Read => 1,
+                Opcode::Write => 2,
+            }
+        }
+
+        fn try_lift(v: i32) -> Result<Opcode> {
+            Ok(match v {
+                1 => Opcode::Read,
+                2 => Opcode::Write,
+                v => anyhow::bail!("Invalid Opcode enum value: {v}"),
+            })
+        }
+
+        const TYPE_ID_META: MetadataBuffer = MetadataBuffer::new();
+    }
+
+    crate::derive_ffi_traits!(impl Lower<UniFfiTag> for Opcode);
+    crate::derive_ffi_traits!(impl Lift<UniFfiTag> for Opcode);
+
+    #[test]
+    fn encoded_scalar_enum_roundtrip() {
+        // The enum is still lowered as the `i32`, but it's serialized as a little-endian `u16`.
+        assert_eq!(<Opcode as FfiConverter<UniFfiTag>>::lower(Opcode::Write), 2);
+        let mut buf = Vec::new();
+        <Vec<Opcode> as Lower<UniFfiTag>>::write(vec![Opcode::Write, Opcode::Read], &mut buf);
+        assert_eq!(buf, [0, 0, 0, 2, 2, 0, 1, 0]);
+        assert_eq!(
+            <Vec<Opcode> as Lift<UniFfiTag>>::try_read(&mut buf.as_slice()).unwrap(),
+            [Opcode::Write, Opcode::Read]
+        );
+        assert!(<Opcode as Lift<UniFfiTag>>::try_read(&mut [3, 0].as_slice()).is_err());
+    }
+}
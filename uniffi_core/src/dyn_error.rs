@@ -0,0 +1,106 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{error::Error, fmt};
+
+/// A dynamically typed error which can be thrown across the FFI.
+///
+/// Functions which return `Result<T, DynError>` don't need to declare a typed error.  Any error
+/// that can be converted into a `Box<dyn Error + Send + Sync>`, including `anyhow::Error`, can be
+/// converted into a `DynError` with `?`.
+///
+/// The foreign code receives a generic exception with the `Display` string of the error and the
+/// `Display` strings of its chain of sources.
+///
+/// Note that `DynError` deliberately doesn't implement `std::error::Error` itself, otherwise the
+/// blanket `From` impl below would conflict with `impl<T> From<T> for T`.
+pub struct DynError(Box<dyn Error + Send + Sync + 'static>);
+
+impl DynError {
+    pub fn new(error: impl Into<Box<dyn Error + Send + Sync + 'static>>) -> Self {
+        Self(error.into())
+    }
+
+    /// The `Display` string of the error.
+    pub fn message(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// The `Display` strings of the error's sources, starting with the immediate source.
+    pub fn cause_chain(&self) -> Vec<String> {
+        let mut chain = vec![];
+        let mut source = self.0.source();
+        while let Some(error) = source {
+            chain.push(error.to_string());
+            source = error.source();
+        }
+        chain
+    }
+
+    pub fn into_inner(self) -> Box<dyn Error + Send + Sync + 'static> {
+        self.0
+    }
+}
+
+impl<E> From<E> for DynError
+where
+    E: Into<Box<dyn Error + Send + Sync + 'static>>,
+{
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+
+impl fmt::Debug for DynError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for DynError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Outer(std::io::Error);
+
+    impl fmt::Display for Outer {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "outer")
+        }
+    }
+
+    impl Error for Outer {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn test_cause_chain() {
+        let err = DynError::from(Outer(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "inner",
+        )));
+        assert_eq!(err.message(), "outer");
+        assert_eq!(err.cause_chain(), vec!["inner".to_string()]);
+
+        let err = DynError::from(anyhow::anyhow!("root").context("middle").context("top"));
+        assert_eq!(err.message(), "top");
+        assert_eq!(
+            err.cause_chain(),
+            vec!["middle".to_string(), "root".to_string()]
+        );
+
+        let err = DynError::from("just a message");
+        assert_eq!(err.message(), "just a message");
+        assert!(err.cause_chain().is_empty());
+    }
+}
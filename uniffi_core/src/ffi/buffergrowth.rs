@@ -0,0 +1,111 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Buffer growth while lowering
+//!
+//! Values which are lowered into a [RustBuffer](super::RustBuffer) are written into a `Vec<u8>`,
+//! which is reallocated whenever it runs out of capacity.  [BufferGrowth] controls how that buffer
+//! is allocated and grown, using the size hints from [crate::FfiConverter::size_hint].
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// How buffers are allocated and grown while values are lowered into them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferGrowth {
+    /// Start with an empty buffer and let it double its capacity whenever it runs out.  Size
+    /// hints are ignored, except that sequences of items with a fixed size still grow the buffer
+    /// once for all of their items.
+    Doubling,
+    /// Start with a buffer of this many bytes, and grow it by at least this many bytes when the
+    /// next item of a sequence or map doesn't fit.  Other writes which don't fit double the
+    /// capacity, like [BufferGrowth::Doubling].
+    FixedIncrement(usize),
+    /// Pre-size the buffer from the value's size hint.  This is exact for values which only
+    /// contain fixed-width fields, so the buffer is never reallocated.  Otherwise the buffer
+    /// doubles its capacity when the hint is exceeded.
+    SizeHint,
+}
+
+impl Default for BufferGrowth {
+    fn default() -> Self {
+        Self::SizeHint
+    }
+}
+
+// The growth policy is read for every lowered value, so it's packed into a single atomic.
+// `FixedIncrement` increments are stored as-is, and are always at least 1.
+const DOUBLING: usize = 0;
+const SIZE_HINT: usize = usize::MAX;
+
+static BUFFER_GROWTH: AtomicUsize = AtomicUsize::new(SIZE_HINT);
+
+/// How buffers are allocated and grown while values are lowered into them.
+pub fn buffer_growth() -> BufferGrowth {
+    match BUFFER_GROWTH.load(Ordering::Relaxed) {
+        DOUBLING => BufferGrowth::Doubling,
+        SIZE_HINT => BufferGrowth::SizeHint,
+        increment => BufferGrowth::FixedIncrement(increment),
+    }
+}
+
+/// Set how buffers are allocated and grown while values are lowered into them.
+///
+/// This applies to values lowered after the call.  Increments below 1 are treated as 1.
+pub fn set_buffer_growth(growth: BufferGrowth) {
+    let value = match growth {
+        BufferGrowth::Doubling => DOUBLING,
+        BufferGrowth::SizeHint => SIZE_HINT,
+        BufferGrowth::FixedIncrement(increment) => increment.clamp(1, SIZE_HINT - 1),
+    };
+    BUFFER_GROWTH.store(value, Ordering::Relaxed)
+}
+
+impl BufferGrowth {
+    /// Allocate the buffer to lower a value into, given the value's size hint.
+    pub fn new_buffer(self, size_hint: usize) -> Vec<u8> {
+        match self {
+            Self::Doubling => Vec::new(),
+            Self::FixedIncrement(increment) => Vec::with_capacity(increment),
+            Self::SizeHint => Vec::with_capacity(size_hint),
+        }
+    }
+
+    /// Make room for the next item of a sequence or map, before it's written into `buf`.
+    ///
+    /// `size_hint` is only called if the policy needs it.
+    pub fn reserve_item(self, buf: &mut Vec<u8>, size_hint: impl FnOnce() -> usize) {
+        if let Self::FixedIncrement(increment) = self {
+            let additional = size_hint();
+            if buf.capacity() - buf.len() < additional {
+                buf.reserve_exact(additional.max(increment));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_buffer_growth() {
+        assert_eq!(BufferGrowth::SizeHint.new_buffer(100).capacity(), 100);
+        assert_eq!(BufferGrowth::Doubling.new_buffer(100).capacity(), 0);
+
+        let growth = BufferGrowth::FixedIncrement(64);
+        let mut buf = growth.new_buffer(100);
+        assert_eq!(buf.capacity(), 64);
+        buf.extend([0; 60]);
+        // The item fits, so the buffer isn't grown.
+        growth.reserve_item(&mut buf, || 4);
+        assert_eq!(buf.capacity(), 64);
+        buf.extend([0; 4]);
+        // The buffer is grown by the increment, or by the item's size if that's larger.
+        growth.reserve_item(&mut buf, || 4);
+        assert_eq!(buf.capacity(), 128);
+        buf.extend([0; 64]);
+        growth.reserve_item(&mut buf, || 100);
+        assert_eq!(buf.capacity(), 228);
+    }
+}
@@ -0,0 +1,291 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Segmented byte sequences
+//!
+//! Passing a `Vec<Vec<u8>>` normally serializes all of the segments into a single [RustBuffer],
+//! and the receiving side splits them up again.  Arguments and return values marked `[Segmented]`
+//! in the UDL are passed as a handle to [ByteSegments] instead, which keeps each segment in its own
+//! buffer.
+//!
+//! This isn't zero-copy vectored I/O: each argument segment is copied into Rust by its own
+//! [byte_segments_push] call, and the bindings copy each returned segment out of its buffer.  What
+//! it saves is building and parsing one large serialized buffer, and the copies into it.
+//!
+//! ## Protocol
+//!
+//! For arguments:
+//! - The foreign code calls [byte_segments_new] to get a handle, then [byte_segments_push] with
+//!   each segment in order.  Each segment is copied from the foreign memory into its own
+//!   `Vec<u8>`.
+//! - It passes the handle as the argument, which hands the segments over to Rust.  If it fails
+//!   before then, it calls [byte_segments_free] instead.
+//! - The scaffolding lifts every argument before checking whether any of them failed, so it always
+//!   takes over the handle.  If another argument fails to lift, the segments are dropped along
+//!   with the other lifted values.  The foreign code mustn't free the handle once it's passed.
+//!
+//! For return values:
+//! - The scaffolding function returns a handle to the segments.
+//! - The foreign code calls [byte_segments_len] to get the number of segments, then calls
+//!   [byte_segments_next] that many times.  Each call hands over the next segment's `Vec<u8>` as
+//!   a [RustBuffer], without copying it.  The buffer contains the raw bytes, rather than a
+//!   serialized value.
+//! - The foreign code then calls [byte_segments_free] to release the handle.  It must also do
+//!   this if reading a segment fails, which drops the remaining segments.
+//!
+//! All of these functions fail the call, rather than dereferencing it, if the handle is 0.
+
+use std::{collections::VecDeque, ops::Deref};
+
+use crate::{
+    rust_call, FfiDefault, ForeignBytes, Lift, LowerReturn, MetadataBuffer, RustBuffer,
+    RustCallStatus, RustCallStatusCode,
+};
+use anyhow::bail;
+
+/// Byte segments which are passed over the FFI without concatenating them
+///
+/// This is used by the scaffolding code for arguments and functions marked `[Segmented]` in
+/// the UDL.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ByteSegments(Vec<Vec<u8>>);
+
+impl ByteSegments {
+    pub fn new(segments: Vec<Vec<u8>>) -> Self {
+        Self(segments)
+    }
+
+    pub fn into_inner(self) -> Vec<Vec<u8>> {
+        self.0
+    }
+}
+
+impl From<Vec<Vec<u8>>> for ByteSegments {
+    fn from(segments: Vec<Vec<u8>>) -> Self {
+        Self(segments)
+    }
+}
+
+impl From<ByteSegments> for Vec<Vec<u8>> {
+    fn from(segments: ByteSegments) -> Self {
+        segments.0
+    }
+}
+
+impl Deref for ByteSegments {
+    type Target = [Vec<u8>];
+
+    fn deref(&self) -> &[Vec<u8>] {
+        &self.0
+    }
+}
+
+/// What handles point to, while the segments are being passed over the FFI.
+type SegmentQueue = VecDeque<Vec<u8>>;
+
+fn into_handle(segments: SegmentQueue) -> u64 {
+    Box::into_raw(Box::new(segments)) as usize as u64
+}
+
+// Check that a handle passed by the foreign code isn't null, failing the call if it is.
+fn check_handle(handle: u64, call_status: &mut RustCallStatus) -> bool {
+    if handle == 0 {
+        *call_status = RustCallStatus::error("null byte segments handle");
+        return false;
+    }
+    true
+}
+
+unsafe impl<UT> Lift<UT> for ByteSegments
+where
+    Vec<Vec<u8>>: Lift<UT>,
+{
+    type FfiType = u64;
+
+    fn try_lift(handle: u64) -> anyhow::Result<Self> {
+        if handle == 0 {
+            bail!("null byte segments handle");
+        }
+        let segments = unsafe { Box::from_raw(handle as usize as *mut SegmentQueue) };
+        Ok(Self(Vec::from(*segments)))
+    }
+
+    fn try_read(buf: &mut &[u8]) -> anyhow::Result<Self> {
+        Ok(Self(<Vec<Vec<u8>> as Lift<UT>>::try_read(buf)?))
+    }
+
+    // The metadata describes a `sequence<bytes>`, since the bindings know about the handle from
+    // the `[Segmented]` attribute.
+    const TYPE_ID_META: MetadataBuffer = <Vec<Vec<u8>> as Lift<UT>>::TYPE_ID_META;
+}
+
+unsafe impl<UT> LowerReturn<UT> for ByteSegments
+where
+    Vec<Vec<u8>>: Lift<UT>,
+{
+    type ReturnType = u64;
+
+    fn lower_return(obj: Self) -> Result<Self::ReturnType, RustBuffer> {
+        Ok(into_handle(obj.0.into()))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Vec<Vec<u8>> as Lift<UT>>::TYPE_ID_META;
+}
+
+/// Create an empty list of byte segments, to pass as an argument
+pub fn byte_segments_new(call_status: &mut RustCallStatus) -> u64 {
+    rust_call(call_status, || Ok(into_handle(SegmentQueue::new())))
+}
+
+/// Copy a segment from the foreign memory onto the end of a list of byte segments
+///
+/// # Safety
+///
+/// `handle` must have been returned by [byte_segments_new] and not yet passed to Rust or freed.
+pub unsafe fn byte_segments_push(
+    handle: u64,
+    segment: ForeignBytes,
+    call_status: &mut RustCallStatus,
+) {
+    if !check_handle(handle, call_status) {
+        return;
+    }
+    rust_call(call_status, || {
+        let segments = &mut *(handle as usize as *mut SegmentQueue);
+        segments.push_back(segment.as_slice().to_vec());
+        Ok(())
+    })
+}
+
+/// Get the number of segments which haven't been returned by [byte_segments_next] yet
+///
+/// # Safety
+///
+/// `handle` must have been returned by a segmented scaffolding function and not yet freed.
+pub unsafe fn byte_segments_len(handle: u64, call_status: &mut RustCallStatus) -> u64 {
+    if !check_handle(handle, call_status) {
+        return 0;
+    }
+    rust_call(call_status, || {
+        let segments = &*(handle as usize as *const SegmentQueue);
+        Ok(segments.len() as u64)
+    })
+}
+
+/// Take the next segment, as a buffer containing its raw bytes
+///
+/// This fails the call if all of the segments were already taken.
+///
+/// # Safety
+///
+/// `handle` must have been returned by a segmented scaffolding function and not yet freed.
+pub unsafe fn byte_segments_next(handle: u64, call_status: &mut RustCallStatus) -> RustBuffer {
+    if !check_handle(handle, call_status) {
+        return RustBuffer::ffi_default();
+    }
+    let segment = rust_call(call_status, || {
+        let segments = &mut *(handle as usize as *mut SegmentQueue);
+        Ok(segments.pop_front().map(RustBuffer::from_vec))
+    });
+    match segment {
+        Some(segment) => segment,
+        None => {
+            // `rust_call` already set the status if it failed.
+            if call_status.code == RustCallStatusCode::Success {
+                *call_status = RustCallStatus::error("no byte segments left");
+            }
+            RustBuffer::ffi_default()
+        }
+    }
+}
+
+/// Free a list of byte segments, dropping any segments that weren't returned yet
+///
+/// # Safety
+///
+/// `handle` must not have been passed to Rust or freed already.
+pub unsafe fn byte_segments_free(handle: u64, call_status: &mut RustCallStatus) {
+    if !check_handle(handle, call_status) {
+        return;
+    }
+    rust_call(call_status, || {
+        drop(Box::from_raw(handle as usize as *mut SegmentQueue));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::UniFfiTag;
+
+    #[test]
+    fn test_segments_argument() {
+        let inputs: [&[u8]; 4] = [b"first", b"", b"second", &[0, 255]];
+        let mut call_status = RustCallStatus::default();
+        let handle = byte_segments_new(&mut call_status);
+        for input in inputs {
+            let segment =
+                unsafe { ForeignBytes::from_raw_parts(input.as_ptr(), input.len() as i32) };
+            unsafe { byte_segments_push(handle, segment, &mut call_status) };
+        }
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+
+        let segments = <ByteSegments as Lift<UniFfiTag>>::try_lift(handle).unwrap();
+        assert_eq!(segments.len(), 4);
+        for (segment, input) in segments.iter().zip(inputs) {
+            assert_eq!(segment, input);
+        }
+        assert!(<ByteSegments as Lift<UniFfiTag>>::try_lift(0).is_err());
+    }
+
+    #[test]
+    fn test_segments_return_value() {
+        let segments = vec![b"first".to_vec(), vec![], b"second".to_vec()];
+        let first_pointer = segments[0].as_ptr();
+        let handle = <ByteSegments as LowerReturn<UniFfiTag>>::lower_return(segments.into())
+            .unwrap_or_else(|_| panic!("lower_return failed"));
+
+        let mut call_status = RustCallStatus::default();
+        assert_eq!(unsafe { byte_segments_len(handle, &mut call_status) }, 3);
+        let first = unsafe { byte_segments_next(handle, &mut call_status) };
+        // Segments are handed over without copying them.
+        assert_eq!(first.data_pointer(), first_pointer);
+        assert_eq!(first.destroy_into_vec(), b"first");
+        assert_eq!(unsafe { byte_segments_len(handle, &mut call_status) }, 2);
+        // Freeing the handle drops the remaining segments.
+        unsafe { byte_segments_free(handle, &mut call_status) };
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+    }
+
+    fn error_message(call_status: RustCallStatus) -> String {
+        assert_eq!(call_status.code, RustCallStatusCode::UnexpectedError);
+        let error_buf = unsafe { call_status.error_buf.assume_init() };
+        <String as Lift<UniFfiTag>>::try_lift(error_buf).unwrap()
+    }
+
+    #[test]
+    fn test_segments_invalid_calls() {
+        // Null handles fail the call, rather than being dereferenced.
+        let mut call_status = RustCallStatus::default();
+        unsafe { byte_segments_len(0, &mut call_status) };
+        assert_eq!(error_message(call_status), "null byte segments handle");
+        let mut call_status = RustCallStatus::default();
+        unsafe { byte_segments_free(0, &mut call_status) };
+        assert_eq!(error_message(call_status), "null byte segments handle");
+
+        // So does reading past the last segment.
+        let handle =
+            <ByteSegments as LowerReturn<UniFfiTag>>::lower_return(vec![b"only".to_vec()].into())
+                .unwrap_or_else(|_| panic!("lower_return failed"));
+        let mut call_status = RustCallStatus::default();
+        unsafe { byte_segments_next(handle, &mut call_status) }.destroy();
+        let buf = unsafe { byte_segments_next(handle, &mut call_status) };
+        assert!(buf.is_empty());
+        assert_eq!(error_message(call_status), "no byte segments left");
+        let mut call_status = RustCallStatus::default();
+        unsafe { byte_segments_free(handle, &mut call_status) };
+        assert_eq!(call_status.code, RustCallStatusCode::Success);
+    }
+}
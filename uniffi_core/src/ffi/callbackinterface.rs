@@ -134,6 +134,9 @@ pub enum CallbackResult {
     /// Unexpected error.
     /// An error message string is serialized to `buf_ptr`.
     UnexpectedError = 2,
+    /// The handle isn't in the foreign handle map, because it's already been freed.
+    /// Nothing is serialized to `buf_ptr`.
+    InterfaceGone = 3,
 }
 
 impl TryFrom<i32> for CallbackResult {
@@ -145,6 +148,7 @@ impl TryFrom<i32> for CallbackResult {
             0 => Ok(Self::Success),
             1 => Ok(Self::Error),
             2 => Ok(Self::UnexpectedError),
+            3 => Ok(Self::InterfaceGone),
             n => Err(n),
         }
     }
@@ -167,7 +171,29 @@ impl ForeignCallbackInternals {
     }
 
     /// Invoke a callback interface method on the foreign side and return the result
+    ///
+    /// If the handle has already been freed, this is reported like any other unexpected
+    /// callback error: callback methods that return a `Result` get an `Err` converted from
+    /// [UnexpectedUniFFICallbackError], other methods panic.
     pub fn invoke_callback<R, UniFfiTag>(&self, handle: u64, method: u32, args: RustBuffer) -> R
+    where
+        R: LiftReturn<UniFfiTag>,
+    {
+        self.try_invoke_callback(handle, method, args)
+            .unwrap_or_else(|e| R::handle_callback_unexpected_error(e.into()))
+    }
+
+    /// Invoke a callback interface method on the foreign side, returning an error if the
+    /// foreign side reports that the handle has already been freed.
+    ///
+    /// The generated code only calls [Self::invoke_callback], which has no typed way to report
+    /// this error, so it's passed on as an [UnexpectedUniFFICallbackError] there.
+    fn try_invoke_callback<R, UniFfiTag>(
+        &self,
+        handle: u64,
+        method: u32,
+        args: RustBuffer,
+    ) -> Result<R, CallbackInterfaceGone>
     where
         R: LiftReturn<UniFfiTag>,
     {
@@ -184,7 +210,7 @@ impl ForeignCallbackInternals {
         };
         let result = CallbackResult::try_from(raw_result)
             .unwrap_or_else(|code| panic!("Callback failed with unexpected return code: {code}"));
-        match result {
+        Ok(match result {
             CallbackResult::Success => R::lift_callback_return(ret_rbuf),
             CallbackResult::Error => R::lift_callback_error(ret_rbuf),
             CallbackResult::UnexpectedError => {
@@ -202,7 +228,39 @@ impl ForeignCallbackInternals {
                 };
                 R::handle_callback_unexpected_error(UnexpectedUniFFICallbackError { reason })
             }
-        }
+            CallbackResult::InterfaceGone => {
+                RustBuffer::destroy(ret_rbuf);
+                return Err(CallbackInterfaceGone { handle, method });
+            }
+        })
+    }
+}
+
+/// Returned when a callback interface method is invoked on a handle that has already been freed.
+///
+/// Callback methods only see this as the reason of an [UnexpectedUniFFICallbackError], so only
+/// methods that return a `Result` can handle it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CallbackInterfaceGone {
+    pub handle: u64,
+    pub method: u32,
+}
+
+impl fmt::Display for CallbackInterfaceGone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "CallbackInterfaceGone(handle: {}, method: {})",
+            self.handle, self.method
+        )
+    }
+}
+
+impl std::error::Error for CallbackInterfaceGone {}
+
+impl From<CallbackInterfaceGone> for UnexpectedUniFFICallbackError {
+    fn from(e: CallbackInterfaceGone) -> Self {
+        Self::from_reason(e.to_string())
     }
 }
 
@@ -306,3 +364,60 @@ macro_rules! convert_unexpected_error {
             .try_convert_unexpected_callback_error($error)
     }};
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct TestTag;
+
+    // The only handle in the test's foreign handle map, until it's freed.
+    const LIVE_HANDLE: u64 = 42;
+    static FREED: AtomicU32 = AtomicU32::new(0);
+    static CALLS: AtomicU32 = AtomicU32::new(0);
+
+    unsafe extern "C" fn test_callback(
+        handle: u64,
+        method: u32,
+        _args_data: *const u8,
+        _args_len: i32,
+        _buf_ptr: *mut RustBuffer,
+    ) -> i32 {
+        // Like the foreign handle maps, a freed handle is missing from the map.
+        if handle != LIVE_HANDLE || FREED.load(Ordering::SeqCst) != 0 {
+            return CallbackResult::InterfaceGone as i32;
+        }
+        if method == IDX_CALLBACK_FREE {
+            FREED.fetch_add(1, Ordering::SeqCst);
+        } else {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+        }
+        CallbackResult::Success as i32
+    }
+
+    #[test]
+    fn test_invoke_after_drop() {
+        let internals = ForeignCallbackInternals::new();
+        internals.set_callback(test_callback);
+
+        internals
+            .try_invoke_callback::<(), TestTag>(LIVE_HANDLE, 1, RustBuffer::new())
+            .unwrap();
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        // Drop the callback
+        internals.invoke_callback::<(), TestTag>(LIVE_HANDLE, IDX_CALLBACK_FREE, RustBuffer::new());
+        assert_eq!(FREED.load(Ordering::SeqCst), 1);
+
+        // Invoking the freed handle returns a clean error
+        assert_eq!(
+            internals.try_invoke_callback::<(), TestTag>(LIVE_HANDLE, 1, RustBuffer::new()),
+            Err(CallbackInterfaceGone {
+                handle: LIVE_HANDLE,
+                method: 1
+            }),
+        );
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+}
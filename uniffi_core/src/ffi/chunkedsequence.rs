@@ -0,0 +1,137 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Chunked sequence transfer
+//!
+//! Returning a `Vec<T>` normally serializes all of it into a single [RustBuffer].  For very large
+//! sequences, functions can opt into returning them in chunks instead, which bounds how much of
+//! the sequence is serialized at any one time.
+//!
+//! ## Protocol
+//!
+//! - The scaffolding function wraps the returned `Vec<T>` in a [ChunkedSequence] and returns a
+//!   `u64` handle to it instead of a [RustBuffer].
+//! - The foreign code repeatedly calls [chunked_sequence_next], which returns the next
+//!   [sequence_chunk_size] items serialized the same way as a `Vec<T>`.  It lifts each chunk with
+//!   the sequence's regular FFI converter and appends the items to its result.
+//! - An empty chunk means that the sequence is exhausted.
+//! - The foreign code then calls [chunked_sequence_free] to release the handle.  It must also do
+//!   this if lifting a chunk fails, which drops the remaining items.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{metadata, rust_call, Lower, LowerReturn, MetadataBuffer, RustBuffer, RustCallStatus};
+
+/// The default value for [sequence_chunk_size].
+pub const DEFAULT_SEQUENCE_CHUNK_SIZE: usize = 1024;
+
+static SEQUENCE_CHUNK_SIZE: AtomicUsize = AtomicUsize::new(DEFAULT_SEQUENCE_CHUNK_SIZE);
+
+/// The number of items in each chunk of a chunked sequence.
+pub fn sequence_chunk_size() -> usize {
+    SEQUENCE_CHUNK_SIZE.load(Ordering::Relaxed)
+}
+
+/// Set the number of items in each chunk of a chunked sequence.
+///
+/// This applies to sequences returned after the call.  Sizes below 1 are treated as 1.
+pub fn set_sequence_chunk_size(size: usize) {
+    SEQUENCE_CHUNK_SIZE.store(size.max(1), Ordering::Relaxed)
+}
+
+/// A sequence returned to the foreign code in chunks
+///
+/// This is used by the scaffolding code for functions marked `[Chunked]` in the UDL.
+pub struct ChunkedSequence<T>(Vec<T>);
+
+impl<T> ChunkedSequence<T> {
+    pub fn new(items: Vec<T>) -> Self {
+        Self(items)
+    }
+}
+
+/// Type-erased source of serialized chunks, which is what handles point to.
+type ChunkSource = Box<dyn FnMut() -> RustBuffer + Send>;
+
+unsafe impl<UT, T> LowerReturn<UT> for ChunkedSequence<T>
+where
+    T: Lower<UT> + Send + 'static,
+{
+    type ReturnType = u64;
+
+    fn lower_return(obj: Self) -> Result<Self::ReturnType, RustBuffer> {
+        let chunk_size = sequence_chunk_size();
+        let mut items = obj.0.into_iter();
+        let source: ChunkSource = Box::new(move || {
+            let chunk: Vec<T> = items.by_ref().take(chunk_size).collect();
+            <Vec<T> as Lower<UT>>::lower(chunk)
+        });
+        Ok(Box::into_raw(Box::new(source)) as usize as u64)
+    }
+
+    // The chunks are lowered by later calls, so all the items are checked up front.
+    fn check_return(obj: &Self) -> crate::Result<()> {
+        obj.0.iter().try_for_each(T::check_lower)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_VEC).concat(T::TYPE_ID_META);
+}
+
+/// Get the next chunk of a chunked sequence
+///
+/// The chunk is serialized like a `Vec<T>`, and is empty once the sequence is exhausted.
+///
+/// # Safety
+///
+/// `handle` must have been returned by a chunked scaffolding function and not yet freed.
+pub unsafe fn chunked_sequence_next(handle: u64, call_status: &mut RustCallStatus) -> RustBuffer {
+    rust_call(call_status, || {
+        let source = &mut *(handle as usize as *mut ChunkSource);
+        Ok(source())
+    })
+}
+
+/// Free a chunked sequence, dropping any items that weren't returned yet
+///
+/// # Safety
+///
+/// `handle` must have been returned by a chunked scaffolding function and not yet freed.
+pub unsafe fn chunked_sequence_free(handle: u64, call_status: &mut RustCallStatus) {
+    rust_call(call_status, || {
+        drop(Box::from_raw(handle as usize as *mut ChunkSource));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, UniFfiTag};
+
+    fn next_chunk(handle: u64) -> Vec<u32> {
+        let mut call_status = RustCallStatus::default();
+        let buf = unsafe { chunked_sequence_next(handle, &mut call_status) };
+        <Vec<u32> as Lift<UniFfiTag>>::try_lift(buf).unwrap()
+    }
+
+    #[test]
+    fn test_chunks_reassemble_to_the_sequence() {
+        let items: Vec<u32> = (0..10).collect();
+        set_sequence_chunk_size(4);
+        let handle = <ChunkedSequence<u32> as LowerReturn<UniFfiTag>>::lower_return(
+            ChunkedSequence::new(items.clone()),
+        )
+        .unwrap_or_else(|_| panic!("lower_return failed"));
+        set_sequence_chunk_size(DEFAULT_SEQUENCE_CHUNK_SIZE);
+
+        assert_eq!(next_chunk(handle), vec![0, 1, 2, 3]);
+        assert_eq!(next_chunk(handle), vec![4, 5, 6, 7]);
+        assert_eq!(next_chunk(handle), vec![8, 9]);
+        assert_eq!(next_chunk(handle), Vec::<u32>::new());
+
+        let mut call_status = RustCallStatus::default();
+        unsafe { chunked_sequence_free(handle, &mut call_status) };
+    }
+}
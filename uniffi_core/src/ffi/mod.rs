@@ -4,18 +4,28 @@
 
 //! Types that can cross the FFI boundary.
 
+pub mod buffergrowth;
+pub mod bytesegments;
 pub mod callbackinterface;
+pub mod chunkedsequence;
 pub mod ffidefault;
 pub mod foreignbytes;
 pub mod foreigncallbacks;
+pub mod outparams;
 pub mod rustbuffer;
 pub mod rustcalls;
 pub mod rustfuture;
+pub mod withwarnings;
 
+pub use buffergrowth::*;
+pub use bytesegments::*;
 pub use callbackinterface::*;
+pub use chunkedsequence::*;
 pub use ffidefault::FfiDefault;
 pub use foreignbytes::*;
 pub use foreigncallbacks::*;
+pub use outparams::*;
 pub use rustbuffer::*;
 pub use rustcalls::*;
 pub use rustfuture::*;
+pub use withwarnings::*;
@@ -0,0 +1,96 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Out-parameters
+//!
+//! Exported functions can take `&mut T` arguments as out-parameters.  The foreign code passes the
+//! initial value as usual, and the scaffolding function returns an [OutParams] with the
+//! function's return value serialized into a [RustBuffer], followed by the final value of each
+//! out-parameter.  Functions which return `()` only serialize the out-parameters.  The bindings
+//! lift all of them and return them together.
+
+use std::marker::PhantomData;
+
+use crate::{LowerReturn, MetadataBuffer, RustBuffer};
+
+/// The serialized return value and out-parameters of a function, where `T` is the return type.
+///
+/// This is generated by `#[uniffi::export]` for functions with `&mut T` arguments.
+#[derive(Debug, Clone)]
+pub struct OutParams<T> {
+    buf: Vec<u8>,
+    rejected: Option<String>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> OutParams<T> {
+    /// Wrap a buffer with the serialized return value, followed by the out-parameters.
+    pub fn new(buf: Vec<u8>) -> Self {
+        Self {
+            buf,
+            rejected: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The return value or an out-parameter can't be sent over the FFI, see
+    /// [crate::Lower::check_lower].  The call fails with an internal error instead.
+    pub fn rejected(e: anyhow::Error) -> Self {
+        Self {
+            buf: Vec::new(),
+            rejected: Some(e.to_string()),
+            _marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl<UT, T> LowerReturn<UT> for OutParams<T>
+where
+    T: LowerReturn<UT>,
+{
+    type ReturnType = RustBuffer;
+
+    fn lower_return(obj: Self) -> Result<Self::ReturnType, RustBuffer> {
+        Ok(RustBuffer::from_vec(obj.buf))
+    }
+
+    fn check_return(obj: &Self) -> crate::Result<()> {
+        match &obj.rejected {
+            Some(message) => anyhow::bail!("{message}"),
+            None => Ok(()),
+        }
+    }
+
+    // The metadata describes the return value, since the bindings know about the out-parameters
+    // from the function's arguments.
+    const TYPE_ID_META: MetadataBuffer = T::TYPE_ID_META;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, Lower, UniFfiTag};
+
+    #[test]
+    fn test_lower_out_params() {
+        let mut buf = Vec::new();
+        <String as Lower<UniFfiTag>>::write("value".to_string(), &mut buf);
+        <u32 as Lower<UniFfiTag>>::write(42, &mut buf);
+        let out_params = OutParams::<String>::new(buf);
+        let rbuf = <OutParams<String> as LowerReturn<UniFfiTag>>::lower_return(out_params).unwrap();
+        let bytes = rbuf.destroy_into_vec();
+        let mut reader = bytes.as_slice();
+        assert_eq!(
+            <String as Lift<UniFfiTag>>::try_read(&mut reader).unwrap(),
+            "value"
+        );
+        assert_eq!(<u32 as Lift<UniFfiTag>>::try_read(&mut reader).unwrap(), 42);
+        assert!(reader.is_empty());
+        // The metadata is the return value's.
+        assert_eq!(
+            <OutParams<String> as LowerReturn<UniFfiTag>>::TYPE_ID_META.as_ref(),
+            <String as LowerReturn<UniFfiTag>>::TYPE_ID_META.as_ref(),
+        );
+    }
+}
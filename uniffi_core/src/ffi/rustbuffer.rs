@@ -3,6 +3,12 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use crate::ffi::{rust_call, ForeignBytes, RustCallStatus};
+use anyhow::anyhow;
+use once_cell::sync::OnceCell;
+use std::{
+    cell::Cell,
+    sync::atomic::{AtomicU8, Ordering},
+};
 
 /// Support for passing an allocated-by-Rust buffer of bytes over the FFI.
 ///
@@ -131,7 +137,57 @@ impl RustBuffer {
             size < i32::MAX as usize,
             "RustBuffer requested size too large"
         );
-        Self::from_vec(vec![0u8; size])
+        match RUSTBUFFER_ALLOCATOR.get() {
+            Some(_) if size == 0 => Self::new(),
+            Some(allocator) => {
+                let data = alloc_custom(*allocator, size);
+                unsafe {
+                    std::ptr::write_bytes(data, 0, size);
+                    Self::from_raw_parts(data, size as i32, size as i32)
+                }
+            }
+            None => Self::from_vec(vec![0u8; size]),
+        }
+    }
+
+    /// Creates a `RustBuffer` from the bytes which `write` appends to a `Vec<u8>`.
+    ///
+    /// `size_hint` is passed to the [crate::BufferGrowth] policy to allocate that `Vec<u8>`.  With
+    /// a custom [RustBufferAllocator], the bytes are written into a reused per-thread buffer and
+    /// then copied into storage from the custom allocator, so the global allocator isn't used for
+    /// each buffer.
+    ///
+    /// The resulting vector will not be automatically dropped; you must
+    /// arrange to call `destroy` or `destroy_into_vec` when finished with it.
+    pub fn from_writer(size_hint: usize, write: impl FnOnce(&mut Vec<u8>)) -> Self {
+        let allocator = match RUSTBUFFER_ALLOCATOR.get() {
+            Some(allocator) => *allocator,
+            None => {
+                let mut buf = crate::buffer_growth().new_buffer(size_hint);
+                write(&mut buf);
+                return Self::from_vec(buf);
+            }
+        };
+        // Take the buffer out of the thread local, so that a nested call gets its own buffer.
+        let mut scratch = SCRATCH_BUFFER.with(Cell::take);
+        scratch.clear();
+        scratch.reserve(size_hint);
+        write(&mut scratch);
+        let rbuf = if scratch.is_empty() {
+            Self::new()
+        } else {
+            let len = i32::try_from(scratch.len()).expect("buffer length cannot fit into a i32.");
+            let data = alloc_custom(allocator, scratch.len());
+            unsafe {
+                std::ptr::copy_nonoverlapping(scratch.as_ptr(), data, scratch.len());
+                Self::from_raw_parts(data, len, len)
+            }
+        };
+        // Don't hold on to the storage of unusually large values.
+        if scratch.capacity() <= MAX_SCRATCH_CAPACITY {
+            SCRATCH_BUFFER.with(|cell| cell.set(scratch));
+        }
+        rbuf
     }
 
     /// Consumes a `Vec<u8>` and returns its raw parts as a `RustBuffer`.
@@ -146,8 +202,29 @@ impl RustBuffer {
     pub fn from_vec(v: Vec<u8>) -> Self {
         let capacity = i32::try_from(v.capacity()).expect("buffer capacity cannot fit into a i32.");
         let len = i32::try_from(v.len()).expect("buffer length cannot fit into a i32.");
-        let mut v = std::mem::ManuallyDrop::new(v);
-        unsafe { Self::from_raw_parts(v.as_mut_ptr(), len, capacity) }
+        // Empty buffers don't own any storage, so they don't decide which allocator is used.
+        let allocator = if v.capacity() == 0 {
+            RUSTBUFFER_ALLOCATOR.get().copied()
+        } else {
+            allocator_for_new_buffer()
+        };
+        match allocator {
+            Some(_) if v.capacity() == 0 => unsafe {
+                Self::from_raw_parts(std::ptr::null_mut(), 0, 0)
+            },
+            Some(allocator) => {
+                // Copy the data into storage from the custom allocator, `v` is dropped as usual.
+                let data = alloc_custom(allocator, v.capacity());
+                unsafe {
+                    std::ptr::copy_nonoverlapping(v.as_ptr(), data, v.len());
+                    Self::from_raw_parts(data, len, capacity)
+                }
+            }
+            None => {
+                let mut v = std::mem::ManuallyDrop::new(v);
+                unsafe { Self::from_raw_parts(v.as_mut_ptr(), len, capacity) }
+            }
+        }
     }
 
     /// Converts this `RustBuffer` back into an owned `Vec<u8>`.
@@ -161,24 +238,112 @@ impl RustBuffer {
     /// Panics if called on an invalid struct obtained from foreign-language code,
     /// which does not respect the invairiants on `len` and `capacity`.
     pub fn destroy_into_vec(self) -> Vec<u8> {
+        let (len, capacity) = match self.checked_len_and_capacity() {
+            Some(len_and_capacity) => len_and_capacity,
+            None => return vec![],
+        };
+        match RUSTBUFFER_ALLOCATOR.get() {
+            // Buffers without any capacity don't own any storage.
+            Some(_) if capacity == 0 => vec![],
+            Some(allocator) => {
+                // Copy the data out, then return the storage to the custom allocator.
+                let v = unsafe { std::slice::from_raw_parts(self.data, len) }.to_vec();
+                unsafe { allocator.free(self.data, capacity) };
+                v
+            }
+            None => unsafe { Vec::from_raw_parts(self.data, len, capacity) },
+        }
+    }
+
+    /// Reads the contents of this `RustBuffer` with `f`, then reclaims its memory.
+    ///
+    /// Unlike [Self::destroy_into_vec], this never copies the contents, even with a custom
+    /// [RustBufferAllocator].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on an invalid struct obtained from foreign-language code,
+    /// which does not respect the invairiants on `len` and `capacity`.
+    pub fn destroy_with<R>(self, f: impl FnOnce(&[u8]) -> R) -> R {
+        let (len, capacity) = match self.checked_len_and_capacity() {
+            Some(len_and_capacity) => len_and_capacity,
+            None => return f(&[]),
+        };
+        match RUSTBUFFER_ALLOCATOR.get() {
+            Some(_) if capacity == 0 => f(&[]),
+            Some(allocator) => {
+                // Free the storage even if `f` panics.
+                struct FreeOnDrop<'a>(&'a dyn RustBufferAllocator, *mut u8, usize);
+                impl Drop for FreeOnDrop<'_> {
+                    fn drop(&mut self) {
+                        unsafe { self.0.free(self.1, self.2) }
+                    }
+                }
+                let _free = FreeOnDrop(*allocator, self.data, capacity);
+                f(unsafe { std::slice::from_raw_parts(self.data, len) })
+            }
+            None => f(&unsafe { Vec::from_raw_parts(self.data, len, capacity) }),
+        }
+    }
+
+    /// Grows this `RustBuffer` so that it has room for at least `additional` more bytes.
+    ///
+    /// Like `Vec::reserve`, this may reserve more capacity than requested.  With a custom
+    /// [RustBufferAllocator], the new storage is allocated from it directly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on an invalid struct obtained from foreign-language code,
+    /// which does not respect the invairiants on `len` and `capacity`.
+    pub fn reserve(self, additional: usize) -> Self {
+        let allocator = match RUSTBUFFER_ALLOCATOR.get() {
+            Some(allocator) => *allocator,
+            None => {
+                let mut v = self.destroy_into_vec();
+                v.reserve(additional);
+                return Self::from_vec(v);
+            }
+        };
+        let (len, capacity) = self.checked_len_and_capacity().unwrap_or((0, 0));
+        let required = len
+            .checked_add(additional)
+            .expect("RustBuffer requested size too large");
+        if required <= capacity {
+            return self;
+        }
+        // Grow like `Vec` does, so that repeated small reservations don't copy every time.
+        let new_capacity = required.max(capacity * 2);
+        let new_capacity_i32 =
+            i32::try_from(new_capacity).expect("buffer capacity cannot fit into a i32.");
+        let data = alloc_custom(allocator, new_capacity);
+        unsafe {
+            if capacity > 0 {
+                std::ptr::copy_nonoverlapping(self.data, data, len);
+                allocator.free(self.data, capacity);
+            }
+            Self::from_raw_parts(data, len as i32, new_capacity_i32)
+        }
+    }
+
+    // Check the invariants on `len` and `capacity`, returning `None` for a null buffer.
+    fn checked_len_and_capacity(&self) -> Option<(usize, usize)> {
         // Rust will never give us a null `data` pointer for a `Vec`, but
         // foreign-language code can use it to cheaply pass an empty buffer.
         if self.data.is_null() {
             assert!(self.capacity == 0, "null RustBuffer had non-zero capacity");
             assert!(self.len == 0, "null RustBuffer had non-zero length");
-            vec![]
-        } else {
-            let capacity: usize = self
-                .capacity
-                .try_into()
-                .expect("buffer capacity negative or overflowed");
-            let len: usize = self
-                .len
-                .try_into()
-                .expect("buffer length negative or overflowed");
-            assert!(len <= capacity, "RustBuffer length exceeds capacity");
-            unsafe { Vec::from_raw_parts(self.data, len, capacity) }
+            return None;
         }
+        let capacity: usize = self
+            .capacity
+            .try_into()
+            .expect("buffer capacity negative or overflowed");
+        let len: usize = self
+            .len
+            .try_into()
+            .expect("buffer length negative or overflowed");
+        assert!(len <= capacity, "RustBuffer length exceeds capacity");
+        Some((len, capacity))
     }
 
     /// Reclaim memory stored in this `RustBuffer`.
@@ -188,7 +353,7 @@ impl RustBuffer {
     /// Panics if called on an invalid struct obtained from foreign-language code,
     /// which does not respect the invairiants on `len` and `capacity`.
     pub fn destroy(self) {
-        drop(self.destroy_into_vec());
+        self.destroy_with(|_| ());
     }
 }
 
@@ -198,6 +363,132 @@ impl Default for RustBuffer {
     }
 }
 
+/// A custom allocator for the storage backing `RustBuffer`s.
+///
+/// By default `RustBuffer`s are backed by the storage of a `Vec<u8>`, from the global allocator.
+/// Installing one of these with [set_rustbuffer_allocator] routes that storage through it
+/// instead, for example to allocate from an arena or a shared pool.
+///
+/// Stable Rust has no way to build a `Vec<u8>` on a custom allocator, so buffers are allocated from
+/// it directly where possible:
+///
+/// - [RustBuffer::new_with_size] and [RustBuffer::reserve], which back the `rustbuffer_alloc` and
+///   `rustbuffer_reserve` functions used by the foreign code, allocate and grow buffers in it.
+/// - Lowering writes values into a reused per-thread `Vec<u8>` with [RustBuffer::from_writer], and
+///   copies the bytes into a buffer of the exact size from the custom allocator.
+/// - Lifting reads buffers in place with [RustBuffer::destroy_with].
+///
+/// [RustBuffer::from_vec] and [RustBuffer::destroy_into_vec] still copy the bytes into and out of
+/// the custom allocator, since they take and return a `Vec<u8>` from the global allocator.  Every
+/// buffer is freed by the allocator which allocated it.
+///
+/// # Thread safety and reentrancy
+///
+/// `alloc` and `free` are called from whichever thread creates or destroys a buffer, which can be
+/// any thread making a Rust call, so they may be called concurrently.  They must not call back into
+/// UniFFI code which might create or destroy a `RustBuffer`, since that would re-enter the
+/// allocator.
+///
+/// # Safety
+///
+/// `alloc(capacity)` must return a pointer to `capacity` writable bytes, or null if the allocation
+/// failed.  That storage must stay valid until it's passed to `free`.
+pub unsafe trait RustBufferAllocator: Send + Sync {
+    /// Allocate storage for a buffer of `capacity` bytes, which is never 0.
+    fn alloc(&self, capacity: usize) -> *mut u8;
+
+    /// Free storage previously returned by `alloc(capacity)`.
+    ///
+    /// # Safety
+    ///
+    /// `data` must have been returned by `alloc(capacity)` and not freed yet.
+    unsafe fn free(&self, data: *mut u8, capacity: usize);
+}
+
+static RUSTBUFFER_ALLOCATOR: OnceCell<&'static dyn RustBufferAllocator> = OnceCell::new();
+
+// Allocate non-empty storage from a custom allocator.
+fn alloc_custom(allocator: &dyn RustBufferAllocator, capacity: usize) -> *mut u8 {
+    let data = allocator.alloc(capacity);
+    assert!(!data.is_null(), "RustBuffer allocation failed");
+    data
+}
+
+thread_local! {
+    // Values are lowered into this before being copied into storage from a custom allocator.
+    static SCRATCH_BUFFER: Cell<Vec<u8>> = const { Cell::new(Vec::new()) };
+}
+
+// The scratch buffer is dropped rather than kept if it grows beyond this.
+const MAX_SCRATCH_CAPACITY: usize = 64 * 1024;
+
+// Which allocator backs non-empty buffers.  This is decided by whichever comes first, the first
+// non-empty buffer or installing a custom allocator, so that a custom allocator is never asked to
+// free a buffer from the global allocator.  It's a single atomic so that the decision can't race.
+static ALLOCATOR_STATE: AtomicU8 = AtomicU8::new(ALLOCATOR_UNDECIDED);
+const ALLOCATOR_UNDECIDED: u8 = 0;
+const ALLOCATOR_DEFAULT: u8 = 1;
+// A custom allocator is being stored in `RUSTBUFFER_ALLOCATOR`.
+const ALLOCATOR_INSTALLING: u8 = 2;
+const ALLOCATOR_CUSTOM: u8 = 3;
+
+// Get the custom allocator for a new non-empty buffer, or `None` to use the global allocator.
+fn allocator_for_new_buffer() -> Option<&'static dyn RustBufferAllocator> {
+    loop {
+        match ALLOCATOR_STATE.compare_exchange(
+            ALLOCATOR_UNDECIDED,
+            ALLOCATOR_DEFAULT,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) | Err(ALLOCATOR_DEFAULT) => return None,
+            Err(ALLOCATOR_CUSTOM) => return RUSTBUFFER_ALLOCATOR.get().copied(),
+            // Wait for the allocator being installed, which only takes a moment.
+            Err(_) => std::hint::spin_loop(),
+        }
+    }
+}
+
+/// Install a custom allocator for the storage backing `RustBuffer`s.
+///
+/// This must be called before any non-empty `RustBuffer` is created, typically at startup before
+/// any Rust calls are made.  It fails if a buffer has already been allocated by the global
+/// allocator, or if a custom allocator was already installed.
+pub fn set_rustbuffer_allocator(allocator: &'static dyn RustBufferAllocator) -> crate::Result<()> {
+    if let Err(state) = ALLOCATOR_STATE.compare_exchange(
+        ALLOCATOR_UNDECIDED,
+        ALLOCATOR_INSTALLING,
+        Ordering::AcqRel,
+        Ordering::Acquire,
+    ) {
+        return Err(allocator_state_error(state));
+    }
+    RUSTBUFFER_ALLOCATOR
+        .set(allocator)
+        .map_err(|_| anyhow!("a RustBuffer allocator is already installed"))?;
+    ALLOCATOR_STATE.store(ALLOCATOR_CUSTOM, Ordering::Release);
+    Ok(())
+}
+
+/// Check that [set_rustbuffer_allocator] can install an allocator.
+///
+/// A buffer can still be allocated before the allocator is installed, so [set_rustbuffer_allocator]
+/// can fail even if this succeeds.
+pub(crate) fn check_rustbuffer_allocator() -> crate::Result<()> {
+    match ALLOCATOR_STATE.load(Ordering::Acquire) {
+        ALLOCATOR_UNDECIDED => Ok(()),
+        state => Err(allocator_state_error(state)),
+    }
+}
+
+fn allocator_state_error(state: u8) -> anyhow::Error {
+    if state == ALLOCATOR_DEFAULT {
+        anyhow!("a RustBuffer was already allocated with the default allocator")
+    } else {
+        anyhow!("a RustBuffer allocator is already installed")
+    }
+}
+
 // extern "C" functions for the RustBuffer functionality.
 //
 // These are used in two ways:
@@ -335,9 +626,7 @@ fn _uniffi_rustbuffer_reserve(
         let additional: usize = additional
             .try_into()
             .expect("additional buffer length negative or overflowed");
-        let mut v = buf.destroy_into_vec();
-        v.reserve(additional);
-        Ok(RustBuffer::from_vec(v))
+        Ok(buf.reserve(additional))
     })
 }
 
@@ -10,10 +10,15 @@
 //!    - Catching panics
 //!    - Adapting the result of `Return::lower_return()` into either a return value or an
 //!      exception
+//!    - Tracking exported functions that re-enter Rust on the same thread, see [ReentrancyPolicy]
+//!    - Replacing error payloads larger than [max_error_len]
+//!    - Failing calls with an internal error without panicking, see [RustCallError]
 
 use crate::{FfiDefault, Lower, RustBuffer, UniFfiTag};
+use std::cell::Cell;
 use std::mem::MaybeUninit;
 use std::panic;
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
 
 /// Represents the success/error of a rust call
 ///
@@ -27,6 +32,12 @@ use std::panic;
 /// - After the call, if `code` is [RustCallStatusCode::Error] or [RustCallStatusCode::UnexpectedError]
 ///   then `error_buf` will be updated to contain a serialized error object.   See
 ///   [RustCallStatusCode] for what gets serialized. The consumer is responsible for freeing `error_buf`.
+/// - If `code` is [RustCallStatusCode::Cancelled], then the async call was cancelled.  This is
+///   distinct from an error, so the bindings raise their language's cancellation exception.
+/// - If `code` is [RustCallStatusCode::WrongThread], then `error_buf` contains a serialized
+///   message, which the bindings raise as a `WrongThread` error.
+/// - If `code` is [RustCallStatusCode::StatePoisoned], then `error_buf` contains a serialized
+///   message, which the bindings raise as a `StatePoisoned` error.
 ///
 /// ## Layout/fields
 ///
@@ -69,6 +80,20 @@ impl RustCallStatus {
             error_buf: MaybeUninit::new(<String as Lower<UniFfiTag>>::lower(message.into())),
         }
     }
+
+    pub fn wrong_thread(message: impl Into<String>) -> Self {
+        Self {
+            code: RustCallStatusCode::WrongThread,
+            error_buf: MaybeUninit::new(<String as Lower<UniFfiTag>>::lower(message.into())),
+        }
+    }
+
+    pub fn state_poisoned(message: impl Into<String>) -> Self {
+        Self {
+            code: RustCallStatusCode::StatePoisoned,
+            error_buf: MaybeUninit::new(<String as Lower<UniFfiTag>>::lower(message.into())),
+        }
+    }
 }
 
 impl Default for RustCallStatus {
@@ -97,6 +122,35 @@ pub enum RustCallStatusCode {
     /// This is only returned for async functions and only if the bindings code uses the
     /// [rust_future_cancel] call.
     Cancelled = 3,
+    /// A method of a thread-bound object was called on another thread, see
+    /// [crate::check_bound_thread].  [RustCallStatus::error_buf] will contain a serialized
+    /// message string.
+    WrongThread = 4,
+    /// A method of an `[OnPoison=error]` interface panicked on state poisoned by an earlier call,
+    /// see [crate::call_with_on_poison].  [RustCallStatus::error_buf] will contain a serialized
+    /// message string.
+    StatePoisoned = 5,
+}
+
+/// Why a Rust call failed, other than by panicking.
+///
+/// Calls to exported functions return this from their callback, see
+/// [rust_call_checking_reentrancy].  It's usually created by `LowerReturn::try_lower_return`.
+#[derive(Debug)]
+pub enum RustCallError {
+    /// An error that should be thrown as an exception in the foreign code, serialized into a
+    /// `RustBuffer`.
+    Error(RustBuffer),
+    /// An internal error with the given message, which the foreign code sees just like a panic.
+    ///
+    /// Unlike a panic this doesn't run the panic hook, so it's safe to use with `panic = "abort"`.
+    InternalError(String),
+}
+
+impl From<RustBuffer> for RustCallError {
+    fn from(buf: RustBuffer) -> Self {
+        Self::Error(buf)
+    }
 }
 
 /// Handle a scaffolding calls
@@ -112,6 +166,8 @@ pub enum RustCallStatusCode {
 ///     - `out_status.code` will be set to [RustCallStatusCode::Error].
 ///     - `out_status.error_buf` will be set to a newly allocated `RustBuffer` containing the error.  The calling
 ///       code is responsible for freeing the `RustBuffer`
+///     - If the serialized error is larger than [max_error_len], it's replaced by an
+///       [RustCallStatusCode::UnexpectedError] with a message saying so.
 ///     - `FfiDefault::ffi_default()` is returned, although foreign code should ignore this value
 /// - If the function panics:
 ///     - `out_status.code` will be set to `CALL_PANIC`
@@ -126,29 +182,173 @@ where
     rust_call_with_out_status(out_status, callback).unwrap_or_else(R::ffi_default)
 }
 
+/// Handle a call to an exported function, method or constructor
+///
+/// This works like [rust_call], except that `callback` returns a [RustCallError], so that it can
+/// fail with an internal error for return values which `LowerReturn::check_return` rejects.
+///
+/// It also tracks calls which re-enter Rust on the same thread.  If that's denied by the
+/// [ReentrancyPolicy]:
+///     - `out_status.code` will be set to [RustCallStatusCode::UnexpectedError].
+///     - `out_status.error_buf` will be set to a newly allocated `RustBuffer` containing a
+///       serialized error message.  The calling code is responsible for freeing the `RustBuffer`
+///     - `FfiDefault::ffi_default()` is returned, although foreign code should ignore this value
+///
+/// The other scaffolding calls, like the ones which allocate a `RustBuffer` or free an object,
+/// don't run code that could take a lock held by the outer call, so they use [rust_call].
+pub fn rust_call_checking_reentrancy<F, R>(out_status: &mut RustCallStatus, callback: F) -> R
+where
+    F: panic::UnwindSafe + FnOnce() -> Result<R, RustCallError>,
+    R: FfiDefault,
+{
+    let _guard = match CallDepthGuard::enter() {
+        Ok(guard) => guard,
+        Err(message) => {
+            *out_status = RustCallStatus::error(message);
+            return R::ffi_default();
+        }
+    };
+    rust_call_with_out_status(out_status, callback).unwrap_or_else(R::ffi_default)
+}
+
+/// How a Rust call that re-enters Rust on the same thread is handled.
+///
+/// This happens when an exported function calls a foreign callback which calls another exported
+/// function before it returns.  That's fine unless both calls need the same lock, in which case
+/// the thread deadlocks, so these policies can help find where that's happening.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ReentrancyPolicy {
+    /// Allow re-entrant calls.
+    #[default]
+    Allow,
+    /// Allow re-entrant calls, but log an error for each of them.  If the thread then deadlocks,
+    /// the last error shows the call which re-entered Rust.
+    Detect,
+    /// Fail re-entrant calls, which the foreign code sees as an internal error.
+    Deny,
+}
+
+static REENTRANCY_POLICY: AtomicU8 = AtomicU8::new(ReentrancyPolicy::Allow as u8);
+
+thread_local! {
+    // How many calls to exported functions are currently in progress on this thread.
+    static CALL_DEPTH: Cell<usize> = Cell::new(0);
+}
+
+/// How calls that re-enter Rust on the same thread are currently handled.
+pub fn reentrancy_policy() -> ReentrancyPolicy {
+    match REENTRANCY_POLICY.load(Ordering::Relaxed) {
+        x if x == ReentrancyPolicy::Detect as u8 => ReentrancyPolicy::Detect,
+        x if x == ReentrancyPolicy::Deny as u8 => ReentrancyPolicy::Deny,
+        _ => ReentrancyPolicy::Allow,
+    }
+}
+
+/// Set how calls that re-enter Rust on the same thread are handled.
+///
+/// This applies to synchronous calls to exported functions, methods and constructors.
+pub fn set_reentrancy_policy(policy: ReentrancyPolicy) {
+    REENTRANCY_POLICY.store(policy as u8, Ordering::Relaxed)
+}
+
+/// Tracks the number of exported functions in progress on the current thread.
+///
+/// The depth is decremented again when it's dropped, including when the call panics.
+struct CallDepthGuard {
+    _private: (),
+}
+
+impl CallDepthGuard {
+    /// Enter a Rust call, failing with an error message if that's denied by the
+    /// [ReentrancyPolicy].
+    fn enter() -> Result<Self, String> {
+        let depth = CALL_DEPTH.with(|d| d.get()) + 1;
+        if depth > 1 {
+            match reentrancy_policy() {
+                ReentrancyPolicy::Allow => (),
+                ReentrancyPolicy::Detect => {
+                    log::error!("Re-entrant Rust call on the same thread (call depth {depth})");
+                }
+                ReentrancyPolicy::Deny => {
+                    return Err(format!(
+                        "Re-entrant Rust call on the same thread denied by the re-entrancy policy (call depth {depth})"
+                    ));
+                }
+            }
+        }
+        CALL_DEPTH.with(|d| d.set(depth));
+        Ok(Self { _private: () })
+    }
+}
+
+impl Drop for CallDepthGuard {
+    fn drop(&mut self) {
+        CALL_DEPTH.with(|d| d.set(d.get() - 1));
+    }
+}
+
+/// The default value for [max_error_len] (1MiB).
+pub const DEFAULT_MAX_ERROR_LEN: usize = 1 << 20;
+
+static MAX_ERROR_LEN: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_ERROR_LEN);
+
+/// The maximum size of a serialized error that we'll return to the foreign code.
+pub fn max_error_len() -> usize {
+    MAX_ERROR_LEN.load(Ordering::Relaxed)
+}
+
+/// Set the maximum size of a serialized error that we'll return to the foreign code.
+///
+/// An error type with an enormous `Display` impl or fields could otherwise send a huge buffer
+/// over the FFI.  Larger errors are dropped and the foreign code sees an internal error instead,
+/// whose message gives the size of the error.
+pub fn set_max_error_len(len: usize) {
+    MAX_ERROR_LEN.store(len, Ordering::Relaxed)
+}
+
 /// Make a Rust call and update `RustCallStatus` based on the result.
 ///
 /// If the call succeeds this returns Some(v) and doesn't touch out_status
 /// If the call fails (including Err results), this returns None and updates out_status
 ///
 /// This contains the shared code between `rust_call` and `rustfuture::do_wake`.
-pub(crate) fn rust_call_with_out_status<F, R>(
+pub(crate) fn rust_call_with_out_status<F, R, E>(
     out_status: &mut RustCallStatus,
     callback: F,
 ) -> Option<R>
 where
-    F: panic::UnwindSafe + FnOnce() -> Result<R, RustBuffer>,
+    F: panic::UnwindSafe + FnOnce() -> Result<R, E>,
+    E: Into<RustCallError>,
 {
     let result = panic::catch_unwind(|| {
         crate::panichook::ensure_setup();
-        callback()
+        callback().map_err(Into::into)
     });
     match result {
         // Happy path.  Note: no need to update out_status in this case because the calling code
         // initializes it to [RustCallStatusCode::Success]
         Ok(Ok(v)) => Some(v),
+        // Callback failed with an internal error.
+        Ok(Err(RustCallError::InternalError(message))) => {
+            log::error!("Internal error calling rust code: {message}");
+            *out_status = RustCallStatus::error(message);
+            None
+        }
+        // Callback returned an Err, which is too large to return.
+        Ok(Err(RustCallError::Error(buf))) if buf.len() > max_error_len() => {
+            let message = format!(
+                "Error payload exceeds the maximum allowed ({} > {} bytes)",
+                buf.len(),
+                max_error_len()
+            );
+            buf.destroy();
+            log::error!("{message}");
+            *out_status = RustCallStatus::error(message);
+            None
+        }
         // Callback returned an Err.
-        Ok(Err(buf)) => {
+        Ok(Err(RustCallError::Error(buf))) => {
             out_status.code = RustCallStatusCode::Error;
             unsafe {
                 // Unsafe because we're setting the `MaybeUninit` value, see above for safety
@@ -157,6 +357,13 @@ where
             }
             None
         }
+        // Callback panicked on poisoned state
+        Err(cause) if cause.is::<crate::StatePoisoned>() => {
+            // The panic hook recorded the location of the original panic.
+            crate::panichook::take_panic_location();
+            *out_status = RustCallStatus::state_poisoned(crate::StatePoisoned.to_string());
+            None
+        }
         // Callback panicked
         Err(cause) => {
             out_status.code = RustCallStatusCode::UnexpectedError;
@@ -171,7 +378,12 @@ where
                 } else {
                     "Unknown panic!".to_string()
                 };
+                let message = match crate::panichook::take_panic_location() {
+                    Some(location) => format!("{message} (at {location})"),
+                    None => message,
+                };
                 log::error!("Caught a panic calling rust code: {:?}", message);
+                crate::panichook::report_panic(&message);
                 <String as Lower<UniFfiTag>>::lower(message)
             }));
             if let Ok(buf) = message_result {
@@ -78,6 +78,7 @@
 use std::{
     future::Future,
     marker::PhantomData,
+    mem::MaybeUninit,
     ops::Deref,
     panic,
     pin::Pin,
@@ -86,7 +87,7 @@ use std::{
 };
 
 use super::{RustFutureContinuationCallback, RustFuturePoll, Scheduler};
-use crate::{rust_call_with_out_status, FfiDefault, LowerReturn, RustCallStatus};
+use crate::{rust_call_with_out_status, FfiDefault, LowerReturn, RustBuffer, RustCallStatus};
 
 /// Wraps the actual future we're polling
 struct WrappedFuture<F, T, UT>
@@ -128,8 +129,13 @@ where
             //    - We never move RustFuture, which contains us.
             //    - RustFuture is private to this module so no other code can move it.
             let pinned = unsafe { Pin::new_unchecked(future) };
-            // Run the poll and lift the result if it's ready
-            let mut out_status = RustCallStatus::default();
+            // Run the poll and lift the result if it's ready.  The error buffer starts out empty,
+            // so it's still valid to read if the future panics and the panic message can't be
+            // lowered.
+            let mut out_status = RustCallStatus {
+                error_buf: MaybeUninit::new(RustBuffer::new()),
+                ..RustCallStatus::default()
+            };
             let result: Option<Poll<T::ReturnType>> = rust_call_with_out_status(
                 &mut out_status,
                 // This closure uses a `&mut F` value, which means it's not UnwindSafe by
@@ -139,7 +145,7 @@ where
                 // case below and we will never poll the future again.
                 panic::AssertUnwindSafe(|| match pinned.poll(context) {
                     Poll::Pending => Ok(Poll::Pending),
-                    Poll::Ready(v) => T::lower_return(v).map(Poll::Ready),
+                    Poll::Ready(v) => T::try_lower_return(v).map(Poll::Ready),
                 }),
             );
             match result {
@@ -174,7 +180,12 @@ where
 
     fn free(&mut self) {
         self.future = None;
-        self.result = None;
+        // If the future is freed before it's completed, for example because the foreign task was
+        // cancelled, nothing else will free the serialized error.
+        if let Some(Err(call_status)) = self.result.take() {
+            // SAFETY: `poll()` always initializes the error buffer.
+            unsafe { call_status.error_buf.assume_init() }.destroy();
+        }
     }
 }
 
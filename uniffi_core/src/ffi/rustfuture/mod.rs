@@ -5,8 +5,11 @@
 use std::{future::Future, sync::Arc};
 
 mod future;
+mod runtime;
 mod scheduler;
 use future::*;
+pub(crate) use runtime::check_async_runtime;
+pub use runtime::{set_async_runtime, AsyncRuntime, BoxedTask};
 use scheduler::*;
 
 #[cfg(test)]
@@ -55,7 +58,21 @@ where
 {
     // Create a RustFuture and coerce to `Arc<dyn RustFutureFfi>`, which is what we use to
     // implement the FFI
-    let future_ffi = RustFuture::new(future, tag) as Arc<dyn RustFutureFfi<T::ReturnType>>;
+    let future_ffi = match runtime::async_runtime() {
+        // If a runtime was registered, spawn the future on it and have the RustFuture wait for the
+        // result.  If the spawned task panics then the sender is dropped, and the panic from
+        // `expect` is reported to the foreign code as an internal error.
+        Some(runtime) => {
+            let (sender, receiver) = oneshot::channel();
+            runtime.spawn(Box::pin(async move {
+                // The receiver is gone if the future was freed before the task completed.
+                let _ = sender.send(future.await);
+            }));
+            let received = async move { receiver.await.expect("async runtime task failed") };
+            RustFuture::new(received, tag) as Arc<dyn RustFutureFfi<T::ReturnType>>
+        }
+        None => RustFuture::new(future, tag) as Arc<dyn RustFutureFfi<T::ReturnType>>,
+    };
     // Box the Arc, to convert the wide pointer into a normal sized pointer so that we can pass it
     // to the foreign code.
     let boxed_ffi = Box::new(future_ffi);
@@ -0,0 +1,72 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Optional Rust runtime for driving exported async functions.
+//!
+//! By default the foreign code polls each [super::RustFuture] to completion itself, so there's no
+//! need for a Rust executor.  Some futures need to run inside a specific runtime though, for
+//! example because they use tokio's IO types.  Registering an [AsyncRuntime] with
+//! [set_async_runtime] spawns every future created by [super::rust_future_new] on that runtime
+//! instead.  The foreign code still polls the [super::RustFuture], which now just waits for the
+//! spawned task to send its result.
+
+use std::{future::Future, pin::Pin};
+
+use anyhow::{anyhow, bail};
+use once_cell::sync::OnceCell;
+
+use crate::Result;
+
+/// A future that's been boxed so that it can be passed to [AsyncRuntime::spawn].
+pub type BoxedTask = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// A Rust runtime that exported async functions can be spawned on.
+///
+/// This is implemented for any `Fn(BoxedTask)`, so a tokio runtime can be registered with
+/// `uniffi::set_async_runtime(move |task| { handle.spawn(task); })`.
+pub trait AsyncRuntime: Send + Sync + 'static {
+    /// Spawn a task, running it to completion in the background.
+    ///
+    /// The task sends its result back to the foreign code, so the runtime doesn't need to
+    /// keep track of it.
+    fn spawn(&self, task: BoxedTask);
+}
+
+impl<F> AsyncRuntime for F
+where
+    F: Fn(BoxedTask) + Send + Sync + 'static,
+{
+    fn spawn(&self, task: BoxedTask) {
+        self(task)
+    }
+}
+
+static ASYNC_RUNTIME: OnceCell<Box<dyn AsyncRuntime>> = OnceCell::new();
+
+/// The runtime that was registered with [set_async_runtime], if any.
+pub(super) fn async_runtime() -> Option<&'static dyn AsyncRuntime> {
+    ASYNC_RUNTIME.get().map(|runtime| runtime.as_ref())
+}
+
+/// Register the runtime that exported async functions are spawned on.
+///
+/// This should be called once at startup, before any async functions are called.  Futures
+/// created before it's called are still polled by the foreign code.  The runtime can only be
+/// registered once, so later calls return an error.
+///
+/// When an async function is cancelled by the foreign code, the task spawned on the runtime
+/// is detached rather than dropped and keeps running until it completes.
+pub fn set_async_runtime(runtime: impl AsyncRuntime) -> Result<()> {
+    ASYNC_RUNTIME
+        .set(Box::new(runtime))
+        .map_err(|_| anyhow!("The async runtime has already been set"))
+}
+
+/// Check that [set_async_runtime] can register a runtime.
+pub(crate) fn check_async_runtime() -> Result<()> {
+    if ASYNC_RUNTIME.get().is_some() {
+        bail!("The async runtime has already been set");
+    }
+    Ok(())
+}
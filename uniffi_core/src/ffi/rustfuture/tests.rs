@@ -137,6 +137,37 @@ fn test_error() {
     }
 }
 
+// A panic is reported as an unexpected error, with the panic message
+#[test]
+fn test_panic() {
+    let future = std::future::poll_fn(|_| -> Poll<Result<String, TestError>> { panic!("Boom") });
+    let rust_future: Arc<dyn RustFutureFfi<RustBuffer>> = RustFuture::new(future, crate::UniFfiTag);
+    let continuation_result = poll(&rust_future);
+    assert_eq!(continuation_result.get(), Some(&RustFuturePoll::Ready));
+
+    let (_, call_status) = complete(rust_future);
+    assert_eq!(call_status.code, RustCallStatusCode::UnexpectedError);
+    let message = <String as Lift<crate::UniFfiTag>>::try_lift(unsafe {
+        call_status.error_buf.assume_init()
+    })
+    .unwrap();
+    assert!(message.starts_with("Boom"), "{message}");
+}
+
+// Freeing a future which finished with an error, without completing it, releases the error
+#[test]
+fn test_free_without_complete() {
+    let (sender, rust_future) = channel();
+    sender.send(Err("Something went wrong".into()));
+    let continuation_result = poll(&rust_future);
+    assert_eq!(continuation_result.get(), Some(&RustFuturePoll::Ready));
+
+    rust_future.clone().ffi_free();
+    // There's nothing left to complete
+    let (_, call_status) = complete(rust_future);
+    assert_eq!(call_status.code, RustCallStatusCode::Cancelled);
+}
+
 // Once `complete` is called, the inner future should be released, even if wakers still hold a
 // reference to the RustFuture
 #[test]
@@ -221,3 +252,67 @@ fn test_wake_during_poll() {
         "All done"
     );
 }
+
+/// Run a future to completion on the current thread, for the test runtime below.
+fn block_on(future: BoxedTask) {
+    struct ThreadWaker(std::thread::Thread);
+
+    impl std::task::Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark()
+        }
+    }
+
+    let mut future = future;
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut context = Context::from_waker(&waker);
+    while future.as_mut().poll(&mut context).is_pending() {
+        std::thread::park();
+    }
+}
+
+async fn current_thread_name() -> String {
+    std::thread::current().name().unwrap_or_default().to_owned()
+}
+
+extern "C" fn channel_continuation(data: *const (), code: RustFuturePoll) {
+    let sender = unsafe { Box::from_raw(data as *mut std::sync::mpsc::Sender<RustFuturePoll>) };
+    sender.send(code).unwrap();
+}
+
+// This is the only test that creates futures with `rust_future_new`, so registering a runtime
+// doesn't affect the others.
+#[test]
+fn test_async_runtime() {
+    set_async_runtime(|task| {
+        std::thread::Builder::new()
+            .name("test-runtime".to_owned())
+            .spawn(move || block_on(task))
+            .unwrap();
+    })
+    .unwrap();
+    assert!(set_async_runtime(|_| ()).is_err());
+
+    let handle = rust_future_new(current_thread_name(), crate::UniFfiTag);
+    // Poll the future like the foreign code would, until it's ready
+    loop {
+        let (sender, receiver) = std::sync::mpsc::channel::<RustFuturePoll>();
+        let data = Box::into_raw(Box::new(sender)) as *const ();
+        unsafe {
+            rust_future_poll::<RustBuffer>(RustFutureHandle(handle.0), channel_continuation, data)
+        };
+        if receiver.recv().unwrap() == RustFuturePoll::Ready {
+            break;
+        }
+    }
+    let mut call_status = RustCallStatus::default();
+    let return_buf =
+        unsafe { rust_future_complete::<RustBuffer>(RustFutureHandle(handle.0), &mut call_status) };
+    unsafe { rust_future_free::<RustBuffer>(handle) };
+    assert_eq!(call_status.code, RustCallStatusCode::Success);
+    // The future ran on the registered runtime rather than the polling thread
+    assert_eq!(
+        <String as Lift<crate::UniFfiTag>>::try_lift(return_buf).unwrap(),
+        "test-runtime"
+    );
+}
@@ -0,0 +1,87 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Values returned with warnings
+//!
+//! Functions marked `[WithWarnings]` in the UDL return a [WithWarnings], for operations which
+//! succeed but have non-fatal warnings to report.  The scaffolding function returns a
+//! [RustBuffer] with the value serialized as usual, followed by the warnings serialized as a
+//! `Vec<String>`.  The bindings lift both into their own `WithWarnings` type, so the foreign code
+//! can surface the warnings without treating them as errors.
+
+use crate::{Lower, LowerReturn, MetadataBuffer, RustBuffer};
+
+/// A value returned along with a list of non-fatal warnings
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WithWarnings<T> {
+    pub value: T,
+    pub warnings: Vec<String>,
+}
+
+impl<T> WithWarnings<T> {
+    /// A value without any warnings
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Add a warning to the value
+    pub fn warn(mut self, warning: impl Into<String>) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+}
+
+impl<T> From<T> for WithWarnings<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+unsafe impl<UT, T> LowerReturn<UT> for WithWarnings<T>
+where
+    T: Lower<UT>,
+    String: Lower<UT>,
+{
+    type ReturnType = RustBuffer;
+
+    fn lower_return(obj: Self) -> Result<Self::ReturnType, RustBuffer> {
+        let mut buf = Vec::new();
+        T::write(obj.value, &mut buf);
+        <Vec<String> as Lower<UT>>::write(obj.warnings, &mut buf);
+        Ok(RustBuffer::from_vec(buf))
+    }
+
+    fn check_return(obj: &Self) -> crate::Result<()> {
+        T::check_lower(&obj.value)
+    }
+
+    // The metadata describes the value, since the bindings know about the warnings from the
+    // function's `[WithWarnings]` attribute.
+    const TYPE_ID_META: MetadataBuffer = T::TYPE_ID_META;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, UniFfiTag};
+
+    #[test]
+    fn test_lower_with_warnings() {
+        let value = WithWarnings::new(42u32)
+            .warn("first warning")
+            .warn("second warning");
+        let buf = <WithWarnings<u32> as LowerReturn<UniFfiTag>>::lower_return(value).unwrap();
+        let bytes = buf.destroy_into_vec();
+        let mut reader = bytes.as_slice();
+        assert_eq!(<u32 as Lift<UniFfiTag>>::try_read(&mut reader).unwrap(), 42);
+        assert_eq!(
+            <Vec<String> as Lift<UniFfiTag>>::try_read(&mut reader).unwrap(),
+            ["first warning", "second warning"]
+        );
+        assert!(reader.is_empty());
+    }
+}
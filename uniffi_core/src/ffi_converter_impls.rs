@@ -22,17 +22,26 @@
 /// consumer crates.  To do this, it defines blanket impls like `impl<UT> FFIConverter<UT> for u8`.
 /// "UT" means an arbitrary `UniFfiTag` type.
 use crate::{
-    check_remaining, derive_ffi_traits, ffi_converter_rust_buffer_lift_and_lower, metadata,
-    ConvertError, FfiConverter, Lift, LiftRef, LiftReturn, Lower, LowerReturn, MetadataBuffer,
-    Result, RustBuffer, UnexpectedUniFFICallbackError,
+    buffer_growth, check_float_special, check_max_buffer_len, check_remaining, derive_ffi_traits,
+    ffi_converter_rust_buffer_lift_and_lower, metadata, str_from_utf8, string_interner,
+    ConvertError, DynError, FfiConverter, FfiConverterArc, Lift, LiftRef, LiftReturn, Lower,
+    LowerReturn, MetadataBuffer, MonotonicDuration, OpaqueHandle, ReadDepthGuard, Result,
+    RustBuffer, UnexpectedUniFFICallbackError,
 };
 use anyhow::bail;
 use bytes::buf::{Buf, BufMut};
 use paste::paste;
 use std::{
+    borrow::Cow,
     collections::HashMap,
     convert::TryFrom,
     error::Error,
+    num::{
+        NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU16, NonZeroU32, NonZeroU64,
+        NonZeroU8,
+    },
+    ops::{Range, RangeInclusive},
+    rc::Rc,
     sync::Arc,
     time::{Duration, SystemTime},
 };
@@ -59,6 +68,12 @@ macro_rules! impl_ffi_converter_for_num_primitive {
                     buf.[<put_ $T>](obj);
                 }
 
+                fn size_hint(_obj: &$T) -> usize {
+                    std::mem::size_of::<$T>()
+                }
+
+                const FIXED_SIZE: Option<usize> = Some(std::mem::size_of::<$T>());
+
                 fn try_read(buf: &mut &[u8]) -> Result<$T> {
                     check_remaining(buf, std::mem::size_of::<$T>())?;
                     Ok(buf.[<get_ $T>]())
@@ -78,8 +93,102 @@ impl_ffi_converter_for_num_primitive!(u32, metadata::codes::TYPE_U32);
 impl_ffi_converter_for_num_primitive!(i32, metadata::codes::TYPE_I32);
 impl_ffi_converter_for_num_primitive!(u64, metadata::codes::TYPE_U64);
 impl_ffi_converter_for_num_primitive!(i64, metadata::codes::TYPE_I64);
-impl_ffi_converter_for_num_primitive!(f32, metadata::codes::TYPE_F32);
-impl_ffi_converter_for_num_primitive!(f64, metadata::codes::TYPE_F64);
+
+/// Implementation of `FfiConverter` for non-zero integers, e.g. `NonZeroU32`.
+///
+/// These are passed exactly like the integer they wrap, but lifting a zero value fails.
+macro_rules! impl_ffi_converter_for_non_zero {
+    ($T:ty, $inner:ty) => {
+        unsafe impl<UT> FfiConverter<UT> for $T {
+            type FfiType = $inner;
+
+            fn lower(obj: $T) -> Self::FfiType {
+                obj.get()
+            }
+
+            fn try_lift(v: Self::FfiType) -> Result<$T> {
+                match <$T>::new(v) {
+                    Some(v) => Ok(v),
+                    None => bail!("zero value for {}", stringify!($T)),
+                }
+            }
+
+            fn write(obj: $T, buf: &mut Vec<u8>) {
+                <$inner as FfiConverter<UT>>::write(obj.get(), buf);
+            }
+
+            fn size_hint(_obj: &$T) -> usize {
+                std::mem::size_of::<$inner>()
+            }
+
+            const FIXED_SIZE: Option<usize> = Some(std::mem::size_of::<$inner>());
+
+            fn try_read(buf: &mut &[u8]) -> Result<$T> {
+                <Self as FfiConverter<UT>>::try_lift(<$inner as FfiConverter<UT>>::try_read(buf)?)
+            }
+
+            const TYPE_ID_META: MetadataBuffer =
+                MetadataBuffer::from_code(metadata::codes::TYPE_NON_ZERO)
+                    .concat(<$inner as FfiConverter<UT>>::TYPE_ID_META);
+        }
+    };
+}
+
+impl_ffi_converter_for_non_zero!(NonZeroU8, u8);
+impl_ffi_converter_for_non_zero!(NonZeroI8, i8);
+impl_ffi_converter_for_non_zero!(NonZeroU16, u16);
+impl_ffi_converter_for_non_zero!(NonZeroI16, i16);
+impl_ffi_converter_for_non_zero!(NonZeroU32, u32);
+impl_ffi_converter_for_non_zero!(NonZeroI32, i32);
+impl_ffi_converter_for_non_zero!(NonZeroU64, u64);
+impl_ffi_converter_for_non_zero!(NonZeroI64, i64);
+
+/// Implementation of `FfiConverter` for floats.
+///
+/// These are the same as the other numeric primitives, except that non-finite values are checked
+/// against [crate::float_special], when they're lifted and in [FfiConverter::check_lower].
+macro_rules! impl_ffi_converter_for_float_primitive {
+    ($T:ty, $type_code:expr) => {
+        paste! {
+            unsafe impl<UT> FfiConverter<UT> for $T {
+                type FfiType = $T;
+
+                fn lower(obj: $T) -> Self::FfiType {
+                    obj
+                }
+
+                fn try_lift(v: Self::FfiType) -> Result<$T> {
+                    check_float_special(v)?;
+                    Ok(v)
+                }
+
+                fn write(obj: $T, buf: &mut Vec<u8>) {
+                    buf.[<put_ $T>](<Self as FfiConverter<UT>>::lower(obj));
+                }
+
+                fn size_hint(_obj: &$T) -> usize {
+                    std::mem::size_of::<$T>()
+                }
+
+                const FIXED_SIZE: Option<usize> = Some(std::mem::size_of::<$T>());
+
+                fn check_lower(obj: &$T) -> Result<()> {
+                    check_float_special(*obj)
+                }
+
+                fn try_read(buf: &mut &[u8]) -> Result<$T> {
+                    check_remaining(buf, std::mem::size_of::<$T>())?;
+                    <Self as FfiConverter<UT>>::try_lift(buf.[<get_ $T>]())
+                }
+
+                const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code($type_code);
+            }
+        }
+    };
+}
+
+impl_ffi_converter_for_float_primitive!(f32, metadata::codes::TYPE_F32);
+impl_ffi_converter_for_float_primitive!(f64, metadata::codes::TYPE_F64);
 
 /// Support for passing boolean values via the FFI.
 ///
@@ -104,6 +213,12 @@ unsafe impl<UT> FfiConverter<UT> for bool {
         buf.put_i8(<Self as FfiConverter<UT>>::lower(obj));
     }
 
+    fn size_hint(_obj: &bool) -> usize {
+        1
+    }
+
+    const FIXED_SIZE: Option<usize> = Some(1);
+
     fn try_read(buf: &mut &[u8]) -> Result<bool> {
         check_remaining(buf, 1)?;
         <Self as FfiConverter<UT>>::try_lift(buf.get_i8())
@@ -153,6 +268,10 @@ unsafe impl<UT> FfiConverter<UT> for String {
         buf.put(obj.as_bytes());
     }
 
+    fn size_hint(obj: &String) -> usize {
+        4 + obj.len()
+    }
+
     fn try_read(buf: &mut &[u8]) -> Result<String> {
         check_remaining(buf, 4)?;
         let len = usize::try_from(buf.get_i32())?;
@@ -161,7 +280,7 @@ unsafe impl<UT> FfiConverter<UT> for String {
         // But in the specific case of `<&[u8] as Buf>` it returns the full slice,
         // so there is no risk of having less than `len` bytes available here.
         let bytes = &buf.chunk()[..len];
-        let res = String::from_utf8(bytes.to_vec())?;
+        let res = str_from_utf8(bytes)?.into_owned();
         buf.advance(len);
         Ok(res)
     }
@@ -169,6 +288,122 @@ unsafe impl<UT> FfiConverter<UT> for String {
     const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_STRING);
 }
 
+/// Support for returning `Cow<str>` via the FFI.
+///
+/// This is lowered exactly like a `String`, so the foreign code sees a normal string.  Owned
+/// strings are passed without copying, borrowed strings are copied into a new buffer.
+///
+/// Only lowering is supported, since a lifted string is always owned.
+unsafe impl<UT> Lower<UT> for Cow<'_, str> {
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Self) -> Self::FfiType {
+        <String as Lower<UT>>::lower(obj.into_owned())
+    }
+
+    fn write(obj: Self, buf: &mut Vec<u8>) {
+        // Write the borrowed bytes directly, rather than via an owned `String`.
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        buf.put(obj.as_bytes());
+    }
+
+    fn size_hint(obj: &Self) -> usize {
+        4 + obj.len()
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <String as Lower<UT>>::TYPE_ID_META;
+}
+
+unsafe impl<UT> LowerReturn<UT> for Cow<'_, str> {
+    type ReturnType = RustBuffer;
+
+    fn lower_return(obj: Self) -> Result<Self::ReturnType, RustBuffer> {
+        Ok(<Self as Lower<UT>>::lower(obj))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <String as Lower<UT>>::TYPE_ID_META;
+}
+
+/// Support for passing `Arc<str>` via the FFI.
+///
+/// This is passed exactly like a `String`, so the foreign code sees a normal string.  When string
+/// interning is enabled with [crate::with_string_interner], equal lifted strings share a single
+/// allocation.
+unsafe impl<UT> FfiConverterArc<UT> for str {
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Arc<Self>) -> Self::FfiType {
+        <String as Lower<UT>>::lower(obj.to_string())
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Arc<Self>> {
+        let s = <String as Lift<UT>>::try_lift(v)?;
+        Ok(string_interner::lift_str(&s))
+    }
+
+    fn write(obj: Arc<Self>, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        buf.put(obj.as_bytes());
+    }
+
+    fn size_hint(obj: &Arc<Self>) -> usize {
+        4 + obj.len()
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Arc<Self>> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        check_remaining(buf, len)?;
+        // Check the bytes in place, so that there's no allocation when the string is interned.
+        let res = string_interner::lift_str(&str_from_utf8(&buf.chunk()[..len])?);
+        buf.advance(len);
+        Ok(res)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <String as Lower<UT>>::TYPE_ID_META;
+}
+
+/// Support for passing `Arc<[u8]>` via the FFI.
+///
+/// This is passed exactly like a `Vec<u8>`, so the foreign code sees normal bytes.  Lifted values
+/// are built directly as an `Arc`, so they can be shared without copying the data again.
+unsafe impl<UT> FfiConverterArc<UT> for [u8] {
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Arc<Self>) -> Self::FfiType {
+        <Arc<Self> as Lower<UT>>::lower_into_rust_buffer(obj)
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Arc<Self>> {
+        <Arc<Self> as Lift<UT>>::try_lift_from_rust_buffer(v)
+    }
+
+    fn write(obj: Arc<Self>, buf: &mut Vec<u8>) {
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len);
+        buf.put(&obj[..]);
+    }
+
+    fn size_hint(obj: &Arc<Self>) -> usize {
+        4 + obj.len()
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Arc<Self>> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        check_max_buffer_len(len)?;
+        check_remaining(buf, len)?;
+        // Copy the bytes straight into the `Arc`, rather than via a `Vec`.
+        let res = Arc::from(&buf.chunk()[..len]);
+        buf.advance(len);
+        Ok(res)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Vec<u8> as Lower<UT>>::TYPE_ID_META;
+}
+
 /// Support for passing timestamp values via the FFI.
 ///
 /// Timestamps values are currently always passed by serializing to a buffer.
@@ -203,16 +438,25 @@ unsafe impl<UT> FfiConverter<UT> for SystemTime {
         buf.put_u32(epoch_offset.subsec_nanos());
     }
 
+    fn size_hint(_obj: &SystemTime) -> usize {
+        12
+    }
+
     fn try_read(buf: &mut &[u8]) -> Result<SystemTime> {
         check_remaining(buf, 12)?;
         let seconds = buf.get_i64();
         let nanos = buf.get_u32();
-        let epoch_offset = Duration::new(seconds.wrapping_abs() as u64, nanos);
+        check_nanos(nanos)?;
+        let epoch_offset = Duration::new(seconds.unsigned_abs(), nanos);
 
-        if seconds >= 0 {
-            Ok(SystemTime::UNIX_EPOCH + epoch_offset)
+        let time = if seconds >= 0 {
+            SystemTime::UNIX_EPOCH.checked_add(epoch_offset)
         } else {
-            Ok(SystemTime::UNIX_EPOCH - epoch_offset)
+            SystemTime::UNIX_EPOCH.checked_sub(epoch_offset)
+        };
+        match time {
+            Some(time) => Ok(time),
+            None => bail!("timestamp out of range ({seconds}s, {nanos}ns)"),
         }
     }
 
@@ -220,6 +464,15 @@ unsafe impl<UT> FfiConverter<UT> for SystemTime {
         MetadataBuffer::from_code(metadata::codes::TYPE_SYSTEM_TIME);
 }
 
+// `Duration::new()` panics if the nanoseconds overflow the seconds, so only accept the range that
+// `write()` produces.
+fn check_nanos(nanos: u32) -> Result<()> {
+    if nanos >= 1_000_000_000 {
+        bail!("nanoseconds out of range ({nanos})");
+    }
+    Ok(())
+}
+
 /// Support for passing duration values via the FFI.
 ///
 /// Duration values are currently always passed by serializing to a buffer.
@@ -236,14 +489,186 @@ unsafe impl<UT> FfiConverter<UT> for Duration {
         buf.put_u32(obj.subsec_nanos());
     }
 
+    fn size_hint(_obj: &Duration) -> usize {
+        12
+    }
+
     fn try_read(buf: &mut &[u8]) -> Result<Duration> {
         check_remaining(buf, 12)?;
-        Ok(Duration::new(buf.get_u64(), buf.get_u32()))
+        let seconds = buf.get_u64();
+        let nanos = buf.get_u32();
+        check_nanos(nanos)?;
+        Ok(Duration::new(seconds, nanos))
     }
 
     const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_DURATION);
 }
 
+/// Support for passing monotonic durations via the FFI.
+///
+/// These are passed exactly like a `Duration`, so the foreign code sees its normal duration type.
+unsafe impl<UT> FfiConverter<UT> for MonotonicDuration {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: MonotonicDuration, buf: &mut Vec<u8>) {
+        <Duration as FfiConverter<UT>>::write(obj.into(), buf)
+    }
+
+    fn size_hint(obj: &MonotonicDuration) -> usize {
+        <Duration as FfiConverter<UT>>::size_hint(&obj.as_duration())
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<MonotonicDuration> {
+        <Duration as FfiConverter<UT>>::try_read(buf).map(Into::into)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Duration as FfiConverter<UT>>::TYPE_ID_META;
+}
+
+// Ranges of durations are passed as their start and end, followed by whether the end is included.
+// The flag lets the lifting side check that it got the kind of range it expected.
+fn write_duration_range<UT>(start: Duration, end: Duration, inclusive: bool, buf: &mut Vec<u8>) {
+    <Duration as FfiConverter<UT>>::write(start, buf);
+    <Duration as FfiConverter<UT>>::write(end, buf);
+    <bool as FfiConverter<UT>>::write(inclusive, buf);
+}
+
+fn read_duration_range<UT>(buf: &mut &[u8], inclusive: bool) -> Result<(Duration, Duration)> {
+    let start = <Duration as FfiConverter<UT>>::try_read(buf)?;
+    let end = <Duration as FfiConverter<UT>>::try_read(buf)?;
+    if <bool as FfiConverter<UT>>::try_read(buf)? != inclusive {
+        match inclusive {
+            true => bail!("expected an inclusive range, but got an exclusive one"),
+            false => bail!("expected an exclusive range, but got an inclusive one"),
+        }
+    }
+    Ok((start, end))
+}
+
+/// Support for passing ranges of durations via the FFI.
+///
+/// Ranges are passed by serializing to a buffer, as the start and end durations followed by a
+/// `bool` which is `false` for `Range` and `true` for `RangeInclusive`.  Empty ranges, including
+/// ones whose start is after their end, are passed unchanged.
+unsafe impl<UT> FfiConverter<UT> for Range<Duration> {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: Range<Duration>, buf: &mut Vec<u8>) {
+        write_duration_range::<UT>(obj.start, obj.end, false, buf);
+    }
+
+    fn size_hint(_obj: &Range<Duration>) -> usize {
+        25
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Range<Duration>> {
+        let (start, end) = read_duration_range::<UT>(buf, false)?;
+        Ok(start..end)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_DURATION_RANGE);
+}
+
+unsafe impl<UT> FfiConverter<UT> for RangeInclusive<Duration> {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: RangeInclusive<Duration>, buf: &mut Vec<u8>) {
+        let (start, end) = obj.into_inner();
+        write_duration_range::<UT>(start, end, true, buf);
+    }
+
+    fn size_hint(_obj: &RangeInclusive<Duration>) -> usize {
+        25
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<RangeInclusive<Duration>> {
+        let (start, end) = read_duration_range::<UT>(buf, true)?;
+        Ok(start..=end)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_DURATION_RANGE_INCLUSIVE);
+}
+
+/// Support for passing opaque handles via the FFI.
+///
+/// These are passed as a `u64`, and are never interpreted.
+unsafe impl<UT> FfiConverter<UT> for OpaqueHandle {
+    type FfiType = u64;
+
+    fn lower(obj: OpaqueHandle) -> Self::FfiType {
+        obj.as_raw()
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<OpaqueHandle> {
+        Ok(OpaqueHandle::from_raw(v))
+    }
+
+    fn write(obj: OpaqueHandle, buf: &mut Vec<u8>) {
+        <u64 as FfiConverter<UT>>::write(obj.as_raw(), buf);
+    }
+
+    fn size_hint(_obj: &OpaqueHandle) -> usize {
+        std::mem::size_of::<u64>()
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<OpaqueHandle> {
+        <u64 as FfiConverter<UT>>::try_read(buf).map(OpaqueHandle::from_raw)
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_OPAQUE_HANDLE);
+}
+
+/// Support for passing file system paths via the FFI.
+///
+/// Paths are currently always passed by serializing to a buffer, using the same representation
+/// as a string: an i32 with the length in bytes, followed by the UTF-8 bytes.
+///
+/// Foreign strings are always valid UTF-8, but Rust paths don't have to be.  Lowering a path
+/// which isn't valid UTF-8 is lossy: each invalid sequence is replaced with U+FFFD, as
+/// [std::path::Path::to_string_lossy] does.
+#[cfg(feature = "path")]
+unsafe impl<UT> FfiConverter<UT> for std::path::PathBuf {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: std::path::PathBuf, buf: &mut Vec<u8>) {
+        <String as Lower<UT>>::write(obj.to_string_lossy().into_owned(), buf);
+    }
+
+    // This is exact unless the path has to be converted lossily.
+    fn size_hint(obj: &std::path::PathBuf) -> usize {
+        4 + obj.as_os_str().len()
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<std::path::PathBuf> {
+        Ok(<String as Lift<UT>>::try_read(buf)?.into())
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_PATH);
+}
+
+/// Support for passing arbitrary JSON values via the FFI.
+///
+/// JSON values are currently always passed by serializing to a buffer, using the same
+/// representation as a string containing the compact JSON text.  Lifting text which isn't valid
+/// JSON fails.
+#[cfg(feature = "json")]
+unsafe impl<UT> FfiConverter<UT> for serde_json::Value {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+
+    fn write(obj: serde_json::Value, buf: &mut Vec<u8>) {
+        <String as Lower<UT>>::write(obj.to_string(), buf);
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<serde_json::Value> {
+        Ok(serde_json::from_str(&<String as Lift<UT>>::try_read(buf)?)?)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_JSON);
+}
+
 // Support for passing optional values via the FFI.
 //
 // Optional values are currently always passed by serializing to a buffer.
@@ -267,6 +692,14 @@ unsafe impl<UT, T: Lower<UT>> Lower<UT> for Option<T> {
         }
     }
 
+    fn size_hint(obj: &Option<T>) -> usize {
+        1 + obj.as_ref().map_or(0, T::size_hint)
+    }
+
+    fn check_lower(obj: &Option<T>) -> Result<()> {
+        obj.as_ref().map_or(Ok(()), T::check_lower)
+    }
+
     fn lower(obj: Option<T>) -> RustBuffer {
         Self::lower_into_rust_buffer(obj)
     }
@@ -279,6 +712,7 @@ unsafe impl<UT, T: Lift<UT>> Lift<UT> for Option<T> {
     type FfiType = RustBuffer;
 
     fn try_read(buf: &mut &[u8]) -> Result<Option<T>> {
+        let _depth = ReadDepthGuard::enter()?;
         check_remaining(buf, 1)?;
         Ok(match buf.get_i8() {
             0 => None,
@@ -312,11 +746,29 @@ unsafe impl<UT, T: Lower<UT>> Lower<UT> for Vec<T> {
         // TODO: would be nice not to panic here :-/
         let len = i32::try_from(obj.len()).unwrap();
         buf.put_i32(len); // We limit arrays to i32::MAX items
+        if let Some(item_size) = <T as Lower<UT>>::FIXED_SIZE {
+            // The size of all the items is known up front, so the buffer only needs to grow once.
+            buf.reserve(item_size * obj.len());
+            for item in obj {
+                <T as Lower<UT>>::write(item, buf);
+            }
+            return;
+        }
+        let growth = buffer_growth();
         for item in obj {
+            growth.reserve_item(buf, || <T as Lower<UT>>::size_hint(&item));
             <T as Lower<UT>>::write(item, buf);
         }
     }
 
+    fn size_hint(obj: &Vec<T>) -> usize {
+        sequence_size_hint::<UT, T>(obj)
+    }
+
+    fn check_lower(obj: &Vec<T>) -> Result<()> {
+        obj.iter().try_for_each(<T as Lower<UT>>::check_lower)
+    }
+
     fn lower(obj: Vec<T>) -> RustBuffer {
         Self::lower_into_rust_buffer(obj)
     }
@@ -325,6 +777,94 @@ unsafe impl<UT, T: Lower<UT>> Lower<UT> for Vec<T> {
         MetadataBuffer::from_code(metadata::codes::TYPE_VEC).concat(T::TYPE_ID_META);
 }
 
+/// The size hint of a sequence, which is computed from its length if the items have a fixed size.
+fn sequence_size_hint<UT, T: Lower<UT>>(items: &[T]) -> usize {
+    match <T as Lower<UT>>::FIXED_SIZE {
+        Some(item_size) => 4 + item_size * items.len(),
+        None => 4 + items.iter().map(<T as Lower<UT>>::size_hint).sum::<usize>(),
+    }
+}
+
+/// Support for passing `Box<[T]>` via the FFI.
+///
+/// This is passed exactly like a `Vec<T>`, so the foreign code sees a normal sequence.  Converting
+/// between the two doesn't copy the items.
+unsafe impl<UT, T: Lower<UT>> Lower<UT> for Box<[T]> {
+    type FfiType = RustBuffer;
+
+    fn write(obj: Box<[T]>, buf: &mut Vec<u8>) {
+        <Vec<T> as Lower<UT>>::write(obj.into_vec(), buf)
+    }
+
+    fn size_hint(obj: &Box<[T]>) -> usize {
+        sequence_size_hint::<UT, T>(obj)
+    }
+
+    fn check_lower(obj: &Box<[T]>) -> Result<()> {
+        obj.iter().try_for_each(<T as Lower<UT>>::check_lower)
+    }
+
+    fn lower(obj: Box<[T]>) -> RustBuffer {
+        Self::lower_into_rust_buffer(obj)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Vec<T> as Lower<UT>>::TYPE_ID_META;
+}
+
+unsafe impl<UT, T: Lift<UT>> Lift<UT> for Box<[T]> {
+    type FfiType = RustBuffer;
+
+    fn try_read(buf: &mut &[u8]) -> Result<Box<[T]>> {
+        Ok(<Vec<T> as Lift<UT>>::try_read(buf)?.into_boxed_slice())
+    }
+
+    fn try_lift(buf: RustBuffer) -> Result<Box<[T]>> {
+        Self::try_lift_from_rust_buffer(buf)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Vec<T> as Lift<UT>>::TYPE_ID_META;
+}
+
+/// Support for passing `Rc<[T]>` via the FFI.
+///
+/// This is passed exactly like a `Vec<T>`.  The items may be shared with other `Rc`s, so lowering
+/// clones each of them.  Since `Rc` isn't `Send`, this can't be used with async functions.
+unsafe impl<UT, T: Lower<UT> + Clone> Lower<UT> for Rc<[T]> {
+    type FfiType = RustBuffer;
+
+    fn write(obj: Rc<[T]>, buf: &mut Vec<u8>) {
+        <Vec<T> as Lower<UT>>::write(obj.to_vec(), buf)
+    }
+
+    fn size_hint(obj: &Rc<[T]>) -> usize {
+        sequence_size_hint::<UT, T>(obj)
+    }
+
+    fn check_lower(obj: &Rc<[T]>) -> Result<()> {
+        obj.iter().try_for_each(<T as Lower<UT>>::check_lower)
+    }
+
+    fn lower(obj: Rc<[T]>) -> RustBuffer {
+        Self::lower_into_rust_buffer(obj)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Vec<T> as Lower<UT>>::TYPE_ID_META;
+}
+
+unsafe impl<UT, T: Lift<UT>> Lift<UT> for Rc<[T]> {
+    type FfiType = RustBuffer;
+
+    fn try_read(buf: &mut &[u8]) -> Result<Rc<[T]>> {
+        Ok(Rc::from(<Vec<T> as Lift<UT>>::try_read(buf)?))
+    }
+
+    fn try_lift(buf: RustBuffer) -> Result<Rc<[T]>> {
+        Self::try_lift_from_rust_buffer(buf)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Vec<T> as Lift<UT>>::TYPE_ID_META;
+}
+
 /// Support for associative arrays via the FFI - `record<u32, u64>` in UDL.
 /// HashMaps are currently always passed by serializing to a buffer.
 /// We write a `i32` entries count followed by each entry (string
@@ -334,9 +874,13 @@ unsafe impl<UT, T: Lift<UT>> Lift<UT> for Vec<T> {
     type FfiType = RustBuffer;
 
     fn try_read(buf: &mut &[u8]) -> Result<Vec<T>> {
+        let _depth = ReadDepthGuard::enter()?;
         check_remaining(buf, 4)?;
         let len = usize::try_from(buf.get_i32())?;
-        let mut vec = Vec::with_capacity(len);
+        check_max_buffer_len(len)?;
+        // The count comes from the foreign code, so don't reserve space for more items than the
+        // buffer could hold if each one took a single byte.
+        let mut vec = Vec::with_capacity(len.min(buf.remaining()));
         for _ in 0..len {
             vec.push(<T as Lift<UT>>::try_read(buf)?)
         }
@@ -362,12 +906,32 @@ where
         // TODO: would be nice not to panic here :-/
         let len = i32::try_from(obj.len()).unwrap();
         buf.put_i32(len); // We limit HashMaps to i32::MAX entries
+        let growth = buffer_growth();
         for (key, value) in obj {
+            growth.reserve_item(buf, || {
+                <K as Lower<UT>>::size_hint(&key) + <V as Lower<UT>>::size_hint(&value)
+            });
             <K as Lower<UT>>::write(key, buf);
             <V as Lower<UT>>::write(value, buf);
         }
     }
 
+    fn size_hint(obj: &HashMap<K, V>) -> usize {
+        4 + obj
+            .iter()
+            .map(|(key, value)| {
+                <K as Lower<UT>>::size_hint(key) + <V as Lower<UT>>::size_hint(value)
+            })
+            .sum::<usize>()
+    }
+
+    fn check_lower(obj: &HashMap<K, V>) -> Result<()> {
+        obj.iter().try_for_each(|(key, value)| {
+            <K as Lower<UT>>::check_lower(key)?;
+            <V as Lower<UT>>::check_lower(value)
+        })
+    }
+
     fn lower(obj: HashMap<K, V>) -> RustBuffer {
         Self::lower_into_rust_buffer(obj)
     }
@@ -385,9 +949,12 @@ where
     type FfiType = RustBuffer;
 
     fn try_read(buf: &mut &[u8]) -> Result<HashMap<K, V>> {
+        let _depth = ReadDepthGuard::enter()?;
         check_remaining(buf, 4)?;
         let len = usize::try_from(buf.get_i32())?;
-        let mut map = HashMap::with_capacity(len);
+        check_max_buffer_len(len)?;
+        // Like for `Vec`, don't trust the count when reserving space.
+        let mut map = HashMap::with_capacity(len.min(buf.remaining()));
         for _ in 0..len {
             let key = <K as Lift<UT>>::try_read(buf)?;
             let value = <V as Lift<UT>>::try_read(buf)?;
@@ -413,12 +980,28 @@ derive_ffi_traits!(blanket u32);
 derive_ffi_traits!(blanket i32);
 derive_ffi_traits!(blanket u64);
 derive_ffi_traits!(blanket i64);
+derive_ffi_traits!(blanket NonZeroU8);
+derive_ffi_traits!(blanket NonZeroI8);
+derive_ffi_traits!(blanket NonZeroU16);
+derive_ffi_traits!(blanket NonZeroI16);
+derive_ffi_traits!(blanket NonZeroU32);
+derive_ffi_traits!(blanket NonZeroI32);
+derive_ffi_traits!(blanket NonZeroU64);
+derive_ffi_traits!(blanket NonZeroI64);
 derive_ffi_traits!(blanket f32);
 derive_ffi_traits!(blanket f64);
 derive_ffi_traits!(blanket bool);
 derive_ffi_traits!(blanket String);
 derive_ffi_traits!(blanket Duration);
+derive_ffi_traits!(blanket MonotonicDuration);
+derive_ffi_traits!(blanket Range<Duration>);
+derive_ffi_traits!(blanket RangeInclusive<Duration>);
+derive_ffi_traits!(blanket OpaqueHandle);
 derive_ffi_traits!(blanket SystemTime);
+#[cfg(feature = "path")]
+derive_ffi_traits!(blanket std::path::PathBuf);
+#[cfg(feature = "json")]
+derive_ffi_traits!(blanket serde_json::Value);
 
 // For composite types, derive LowerReturn, LiftReturn, etc, from Lift/Lower.
 //
@@ -432,6 +1015,14 @@ derive_ffi_traits!(impl<T, UT> LowerReturn<UT> for Vec<T> where Vec<T>: Lower<UT
 derive_ffi_traits!(impl<T, UT> LiftReturn<UT> for Vec<T> where Vec<T>: Lift<UT>);
 derive_ffi_traits!(impl<T, UT> LiftRef<UT> for Vec<T> where Vec<T>: Lift<UT>);
 
+derive_ffi_traits!(impl<T, UT> LowerReturn<UT> for Box<[T]> where Box<[T]>: Lower<UT>);
+derive_ffi_traits!(impl<T, UT> LiftReturn<UT> for Box<[T]> where Box<[T]>: Lift<UT>);
+derive_ffi_traits!(impl<T, UT> LiftRef<UT> for Box<[T]> where Box<[T]>: Lift<UT>);
+
+derive_ffi_traits!(impl<T, UT> LowerReturn<UT> for Rc<[T]> where Rc<[T]>: Lower<UT>);
+derive_ffi_traits!(impl<T, UT> LiftReturn<UT> for Rc<[T]> where Rc<[T]>: Lift<UT>);
+derive_ffi_traits!(impl<T, UT> LiftRef<UT> for Rc<[T]> where Rc<[T]>: Lift<UT>);
+
 derive_ffi_traits!(impl<K, V, UT> LowerReturn<UT> for HashMap<K, V> where HashMap<K, V>: Lower<UT>);
 derive_ffi_traits!(impl<K, V, UT> LiftReturn<UT> for HashMap<K, V> where HashMap<K, V>: Lift<UT>);
 derive_ffi_traits!(impl<K, V, UT> LiftRef<UT> for HashMap<K, V> where HashMap<K, V>: Lift<UT>);
@@ -478,6 +1069,13 @@ where
         }
     }
 
+    fn check_return(v: &Self) -> Result<()> {
+        match v {
+            Ok(r) => R::check_return(r),
+            Err(e) => E::check_lower(e),
+        }
+    }
+
     fn handle_failed_lift(arg_name: &str, err: anyhow::Error) -> Self {
         match err.downcast::<E>() {
             Ok(actual_error) => Err(actual_error),
@@ -490,6 +1088,53 @@ where
         .concat(E::TYPE_ID_META);
 }
 
+// `DynError` can only be lowered, since it's only ever thrown by Rust code.  It's serialized as
+// the error message followed by the messages of its chain of sources.
+unsafe impl<UT> Lower<UT> for DynError {
+    type FfiType = RustBuffer;
+
+    fn lower(obj: DynError) -> RustBuffer {
+        <Self as Lower<UT>>::lower_into_rust_buffer(obj)
+    }
+
+    fn write(obj: DynError, buf: &mut Vec<u8>) {
+        <String as Lower<UT>>::write(obj.message(), buf);
+        <Vec<String> as Lower<UT>>::write(obj.cause_chain(), buf);
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_DYN_ERROR);
+}
+
+// `DynError` doesn't implement `Error`, so `Result<R, DynError>` needs its own impl.
+unsafe impl<UT, R> LowerReturn<UT> for Result<R, DynError>
+where
+    R: LowerReturn<UT>,
+{
+    type ReturnType = R::ReturnType;
+
+    fn lower_return(v: Self) -> Result<Self::ReturnType, RustBuffer> {
+        match v {
+            Ok(r) => R::lower_return(r),
+            Err(e) => Err(<DynError as Lower<UT>>::lower_into_rust_buffer(e)),
+        }
+    }
+
+    fn check_return(v: &Self) -> Result<()> {
+        v.as_ref().map_or(Ok(()), R::check_return)
+    }
+
+    // Any error can be thrown as a `DynError`, so there's no need to panic here.
+    fn handle_failed_lift(arg_name: &str, err: anyhow::Error) -> Self {
+        Err(err
+            .context(format!("Failed to convert arg '{arg_name}'"))
+            .into())
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_RESULT)
+        .concat(R::TYPE_ID_META)
+        .concat(<DynError as Lower<UT>>::TYPE_ID_META);
+}
+
 unsafe impl<UT, R, E> LiftReturn<UT> for Result<R, E>
 where
     R: LiftReturn<UT>,
@@ -529,3 +1174,8 @@ where
 unsafe impl<UT> LiftRef<UT> for str {
     type LiftType = String;
 }
+
+#[cfg(feature = "path")]
+unsafe impl<UT> LiftRef<UT> for std::path::Path {
+    type LiftType = std::path::PathBuf;
+}
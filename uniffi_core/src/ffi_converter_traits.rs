@@ -51,7 +51,10 @@ use std::{borrow::Borrow, sync::Arc};
 use anyhow::bail;
 use bytes::Buf;
 
-use crate::{FfiDefault, MetadataBuffer, Result, RustBuffer, UnexpectedUniFFICallbackError};
+use crate::{
+    check_max_buffer_len, FfiDefault, MetadataBuffer, Result, RustBuffer, RustCallError,
+    UnexpectedUniFFICallbackError,
+};
 
 /// Generalized FFI conversions
 ///
@@ -110,6 +113,30 @@ pub unsafe trait FfiConverter<UT>: Sized {
     /// to the foreign language code via the RustBuffer.
     fn write(obj: Self, buf: &mut Vec<u8>);
 
+    /// How many bytes [Self::write] writes for a value, used to pre-size the buffer it's written
+    /// into.
+    ///
+    /// This should be cheap to compute, and exact where possible, for example for records which
+    /// only contain fixed-width fields.  The default of 0 means that the size is unknown.
+    fn size_hint(_obj: &Self) -> usize {
+        0
+    }
+
+    /// How many bytes [Self::write] writes for every value, if that's the same for all of them.
+    ///
+    /// Sequences of these values reserve room for all of their items at once, rather than
+    /// growing the buffer item by item.  If this is set, [Self::size_hint] must return it.
+    const FIXED_SIZE: Option<usize> = None;
+
+    /// Check that a rust value can be sent over the FFI, before it's lowered or written.
+    ///
+    /// Lowering can't fail, so values which shouldn't cross the FFI, like non-finite floats under
+    /// [crate::FloatSpecial::Error], are rejected here instead.  Types that contain other values
+    /// should check each of them.  The default accepts every value.
+    fn check_lower(_obj: &Self) -> Result<()> {
+        Ok(())
+    }
+
     /// Read a rust value from a buffer, received over the FFI in serialized form.
     ///
     /// This trait method can be used for receiving data from the foreign language code in rust,
@@ -152,6 +179,14 @@ pub unsafe trait FfiConverterArc<UT>: Send + Sync {
     fn lower(obj: Arc<Self>) -> Self::FfiType;
     fn try_lift(v: Self::FfiType) -> Result<Arc<Self>>;
     fn write(obj: Arc<Self>, buf: &mut Vec<u8>);
+    fn size_hint(_obj: &Arc<Self>) -> usize {
+        0
+    }
+    /// See [FfiConverter::check_lower].  This takes the object rather than the `Arc`, so that
+    /// objects returned by value can be checked too.
+    fn check_lower(_obj: &Self) -> Result<()> {
+        Ok(())
+    }
     fn try_read(buf: &mut &[u8]) -> Result<Arc<Self>>;
 
     const TYPE_ID_META: MetadataBuffer;
@@ -175,6 +210,14 @@ where
         T::write(obj, buf)
     }
 
+    fn size_hint(obj: &Self) -> usize {
+        T::size_hint(obj)
+    }
+
+    fn check_lower(obj: &Self) -> Result<()> {
+        T::check_lower(obj)
+    }
+
     fn try_read(buf: &mut &[u8]) -> Result<Self> {
         T::try_read(buf)
     }
@@ -206,18 +249,34 @@ pub unsafe trait Lift<UT>: Sized {
 
     /// Convenience method
     fn try_lift_from_rust_buffer(v: RustBuffer) -> Result<Self> {
-        let vec = v.destroy_into_vec();
-        let mut buf = vec.as_slice();
-        let value = Self::try_read(&mut buf)?;
-        match Buf::remaining(&buf) {
-            0 => Ok(value),
-            n => bail!("junk data left in buffer after lifting (count: {n})",),
-        }
+        v.destroy_with(try_read_all)
+    }
+
+    /// Lift a value from arbitrary bytes, in the format of a `RustBuffer`'s contents.
+    ///
+    /// This is intended for fuzzing the lift code: any input returns either the value or an
+    /// error, without panicking or undefined behaviour.  Types which contain object or callback
+    /// interface handles always fail, since a handle from arbitrary bytes can't be valid.
+    #[cfg(feature = "fuzzing")]
+    fn try_lift_from_bytes(bytes: &[u8]) -> Result<Self> {
+        let _guard = crate::LiftingFromBytesGuard::enter();
+        try_read_all(bytes)
     }
 
     const TYPE_ID_META: MetadataBuffer;
 }
 
+// Read a value which must take up all of `bytes`.
+fn try_read_all<UT, T: Lift<UT>>(bytes: &[u8]) -> Result<T> {
+    check_max_buffer_len(bytes.len())?;
+    let mut buf = bytes;
+    let value = T::try_read(&mut buf)?;
+    match Buf::remaining(&buf) {
+        0 => Ok(value),
+        n => bail!("junk data left in buffer after lifting (count: {n})",),
+    }
+}
+
 /// Lower Rust values to pass them to the foreign code
 ///
 /// This is used to pass arguments to callback interfaces. It's usually derived from
@@ -240,11 +299,19 @@ pub unsafe trait Lower<UT>: Sized {
 
     fn write(obj: Self, buf: &mut Vec<u8>);
 
+    fn size_hint(_obj: &Self) -> usize {
+        0
+    }
+
+    const FIXED_SIZE: Option<usize> = None;
+
+    fn check_lower(_obj: &Self) -> Result<()> {
+        Ok(())
+    }
+
     /// Convenience method
     fn lower_into_rust_buffer(obj: Self) -> RustBuffer {
-        let mut buf = ::std::vec::Vec::new();
-        Self::write(obj, &mut buf);
-        RustBuffer::from_vec(buf)
+        RustBuffer::from_writer(Self::size_hint(&obj), |buf| Self::write(obj, buf))
     }
 
     const TYPE_ID_META: MetadataBuffer;
@@ -276,6 +343,24 @@ pub unsafe trait LowerReturn<UT>: Sized {
     /// `Err(buf)`
     fn lower_return(obj: Self) -> Result<Self::ReturnType, RustBuffer>;
 
+    /// Check that this value can be returned to the foreign code, see [FfiConverter::check_lower]
+    ///
+    /// The default accepts every value.
+    fn check_return(_obj: &Self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Check this value with [Self::check_return], then lower it with [Self::lower_return]
+    ///
+    /// This is what the scaffolding functions call.  A rejected value is dropped rather than
+    /// lowered, and the call fails with an internal error.
+    fn try_lower_return(obj: Self) -> Result<Self::ReturnType, RustCallError> {
+        if let Err(e) = Self::check_return(&obj) {
+            return Err(RustCallError::InternalError(e.to_string()));
+        }
+        Self::lower_return(obj).map_err(RustCallError::Error)
+    }
+
     /// If possible, get a serialized error for failed argument lifts
     ///
     /// By default, we just panic and let `rust_call` handle things.  However, for `Result<_, E>`
@@ -347,6 +432,27 @@ pub unsafe trait LiftRef<UT> {
     type LiftType: Lift<UT> + Borrow<Self>;
 }
 
+/// Take ownership of the receiver of a method that consumes `self`
+///
+/// Method scaffolding lifts `self` as `LiftRef::LiftType`.  For objects that's an `Arc<T>`,
+/// which must hold the only reference to the object, while enums are lifted by value.
+#[doc(hidden)]
+pub trait ConsumeReceiver<T> {
+    fn consume_receiver(self) -> Option<T>;
+}
+
+impl<T> ConsumeReceiver<T> for T {
+    fn consume_receiver(self) -> Option<T> {
+        Some(self)
+    }
+}
+
+impl<T> ConsumeReceiver<T> for Arc<T> {
+    fn consume_receiver(self) -> Option<T> {
+        Arc::try_unwrap(self).ok()
+    }
+}
+
 pub trait ConvertError<UT>: Sized {
     fn try_convert_unexpected_callback_error(e: UnexpectedUniFFICallbackError) -> Result<Self>;
 }
@@ -402,6 +508,16 @@ macro_rules! derive_ffi_traits {
                 <Self as $crate::FfiConverter<$ut>>::write(obj, buf)
             }
 
+            fn size_hint(obj: &Self) -> usize {
+                <Self as $crate::FfiConverter<$ut>>::size_hint(obj)
+            }
+
+            const FIXED_SIZE: ::std::option::Option<usize> = <Self as $crate::FfiConverter<$ut>>::FIXED_SIZE;
+
+            fn check_lower(obj: &Self) -> $crate::deps::anyhow::Result<()> {
+                <Self as $crate::FfiConverter<$ut>>::check_lower(obj)
+            }
+
             const TYPE_ID_META: $crate::MetadataBuffer = <Self as $crate::FfiConverter<$ut>>::TYPE_ID_META;
         }
     };
@@ -432,6 +548,10 @@ macro_rules! derive_ffi_traits {
                 Ok(<Self as $crate::Lower<$ut>>::lower(obj))
             }
 
+            fn check_return(obj: &Self) -> $crate::deps::anyhow::Result<()> {
+                <Self as $crate::Lower<$ut>>::check_lower(obj)
+            }
+
             const TYPE_ID_META: $crate::MetadataBuffer =<Self as $crate::Lower<$ut>>::TYPE_ID_META;
         }
     };
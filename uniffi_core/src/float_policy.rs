@@ -0,0 +1,51 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::Result;
+use anyhow::bail;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// How non-finite floats (`NaN`, `inf` and `-inf`) are handled when passed over the FFI.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FloatSpecial {
+    /// Pass non-finite values through unchanged, including the bit pattern of `NaN` values.
+    #[default]
+    Pass,
+    /// Reject non-finite values.  Lifting them fails.  Values are checked before they're lowered,
+    /// see `Lower::check_lower`: a rejected return value fails the call with an internal error,
+    /// and a rejected callback interface argument fails the callback before it's invoked, like an
+    /// unexpected error raised by the callback.
+    ///
+    /// Custom types are lowered by converting them, so floats inside them are only checked when
+    /// they're lifted.
+    Error,
+}
+
+static REJECT_FLOAT_SPECIAL: AtomicBool = AtomicBool::new(false);
+
+/// How non-finite floats are currently handled when passed over the FFI.
+pub fn float_special() -> FloatSpecial {
+    if REJECT_FLOAT_SPECIAL.load(Ordering::Relaxed) {
+        FloatSpecial::Error
+    } else {
+        FloatSpecial::Pass
+    }
+}
+
+/// Set how non-finite floats are handled when passed over the FFI.
+///
+/// Non-finite values can break serialization layers in the foreign code, for example JSON which
+/// can't represent them.  [FloatSpecial::Error] stops them from crossing the FFI at all.
+pub fn set_float_special(policy: FloatSpecial) {
+    REJECT_FLOAT_SPECIAL.store(policy == FloatSpecial::Error, Ordering::Relaxed)
+}
+
+/// A helper function to ensure a float is allowed to cross the FFI by [float_special].
+pub fn check_float_special<T: Into<f64>>(value: T) -> Result<()> {
+    let value = value.into();
+    if !value.is_finite() && float_special() == FloatSpecial::Error {
+        bail!("non-finite float value `{value}` is not allowed over the FFI");
+    }
+    Ok(())
+}
@@ -34,20 +34,55 @@
 
 use anyhow::bail;
 use bytes::buf::Buf;
+use std::sync::Arc;
 
 // Make Result<> public to support external impls of FfiConverter
 pub use anyhow::Result;
 
+mod buffer_len;
+mod discriminant;
+mod dyn_error;
 pub mod ffi;
 mod ffi_converter_impls;
 mod ffi_converter_traits;
+mod float_policy;
+#[cfg(feature = "tracing")]
+mod log_sink;
 pub mod metadata;
-
+mod monotonic;
+mod opaque_handle;
+mod packed_bools;
+mod poison;
+mod read_depth;
+mod runtime_config;
+mod string_interner;
+mod string_policy;
+mod threadbound;
+
+pub use buffer_len::{
+    check_max_buffer_len, max_buffer_len, set_max_buffer_len, DEFAULT_MAX_BUFFER_LEN,
+};
+pub use discriminant::DiscriminantEncoding;
+pub use dyn_error::DynError;
 pub use ffi::*;
 pub use ffi_converter_traits::{
-    ConvertError, FfiConverter, FfiConverterArc, Lift, LiftRef, LiftReturn, Lower, LowerReturn,
+    ConsumeReceiver, ConvertError, FfiConverter, FfiConverterArc, Lift, LiftRef, LiftReturn, Lower,
+    LowerReturn,
 };
+pub use float_policy::{check_float_special, float_special, set_float_special, FloatSpecial};
+#[cfg(feature = "tracing")]
+pub use log_sink::*;
 pub use metadata::*;
+pub use monotonic::MonotonicDuration;
+pub use opaque_handle::OpaqueHandle;
+pub use packed_bools::{read_packed_bools, write_packed_bools};
+pub use panichook::{capture_panic_locations, set_panic_callback};
+pub use poison::{call_with_on_poison, Mutex, MutexGuard, OnPoison, StatePoisoned};
+pub use read_depth::{max_read_depth, set_max_read_depth, ReadDepthGuard, DEFAULT_MAX_READ_DEPTH};
+pub use runtime_config::{initialize, RuntimeConfig};
+pub use string_interner::{with_string_interner, StringInterner};
+pub use string_policy::{set_string_policy, str_from_utf8, string_policy, StringPolicy};
+pub use threadbound::{check_bound_thread, BindToCurrentThread, ThreadBound};
 
 // Re-export the libs that we use in the generated code,
 // so the consumer doesn't have to depend on them directly.
@@ -57,12 +92,16 @@ pub mod deps {
     pub use async_compat;
     pub use bytes;
     pub use log;
+    pub use once_cell;
+    #[cfg(feature = "json")]
+    pub use serde_json;
     pub use static_assertions;
 }
 
 mod panichook;
 
-const PACKAGE_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// The version of the uniffi runtime crate.
+pub const PACKAGE_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // For the significance of this magic number 10 here, and the reason that
 // it can't be a named constant, see the `check_compatible_version` function.
@@ -134,6 +173,25 @@ macro_rules! assert_compatible_version {
 /// Struct to use when we want to lift/lower/serialize types inside the `uniffi` crate.
 struct UniFfiTag;
 
+/// Convert an object to the interface it inherits from.
+///
+/// UDL interfaces declared as `interface Child : Parent` must implement `Upcast<Parent>`, which is
+/// used when a `Child` is passed to foreign code that expects a `Parent`.
+pub trait Upcast<P: ?Sized> {
+    fn upcast(self: Arc<Self>) -> Arc<P>;
+}
+
+/// Copy borrowed data into an owned value, so that it can be returned across the FFI.
+///
+/// Methods marked with `#[uniffi::snapshot]` can return a type which borrows from the object, like
+/// `DataView<'_>`.  The scaffolding calls `snapshot()` on it before the method returns, so the
+/// foreign code gets a copy of the data as it was at the time of the call, rather than a view.
+pub trait Snapshot {
+    type Owned;
+
+    fn snapshot(self) -> Self::Owned;
+}
+
 /// A helper function to ensure we don't read past the end of a buffer.
 ///
 /// Rust won't actually let us read past the end of a buffer, but the `Buf` trait does not support
@@ -149,6 +207,90 @@ pub fn check_remaining(buf: &[u8], num_bytes: usize) -> Result<()> {
     Ok(())
 }
 
+/// Add up the [FfiConverter::FIXED_SIZE]s of a record's fields.
+///
+/// The record only has a fixed size if all of its fields do.
+pub const fn sum_fixed_sizes(sizes: &[Option<usize>]) -> Option<usize> {
+    let mut total = 0;
+    let mut i = 0;
+    while i < sizes.len() {
+        match sizes[i] {
+            Some(size) => total += size,
+            None => return None,
+        }
+        i += 1;
+    }
+    Some(total)
+}
+
+#[cfg(feature = "fuzzing")]
+thread_local! {
+    // Whether the data currently being lifted on this thread came from `Lift::try_lift_from_bytes`.
+    static LIFTING_FROM_BYTES: std::cell::Cell<bool> = std::cell::Cell::new(false);
+}
+
+/// Marks the data being lifted on this thread as arbitrary bytes, until it's dropped.
+#[cfg(feature = "fuzzing")]
+pub(crate) struct LiftingFromBytesGuard {
+    was_lifting: bool,
+}
+
+#[cfg(feature = "fuzzing")]
+impl LiftingFromBytesGuard {
+    pub(crate) fn enter() -> Self {
+        Self {
+            was_lifting: LIFTING_FROM_BYTES.with(|l| l.replace(true)),
+        }
+    }
+}
+
+#[cfg(feature = "fuzzing")]
+impl Drop for LiftingFromBytesGuard {
+    fn drop(&mut self) {
+        LIFTING_FROM_BYTES.with(|l| l.set(self.was_lifting));
+    }
+}
+
+/// A helper function to ensure we don't read a handle from arbitrary bytes.
+///
+/// Object handles are pointers, which are only valid if they came from the foreign code.  Reading
+/// one from the bytes passed to [Lift::try_lift_from_bytes] would be undefined behaviour, so it's
+/// an error instead.  Without the `fuzzing` feature this always succeeds.
+pub fn check_read_handle() -> Result<()> {
+    #[cfg(feature = "fuzzing")]
+    if LIFTING_FROM_BYTES.with(|l| l.get()) {
+        bail!("handles can't be lifted from arbitrary bytes");
+    }
+    Ok(())
+}
+
+/// Check a callback interface handle passed by the foreign code.
+///
+/// The bindings never hand out handle 0, so it's an error rather than a callback which fails when
+/// it's called.  A missing callback is passed as an `Option` instead.
+pub fn check_callback_handle(handle: u64) -> Result<u64> {
+    if handle == 0 {
+        bail!("null callback interface handle");
+    }
+    Ok(handle)
+}
+
+/// The id of the object behind a handle, which the foreign code uses to compare handles.
+///
+/// Handles to trait objects are boxed, so two handles to the same object can have different
+/// pointers.  This is the address of the object in the `Arc` instead, which is the same for every
+/// handle to it.
+pub fn arc_handle_id<T: ?Sized>(arc: &Arc<T>) -> u64 {
+    Arc::as_ptr(arc).cast::<()>() as usize as u64
+}
+
+/// Checks that an exported trait is `Send + Sync`.
+///
+/// The scaffolding implements this for `dyn Trait`, spanned to the trait's name, so the error
+/// points at the trait and names this check rather than somewhere in the generated code.
+#[doc(hidden)]
+pub trait ExportedTraitMustBeSendAndSync: Send + Sync {}
+
 /// Macro to implement lowering/lifting using a `RustBuffer`
 ///
 /// For complex types where it's too fiddly or too unsafe to convert them into a special-purpose
@@ -162,21 +304,71 @@ macro_rules! ffi_converter_rust_buffer_lift_and_lower {
         type FfiType = $crate::RustBuffer;
 
         fn lower(v: Self) -> $crate::RustBuffer {
-            let mut buf = ::std::vec::Vec::new();
-            <Self as $crate::FfiConverter<$uniffi_tag>>::write(v, &mut buf);
-            $crate::RustBuffer::from_vec(buf)
+            $crate::RustBuffer::from_writer(
+                <Self as $crate::FfiConverter<$uniffi_tag>>::size_hint(&v),
+                |buf| <Self as $crate::FfiConverter<$uniffi_tag>>::write(v, buf),
+            )
         }
 
         fn try_lift(buf: $crate::RustBuffer) -> $crate::Result<Self> {
-            let vec = buf.destroy_into_vec();
-            let mut buf = vec.as_slice();
-            let value = <Self as $crate::FfiConverter<$uniffi_tag>>::try_read(&mut buf)?;
-            match $crate::deps::bytes::Buf::remaining(&buf) {
-                0 => Ok(value),
-                n => $crate::deps::anyhow::bail!(
-                    "junk data left in buffer after lifting (count: {n})",
-                ),
-            }
+            buf.destroy_with(|bytes| {
+                $crate::check_max_buffer_len(bytes.len())?;
+                let mut buf = bytes;
+                let value = <Self as $crate::FfiConverter<$uniffi_tag>>::try_read(&mut buf)?;
+                match $crate::deps::bytes::Buf::remaining(&buf) {
+                    0 => Ok(value),
+                    n => $crate::deps::anyhow::bail!(
+                        "junk data left in buffer after lifting (count: {n})",
+                    ),
+                }
+            })
+        }
+    };
+}
+
+/// Macro to implement writing/reading for enums that are lowered into a scalar
+///
+/// Fieldless enums don't need a `RustBuffer`, they're passed across the FFI as the `i32` that
+/// identifies their variant.  That's the same `i32` their serialized form consists of, so this
+/// macro implements `write()` in terms of `lower()` and `read()` in terms of `lift()`.
+///
+/// This macro implements the boilerplate needed to define `write`, `try_read` and `FFIType`.
+#[macro_export]
+macro_rules! ffi_converter_scalar_enum_read_and_write {
+    ($uniffi_tag:ty) => {
+        type FfiType = i32;
+
+        fn write(obj: Self, buf: &mut ::std::vec::Vec<u8>) {
+            $crate::deps::bytes::BufMut::put_i32(
+                buf,
+                <Self as $crate::FfiConverter<$uniffi_tag>>::lower(obj),
+            );
+        }
+
+        fn try_read(buf: &mut &[u8]) -> $crate::Result<Self> {
+            $crate::check_remaining(buf, 4)?;
+            <Self as $crate::FfiConverter<$uniffi_tag>>::try_lift(
+                $crate::deps::bytes::Buf::get_i32(buf),
+            )
+        }
+    };
+    // Enums marked `[Discriminant=...]` still lower into the `i32`, but they're serialized with
+    // the discriminant's encoding.
+    ($uniffi_tag:ty, $encoding:expr) => {
+        type FfiType = i32;
+
+        fn write(obj: Self, buf: &mut ::std::vec::Vec<u8>) {
+            $crate::DiscriminantEncoding::write(
+                $encoding,
+                <Self as $crate::FfiConverter<$uniffi_tag>>::lower(obj),
+                buf,
+            );
+        }
+
+        fn try_read(buf: &mut &[u8]) -> $crate::Result<Self> {
+            <Self as $crate::FfiConverter<$uniffi_tag>>::try_lift(
+                $crate::DiscriminantEncoding::read($encoding, buf)?,
+            )
         }
     };
 }
@@ -250,8 +442,14 @@ macro_rules! do_ffi_converter_forward {
 
 #[cfg(test)]
 mod test {
-    use super::{FfiConverter, UniFfiTag};
-    use std::time::{Duration, SystemTime};
+    use super::{
+        check_callback_handle, FfiConverter, Lift, Lower, LowerReturn, MetadataBuffer,
+        MonotonicDuration, OpaqueHandle, Result, RustBuffer, UniFfiTag,
+    };
+    use std::ops::{Range, RangeInclusive};
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant, SystemTime};
 
     #[test]
     fn timestamp_roundtrip_post_epoch() {
@@ -277,6 +475,397 @@ mod test {
             "Expected results after lowering and lifting to be equal"
         )
     }
+
+    #[test]
+    fn time_lift_invalid_nanos() {
+        let lift_bytes = |seconds: i64, nanos: u32| {
+            let mut buf = seconds.to_be_bytes().to_vec();
+            buf.extend(nanos.to_be_bytes());
+            RustBuffer::from_vec(buf)
+        };
+        assert!(<Duration as Lift<UniFfiTag>>::try_lift(lift_bytes(-1, 1_000_000_000)).is_err());
+        assert!(<SystemTime as Lift<UniFfiTag>>::try_lift(lift_bytes(1, u32::MAX)).is_err());
+    }
+
+    #[test]
+    fn non_zero_roundtrip() {
+        use std::num::{NonZeroI8, NonZeroU64};
+
+        let expected = NonZeroU64::new(u64::MAX).unwrap();
+        let lowered = <NonZeroU64 as Lower<UniFfiTag>>::lower(expected);
+        assert_eq!(lowered, u64::MAX);
+        assert_eq!(
+            <NonZeroU64 as Lift<UniFfiTag>>::try_lift(lowered).unwrap(),
+            expected
+        );
+
+        // Serialized like the integer, for example in a `Vec`.
+        let expected = vec![NonZeroI8::new(-1).unwrap(), NonZeroI8::new(7).unwrap()];
+        let buf = <Vec<NonZeroI8> as Lower<UniFfiTag>>::lower(expected.clone());
+        assert_eq!(
+            <Vec<NonZeroI8> as Lift<UniFfiTag>>::try_lift(buf).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn non_zero_lift_zero() {
+        use std::num::{NonZeroI8, NonZeroU64};
+
+        assert!(<NonZeroU64 as Lift<UniFfiTag>>::try_lift(0).is_err());
+        let buf = <Vec<i8> as Lower<UniFfiTag>>::lower(vec![1, 0]);
+        assert!(<Vec<NonZeroI8> as Lift<UniFfiTag>>::try_lift(buf).is_err());
+    }
+
+    #[test]
+    fn callback_handle_zero() {
+        assert!(check_callback_handle(0).is_err());
+        assert_eq!(check_callback_handle(1).unwrap(), 1);
+    }
+
+    #[test]
+    fn fixed_size_sequence_reserves_once() {
+        use std::cell::RefCell;
+
+        thread_local! {
+            // The buffer's capacity after each item is written.
+            static CAPACITIES: RefCell<Vec<usize>> = RefCell::new(Vec::new());
+        }
+
+        // The same record, written with and without a fixed size.
+        macro_rules! point {
+            ($name:ident, $fixed_size:expr) => {
+                struct $name(i32, i32);
+
+                unsafe impl Lower<UniFfiTag> for $name {
+                    type FfiType = RustBuffer;
+
+                    fn lower(obj: Self) -> RustBuffer {
+                        Self::lower_into_rust_buffer(obj)
+                    }
+
+                    fn write(obj: Self, buf: &mut Vec<u8>) {
+                        <i32 as Lower<UniFfiTag>>::write(obj.0, buf);
+                        <i32 as Lower<UniFfiTag>>::write(obj.1, buf);
+                        CAPACITIES.with(|c| c.borrow_mut().push(buf.capacity()));
+                    }
+
+                    fn size_hint(_obj: &Self) -> usize {
+                        8
+                    }
+
+                    const FIXED_SIZE: Option<usize> = $fixed_size;
+
+                    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::new();
+                }
+            };
+        }
+        point!(FixedPoint, Some(8));
+        point!(Point, None);
+
+        // Write the points into an empty buffer, like a sequence nested in a value whose size
+        // isn't known, and count how often the buffer was reallocated.
+        fn write_points<T: Lower<UniFfiTag>>(points: Vec<T>) -> (Vec<u8>, usize) {
+            CAPACITIES.with(|c| c.borrow_mut().clear());
+            let mut buf = Vec::new();
+            <Vec<T> as Lower<UniFfiTag>>::write(points, &mut buf);
+            let mut capacities = CAPACITIES.with(|c| c.take());
+            capacities.insert(0, 0);
+            capacities.dedup();
+            (buf, capacities.len() - 1)
+        }
+
+        const LEN: i32 = 10_000;
+        let (fixed_buf, fixed_reallocs) =
+            write_points((0..LEN).map(|i| FixedPoint(i, -i)).collect());
+        let (buf, reallocs) = write_points((0..LEN).map(|i| Point(i, -i)).collect());
+        assert_eq!(fixed_buf, buf);
+        assert_eq!(fixed_buf.capacity(), 4 + 8 * LEN as usize);
+        assert_eq!(fixed_reallocs, 1);
+        assert!(reallocs > fixed_reallocs, "{reallocs} reallocations");
+
+        // The size hint is computed from the length.
+        let points: Vec<_> = (0..LEN).map(|i| FixedPoint(i, -i)).collect();
+        assert_eq!(
+            <Vec<FixedPoint> as Lower<UniFfiTag>>::size_hint(&points),
+            4 + 8 * LEN as usize
+        );
+        assert_eq!(
+            crate::sum_fixed_sizes(&[Some(4), Some(8), Some(1)]),
+            Some(13)
+        );
+        assert_eq!(crate::sum_fixed_sizes(&[Some(4), None]), None);
+    }
+
+    #[cfg(feature = "path")]
+    #[test]
+    fn path_roundtrip() {
+        let expected = std::path::PathBuf::from("/tmp/some dir/file.txt");
+        let result =
+            <std::path::PathBuf as Lift<UniFfiTag>>::try_lift(<std::path::PathBuf as Lower<
+                UniFfiTag,
+            >>::lower(
+                expected.clone()
+            ))
+            .expect("Failed to lift!");
+        assert_eq!(expected, result);
+    }
+
+    #[cfg(all(feature = "path", unix))]
+    #[test]
+    fn path_non_utf8_is_lossy() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::PathBuf};
+
+        let path = PathBuf::from(OsStr::from_bytes(b"/tmp/caf\xe9.txt"));
+        let result =
+            <PathBuf as Lift<UniFfiTag>>::try_lift(<PathBuf as Lower<UniFfiTag>>::lower(path))
+                .expect("Failed to lift!");
+        assert_eq!(result, PathBuf::from("/tmp/caf\u{FFFD}.txt"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_roundtrip() {
+        use serde_json::{json, Value};
+
+        for expected in [
+            json!({"name": "uniffi", "nested": {"list": [1, 2.5, null], "flag": true}}),
+            json!(["a", {"b": []}, -3]),
+            json!("text"),
+            json!(42),
+            json!(null),
+        ] {
+            let result = <Value as Lift<UniFfiTag>>::try_lift(<Value as Lower<UniFfiTag>>::lower(
+                expected.clone(),
+            ))
+            .expect("Failed to lift!");
+            assert_eq!(expected, result);
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_lift_invalid() {
+        let buf = <String as Lower<UniFfiTag>>::lower("{not json".to_owned());
+        assert!(<serde_json::Value as Lift<UniFfiTag>>::try_lift(buf).is_err());
+    }
+
+    #[cfg(feature = "fuzzing")]
+    #[test]
+    fn lift_from_bytes() {
+        let bytes =
+            <Vec<String> as Lower<UniFfiTag>>::lower(vec!["a".to_owned()]).destroy_into_vec();
+        assert_eq!(
+            <Vec<String> as Lift<UniFfiTag>>::try_lift_from_bytes(&bytes).unwrap(),
+            vec!["a".to_owned()]
+        );
+        // Every truncation of the bytes is an error rather than a panic.
+        for len in 0..bytes.len() {
+            assert!(<Vec<String> as Lift<UniFfiTag>>::try_lift_from_bytes(&bytes[..len]).is_err());
+        }
+        // A length which is larger than the remaining bytes.
+        assert!(<Vec<String> as Lift<UniFfiTag>>::try_lift_from_bytes(&[
+            0, 0, 0, 1, 0x7f, 0, 0, 0
+        ])
+        .is_err());
+        // Handles can't be read.
+        assert!(super::check_read_handle().is_ok());
+        let _guard = super::LiftingFromBytesGuard::enter();
+        assert!(super::check_read_handle().is_err());
+    }
+
+    #[test]
+    fn arc_str_roundtrip() {
+        let returned = <Arc<str> as LowerReturn<UniFfiTag>>::lower_return(Arc::from("hello"))
+            .expect("Failed to lower!");
+        let result = <String as Lift<UniFfiTag>>::try_lift(returned).expect("Failed to lift!");
+        assert_eq!(result, "hello");
+
+        // The lifted value is already an `Arc`, so cloning it shares the allocation.
+        let lifted =
+            <Arc<str> as Lift<UniFfiTag>>::try_lift(<String as Lower<UniFfiTag>>::lower(result))
+                .expect("Failed to lift!");
+        let cloned = Arc::clone(&lifted);
+        assert_eq!(lifted.as_ptr(), cloned.as_ptr());
+        assert_eq!(Arc::strong_count(&lifted), 2);
+    }
+
+    #[test]
+    fn arc_bytes_roundtrip() {
+        let expected: Arc<[u8]> = Arc::from(&[1u8, 2, 3][..]);
+        let result = <Arc<[u8]> as Lift<UniFfiTag>>::try_lift(
+            <Arc<[u8]> as Lower<UniFfiTag>>::lower(expected.clone()),
+        )
+        .expect("Failed to lift!");
+        assert_eq!(expected, result);
+
+        // `Arc<[u8]>` is passed exactly like `Vec<u8>`.
+        let vec = <Vec<u8> as Lift<UniFfiTag>>::try_lift(<Arc<[u8]> as Lower<UniFfiTag>>::lower(
+            expected.clone(),
+        ))
+        .expect("Failed to lift!");
+        assert_eq!(vec, [1, 2, 3]);
+        let lifted =
+            <Arc<[u8]> as Lift<UniFfiTag>>::try_lift(<Vec<u8> as Lower<UniFfiTag>>::lower(vec))
+                .expect("Failed to lift!");
+        assert_eq!(expected, lifted);
+        let cloned = Arc::clone(&lifted);
+        assert_eq!(lifted.as_ptr(), cloned.as_ptr());
+    }
+
+    #[test]
+    fn opaque_handle_roundtrip() {
+        let mut value = 42u32;
+        let handle = OpaqueHandle::from_ptr(&mut value as *mut u32 as *mut std::ffi::c_void);
+        let lowered = <OpaqueHandle as Lower<UniFfiTag>>::lower(handle);
+        assert_eq!(lowered, &mut value as *mut u32 as usize as u64);
+        let lifted = <OpaqueHandle as Lift<UniFfiTag>>::try_lift(lowered).expect("Failed to lift!");
+        assert_eq!(lifted, handle);
+        assert_eq!(unsafe { *(lifted.as_ptr() as *mut u32) }, 42);
+
+        let mut buf = Vec::new();
+        <OpaqueHandle as Lower<UniFfiTag>>::write(OpaqueHandle::from_raw(u64::MAX), &mut buf);
+        assert_eq!(buf, [0xff; 8]);
+        let read = <OpaqueHandle as Lift<UniFfiTag>>::try_read(&mut buf.as_slice()).unwrap();
+        assert_eq!(read.as_raw(), u64::MAX);
+    }
+
+    #[test]
+    fn duration_range_roundtrip() {
+        let secs = Duration::from_secs;
+        for range in [secs(1)..secs(5), secs(3)..secs(3), secs(5)..secs(1)] {
+            let lowered = <Range<Duration> as Lower<UniFfiTag>>::lower(range.clone());
+            let lifted = <Range<Duration> as Lift<UniFfiTag>>::try_lift(lowered).unwrap();
+            assert_eq!(lifted, range);
+        }
+        for range in [secs(1)..=secs(5), secs(3)..=secs(3), secs(5)..=secs(1)] {
+            let lowered = <RangeInclusive<Duration> as Lower<UniFfiTag>>::lower(range.clone());
+            let lifted = <RangeInclusive<Duration> as Lift<UniFfiTag>>::try_lift(lowered).unwrap();
+            assert_eq!(lifted, range);
+        }
+
+        // The inclusivity flag has to match the range being lifted.
+        let lowered = <Range<Duration> as Lower<UniFfiTag>>::lower(secs(1)..secs(5));
+        assert!(<RangeInclusive<Duration> as Lift<UniFfiTag>>::try_lift(lowered).is_err());
+        let lowered = <RangeInclusive<Duration> as Lower<UniFfiTag>>::lower(secs(1)..=secs(5));
+        assert!(<Range<Duration> as Lift<UniFfiTag>>::try_lift(lowered).is_err());
+    }
+
+    #[test]
+    fn monotonic_duration_roundtrip() {
+        let start = Instant::now();
+        std::thread::sleep(Duration::from_millis(1));
+        let elapsed = MonotonicDuration::since(start);
+        assert!(elapsed.as_duration() >= Duration::from_millis(1));
+        let result =
+            <MonotonicDuration as Lift<UniFfiTag>>::try_lift(<MonotonicDuration as Lower<
+                UniFfiTag,
+            >>::lower(elapsed))
+            .expect("Failed to lift!");
+        assert_eq!(elapsed, result);
+
+        // `MonotonicDuration` is passed exactly like `Duration`.
+        let duration = <Duration as Lift<UniFfiTag>>::try_lift(<MonotonicDuration as Lower<
+            UniFfiTag,
+        >>::lower(elapsed))
+        .expect("Failed to lift!");
+        assert_eq!(duration, elapsed.as_duration());
+        assert_eq!(
+            <MonotonicDuration as Lower<UniFfiTag>>::TYPE_ID_META.as_ref(),
+            <Duration as Lower<UniFfiTag>>::TYPE_ID_META.as_ref(),
+        );
+
+        // The time between two instants is never negative.
+        let later = Instant::now();
+        assert_eq!(
+            MonotonicDuration::between(later, start).as_duration(),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn boxed_slice_roundtrip() {
+        let expected: Box<[u32]> = vec![1, 2, 3].into_boxed_slice();
+        // `Box<[T]>` is passed exactly like `Vec<T>`.
+        let vec = <Vec<u32> as Lift<UniFfiTag>>::try_lift(<Box<[u32]> as Lower<UniFfiTag>>::lower(
+            expected.clone(),
+        ))
+        .expect("Failed to lift!");
+        assert_eq!(vec, [1, 2, 3]);
+        let lifted =
+            <Box<[u32]> as Lift<UniFfiTag>>::try_lift(<Vec<u32> as Lower<UniFfiTag>>::lower(vec))
+                .expect("Failed to lift!");
+        assert_eq!(expected, lifted);
+        assert_eq!(
+            <Box<[u32]> as LowerReturn<UniFfiTag>>::TYPE_ID_META.as_ref(),
+            <Vec<u32> as LowerReturn<UniFfiTag>>::TYPE_ID_META.as_ref(),
+        );
+    }
+
+    #[test]
+    fn rc_slice_roundtrip() {
+        let expected: Rc<[String]> = Rc::from(vec!["a".to_string(), "b".to_string()]);
+        let shared = Rc::clone(&expected);
+        let result = <Rc<[String]> as Lift<UniFfiTag>>::try_lift(<Rc<[String]> as Lower<
+            UniFfiTag,
+        >>::lower(shared))
+        .expect("Failed to lift!");
+        assert_eq!(expected, result);
+        assert_eq!(
+            <Rc<[String]> as LowerReturn<UniFfiTag>>::TYPE_ID_META.as_ref(),
+            <Vec<String> as LowerReturn<UniFfiTag>>::TYPE_ID_META.as_ref(),
+        );
+    }
+
+    // A fieldless enum, implemented the way `#[derive(uniffi::Enum)]` implements them.
+    #[derive(Debug, PartialEq, Eq)]
+    enum Direction {
+        Up,
+        Down,
+    }
+
+    unsafe impl FfiConverter<UniFfiTag> for Direction {
+        ffi_converter_scalar_enum_read_and_write!(UniFfiTag);
+
+        fn lower(obj: Direction) -> i32 {
+            match obj {
+                Direction::Up => 1,
+                Direction::Down => 2,
+            }
+        }
+
+        fn try_lift(v: i32) -> Result<Direction> {
+            Ok(match v {
+                1 => Direction::Up,
+                2 => Direction::Down,
+                v => anyhow::bail!("Invalid Direction enum value: {v}"),
+            })
+        }
+
+        const TYPE_ID_META: MetadataBuffer = MetadataBuffer::new();
+    }
+
+    crate::derive_ffi_traits!(impl Lower<UniFfiTag> for Direction);
+    crate::derive_ffi_traits!(impl Lift<UniFfiTag> for Direction);
+
+    #[test]
+    fn scalar_enum_roundtrip() {
+        let lowered: i32 = <Direction as FfiConverter<UniFfiTag>>::lower(Direction::Down);
+        assert_eq!(lowered, 2);
+        assert_eq!(
+            <Direction as FfiConverter<UniFfiTag>>::try_lift(lowered).unwrap(),
+            Direction::Down
+        );
+        assert!(<Direction as FfiConverter<UniFfiTag>>::try_lift(3).is_err());
+        // Inside a `RustBuffer`, the enum is serialized as the same `i32`.
+        let mut buf = Vec::new();
+        <Option<Direction> as Lower<UniFfiTag>>::write(Some(Direction::Up), &mut buf);
+        assert_eq!(buf, [1, 0, 0, 0, 1]);
+        assert_eq!(
+            <Option<Direction> as Lift<UniFfiTag>>::try_read(&mut buf.as_slice()).unwrap(),
+            Some(Direction::Up)
+        );
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,350 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Forwarding `tracing` events to the foreign code
+//!
+//! [LogSubscriber] is a `tracing` subscriber which formats each event and passes it to the callback
+//! registered with [set_log_callback].  The message is prefixed with the spans the event happened
+//! in, like `request{id=1}:parse: invalid header`.
+//!
+//! Components use [export_log_sink!](crate::export_log_sink) to export a `LogSink` callback
+//! interface which the foreign code implements, and a `set_log_sink()` function to register it.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::{self, Write},
+    sync::{
+        atomic::{AtomicU64, AtomicU8, Ordering},
+        Mutex, RwLock,
+    },
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Level, Metadata, Subscriber,
+};
+
+/// The level of a log event, from the most to the least severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<&Level> for LogLevel {
+    fn from(level: &Level) -> Self {
+        match *level {
+            Level::ERROR => Self::Error,
+            Level::WARN => Self::Warn,
+            Level::INFO => Self::Info,
+            Level::DEBUG => Self::Debug,
+            Level::TRACE => Self::Trace,
+        }
+    }
+}
+
+/// A log event, as it's passed to the foreign code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    pub level: LogLevel,
+    /// The module the event came from, unless the event overrides it.
+    pub target: String,
+    /// The message, followed by the event's other fields and prefixed with its spans.
+    pub message: String,
+}
+
+type LogCallback = Box<dyn Fn(LogRecord) + Send + Sync>;
+
+static LOG_CALLBACK: RwLock<Option<LogCallback>> = RwLock::new(None);
+// The least severe level which is passed to the callback, or 0 if there's no callback.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+thread_local! {
+    // Set while the callback is running, so that events logged by the foreign code calling back
+    // into Rust are dropped rather than recursing.
+    static IN_CALLBACK: Cell<bool> = Cell::new(false);
+}
+
+/// Pass the `tracing` events at `max_level` or more severe to `callback`.
+///
+/// This replaces any previous callback.  The first call also installs a [LogSubscriber] as the
+/// global default `tracing` subscriber, unless the application already has one, in which case it
+/// needs to forward the events to a [LogSubscriber] itself.
+pub fn set_log_callback(callback: impl Fn(LogRecord) + Send + Sync + 'static, max_level: LogLevel) {
+    *LOG_CALLBACK.write().unwrap() = Some(Box::new(callback));
+    MAX_LEVEL.store(max_level as u8, Ordering::Relaxed);
+    // This fails if a global default was already set, possibly by an earlier call.
+    let _ = tracing::subscriber::set_global_default(LogSubscriber::default());
+}
+
+/// Stop passing `tracing` events to the callback.
+pub fn clear_log_callback() {
+    MAX_LEVEL.store(0, Ordering::Relaxed);
+    *LOG_CALLBACK.write().unwrap() = None;
+}
+
+fn log(record: LogRecord) {
+    IN_CALLBACK.with(|in_callback| {
+        if in_callback.replace(true) {
+            return;
+        }
+        if let Some(callback) = LOG_CALLBACK.read().unwrap().as_ref() {
+            callback(record);
+        }
+        in_callback.set(false);
+    })
+}
+
+/// A `tracing` subscriber which passes events to the callback registered with
+/// [set_log_callback].
+#[derive(Default)]
+pub struct LogSubscriber {
+    next_id: AtomicU64,
+    // The formatted name and fields of each open span, with its reference count.
+    spans: Mutex<HashMap<u64, (String, usize)>>,
+}
+
+thread_local! {
+    // The spans entered on this thread, innermost last.
+    static ENTERED: RefCell<Vec<u64>> = RefCell::new(Vec::new());
+}
+
+impl Subscriber for LogSubscriber {
+    fn register_callsite(
+        &self,
+        _metadata: &'static Metadata<'static>,
+    ) -> tracing::subscriber::Interest {
+        // The level can change, so check `enabled()` every time.
+        tracing::subscriber::Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        (LogLevel::from(metadata.level()) as u8) <= MAX_LEVEL.load(Ordering::Relaxed)
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        let mut fields = FieldFormatter::default();
+        span.record(&mut fields);
+        let name = match fields.fields.is_empty() {
+            true => span.metadata().name().to_string(),
+            false => format!("{}{{{}}}", span.metadata().name(), fields.fields),
+        };
+        // Span ids must be non-zero.
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        self.spans.lock().unwrap().insert(id, (name, 1));
+        span::Id::from_u64(id)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        if IN_CALLBACK.with(Cell::get) {
+            return;
+        }
+        let mut fields = FieldFormatter::default();
+        event.record(&mut fields);
+        let mut message = String::new();
+        ENTERED.with(|entered| {
+            let spans = self.spans.lock().unwrap();
+            for id in entered.borrow().iter() {
+                if let Some((name, _)) = spans.get(id) {
+                    message.push_str(name);
+                    message.push(':');
+                }
+            }
+        });
+        if !message.is_empty() {
+            message.push(' ');
+        }
+        message.push_str(&fields.message);
+        if !fields.fields.is_empty() {
+            if !fields.message.is_empty() {
+                message.push(' ');
+            }
+            message.push_str(&fields.fields);
+        }
+        log(LogRecord {
+            level: event.metadata().level().into(),
+            target: event.metadata().target().to_string(),
+            message,
+        });
+    }
+
+    fn enter(&self, span: &span::Id) {
+        ENTERED.with(|entered| entered.borrow_mut().push(span.into_u64()));
+    }
+
+    fn exit(&self, span: &span::Id) {
+        ENTERED.with(|entered| {
+            let mut entered = entered.borrow_mut();
+            if let Some(pos) = entered.iter().rposition(|id| *id == span.into_u64()) {
+                entered.remove(pos);
+            }
+        });
+    }
+
+    fn clone_span(&self, span: &span::Id) -> span::Id {
+        if let Some((_, refs)) = self.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            *refs += 1;
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, span: span::Id) -> bool {
+        let mut spans = self.spans.lock().unwrap();
+        let id = span.into_u64();
+        match spans.get_mut(&id) {
+            Some((_, refs)) if *refs > 1 => {
+                *refs -= 1;
+                false
+            }
+            Some(_) => {
+                spans.remove(&id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// Formats the `message` field, and the other fields as `name=value`.
+#[derive(Default)]
+struct FieldFormatter {
+    message: String,
+    fields: String,
+}
+
+impl Visit for FieldFormatter {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value)
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            if !self.fields.is_empty() {
+                self.fields.push(' ');
+            }
+            let _ = write!(self.fields, "{}={value:?}", field.name());
+        }
+    }
+}
+
+/// Export a `LogSink` callback interface and a `set_log_sink()` function, so that the foreign
+/// code can receive the component's `tracing` events.
+///
+/// The foreign code implements `LogSink.log(level, target, message)` and passes it to
+/// `set_log_sink(sink, max_level)`, along with the least severe `LogLevel` it wants.  This needs
+/// the `tracing` feature of the `uniffi` crate.
+#[macro_export]
+macro_rules! export_log_sink {
+    () => {
+        /// The level of a log event, from the most to the least severe.
+        #[derive(::uniffi::Enum)]
+        pub enum LogLevel {
+            Error,
+            Warn,
+            Info,
+            Debug,
+            Trace,
+        }
+
+        impl ::std::convert::From<LogLevel> for $crate::LogLevel {
+            fn from(level: LogLevel) -> Self {
+                match level {
+                    LogLevel::Error => Self::Error,
+                    LogLevel::Warn => Self::Warn,
+                    LogLevel::Info => Self::Info,
+                    LogLevel::Debug => Self::Debug,
+                    LogLevel::Trace => Self::Trace,
+                }
+            }
+        }
+
+        impl ::std::convert::From<$crate::LogLevel> for LogLevel {
+            fn from(level: $crate::LogLevel) -> Self {
+                match level {
+                    $crate::LogLevel::Error => Self::Error,
+                    $crate::LogLevel::Warn => Self::Warn,
+                    $crate::LogLevel::Info => Self::Info,
+                    $crate::LogLevel::Debug => Self::Debug,
+                    $crate::LogLevel::Trace => Self::Trace,
+                }
+            }
+        }
+
+        /// Receives the `tracing` events from the Rust code.
+        #[::uniffi::export(callback_interface)]
+        pub trait LogSink: Send + Sync {
+            /// Log an event.  The message is prefixed with the spans the event happened in.
+            fn log(&self, level: LogLevel, target: String, message: String);
+        }
+
+        /// Pass the `tracing` events at `max_level` or more severe to `sink`, replacing any
+        /// previous sink.
+        #[::uniffi::export]
+        pub fn set_log_sink(sink: Box<dyn LogSink>, max_level: LogLevel) {
+            $crate::set_log_callback(
+                move |record: $crate::LogRecord| {
+                    sink.log(record.level.into(), record.target, record.message)
+                },
+                max_level.into(),
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_log_subscriber() {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let records2 = Arc::clone(&records);
+        set_log_callback(
+            move |record| records2.lock().unwrap().push(record),
+            LogLevel::Info,
+        );
+        let subscriber = LogSubscriber::default();
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", id = 1);
+            let _guard = span.enter();
+            tracing::info!(count = 2, "hello {}", "world");
+            // Less severe than the max level.
+            tracing::debug!("dropped");
+            tracing::error!(target: "custom", "failed");
+        });
+        clear_log_callback();
+        tracing::subscriber::with_default(LogSubscriber::default(), || tracing::error!("cleared"));
+
+        assert_eq!(
+            *records.lock().unwrap(),
+            [
+                LogRecord {
+                    level: LogLevel::Info,
+                    target: "uniffi_core::log_sink::test".into(),
+                    message: "request{id=1}: hello world count=2".into(),
+                },
+                LogRecord {
+                    level: LogLevel::Error,
+                    target: "custom".into(),
+                    message: "request{id=1}: failed".into(),
+                },
+            ]
+        );
+    }
+}
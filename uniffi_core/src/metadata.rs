@@ -39,6 +39,7 @@ pub mod codes {
     pub const CALLBACK_INTERFACE: u8 = 9;
     pub const TRAIT_METHOD: u8 = 10;
     pub const UNIFFI_TRAIT: u8 = 11;
+    pub const CONST_FN: u8 = 12;
     pub const UNKNOWN: u8 = 255;
 
     // Type codes
@@ -67,6 +68,13 @@ pub mod codes {
     pub const TYPE_CUSTOM: u8 = 22;
     pub const TYPE_RESULT: u8 = 23;
     pub const TYPE_FUTURE: u8 = 24;
+    pub const TYPE_DYN_ERROR: u8 = 25;
+    pub const TYPE_PATH: u8 = 26;
+    pub const TYPE_JSON: u8 = 27;
+    pub const TYPE_NON_ZERO: u8 = 28;
+    pub const TYPE_OPAQUE_HANDLE: u8 = 29;
+    pub const TYPE_DURATION_RANGE: u8 = 30;
+    pub const TYPE_DURATION_RANGE_INCLUSIVE: u8 = 31;
     pub const TYPE_UNIT: u8 = 255;
 
     // Literal codes for LiteralMetadata - note that we don't support
@@ -225,6 +233,12 @@ impl MetadataBuffer {
         result
     }
 
+    /// Whether this is the metadata for a type without parameters, like `bool`, which is just
+    /// the type code.
+    pub const fn is_type_code(&self, code: u8) -> bool {
+        self.size == 1 && self.bytes[0] == code
+    }
+
     // Create a checksum from this MetadataBuffer
     //
     // This is used by the bindings code to verify that the library they link to is the same one
@@ -0,0 +1,46 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::time::{Duration, Instant};
+
+/// An elapsed time measured with the monotonic clock, which can be passed via the FFI.
+///
+/// An `Instant` is only meaningful in the process which created it, so it can't be passed to the
+/// foreign code.  Instead, measure the time since it on the Rust side and return the
+/// `MonotonicDuration`, which is passed exactly like a `Duration`: the foreign code sees its usual
+/// duration type, such as `java.time.Duration` in Kotlin, `TimeInterval` in Swift and
+/// `datetime.timedelta` in Python.
+///
+/// Unlike a difference between two `SystemTime`s, this is never negative and isn't affected by
+/// changes to the system clock.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MonotonicDuration(Duration);
+
+impl MonotonicDuration {
+    /// The time elapsed since `earlier`.
+    pub fn since(earlier: Instant) -> Self {
+        Self::between(earlier, Instant::now())
+    }
+
+    /// The time elapsed between `earlier` and `later`, or zero if `later` is actually earlier.
+    pub fn between(earlier: Instant, later: Instant) -> Self {
+        Self(later.saturating_duration_since(earlier))
+    }
+
+    pub fn as_duration(&self) -> Duration {
+        self.0
+    }
+}
+
+impl From<Duration> for MonotonicDuration {
+    fn from(duration: Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<MonotonicDuration> for Duration {
+    fn from(duration: MonotonicDuration) -> Self {
+        duration.0
+    }
+}
@@ -0,0 +1,52 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::ffi::c_void;
+
+/// A value, usually a pointer, which is passed via the FFI without UniFFI interpreting it.
+///
+/// This is for things like a handle from a C library, which the foreign code needs to hold on to
+/// and give back later.  It's passed as a `u64`, and the foreign code gets a small wrapper type
+/// around that integer, so it can't be mixed up with other integers.
+///
+/// UniFFI never dereferences the handle, frees it or keeps track of it.  Keeping whatever it
+/// points to alive for as long as the foreign code might pass it back, and freeing it afterwards,
+/// is up to you.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct OpaqueHandle(u64);
+
+impl OpaqueHandle {
+    pub const fn from_raw(raw: u64) -> Self {
+        Self(raw)
+    }
+
+    pub const fn as_raw(&self) -> u64 {
+        self.0
+    }
+
+    pub fn from_ptr(ptr: *mut c_void) -> Self {
+        Self(ptr as usize as u64)
+    }
+
+    /// The handle as a pointer.
+    ///
+    /// Creating the pointer is safe, but the handle might have come from the foreign code, so
+    /// check it's one you handed out before dereferencing it.
+    pub fn as_ptr(&self) -> *mut c_void {
+        self.0 as usize as *mut c_void
+    }
+}
+
+impl From<*mut c_void> for OpaqueHandle {
+    fn from(ptr: *mut c_void) -> Self {
+        Self::from_ptr(ptr)
+    }
+}
+
+impl From<OpaqueHandle> for *mut c_void {
+    fn from(handle: OpaqueHandle) -> Self {
+        handle.as_ptr()
+    }
+}
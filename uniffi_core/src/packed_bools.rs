@@ -0,0 +1,63 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::{check_remaining, Result};
+use anyhow::bail;
+use bytes::Buf;
+
+/// Write `bool` fields of a `[Packed]` record, packed into bytes.
+///
+/// Each byte holds up to 8 values, starting from the least significant bit.  The unused bits of
+/// the last byte are zero.
+pub fn write_packed_bools(bools: &[bool], buf: &mut Vec<u8>) {
+    for chunk in bools.chunks(8) {
+        let byte = chunk
+            .iter()
+            .enumerate()
+            .fold(0u8, |byte, (bit, &b)| byte | (u8::from(b) << bit));
+        buf.push(byte);
+    }
+}
+
+/// Read `N` `bool` fields of a `[Packed]` record, written by [write_packed_bools].
+pub fn read_packed_bools<const N: usize>(buf: &mut &[u8]) -> Result<[bool; N]> {
+    let mut bools = [false; N];
+    for chunk in bools.chunks_mut(8) {
+        check_remaining(buf, 1)?;
+        let byte = buf.get_u8();
+        if u16::from(byte) >> chunk.len() != 0 {
+            bail!("unexpected bits set in packed booleans ({byte:#010b})");
+        }
+        for (bit, b) in chunk.iter_mut().enumerate() {
+            *b = byte & (1 << bit) != 0;
+        }
+    }
+    Ok(bools)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn packed_bools_roundtrip() {
+        let bools = [true, false, false, true, true, false, true, false, true];
+        let mut buf = Vec::new();
+        write_packed_bools(&bools, &mut buf);
+        assert_eq!(buf, [0b0101_1001, 0b0000_0001]);
+        assert_eq!(read_packed_bools::<9>(&mut buf.as_slice()).unwrap(), bools);
+
+        let mut buf = Vec::new();
+        write_packed_bools(&[true; 8], &mut buf);
+        assert_eq!(buf, [0xff]);
+        assert_eq!(
+            read_packed_bools::<8>(&mut buf.as_slice()).unwrap(),
+            [true; 8]
+        );
+
+        // Bits past the last value must be zero.
+        assert!(read_packed_bools::<3>(&mut [0b0000_1000].as_slice()).is_err());
+        assert!(read_packed_bools::<9>(&mut [0xff].as_slice()).is_err());
+    }
+}
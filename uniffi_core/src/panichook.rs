@@ -1,3 +1,8 @@
+use std::{
+    cell::RefCell,
+    sync::{Arc, RwLock},
+};
+
 /// Initialize our panic handling hook to optionally log panics
 #[cfg(feature = "log_panics")]
 pub fn ensure_setup() {
@@ -27,8 +32,96 @@ pub fn ensure_setup() {
             }
         }));
     });
+    #[cfg(feature = "panic-locations")]
+    capture_panic_locations();
 }
 
 /// Initialize our panic handling hook to optionally log panics
 #[cfg(not(feature = "log_panics"))]
-pub fn ensure_setup() {}
+pub fn ensure_setup() {
+    #[cfg(feature = "panic-locations")]
+    capture_panic_locations();
+}
+
+thread_local! {
+    // The location of the last panic on this thread, set by the hook that
+    // `capture_panic_locations` installs.
+    static PANIC_LOCATION: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Include the location of panics in the errors passed to the foreign code.
+///
+/// This installs a panic hook which records the file and line of each panic, then calls the
+/// previously installed hook.  The hook is only installed once, no matter how many times this is
+/// called.  Enabling the `panic-locations` feature calls this before every Rust call.
+pub fn capture_panic_locations() {
+    use std::sync::Once;
+    static INIT_PANIC_LOCATIONS: Once = Once::new();
+    INIT_PANIC_LOCATIONS.call_once(|| {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            if let Some(loc) = panic_info.location() {
+                let location = format!("{}:{}:{}", loc.file(), loc.line(), loc.column());
+                PANIC_LOCATION.with(|l| *l.borrow_mut() = Some(location));
+            }
+            previous_hook(panic_info);
+        }));
+    });
+}
+
+/// Take the location of the last panic on this thread, if it was captured.
+pub(crate) fn take_panic_location() -> Option<String> {
+    PANIC_LOCATION.with(|l| l.borrow_mut().take())
+}
+
+type PanicCallback = dyn Fn(&str, Option<&str>) + Send + Sync;
+
+static PANIC_CALLBACK: RwLock<Option<Arc<PanicCallback>>> = RwLock::new(None);
+
+thread_local! {
+    // The backtrace of the last panic on this thread, set by the hook that `set_panic_callback`
+    // installs.
+    static PANIC_BACKTRACE: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Report the panics caught when calling into Rust, for example to send them to a crash reporter.
+///
+/// `callback` is called with the message of each panic which is turned into an error for the
+/// foreign code, along with its backtrace.  It's called once per panic, on the thread that
+/// panicked, before the error is returned.  This replaces any previously set callback.
+///
+/// This installs a panic hook which captures the backtrace, then calls the previously installed
+/// hook.  The backtrace is `None` if it couldn't be captured, for example on platforms without
+/// backtrace support.
+pub fn set_panic_callback(callback: impl Fn(&str, Option<&str>) + Send + Sync + 'static) {
+    use std::sync::Once;
+    static INIT_PANIC_BACKTRACES: Once = Once::new();
+    INIT_PANIC_BACKTRACES.call_once(|| {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            let backtrace = std::backtrace::Backtrace::force_capture();
+            let backtrace = (backtrace.status() == std::backtrace::BacktraceStatus::Captured)
+                .then(|| backtrace.to_string());
+            PANIC_BACKTRACE.with(|b| *b.borrow_mut() = backtrace);
+            previous_hook(panic_info);
+        }));
+    });
+    *PANIC_CALLBACK.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(callback));
+}
+
+/// Pass a panic which was caught when calling into Rust to the callback set by
+/// [`set_panic_callback`], if there is one.
+pub(crate) fn report_panic(message: &str) {
+    let backtrace = PANIC_BACKTRACE.with(|b| b.borrow_mut().take());
+    // Release the lock before the call, so the callback can call into Rust itself.
+    let callback = PANIC_CALLBACK
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    if let Some(callback) = callback {
+        // A panic in the callback itself can't be reported, so it's ignored.
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            callback(message, backtrace.as_deref())
+        }));
+    }
+}
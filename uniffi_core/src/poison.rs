@@ -0,0 +1,334 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Poisoned object state
+//!
+//! Panics in exported functions are caught and thrown as an internal error in the foreign code,
+//! but the object they were called on stays alive.  If the panic happened while a
+//! `std::sync::Mutex` in the object was locked, the mutex is poisoned and every later call which
+//! unwraps the lock panics too.
+//!
+//! [Mutex] is a mutex for the state of exported objects, which either recovers from the poisoning
+//! or returns a [StatePoisoned] error, depending on its [OnPoison] policy.  The crate's error
+//! types can convert [StatePoisoned] into one of their variants, so the foreign code gets a
+//! specific error rather than an internal one.
+//!
+//! Interfaces marked `[OnPoison=error]` or `[OnPoison=recover]` in the UDL set the policy for the
+//! calls to their methods, with [call_with_on_poison].  Mutexes created with [Mutex::new] follow
+//! it, and a call which panics after locking a poisoned mutex, for example by unwrapping the lock,
+//! fails with [crate::RustCallStatusCode::StatePoisoned] rather than as an internal error.
+
+use std::{
+    cell::Cell,
+    error::Error,
+    fmt,
+    ops::{Deref, DerefMut},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        PoisonError,
+    },
+};
+
+/// What [Mutex::lock] does when a panic poisoned the mutex while it was locked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnPoison {
+    /// Return [StatePoisoned], from this call and every later one.
+    #[default]
+    Error,
+    /// Clear the poisoning and lock the mutex as usual.  Use this if the state is still valid
+    /// after any panic, for example because it's only changed in a single step.
+    Recover,
+}
+
+/// The error returned by [Mutex::lock] when a panic poisoned the mutex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatePoisoned;
+
+impl fmt::Display for StatePoisoned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("state poisoned by a panic in an earlier call")
+    }
+}
+
+impl Error for StatePoisoned {}
+
+#[derive(Clone, Copy)]
+struct PoisonCall {
+    on_poison: OnPoison,
+    locked_poisoned: bool,
+}
+
+thread_local! {
+    // The policy of the method call in progress on this thread, if its interface has one.
+    static POISON_CALL: Cell<Option<PoisonCall>> = const { Cell::new(None) };
+}
+
+/// Call a method of an interface with an [OnPoison] policy.
+///
+/// During the call, mutexes created with [Mutex::new] follow `on_poison`.  If `f` panics after
+/// one of them returned [StatePoisoned], the panic is replaced by a [StatePoisoned] payload, which
+/// `rust_call` turns into [crate::RustCallStatusCode::StatePoisoned].
+pub fn call_with_on_poison<R>(on_poison: OnPoison, f: impl FnOnce() -> R) -> R {
+    let outer = POISON_CALL.with(|c| {
+        c.replace(Some(PoisonCall {
+            on_poison,
+            locked_poisoned: false,
+        }))
+    });
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let call = POISON_CALL.with(|c| c.replace(outer));
+    match result {
+        Ok(v) => v,
+        Err(_)
+            if matches!(
+                call,
+                Some(PoisonCall {
+                    locked_poisoned: true,
+                    ..
+                })
+            ) =>
+        {
+            panic::resume_unwind(Box::new(StatePoisoned))
+        }
+        Err(cause) => panic::resume_unwind(cause),
+    }
+}
+
+/// A mutex for the state of exported objects, with a policy for when it's poisoned.
+///
+/// This tracks the poisoning itself, so that [OnPoison::Recover] can clear it.
+#[derive(Debug, Default)]
+pub struct Mutex<T: ?Sized> {
+    // `None` to follow the policy of the method call, see [call_with_on_poison].
+    on_poison: Option<OnPoison>,
+    poisoned: AtomicBool,
+    inner: std::sync::Mutex<T>,
+}
+
+impl<T> Mutex<T> {
+    /// Create a mutex which follows the [OnPoison] policy of the interface whose method locks it,
+    /// and otherwise returns [StatePoisoned] once it's poisoned.
+    pub const fn new(value: T) -> Self {
+        Self {
+            on_poison: None,
+            poisoned: AtomicBool::new(false),
+            inner: std::sync::Mutex::new(value),
+        }
+    }
+
+    /// Create a mutex with the given policy for when it's poisoned.
+    pub const fn with_on_poison(value: T, on_poison: OnPoison) -> Self {
+        Self {
+            on_poison: Some(on_poison),
+            poisoned: AtomicBool::new(false),
+            inner: std::sync::Mutex::new(value),
+        }
+    }
+
+    /// Consume the mutex and return its value, whether or not it's poisoned.
+    pub fn into_inner(self) -> T {
+        self.inner
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: ?Sized> Mutex<T> {
+    /// Lock the mutex, blocking until it's available.
+    ///
+    /// If a panic poisoned the mutex, this follows its [OnPoison] policy.
+    pub fn lock(&self) -> Result<MutexGuard<'_, T>, StatePoisoned> {
+        let guard = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        if self.poisoned.load(Ordering::Relaxed) {
+            let call = POISON_CALL.with(Cell::get);
+            let on_poison = self
+                .on_poison
+                .or(call.map(|call| call.on_poison))
+                .unwrap_or_default();
+            match on_poison {
+                OnPoison::Error => {
+                    if let Some(call) = call {
+                        POISON_CALL.with(|c| {
+                            c.set(Some(PoisonCall {
+                                locked_poisoned: true,
+                                ..call
+                            }))
+                        });
+                    }
+                    return Err(StatePoisoned);
+                }
+                OnPoison::Recover => self.poisoned.store(false, Ordering::Relaxed),
+            }
+        }
+        Ok(MutexGuard { mutex: self, guard })
+    }
+
+    /// Whether a panic poisoned the mutex, which hasn't been cleared yet.
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned.load(Ordering::Relaxed)
+    }
+
+    /// Clear the poisoning, for a mutex which returns [StatePoisoned] until its state is reset.
+    pub fn clear_poison(&self) {
+        self.poisoned.store(false, Ordering::Relaxed)
+    }
+}
+
+/// The guard returned by [Mutex::lock], which unlocks the mutex when it's dropped.
+///
+/// Dropping the guard while panicking poisons the mutex.
+pub struct MutexGuard<'a, T: ?Sized> {
+    mutex: &'a Mutex<T>,
+    guard: std::sync::MutexGuard<'a, T>,
+}
+
+impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T: ?Sized> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        // This runs before `guard` is dropped, so the flag is set before the mutex is unlocked.
+        if std::thread::panicking() {
+            self.mutex.poisoned.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for MutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{rust_call, RustCallStatus, RustCallStatusCode};
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum CounterError {
+        StatePoisoned,
+    }
+
+    impl From<StatePoisoned> for CounterError {
+        fn from(_: StatePoisoned) -> Self {
+            Self::StatePoisoned
+        }
+    }
+
+    struct Counter {
+        value: Mutex<u32>,
+    }
+
+    impl Counter {
+        fn increment(&self) -> Result<u32, CounterError> {
+            let mut value = self.value.lock()?;
+            *value += 1;
+            Ok(*value)
+        }
+
+        fn increment_and_panic(&self) -> Result<u32, CounterError> {
+            let mut value = self.value.lock()?;
+            *value += 1;
+            panic!("increment_and_panic");
+        }
+    }
+
+    // Call a method like the scaffolding does, where the panic is caught.
+    fn call_panicking_method(counter: &Counter) {
+        let mut status = RustCallStatus::default();
+        rust_call(&mut status, || {
+            counter.increment_and_panic().unwrap();
+            Ok(())
+        });
+        assert_eq!(status.code, RustCallStatusCode::UnexpectedError);
+    }
+
+    #[test]
+    fn test_poisoned_state_error() {
+        let counter = Counter {
+            value: Mutex::new(0),
+        };
+        assert_eq!(counter.increment(), Ok(1));
+        call_panicking_method(&counter);
+        assert!(counter.value.is_poisoned());
+        // The later calls get the error, rather than panicking.
+        assert_eq!(counter.increment(), Err(CounterError::StatePoisoned));
+        assert_eq!(counter.increment(), Err(CounterError::StatePoisoned));
+
+        counter.value.clear_poison();
+        assert_eq!(counter.increment(), Ok(3));
+    }
+
+    #[test]
+    fn test_call_on_poison_policy() {
+        let counter = Counter {
+            value: Mutex::new(0),
+        };
+        call_panicking_method(&counter);
+        // Mutexes created with `new` follow the policy of the call.
+        let result = call_with_on_poison(OnPoison::Recover, || counter.increment());
+        assert_eq!(result, Ok(2));
+        // A mutex's own policy takes precedence.
+        let counter = Counter {
+            value: Mutex::with_on_poison(0, OnPoison::Error),
+        };
+        call_panicking_method(&counter);
+        let result = call_with_on_poison(OnPoison::Recover, || counter.increment());
+        assert_eq!(result, Err(CounterError::StatePoisoned));
+    }
+
+    #[test]
+    fn test_call_state_poisoned_status() {
+        let counter = Counter {
+            value: Mutex::new(0),
+        };
+        call_panicking_method(&counter);
+        // Unwrapping the poisoned lock fails the call with its own status.
+        let mut status = RustCallStatus::default();
+        rust_call(&mut status, || {
+            call_with_on_poison(OnPoison::Error, || counter.increment().unwrap());
+            Ok(())
+        });
+        assert_eq!(status.code, RustCallStatusCode::StatePoisoned);
+        let message = unsafe { status.error_buf.assume_init() };
+        assert_eq!(
+            <String as crate::Lift<crate::UniFfiTag>>::try_lift(message).unwrap(),
+            "state poisoned by a panic in an earlier call"
+        );
+
+        // Other panics are still unexpected errors.
+        let mut status = RustCallStatus::default();
+        rust_call(&mut status, || {
+            call_with_on_poison(OnPoison::Error, || panic!("unrelated"));
+            Ok(())
+        });
+        assert_eq!(status.code, RustCallStatusCode::UnexpectedError);
+    }
+
+    #[test]
+    fn test_poisoned_state_recover() {
+        let counter = Counter {
+            value: Mutex::with_on_poison(0, OnPoison::Recover),
+        };
+        call_panicking_method(&counter);
+        assert!(counter.value.is_poisoned());
+        // The change made before the panic is kept.
+        assert_eq!(counter.increment(), Ok(2));
+        assert!(!counter.value.is_poisoned());
+        assert_eq!(counter.value.into_inner(), 2);
+    }
+}
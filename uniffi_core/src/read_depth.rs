@@ -0,0 +1,121 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::Result;
+use anyhow::bail;
+use std::{
+    cell::Cell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The default value for [max_read_depth].
+pub const DEFAULT_MAX_READ_DEPTH: usize = 256;
+
+static MAX_READ_DEPTH: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_READ_DEPTH);
+
+thread_local! {
+    // How deeply nested the container currently being read on this thread is.
+    static READ_DEPTH: Cell<usize> = Cell::new(0);
+}
+
+/// The maximum nesting depth of containers that we'll accept when reading data from a buffer.
+pub fn max_read_depth() -> usize {
+    MAX_READ_DEPTH.load(Ordering::Relaxed)
+}
+
+/// Set the maximum nesting depth of containers that we'll accept when reading data from a buffer.
+///
+/// Reading nested containers recurses, so deeply nested data from the foreign code, for example a
+/// long chain of recursive records, could otherwise overflow the stack.  Data nested deeper than
+/// this value results in a lift error instead.
+pub fn set_max_read_depth(depth: usize) {
+    MAX_READ_DEPTH.store(depth, Ordering::Relaxed)
+}
+
+/// Tracks the nesting depth while reading a container from a buffer.
+///
+/// `try_read` implementations for containers should hold one of these while they read their
+/// items.  The depth is decremented again when it's dropped, including when reading fails.
+pub struct ReadDepthGuard {
+    _private: (),
+}
+
+impl ReadDepthGuard {
+    /// Enter a nested container, failing if that exceeds [max_read_depth].
+    pub fn enter() -> Result<Self> {
+        let depth = READ_DEPTH.with(|d| d.get()) + 1;
+        let max_depth = max_read_depth();
+        if depth > max_depth {
+            bail!("nesting depth exceeds the maximum allowed ({depth} > {max_depth})");
+        }
+        READ_DEPTH.with(|d| d.set(depth));
+        Ok(Self { _private: () })
+    }
+}
+
+impl Drop for ReadDepthGuard {
+    fn drop(&mut self) {
+        READ_DEPTH.with(|d| d.set(d.get() - 1));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, Lower, MetadataBuffer, RustBuffer, UniFfiTag};
+
+    // A recursive type, to build arbitrarily deeply nested data.
+    #[derive(Debug)]
+    struct Tree(Vec<Tree>);
+
+    unsafe impl Lift<UniFfiTag> for Tree {
+        type FfiType = RustBuffer;
+
+        fn try_read(buf: &mut &[u8]) -> Result<Tree> {
+            <Vec<Tree> as Lift<UniFfiTag>>::try_read(buf).map(Tree)
+        }
+
+        fn try_lift(buf: RustBuffer) -> Result<Tree> {
+            Self::try_lift_from_rust_buffer(buf)
+        }
+
+        const TYPE_ID_META: MetadataBuffer = MetadataBuffer::new();
+    }
+
+    // A tree which is `depth` levels deep, with a single child at each level.
+    fn nested_tree_buf(depth: usize) -> RustBuffer {
+        let mut buf = Vec::new();
+        for _ in 1..depth {
+            buf.extend_from_slice(&1i32.to_be_bytes());
+        }
+        buf.extend_from_slice(&0i32.to_be_bytes());
+        RustBuffer::from_vec(buf)
+    }
+
+    #[test]
+    fn lift_under_max_read_depth() {
+        let tree = <Tree as Lift<UniFfiTag>>::try_lift(nested_tree_buf(DEFAULT_MAX_READ_DEPTH))
+            .expect("Failed to lift!");
+        assert_eq!(tree.0.len(), 1);
+    }
+
+    #[test]
+    fn lift_over_max_read_depth() {
+        let err = <Tree as Lift<UniFfiTag>>::try_lift(nested_tree_buf(DEFAULT_MAX_READ_DEPTH + 1))
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("nesting depth exceeds the maximum allowed"),
+            "unexpected error: {err}"
+        );
+        // The depth is reset after the failure, so the next lift starts from the top level again.
+        let values =
+            <Option<Vec<u8>> as Lift<UniFfiTag>>::try_lift(
+                <Option<Vec<u8>> as Lower<UniFfiTag>>::lower(Some(vec![1])),
+            )
+            .expect("Failed to lift!");
+        assert_eq!(values, Some(vec![1]));
+        assert_eq!(READ_DEPTH.with(|d| d.get()), 0);
+    }
+}
@@ -0,0 +1,231 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Configuring the runtime in one call
+//!
+//! Each runtime setting has its own function, like [crate::set_async_runtime] or
+//! [crate::set_string_policy].  A [RuntimeConfig] collects any of them, and [initialize] applies
+//! them together.  The settings which can fail are checked first, so that either all of the
+//! settings are applied or none of them are.
+//!
+//! Components use [export_runtime_config!](crate::export_runtime_config) to export a
+//! `uniffi_initialize()` function, so that the foreign code can apply the settings it controls in
+//! the same way.  The bindings generate a `UniffiRuntimeConfig` builder for its arguments, and a
+//! `uniffiInitialize(config)` function which calls it.
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::{
+    check_async_runtime, check_rustbuffer_allocator, set_async_runtime, set_float_special,
+    set_max_buffer_len, set_max_read_depth, set_reentrancy_policy, set_rustbuffer_allocator,
+    set_string_policy, AsyncRuntime, FloatSpecial, ReentrancyPolicy, Result, RustBufferAllocator,
+    StringPolicy,
+};
+#[cfg(feature = "tracing")]
+use crate::{set_log_callback, LogLevel, LogRecord};
+
+#[cfg(feature = "tracing")]
+type LogCallback = Arc<dyn Fn(LogRecord) + Send + Sync>;
+
+/// Runtime settings to apply with [initialize].
+///
+/// Each method sets one setting and returns the config, so they can be chained.  Settings which
+/// aren't set are left unchanged by [initialize].
+#[derive(Default, Clone)]
+pub struct RuntimeConfig {
+    async_runtime: Option<Arc<dyn AsyncRuntime>>,
+    rustbuffer_allocator: Option<&'static dyn RustBufferAllocator>,
+    #[cfg(feature = "tracing")]
+    log_callback: Option<(LogCallback, LogLevel)>,
+    string_policy: Option<StringPolicy>,
+    float_special: Option<FloatSpecial>,
+    reentrancy_policy: Option<ReentrancyPolicy>,
+    max_buffer_len: Option<usize>,
+    max_read_depth: Option<usize>,
+}
+
+impl RuntimeConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The runtime that exported async functions are spawned on, see [crate::set_async_runtime].
+    pub fn async_runtime(mut self, runtime: impl AsyncRuntime) -> Self {
+        self.async_runtime = Some(Arc::new(runtime));
+        self
+    }
+
+    /// The allocator for the storage backing `RustBuffer`s, see [crate::set_rustbuffer_allocator].
+    pub fn rustbuffer_allocator(mut self, allocator: &'static dyn RustBufferAllocator) -> Self {
+        self.rustbuffer_allocator = Some(allocator);
+        self
+    }
+
+    /// The callback which the `tracing` events at `max_level` or more severe are passed to, see
+    /// [crate::set_log_callback].
+    #[cfg(feature = "tracing")]
+    pub fn log_callback(
+        mut self,
+        callback: impl Fn(LogRecord) + Send + Sync + 'static,
+        max_level: LogLevel,
+    ) -> Self {
+        self.log_callback = Some((Arc::new(callback), max_level));
+        self
+    }
+
+    /// How invalid UTF-8 is handled when lifting strings, see [crate::set_string_policy].
+    pub fn string_policy(mut self, policy: StringPolicy) -> Self {
+        self.string_policy = Some(policy);
+        self
+    }
+
+    /// How non-finite floats are handled, see [crate::set_float_special].
+    pub fn float_special(mut self, policy: FloatSpecial) -> Self {
+        self.float_special = Some(policy);
+        self
+    }
+
+    /// How calls that re-enter Rust are handled, see [crate::set_reentrancy_policy].
+    pub fn reentrancy_policy(mut self, policy: ReentrancyPolicy) -> Self {
+        self.reentrancy_policy = Some(policy);
+        self
+    }
+
+    /// The maximum length accepted when lifting data, see [crate::set_max_buffer_len].
+    pub fn max_buffer_len(mut self, len: usize) -> Self {
+        self.max_buffer_len = Some(len);
+        self
+    }
+
+    /// The maximum nesting depth accepted when lifting data, see [crate::set_max_read_depth].
+    pub fn max_read_depth(mut self, depth: usize) -> Self {
+        self.max_read_depth = Some(depth);
+        self
+    }
+}
+
+// Held while a config is applied, so that concurrent calls don't interleave their settings.
+static INITIALIZE: Mutex<()> = Mutex::new(());
+
+/// Apply the settings in `config`.
+///
+/// The async runtime and the `RustBuffer` allocator can only be set once, and the allocator only
+/// before any buffer is allocated.  Those are checked before anything is applied, so if either
+/// can't be set this returns an error and no setting is changed.
+pub fn initialize(config: RuntimeConfig) -> Result<()> {
+    let _guard = INITIALIZE.lock().unwrap_or_else(PoisonError::into_inner);
+    if config.async_runtime.is_some() {
+        check_async_runtime()?;
+    }
+    if config.rustbuffer_allocator.is_some() {
+        check_rustbuffer_allocator()?;
+    }
+
+    // A buffer can be allocated at any moment, so the allocator's check can still go stale.
+    // Installing it first means nothing else has been changed if it does.
+    if let Some(allocator) = config.rustbuffer_allocator {
+        set_rustbuffer_allocator(allocator)?;
+    }
+    if let Some(runtime) = config.async_runtime {
+        set_async_runtime(move |task| runtime.spawn(task))?;
+    }
+    #[cfg(feature = "tracing")]
+    if let Some((callback, max_level)) = config.log_callback {
+        set_log_callback(move |record| callback(record), max_level);
+    }
+    if let Some(policy) = config.string_policy {
+        set_string_policy(policy);
+    }
+    if let Some(policy) = config.float_special {
+        set_float_special(policy);
+    }
+    if let Some(policy) = config.reentrancy_policy {
+        set_reentrancy_policy(policy);
+    }
+    if let Some(len) = config.max_buffer_len {
+        set_max_buffer_len(len);
+    }
+    if let Some(depth) = config.max_read_depth {
+        set_max_read_depth(depth);
+    }
+    Ok(())
+}
+
+/// Export a `uniffi_initialize()` function, which the bindings call with the settings of their
+/// `UniffiRuntimeConfig` builder.
+///
+/// The bindings for Kotlin, Swift and Python don't expose the function itself.  Instead, they
+/// generate a `UniffiRuntimeConfig` class with a chainable setter for each of its arguments, and
+/// a `uniffiInitialize(config)` function which applies the settings with a single call:
+///
+///   - `logSink(sink)` and `maxLogLevel(level)`, like `set_log_sink()`.  The level is `Info` by
+///     default.
+///   - `stringPolicy(policy)`, with the exported `StringPolicy` enum.
+///   - `maxBufferLen(len)` and `maxReadDepth(depth)`.
+///
+/// This also exports everything [export_log_sink!](crate::export_log_sink) does, so it replaces
+/// that macro rather than being used with it.  It needs the `tracing` feature of the `uniffi`
+/// crate.
+#[cfg(feature = "tracing")]
+#[macro_export]
+macro_rules! export_runtime_config {
+    () => {
+        $crate::export_log_sink!();
+
+        /// How invalid UTF-8 is handled when Rust lifts strings.
+        #[derive(::uniffi::Enum)]
+        pub enum StringPolicy {
+            /// Reject invalid UTF-8, which fails the call.
+            Strict,
+            /// Replace each invalid sequence with U+FFFD.
+            Lossy,
+        }
+
+        impl ::std::convert::From<StringPolicy> for $crate::StringPolicy {
+            fn from(policy: StringPolicy) -> Self {
+                match policy {
+                    StringPolicy::Strict => Self::Strict,
+                    StringPolicy::Lossy => Self::Lossy,
+                }
+            }
+        }
+
+        /// Apply the settings of a `UniffiRuntimeConfig` together.  Settings which aren't set are
+        /// left unchanged.
+        #[::uniffi::export(runtime_initializer)]
+        pub fn uniffi_initialize(
+            log_sink: ::std::option::Option<::std::boxed::Box<dyn LogSink>>,
+            max_log_level: ::std::option::Option<LogLevel>,
+            string_policy: ::std::option::Option<StringPolicy>,
+            max_buffer_len: ::std::option::Option<u64>,
+            max_read_depth: ::std::option::Option<u32>,
+        ) {
+            let mut config = $crate::RuntimeConfig::new();
+            if let ::std::option::Option::Some(sink) = log_sink {
+                let max_level =
+                    max_log_level.map_or($crate::LogLevel::Info, ::std::convert::Into::into);
+                config = config.log_callback(
+                    move |record: $crate::LogRecord| {
+                        sink.log(record.level.into(), record.target, record.message)
+                    },
+                    max_level,
+                );
+            }
+            if let ::std::option::Option::Some(policy) = string_policy {
+                config = config.string_policy(policy.into());
+            }
+            if let ::std::option::Option::Some(len) = max_buffer_len {
+                let len =
+                    <usize as ::std::convert::TryFrom<u64>>::try_from(len).unwrap_or(usize::MAX);
+                config = config.max_buffer_len(len);
+            }
+            if let ::std::option::Option::Some(depth) = max_read_depth {
+                config = config.max_read_depth(depth as usize);
+            }
+            // Only the async runtime and the allocator can fail, and they're not exposed to the
+            // foreign code.
+            $crate::initialize(config).expect("runtime settings can always be applied");
+        }
+    };
+}
@@ -0,0 +1,183 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Interning of lifted strings.
+//!
+//! Lifting a `String` always allocates, so lifting many records which share the same field
+//! values allocates the same string over and over.  Lifting an `Arc<str>` instead can share a
+//! single allocation between all the equal strings, when string interning is enabled with
+//! [`with_string_interner`].
+//!
+//! The interner is owned by the `with_string_interner` call and only lives as long as it does.
+//! The lifted `Arc<str>`s are normal reference-counted strings, so they can outlive the interner.
+//! Strings are compared by their full contents, including their length, so a string is never
+//! shared with a different string that it's a prefix of.
+
+use std::{cell::RefCell, collections::HashSet, sync::Arc};
+
+/// A bounded set of interned strings.
+#[derive(Debug)]
+pub struct StringInterner {
+    strings: HashSet<Arc<str>>,
+    max_entries: usize,
+}
+
+impl StringInterner {
+    /// Create an interner that stores at most `max_entries` distinct strings.
+    ///
+    /// Once the interner is full, strings that aren't already stored are still returned, but
+    /// aren't shared.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            strings: HashSet::new(),
+            max_entries,
+        }
+    }
+
+    /// Get the shared copy of `s`, storing it if this is the first time it's been interned.
+    pub fn intern(&mut self, s: &str) -> Arc<str> {
+        if let Some(interned) = self.strings.get(s) {
+            return Arc::clone(interned);
+        }
+        let interned: Arc<str> = Arc::from(s);
+        if self.strings.len() < self.max_entries {
+            self.strings.insert(Arc::clone(&interned));
+        }
+        interned
+    }
+
+    /// The number of distinct strings stored in the interner.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+thread_local! {
+    static ACTIVE_INTERNER: RefCell<Option<StringInterner>> = RefCell::new(None);
+}
+
+/// Run `f` with string interning enabled on the current thread.
+///
+/// Equal `Arc<str>` values lifted while `f` runs, for example from a callback interface return
+/// value, share a single allocation.  A new interner, which stores at most `max_entries` strings,
+/// is used for each call and dropped when `f` returns.
+pub fn with_string_interner<R>(max_entries: usize, f: impl FnOnce() -> R) -> R {
+    // Restore the previous interner when we're done, even if `f` panics.
+    struct Restore(Option<StringInterner>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            ACTIVE_INTERNER.with(|active| *active.borrow_mut() = previous);
+        }
+    }
+
+    let previous =
+        ACTIVE_INTERNER.with(|active| active.replace(Some(StringInterner::new(max_entries))));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Convert a lifted string into an `Arc<str>`, using the active interner if there is one.
+pub(crate) fn lift_str(s: &str) -> Arc<str> {
+    ACTIVE_INTERNER.with(|active| match active.borrow_mut().as_mut() {
+        Some(interner) => interner.intern(s),
+        None => Arc::from(s),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Lift, Lower};
+    use std::collections::HashSet;
+
+    fn lift_tags(buf: &[u8]) -> Vec<Arc<str>> {
+        <Vec<Arc<str>> as Lift<()>>::try_read(&mut &buf[..]).unwrap()
+    }
+
+    fn distinct_allocations(strings: &[Arc<str>]) -> usize {
+        strings
+            .iter()
+            .map(|s| s.as_ptr())
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    #[test]
+    fn test_interning_shares_allocations() {
+        let tags: Vec<String> = (0..1000)
+            .map(|i| ["alpha", "beta", "gamma"][i % 3].to_string())
+            .collect();
+        let mut buf = vec![];
+        <Vec<String> as Lower<()>>::write(tags.clone(), &mut buf);
+
+        let lifted = lift_tags(&buf);
+        assert_eq!(distinct_allocations(&lifted), 1000);
+
+        let interned = with_string_interner(16, || lift_tags(&buf));
+        assert_eq!(distinct_allocations(&interned), 3);
+        assert_eq!(
+            interned.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+            tags
+        );
+
+        // The interner is dropped at the end of the call, but the strings live on.
+        assert_eq!(Arc::strong_count(&interned[0]), 334);
+        assert_eq!(distinct_allocations(&lift_tags(&buf)), 1000);
+    }
+
+    #[test]
+    fn test_interning_bounded() {
+        let tags: Vec<String> = (0..100).map(|i| format!("tag-{}", i % 10)).collect();
+        let mut buf = vec![];
+        <Vec<String> as Lower<()>>::write(tags, &mut buf);
+
+        // Only the first 4 distinct strings are shared, the others get their own allocations.
+        let interned = with_string_interner(4, || lift_tags(&buf));
+        assert_eq!(distinct_allocations(&interned), 4 + 60);
+    }
+
+    #[test]
+    fn test_interning_compares_lengths() {
+        let mut interner = StringInterner::new(16);
+        let abc = interner.intern("abc");
+        let ab = interner.intern("ab");
+        let abcd = interner.intern("abcd");
+        assert_eq!((&*abc, &*ab, &*abcd), ("abc", "ab", "abcd"));
+        assert!(Arc::ptr_eq(&abc, &interner.intern("abc")));
+        assert!(!Arc::ptr_eq(&abc, &abcd));
+        assert_eq!(interner.len(), 3);
+
+        // The same bytes with different length prefixes in a buffer.
+        let mut buf = vec![];
+        <Vec<String> as Lower<()>>::write(
+            vec!["abcd".into(), "abc".into(), "abcd".into(), "".into()],
+            &mut buf,
+        );
+        let interned = with_string_interner(16, || lift_tags(&buf));
+        assert_eq!(
+            interned.iter().map(|s| &**s).collect::<Vec<_>>(),
+            vec!["abcd", "abc", "abcd", ""]
+        );
+        assert!(Arc::ptr_eq(&interned[0], &interned[2]));
+        assert!(!Arc::ptr_eq(&interned[0], &interned[1]));
+    }
+
+    #[test]
+    fn test_nested_interners() {
+        with_string_interner(16, || {
+            let outer = lift_str("outer");
+            with_string_interner(16, || {
+                assert!(!Arc::ptr_eq(&outer, &lift_str("outer")));
+            });
+            assert!(Arc::ptr_eq(&outer, &lift_str("outer")));
+        });
+        assert!(ACTIVE_INTERNER.with(|active| active.borrow().is_none()));
+    }
+}
@@ -0,0 +1,46 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::Result;
+use std::{
+    borrow::Cow,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// How invalid UTF-8 is handled when lifting strings from a buffer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum StringPolicy {
+    /// Reject invalid UTF-8, which is a lift error.
+    #[default]
+    Strict,
+    /// Replace each invalid sequence with U+FFFD, as [String::from_utf8_lossy] does.
+    Lossy,
+}
+
+static LOSSY_STRINGS: AtomicBool = AtomicBool::new(false);
+
+/// How invalid UTF-8 is currently handled when lifting strings.
+pub fn string_policy() -> StringPolicy {
+    if LOSSY_STRINGS.load(Ordering::Relaxed) {
+        StringPolicy::Lossy
+    } else {
+        StringPolicy::Strict
+    }
+}
+
+/// Set how invalid UTF-8 is handled when lifting strings.
+///
+/// The bindings always pass valid UTF-8, but foreign code which serializes strings from untrusted
+/// bytes itself may not.  [StringPolicy::Lossy] accepts those strings rather than failing the call.
+pub fn set_string_policy(policy: StringPolicy) {
+    LOSSY_STRINGS.store(policy == StringPolicy::Lossy, Ordering::Relaxed)
+}
+
+/// A helper function to convert bytes read from a buffer to a string, following [string_policy].
+pub fn str_from_utf8(bytes: &[u8]) -> Result<Cow<'_, str>> {
+    match string_policy() {
+        StringPolicy::Strict => Ok(Cow::Borrowed(std::str::from_utf8(bytes)?)),
+        StringPolicy::Lossy => Ok(String::from_utf8_lossy(bytes)),
+    }
+}
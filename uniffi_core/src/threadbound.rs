@@ -0,0 +1,230 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Thread-bound objects
+//!
+//! Objects marked `[ThreadBound]` in the UDL wrap resources which must only be used on the thread
+//! that created them, like a GPU context.  Their constructors return a [BindToCurrentThread], and
+//! the scaffolding calls [check_bound_thread] before each method call.  Calls on any other thread
+//! fail with [crate::RustCallStatusCode::WrongThread], which the bindings throw as a `WrongThread`
+//! error.  Freeing the object is allowed on any thread, since that's up to the foreign language's
+//! garbage collector.
+//!
+//! Other objects are passed to the foreign code as a pointer to their `Arc<T>`.  Thread-bound
+//! objects are passed as a pointer to an `Arc<ThreadBound<T>>` instead, which records the thread
+//! along with the object.  Each handle carries its own binding, so there's no global state to look
+//! it up in, and the object is dropped as soon as the last handle is freed.
+//!
+//! Since only constructors know which thread an object belongs to, they're the only way to pass a
+//! thread-bound object to the foreign code.  Lowering it anywhere else, like a method returning
+//! `Arc<Self>`, a record field or a callback interface argument, is rejected by its
+//! `FfiConverterArc::check_lower`.
+
+use std::{
+    ffi::c_void,
+    sync::Arc,
+    thread::{self, ThreadId},
+};
+
+use crate::{LowerReturn, MetadataBuffer, RustBuffer};
+
+struct Owner {
+    thread_id: ThreadId,
+    thread_name: Option<String>,
+}
+
+impl Owner {
+    fn current() -> Self {
+        let thread = thread::current();
+        Self {
+            thread_id: thread.id(),
+            thread_name: thread.name().map(str::to_owned),
+        }
+    }
+}
+
+fn describe_thread(thread_id: ThreadId, thread_name: Option<&str>) -> String {
+    match thread_name {
+        Some(name) => format!("thread `{name}`"),
+        None => format!("thread {thread_id:?}"),
+    }
+}
+
+/// What the handle of a `[ThreadBound]` object points to
+///
+/// The scaffolding derives the `FfiConverterArc` impl of thread-bound objects from this, rather
+/// than passing the `Arc<T>` itself.
+pub struct ThreadBound<T> {
+    owner: Owner,
+    obj: Arc<T>,
+}
+
+impl<T: Send + Sync + 'static> ThreadBound<T> {
+    /// Get a handle to an object which can only be used on the current thread.
+    pub fn lower_bound_to_current_thread(obj: Arc<T>) -> *const c_void {
+        Self {
+            owner: Owner::current(),
+            obj,
+        }
+        .into_handle()
+    }
+
+    fn into_handle(self) -> *const c_void {
+        Arc::into_raw(Arc::new(self)).cast::<c_void>()
+    }
+
+    /// Take over the reference to the object which the foreign code passed.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must have been returned by [Self::lower_bound_to_current_thread], or cloned from
+    /// one, and not freed yet.
+    pub unsafe fn lift(handle: *const c_void) -> Arc<T> {
+        let bound = Arc::from_raw(handle.cast::<Self>());
+        Arc::clone(&bound.obj)
+    }
+
+    /// Borrow the binding a handle points to, without taking over the foreign code's reference.
+    ///
+    /// # Safety
+    ///
+    /// The same as [Self::lift], and the handle must outlive the borrow.
+    pub unsafe fn borrow<'a>(handle: *const c_void) -> &'a Self {
+        &*handle.cast::<Self>()
+    }
+
+    pub fn object(&self) -> &Arc<T> {
+        &self.obj
+    }
+
+    /// Check that a method of the object is called on the thread the handle is bound to.
+    ///
+    /// `method` is the name of the method for the error message.
+    pub fn check_thread(&self, method: &str) -> Result<(), String> {
+        let owner = &self.owner;
+        let current = thread::current();
+        if owner.thread_id == current.id() {
+            return Ok(());
+        }
+        Err(format!(
+            "`{method}` called on {}, but the object is bound to {}",
+            describe_thread(current.id(), current.name()),
+            describe_thread(owner.thread_id, owner.thread_name.as_deref()),
+        ))
+    }
+}
+
+/// Check that a method of a thread-bound object is called on the thread its handle is bound to.
+///
+/// # Safety
+///
+/// `handle` must be a handle to a `T`, as passed by the foreign code for the call.
+pub unsafe fn check_bound_thread<T: Send + Sync + 'static>(
+    handle: *const c_void,
+    method: &str,
+) -> Result<(), String> {
+    ThreadBound::<T>::borrow(handle).check_thread(method)
+}
+
+/// The return value of a thread-bound object's constructor, which binds the handle returned to
+/// the foreign code to the current thread.
+///
+/// This is only used for objects whose `FfiConverterArc` impl is derived from [ThreadBound].
+pub struct BindToCurrentThread<R>(pub R);
+
+unsafe impl<UT, T> LowerReturn<UT> for BindToCurrentThread<Arc<T>>
+where
+    T: Send + Sync + 'static,
+    Arc<T>: LowerReturn<UT, ReturnType = *const c_void>,
+{
+    type ReturnType = *const c_void;
+
+    fn lower_return(v: Self) -> Result<Self::ReturnType, RustBuffer> {
+        Ok(ThreadBound::lower_bound_to_current_thread(v.0))
+    }
+
+    fn handle_failed_lift(arg_name: &str, e: anyhow::Error) -> Self {
+        Self(<Arc<T> as LowerReturn<UT>>::handle_failed_lift(arg_name, e))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Arc<T> as LowerReturn<UT>>::TYPE_ID_META;
+}
+
+unsafe impl<UT, T, E> LowerReturn<UT> for BindToCurrentThread<Result<Arc<T>, E>>
+where
+    T: Send + Sync + 'static,
+    Result<Arc<T>, E>: LowerReturn<UT, ReturnType = *const c_void>,
+{
+    type ReturnType = *const c_void;
+
+    fn lower_return(v: Self) -> Result<Self::ReturnType, RustBuffer> {
+        match v.0 {
+            Ok(obj) => Ok(ThreadBound::lower_bound_to_current_thread(obj)),
+            Err(e) => <Result<Arc<T>, E> as LowerReturn<UT>>::lower_return(Err(e)),
+        }
+    }
+
+    // Only the error is checked, since the object is the one thing that may be thread-bound.
+    fn check_return(v: &Self) -> anyhow::Result<()> {
+        match &v.0 {
+            Ok(_) => Ok(()),
+            Err(_) => <Result<Arc<T>, E> as LowerReturn<UT>>::check_return(&v.0),
+        }
+    }
+
+    fn handle_failed_lift(arg_name: &str, e: anyhow::Error) -> Self {
+        Self(<Result<Arc<T>, E> as LowerReturn<UT>>::handle_failed_lift(
+            arg_name, e,
+        ))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = <Result<Arc<T>, E> as LowerReturn<UT>>::TYPE_ID_META;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Handles can't be sent to other threads, but their addresses can.
+    fn check_on_worker(handle: *const c_void) -> Result<(), String> {
+        let addr = handle as usize;
+        thread::Builder::new()
+            .name("worker".into())
+            .spawn(move || unsafe {
+                check_bound_thread::<u32>(addr as *const c_void, "Test.method")
+            })
+            .unwrap()
+            .join()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_bound_thread() {
+        let handle = ThreadBound::lower_bound_to_current_thread(Arc::new(42u32));
+        assert_eq!(
+            unsafe { check_bound_thread::<u32>(handle, "Test.method") },
+            Ok(())
+        );
+        let err = check_on_worker(handle).unwrap_err();
+        assert!(
+            err.starts_with(
+                "`Test.method` called on thread `worker`, but the object is bound to thread"
+            ),
+            "{err}"
+        );
+        drop(unsafe { ThreadBound::<u32>::lift(handle) });
+    }
+
+    #[test]
+    fn test_lift_takes_over_the_handle() {
+        let obj = Arc::new(42u32);
+        let handle = ThreadBound::lower_bound_to_current_thread(Arc::clone(&obj));
+        let lifted = unsafe { ThreadBound::<u32>::lift(handle) };
+        assert!(Arc::ptr_eq(&lifted, &obj));
+        // Freeing the binding leaves only the lifted reference and ours.
+        assert_eq!(Arc::strong_count(&obj), 2);
+        drop(lifted);
+        assert_eq!(Arc::strong_count(&obj), 1);
+    }
+}
@@ -0,0 +1,24 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// This is a separate test binary, since the buffer growth policy is process-wide and other tests
+// lower values with the default policy.
+
+use uniffi_core::{buffer_growth, set_buffer_growth, BufferGrowth};
+
+#[test]
+fn test_set_buffer_growth() {
+    assert_eq!(buffer_growth(), BufferGrowth::SizeHint);
+    set_buffer_growth(BufferGrowth::FixedIncrement(0));
+    assert_eq!(buffer_growth(), BufferGrowth::FixedIncrement(1));
+    set_buffer_growth(BufferGrowth::FixedIncrement(usize::MAX));
+    assert_eq!(
+        buffer_growth(),
+        BufferGrowth::FixedIncrement(usize::MAX - 1)
+    );
+    set_buffer_growth(BufferGrowth::Doubling);
+    assert_eq!(buffer_growth(), BufferGrowth::Doubling);
+    set_buffer_growth(BufferGrowth::SizeHint);
+    assert_eq!(buffer_growth(), BufferGrowth::SizeHint);
+}
@@ -0,0 +1,156 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// This is a separate test binary, since the float policy is process-wide and other tests lower
+// floats with the default policy.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use uniffi_core::{
+    rust_call_checking_reentrancy, set_float_special, CallbackResult, FloatSpecial,
+    ForeignCallbackInternals, Lift, Lower, LowerReturn, RustBuffer, RustCallStatus,
+    RustCallStatusCode,
+};
+
+static CALLBACK_CALLS: AtomicU32 = AtomicU32::new(0);
+
+unsafe extern "C" fn test_callback(
+    _handle: u64,
+    _method: u32,
+    _args_data: *const u8,
+    _args_len: i32,
+    _buf_ptr: *mut RustBuffer,
+) -> i32 {
+    CALLBACK_CALLS.fetch_add(1, Ordering::SeqCst);
+    CallbackResult::Success as i32
+}
+
+// An error type carrying a float, like a `#[derive(uniffi::Error)]` with a float field.
+#[derive(Debug)]
+struct FloatError(f64);
+
+impl std::fmt::Display for FloatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "float error {}", self.0)
+    }
+}
+
+impl std::error::Error for FloatError {}
+
+unsafe impl Lower<()> for FloatError {
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Self) -> RustBuffer {
+        Self::lower_into_rust_buffer(obj)
+    }
+
+    fn write(obj: Self, buf: &mut Vec<u8>) {
+        <f64 as Lower<()>>::write(obj.0, buf)
+    }
+
+    fn check_lower(obj: &Self) -> uniffi_core::Result<()> {
+        <f64 as Lower<()>>::check_lower(&obj.0)
+    }
+
+    const TYPE_ID_META: uniffi_core::MetadataBuffer = <f64 as Lower<()>>::TYPE_ID_META;
+}
+
+fn unexpected_error_message(status: RustCallStatus) -> String {
+    assert_eq!(status.code, RustCallStatusCode::UnexpectedError);
+    let error_buf = unsafe { status.error_buf.assume_init() };
+    <String as Lift<()>>::try_lift(error_buf).unwrap()
+}
+
+// Both policies are tested together, since the policy is global and tests run in parallel.
+#[test]
+fn test_float_special_policy() {
+    // A `NaN` with a payload, to check the bit pattern is preserved.
+    let nan = f64::from_bits(0x7ff8_0000_dead_beef);
+    let nan32 = f32::from_bits(0x7fc0_beef);
+
+    let lifted = <f64 as Lift<()>>::try_lift(<f64 as Lower<()>>::lower(nan)).unwrap();
+    assert_eq!(lifted.to_bits(), nan.to_bits());
+    let lifted =
+        <Vec<f32> as Lift<()>>::try_lift(<Vec<f32> as Lower<()>>::lower(vec![nan32])).unwrap();
+    assert_eq!(lifted[0].to_bits(), nan32.to_bits());
+    let lifted = <f64 as Lift<()>>::try_lift(f64::INFINITY).unwrap();
+    assert_eq!(lifted, f64::INFINITY);
+
+    set_float_special(FloatSpecial::Error);
+    let lift_err = <f64 as Lift<()>>::try_lift(f64::NAN).unwrap_err();
+    assert_eq!(
+        lift_err.to_string(),
+        "non-finite float value `NaN` is not allowed over the FFI"
+    );
+    let read_err = <Vec<f32> as Lift<()>>::try_lift(RustBuffer::from_vec(
+        [1i32.to_be_bytes(), f32::NEG_INFINITY.to_be_bytes()].concat(),
+    ))
+    .unwrap_err();
+    assert_eq!(
+        read_err.to_string(),
+        "non-finite float value `-inf` is not allowed over the FFI"
+    );
+
+    // Exported functions returning a non-finite float fail with an internal error, the way the
+    // scaffolding calls them.
+    let mut status = RustCallStatus::default();
+    rust_call_checking_reentrancy(&mut status, || {
+        <f64 as LowerReturn<()>>::try_lower_return(f64::NAN)
+    });
+    assert_eq!(
+        unexpected_error_message(status),
+        "non-finite float value `NaN` is not allowed over the FFI"
+    );
+    // So do ones returning one inside a buffer, or as an error.
+    let mut status = RustCallStatus::default();
+    rust_call_checking_reentrancy(&mut status, || {
+        <Vec<f32> as LowerReturn<()>>::try_lower_return(vec![1.5, f32::INFINITY])
+    });
+    assert_eq!(
+        unexpected_error_message(status),
+        "non-finite float value `inf` is not allowed over the FFI"
+    );
+    let mut status = RustCallStatus::default();
+    rust_call_checking_reentrancy(&mut status, || {
+        <Result<(), FloatError> as LowerReturn<()>>::try_lower_return(Err(FloatError(f64::NAN)))
+    });
+    assert_eq!(
+        unexpected_error_message(status),
+        "non-finite float value `NaN` is not allowed over the FFI"
+    );
+    // Finite values are still fine.
+    let mut status = RustCallStatus::default();
+    let returned = rust_call_checking_reentrancy(&mut status, || {
+        <f64 as LowerReturn<()>>::try_lower_return(1.5)
+    });
+    assert_eq!(status.code, RustCallStatusCode::Success);
+    assert_eq!(returned, 1.5);
+
+    // Callback interface arguments are checked before they're written, the way the generated
+    // proxies do it.  A rejected one fails the callback without invoking it.
+    let internals = ForeignCallbackInternals::new();
+    internals.set_callback(test_callback);
+    let call_with_arg = |arg: f64| -> Result<(), String> {
+        <f64 as Lower<()>>::check_lower(&arg).map_err(|e| e.to_string())?;
+        let mut args_buf = Vec::new();
+        <f64 as Lower<()>>::write(arg, &mut args_buf);
+        internals.invoke_callback::<(), ()>(1, 1, RustBuffer::from_vec(args_buf));
+        Ok(())
+    };
+    assert_eq!(
+        call_with_arg(f64::NEG_INFINITY).unwrap_err(),
+        "non-finite float value `-inf` is not allowed over the FFI"
+    );
+    assert_eq!(CALLBACK_CALLS.load(Ordering::SeqCst), 0);
+    call_with_arg(1.5).unwrap();
+    assert_eq!(CALLBACK_CALLS.load(Ordering::SeqCst), 1);
+
+    set_float_special(FloatSpecial::Pass);
+
+    let mut status = RustCallStatus::default();
+    let returned = rust_call_checking_reentrancy(&mut status, || {
+        <f64 as LowerReturn<()>>::try_lower_return(nan)
+    });
+    assert_eq!(status.code, RustCallStatusCode::Success);
+    assert_eq!(returned.to_bits(), nan.to_bits());
+}
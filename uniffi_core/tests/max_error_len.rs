@@ -0,0 +1,43 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// This is a separate test binary, since the maximum error length is process-wide and other tests
+// return errors with the default limit.
+
+use uniffi_core::{
+    max_error_len, rust_call, set_max_error_len, Lift, RustBuffer, RustCallStatus,
+    RustCallStatusCode, DEFAULT_MAX_ERROR_LEN,
+};
+
+fn call_with_error(len: usize) -> RustCallStatus {
+    let mut status = RustCallStatus::default();
+    rust_call(&mut status, || -> Result<i8, RustBuffer> {
+        Err(RustBuffer::from_vec(vec![1; len]))
+    });
+    status
+}
+
+#[test]
+fn test_max_error_len() {
+    assert_eq!(max_error_len(), DEFAULT_MAX_ERROR_LEN);
+    set_max_error_len(64);
+
+    let status = call_with_error(64);
+    assert_eq!(status.code, RustCallStatusCode::Error);
+    let error_buf = unsafe { status.error_buf.assume_init() };
+    assert_eq!(error_buf.destroy_into_vec(), vec![1; 64]);
+
+    let status = call_with_error(65);
+    assert_eq!(status.code, RustCallStatusCode::UnexpectedError);
+    let error_buf = unsafe { status.error_buf.assume_init() };
+    assert_eq!(
+        <String as Lift<()>>::try_lift(error_buf).unwrap(),
+        "Error payload exceeds the maximum allowed (65 > 64 bytes)"
+    );
+
+    set_max_error_len(DEFAULT_MAX_ERROR_LEN);
+    let status = call_with_error(65);
+    assert_eq!(status.code, RustCallStatusCode::Error);
+    unsafe { status.error_buf.assume_init() }.destroy();
+}
@@ -0,0 +1,40 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// This is a separate test binary, since the panic callback is process-wide and would see the
+// panics from other tests.
+
+use std::sync::Mutex;
+use uniffi_core::{
+    rust_call, set_panic_callback, Lift, RustBuffer, RustCallStatus, RustCallStatusCode,
+};
+
+static PANICS: Mutex<Vec<(String, bool)>> = Mutex::new(Vec::new());
+
+#[test]
+fn test_panic_callback() {
+    set_panic_callback(|message, backtrace| {
+        PANICS
+            .lock()
+            .unwrap()
+            .push((message.to_owned(), backtrace.is_some()));
+    });
+
+    let mut status = RustCallStatus::default();
+    rust_call(&mut status, || -> Result<i8, RustBuffer> {
+        panic!("Unexpected value: 7");
+    });
+    assert_eq!(status.code, RustCallStatusCode::UnexpectedError);
+    let error_buf = unsafe { status.error_buf.assume_init() };
+    assert_eq!(
+        <String as Lift<()>>::try_lift(error_buf).unwrap(),
+        "Unexpected value: 7"
+    );
+
+    // The callback is called once, before the panic is returned as an error.
+    let panics = PANICS.lock().unwrap();
+    assert_eq!(panics.len(), 1);
+    assert_eq!(panics[0].0, "Unexpected value: 7");
+    assert!(panics[0].1, "the backtrace wasn't captured");
+}
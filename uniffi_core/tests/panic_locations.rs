@@ -0,0 +1,44 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// This is a separate test binary, since capturing panic locations installs a process-wide panic
+// hook.
+
+use uniffi_core::{
+    capture_panic_locations, rust_call, Lift, RustBuffer, RustCallStatus, RustCallStatusCode,
+};
+
+fn panic_message(status: RustCallStatus) -> String {
+    assert_eq!(status.code, RustCallStatusCode::UnexpectedError);
+    let error_buf = unsafe { status.error_buf.assume_init() };
+    <String as Lift<()>>::try_lift(error_buf).unwrap()
+}
+
+#[test]
+fn test_panic_location() {
+    // Calling this more than once only installs the hook once.
+    capture_panic_locations();
+    capture_panic_locations();
+
+    let mut status = RustCallStatus::default();
+    let line = line!() + 2;
+    rust_call(&mut status, || -> Result<i8, RustBuffer> {
+        panic!("oh no");
+    });
+    assert_eq!(
+        panic_message(status),
+        format!("oh no (at {}:{line}:9)", file!())
+    );
+
+    // Each panic gets its own location.
+    let mut status = RustCallStatus::default();
+    let line = line!() + 2;
+    rust_call(&mut status, || -> Result<i8, RustBuffer> {
+        panic!("again")
+    });
+    assert_eq!(
+        panic_message(status),
+        format!("again (at {}:{line}:9)", file!())
+    );
+}
@@ -0,0 +1,81 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// This is a separate test binary, since the re-entrancy policy is process-wide and other tests
+// make re-entrant calls with the default policy.
+
+use std::panic::AssertUnwindSafe;
+use uniffi_core::{
+    reentrancy_policy, rust_call, rust_call_checking_reentrancy, set_reentrancy_policy, Lift,
+    ReentrancyPolicy, RustCallError, RustCallStatus, RustCallStatusCode,
+};
+
+// Call an exported function, which calls a "callback" that calls another exported function on the
+// same thread.  The outer call isn't re-entrant, so it always succeeds.
+fn call_reentrant() -> (i8, RustCallStatus) {
+    let mut inner_status = RustCallStatus::default();
+    let mut status = RustCallStatus::default();
+    let return_value = rust_call_checking_reentrancy(
+        &mut status,
+        AssertUnwindSafe(|| {
+            Ok(rust_call_checking_reentrancy(
+                &mut inner_status,
+                || -> Result<i8, RustCallError> { Ok(100) },
+            ))
+        }),
+    );
+    assert_eq!(status.code, RustCallStatusCode::Success);
+    (return_value, inner_status)
+}
+
+#[test]
+fn test_reentrancy_policy() {
+    assert_eq!(reentrancy_policy(), ReentrancyPolicy::Allow);
+    let (return_value, inner_status) = call_reentrant();
+    assert_eq!(inner_status.code, RustCallStatusCode::Success);
+    assert_eq!(return_value, 100);
+
+    set_reentrancy_policy(ReentrancyPolicy::Detect);
+    assert_eq!(reentrancy_policy(), ReentrancyPolicy::Detect);
+    let (return_value, inner_status) = call_reentrant();
+    assert_eq!(inner_status.code, RustCallStatusCode::Success);
+    assert_eq!(return_value, 100);
+
+    set_reentrancy_policy(ReentrancyPolicy::Deny);
+    assert_eq!(reentrancy_policy(), ReentrancyPolicy::Deny);
+    let (return_value, inner_status) = call_reentrant();
+    assert_eq!(inner_status.code, RustCallStatusCode::UnexpectedError);
+    assert_eq!(return_value, 0);
+    let error_buf = unsafe { inner_status.error_buf.assume_init() };
+    assert_eq!(
+        <String as Lift<()>>::try_lift(error_buf).unwrap(),
+        "Re-entrant Rust call on the same thread denied by the re-entrancy policy (call depth 2)"
+    );
+
+    // Other scaffolding calls, like allocating a `RustBuffer` for the callback's return value,
+    // aren't tracked.
+    let mut status = RustCallStatus::default();
+    let mut inner_status = RustCallStatus::default();
+    rust_call_checking_reentrancy(
+        &mut status,
+        AssertUnwindSafe(|| {
+            rust_call(&mut inner_status, || Ok(()));
+            Ok::<_, RustCallError>(())
+        }),
+    );
+    assert_eq!(inner_status.code, RustCallStatusCode::Success);
+
+    // The depth is reset after a panic, so the next call isn't re-entrant.
+    let mut status = RustCallStatus::default();
+    rust_call_checking_reentrancy(&mut status, || -> Result<i8, RustCallError> {
+        panic!("oh no")
+    });
+    assert_eq!(status.code, RustCallStatusCode::UnexpectedError);
+    unsafe { status.error_buf.assume_init() }.destroy();
+    let mut status = RustCallStatus::default();
+    rust_call_checking_reentrancy(&mut status, || -> Result<i8, RustCallError> { Ok(1) });
+    assert_eq!(status.code, RustCallStatusCode::Success);
+
+    set_reentrancy_policy(ReentrancyPolicy::Allow);
+}
@@ -0,0 +1,42 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// This is a separate test binary, since the runtime settings are process-wide and other tests
+// depend on their defaults.
+
+use uniffi_core::{
+    initialize, max_read_depth, RuntimeConfig, RustBuffer, RustBufferAllocator,
+    DEFAULT_MAX_READ_DEPTH,
+};
+
+struct NullAllocator;
+
+unsafe impl RustBufferAllocator for NullAllocator {
+    fn alloc(&self, _capacity: usize) -> *mut u8 {
+        std::ptr::null_mut()
+    }
+
+    unsafe fn free(&self, _data: *mut u8, _capacity: usize) {}
+}
+
+#[test]
+fn test_initialize_is_all_or_nothing() {
+    // An allocator can't be installed once a buffer was allocated without it.
+    RustBuffer::from_vec(vec![1]).destroy();
+    let depth = DEFAULT_MAX_READ_DEPTH + 1;
+    let err = initialize(
+        RuntimeConfig::new()
+            .max_read_depth(depth)
+            .rustbuffer_allocator(&NullAllocator),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "a RustBuffer was already allocated with the default allocator"
+    );
+    assert_eq!(max_read_depth(), DEFAULT_MAX_READ_DEPTH);
+
+    initialize(RuntimeConfig::new().max_read_depth(depth)).unwrap();
+    assert_eq!(max_read_depth(), depth);
+}
@@ -0,0 +1,109 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// This is a separate test binary, since the `RustBuffer` allocator is process-wide and must be
+// installed before any buffers are allocated.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use uniffi_core::{
+    set_rustbuffer_allocator, uniffi_rustbuffer_alloc, uniffi_rustbuffer_free,
+    uniffi_rustbuffer_reserve, Lift, Lower, RustBuffer, RustBufferAllocator, RustCallStatus,
+};
+
+struct CountingAllocator {
+    allocs: AtomicUsize,
+    frees: AtomicUsize,
+}
+
+unsafe impl RustBufferAllocator for CountingAllocator {
+    fn alloc(&self, capacity: usize) -> *mut u8 {
+        self.allocs.fetch_add(1, Ordering::SeqCst);
+        unsafe { System.alloc(Layout::array::<u8>(capacity).unwrap()) }
+    }
+
+    unsafe fn free(&self, data: *mut u8, capacity: usize) {
+        self.frees.fetch_add(1, Ordering::SeqCst);
+        System.dealloc(data, Layout::array::<u8>(capacity).unwrap())
+    }
+}
+
+static ALLOCATOR: CountingAllocator = CountingAllocator {
+    allocs: AtomicUsize::new(0),
+    frees: AtomicUsize::new(0),
+};
+
+// Counts the global allocations made by each thread, to check which buffers avoid it.
+struct CountingGlobalAllocator;
+
+thread_local! {
+    static GLOBAL_ALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn global_allocs() -> usize {
+    GLOBAL_ALLOCS.with(Cell::get)
+}
+
+unsafe impl GlobalAlloc for CountingGlobalAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = GLOBAL_ALLOCS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingGlobalAllocator = CountingGlobalAllocator;
+
+#[test]
+fn test_counting_allocator() {
+    set_rustbuffer_allocator(&ALLOCATOR).unwrap();
+    // Only one allocator can be installed.
+    assert!(set_rustbuffer_allocator(&ALLOCATOR).is_err());
+
+    // Lowering and lifting goes through the allocator.
+    let buf = <String as Lower<()>>::lower("hello".to_string());
+    assert_eq!(ALLOCATOR.allocs.load(Ordering::SeqCst), 1);
+    assert_eq!(<String as Lift<()>>::try_lift(buf).unwrap(), "hello");
+    assert_eq!(ALLOCATOR.frees.load(Ordering::SeqCst), 1);
+
+    // So do the FFI functions which the foreign code uses to manage buffers.
+    let mut status = RustCallStatus::default();
+    let buf = uniffi_rustbuffer_alloc(16, &mut status);
+    let buf = uniffi_rustbuffer_reserve(buf, 1024, &mut status);
+    uniffi_rustbuffer_free(buf, &mut status);
+
+    // Empty buffers don't allocate at all.
+    RustBuffer::new().destroy();
+
+    assert_eq!(ALLOCATOR.allocs.load(Ordering::SeqCst), 3);
+    assert_eq!(
+        ALLOCATOR.allocs.load(Ordering::SeqCst),
+        ALLOCATOR.frees.load(Ordering::SeqCst)
+    );
+
+    // Values are lowered into a reused buffer and lifted in place, so once that buffer exists
+    // they don't use the global allocator.  Neither do the buffers managed by the foreign code.
+    let lower_and_lift = || {
+        let buf = <Option<i32> as Lower<()>>::lower(Some(42));
+        assert_eq!(<Option<i32> as Lift<()>>::try_lift(buf).unwrap(), Some(42));
+    };
+    lower_and_lift();
+    let allocs_before = global_allocs();
+    lower_and_lift();
+    let buf = uniffi_rustbuffer_alloc(16, &mut status);
+    let buf = uniffi_rustbuffer_reserve(buf, 1024, &mut status);
+    uniffi_rustbuffer_free(buf, &mut status);
+    assert_eq!(global_allocs(), allocs_before);
+    assert_eq!(
+        ALLOCATOR.allocs.load(Ordering::SeqCst),
+        ALLOCATOR.frees.load(Ordering::SeqCst)
+    );
+}
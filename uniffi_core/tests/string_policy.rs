@@ -0,0 +1,34 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// This is a separate test binary, since the string policy is process-wide and other tests lift
+// strings with the default policy.
+
+use std::sync::Arc;
+use uniffi_core::{set_string_policy, Lift, RustBuffer, StringPolicy};
+
+// Both policies are tested together, since the policy is global and tests run in parallel.
+#[test]
+fn test_string_policy() {
+    let invalid = || RustBuffer::from_vec([2i32.to_be_bytes().as_slice(), b"a\xff"].concat());
+
+    let strict_err = <Vec<String> as Lift<()>>::try_lift(RustBuffer::from_vec(
+        [1i32.to_be_bytes().as_slice(), &invalid().destroy_into_vec()].concat(),
+    ))
+    .unwrap_err();
+    assert!(
+        strict_err.to_string().contains("invalid utf-8"),
+        "{strict_err}"
+    );
+
+    set_string_policy(StringPolicy::Lossy);
+    let lossy = <Vec<String> as Lift<()>>::try_lift(RustBuffer::from_vec(
+        [1i32.to_be_bytes().as_slice(), &invalid().destroy_into_vec()].concat(),
+    ));
+    let lossy_str = <Arc<str> as Lift<()>>::try_read(&mut invalid().destroy_into_vec().as_slice());
+    set_string_policy(StringPolicy::Strict);
+
+    assert_eq!(lossy.unwrap(), ["a\u{FFFD}"]);
+    assert_eq!(&*lossy_str.unwrap(), "a\u{FFFD}");
+}
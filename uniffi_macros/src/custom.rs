@@ -2,10 +2,39 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::util::{derive_all_ffi_traits, ident_to_string, mod_path, tagged_impl_header};
+use crate::util::{derive_all_ffi_traits, ident_to_string, kw, mod_path, tagged_impl_header};
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
-use syn::Path;
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    Expr, Path, Token,
+};
+
+/// The conversions passed to `custom_type!`, as `{ try_lift: |val| ..., lower: |obj| ... }`.
+///
+/// `try_lift` returns a `uniffi::Result`, so that a value passed from the foreign code can be
+/// rejected.
+pub(crate) struct CustomTypeConverters {
+    try_lift: Expr,
+    lower: Expr,
+}
+
+impl Parse for CustomTypeConverters {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let content;
+        braced!(content in input);
+        let _: kw::try_lift = content.parse()?;
+        let _: Token![:] = content.parse()?;
+        let try_lift = content.parse()?;
+        let _: Token![,] = content.parse()?;
+        let _: kw::lower = content.parse()?;
+        let _: Token![:] = content.parse()?;
+        let lower = content.parse()?;
+        let _: Option<Token![,]> = content.parse()?;
+        Ok(Self { try_lift, lower })
+    }
+}
 
 // Generate an FfiConverter impl based on the UniffiCustomTypeConverter
 // implementation that the library supplies
@@ -52,6 +81,39 @@ pub(crate) fn expand_ffi_converter_custom_type(
     })
 }
 
+// Generate an FfiConverter impl, and an UniffiCustomTypeConverter if the conversions were passed
+// to `custom_type!`.
+pub(crate) fn expand_ffi_converter_custom_type_with_converters(
+    ident: &Ident,
+    builtin: &Path,
+    converters: Option<&CustomTypeConverters>,
+) -> syn::Result<TokenStream> {
+    let ffi_converter = expand_ffi_converter_custom_type(ident, builtin, true)?;
+    let type_converter = converters.map(|CustomTypeConverters { try_lift, lower }| {
+        quote! {
+            impl crate::UniffiCustomTypeConverter for #ident {
+                type Builtin = #builtin;
+
+                fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> {
+                    let try_lift: fn(#builtin) -> uniffi::Result<#ident> = #try_lift;
+                    try_lift(val)
+                }
+
+                fn from_custom(obj: Self) -> Self::Builtin {
+                    let lower: fn(#ident) -> #builtin = #lower;
+                    lower(obj)
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #ffi_converter
+
+        #type_converter
+    })
+}
+
 // Generate an FfiConverter impl *and* an UniffiCustomTypeConverter.
 pub(crate) fn expand_ffi_converter_custom_newtype(
     ident: &Ident,
@@ -2,9 +2,12 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use syn::{
     parse::{Parse, ParseStream},
-    Data, DataEnum, DeriveInput, Expr, Field, Index, Lit, Variant,
+    punctuated::Punctuated,
+    Attribute, Data, DataEnum, DeriveInput, Expr, ExprLit, Field, Index, Lit, Meta, Token, Variant,
 };
 
+use uniffi_meta::DiscriminantEncoding;
+
 use crate::util::{
     create_metadata_items, derive_all_ffi_traits, either_attribute_arg, extract_docstring,
     ident_to_string, kw, mod_path, parse_comma_separated, tagged_impl_header,
@@ -27,11 +30,19 @@ pub fn expand_enum(
         }
     };
     let ident = &input.ident;
+    check_discriminants(&input.attrs, &enum_)?;
     let docstring = extract_docstring(&input.attrs)?;
     let mut attr: EnumAttr = input.attrs.parse_uniffi_attr_args()?;
     if let Some(attr_from_udl_mode) = attr_from_udl_mode {
         attr = attr.merge(attr_from_udl_mode)?;
     }
+    if let Some(default) = attr.default.as_ref().filter(|_| !udl_mode) {
+        return Err(syn::Error::new_spanned(
+            default,
+            "default variants are only supported for enums defined in UDL",
+        ));
+    }
+    check_discriminant_encoding(&enum_, &attr)?;
     let ffi_converter_impl = enum_ffi_converter_impl(ident, &enum_, udl_mode, &attr);
 
     let meta_static_var = (!udl_mode).then(|| {
@@ -51,11 +62,14 @@ pub(crate) fn enum_ffi_converter_impl(
     udl_mode: bool,
     attr: &EnumAttr,
 ) -> TokenStream {
+    // Fieldless enums are passed across the FFI as the `i32` that identifies their variant.
+    let scalar = enum_.variants.iter().all(|v| v.fields.is_empty());
     enum_or_error_ffi_converter_impl(
         ident,
         enum_,
         udl_mode,
         attr,
+        scalar,
         quote! { ::uniffi::metadata::codes::TYPE_ENUM },
     )
 }
@@ -71,6 +85,7 @@ pub(crate) fn rich_error_ffi_converter_impl(
         enum_,
         udl_mode,
         attr,
+        false,
         quote! { ::uniffi::metadata::codes::TYPE_ENUM },
     )
 }
@@ -80,6 +95,7 @@ fn enum_or_error_ffi_converter_impl(
     enum_: &DataEnum,
     udl_mode: bool,
     attr: &EnumAttr,
+    scalar: bool,
     metadata_type_code: TokenStream,
 ) -> TokenStream {
     let name = ident_to_string(ident);
@@ -89,6 +105,23 @@ fn enum_or_error_ffi_converter_impl(
         Ok(p) => p,
         Err(e) => return e.into_compile_error(),
     };
+    let type_id_meta = quote! {
+        ::uniffi::MetadataBuffer::from_code(#metadata_type_code)
+            .concat_str(#mod_path)
+            .concat_str(#name)
+    };
+    if scalar {
+        return scalar_enum_ffi_converter_impl(
+            ident,
+            enum_,
+            attr,
+            impl_spec,
+            derive_ffi_traits,
+            type_id_meta,
+        );
+    }
+
+    let encoding = attr.discriminant_encoding_tokens();
     let mut write_match_arms: Vec<_> = enum_
         .variants
         .iter()
@@ -99,9 +132,14 @@ fn enum_or_error_ffi_converter_impl(
             let idx = Index::from(i + 1);
             let write_fields = v.fields.iter().map(write_field);
 
+            let write_discriminant = match &encoding {
+                Some(encoding) => quote! { #encoding.write(#idx, buf); },
+                None => quote! { ::uniffi::deps::bytes::BufMut::put_i32(buf, #idx); },
+            };
+
             quote! {
                 Self::#v_ident { #(#fields),* } => {
-                    ::uniffi::deps::bytes::BufMut::put_i32(buf, #idx);
+                    #write_discriminant
                     #(#write_fields)*
                 }
             }
@@ -116,6 +154,25 @@ fn enum_or_error_ffi_converter_impl(
         match obj { #(#write_match_arms)* }
     };
 
+    let mut check_lower_match_arms: Vec<_> = enum_
+        .variants
+        .iter()
+        .map(|v| {
+            let v_ident = &v.ident;
+            let fields = v.fields.iter().map(|f| &f.ident);
+            let check_lower_fields = v.fields.iter().map(check_lower_field);
+
+            quote! {
+                Self::#v_ident { #(ref #fields),* } => {
+                    #(#check_lower_fields)*
+                }
+            }
+        })
+        .collect();
+    if attr.non_exhaustive.is_some() {
+        check_lower_match_arms.push(quote! { _ => {} })
+    }
+
     let try_read_match_arms = enum_.variants.iter().enumerate().map(|(i, v)| {
         let idx = Index::from(i + 1);
         let v_ident = &v.ident;
@@ -126,13 +183,28 @@ fn enum_or_error_ffi_converter_impl(
         }
     });
     let error_format_string = format!("Invalid {ident} enum value: {{}}");
-    let try_read_impl = quote! {
-        ::uniffi::check_remaining(buf, 4)?;
+    let read_discriminant = match &encoding {
+        Some(encoding) => quote! { let v = #encoding.read(buf)?; },
+        None => quote! {
+            ::uniffi::check_remaining(buf, 4)?;
 
-        Ok(match ::uniffi::deps::bytes::Buf::get_i32(buf) {
-            #(#try_read_match_arms)*
-            v => ::uniffi::deps::anyhow::bail!(#error_format_string, v),
-        })
+            let v = ::uniffi::deps::bytes::Buf::get_i32(buf);
+        },
+    };
+    let try_read_impl = if enum_.variants.is_empty() {
+        // There are no valid values, so avoid generating an unreachable `Ok(...)`.
+        quote! {
+            #read_discriminant
+            ::uniffi::deps::anyhow::bail!(#error_format_string, v)
+        }
+    } else {
+        quote! {
+            #read_discriminant
+            Ok(match v {
+                #(#try_read_match_arms)*
+                v => ::uniffi::deps::anyhow::bail!(#error_format_string, v),
+            })
+        }
     };
 
     quote! {
@@ -144,13 +216,96 @@ fn enum_or_error_ffi_converter_impl(
                 #write_impl
             }
 
+            #[allow(unreachable_code)]
+            fn check_lower(obj: &Self) -> ::uniffi::deps::anyhow::Result<()> {
+                // Matching on `*obj` rather than `obj` also works for enums without variants.
+                match *obj { #(#check_lower_match_arms)* }
+                Ok(())
+            }
+
             fn try_read(buf: &mut &[::std::primitive::u8]) -> ::uniffi::deps::anyhow::Result<Self> {
                 #try_read_impl
             }
 
-            const TYPE_ID_META: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::from_code(#metadata_type_code)
-                .concat_str(#mod_path)
-                .concat_str(#name);
+            const TYPE_ID_META: ::uniffi::MetadataBuffer = #type_id_meta;
+        }
+
+        #derive_ffi_traits
+    }
+}
+
+// Fieldless enums lower directly into the `i32` that identifies their variant, which is also
+// what they're serialized as.
+fn scalar_enum_ffi_converter_impl(
+    ident: &Ident,
+    enum_: &DataEnum,
+    attr: &EnumAttr,
+    impl_spec: TokenStream,
+    derive_ffi_traits: TokenStream,
+    type_id_meta: TokenStream,
+) -> TokenStream {
+    let mut lower_match_arms: Vec<_> = enum_
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let v_ident = &v.ident;
+            let idx = Index::from(i + 1);
+            quote! { Self::#v_ident {} => #idx, }
+        })
+        .collect();
+    if attr.non_exhaustive.is_some() {
+        lower_match_arms.push(quote! {
+            _ => panic!("Unexpected variant in non-exhaustive enum"),
+        })
+    }
+
+    let lift_match_arms = enum_.variants.iter().enumerate().map(|(i, v)| {
+        let idx = Index::from(i + 1);
+        let v_ident = &v.ident;
+        quote! { #idx => Self::#v_ident {}, }
+    });
+    let error_format_string = format!("Invalid {ident} enum value: {{}}");
+    // Unknown values are lifted as the default variant, if there is one.
+    let unknown_arm = match &attr.default {
+        Some(default) => quote! { _ => Self::#default {}, },
+        None => quote! { v => ::uniffi::deps::anyhow::bail!(#error_format_string, v), },
+    };
+    let try_lift_impl = if enum_.variants.is_empty() {
+        // There are no valid values, so avoid generating an unreachable `Ok(...)`.
+        quote! { ::uniffi::deps::anyhow::bail!(#error_format_string, v) }
+    } else {
+        quote! {
+            Ok(match v {
+                #(#lift_match_arms)*
+                #unknown_arm
+            })
+        }
+    };
+
+    let read_and_write = match attr.discriminant_encoding_tokens() {
+        Some(encoding) => quote! {
+            ::uniffi::ffi_converter_scalar_enum_read_and_write!(crate::UniFfiTag, #encoding);
+        },
+        None => quote! {
+            ::uniffi::ffi_converter_scalar_enum_read_and_write!(crate::UniFfiTag);
+        },
+    };
+
+    quote! {
+        #[automatically_derived]
+        unsafe #impl_spec {
+            #read_and_write
+
+            fn lower(obj: Self) -> ::std::primitive::i32 {
+                match obj { #(#lower_match_arms)* }
+            }
+
+            fn try_lift(v: ::std::primitive::i32) -> ::uniffi::deps::anyhow::Result<Self> {
+                #try_lift_impl
+            }
+
+            const TYPE_ID_META: ::uniffi::MetadataBuffer = #type_id_meta;
         }
 
         #derive_ffi_traits
@@ -166,6 +321,15 @@ fn write_field(f: &Field) -> TokenStream {
     }
 }
 
+fn check_lower_field(f: &Field) -> TokenStream {
+    let ident = &f.ident;
+    let ty = &f.ty;
+
+    quote! {
+        <#ty as ::uniffi::Lower<crate::UniFfiTag>>::check_lower(#ident)?;
+    }
+}
+
 pub(crate) fn enum_meta_static_var(
     ident: &Ident,
     docstring: String,
@@ -186,9 +350,97 @@ pub(crate) fn enum_meta_static_var(
         .concat_bool(#non_exhaustive)
         .concat_long_str(#docstring)
     });
+    metadata_expr.extend(match &attr.discriminant {
+        Some(encoding) => {
+            let encoding = ident_to_string(encoding);
+            quote! { .concat_bool(true).concat_str(#encoding) }
+        }
+        None => quote! { .concat_bool(false) },
+    });
     Ok(create_metadata_items("enum", &name, metadata_expr, None))
 }
 
+/// Check that the variants' discriminants fit in the enum's `#[repr(...)]`, if it has one.
+///
+/// rustc rejects these enums too, but its error doesn't say which variant overflowed.
+pub(crate) fn check_discriminants(attrs: &[Attribute], enum_: &DataEnum) -> syn::Result<()> {
+    let Some(repr) = int_repr(attrs)? else {
+        return Ok(());
+    };
+    let max = match repr.to_string().as_str() {
+        "u8" => u8::MAX as u128,
+        "u16" => u16::MAX as u128,
+        "u32" => u32::MAX as u128,
+        "u64" | "usize" => u64::MAX as u128,
+        "i8" => i8::MAX as u128,
+        "i16" => i16::MAX as u128,
+        "i32" => i32::MAX as u128,
+        "i64" | "isize" => i64::MAX as u128,
+        _ => return Ok(()),
+    };
+    let mut next = 0u128;
+    for v in &enum_.variants {
+        let value = match &v.discriminant {
+            None => next,
+            Some((
+                _,
+                Expr::Lit(ExprLit {
+                    lit: Lit::Int(lit), ..
+                }),
+            )) => lit.base10_parse()?,
+            // Negative and computed discriminants are left for rustc to check.
+            Some(_) => return Ok(()),
+        };
+        if value > max {
+            return Err(syn::Error::new_spanned(
+                v,
+                format!(
+                    "the discriminant of variant `{}` ({value}) doesn't fit in `#[repr({repr})]`",
+                    v.ident
+                ),
+            ));
+        }
+        next = value + 1;
+    }
+    Ok(())
+}
+
+/// Check that the encoding set with `#[uniffi(discriminant = ...)]` is known, and that the
+/// discriminant of each variant fits in it.
+fn check_discriminant_encoding(enum_: &DataEnum, attr: &EnumAttr) -> syn::Result<()> {
+    let Some(ident) = &attr.discriminant else {
+        return Ok(());
+    };
+    let encoding: DiscriminantEncoding = ident_to_string(ident)
+        .parse()
+        .map_err(|e| syn::Error::new_spanned(ident, e))?;
+    // The discriminants are the 1-based indexes of the variants.
+    if encoding == DiscriminantEncoding::U8 && enum_.variants.len() > u8::MAX as usize {
+        return Err(syn::Error::new_spanned(
+            ident,
+            format!(
+                "the enum has {} variants, so its discriminant doesn't fit in `u8`",
+                enum_.variants.len()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// The integer type in an enum's `#[repr(...)]`, if it has one.
+fn int_repr(attrs: &[Attribute]) -> syn::Result<Option<Ident>> {
+    for attr in attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        if let Some(ident) = metas.iter().find_map(|m| match m {
+            Meta::Path(p) => p.get_ident().filter(|i| *i != "C" && *i != "transparent"),
+            _ => None,
+        }) {
+            return Ok(Some(ident.clone()));
+        }
+    }
+    Ok(None)
+}
+
 fn variant_value(v: &Variant) -> syn::Result<TokenStream> {
     let Some((_, e)) = &v.discriminant else {
         return Ok(quote! { .concat_bool(false) });
@@ -271,6 +523,25 @@ pub fn variant_metadata(enum_: &DataEnum) -> syn::Result<Vec<TokenStream>> {
 #[derive(Default)]
 pub struct EnumAttr {
     pub non_exhaustive: Option<kw::non_exhaustive>,
+    pub default: Option<Ident>,
+    pub discriminant: Option<Ident>,
+}
+
+impl EnumAttr {
+    /// The `::uniffi::DiscriminantEncoding` which the discriminant is serialized with, if it's set.
+    ///
+    /// This must only be called after [check_discriminant_encoding].
+    fn discriminant_encoding_tokens(&self) -> Option<TokenStream> {
+        let ident = self.discriminant.as_ref()?;
+        let encoding = match ident_to_string(ident).parse().ok()? {
+            DiscriminantEncoding::U8 => quote! { U8 },
+            DiscriminantEncoding::U16Le => quote! { U16Le },
+            DiscriminantEncoding::U16Be => quote! { U16Be },
+            DiscriminantEncoding::U32Le => quote! { U32Le },
+            DiscriminantEncoding::U32Be => quote! { U32Be },
+        };
+        Some(quote! { ::uniffi::DiscriminantEncoding::#encoding })
+    }
 }
 
 // So ErrorAttr can be used with `parse_macro_input!`
@@ -286,6 +557,21 @@ impl UniffiAttributeArgs for EnumAttr {
         if lookahead.peek(kw::non_exhaustive) {
             Ok(Self {
                 non_exhaustive: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::default) {
+            let _: kw::default = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                default: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::discriminant) {
+            let _: kw::discriminant = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                discriminant: Some(input.parse()?),
+                ..Self::default()
             })
         } else {
             Err(lookahead.error())
@@ -295,6 +581,8 @@ impl UniffiAttributeArgs for EnumAttr {
     fn merge(self, other: Self) -> syn::Result<Self> {
         Ok(Self {
             non_exhaustive: either_attribute_arg(self.non_exhaustive, other.non_exhaustive)?,
+            default: either_attribute_arg(self.default, other.default)?,
+            discriminant: either_attribute_arg(self.discriminant, other.discriminant)?,
         })
     }
 }
@@ -6,7 +6,7 @@ use syn::{
 };
 
 use crate::{
-    enum_::{rich_error_ffi_converter_impl, variant_metadata, EnumAttr},
+    enum_::{check_discriminants, rich_error_ffi_converter_impl, variant_metadata, EnumAttr},
     util::{
         chain, create_metadata_items, derive_ffi_traits, either_attribute_arg, extract_docstring,
         ident_to_string, kw, mod_path, parse_comma_separated, tagged_impl_header,
@@ -30,6 +30,7 @@ pub fn expand_error(
         }
     };
     let ident = &input.ident;
+    check_discriminants(&input.attrs, &enum_)?;
     let docstring = extract_docstring(&input.attrs)?;
     let mut attr: ErrorAttr = input.attrs.parse_uniffi_attr_args()?;
     if let Some(attr_from_udl_mode) = attr_from_udl_mode {
@@ -307,6 +308,7 @@ impl TryFrom<ErrorAttr> for EnumAttr {
         } else {
             Ok(EnumAttr {
                 non_exhaustive: error_attr.non_exhaustive,
+                ..EnumAttr::default()
             })
         }
     }
@@ -16,14 +16,14 @@ mod utrait;
 use self::{
     item::{ExportItem, ImplItem},
     scaffolding::{
-        gen_constructor_scaffolding, gen_ffi_function, gen_fn_scaffolding, gen_method_scaffolding,
+        gen_constructor_scaffolding, gen_default_method_ffi_function, gen_ffi_function,
+        gen_fn_scaffolding, gen_method_scaffolding,
     },
 };
 use crate::util::{ident_to_string, mod_path};
 pub use attributes::ExportAttributeArguments;
 pub use callback_interface::ffi_converter_callback_interface_impl;
 
-// TODO(jplatte): Ensure no generics, …
 // TODO(jplatte): Aggregate errors instead of short-circuiting, wherever possible
 
 pub(crate) fn expand_export(
@@ -38,16 +38,76 @@ pub(crate) fn expand_export(
     // new functions outside of the `impl`).
     rewrite_self_type(&mut item);
 
+    // A trait without supertraits can't be `Send + Sync`.  Trait interfaces from UDL are
+    // redeclared without them, so they rely on the check in the scaffolding.
+    if let Item::Trait(item) = &item {
+        if !udl_mode && args.callback_interface.is_none() && item.supertraits.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &item.ident,
+                format!(
+                    "exported traits must be `Send + Sync`, e.g. `pub trait {}: Send + Sync`",
+                    item.ident
+                ),
+            ));
+        }
+    }
+
     let metadata = ExportItem::new(item, &args)?;
+    if let Some(chunked) = &args.chunked {
+        if !matches!(metadata, ExportItem::Function { .. }) {
+            return Err(syn::Error::new_spanned(
+                chunked,
+                "this attribute is only allowed on functions",
+            ));
+        }
+    }
+
+    if let Some(thread_bound) = &args.thread_bound {
+        if !udl_mode {
+            return Err(syn::Error::new_spanned(
+                thread_bound,
+                "this attribute is only supported for UDL interfaces",
+            ));
+        }
+        if !matches!(metadata, ExportItem::Impl { .. }) {
+            return Err(syn::Error::new_spanned(
+                thread_bound,
+                "this attribute is only allowed on impl blocks",
+            ));
+        }
+    }
+
+    if let Some(runtime_initializer) = &args.runtime_initializer {
+        if udl_mode || args.constant.is_some() || !matches!(metadata, ExportItem::Function { .. }) {
+            return Err(syn::Error::new_spanned(
+                runtime_initializer,
+                "this attribute is only allowed on functions exported with proc-macros",
+            ));
+        }
+    }
+
+    if let Some(on_poison) = &args.on_poison {
+        if !udl_mode {
+            return Err(syn::Error::new_spanned(
+                on_poison,
+                "this attribute is only supported for UDL interfaces",
+            ));
+        }
+        if !matches!(metadata, ExportItem::Impl { .. }) {
+            return Err(syn::Error::new_spanned(
+                on_poison,
+                "this attribute is only allowed on impl blocks",
+            ));
+        }
+    }
 
     match metadata {
         ExportItem::Function { sig } => gen_fn_scaffolding(sig, &args, udl_mode),
         ExportItem::Impl { items, self_ident } => {
             if let Some(rt) = &args.async_runtime {
-                if items
-                    .iter()
-                    .all(|item| !matches!(item, ImplItem::Method(sig) if sig.is_async))
-                {
+                if items.iter().all(|item| match item {
+                    ImplItem::Constructor(sig) | ImplItem::Method(sig) => !sig.is_async,
+                }) {
                     return Err(syn::Error::new_spanned(
                         rt,
                         "no async methods in this impl block",
@@ -1,17 +1,25 @@
-use crate::util::{either_attribute_arg, kw, parse_comma_separated, UniffiAttributeArgs};
+use crate::util::{
+    either_attribute_arg, ident_to_string, kw, parse_comma_separated, UniffiAttributeArgs,
+};
 
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
-    Attribute, LitStr, Meta, PathArguments, PathSegment, Token,
+    Attribute, Ident, LitStr, Meta, PathArguments, PathSegment, Token,
 };
+use uniffi_meta::OnPoison;
 
 #[derive(Default)]
 pub struct ExportAttributeArguments {
     pub(crate) async_runtime: Option<AsyncRuntime>,
     pub(crate) callback_interface: Option<kw::callback_interface>,
+    pub(crate) chunked: Option<kw::chunked>,
+    pub(crate) constant: Option<kw::constant>,
     pub(crate) constructor: Option<kw::constructor>,
+    pub(crate) thread_bound: Option<kw::thread_bound>,
+    pub(crate) on_poison: Option<OnPoisonArg>,
+    pub(crate) runtime_initializer: Option<kw::runtime_initializer>,
     // tried to make this a vec but that got messy quickly...
     pub(crate) trait_debug: Option<kw::Debug>,
     pub(crate) trait_display: Option<kw::Display>,
@@ -40,11 +48,38 @@ impl UniffiAttributeArgs for ExportAttributeArguments {
                 callback_interface: input.parse()?,
                 ..Self::default()
             })
+        } else if lookahead.peek(kw::chunked) {
+            Ok(Self {
+                chunked: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::constant) {
+            Ok(Self {
+                constant: input.parse()?,
+                ..Self::default()
+            })
         } else if lookahead.peek(kw::constructor) {
             Ok(Self {
                 constructor: input.parse()?,
                 ..Self::default()
             })
+        } else if lookahead.peek(kw::thread_bound) {
+            Ok(Self {
+                thread_bound: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::on_poison) {
+            let _: kw::on_poison = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                on_poison: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::runtime_initializer) {
+            Ok(Self {
+                runtime_initializer: input.parse()?,
+                ..Self::default()
+            })
         } else if lookahead.peek(kw::Debug) {
             Ok(Self {
                 trait_debug: input.parse()?,
@@ -77,7 +112,15 @@ impl UniffiAttributeArgs for ExportAttributeArguments {
                 self.callback_interface,
                 other.callback_interface,
             )?,
+            chunked: either_attribute_arg(self.chunked, other.chunked)?,
+            constant: either_attribute_arg(self.constant, other.constant)?,
             constructor: either_attribute_arg(self.constructor, other.constructor)?,
+            thread_bound: either_attribute_arg(self.thread_bound, other.thread_bound)?,
+            on_poison: either_attribute_arg(self.on_poison, other.on_poison)?,
+            runtime_initializer: either_attribute_arg(
+                self.runtime_initializer,
+                other.runtime_initializer,
+            )?,
             trait_debug: either_attribute_arg(self.trait_debug, other.trait_debug)?,
             trait_display: either_attribute_arg(self.trait_display, other.trait_display)?,
             trait_hash: either_attribute_arg(self.trait_hash, other.trait_hash)?,
@@ -111,9 +154,44 @@ impl ToTokens for AsyncRuntime {
     }
 }
 
+/// The policy set with `on_poison = error` or `on_poison = recover`, for the methods of UDL
+/// interfaces marked `[OnPoison=...]`.
+pub(crate) struct OnPoisonArg {
+    ident: Ident,
+    policy: OnPoison,
+}
+
+impl OnPoisonArg {
+    /// The `::uniffi::OnPoison` the methods are called with.
+    pub(crate) fn policy_tokens(&self) -> TokenStream {
+        match self.policy {
+            OnPoison::Error => quote! { ::uniffi::OnPoison::Error },
+            OnPoison::Recover => quote! { ::uniffi::OnPoison::Recover },
+        }
+    }
+}
+
+impl Parse for OnPoisonArg {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        let policy = ident_to_string(&ident)
+            .parse()
+            .map_err(|e| syn::Error::new_spanned(&ident, e))?;
+        Ok(Self { ident, policy })
+    }
+}
+
+impl ToTokens for OnPoisonArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.ident.to_tokens(tokens)
+    }
+}
+
 #[derive(Default)]
 pub(super) struct ExportedImplFnAttributes {
     pub constructor: bool,
+    pub snapshot: bool,
+    pub collect: bool,
 }
 
 impl ExportedImplFnAttributes {
@@ -156,6 +234,21 @@ impl ExportedImplFnAttributes {
                     }
                     this.constructor = true;
                 }
+                "snapshot" => {
+                    if this.snapshot {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "duplicate snapshot attribute",
+                        ));
+                    }
+                    this.snapshot = true;
+                }
+                "collect" => {
+                    if this.collect {
+                        return Err(syn::Error::new_spanned(attr, "duplicate collect attribute"));
+                    }
+                    this.collect = true;
+                }
                 _ => return Err(syn::Error::new_spanned(snd, "unknown uniffi attribute")),
             }
         }
@@ -95,6 +95,7 @@ pub fn ffi_converter_callback_interface_impl(
     let trait_name = ident_to_string(trait_ident);
     let dyn_trait = quote! { dyn #trait_ident };
     let box_dyn_trait = quote! { ::std::boxed::Box<#dyn_trait> };
+    let arc_dyn_trait = quote! { ::std::sync::Arc<#dyn_trait> };
     let lift_impl_spec = tagged_impl_header("Lift", &box_dyn_trait, udl_mode);
     let lift_ref_impl_spec = tagged_impl_header("LiftRef", &dyn_trait, udl_mode);
     let mod_path = match mod_path() {
@@ -109,12 +110,14 @@ pub fn ffi_converter_callback_interface_impl(
             type FfiType = u64;
 
             fn try_lift(v: Self::FfiType) -> ::uniffi::deps::anyhow::Result<Self> {
-                Ok(::std::boxed::Box::new(<#trait_impl_ident>::new(v)))
+                let handle = ::uniffi::check_callback_handle(v)?;
+                Ok(::std::boxed::Box::new(<#trait_impl_ident>::new(handle)))
             }
 
             fn try_read(buf: &mut &[u8]) -> ::uniffi::deps::anyhow::Result<Self> {
                 use uniffi::deps::bytes::Buf;
                 ::uniffi::check_remaining(buf, 8)?;
+                ::uniffi::check_read_handle()?;
                 <Self as ::uniffi::Lift<crate::UniFfiTag>>::try_lift(buf.get_u64())
             }
 
@@ -128,6 +131,30 @@ pub fn ffi_converter_callback_interface_impl(
         unsafe #lift_ref_impl_spec {
             type LiftType = #box_dyn_trait;
         }
+
+        // Callbacks can also be lifted into an `Arc`, for example to share an optional callback.
+        // This is only implemented for the local tag: the blanket `Lift` impl for `Arc<T>` could
+        // overlap with it for other tags.
+        #[doc(hidden)]
+        #[automatically_derived]
+        unsafe impl ::uniffi::Lift<crate::UniFfiTag> for #arc_dyn_trait {
+            type FfiType = u64;
+
+            fn try_lift(v: Self::FfiType) -> ::uniffi::deps::anyhow::Result<Self> {
+                let handle = ::uniffi::check_callback_handle(v)?;
+                Ok(::std::sync::Arc::new(<#trait_impl_ident>::new(handle)))
+            }
+
+            fn try_read(buf: &mut &[u8]) -> ::uniffi::deps::anyhow::Result<Self> {
+                use uniffi::deps::bytes::Buf;
+                ::uniffi::check_remaining(buf, 8)?;
+                ::uniffi::check_read_handle()?;
+                <Self as ::uniffi::Lift<crate::UniFfiTag>>::try_lift(buf.get_u64())
+            }
+
+            const TYPE_ID_META: ::uniffi::MetadataBuffer =
+                <#box_dyn_trait as ::uniffi::Lift<crate::UniFfiTag>>::TYPE_ID_META;
+        }
     }
 }
 
@@ -137,6 +164,7 @@ fn gen_method_impl(sig: &FnSignature, internals_ident: &Ident) -> syn::Result<To
         return_ty,
         kind,
         receiver,
+        generics,
         ..
     } = sig;
     let index = match kind {
@@ -161,17 +189,38 @@ fn gen_method_impl(sig: &FnSignature, internals_ident: &Ident) -> syn::Result<To
         }
         Some(ReceiverArg::Ref) => quote! { &self },
         Some(ReceiverArg::Arc) => quote! { self: Arc<Self> },
+        Some(ReceiverArg::Value) => {
+            return Err(syn::Error::new(
+                sig.span,
+                "callback interface methods can't take `self` by value",
+            ));
+        }
     };
     let params = sig.params();
+    let where_clause = &generics.where_clause;
     let buf_ident = Ident::new("uniffi_args_buf", Span::call_site());
+    let owned_arg_stmts = sig.owned_arg_stmts();
+    let check_lower_exprs = sig.check_lower_exprs();
     let write_exprs = sig.write_exprs(&buf_ident);
 
     Ok(quote! {
-        fn #ident(#self_param, #(#params),*) -> #return_ty {
-            #[allow(unused_mut)]
-            let mut #buf_ident = ::std::vec::Vec::new();
-            #(#write_exprs;)*
-            let uniffi_args_rbuf = uniffi::RustBuffer::from_vec(#buf_ident);
+        fn #ident #generics(#self_param, #(#params),*) -> #return_ty #where_clause {
+            #(#owned_arg_stmts)*
+            // Arguments that can't be sent to the foreign code fail the call before it's made,
+            // the same way as an unexpected error raised by the callback.
+            #(
+                if let Err(e) = #check_lower_exprs {
+                    return <#return_ty as ::uniffi::LiftReturn<crate::UniFfiTag>>::handle_callback_unexpected_error(
+                        ::uniffi::UnexpectedUniFFICallbackError::from_reason(e.to_string())
+                    );
+                }
+            )*
+            let uniffi_args_rbuf = uniffi::RustBuffer::from_writer(
+                0,
+                |#[allow(unused_mut, unused_variables)] mut #buf_ident: &mut ::std::vec::Vec<u8>| {
+                    #(#write_exprs;)*
+                },
+            );
 
             #internals_ident.invoke_callback::<#return_ty, crate::UniFfiTag>(self.handle, #index, uniffi_args_rbuf)
         }
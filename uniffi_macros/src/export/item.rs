@@ -35,7 +35,12 @@ impl ExportItem {
         match item {
             syn::Item::Fn(item) => {
                 let docstring = extract_docstring(&item.attrs)?;
-                let sig = FnSignature::new_function(item.sig, docstring)?;
+                let mut sig = if args.constant.is_some() {
+                    FnSignature::new_const_fn(item.sig, docstring)?
+                } else {
+                    FnSignature::new_function(item.sig, docstring)?
+                };
+                sig.runtime_initializer = args.runtime_initializer.is_some();
                 Ok(Self::Function { sig })
             }
             syn::Item::Impl(item) => Self::from_impl(item, args.constructor.is_some()),
@@ -94,17 +99,42 @@ impl ExportItem {
                 let docstring = extract_docstring(&impl_fn.attrs)?;
                 let attrs = ExportedImplFnAttributes::new(&impl_fn.attrs)?;
                 let item = if force_constructor || attrs.constructor {
+                    if attrs.snapshot {
+                        return Err(syn::Error::new_spanned(
+                            &impl_fn.sig,
+                            "constructors can't be snapshots",
+                        ));
+                    }
+                    if attrs.collect {
+                        return Err(syn::Error::new_spanned(
+                            &impl_fn.sig,
+                            "constructors can't be collect methods",
+                        ));
+                    }
                     ImplItem::Constructor(FnSignature::new_constructor(
                         self_ident.clone(),
                         impl_fn.sig,
                         docstring,
                     )?)
-                } else {
-                    ImplItem::Method(FnSignature::new_method(
+                } else if attrs.collect {
+                    if attrs.snapshot {
+                        return Err(syn::Error::new_spanned(
+                            &impl_fn.sig,
+                            "collect methods can't be snapshots",
+                        ));
+                    }
+                    ImplItem::Method(FnSignature::new_collect_method(
                         self_ident.clone(),
                         impl_fn.sig,
                         docstring,
                     )?)
+                } else {
+                    let sig = FnSignature::new_method(self_ident.clone(), impl_fn.sig, docstring)?;
+                    ImplItem::Method(if attrs.snapshot {
+                        sig.into_snapshot()?
+                    } else {
+                        sig
+                    })
                 };
 
                 Ok(item)
@@ -149,11 +179,25 @@ impl ExportItem {
                         tim,
                         "exported traits can not have constructors",
                     ));
+                } else if attrs.snapshot {
+                    return Err(syn::Error::new_spanned(
+                        tim,
+                        "trait methods can't be snapshots",
+                    ));
+                } else if attrs.collect {
+                    return Err(syn::Error::new_spanned(
+                        tim,
+                        "trait methods can't be collect methods",
+                    ));
                 } else {
+                    // Callback interfaces are always implemented on the foreign side, so only
+                    // trait interfaces need to know about default implementations.
+                    let has_default = !callback_interface && tim.default.is_some();
                     ImplItem::Method(FnSignature::new_trait_method(
                         self_ident.clone(),
                         tim.sig,
                         i as u32,
+                        has_default,
                         docstring,
                     )?)
                 };
@@ -7,7 +7,10 @@ use quote::quote;
 use std::iter;
 
 use super::attributes::{AsyncRuntime, ExportAttributeArguments};
-use crate::fnsig::{FnKind, FnSignature, NamedArg};
+use crate::{
+    fnsig::{FnKind, FnSignature, NamedArg, ReceiverArg},
+    util::ident_to_string,
+};
 
 pub(super) fn gen_fn_scaffolding(
     sig: FnSignature,
@@ -28,6 +31,20 @@ pub(super) fn gen_fn_scaffolding(
             ));
         }
     }
+    if let Some(chunked) = &arguments.chunked {
+        if !udl_mode {
+            return Err(syn::Error::new_spanned(
+                chunked,
+                "this attribute is only supported for UDL functions",
+            ));
+        }
+        if sig.is_async || sig.looks_like_result {
+            return Err(syn::Error::new_spanned(
+                chunked,
+                "this attribute is only allowed on sync functions which don't return a `Result`",
+            ));
+        }
+    }
     let metadata_items = (!udl_mode).then(|| {
         sig.metadata_items()
             .unwrap_or_else(syn::Error::into_compile_error)
@@ -50,9 +67,6 @@ pub(super) fn gen_constructor_scaffolding(
             "constructors must not have a self parameter",
         ));
     }
-    if sig.is_async {
-        return Err(syn::Error::new(sig.span, "constructors can't be async"));
-    }
     let metadata_items = (!udl_mode).then(|| {
         sig.metadata_items()
             .unwrap_or_else(syn::Error::into_compile_error)
@@ -103,13 +117,29 @@ impl ScaffoldingBits {
         let ident = &sig.ident;
         let params: Vec<_> = sig.args.iter().map(NamedArg::scaffolding_param).collect();
         let call_params = sig.rust_call_params(false);
-        let rust_fn_call = quote! { #ident(#call_params) };
-        // UDL mode adds an extra conversion (#1749)
-        let rust_fn_call = if udl_mode && sig.looks_like_result {
-            quote! { #rust_fn_call.map_err(::std::convert::Into::into) }
+        let rust_fn_call = sig.owned_return(quote! { #ident(#call_params) });
+        let rust_fn_call = if sig.has_out_args() {
+            sig.out_params_return(rust_fn_call)
         } else {
             rust_fn_call
         };
+        // UDL mode adds an extra conversion (#1749)
+        let rust_fn_call = match (udl_mode && sig.looks_like_result, sig.is_async) {
+            (true, false) => quote! { #rust_fn_call.map_err(::std::convert::Into::into) },
+            // Async functions need to apply it to the output of the future, which has to be
+            // annotated with the return type to infer what it's converted into.
+            (true, true) => {
+                let return_ty = &sig.return_ty;
+                quote! {
+                    async move {
+                        let result: #return_ty =
+                            #rust_fn_call.await.map_err(::std::convert::Into::into);
+                        result
+                    }
+                }
+            }
+            (false, _) => rust_fn_call,
+        };
 
         Self {
             params,
@@ -130,8 +160,9 @@ impl ScaffoldingBits {
                 <::std::sync::Arc<dyn #self_ident> as ::uniffi::Lift<crate::UniFfiTag>>
             }
         } else {
+            // Objects are lifted as `Arc<Self>` and enums by value.
             quote! {
-                <::std::sync::Arc<#self_ident> as ::uniffi::Lift<crate::UniFfiTag>>
+                <<#self_ident as ::uniffi::LiftRef<crate::UniFfiTag>>::LiftType as ::uniffi::Lift<crate::UniFfiTag>>
             }
         };
         let params: Vec<_> = iter::once(quote! { uniffi_self_lowered: #lift_impl::FfiType })
@@ -148,6 +179,20 @@ impl ScaffoldingBits {
                     Ok(*boxed_foreign_arc)
                 }
             }
+        } else if sig.takes_self_by_value() {
+            // Consuming methods need the only reference to the object, which the foreign code
+            // gives up for the call.
+            let error_message = format!(
+                "`{}.{}` consumes the object, but there are other references to it",
+                ident_to_string(self_ident),
+                sig.name,
+            );
+            quote! {
+                #lift_impl::try_lift(uniffi_self_lowered).and_then(|v| {
+                    ::uniffi::ConsumeReceiver::<#self_ident>::consume_receiver(v)
+                        .ok_or_else(|| ::uniffi::deps::anyhow::anyhow!(#error_message))
+                })
+            }
         } else {
             quote! { #lift_impl::try_lift(uniffi_self_lowered) }
         };
@@ -159,13 +204,70 @@ impl ScaffoldingBits {
             }
         }));
         let call_params = sig.rust_call_params(true);
-        let rust_fn_call = quote! { uniffi_args.0.#ident(#call_params) };
-        // UDL mode adds an extra conversion (#1749)
-        let rust_fn_call = if udl_mode && sig.looks_like_result {
-            quote! { #rust_fn_call.map_err(::std::convert::Into::into) }
+        let rust_fn_call = sig.owned_return(quote! { uniffi_args.0.#ident(#call_params) });
+        // Snapshot methods copy the view they return before the borrow of `self` ends.
+        let rust_fn_call = if sig.snapshot {
+            quote! { ::uniffi::Snapshot::snapshot(#rust_fn_call) }
+        } else if sig.collect {
+            // Collect methods collect the iterator they return before the borrow of `self` ends.
+            quote! { ::std::iter::Iterator::collect::<::std::vec::Vec<_>>(#rust_fn_call) }
         } else {
             rust_fn_call
         };
+        // UDL mode adds an extra conversion (#1749)
+        let rust_fn_call = match (udl_mode && sig.looks_like_result, sig.is_async) {
+            (true, false) => quote! { #rust_fn_call.map_err(::std::convert::Into::into) },
+            // Async functions need to apply it to the output of the future, which has to be
+            // annotated with the return type to infer what it's converted into.
+            (true, true) => {
+                let return_ty = &sig.return_ty;
+                quote! {
+                    async move {
+                        let result: #return_ty =
+                            #rust_fn_call.await.map_err(::std::convert::Into::into);
+                        result
+                    }
+                }
+            }
+            (false, _) => rust_fn_call,
+        };
+
+        Self {
+            params,
+            lift_closure,
+            rust_fn_call,
+        }
+    }
+
+    /// Scaffolding for calling the trait's default implementation of a method.
+    ///
+    /// `self` is a foreign trait impl, which gets wrapped in `default_impl_ident` so that
+    /// the trait's default method body is used rather than calling back into the foreign code.
+    fn new_for_default_method(
+        sig: &FnSignature,
+        self_ident: &Ident,
+        default_impl_ident: &Ident,
+    ) -> Self {
+        let ident = &sig.ident;
+        let lift_impl = quote! {
+            <::std::sync::Arc<dyn #self_ident> as ::uniffi::Lift<crate::UniFfiTag>>
+        };
+        let params: Vec<_> = iter::once(quote! { uniffi_self_lowered: #lift_impl::FfiType })
+            .chain(sig.scaffolding_params())
+            .collect();
+        let lift_closure = sig.lift_closure(Some(quote! {
+            match #lift_impl::try_lift(uniffi_self_lowered) {
+                Ok(v) => #default_impl_ident(v),
+                Err(e) => return Err(("self", e))
+            }
+        }));
+        let call_params = sig.rust_call_params(true);
+        let rust_fn_call = match sig.receiver {
+            Some(ReceiverArg::Arc) => quote! {
+                ::std::sync::Arc::new(uniffi_args.0).#ident(#call_params)
+            },
+            _ => quote! { uniffi_args.0.#ident(#call_params) },
+        };
 
         Self {
             params,
@@ -180,13 +282,24 @@ impl ScaffoldingBits {
         let call_params = sig.rust_call_params(false);
         let rust_fn_call = quote! { #self_ident::#ident(#call_params) };
         // UDL mode adds extra conversions (#1749)
-        let rust_fn_call = match (udl_mode, sig.looks_like_result) {
+        let rust_fn_call = match (udl_mode, sig.is_async, sig.looks_like_result) {
             // For UDL
-            (true, false) => quote! { ::std::sync::Arc::new(#rust_fn_call) },
-            (true, true) => {
+            (true, false, false) => quote! { ::std::sync::Arc::new(#rust_fn_call) },
+            (true, false, true) => {
                 quote! { #rust_fn_call.map(::std::sync::Arc::new).map_err(::std::convert::Into::into) }
             }
-            (false, _) => rust_fn_call,
+            // Async UDL constructors need to apply the conversions to the output of the future.
+            (true, true, false) => {
+                quote! { async move { ::std::sync::Arc::new(#rust_fn_call.await) } }
+            }
+            (true, true, true) => {
+                quote! {
+                    async move {
+                        #rust_fn_call.await.map(::std::sync::Arc::new).map_err(::std::convert::Into::into)
+                    }
+                }
+            }
+            (false, _, _) => rust_fn_call,
         };
 
         Self {
@@ -211,7 +324,7 @@ pub(super) fn gen_ffi_function(
         lift_closure,
         rust_fn_call,
     } = match &sig.kind {
-        FnKind::Function => ScaffoldingBits::new_for_function(sig, udl_mode),
+        FnKind::Function | FnKind::ConstFn => ScaffoldingBits::new_for_function(sig, udl_mode),
         FnKind::Method { self_ident } => {
             ScaffoldingBits::new_for_method(sig, self_ident, false, udl_mode)
         }
@@ -231,30 +344,90 @@ pub(super) fn gen_ffi_function(
 
     let ffi_ident = sig.scaffolding_fn_ident()?;
     let name = &sig.name;
-    let return_impl = &sig.return_impl();
+    let mut return_impl = sig.return_impl();
 
-    Ok(if !sig.is_async {
-        quote! {
-            #[doc(hidden)]
-            #[no_mangle]
-            #vis extern "C" fn #ffi_ident(
-                #(#params,)*
-                call_status: &mut ::uniffi::RustCallStatus,
-            ) -> #return_impl::ReturnType {
-                ::uniffi::deps::log::debug!(#name);
-                let uniffi_lift_args = #lift_closure;
-                ::uniffi::rust_call(call_status, || {
-                    #return_impl::lower_return(
-                        match uniffi_lift_args() {
-                            Ok(uniffi_args) => #rust_fn_call,
-                            Err((arg_name, anyhow_error)) => {
-                                #return_impl::handle_failed_lift(arg_name, anyhow_error)
-                            },
-                        }
-                    )
-                })
-            }
+    // `[ThreadBound]` objects are bound to the thread which constructs them, and each method call
+    // checks it's made on that thread.  The handles record the thread, see `uniffi::ThreadBound`.
+    let mut thread_check = None;
+    let rust_fn_call = match (&arguments.thread_bound, &sig.kind) {
+        (Some(thread_bound), _) if sig.is_async => {
+            return Err(syn::Error::new_spanned(
+                thread_bound,
+                "thread-bound objects can't have async constructors or methods",
+            ));
+        }
+        (Some(_), FnKind::Constructor { .. }) => {
+            let return_ty = &sig.return_ty;
+            return_impl = quote! {
+                <::uniffi::BindToCurrentThread<#return_ty> as ::uniffi::LowerReturn<crate::UniFfiTag>>
+            };
+            quote! { ::uniffi::BindToCurrentThread(#rust_fn_call) }
         }
+        (Some(_), FnKind::Method { self_ident }) => {
+            let method_name = format!("{}.{}", ident_to_string(self_ident), sig.name);
+            thread_check = Some(quote! {
+                // SAFETY: the foreign code passes a handle it owns for the call.
+                if let Err(message) = unsafe {
+                    ::uniffi::check_bound_thread::<#self_ident>(uniffi_self_lowered, #method_name)
+                } {
+                    // Lift the arguments anyway, so that the values passed for the call are dropped.
+                    // This uses its own status, since `call_status` gets the wrong thread error.
+                    let mut lift_status = ::uniffi::RustCallStatus {
+                        code: ::uniffi::RustCallStatusCode::Success,
+                        error_buf: ::std::mem::MaybeUninit::new(::uniffi::RustBuffer::new()),
+                    };
+                    ::uniffi::rust_call(&mut lift_status, || {
+                        ::std::mem::drop(uniffi_lift_args());
+                        Ok(())
+                    });
+                    // SAFETY: the error buffer starts out initialized, and `rust_call()` only
+                    // ever replaces it with another buffer.
+                    unsafe { lift_status.error_buf.assume_init() }.destroy();
+                    *call_status = ::uniffi::RustCallStatus::wrong_thread(message);
+                    return ::uniffi::FfiDefault::ffi_default();
+                }
+            });
+            rust_fn_call
+        }
+        _ => rust_fn_call,
+    };
+
+    // The methods of `[OnPoison=...]` interfaces are called with the interface's policy, so that
+    // panicking on poisoned state fails the call with a `StatePoisoned` error.  See
+    // `uniffi::call_with_on_poison`.
+    let rust_fn_call = match (&arguments.on_poison, &sig.kind) {
+        (Some(on_poison), _) if sig.is_async => {
+            return Err(syn::Error::new_spanned(
+                on_poison,
+                "objects with an `on_poison` policy can't have async constructors or methods",
+            ));
+        }
+        (Some(on_poison), FnKind::Method { .. }) => {
+            let policy = on_poison.policy_tokens();
+            quote! { ::uniffi::call_with_on_poison(#policy, || #rust_fn_call) }
+        }
+        _ => rust_fn_call,
+    };
+
+    Ok(if arguments.chunked.is_some() {
+        // Return the sequence in chunks, via a `ChunkedSequence` handle.
+        let item_ty = chunked_item_type(sig)?;
+        let bits = ScaffoldingBits {
+            params,
+            lift_closure,
+            rust_fn_call: quote! { ::uniffi::ChunkedSequence::new(#rust_fn_call) },
+        };
+        let return_impl = quote! {
+            <::uniffi::ChunkedSequence<#item_ty> as ::uniffi::LowerReturn<crate::UniFfiTag>>
+        };
+        gen_sync_ffi_function(sig, &ffi_ident, bits, vis, return_impl, None)
+    } else if !sig.is_async {
+        let bits = ScaffoldingBits {
+            params,
+            lift_closure,
+            rust_fn_call,
+        };
+        gen_sync_ffi_function(sig, &ffi_ident, bits, vis, return_impl, thread_check)
     } else {
         let mut future_expr = rust_fn_call;
         if matches!(arguments.async_runtime, Some(AsyncRuntime::Tokio(_))) {
@@ -287,3 +460,89 @@ pub(super) fn gen_ffi_function(
         }
     })
 }
+
+/// Generate a scaffolding function that calls the trait's default implementation of a method
+pub(super) fn gen_default_method_ffi_function(
+    sig: &FnSignature,
+    default_impl_ident: &Ident,
+) -> syn::Result<TokenStream> {
+    let self_ident = match &sig.kind {
+        FnKind::TraitMethod { self_ident, .. } => self_ident,
+        k => {
+            return Err(syn::Error::new(
+                sig.span,
+                format!("Internal UniFFI error: Unexpected function kind for default method {k:?}"),
+            ))
+        }
+    };
+    let bits = ScaffoldingBits::new_for_default_method(sig, self_ident, default_impl_ident);
+    let ffi_ident = sig.default_scaffolding_fn_ident()?;
+    Ok(gen_sync_ffi_function(
+        sig,
+        &ffi_ident,
+        bits,
+        quote! { pub },
+        sig.return_impl(),
+        None,
+    ))
+}
+
+/// Get `T` for a chunked function returning `Vec<T>`
+fn chunked_item_type(sig: &FnSignature) -> syn::Result<syn::Type> {
+    let return_ty: syn::Type = syn::parse2(sig.return_ty.clone())?;
+    if let syn::Type::Path(path) = &return_ty {
+        if let Some(segment) = path.path.segments.last() {
+            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                if let (true, Some(syn::GenericArgument::Type(item_ty))) =
+                    (segment.ident == "Vec", args.args.first())
+                {
+                    return Ok(item_ty.clone());
+                }
+            }
+        }
+    }
+    Err(syn::Error::new(
+        sig.span,
+        "chunked functions must return a `Vec`",
+    ))
+}
+
+/// `thread_check` is run before the Rust call, and returns early if the call should fail.
+fn gen_sync_ffi_function(
+    sig: &FnSignature,
+    ffi_ident: &Ident,
+    bits: ScaffoldingBits,
+    vis: TokenStream,
+    return_impl: TokenStream,
+    thread_check: Option<TokenStream>,
+) -> TokenStream {
+    let ScaffoldingBits {
+        params,
+        lift_closure,
+        rust_fn_call,
+    } = bits;
+    let name = &sig.name;
+
+    quote! {
+        #[doc(hidden)]
+        #[no_mangle]
+        #vis extern "C" fn #ffi_ident(
+            #(#params,)*
+            call_status: &mut ::uniffi::RustCallStatus,
+        ) -> #return_impl::ReturnType {
+            ::uniffi::deps::log::debug!(#name);
+            let uniffi_lift_args = #lift_closure;
+            #thread_check
+            ::uniffi::rust_call_checking_reentrancy(call_status, || {
+                // Lower the return value while the args are still alive, since it may borrow
+                // from them (for example a method returning `Cow<str>`).
+                match uniffi_lift_args() {
+                    Ok(uniffi_args) => #return_impl::try_lower_return(#rust_fn_call),
+                    Err((arg_name, anyhow_error)) => #return_impl::try_lower_return(
+                        #return_impl::handle_failed_lift(arg_name, anyhow_error)
+                    ),
+                }
+            })
+        }
+    }
+}
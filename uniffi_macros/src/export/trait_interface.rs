@@ -7,9 +7,10 @@ use quote::{quote, quote_spanned};
 
 use crate::{
     export::{
-        attributes::ExportAttributeArguments, callback_interface, gen_method_scaffolding,
-        item::ImplItem,
+        attributes::ExportAttributeArguments, callback_interface, gen_default_method_ffi_function,
+        gen_method_scaffolding, item::ImplItem,
     },
+    fnsig::ReceiverArg,
     object::interface_meta_static_var,
     util::{ident_to_string, tagged_impl_header},
 };
@@ -28,6 +29,7 @@ pub(super) fn gen_trait_scaffolding(
     let trait_name = ident_to_string(&self_ident);
     let trait_impl = callback_interface::trait_impl(mod_path, &self_ident, &items)
         .unwrap_or_else(|e| e.into_compile_error());
+    let default_impl = gen_default_impl(&self_ident, &items)?;
 
     let clone_fn_ident = Ident::new(
         &uniffi_meta::clone_fn_symbol_name(mod_path, &trait_name),
@@ -37,6 +39,10 @@ pub(super) fn gen_trait_scaffolding(
         &uniffi_meta::free_fn_symbol_name(mod_path, &trait_name),
         Span::call_site(),
     );
+    let handle_id_fn_ident = Ident::new(
+        &uniffi_meta::handle_id_fn_symbol_name(mod_path, &trait_name),
+        Span::call_site(),
+    );
 
     let helper_fn_tokens = quote! {
         #[doc(hidden)]
@@ -75,6 +81,25 @@ pub(super) fn gen_trait_scaffolding(
                 Ok(())
             });
         }
+
+        #[doc(hidden)]
+        #[no_mangle]
+        /// Get the id of the object behind a pointer to this object type
+        ///
+        /// Each pointer is a separate box, so this is the address of the object inside the `Arc`.
+        ///
+        /// Safety: Only pass pointers returned by a UniFFI call.  Do not pass pointers that were
+        /// passed to the free function.
+        pub unsafe extern "C" fn #handle_id_fn_ident(
+            ptr: *const ::std::ffi::c_void,
+            call_status: &mut ::uniffi::RustCallStatus
+        ) -> u64 {
+            uniffi::rust_call(call_status, || {
+                assert!(!ptr.is_null());
+                let arc = unsafe { &*(ptr as *const std::sync::Arc<dyn #self_ident>) };
+                Ok(::uniffi::arc_handle_id(arc))
+            })
+        }
     };
 
     let impl_tokens: TokenStream = items
@@ -99,15 +124,83 @@ pub(super) fn gen_trait_scaffolding(
     });
     let ffi_converter_tokens = ffi_converter(mod_path, &self_ident, udl_mode);
 
+    // Check the trait is `Send + Sync` first, so its error points at the trait.
+    let send_sync_check = quote_spanned! { self_ident.span() =>
+        impl ::uniffi::ExportedTraitMustBeSendAndSync for dyn #self_ident {}
+    };
+
     Ok(quote_spanned! { self_ident.span() =>
+        #send_sync_check
         #meta_static_var
         #helper_fn_tokens
         #trait_impl
+        #default_impl
         #impl_tokens
         #ffi_converter_tokens
     })
 }
 
+/// Generate scaffolding functions for methods with a default implementation.
+///
+/// Foreign implementations which don't override one of these methods call the scaffolding
+/// function, which runs the trait's default method body.  To do that, the foreign impl is wrapped
+/// in a struct which forwards the other methods back to the foreign impl, but leaves the defaulted
+/// methods to the trait.
+fn gen_default_impl(self_ident: &Ident, items: &[ImplItem]) -> syn::Result<TokenStream> {
+    let sigs: Vec<_> = items
+        .iter()
+        .map(|item| match item {
+            ImplItem::Method(sig) => sig,
+            _ => unreachable!("traits have no constructors"),
+        })
+        .collect();
+    if !sigs.iter().any(|sig| sig.has_default()) {
+        return Ok(TokenStream::new());
+    }
+
+    let default_impl_ident = default_impl_ident(&ident_to_string(self_ident));
+    let forwarding_methods = sigs.iter().filter(|sig| !sig.has_default()).map(|sig| {
+        let ident = &sig.ident;
+        let return_ty = &sig.return_ty;
+        let generics = &sig.generics;
+        let where_clause = &generics.where_clause;
+        let params = sig.params();
+        let args = sig.args.iter().map(|arg| &arg.ident);
+        match sig.receiver {
+            Some(ReceiverArg::Arc) => quote! {
+                fn #ident #generics(self: ::std::sync::Arc<Self>, #(#params),*) -> #return_ty #where_clause {
+                    ::std::sync::Arc::clone(&self.0).#ident(#(#args),*)
+                }
+            },
+            _ => quote! {
+                fn #ident #generics(&self, #(#params),*) -> #return_ty #where_clause {
+                    self.0.#ident(#(#args),*)
+                }
+            },
+        }
+    });
+    let default_fns = sigs
+        .iter()
+        .filter(|sig| sig.has_default())
+        .map(|sig| gen_default_method_ffi_function(sig, &default_impl_ident))
+        .collect::<syn::Result<TokenStream>>()?;
+
+    Ok(quote! {
+        #[doc(hidden)]
+        struct #default_impl_ident(::std::sync::Arc<dyn #self_ident>);
+
+        impl #self_ident for #default_impl_ident {
+            #(#forwarding_methods)*
+        }
+
+        #default_fns
+    })
+}
+
+fn default_impl_ident(trait_name: &str) -> Ident {
+    Ident::new(&format!("UniFFIDefaultImpl{trait_name}"), Span::call_site())
+}
+
 pub(crate) fn ffi_converter(mod_path: &str, trait_ident: &Ident, udl_mode: bool) -> TokenStream {
     let impl_spec = tagged_impl_header("FfiConverterArc", &quote! { dyn #trait_ident }, udl_mode);
     let lift_ref_impl_spec = tagged_impl_header("LiftRef", &quote! { dyn #trait_ident }, udl_mode);
@@ -140,9 +233,14 @@ pub(crate) fn ffi_converter(mod_path: &str, trait_ident: &Ident, udl_mode: bool)
                 );
             }
 
+            fn size_hint(_obj: &::std::sync::Arc<Self>) -> usize {
+                8
+            }
+
             fn try_read(buf: &mut &[u8]) -> ::uniffi::Result<::std::sync::Arc<Self>> {
                 ::uniffi::deps::static_assertions::const_assert!(::std::mem::size_of::<*const ::std::ffi::c_void>() <= 8);
                 ::uniffi::check_remaining(buf, 8)?;
+                ::uniffi::check_read_handle()?;
                 <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::try_lift(
                     ::uniffi::deps::bytes::Buf::get_u64(buf) as Self::FfiType)
             }
@@ -7,7 +7,11 @@ use crate::util::{
 };
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{spanned::Spanned, FnArg, Ident, Pat, Receiver, ReturnType, Type};
+use syn::{
+    spanned::Spanned, visit_mut::VisitMut, FnArg, GenericArgument, GenericParam, Generics, Ident,
+    Lifetime, Pat, PathArguments, PathSegment, Receiver, ReturnType, Type, TypeParamBound,
+    TypeReference,
+};
 
 pub(crate) struct FnSignature {
     pub kind: FnKind,
@@ -16,6 +20,8 @@ pub(crate) struct FnSignature {
     pub ident: Ident,
     pub name: String,
     pub is_async: bool,
+    // Lifetime parameters of the function, which are needed to re-declare trait methods.
+    pub generics: Generics,
     pub receiver: Option<ReceiverArg>,
     pub args: Vec<NamedArg>,
     pub return_ty: TokenStream,
@@ -23,6 +29,18 @@ pub(crate) struct FnSignature {
     // Only use this in UDL mode.
     // In general, it's not reliable because it fails for type aliases.
     pub looks_like_result: bool,
+    // Is the return value a view which is converted with `uniffi::Snapshot`?
+    pub snapshot: bool,
+    // Is the return value an iterator which is collected into a `Vec`?
+    pub collect: bool,
+    // Is the return value a `'static` borrow, like `&'static str`, which is copied with `ToOwned`?
+    pub static_return: bool,
+    // Does the function return `()`, or `Result<(), E>`?  Functions with out-parameters then only
+    // serialize the out-parameters.
+    pub unit_return: bool,
+    // Is this the function exported by `uniffi::export_runtime_config!()`, which the bindings call
+    // from `uniffiInitialize(config)`?
+    pub runtime_initializer: bool,
     pub docstring: String,
 }
 
@@ -31,6 +49,34 @@ impl FnSignature {
         Self::new(FnKind::Function, sig, docstring)
     }
 
+    pub(crate) fn new_const_fn(sig: syn::Signature, docstring: String) -> syn::Result<Self> {
+        if !sig.inputs.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &sig.inputs,
+                "constant functions can't have arguments",
+            ));
+        }
+        if let Some(asyncness) = &sig.asyncness {
+            return Err(syn::Error::new_spanned(
+                asyncness,
+                "constant functions can't be async",
+            ));
+        }
+        if matches!(sig.output, ReturnType::Default) {
+            return Err(syn::Error::new_spanned(
+                &sig,
+                "constant functions must return a value",
+            ));
+        }
+        if looks_like_result(&sig.output) {
+            return Err(syn::Error::new_spanned(
+                &sig.output,
+                "constant functions can't return a `Result`",
+            ));
+        }
+        Self::new(FnKind::ConstFn, sig, docstring)
+    }
+
     pub(crate) fn new_method(
         self_ident: Ident,
         sig: syn::Signature,
@@ -39,6 +85,36 @@ impl FnSignature {
         Self::new(FnKind::Method { self_ident }, sig, docstring)
     }
 
+    /// A method marked with `#[uniffi::collect]`, which returns `impl Iterator<Item = T>`.
+    ///
+    /// The iterator is collected during the call, so the method is exported as returning `Vec<T>`.
+    pub(crate) fn new_collect_method(
+        self_ident: Ident,
+        mut sig: syn::Signature,
+        docstring: String,
+    ) -> syn::Result<Self> {
+        if let Some(asyncness) = &sig.asyncness {
+            return Err(syn::Error::new_spanned(
+                asyncness,
+                "collect methods can't be async",
+            ));
+        }
+        let item_ty = match &sig.output {
+            ReturnType::Type(_, ty) => iterator_item_type(ty),
+            ReturnType::Default => None,
+        }
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &sig.output,
+                "collect methods must return `impl Iterator<Item = T>`",
+            )
+        })?;
+        sig.output = syn::parse_quote! { -> ::std::vec::Vec<#item_ty> };
+        let mut this = Self::new(FnKind::Method { self_ident }, sig, docstring)?;
+        this.collect = true;
+        Ok(this)
+    }
+
     pub(crate) fn new_constructor(
         self_ident: Ident,
         sig: syn::Signature,
@@ -51,27 +127,60 @@ impl FnSignature {
         self_ident: Ident,
         sig: syn::Signature,
         index: u32,
+        has_default: bool,
         docstring: String,
     ) -> syn::Result<Self> {
-        Self::new(FnKind::TraitMethod { self_ident, index }, sig, docstring)
+        if let Some(FnArg::Receiver(receiver)) = sig.inputs.first() {
+            if matches!(ReceiverArg::from(receiver.clone()), ReceiverArg::Value) {
+                return Err(syn::Error::new_spanned(
+                    receiver,
+                    "trait methods can't take `self` by value",
+                ));
+            }
+        }
+        Self::new(
+            FnKind::TraitMethod {
+                self_ident,
+                index,
+                has_default,
+            },
+            sig,
+            docstring,
+        )
     }
 
     pub(crate) fn new(kind: FnKind, sig: syn::Signature, docstring: String) -> syn::Result<Self> {
         let span = sig.span();
+        check_generics(&sig.generics)?;
+        if let ReturnType::Type(_, ty) = &sig.output {
+            if let Type::ImplTrait(_) = ty.as_ref() {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "`impl Trait` return types are not supported, except for methods which \
+                     return `impl Iterator` and are marked with `#[uniffi::collect]`",
+                ));
+            }
+        }
         let ident = sig.ident;
         let looks_like_result = looks_like_result(&sig.output);
-        let output = match sig.output {
-            ReturnType::Default => quote! { () },
-            ReturnType::Type(_, ty) => quote! { #ty },
+        let static_return = match &sig.output {
+            ReturnType::Type(_, ty) => static_borrow(ty),
+            ReturnType::Default => None,
         };
-        let is_async = sig.asyncness.is_some();
-
-        if is_async && matches!(kind, FnKind::Constructor { .. }) {
-            return Err(syn::Error::new(
-                span,
-                "Async constructors are not supported",
+        if static_return.is_some() && matches!(kind, FnKind::TraitMethod { .. }) {
+            return Err(syn::Error::new_spanned(
+                &sig.output,
+                "trait methods can't return a `'static` borrow",
             ));
         }
+        let output = match (static_return, &sig.output) {
+            (Some(elem), _) => quote! { <#elem as ::std::borrow::ToOwned>::Owned },
+            (None, ReturnType::Default) => quote! { () },
+            (None, ReturnType::Type(_, ty)) => quote! { #ty },
+        };
+        let static_return = static_return.is_some();
+        let is_async = sig.asyncness.is_some();
+        let generics = sig.generics;
 
         let mut input_iter = sig.inputs.into_iter().map(Arg::try_from).peekable();
 
@@ -96,6 +205,32 @@ impl FnSignature {
             .collect::<syn::Result<Vec<_>>>()?;
         let mod_path = mod_path()?;
 
+        let unit_return = match &sig.output {
+            ReturnType::Default => true,
+            ReturnType::Type(_, ty) if looks_like_result => {
+                is_unit(result_ok_type(ty).unwrap_or(ty))
+            }
+            ReturnType::Type(_, ty) => is_unit(ty),
+        };
+        let output = match args.iter().find(|a| a.out) {
+            Some(arg) => {
+                if !matches!(kind, FnKind::Function) {
+                    return Err(syn::Error::new_spanned(
+                        &arg.declared_ty,
+                        "`&mut` out-parameters are only supported for functions",
+                    ));
+                }
+                if is_async {
+                    return Err(syn::Error::new_spanned(
+                        &arg.declared_ty,
+                        "functions with `&mut` out-parameters can't be async",
+                    ));
+                }
+                out_params_return_ty(output, looks_like_result)?
+            }
+            None => output,
+        };
+
         Ok(Self {
             kind,
             span,
@@ -103,14 +238,149 @@ impl FnSignature {
             name: ident_to_string(&ident),
             ident,
             is_async,
+            generics,
             receiver,
             args,
             return_ty: output,
             looks_like_result,
+            snapshot: false,
+            collect: false,
+            static_return,
+            unit_return,
+            runtime_initializer: false,
             docstring,
         })
     }
 
+    /// Convert a method marked with `#[uniffi::snapshot]`.
+    ///
+    /// The return type may borrow from the object, so the method returns its
+    /// `uniffi::Snapshot::Owned` type instead.  That can't depend on the lifetime of the borrow,
+    /// so the lifetimes are replaced with `'static` to name it.
+    pub(crate) fn into_snapshot(mut self) -> syn::Result<Self> {
+        if self.is_async {
+            return Err(syn::Error::new(
+                self.span,
+                "snapshot methods can't be async",
+            ));
+        }
+        if self.looks_like_result {
+            return Err(syn::Error::new(
+                self.span,
+                "snapshot methods can't return a `Result`",
+            ));
+        }
+        if matches!(self.receiver, Some(ReceiverArg::Value)) {
+            return Err(syn::Error::new(
+                self.span,
+                "snapshot methods can't take `self` by value",
+            ));
+        }
+
+        struct StaticLifetimes;
+
+        impl VisitMut for StaticLifetimes {
+            fn visit_lifetime_mut(&mut self, i: &mut Lifetime) {
+                *i = Lifetime::new("'static", i.span());
+            }
+
+            fn visit_type_reference_mut(&mut self, i: &mut TypeReference) {
+                i.lifetime = Some(Lifetime::new("'static", i.and_token.span()));
+                syn::visit_mut::visit_type_reference_mut(self, i);
+            }
+        }
+
+        let mut return_ty: Type = syn::parse2(self.return_ty)?;
+        StaticLifetimes.visit_type_mut(&mut return_ty);
+        self.return_ty = quote! { <#return_ty as ::uniffi::Snapshot>::Owned };
+        self.snapshot = true;
+        Ok(self)
+    }
+
+    /// Copy the value returned by `rust_fn_call`, if it's a `'static` borrow.
+    pub fn owned_return(&self, rust_fn_call: TokenStream) -> TokenStream {
+        match (self.static_return, self.is_async) {
+            (false, _) => rust_fn_call,
+            (true, false) => quote! { ::std::borrow::ToOwned::to_owned(#rust_fn_call) },
+            (true, true) => quote! {
+                async move { ::std::borrow::ToOwned::to_owned(#rust_fn_call.await) }
+            },
+        }
+    }
+
+    /// Does this function have `&mut` out-parameters?
+    pub fn has_out_args(&self) -> bool {
+        self.args.iter().any(|a| a.out)
+    }
+
+    /// Serialize the value returned by `rust_fn_call` along with the final values of the
+    /// out-parameters, for a function which has them.
+    ///
+    /// This must be called in the scope of the `uniffi_args` from [Self::lift_closure].
+    pub fn out_params_return(&self, rust_fn_call: TokenStream) -> TokenStream {
+        let out_args: Vec<_> = self
+            .args
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| a.out)
+            .map(|(i, arg)| (syn::Index::from(i), arg.lower_impl()))
+            .collect();
+        let check_out_args = out_args.iter().map(|(idx, lower_impl)| {
+            quote! { #lower_impl::check_lower(&uniffi_args.#idx)?; }
+        });
+        let write_out_args = out_args.iter().map(|(idx, lower_impl)| {
+            quote! { #lower_impl::write(uniffi_args.#idx, &mut uniffi_buf); }
+        });
+        let (value_pat, check_value, write_value) = if self.unit_return {
+            (quote! { () }, None, None)
+        } else {
+            (
+                quote! { uniffi_value },
+                Some(quote! {
+                    ::uniffi::Lower::<crate::UniFfiTag>::check_lower(&uniffi_value)?;
+                }),
+                Some(quote! {
+                    ::uniffi::Lower::<crate::UniFfiTag>::write(uniffi_value, &mut uniffi_buf);
+                }),
+            )
+        };
+        // Everything is checked before it's serialized, so a rejected value isn't lowered.
+        let serialize = quote! {
+            |#value_pat| {
+                let uniffi_check = || -> ::uniffi::Result<()> {
+                    #check_value
+                    #(#check_out_args)*
+                    Ok(())
+                };
+                if let Err(e) = uniffi_check() {
+                    return ::uniffi::OutParams::rejected(e);
+                }
+                let mut uniffi_buf = ::std::vec::Vec::new();
+                #write_value
+                #(#write_out_args)*
+                ::uniffi::OutParams::new(uniffi_buf)
+            }
+        };
+        // Errors are returned as usual, without the out-parameters.
+        let wrap = if self.looks_like_result {
+            quote! { uniffi_result.map(#serialize) }
+        } else {
+            quote! { (#serialize)(uniffi_result) }
+        };
+        quote! {
+            {
+                let mut uniffi_args = uniffi_args;
+                let uniffi_result = #rust_fn_call;
+                #wrap
+            }
+        }
+    }
+
+    /// Does this method consume the object, by taking `self` by value?
+    pub fn takes_self_by_value(&self) -> bool {
+        matches!(self.receiver, Some(ReceiverArg::Value))
+    }
+
     pub fn return_impl(&self) -> TokenStream {
         let return_ty = &self.return_ty;
         quote! {
@@ -123,23 +393,41 @@ impl FnSignature {
     /// The closure moves all scaffolding arguments into itself and returns:
     ///   - The lifted argument tuple on success
     ///   - The field name and error on failure (`Err(&'static str, anyhow::Error>`)
+    ///
+    /// Every argument is lifted before checking for a failure, since lifting takes over the
+    /// handles passed for arguments like objects and `[Segmented]` byte segments.  If one
+    /// argument fails, the values lifted from the others are dropped rather than leaked.
     pub fn lift_closure(&self, self_lift: Option<TokenStream>) -> TokenStream {
+        // `self_lift` returns its error, so it's wrapped in a closure to get a `Result`.
+        let self_lift = self_lift.map(|self_lift| quote! { (move || Ok(#self_lift))() });
         let arg_lifts = self.args.iter().map(|arg| {
             let ident = &arg.ident;
             let lift_impl = arg.lift_impl();
             let name = &arg.name;
+            quote! { #lift_impl::try_lift(#ident).map_err(|e| (#name, e)) }
+        });
+        let all_lifts: Vec<_> = self_lift.into_iter().chain(arg_lifts).collect();
+        if all_lifts.is_empty() {
+            return quote! { move || Ok(()) };
+        }
+        let checks = (0..all_lifts.len()).map(|i| {
+            let idx = syn::Index::from(i);
             quote! {
-                match #lift_impl::try_lift(#ident) {
+                match uniffi_lifted.#idx {
                     Ok(v) => v,
-                    Err(e) => return Err((#name, e)),
+                    Err(e) => return Err(e),
                 }
             }
         });
-        let all_lifts = self_lift.into_iter().chain(arg_lifts);
         quote! {
-            move || Ok((
-                #(#all_lifts,)*
-            ))
+            move || {
+                let uniffi_lifted = (
+                    #(#all_lifts,)*
+                );
+                Ok((
+                    #(#checks,)*
+                ))
+            }
         }
     }
 
@@ -153,6 +441,7 @@ impl FnSignature {
             let idx = syn::Index::from(i + start_idx);
             let ty = &arg.ty;
             match &arg.ref_type {
+                None if arg.out => quote! { &mut uniffi_args.#idx },
                 None => quote! { uniffi_args.#idx },
                 Some(ref_type) => quote! {
                     <#ty as ::std::borrow::Borrow<#ref_type>>::borrow(&uniffi_args.#idx)
@@ -170,6 +459,18 @@ impl FnSignature {
         self.args.iter().map(|a| a.write_expr(buf_ident))
     }
 
+    /// Statements which shadow each borrowed argument with an owned copy, for [Self::write_exprs]
+    pub fn owned_arg_stmts(&self) -> impl Iterator<Item = TokenStream> + '_ {
+        self.args.iter().filter_map(NamedArg::owned_arg_stmt)
+    }
+
+    /// Expressions checking that each argument can be lowered, see `Lower::check_lower`
+    ///
+    /// These must come after [Self::owned_arg_stmts].
+    pub fn check_lower_exprs(&self) -> impl Iterator<Item = TokenStream> + '_ {
+        self.args.iter().map(NamedArg::check_lower_expr)
+    }
+
     /// Parameters expressions for each of our arguments
     pub fn params(&self) -> impl Iterator<Item = TokenStream> + '_ {
         self.args.iter().map(NamedArg::param)
@@ -179,7 +480,7 @@ impl FnSignature {
     pub fn scaffolding_fn_ident(&self) -> syn::Result<Ident> {
         let name = &self.name;
         let name = match &self.kind {
-            FnKind::Function => uniffi_meta::fn_symbol_name(&self.mod_path, name),
+            FnKind::Function | FnKind::ConstFn => uniffi_meta::fn_symbol_name(&self.mod_path, name),
             FnKind::Method { self_ident } | FnKind::TraitMethod { self_ident, .. } => {
                 uniffi_meta::method_symbol_name(&self.mod_path, &ident_to_string(self_ident), name)
             }
@@ -192,6 +493,37 @@ impl FnSignature {
         Ok(Ident::new(&name, Span::call_site()))
     }
 
+    /// Name of the scaffolding function which calls the trait's default implementation of this
+    /// method
+    pub fn default_scaffolding_fn_ident(&self) -> syn::Result<Ident> {
+        let self_ident = match &self.kind {
+            FnKind::TraitMethod { self_ident, .. } => self_ident,
+            _ => {
+                return Err(syn::Error::new(
+                    self.span,
+                    "Internal UniFFI error: only trait methods have default implementations",
+                ))
+            }
+        };
+        let name = uniffi_meta::default_method_symbol_name(
+            &self.mod_path,
+            &ident_to_string(self_ident),
+            &self.name,
+        );
+        Ok(Ident::new(&name, Span::call_site()))
+    }
+
+    /// Does this trait method have a default implementation?
+    pub fn has_default(&self) -> bool {
+        matches!(
+            self.kind,
+            FnKind::TraitMethod {
+                has_default: true,
+                ..
+            }
+        )
+    }
+
     /// Scaffolding parameters expressions for each of our arguments
     pub fn scaffolding_params(&self) -> impl Iterator<Item = TokenStream> + '_ {
         self.args.iter().map(NamedArg::scaffolding_param)
@@ -204,9 +536,12 @@ impl FnSignature {
             return_ty,
             is_async,
             mod_path,
+            static_return,
+            runtime_initializer,
             docstring,
             ..
         } = &self;
+        let takes_self_by_value = self.takes_self_by_value();
         let args_len = try_metadata_value_from_usize(
             // Use param_lifts to calculate this instead of sig.inputs to avoid counting any self
             // params
@@ -224,6 +559,16 @@ impl FnSignature {
                     .concat_value(#args_len)
                     #(#arg_metadata_calls)*
                     .concat(<#return_ty as ::uniffi::LowerReturn<crate::UniFfiTag>>::TYPE_ID_META)
+                    .concat_bool(#static_return)
+                    .concat_bool(#runtime_initializer)
+                    .concat_long_str(#docstring)
+            }),
+
+            FnKind::ConstFn => Ok(quote! {
+                ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::CONST_FN)
+                    .concat_str(#mod_path)
+                    .concat_str(#name)
+                    .concat(<#return_ty as ::uniffi::LowerReturn<crate::UniFfiTag>>::TYPE_ID_META)
                     .concat_long_str(#docstring)
             }),
 
@@ -235,6 +580,7 @@ impl FnSignature {
                         .concat_str(#object_name)
                         .concat_str(#name)
                         .concat_bool(#is_async)
+                        .concat_bool(#takes_self_by_value)
                         .concat_value(#args_len)
                         #(#arg_metadata_calls)*
                         .concat(<#return_ty as ::uniffi::LowerReturn<crate::UniFfiTag>>::TYPE_ID_META)
@@ -242,7 +588,11 @@ impl FnSignature {
                 })
             }
 
-            FnKind::TraitMethod { self_ident, index } => {
+            FnKind::TraitMethod {
+                self_ident,
+                index,
+                has_default,
+            } => {
                 let object_name = ident_to_string(self_ident);
                 Ok(quote! {
                     ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TRAIT_METHOD)
@@ -251,6 +601,7 @@ impl FnSignature {
                         .concat_u32(#index)
                         .concat_str(#name)
                         .concat_bool(#is_async)
+                        .concat_bool(#has_default)
                         .concat_value(#args_len)
                         #(#arg_metadata_calls)*
                         .concat(<#return_ty as ::uniffi::LowerReturn<crate::UniFfiTag>>::TYPE_ID_META)
@@ -265,6 +616,7 @@ impl FnSignature {
                         .concat_str(#mod_path)
                         .concat_str(#object_name)
                         .concat_str(#name)
+                        .concat_bool(#is_async)
                         .concat_value(#args_len)
                         #(#arg_metadata_calls)*
                         .concat(<#return_ty as ::uniffi::LowerReturn<crate::UniFfiTag>>::TYPE_ID_META)
@@ -284,6 +636,13 @@ impl FnSignature {
                 Some(self.checksum_symbol_name()),
             )),
 
+            FnKind::ConstFn => Ok(create_metadata_items(
+                "const_fn",
+                name,
+                self.metadata_expr()?,
+                Some(self.checksum_symbol_name()),
+            )),
+
             FnKind::Method { self_ident } => {
                 let object_name = ident_to_string(self_ident);
                 Ok(create_metadata_items(
@@ -319,7 +678,9 @@ impl FnSignature {
     pub(crate) fn checksum_symbol_name(&self) -> String {
         let name = &self.name;
         match &self.kind {
-            FnKind::Function => uniffi_meta::fn_checksum_symbol_name(&self.mod_path, name),
+            FnKind::Function | FnKind::ConstFn => {
+                uniffi_meta::fn_checksum_symbol_name(&self.mod_path, name)
+            }
             FnKind::Method { self_ident } | FnKind::TraitMethod { self_ident, .. } => {
                 uniffi_meta::method_checksum_symbol_name(
                     &self.mod_path,
@@ -372,21 +733,25 @@ impl TryFrom<FnArg> for Arg {
 pub(crate) enum ReceiverArg {
     Ref,
     Arc,
+    // `self` by value, which consumes the object
+    Value,
 }
 
 impl From<Receiver> for ReceiverArg {
     fn from(receiver: Receiver) -> Self {
-        if let Type::Path(p) = *receiver.ty {
-            if let Some(segment) = p.path.segments.last() {
+        match *receiver.ty {
+            Type::Reference(_) => Self::Ref,
+            Type::Path(p) => match p.path.segments.last() {
                 // This comparison will fail if a user uses a typedef for Arc.  Maybe we could
                 // implement some system like TYPE_ID_META to figure this out from the type system.
                 // However, this seems good enough for now.
-                if segment.ident == "Arc" {
-                    return ReceiverArg::Arc;
-                }
-            }
+                Some(segment) if segment.ident == "Arc" => Self::Arc,
+                // `self` and `mut self`.  Note that `Self` has been replaced with the type name
+                // by this point.
+                _ => Self::Value,
+            },
+            _ => Self::Ref,
         }
-        Self::Ref
     }
 }
 
@@ -395,11 +760,27 @@ pub(crate) struct NamedArg {
     pub(crate) name: String,
     pub(crate) ty: TokenStream,
     pub(crate) ref_type: Option<Type>,
+    // Is this a `&mut T` out-parameter?  `ty` is then `T`, which is lifted from the initial value
+    // and lowered again after the call.
+    pub(crate) out: bool,
+    // The type as it was declared, including any lifetime
+    pub(crate) declared_ty: Type,
 }
 
 impl NamedArg {
     pub(crate) fn new(ident: Ident, ty: &Type) -> Self {
         match ty {
+            Type::Reference(r) if r.mutability.is_some() => {
+                let inner = &r.elem;
+                Self {
+                    name: ident_to_string(&ident),
+                    ident,
+                    ty: quote! { #inner },
+                    ref_type: None,
+                    out: true,
+                    declared_ty: ty.clone(),
+                }
+            }
             Type::Reference(r) => {
                 let inner = &r.elem;
                 Self {
@@ -407,6 +788,8 @@ impl NamedArg {
                     ident,
                     ty: quote! { <#inner as ::uniffi::LiftRef<crate::UniFfiTag>>::LiftType },
                     ref_type: Some(*inner.clone()),
+                    out: false,
+                    declared_ty: ty.clone(),
                 }
             }
             _ => Self {
@@ -414,6 +797,8 @@ impl NamedArg {
                 ident,
                 ty: quote! { #ty },
                 ref_type: None,
+                out: false,
+                declared_ty: ty.clone(),
             },
         }
     }
@@ -431,7 +816,7 @@ impl NamedArg {
     /// Generate the parameter for this Arg
     pub(crate) fn param(&self) -> TokenStream {
         let ident = &self.ident;
-        let ty = &self.ty;
+        let ty = &self.declared_ty;
         quote! { #ident: #ty }
     }
 
@@ -443,40 +828,163 @@ impl NamedArg {
     }
 
     /// Generate the expression to write the scaffolding parameter for this arg
+    /// Write the argument, which must already be owned, see [Self::owned_arg_stmt]
     pub(crate) fn write_expr(&self, buf_ident: &Ident) -> TokenStream {
         let ident = &self.ident;
         let lower_impl = self.lower_impl();
         quote! { #lower_impl::write(#ident, &mut #buf_ident) }
     }
 
+    /// Borrowed args only live for the duration of the call, so we write an owned copy
+    pub(crate) fn owned_arg_stmt(&self) -> Option<TokenStream> {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        self.ref_type.as_ref().map(|ref_type| {
+            quote! { let #ident: #ty = <#ref_type as ::std::borrow::ToOwned>::to_owned(#ident); }
+        })
+    }
+
+    pub(crate) fn check_lower_expr(&self) -> TokenStream {
+        let ident = &self.ident;
+        let lower_impl = self.lower_impl();
+        quote! { #lower_impl::check_lower(&#ident) }
+    }
+
     pub(crate) fn arg_metadata(&self) -> TokenStream {
         let name = &self.name;
         let lift_impl = self.lift_impl();
+        let out = self.out;
         quote! {
             .concat_str(#name)
             .concat(#lift_impl::TYPE_ID_META)
+            .concat_bool(#out)
+        }
+    }
+}
+
+/// Only lifetime parameters are allowed, since there's no way to pick a concrete type for the
+/// scaffolding functions.
+fn check_generics(generics: &Generics) -> syn::Result<()> {
+    for param in &generics.params {
+        match param {
+            GenericParam::Lifetime(_) => {}
+            GenericParam::Type(_) => {
+                return Err(syn::Error::new_spanned(
+                    param,
+                    "generic type parameters are not supported by uniffi::export, only lifetimes",
+                ));
+            }
+            GenericParam::Const(_) => {
+                return Err(syn::Error::new_spanned(
+                    param,
+                    "const generic parameters are not supported by uniffi::export, only lifetimes",
+                ));
+            }
         }
     }
+    Ok(())
 }
 
 fn looks_like_result(return_type: &ReturnType) -> bool {
-    if let ReturnType::Type(_, ty) = return_type {
-        if let Type::Path(p) = &**ty {
-            if let Some(seg) = p.path.segments.last() {
-                if seg.ident == "Result" {
-                    return true;
+    match return_type {
+        ReturnType::Type(_, ty) => result_path_segment(ty).is_some(),
+        ReturnType::Default => false,
+    }
+}
+
+/// The last path segment of a type that looks like `Result`, including aliases like
+/// `anyhow::Result<T>`.
+fn result_path_segment(ty: &Type) -> Option<&PathSegment> {
+    let Type::Path(p) = ty else {
+        return None;
+    };
+    p.path.segments.last().filter(|seg| seg.ident == "Result")
+}
+
+/// Get `T` for a return type of `impl Iterator<Item = T>`
+fn iterator_item_type(ty: &Type) -> Option<&Type> {
+    let Type::ImplTrait(impl_trait) = ty else {
+        return None;
+    };
+    impl_trait.bounds.iter().find_map(|bound| {
+        let TypeParamBound::Trait(bound) = bound else {
+            return None;
+        };
+        let seg = bound.path.segments.last()?;
+        if seg.ident != "Iterator" {
+            return None;
+        }
+        let PathArguments::AngleBracketed(args) = &seg.arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            GenericArgument::AssocType(assoc) if assoc.ident == "Item" => Some(&assoc.ty),
+            _ => None,
+        })
+    })
+}
+
+/// Get `T` for a return type that [looks_like_result], like `Result<T, E>` or `anyhow::Result<T>`.
+fn result_ok_type(ty: &Type) -> Option<&Type> {
+    let PathArguments::AngleBracketed(args) = &result_path_segment(ty)?.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(ok_ty) => Some(ok_ty),
+        _ => None,
+    }
+}
+
+fn is_unit(ty: &Type) -> bool {
+    matches!(ty, Type::Tuple(t) if t.elems.is_empty())
+}
+
+/// The return type of a function with out-parameters, which returns a `uniffi::OutParams`
+/// instead of its value.  Functions which return a `Result` return it in the `Ok` variant.
+fn out_params_return_ty(
+    return_ty: TokenStream,
+    looks_like_result: bool,
+) -> syn::Result<TokenStream> {
+    let mut ty: Type = syn::parse2(return_ty)?;
+    if looks_like_result && result_ok_type(&ty).is_some() {
+        if let Type::Path(p) = &mut ty {
+            let seg = p.path.segments.last_mut().expect("`Result` path segment");
+            if let PathArguments::AngleBracketed(args) = &mut seg.arguments {
+                if let Some(GenericArgument::Type(ok_ty)) = args.args.first_mut() {
+                    *ok_ty = syn::parse_quote! { ::uniffi::OutParams<#ok_ty> };
                 }
             }
         }
+        return Ok(quote! { #ty });
     }
+    Ok(quote! { ::uniffi::OutParams<#ty> })
+}
 
-    false
+/// If `ty` is a `'static` borrow, like `&'static str`, get the type it borrows.
+fn static_borrow(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Reference(r) if r.mutability.is_none() => r
+            .lifetime
+            .as_ref()
+            .filter(|l| l.ident == "static")
+            .map(|_| r.elem.as_ref()),
+        _ => None,
+    }
 }
 
 #[derive(Debug)]
 pub(crate) enum FnKind {
     Function,
-    Constructor { self_ident: Ident },
-    Method { self_ident: Ident },
-    TraitMethod { self_ident: Ident, index: u32 },
+    ConstFn,
+    Constructor {
+        self_ident: Ident,
+    },
+    Method {
+        self_ident: Ident,
+    },
+    TraitMethod {
+        self_ident: Ident,
+        index: u32,
+        has_default: bool,
+    },
 }
@@ -36,6 +36,7 @@ use self::{
 struct CustomTypeInfo {
     ident: Ident,
     builtin: Path,
+    converters: Option<custom::CustomTypeConverters>,
 }
 
 impl Parse for CustomTypeInfo {
@@ -43,7 +44,16 @@ impl Parse for CustomTypeInfo {
         let ident = input.parse()?;
         input.parse::<Token![,]>()?;
         let builtin = input.parse()?;
-        Ok(Self { ident, builtin })
+        let converters = if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self {
+            ident,
+            builtin,
+            converters,
+        })
     }
 }
 
@@ -113,7 +123,7 @@ pub fn derive_record(input: TokenStream) -> TokenStream {
         .into()
 }
 
-#[proc_macro_derive(Enum)]
+#[proc_macro_derive(Enum, attributes(uniffi))]
 pub fn derive_enum(input: TokenStream) -> TokenStream {
     expand_enum(parse_macro_input!(input), None, false)
         .unwrap_or_else(syn::Error::into_compile_error)
@@ -122,7 +132,7 @@ pub fn derive_enum(input: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(Object)]
 pub fn derive_object(input: TokenStream) -> TokenStream {
-    expand_object(parse_macro_input!(input), false)
+    expand_object(parse_macro_input!(input), false, false)
         .unwrap_or_else(syn::Error::into_compile_error)
         .into()
 }
@@ -136,12 +146,19 @@ pub fn derive_error(input: TokenStream) -> TokenStream {
 
 /// Generate the `FfiConverter` implementation for a Custom Type - ie,
 /// for a `<T>` which implements `UniffiCustomTypeConverter`.
+///
+/// The `UniffiCustomTypeConverter` implementation is also generated if the conversions are passed
+/// as `{ try_lift: |val| ..., lower: |obj| ... }`.
 #[proc_macro]
 pub fn custom_type(tokens: TokenStream) -> TokenStream {
     let input: CustomTypeInfo = syn::parse_macro_input!(tokens);
-    custom::expand_ffi_converter_custom_type(&input.ident, &input.builtin, true)
-        .unwrap_or_else(syn::Error::into_compile_error)
-        .into()
+    custom::expand_ffi_converter_custom_type_with_converters(
+        &input.ident,
+        &input.builtin,
+        input.converters.as_ref(),
+    )
+    .unwrap_or_else(syn::Error::into_compile_error)
+    .into()
 }
 
 /// Generate the `FfiConverter` and the `UniffiCustomTypeConverter` implementations for a
@@ -150,6 +167,14 @@ pub fn custom_type(tokens: TokenStream) -> TokenStream {
 #[proc_macro]
 pub fn custom_newtype(tokens: TokenStream) -> TokenStream {
     let input: CustomTypeInfo = syn::parse_macro_input!(tokens);
+    if input.converters.is_some() {
+        return syn::Error::new_spanned(
+            &input.ident,
+            "custom_newtype! generates its own conversions, use custom_type! instead",
+        )
+        .into_compile_error()
+        .into();
+    }
     custom::expand_ffi_converter_custom_newtype(&input.ident, &input.builtin, true)
         .unwrap_or_else(syn::Error::into_compile_error)
         .into()
@@ -235,8 +260,9 @@ pub fn derive_error_for_udl(attrs: TokenStream, input: TokenStream) -> TokenStre
 
 #[doc(hidden)]
 #[proc_macro_attribute]
-pub fn derive_object_for_udl(_attrs: TokenStream, input: TokenStream) -> TokenStream {
-    expand_object(syn::parse_macro_input!(input), true)
+pub fn derive_object_for_udl(attrs: TokenStream, input: TokenStream) -> TokenStream {
+    let thread_bound = syn::parse_macro_input!(attrs as Option<util::kw::thread_bound>);
+    expand_object(syn::parse_macro_input!(input), thread_bound.is_some(), true)
         .unwrap_or_else(syn::Error::into_compile_error)
         .into()
 }
@@ -382,3 +408,19 @@ pub fn generate_and_include_scaffolding(udl_file: TokenStream) -> TokenStream {
 pub fn constructor(_attrs: TokenStream, input: TokenStream) -> TokenStream {
     input
 }
+
+/// A dummy macro that does nothing.
+///
+/// Like `#[uniffi::constructor]`, this only marks a method for `#[uniffi::export]`.
+#[proc_macro_attribute]
+pub fn snapshot(_attrs: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
+/// A dummy macro that does nothing.
+///
+/// Like `#[uniffi::constructor]`, this only marks a method for `#[uniffi::export]`.
+#[proc_macro_attribute]
+pub fn collect(_attrs: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
@@ -6,7 +6,15 @@ use crate::util::{
     create_metadata_items, extract_docstring, ident_to_string, mod_path, tagged_impl_header,
 };
 
-pub fn expand_object(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStream> {
+/// Derive the FFI support for an object.
+///
+/// `thread_bound` objects, from UDL interfaces marked `[ThreadBound]`, are passed as a handle to a
+/// `uniffi::ThreadBound` rather than to the `Arc` itself.
+pub fn expand_object(
+    input: DeriveInput,
+    thread_bound: bool,
+    udl_mode: bool,
+) -> syn::Result<TokenStream> {
     let module_path = mod_path()?;
     let ident = &input.ident;
     let docstring = extract_docstring(&input.attrs)?;
@@ -19,11 +27,26 @@ pub fn expand_object(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
         &uniffi_meta::free_fn_symbol_name(&module_path, &name),
         Span::call_site(),
     );
+    let handle_id_fn_ident = Ident::new(
+        &uniffi_meta::handle_id_fn_symbol_name(&module_path, &name),
+        Span::call_site(),
+    );
     let meta_static_var = (!udl_mode).then(|| {
         interface_meta_static_var(ident, false, &module_path, docstring)
             .unwrap_or_else(syn::Error::into_compile_error)
     });
-    let interface_impl = interface_impl(ident, udl_mode);
+    let interface_impl = interface_impl(ident, thread_bound, udl_mode);
+    // The type the handles point to an `Arc` of.
+    let handle_target = if thread_bound {
+        quote! { ::uniffi::ThreadBound<#ident> }
+    } else {
+        quote! { #ident }
+    };
+    let handle_object = if thread_bound {
+        quote! { arc.object() }
+    } else {
+        quote! { &arc }
+    };
 
     Ok(quote! {
         #[doc(hidden)]
@@ -33,7 +56,7 @@ pub fn expand_object(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
             call_status: &mut ::uniffi::RustCallStatus
         ) -> *const ::std::ffi::c_void {
             uniffi::rust_call(call_status, || {
-                unsafe { ::std::sync::Arc::increment_strong_count(ptr) };
+                unsafe { ::std::sync::Arc::increment_strong_count(ptr.cast::<#handle_target>()) };
                 Ok(ptr)
             })
         }
@@ -46,7 +69,7 @@ pub fn expand_object(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
         ) {
             uniffi::rust_call(call_status, || {
                 assert!(!ptr.is_null());
-                let ptr = ptr.cast::<#ident>();
+                let ptr = ptr.cast::<#handle_target>();
                 unsafe {
                     ::std::sync::Arc::decrement_strong_count(ptr);
                 }
@@ -54,20 +77,70 @@ pub fn expand_object(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
             });
         }
 
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #handle_id_fn_ident(
+            ptr: *const ::std::ffi::c_void,
+            call_status: &mut ::uniffi::RustCallStatus
+        ) -> u64 {
+            uniffi::rust_call(call_status, || {
+                assert!(!ptr.is_null());
+                // Borrow the foreign code's reference, without changing the reference count.
+                let arc = ::std::mem::ManuallyDrop::new(unsafe {
+                    ::std::sync::Arc::from_raw(ptr.cast::<#handle_target>())
+                });
+                Ok(::uniffi::arc_handle_id(#handle_object))
+            })
+        }
+
         #interface_impl
         #meta_static_var
     })
 }
 
-pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
+pub(crate) fn interface_impl(ident: &Ident, thread_bound: bool, udl_mode: bool) -> TokenStream {
     let name = ident_to_string(ident);
     let impl_spec = tagged_impl_header("FfiConverterArc", ident, udl_mode);
     let lower_return_impl_spec = tagged_impl_header("LowerReturn", ident, udl_mode);
+    let lower_impl_spec = tagged_impl_header("Lower", ident, udl_mode);
     let lift_ref_impl_spec = tagged_impl_header("LiftRef", ident, udl_mode);
     let mod_path = match mod_path() {
         Ok(p) => p,
         Err(e) => return e.into_compile_error(),
     };
+    let by_value_error =
+        format!("`{name}` is an object and can't be nested by value, use `Arc<{name}>` instead");
+    // Thread-bound objects are only bound by their constructors, which lower them with
+    // `uniffi::BindToCurrentThread`.  Any other handle to them could be used on the wrong thread,
+    // so `check_lower` rejects them everywhere else.  Values which are lowered without being
+    // checked are bound to the current thread, rather than to none.
+    let lower = if thread_bound {
+        quote! { ::uniffi::ThreadBound::lower_bound_to_current_thread(obj) }
+    } else {
+        quote! { ::std::sync::Arc::into_raw(obj) as Self::FfiType }
+    };
+    let check_lower = thread_bound.then(|| {
+        let message = format!(
+            "`{name}` is thread-bound, so it can only be passed to the foreign code by its constructors"
+        );
+        quote! {
+            fn check_lower(_obj: &Self) -> ::uniffi::Result<()> {
+                ::uniffi::deps::anyhow::bail!(#message)
+            }
+        }
+    });
+    let try_lift = if thread_bound {
+        quote! { Ok(unsafe { ::uniffi::ThreadBound::<Self>::lift(v) }) }
+    } else {
+        quote! {
+            let v = v as *const #ident;
+            Ok(unsafe { ::std::sync::Arc::<Self>::from_raw(v) })
+        }
+    };
+    // Like `Arc::from_raw()` above, `ThreadBound::lift()` relies on the `FfiConverterArc`
+    // contract that `v` came from `lower()`, which clippy can't see.
+    let try_lift_allow =
+        thread_bound.then(|| quote! { #[allow(clippy::not_unsafe_ptr_arg_deref)] });
 
     quote! {
         // All Object structs must be `Sync + Send`. The generated scaffolding will fail to compile
@@ -97,13 +170,13 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
             /// call the destructor function specific to the type `T`. Calling the destructor
             /// function for other types may lead to undefined behaviour.
             fn lower(obj: ::std::sync::Arc<Self>) -> Self::FfiType {
-                ::std::sync::Arc::into_raw(obj) as Self::FfiType
+                #lower
             }
 
             /// When lifting, we receive an owned `Arc` that the foreign language code cloned.
+            #try_lift_allow
             fn try_lift(v: Self::FfiType) -> ::uniffi::Result<::std::sync::Arc<Self>> {
-                let v = v as *const #ident;
-                Ok(unsafe { ::std::sync::Arc::<Self>::from_raw(v) })
+                #try_lift
             }
 
             /// When writing as a field of a complex structure, make a clone and transfer ownership
@@ -119,6 +192,12 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
                 ::uniffi::deps::bytes::BufMut::put_u64(buf, <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(obj) as u64);
             }
 
+            fn size_hint(_obj: &::std::sync::Arc<Self>) -> usize {
+                8
+            }
+
+            #check_lower
+
             /// When reading as a field of a complex structure, we receive a "borrow" of the `Arc`
             /// that is owned by the foreign-language code, and make a clone for our own use.
             ///
@@ -127,6 +206,7 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
             fn try_read(buf: &mut &[u8]) -> ::uniffi::Result<::std::sync::Arc<Self>> {
                 ::uniffi::deps::static_assertions::const_assert!(::std::mem::size_of::<*const ::std::ffi::c_void>() <= 8);
                 ::uniffi::check_remaining(buf, 8)?;
+                ::uniffi::check_read_handle()?;
                 <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::try_lift(::uniffi::deps::bytes::Buf::get_u64(buf) as Self::FfiType)
             }
 
@@ -143,12 +223,36 @@ pub(crate) fn interface_impl(ident: &Ident, udl_mode: bool) -> TokenStream {
                 Ok(<Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(::std::sync::Arc::new(obj)))
             }
 
+            fn check_return(obj: &Self) -> ::uniffi::Result<()> {
+                <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::check_lower(obj)
+            }
+
             const TYPE_ID_META: ::uniffi::MetadataBuffer = <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::TYPE_ID_META;
         }
 
         unsafe #lift_ref_impl_spec {
             type LiftType = ::std::sync::Arc<Self>;
         }
+
+        /// Objects can only be lowered by value when they're returned directly (see the
+        /// `LowerReturn` impl above).  When nested inside another type, for example
+        /// `Vec<MyObject>`, they must be wrapped in an `Arc`.  This impl only exists to
+        /// turn the obscure "trait not implemented" error into one that says so.
+        #[doc(hidden)]
+        #[automatically_derived]
+        unsafe #lower_impl_spec {
+            type FfiType = <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::FfiType;
+
+            fn lower(_obj: Self) -> Self::FfiType {
+                ::std::unreachable!(#by_value_error)
+            }
+
+            fn write(_obj: Self, _buf: &mut Vec<u8>) {
+                ::std::unreachable!(#by_value_error)
+            }
+
+            const TYPE_ID_META: ::uniffi::MetadataBuffer = ::std::panic!(#by_value_error);
+        }
     }
 }
 
@@ -168,6 +272,10 @@ pub(crate) fn interface_meta_static_var(
                 .concat_str(#module_path)
                 .concat_str(#name)
                 .concat_bool(#is_trait)
+                // Implemented trait interfaces, which are only supported in UDL
+                .concat_value(0)
+                // Singletons are only supported in UDL
+                .concat_bool(false)
                 .concat_long_str(#docstring)
         },
         None,
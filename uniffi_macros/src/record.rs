@@ -1,8 +1,8 @@
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
-    Data, DataStruct, DeriveInput, Field, Lit, Token,
+    Data, DataStruct, DeriveInput, Field, Lit, LitInt, Token,
 };
 
 use crate::util::{
@@ -23,11 +23,12 @@ pub fn expand_record(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
     };
 
     let ident = &input.ident;
+    let attr: RecordAttr = input.attrs.parse_uniffi_attr_args()?;
     let docstring = extract_docstring(&input.attrs)?;
-    let ffi_converter = record_ffi_converter_impl(ident, &record, udl_mode)
+    let ffi_converter = record_ffi_converter_impl(ident, &record, &attr, udl_mode)
         .unwrap_or_else(syn::Error::into_compile_error);
     let meta_static_var = (!udl_mode).then(|| {
-        record_meta_static_var(ident, docstring, &record)
+        record_meta_static_var(ident, docstring, &record, &attr)
             .unwrap_or_else(syn::Error::into_compile_error)
     });
 
@@ -40,14 +41,29 @@ pub fn expand_record(input: DeriveInput, udl_mode: bool) -> syn::Result<TokenStr
 pub(crate) fn record_ffi_converter_impl(
     ident: &Ident,
     record: &DataStruct,
+    attr: &RecordAttr,
     udl_mode: bool,
 ) -> syn::Result<TokenStream> {
     let impl_spec = tagged_impl_header("FfiConverter", ident, udl_mode);
     let derive_ffi_traits = derive_all_ffi_traits(ident, udl_mode);
     let name = ident_to_string(ident);
     let mod_path = mod_path()?;
-    let write_impl: TokenStream = record.fields.iter().map(write_field).collect();
-    let try_read_fields: TokenStream = record.fields.iter().map(try_read_field).collect();
+    let wire_fields = wire_ordered_fields(record)?;
+    let (write_impl, size_hint_impl, fixed_sizes, try_read_impl) = if attr.packed.is_some() {
+        packed_record_impls(record, &wire_fields)
+    } else {
+        // Struct expressions evaluate their fields in the order they're written, so listing the
+        // fields in wire order reads them in that order too.
+        let try_read_fields: TokenStream =
+            wire_fields.iter().copied().map(try_read_field).collect();
+        (
+            wire_fields.iter().copied().map(write_field).collect(),
+            wire_fields.iter().copied().map(field_size_hint).collect(),
+            wire_fields.iter().copied().map(field_fixed_size).collect(),
+            quote! { Ok(Self { #try_read_fields }) },
+        )
+    };
+    let check_lower_fields = record.fields.iter().map(check_lower_field);
 
     Ok(quote! {
         #[automatically_derived]
@@ -58,8 +74,20 @@ pub(crate) fn record_ffi_converter_impl(
                 #write_impl
             }
 
+            fn size_hint(obj: &Self) -> usize {
+                0 #size_hint_impl
+            }
+
+            const FIXED_SIZE: ::std::option::Option<usize> =
+                ::uniffi::sum_fixed_sizes(&[#(#fixed_sizes),*]);
+
+            fn check_lower(obj: &Self) -> ::uniffi::deps::anyhow::Result<()> {
+                #(#check_lower_fields)*
+                Ok(())
+            }
+
             fn try_read(buf: &mut &[::std::primitive::u8]) -> ::uniffi::deps::anyhow::Result<Self> {
-                Ok(Self { #try_read_fields })
+                #try_read_impl
             }
 
             const TYPE_ID_META: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TYPE_RECORD)
@@ -71,6 +99,137 @@ pub(crate) fn record_ffi_converter_impl(
     })
 }
 
+/// The fields in the order they're serialized, which is the declaration order unless the fields
+/// set `#[uniffi(order = N)]`.
+fn wire_ordered_fields(record: &DataStruct) -> syn::Result<Vec<&Field>> {
+    let mut ordered = Vec::new();
+    let mut unordered = Vec::new();
+    for f in &record.fields {
+        let attrs = f
+            .attrs
+            .parse_uniffi_attr_args::<FieldAttributeArguments>()?;
+        match attrs.order {
+            Some(order) => ordered.push((order.base10_parse::<u8>()?, f)),
+            None => unordered.push(f),
+        }
+    }
+    if ordered.is_empty() {
+        return Ok(unordered);
+    }
+    if let Some(f) = unordered.first() {
+        return Err(syn::Error::new_spanned(
+            f,
+            "`#[uniffi(order = N)]` must be set on all of the fields or none of them",
+        ));
+    }
+    ordered.sort_by_key(|(order, _)| *order);
+    if ordered
+        .iter()
+        .map(|(order, _)| usize::from(*order))
+        .ne(0..ordered.len())
+    {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!(
+                "the `order` values must be 0 to {}, each used once",
+                ordered.len() - 1
+            ),
+        ));
+    }
+    Ok(ordered.into_iter().map(|(_, f)| f).collect())
+}
+
+/// The `write`, `size_hint`, `FIXED_SIZE` and `try_read` implementations for a
+/// `#[uniffi(packed)]` record, which packs each run of adjacent `bool` fields into bytes.
+fn packed_record_impls(
+    record: &DataStruct,
+    wire_fields: &[&Field],
+) -> (TokenStream, TokenStream, Vec<TokenStream>, TokenStream) {
+    let mut write_impl = TokenStream::new();
+    let mut size_hint_impl = TokenStream::new();
+    let mut fixed_sizes = Vec::new();
+    let mut read_stmts = TokenStream::new();
+    let local = |f: &Field| {
+        format_ident!(
+            "uniffi_field_{}",
+            ident_to_string(f.ident.as_ref().unwrap())
+        )
+    };
+    // Split the fields into runs of adjacent `bool` fields and single fields of other types.
+    let mut runs: Vec<Vec<&Field>> = Vec::new();
+    for &f in wire_fields {
+        match runs.last_mut() {
+            Some(run) if is_bool(&f.ty) && is_bool(&run[0].ty) => run.push(f),
+            _ => runs.push(vec![f]),
+        }
+    }
+    for run in runs {
+        if !is_bool(&run[0].ty) {
+            let f = run[0];
+            let local = local(f);
+            let ty = &f.ty;
+            write_impl.extend(write_field(f));
+            size_hint_impl.extend(field_size_hint(f));
+            fixed_sizes.push(field_fixed_size(f));
+            read_stmts.extend(quote! {
+                let #local = <#ty as ::uniffi::Lift<crate::UniFfiTag>>::try_read(buf)?;
+            });
+            // The bindings pack fields by their UniFFI type, so make sure that this isn't a
+            // `bool` behind a type alias.
+            write_impl.extend(quote! {
+                const _: () = ::std::assert!(
+                    !<#ty as ::uniffi::Lower<crate::UniFfiTag>>::TYPE_ID_META
+                        .is_type_code(::uniffi::metadata::codes::TYPE_BOOL),
+                    "`bool` fields of packed records must be written as `bool`",
+                );
+            });
+            continue;
+        }
+        let idents = run.iter().map(|f| &f.ident);
+        let locals: Vec<_> = run.iter().map(|f| local(f)).collect();
+        let len = run.len();
+        write_impl.extend(quote! {
+            ::uniffi::write_packed_bools(&[#(obj.#idents),*], buf);
+        });
+        size_hint_impl.extend(quote! { + (#len + 7) / 8 });
+        fixed_sizes.push(quote! { ::std::option::Option::Some((#len + 7) / 8) });
+        read_stmts.extend(quote! {
+            let [#(#locals),*] = ::uniffi::read_packed_bools::<#len>(buf)?;
+        });
+    }
+    let field_inits = record.fields.iter().map(|f| {
+        let ident = &f.ident;
+        let local = local(f);
+        quote! { #ident: #local }
+    });
+    let try_read_impl = quote! {
+        #read_stmts
+        Ok(Self { #(#field_inits),* })
+    };
+    (write_impl, size_hint_impl, fixed_sizes, try_read_impl)
+}
+
+/// Whether a field's type is written as `bool`, which is how `#[uniffi(packed)]` records find
+/// the fields to pack.
+fn is_bool(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(p) if p.qself.is_none() => {
+            let segments: Vec<_> = p
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .collect();
+            let segments: Vec<_> = segments.iter().map(String::as_str).collect();
+            matches!(
+                segments.as_slice(),
+                ["bool"] | ["std" | "core", "primitive", "bool"]
+            )
+        }
+        _ => false,
+    }
+}
+
 fn write_field(f: &Field) -> TokenStream {
     let ident = &f.ident;
     let ty = &f.ty;
@@ -80,6 +239,32 @@ fn write_field(f: &Field) -> TokenStream {
     }
 }
 
+fn field_size_hint(f: &Field) -> TokenStream {
+    let ident = &f.ident;
+    let ty = &f.ty;
+
+    quote! {
+        + <#ty as ::uniffi::Lower<crate::UniFfiTag>>::size_hint(&obj.#ident)
+    }
+}
+
+fn check_lower_field(f: &Field) -> TokenStream {
+    let ident = &f.ident;
+    let ty = &f.ty;
+
+    quote! {
+        <#ty as ::uniffi::Lower<crate::UniFfiTag>>::check_lower(&obj.#ident)?;
+    }
+}
+
+fn field_fixed_size(f: &Field) -> TokenStream {
+    let ty = &f.ty;
+
+    quote! {
+        <#ty as ::uniffi::Lower<crate::UniFfiTag>>::FIXED_SIZE
+    }
+}
+
 pub enum FieldDefault {
     Literal(Lit),
     Null(kw::None),
@@ -109,21 +294,36 @@ impl Parse for FieldDefault {
 #[derive(Default)]
 pub struct FieldAttributeArguments {
     pub(crate) default: Option<FieldDefault>,
+    pub(crate) order: Option<LitInt>,
 }
 
 impl UniffiAttributeArgs for FieldAttributeArguments {
     fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
-        let _: kw::default = input.parse()?;
-        let _: Token![=] = input.parse()?;
-        let default = input.parse()?;
-        Ok(Self {
-            default: Some(default),
-        })
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::default) {
+            let _: kw::default = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            let default = input.parse()?;
+            Ok(Self {
+                default: Some(default),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::order) {
+            let _: kw::order = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                order: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else {
+            Err(lookahead.error())
+        }
     }
 
     fn merge(self, other: Self) -> syn::Result<Self> {
         Ok(Self {
             default: either_attribute_arg(self.default, other.default)?,
+            order: either_attribute_arg(self.order, other.order)?,
         })
     }
 }
@@ -132,12 +332,36 @@ pub(crate) fn record_meta_static_var(
     ident: &Ident,
     docstring: String,
     record: &DataStruct,
+    attr: &RecordAttr,
 ) -> syn::Result<TokenStream> {
     let name = ident_to_string(ident);
     let module_path = mod_path()?;
     let fields_len =
         try_metadata_value_from_usize(record.fields.len(), "UniFFI limits structs to 256 fields")?;
 
+    let orders = record
+        .fields
+        .iter()
+        .map(|f| {
+            let attrs = f
+                .attrs
+                .parse_uniffi_attr_args::<FieldAttributeArguments>()?;
+            attrs
+                .order
+                .map(|order| order.base10_parse::<u8>())
+                .transpose()
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let concat_orders = match orders.into_iter().collect::<Option<Vec<_>>>() {
+        Some(orders) if !orders.is_empty() => quote! {
+            .concat_bool(true)
+            #(.concat_value(#orders))*
+        },
+        _ => quote! { .concat_bool(false) },
+    };
+
+    let packed = attr.packed.is_some();
+
     let concat_fields: TokenStream = record
         .fields
         .iter()
@@ -180,6 +404,8 @@ pub(crate) fn record_meta_static_var(
                 .concat_str(#name)
                 .concat_value(#fields_len)
                 #concat_fields
+                #concat_orders
+                .concat_bool(#packed)
                 .concat_long_str(#docstring)
         },
         None,
@@ -228,3 +454,27 @@ fn default_value_concat_calls(default: FieldDefault) -> syn::Result<TokenStream>
         }),
     }
 }
+
+#[derive(Default)]
+pub struct RecordAttr {
+    pub packed: Option<kw::packed>,
+}
+
+impl UniffiAttributeArgs for RecordAttr {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::packed) {
+            Ok(Self {
+                packed: input.parse()?,
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            packed: either_attribute_arg(self.packed, other.packed)?,
+        })
+    }
+}
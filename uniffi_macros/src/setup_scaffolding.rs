@@ -2,16 +2,24 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use proc_macro2::TokenStream;
+use fs_err as fs;
+use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::Result;
 
-use crate::util::mod_path;
+use crate::util::{manifest_path, mod_path};
 use uniffi_meta::UNIFFI_CONTRACT_VERSION;
 
 pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
     let module_path = mod_path()?;
     let ffi_contract_version_ident = format_ident!("ffi_{module_path}_uniffi_contract_version");
+    let ffi_component_version_ident = format_ident!("ffi_{module_path}_uniffi_component_version");
+    let (config, track_uniffi_toml) = read_uniffi_toml()?;
+    let component_version = component_version(config.as_ref())?;
+    let enabled_features_fn = enabled_features_fn(config.as_ref())?;
+    let panic_callback_fn = panic_callback_fn(config.as_ref())?;
+    let component_version_fn = component_version_fn(config.as_ref())?;
+    let self_test_fns = self_test_fns(config.as_ref())?;
     let namespace_upper = namespace.to_ascii_uppercase();
     let namespace_const_ident = format_ident!("UNIFFI_META_CONST_NAMESPACE_{namespace_upper}");
     let namespace_static_ident = format_ident!("UNIFFI_META_NAMESPACE_{namespace_upper}");
@@ -19,6 +27,13 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
     let ffi_rustbuffer_from_bytes_ident = format_ident!("ffi_{module_path}_rustbuffer_from_bytes");
     let ffi_rustbuffer_free_ident = format_ident!("ffi_{module_path}_rustbuffer_free");
     let ffi_rustbuffer_reserve_ident = format_ident!("ffi_{module_path}_rustbuffer_reserve");
+    let ffi_chunked_sequence_next_ident = format_ident!("ffi_{module_path}_chunked_sequence_next");
+    let ffi_chunked_sequence_free_ident = format_ident!("ffi_{module_path}_chunked_sequence_free");
+    let ffi_byte_segments_new_ident = format_ident!("ffi_{module_path}_byte_segments_new");
+    let ffi_byte_segments_push_ident = format_ident!("ffi_{module_path}_byte_segments_push");
+    let ffi_byte_segments_len_ident = format_ident!("ffi_{module_path}_byte_segments_len");
+    let ffi_byte_segments_next_ident = format_ident!("ffi_{module_path}_byte_segments_next");
+    let ffi_byte_segments_free_ident = format_ident!("ffi_{module_path}_byte_segments_free");
     let reexport_hack_ident = format_ident!("{module_path}_uniffi_reexport_hack");
     let ffi_rust_future_scaffolding_fns = rust_future_scaffolding_fns(&module_path);
 
@@ -40,6 +55,22 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
             #UNIFFI_CONTRACT_VERSION
         }
 
+        #track_uniffi_toml
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #ffi_component_version_ident() -> ::uniffi::RustBuffer {
+            ::uniffi::RustBuffer::from_vec(#component_version.as_bytes().to_vec())
+        }
+
+        #enabled_features_fn
+
+        #panic_callback_fn
+
+        #component_version_fn
+
+        #self_test_fns
 
         /// Export namespace metadata.
         ///
@@ -85,6 +116,63 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
             uniffi::ffi::uniffi_rustbuffer_reserve(buf, additional, call_status)
         }
 
+        // Support for sequences returned in chunks.
+        //
+        // See `uniffi/src/ffi/chunkedsequence.rs` for documentation on these functions
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_chunked_sequence_next_ident(handle: u64, call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {
+            uniffi::ffi::chunked_sequence_next(handle, call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_chunked_sequence_free_ident(handle: u64, call_status: &mut uniffi::RustCallStatus) {
+            uniffi::ffi::chunked_sequence_free(handle, call_status);
+        }
+
+        // Support for byte segments passed without concatenating them.
+        //
+        // See `uniffi/src/ffi/bytesegments.rs` for documentation on these functions
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #ffi_byte_segments_new_ident(call_status: &mut uniffi::RustCallStatus) -> u64 {
+            uniffi::ffi::byte_segments_new(call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_byte_segments_push_ident(handle: u64, segment: uniffi::ForeignBytes, call_status: &mut uniffi::RustCallStatus) {
+            uniffi::ffi::byte_segments_push(handle, segment, call_status);
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_byte_segments_len_ident(handle: u64, call_status: &mut uniffi::RustCallStatus) -> u64 {
+            uniffi::ffi::byte_segments_len(handle, call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_byte_segments_next_ident(handle: u64, call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {
+            uniffi::ffi::byte_segments_next(handle, call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_byte_segments_free_ident(handle: u64, call_status: &mut uniffi::RustCallStatus) {
+            uniffi::ffi::byte_segments_free(handle, call_status);
+        }
+
         #ffi_rust_future_scaffolding_fns
 
         // Code to re-export the UniFFI scaffolding functions.
@@ -127,6 +215,305 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
     })
 }
 
+/// Read the crate's `uniffi.toml`, if it has one
+///
+/// This returns the config along with the code to make cargo rebuild the crate when `uniffi.toml`
+/// changes.
+fn read_uniffi_toml() -> Result<(Option<toml::Value>, TokenStream)> {
+    let toml_path = manifest_path()
+        .map_err(|e| syn::Error::new(Span::call_site(), e))?
+        .with_file_name("uniffi.toml");
+    if !toml_path.exists() {
+        return Ok((None, quote! {}));
+    }
+    let config = fs::read_to_string(&toml_path)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| {
+            toml::from_str::<toml::Value>(&contents)
+                .map_err(|e| format!("Failed to parse `uniffi.toml`: {e}"))
+        })
+        .map_err(|e| syn::Error::new(Span::call_site(), e))?;
+    let toml_path = toml_path.display().to_string();
+    Ok((
+        Some(config),
+        quote! {
+            // Include `uniffi.toml` into the build, so cargo rebuilds the crate when the
+            // config read by the scaffolding changes.
+            #[allow(dead_code)]
+            const _: &[u8] = include_bytes!(#toml_path);
+        },
+    ))
+}
+
+/// Get a value from the `[bindings]` section of `uniffi.toml`
+fn bindings_config<'a>(config: Option<&'a toml::Value>, key: &str) -> Option<&'a toml::Value> {
+    config?.get("bindings")?.get(key)
+}
+
+/// Read `component_version` from the `[bindings]` section of the crate's `uniffi.toml`
+///
+/// The version is empty if it's not set.
+fn component_version(config: Option<&toml::Value>) -> Result<String> {
+    match bindings_config(config, "component_version") {
+        None => Ok(String::new()),
+        Some(toml::Value::String(version)) => Ok(version.clone()),
+        Some(_) => Err(syn::Error::new(
+            Span::call_site(),
+            "`component_version` in `uniffi.toml` must be a string",
+        )),
+    }
+}
+
+/// Generate the `enabled_features()` function, for the `exposed_features` in the `[bindings]`
+/// section of the crate's `uniffi.toml`
+///
+/// The features are checked with `cfg!`, which is expanded in the crate calling
+/// `setup_scaffolding!()`, so it sees that crate's features.
+fn enabled_features_fn(config: Option<&toml::Value>) -> Result<TokenStream> {
+    let Some(value) = bindings_config(config, "exposed_features") else {
+        return Ok(quote! {});
+    };
+    let features = value
+        .as_array()
+        .and_then(|features| {
+            features
+                .iter()
+                .map(toml::Value::as_str)
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| {
+            syn::Error::new(
+                Span::call_site(),
+                "`exposed_features` in `uniffi.toml` must be a list of strings",
+            )
+        })?;
+    Ok(quote! {
+        // In a module, so it doesn't clash with the crate's own items.
+        #[doc(hidden)]
+        mod uniffi_enabled_features {
+            /// The features which the library was built with, out of the ones it exposes.
+            #[::uniffi::export]
+            fn enabled_features() -> ::std::vec::Vec<::std::string::String> {
+                let mut features = ::std::vec::Vec::new();
+                #(
+                    if cfg!(feature = #features) {
+                        features.push(::std::string::String::from(#features));
+                    }
+                )*
+                features
+            }
+        }
+    })
+}
+
+/// Generate the `set_panic_callback()` function, if `panic_callback` is set in the `[bindings]`
+/// section of the crate's `uniffi.toml`
+fn panic_callback_fn(config: Option<&toml::Value>) -> Result<TokenStream> {
+    match bindings_config(config, "panic_callback") {
+        None | Some(toml::Value::Boolean(false)) => return Ok(quote! {}),
+        Some(toml::Value::Boolean(true)) => (),
+        Some(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`panic_callback` in `uniffi.toml` must be a boolean",
+            ))
+        }
+    }
+    Ok(quote! {
+        // In a module, so it doesn't clash with the crate's own items.
+        #[doc(hidden)]
+        mod uniffi_panic_callback {
+            /// Receives the panics caught when calling into the library, for crash reporting.
+            #[::uniffi::export(callback_interface)]
+            pub trait PanicCallback: ::std::marker::Send + ::std::marker::Sync {
+                /// Called with the panic message, and its backtrace if it could be captured.
+                fn on_panic(
+                    &self,
+                    message: ::std::string::String,
+                    backtrace: ::std::option::Option<::std::string::String>,
+                );
+            }
+
+            /// Register a callback which is called with each panic caught when calling into the
+            /// library, before it's thrown as an error.  This replaces any previous callback.
+            #[::uniffi::export]
+            fn set_panic_callback(callback: ::std::boxed::Box<dyn PanicCallback>) {
+                ::uniffi::set_panic_callback(move |message, backtrace| {
+                    callback.on_panic(
+                        ::std::string::ToString::to_string(message),
+                        backtrace.map(::std::string::ToString::to_string),
+                    )
+                });
+            }
+        }
+    })
+}
+
+/// Generate the `uniffi_component_version()` function, if `expose_component_version` is set in
+/// the `[bindings]` section of the crate's `uniffi.toml`
+///
+/// `CARGO_PKG_VERSION` is read with `env!`, which is expanded in the crate calling
+/// `setup_scaffolding!()`, so it's that crate's version.
+fn component_version_fn(config: Option<&toml::Value>) -> Result<TokenStream> {
+    match bindings_config(config, "expose_component_version") {
+        None | Some(toml::Value::Boolean(false)) => return Ok(quote! {}),
+        Some(toml::Value::Boolean(true)) => (),
+        Some(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`expose_component_version` in `uniffi.toml` must be a boolean",
+            ))
+        }
+    }
+    Ok(quote! {
+        // In a module, so it doesn't clash with the crate's own items.
+        #[doc(hidden)]
+        pub mod uniffi_component_version {
+            /// The versions the library was built with.
+            #[derive(::uniffi::Record)]
+            pub struct UniffiComponentVersion {
+                /// The version of the crate, from its `Cargo.toml`.
+                pub crate_version: ::std::string::String,
+                /// The version of the uniffi runtime crate.
+                pub uniffi_version: ::std::string::String,
+                /// The version of the contract between the bindings and the scaffolding.
+                pub contract_version: u32,
+            }
+
+            /// Get the versions the library was built with, to log what the app is linked
+            /// against.
+            #[::uniffi::export]
+            pub fn uniffi_component_version() -> UniffiComponentVersion {
+                UniffiComponentVersion {
+                    crate_version: ::std::string::String::from(env!("CARGO_PKG_VERSION")),
+                    uniffi_version: ::std::string::String::from(::uniffi::PACKAGE_VERSION),
+                    contract_version: #UNIFFI_CONTRACT_VERSION,
+                }
+            }
+        }
+    })
+}
+
+/// Generate the functions behind the bindings' self-test, if `expose_self_test` is set in the
+/// `[bindings]` section of the crate's `uniffi.toml`
+///
+/// The bindings pass `UniffiSelfTestValues` to `uniffi_self_test_check()`, which reports each
+/// field that Rust received with a different value, and compare the values returned by
+/// `uniffi_self_test_values()` with their own.  The values are also hard-coded in the self-test
+/// templates of each bindings generator, so they must be changed together.
+fn self_test_fns(config: Option<&toml::Value>) -> Result<TokenStream> {
+    match bindings_config(config, "expose_self_test") {
+        None | Some(toml::Value::Boolean(false)) => return Ok(quote! {}),
+        Some(toml::Value::Boolean(true)) => (),
+        Some(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`expose_self_test` in `uniffi.toml` must be a boolean",
+            ))
+        }
+    }
+    // Compare floats by their bits, to check their encoding rather than just their value.
+    let checks = [
+        ("boolean", false),
+        ("int8", false),
+        ("uint8", false),
+        ("int16", false),
+        ("uint16", false),
+        ("int32", false),
+        ("uint32", false),
+        ("int64", false),
+        ("uint64", false),
+        ("float32", true),
+        ("float64", true),
+        ("string", false),
+        ("bytes", false),
+    ]
+    .into_iter()
+    .map(|(name, is_float)| {
+        let field = format_ident!("{name}");
+        let matches = if is_float {
+            quote! { values.#field.to_bits() == expected.#field.to_bits() }
+        } else {
+            quote! { values.#field == expected.#field }
+        };
+        quote! {
+            if !(#matches) {
+                failures.push(::std::format!(
+                    "{}: Rust received {:?}, expected {:?}",
+                    #name,
+                    values.#field,
+                    expected.#field,
+                ));
+            }
+        }
+    });
+    Ok(quote! {
+        // In a module, so it doesn't clash with the crate's own items.
+        #[doc(hidden)]
+        pub mod uniffi_self_test {
+            /// A value of each primitive type, passed both ways by the bindings' self-test.
+            #[derive(::uniffi::Record)]
+            pub struct UniffiSelfTestValues {
+                pub boolean: bool,
+                pub int8: i8,
+                pub uint8: u8,
+                pub int16: i16,
+                pub uint16: u16,
+                pub int32: i32,
+                pub uint32: u32,
+                pub int64: i64,
+                pub uint64: u64,
+                pub float32: f32,
+                pub float64: f64,
+                pub string: ::std::string::String,
+                pub bytes: ::std::vec::Vec<u8>,
+            }
+
+            /// The result of the bindings' self-test.
+            #[derive(::uniffi::Record)]
+            pub struct UniffiSelfTestReport {
+                /// Whether every value was passed over the FFI unchanged.
+                pub passed: bool,
+                /// A message for each value that changed.
+                pub failures: ::std::vec::Vec<::std::string::String>,
+            }
+
+            /// Get the values which the bindings' self-test expects to receive.
+            #[::uniffi::export]
+            pub fn uniffi_self_test_values() -> UniffiSelfTestValues {
+                UniffiSelfTestValues {
+                    boolean: true,
+                    int8: i8::MIN,
+                    uint8: u8::MAX,
+                    int16: i16::MIN,
+                    uint16: u16::MAX,
+                    int32: i32::MIN,
+                    uint32: u32::MAX,
+                    int64: i64::MIN,
+                    uint64: u64::MAX,
+                    float32: -0.15625,
+                    float64: 0.1,
+                    // Characters encoded as 1, 2, 3 and 4 bytes of UTF-8.
+                    string: ::std::string::String::from("a\u{df}\u{20ac}\u{1f980}"),
+                    bytes: ::std::vec![0, 1, 127, 128, 255],
+                }
+            }
+
+            /// Check the values sent by the bindings' self-test.
+            #[::uniffi::export]
+            pub fn uniffi_self_test_check(values: UniffiSelfTestValues) -> UniffiSelfTestReport {
+                let expected = uniffi_self_test_values();
+                let mut failures = ::std::vec::Vec::new();
+                #(#checks)*
+                UniffiSelfTestReport {
+                    passed: failures.is_empty(),
+                    failures,
+                }
+            }
+        }
+    })
+}
+
 /// Generates the rust_future_* functions
 ///
 /// The foreign side uses a type-erased `RustFutureHandle` to interact with futures, which presents
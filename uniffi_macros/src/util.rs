@@ -8,7 +8,7 @@ use std::path::{Path as StdPath, PathBuf};
 use syn::{
     ext::IdentExt,
     parse::{Parse, ParseStream},
-    Attribute, Expr, Lit, Token,
+    Attribute, Expr, ExprLit, Lit, Meta, MetaNameValue, Token,
 };
 
 pub fn manifest_path() -> Result<PathBuf, String> {
@@ -247,12 +247,22 @@ pub(crate) fn derive_ffi_traits(ty: &Ident, udl_mode: bool, trait_names: &[&str]
 pub mod kw {
     syn::custom_keyword!(async_runtime);
     syn::custom_keyword!(callback_interface);
+    syn::custom_keyword!(chunked);
+    syn::custom_keyword!(constant);
     syn::custom_keyword!(constructor);
     syn::custom_keyword!(default);
+    syn::custom_keyword!(discriminant);
     syn::custom_keyword!(flat_error);
+    syn::custom_keyword!(lower);
     syn::custom_keyword!(None);
     syn::custom_keyword!(with_try_read);
     syn::custom_keyword!(non_exhaustive);
+    syn::custom_keyword!(on_poison);
+    syn::custom_keyword!(order);
+    syn::custom_keyword!(packed);
+    syn::custom_keyword!(runtime_initializer);
+    syn::custom_keyword!(thread_bound);
+    syn::custom_keyword!(try_lift);
     syn::custom_keyword!(Debug);
     syn::custom_keyword!(Display);
     syn::custom_keyword!(Eq);
@@ -278,19 +288,25 @@ impl Parse for ExternalTypeItem {
     }
 }
 
+/// Get the docstring from the `#[doc = "..."]` attributes, which doc comments desugar to.
+///
+/// Other `doc` attributes, like `#[doc(hidden)]` or `#[doc = include_str!("...")]`, are left for
+/// rustc and not included.
 pub(crate) fn extract_docstring(attrs: &[Attribute]) -> syn::Result<String> {
-    return attrs
+    let lines: Vec<_> = attrs
         .iter()
         .filter(|attr| attr.path().is_ident("doc"))
-        .map(|attr| {
-            let name_value = attr.meta.require_name_value()?;
-            if let Expr::Lit(expr) = &name_value.value {
-                if let Lit::Str(lit_str) = &expr.lit {
-                    return Ok(lit_str.value().trim().to_owned());
-                }
-            }
-            Err(syn::Error::new_spanned(attr, "Cannot parse doc attribute"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(lit_str),
+                        ..
+                    }),
+                ..
+            }) => Some(lit_str.value().trim().to_owned()),
+            _ => None,
         })
-        .collect::<syn::Result<Vec<_>>>()
-        .map(|lines| lines.join("\n"));
+        .collect();
+    Ok(lines.join("\n"))
 }
@@ -33,6 +33,13 @@ pub fn method_symbol_name(namespace: &str, object_name: &str, name: &str) -> Str
     format!("uniffi_{namespace}_fn_method_{object_name}_{name}")
 }
 
+/// FFI symbol name for the function which calls the Rust default implementation of a trait method
+pub fn default_method_symbol_name(namespace: &str, object_name: &str, name: &str) -> String {
+    let object_name = object_name.to_ascii_lowercase();
+    let name = name.to_ascii_lowercase();
+    format!("uniffi_{namespace}_fn_default_{object_name}_{name}")
+}
+
 /// FFI symbol name for the `clone` function for an object.
 pub fn clone_fn_symbol_name(namespace: &str, object_name: &str) -> String {
     let object_name = object_name.to_ascii_lowercase();
@@ -45,6 +52,30 @@ pub fn free_fn_symbol_name(namespace: &str, object_name: &str) -> String {
     format!("uniffi_{namespace}_fn_free_{object_name}")
 }
 
+/// FFI symbol name for the function which returns the id of the object behind a handle
+pub fn handle_id_fn_symbol_name(namespace: &str, object_name: &str) -> String {
+    let object_name = object_name.to_ascii_lowercase();
+    format!("uniffi_{namespace}_fn_handle_id_{object_name}")
+}
+
+/// FFI symbol name for the function which returns the instance of a singleton object
+pub fn singleton_fn_symbol_name(namespace: &str, object_name: &str) -> String {
+    let object_name = object_name.to_ascii_lowercase();
+    format!("uniffi_{namespace}_fn_singleton_{object_name}")
+}
+
+/// FFI symbol name for the function which returns the `Default::default()` value of a record
+pub fn default_fn_symbol_name(namespace: &str, record_name: &str) -> String {
+    let record_name = record_name.to_ascii_lowercase();
+    format!("uniffi_{namespace}_fn_default_{record_name}")
+}
+
+/// FFI symbol name for the function which converts an object to the interface it inherits from
+pub fn upcast_fn_symbol_name(namespace: &str, object_name: &str) -> String {
+    let object_name = object_name.to_ascii_lowercase();
+    format!("uniffi_{namespace}_fn_upcast_{object_name}")
+}
+
 /// FFI symbol name for the `init_callback` function for a callback interface
 pub fn init_callback_fn_symbol_name(namespace: &str, callback_interface_name: &str) -> String {
     let callback_interface_name = callback_interface_name.to_ascii_lowercase();
@@ -62,7 +62,8 @@ pub fn group_metadata(group_map: &mut MetadataGroupMap, items: Vec<Metadata>) ->
     Ok(())
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MetadataGroup {
     pub namespace: NamespaceMetadata,
     pub namespace_docstring: Option<String>,
@@ -108,6 +109,10 @@ impl<'a> ExternalTypeConverter<'a> {
                 throws: self.convert_optional(meta.throws),
                 ..meta
             }),
+            Metadata::ConstFn(meta) => Metadata::ConstFn(ConstFnMetadata {
+                return_type: self.convert_type(meta.return_type),
+                ..meta
+            }),
             Metadata::Method(meta) => Metadata::Method(MethodMetadata {
                 inputs: self.convert_params(meta.inputs),
                 return_type: self.convert_optional(meta.return_type),
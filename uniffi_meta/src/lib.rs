@@ -23,7 +23,7 @@ mod metadata;
 // `docs/uniffi-versioning.md` for details.
 //
 // Once we get to 1.0, then we'll need to update the scheme to something like 100 + major_version
-pub const UNIFFI_CONTRACT_VERSION: u32 = 25;
+pub const UNIFFI_CONTRACT_VERSION: u32 = 26;
 
 /// Similar to std::hash::Hash.
 ///
@@ -39,6 +39,12 @@ impl Checksum for bool {
     }
 }
 
+impl Checksum for u8 {
+    fn checksum<H: Hasher>(&self, state: &mut H) {
+        state.write_u8(*self);
+    }
+}
+
 impl Checksum for u64 {
     fn checksum<H: Hasher>(&self, state: &mut H) {
         state.write(&self.to_le_bytes());
@@ -107,16 +113,24 @@ impl Checksum for String {
     }
 }
 
-impl Checksum for &str {
+impl<T: Checksum + ?Sized> Checksum for &T {
     fn checksum<H: Hasher>(&self, state: &mut H) {
         (**self).checksum(state)
     }
 }
 
+impl<A: Checksum, B: Checksum> Checksum for (A, B) {
+    fn checksum<H: Hasher>(&self, state: &mut H) {
+        self.0.checksum(state);
+        self.1.checksum(state);
+    }
+}
+
 // The namespace of a Component interface.
 //
 // This is used to match up the macro metadata with the UDL items.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NamespaceMetadata {
     pub crate_name: String,
     pub name: String,
@@ -126,6 +140,7 @@ pub struct NamespaceMetadata {
 //
 // This is to find the UDL files in library mode generation
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UdlFile {
     // The module path specified when the UDL file was parsed.
     pub module_path: String,
@@ -135,6 +150,7 @@ pub struct UdlFile {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FnMetadata {
     pub module_path: String,
     pub name: String,
@@ -144,6 +160,40 @@ pub struct FnMetadata {
     pub throws: Option<Type>,
     pub checksum: Option<u16>,
     pub docstring: Option<String>,
+    /// Documentation for the return value, from a `@returns` tag in the UDL docstring.
+    pub return_docstring: Option<String>,
+    /// The version the item was added in, from a `@since` tag in the UDL docstring.
+    pub since: Option<String>,
+    /// Name of the namespace object that the bindings should group this function under, if any.
+    pub namespace_object: Option<String>,
+    /// The deprecation message, if the function is deprecated.
+    pub deprecated: Option<String>,
+    /// How many times the bindings retry the function when it throws a retryable error.
+    pub retries: Option<u8>,
+    /// Whether the function must only be called from the main thread.
+    pub main_thread: bool,
+    /// Whether the returned sequence is passed over the FFI in chunks.
+    pub chunked: bool,
+    /// Whether the function returns its value along with a list of non-fatal warnings.
+    pub with_warnings: bool,
+    /// Whether the returned byte segments are passed over the FFI without concatenating them.
+    pub segmented: bool,
+    /// Whether the bindings also generate a variant which returns a cancellable handle.
+    pub cancellable: bool,
+    /// The target variant the bindings only generate the function for, such as `ios`.
+    pub target: Option<String>,
+    /// The type which the bindings generate the function as an extension of, which is the type of
+    /// its first argument.
+    pub extension: Option<String>,
+    /// The value which the bindings return from a variant of the function, when the function
+    /// returns `None`.
+    pub default_on_none: Option<LiteralMetadata>,
+    /// Whether the Rust function returns a `'static` borrow, like `&'static str`, so it always
+    /// returns the same value.
+    pub static_return: bool,
+    /// Whether this is the function which the bindings call from `uniffiInitialize(config)`, with
+    /// the settings of their generated `UniffiRuntimeConfig` as the arguments.
+    pub runtime_initializer: bool,
 }
 
 impl FnMetadata {
@@ -156,15 +206,43 @@ impl FnMetadata {
     }
 }
 
+/// A zero-argument function whose result the bindings expose as a constant.
+///
+/// The bindings call the function once, the first time the constant is accessed, and cache the
+/// result.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConstFnMetadata {
+    pub module_path: String,
+    pub name: String,
+    pub return_type: Type,
+    pub checksum: Option<u16>,
+    pub docstring: Option<String>,
+}
+
+impl ConstFnMetadata {
+    pub fn ffi_symbol_name(&self) -> String {
+        fn_symbol_name(&self.module_path, &self.name)
+    }
+
+    pub fn checksum_symbol_name(&self) -> String {
+        fn_checksum_symbol_name(&self.module_path, &self.name)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConstructorMetadata {
     pub module_path: String,
     pub self_name: String,
     pub name: String,
+    pub is_async: bool,
     pub inputs: Vec<FnParamMetadata>,
     pub throws: Option<Type>,
     pub checksum: Option<u16>,
     pub docstring: Option<String>,
+    /// The version the item was added in, from a `@since` tag in the UDL docstring.
+    pub since: Option<String>,
 }
 
 impl ConstructorMetadata {
@@ -182,6 +260,7 @@ impl ConstructorMetadata {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MethodMetadata {
     pub module_path: String,
     pub self_name: String,
@@ -191,8 +270,13 @@ pub struct MethodMetadata {
     pub return_type: Option<Type>,
     pub throws: Option<Type>,
     pub takes_self_by_arc: bool, // unused except by rust udl bindgen.
+    /// The method takes `self` by value, which consumes the object.
+    pub takes_self_by_value: bool,
     pub checksum: Option<u16>,
     pub docstring: Option<String>,
+    pub return_docstring: Option<String>,
+    /// The version the item was added in, from a `@since` tag in the UDL docstring.
+    pub since: Option<String>,
 }
 
 impl MethodMetadata {
@@ -206,6 +290,7 @@ impl MethodMetadata {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TraitMethodMetadata {
     pub module_path: String,
     pub trait_name: String,
@@ -214,12 +299,17 @@ pub struct TraitMethodMetadata {
     pub index: u32,
     pub name: String,
     pub is_async: bool,
+    // Does the trait provide a default implementation for this method?
+    pub has_default: bool,
     pub inputs: Vec<FnParamMetadata>,
     pub return_type: Option<Type>,
     pub throws: Option<Type>,
     pub takes_self_by_arc: bool, // unused except by rust udl bindgen.
     pub checksum: Option<u16>,
     pub docstring: Option<String>,
+    pub return_docstring: Option<String>,
+    /// The version the item was added in, from a `@since` tag in the UDL docstring.
+    pub since: Option<String>,
 }
 
 impl TraitMethodMetadata {
@@ -227,18 +317,37 @@ impl TraitMethodMetadata {
         method_symbol_name(&self.module_path, &self.trait_name, &self.name)
     }
 
+    pub fn default_ffi_symbol_name(&self) -> String {
+        default_method_symbol_name(&self.module_path, &self.trait_name, &self.name)
+    }
+
     pub fn checksum_symbol_name(&self) -> String {
         method_checksum_symbol_name(&self.module_path, &self.trait_name, &self.name)
     }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FnParamMetadata {
     pub name: String,
     pub ty: Type,
     pub by_ref: bool,
     pub optional: bool,
     pub default: Option<LiteralMetadata>,
+    /// Whether the bindings take this trailing sequence parameter as variadic arguments, from
+    /// the UDL `[Variadic]` attribute.
+    pub variadic: bool,
+    /// Whether these byte segments are passed over the FFI without concatenating them, from the
+    /// UDL `[Segmented]` attribute.
+    pub segmented: bool,
+    /// Whether this callback interface parameter receives the progress events of an async
+    /// function, from the UDL `[Progress]` attribute.
+    pub progress: bool,
+    /// Whether this is an out-parameter, from a `&mut T` argument of an exported function.  The
+    /// bindings pass its initial value and return its final value along with the function's.
+    pub out: bool,
+    /// Documentation for the parameter, from a `@param` tag in the UDL docstring.
+    pub docstring: Option<String>,
 }
 
 impl FnParamMetadata {
@@ -249,11 +358,17 @@ impl FnParamMetadata {
             by_ref: false,
             optional: false,
             default: None,
+            variadic: false,
+            segmented: false,
+            progress: false,
+            out: false,
+            docstring: None,
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Checksum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LiteralMetadata {
     Boolean(bool),
     String(String),
@@ -282,6 +397,7 @@ impl LiteralMetadata {
 // Represent the radix of integer literal values.
 // We preserve the radix into the generated bindings for readability reasons.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Checksum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Radix {
     Decimal = 10,
     Octal = 8,
@@ -289,47 +405,208 @@ pub enum Radix {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RecordMetadata {
     pub module_path: String,
     pub name: String,
     pub fields: Vec<FieldMetadata>,
+    /// Whether adjacent `bool` fields are packed into bytes when serialized.
+    pub packed: bool,
+    /// Do the bindings generate a constructor returning the record's `Default::default()` value?
+    /// Only supported in UDL.
+    pub has_default: bool,
     pub docstring: Option<String>,
+    /// The version the item was added in, from a `@since` tag in the UDL docstring.
+    pub since: Option<String>,
+    /// The deprecation message, if the record is deprecated.
+    pub deprecated: Option<String>,
+}
+
+impl RecordMetadata {
+    /// FFI symbol name for the function which returns the record's `Default::default()` value.
+    pub fn default_ffi_symbol_name(&self) -> String {
+        default_fn_symbol_name(&self.module_path, &self.name)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FieldMetadata {
     pub name: String,
     pub ty: Type,
     pub default: Option<LiteralMetadata>,
     pub docstring: Option<String>,
+    /// The position of the field when serialized, if it's been set with `[Order=N]`.
+    pub order: Option<u8>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnumMetadata {
     pub module_path: String,
     pub name: String,
     pub variants: Vec<VariantMetadata>,
     pub non_exhaustive: bool,
+    /// The variant that unknown values of a non-exhaustive enum are lifted as, from a `[Default]`
+    /// variant in UDL.
+    pub default_variant: Option<String>,
+    /// How the discriminant is serialized, if it's not the usual big-endian `i32`.
+    pub discriminant: Option<DiscriminantEncoding>,
     pub docstring: Option<String>,
+    /// The version the item was added in, from a `@since` tag in the UDL docstring.
+    pub since: Option<String>,
+}
+
+/// A fixed width and byte order for serializing an enum's discriminant, for enums which are synced
+/// with an external protocol.
+///
+/// This matches `uniffi::DiscriminantEncoding`, which the scaffolding uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Checksum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiscriminantEncoding {
+    U8,
+    U16Le,
+    U16Be,
+    U32Le,
+    U32Be,
+}
+
+impl DiscriminantEncoding {
+    /// The name used by `[Discriminant=...]` and `#[uniffi(discriminant = ...)]`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::U8 => "u8",
+            Self::U16Le => "u16_le",
+            Self::U16Be => "u16_be",
+            Self::U32Le => "u32_le",
+            Self::U32Be => "u32_be",
+        }
+    }
+
+    /// The number of bytes the discriminant is serialized as.
+    pub fn width(&self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::U16Le | Self::U16Be => 2,
+            Self::U32Le | Self::U32Be => 4,
+        }
+    }
+
+    /// Whether the discriminant is little-endian.  `u8` discriminants are a single byte, so they
+    /// count as big-endian.
+    pub fn is_little_endian(&self) -> bool {
+        matches!(self, Self::U16Le | Self::U32Le)
+    }
+}
+
+impl std::str::FromStr for DiscriminantEncoding {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> anyhow::Result<Self> {
+        Ok(match name {
+            "u8" => Self::U8,
+            "u16_le" => Self::U16Le,
+            "u16_be" => Self::U16Be,
+            "u32_le" => Self::U32Le,
+            "u32_be" => Self::U32Be,
+            _ => anyhow::bail!(
+                "unknown discriminant encoding {name:?}, expected one of `u8`, `u16_le`, `u16_be`, `u32_le` or `u32_be`"
+            ),
+        })
+    }
+}
+
+/// What the methods of an interface do when they find its state poisoned by a panic in an
+/// earlier call.
+///
+/// This matches `uniffi::OnPoison`, which the scaffolding uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Checksum)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OnPoison {
+    /// Fail the call with a `StatePoisoned` error.
+    Error,
+    /// Clear the poisoning and continue with the state as it is.
+    Recover,
+}
+
+impl OnPoison {
+    /// The name used by `[OnPoison=...]` and `#[uniffi::export(on_poison = ...)]`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Recover => "recover",
+        }
+    }
+}
+
+impl std::str::FromStr for OnPoison {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> anyhow::Result<Self> {
+        Ok(match name {
+            "error" => Self::Error,
+            "recover" => Self::Recover,
+            _ => anyhow::bail!("unknown poison policy {name:?}, expected `error` or `recover`"),
+        })
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VariantMetadata {
     pub name: String,
     pub discr: Option<LiteralMetadata>,
     pub fields: Vec<FieldMetadata>,
     pub docstring: Option<String>,
+    /// The deprecation message, if the variant is deprecated.
+    pub deprecated: Option<String>,
+    /// Whether functions which throw this error variant are retried, if they're marked to be.
+    pub retryable: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectMetadata {
     pub module_path: String,
     pub name: String,
     pub imp: types::ObjectImpl,
+    /// The trait interfaces which the object can be used as.
+    pub implements: Vec<Type>,
+    /// Is there only ever one instance of the object, shared by all callers?
+    pub is_singleton: bool,
+    /// The name of the interface this object inherits from, if any.  Only supported in UDL.
+    pub parent: Option<String>,
+    /// Constants associated with the object, for example `Color.RED`.  Only supported in UDL.
+    pub constants: Vec<ConstantMetadata>,
+    /// Do the bindings generate a stream of the events passed to the object's observers?  Only
+    /// supported in UDL.
+    pub observable: bool,
+    /// Do the bindings generate helpers iterating over the object's items, fetched in batches with
+    /// its `next_batch` method?  Only supported in UDL.
+    pub cursor: bool,
+    /// Must the object's methods be called on the thread which constructed it?  Only supported
+    /// in UDL.
+    pub thread_bound: bool,
+    /// What happens when a method call panics on state poisoned by an earlier call, if the
+    /// interface sets it.  Only supported in UDL.
+    pub on_poison: Option<OnPoison>,
+    pub docstring: Option<String>,
+    /// The version the item was added in, from a `@since` tag in the UDL docstring.
+    pub since: Option<String>,
+}
+
+/// A constant with a literal value, declared as `const type NAME = value;` in UDL.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConstantMetadata {
+    pub name: String,
+    pub ty: Type,
+    pub value: LiteralMetadata,
     pub docstring: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CallbackInterfaceMetadata {
     pub module_path: String,
     pub name: String,
@@ -351,10 +628,28 @@ impl ObjectMetadata {
     pub fn free_ffi_symbol_name(&self) -> String {
         free_fn_symbol_name(&self.module_path, &self.name)
     }
+
+    /// FFI symbol name for the function which returns the id of the object behind a handle.
+    ///
+    /// The foreign code compares these ids to tell whether two handles are the same object.
+    pub fn handle_id_ffi_symbol_name(&self) -> String {
+        handle_id_fn_symbol_name(&self.module_path, &self.name)
+    }
+
+    /// FFI symbol name for the function which returns the instance of a singleton object.
+    pub fn singleton_ffi_symbol_name(&self) -> String {
+        singleton_fn_symbol_name(&self.module_path, &self.name)
+    }
+
+    /// FFI symbol name for the function which converts this object to its parent interface.
+    pub fn upcast_ffi_symbol_name(&self) -> String {
+        upcast_fn_symbol_name(&self.module_path, &self.name)
+    }
 }
 
 /// The list of traits we support generating helper methods for.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UniffiTraitMetadata {
     Debug {
         fmt: MethodMetadata,
@@ -369,6 +664,13 @@ pub enum UniffiTraitMetadata {
     Hash {
         hash: MethodMetadata,
     },
+    /// A UDL `stringifier` method, which the bindings expose like a `Display` implementation.
+    ///
+    /// `fmt` is the method itself, so unlike the other traits there's no generated method.  This
+    /// only comes from UDL, so it never appears in the metadata of a compiled library.
+    Stringifier {
+        fmt: MethodMetadata,
+    },
 }
 
 impl UniffiTraitMetadata {
@@ -378,6 +680,7 @@ impl UniffiTraitMetadata {
             UniffiTraitMetadata::Display { fmt } => fmt,
             UniffiTraitMetadata::Eq { eq, .. } => eq,
             UniffiTraitMetadata::Hash { hash } => hash,
+            UniffiTraitMetadata::Stringifier { fmt } => fmt,
         }
         .module_path
     }
@@ -388,9 +691,15 @@ impl UniffiTraitMetadata {
             UniffiTraitMetadata::Display { fmt } => fmt,
             UniffiTraitMetadata::Eq { eq, .. } => eq,
             UniffiTraitMetadata::Hash { hash } => hash,
+            UniffiTraitMetadata::Stringifier { fmt } => fmt,
         }
         .self_name
     }
+
+    /// Is this a UDL `stringifier` method, rather than a method generated from a Rust trait?
+    pub fn is_stringifier(&self) -> bool {
+        matches!(self, UniffiTraitMetadata::Stringifier { .. })
+    }
 }
 
 #[repr(u8)]
@@ -414,6 +723,7 @@ impl UniffiTraitDiscriminants {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ErrorMetadata {
     Enum { enum_: EnumMetadata, is_flat: bool },
 }
@@ -433,6 +743,7 @@ impl ErrorMetadata {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CustomTypeMetadata {
     pub module_path: String,
     pub name: String,
@@ -444,17 +755,26 @@ pub struct CustomTypeMetadata {
 /// This is used as a safeguard against different UniFFI versions being used for scaffolding and
 /// bindings generation.
 pub fn checksum<T: Checksum>(val: &T) -> u16 {
+    (checksum_u64(val) & 0x000000000000FFFF) as u16
+}
+
+/// Returns the value's full 64-bit hash as computed with [`SipHasher13`].
+///
+/// The hasher uses fixed keys, so this is the same on every platform and in every run.
+pub fn checksum_u64<T: Checksum + ?Sized>(val: &T) -> u64 {
     let mut hasher = siphasher::sip::SipHasher13::new();
     val.checksum(&mut hasher);
-    (hasher.finish() & 0x000000000000FFFF) as u16
+    hasher.finish()
 }
 
 /// Enum covering all the possible metadata types
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Metadata {
     Namespace(NamespaceMetadata),
     UdlFile(UdlFile),
     Func(FnMetadata),
+    ConstFn(ConstFnMetadata),
     Object(ObjectMetadata),
     CallbackInterface(CallbackInterfaceMetadata),
     Record(RecordMetadata),
@@ -477,6 +797,7 @@ impl Metadata {
             Metadata::Namespace(meta) => &meta.crate_name,
             Metadata::UdlFile(meta) => &meta.module_path,
             Metadata::Func(meta) => &meta.module_path,
+            Metadata::ConstFn(meta) => &meta.module_path,
             Metadata::Constructor(meta) => &meta.module_path,
             Metadata::Method(meta) => &meta.module_path,
             Metadata::Record(meta) => &meta.module_path,
@@ -509,6 +830,12 @@ impl From<FnMetadata> for Metadata {
     }
 }
 
+impl From<ConstFnMetadata> for Metadata {
+    fn from(value: ConstFnMetadata) -> Metadata {
+        Self::ConstFn(value)
+    }
+}
+
 impl From<ConstructorMetadata> for Metadata {
     fn from(c: ConstructorMetadata) -> Self {
         Self::Constructor(c)
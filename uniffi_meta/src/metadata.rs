@@ -22,6 +22,7 @@ pub mod codes {
     pub const CALLBACK_INTERFACE: u8 = 9;
     pub const TRAIT_METHOD: u8 = 10;
     pub const UNIFFI_TRAIT: u8 = 11;
+    pub const CONST_FN: u8 = 12;
     //pub const UNKNOWN: u8 = 255;
 
     // Type codes
@@ -50,6 +51,13 @@ pub mod codes {
     pub const TYPE_CUSTOM: u8 = 22;
     pub const TYPE_RESULT: u8 = 23;
     //pub const TYPE_FUTURE: u8 = 24;
+    pub const TYPE_DYN_ERROR: u8 = 25;
+    pub const TYPE_PATH: u8 = 26;
+    pub const TYPE_JSON: u8 = 27;
+    pub const TYPE_NON_ZERO: u8 = 28;
+    pub const TYPE_OPAQUE_HANDLE: u8 = 29;
+    pub const TYPE_DURATION_RANGE: u8 = 30;
+    pub const TYPE_DURATION_RANGE_INCLUSIVE: u8 = 31;
     pub const TYPE_UNIT: u8 = 255;
 
     // Literal codes
@@ -49,10 +49,11 @@ impl<'a> MetadataReader<'a> {
             }
             .into(),
             codes::FUNC => self.read_func()?.into(),
+            codes::CONST_FN => self.read_const_fn()?.into(),
             codes::CONSTRUCTOR => self.read_constructor()?.into(),
             codes::METHOD => self.read_method()?.into(),
             codes::RECORD => self.read_record()?.into(),
-            codes::ENUM => self.read_enum(false)?.into(),
+            codes::ENUM => self.read_plain_enum()?.into(),
             codes::ERROR => self.read_error()?.into(),
             codes::INTERFACE => self.read_object()?.into(),
             codes::CALLBACK_INTERFACE => self.read_callback_interface()?.into(),
@@ -143,7 +144,13 @@ impl<'a> MetadataReader<'a> {
             codes::TYPE_BOOL => Type::Boolean,
             codes::TYPE_STRING => Type::String,
             codes::TYPE_DURATION => Type::Duration,
+            codes::TYPE_PATH => Type::Path,
+            codes::TYPE_JSON => Type::Json,
             codes::TYPE_SYSTEM_TIME => Type::Timestamp,
+            codes::TYPE_DYN_ERROR => Type::DynError,
+            codes::TYPE_OPAQUE_HANDLE => Type::OpaqueHandle,
+            codes::TYPE_DURATION_RANGE => Type::DurationRange { inclusive: false },
+            codes::TYPE_DURATION_RANGE_INCLUSIVE => Type::DurationRange { inclusive: true },
             codes::TYPE_RECORD => Type::Record {
                 module_path: self.read_string()?,
                 name: self.read_string()?,
@@ -169,6 +176,9 @@ impl<'a> MetadataReader<'a> {
             codes::TYPE_OPTION => Type::Optional {
                 inner_type: Box::new(self.read_type()?),
             },
+            codes::TYPE_NON_ZERO => Type::NonZero {
+                inner_type: Box::new(self.read_type()?),
+            },
             codes::TYPE_VEC => {
                 let inner_type = self.read_type()?;
                 if inner_type == Type::UInt8 {
@@ -189,6 +199,11 @@ impl<'a> MetadataReader<'a> {
         })
     }
 
+    fn read_types(&mut self) -> Result<Vec<Type>> {
+        let len = self.read_u8()?;
+        (0..len).map(|_| self.read_type()).collect()
+    }
+
     fn read_optional_type(&mut self) -> Result<Option<Type>> {
         Ok(match self.peek_u8()? {
             codes::TYPE_UNIT => {
@@ -219,6 +234,8 @@ impl<'a> MetadataReader<'a> {
         let is_async = self.read_bool()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
+        let static_return = self.read_bool()?;
+        let runtime_initializer = self.read_bool()?;
         let docstring = self.read_optional_long_string()?;
         Ok(FnMetadata {
             module_path,
@@ -228,6 +245,45 @@ impl<'a> MetadataReader<'a> {
             return_type,
             throws,
             docstring,
+            // `@returns` and `@since` tags are only parsed from UDL docstrings
+            return_docstring: None,
+            since: None,
+            checksum: self.calc_checksum(),
+            namespace_object: None,
+            // `[Deprecated]`, `[Retry]`, `[MainThread]`, `[Chunked]`, `[WithWarnings]`,
+            // `[Segmented]`, `[Cancellable]`, `[Target]`, `[Extension]` and `[DefaultOnNone]`
+            // are only supported in UDL
+            deprecated: None,
+            retries: None,
+            main_thread: false,
+            chunked: false,
+            with_warnings: false,
+            segmented: false,
+            cancellable: false,
+            target: None,
+            extension: None,
+            default_on_none: None,
+            static_return,
+            runtime_initializer,
+        })
+    }
+
+    fn read_const_fn(&mut self) -> Result<ConstFnMetadata> {
+        let module_path = self.read_string()?;
+        let name = self.read_string()?;
+        let (return_type, throws) = self.read_return_type()?;
+        ensure!(
+            throws.is_none(),
+            "constant function `{name}` can't return a `Result`"
+        );
+        let return_type = return_type
+            .with_context(|| format!("constant function `{name}` must return a value"))?;
+        let docstring = self.read_optional_long_string()?;
+        Ok(ConstFnMetadata {
+            module_path,
+            name,
+            return_type,
+            docstring,
             checksum: self.calc_checksum(),
         })
     }
@@ -236,6 +292,7 @@ impl<'a> MetadataReader<'a> {
         let module_path = self.read_string()?;
         let self_name = self.read_string()?;
         let name = self.read_string()?;
+        let is_async = self.read_bool()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         let docstring = self.read_optional_long_string()?;
@@ -253,10 +310,12 @@ impl<'a> MetadataReader<'a> {
             module_path,
             self_name,
             name,
+            is_async,
             inputs,
             throws,
             checksum: self.calc_checksum(),
             docstring,
+            since: None,
         })
     }
 
@@ -265,6 +324,7 @@ impl<'a> MetadataReader<'a> {
         let self_name = self.read_string()?;
         let name = self.read_string()?;
         let is_async = self.read_bool()?;
+        let takes_self_by_value = self.read_bool()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         let docstring = self.read_optional_long_string()?;
@@ -277,8 +337,11 @@ impl<'a> MetadataReader<'a> {
             return_type,
             throws,
             takes_self_by_arc: false, // not emitted by macros
+            takes_self_by_value,
             checksum: self.calc_checksum(),
             docstring,
+            return_docstring: None,
+            since: None,
         })
     }
 
@@ -286,11 +349,26 @@ impl<'a> MetadataReader<'a> {
         Ok(RecordMetadata {
             module_path: self.read_string()?,
             name: self.read_string()?,
-            fields: self.read_fields()?,
+            fields: self.read_record_fields()?,
+            packed: self.read_bool()?,
+            has_default: false,
             docstring: self.read_optional_long_string()?,
+            since: None,
+            deprecated: None,
         })
     }
 
+    fn read_record_fields(&mut self) -> Result<Vec<FieldMetadata>> {
+        let mut fields = self.read_fields()?;
+        // Records which set `[Order=N]` on their fields are followed by the order of each field.
+        if self.read_bool()? {
+            for field in fields.iter_mut() {
+                field.order = Some(self.read_u8()?);
+            }
+        }
+        Ok(fields)
+    }
+
     fn read_enum(&mut self, is_flat_error: bool) -> Result<EnumMetadata> {
         let module_path = self.read_string()?;
         let name = self.read_string()?;
@@ -305,10 +383,22 @@ impl<'a> MetadataReader<'a> {
             name,
             variants,
             non_exhaustive: self.read_bool()?,
+            default_variant: None,
+            discriminant: None,
             docstring: self.read_optional_long_string()?,
+            since: None,
         })
     }
 
+    // Enums, unlike errors, are followed by how their discriminant is serialized, if it's set.
+    fn read_plain_enum(&mut self) -> Result<EnumMetadata> {
+        let mut enum_ = self.read_enum(false)?;
+        if self.read_bool()? {
+            enum_.discriminant = Some(self.read_string()?.parse()?);
+        }
+        Ok(enum_)
+    }
+
     fn read_error(&mut self) -> Result<ErrorMetadata> {
         let is_flat = self.read_bool()?;
         let enum_ = self.read_enum(is_flat)?;
@@ -320,7 +410,16 @@ impl<'a> MetadataReader<'a> {
             module_path: self.read_string()?,
             name: self.read_string()?,
             imp: ObjectImpl::from_is_trait(self.read_bool()?),
+            implements: self.read_types()?,
+            is_singleton: self.read_bool()?,
+            parent: None,
+            constants: vec![],
+            observable: false,
+            cursor: false,
+            thread_bound: false,
+            on_poison: None,
             docstring: self.read_optional_long_string()?,
+            since: None,
         })
     }
 
@@ -363,6 +462,7 @@ impl<'a> MetadataReader<'a> {
         let index = self.read_u32()?;
         let name = self.read_string()?;
         let is_async = self.read_bool()?;
+        let has_default = self.read_bool()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         let docstring = self.read_optional_long_string()?;
@@ -372,12 +472,15 @@ impl<'a> MetadataReader<'a> {
             index,
             name,
             is_async,
+            has_default,
             inputs,
             return_type,
             throws,
             takes_self_by_arc: false, // not emitted by macros
             checksum: self.calc_checksum(),
             docstring,
+            return_docstring: None,
+            since: None,
         })
     }
 
@@ -393,6 +496,7 @@ impl<'a> MetadataReader<'a> {
                     ty,
                     default,
                     docstring: self.read_optional_long_string()?,
+                    order: None,
                 })
             })
             .collect()
@@ -407,6 +511,8 @@ impl<'a> MetadataReader<'a> {
                     discr: self.read_default("<variant-value>", &Type::UInt64)?,
                     fields: self.read_fields()?,
                     docstring: self.read_optional_long_string()?,
+                    deprecated: None,
+                    retryable: false,
                 })
             })
             .collect()
@@ -421,6 +527,8 @@ impl<'a> MetadataReader<'a> {
                     discr: None,
                     fields: vec![],
                     docstring: self.read_optional_long_string()?,
+                    deprecated: None,
+                    retryable: false,
                 })
             })
             .collect()
@@ -433,10 +541,15 @@ impl<'a> MetadataReader<'a> {
                 Ok(FnParamMetadata {
                     name: self.read_string()?,
                     ty: self.read_type()?,
+                    out: self.read_bool()?,
                     // not emitted by macros
                     by_ref: false,
                     optional: false,
                     default: None,
+                    variadic: false,
+                    segmented: false,
+                    progress: false,
+                    docstring: None,
                 })
             })
             .collect()
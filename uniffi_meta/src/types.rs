@@ -20,6 +20,7 @@
 use crate::Checksum;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Checksum, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ObjectImpl {
     Struct,
     Trait,
@@ -50,17 +51,21 @@ impl ObjectImpl {
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Checksum, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExternalKind {
     Interface,
     Trait,
     // Either a record or enum
     DataClass,
+    // An enum without fields, which is passed as the `i32` identifying its variant
+    ScalarEnum,
 }
 
 /// Represents all the different high-level types that can be used in a component interface.
 /// At this level we identify user-defined types by name, without knowing any details
 /// of their internal structure apart from what type of thing they are (record, enum, etc).
 #[derive(Debug, Clone, Eq, PartialEq, Checksum, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Type {
     // Primitive types.
     UInt8,
@@ -78,6 +83,18 @@ pub enum Type {
     Bytes,
     Timestamp,
     Duration,
+    // A range of durations, i.e. `Range<Duration>`, or `RangeInclusive<Duration>` if `inclusive`.
+    DurationRange {
+        inclusive: bool,
+    },
+    // A file system path, i.e. `std::path::PathBuf`.
+    Path,
+    // An arbitrary JSON value, i.e. `serde_json::Value`.
+    Json,
+    // A dynamically typed error, i.e. `uniffi::DynError`.
+    DynError,
+    // A value passed without being interpreted, i.e. `uniffi::OpaqueHandle`.
+    OpaqueHandle,
     Object {
         // The module path to the object
         module_path: String,
@@ -99,6 +116,10 @@ pub enum Type {
         module_path: String,
         name: String,
     },
+    // A non-zero integer, e.g. `std::num::NonZeroU32`.  `inner_type` is the integer type.
+    NonZero {
+        inner_type: Box<Type>,
+    },
     // Structurally recursive types.
     Optional {
         inner_type: Box<Type>,
@@ -130,9 +151,9 @@ pub enum Type {
 impl Type {
     pub fn iter_types(&self) -> TypeIterator<'_> {
         let nested_types = match self {
-            Type::Optional { inner_type } | Type::Sequence { inner_type } => {
-                inner_type.iter_types()
-            }
+            Type::Optional { inner_type }
+            | Type::Sequence { inner_type }
+            | Type::NonZero { inner_type } => inner_type.iter_types(),
             Type::Map {
                 key_type,
                 value_type,
@@ -15,7 +15,7 @@
 //! if we grow significantly more complicated attribute handling.
 
 use anyhow::{bail, Result};
-use uniffi_meta::{Checksum, ExternalKind, ObjectImpl};
+use uniffi_meta::{Checksum, DiscriminantEncoding, ExternalKind, ObjectImpl, OnPoison};
 
 /// Represents an attribute parsed from UDL, like `[ByRef]` or `[Throws]`.
 ///
@@ -30,7 +30,15 @@ pub(super) enum Attribute {
     Name(String),
     SelfType(SelfType),
     Throws(String),
+    // `[Throws=(ErrorA, ErrorB)]` - The function throws an error which wraps one of the errors,
+    // named by joining their names with `Or`, e.g. `ErrorAOrErrorB`.
+    ThrowsUnion {
+        name: String,
+        members: Vec<String>,
+    },
     Traits(Vec<String>),
+    // `[Implements=(Trait, ...)]` - The trait interfaces an interface can be used as.
+    Implements(Vec<String>),
     // `[External="crate_name"]` - We can `use crate_name::...` for the type.
     External {
         crate_name: String,
@@ -44,8 +52,64 @@ pub(super) enum Attribute {
     Custom,
     // The interface described is implemented as a trait.
     Trait,
+    // `[Singleton]` - The interface has a single instance, shared by all callers.
+    Singleton,
     Async,
     NonExhaustive,
+    // `[Namespace=Name]` - group a function under a namespace object in the bindings.
+    Namespace(String),
+    // `[Deprecated="message"]` - The bindings mark the item as deprecated, with the message.
+    Deprecated(String),
+    // `[Order=N]` - The position of a record field when serialized.
+    Order(u8),
+    // `[Retry=N]` - The bindings retry an async function up to N times on a retryable error.
+    Retry(u8),
+    // `[Retryable]` - Functions which are retried are retried when they throw this error variant.
+    Retryable,
+    // `[MainThread]` - The function must only be called from the main thread.
+    MainThread,
+    // `[Chunked]` - The returned sequence is passed over the FFI in chunks.
+    Chunked,
+    // `[Cancellable]` - The bindings also generate a variant which returns a cancellable handle.
+    Cancellable,
+    // `[Default]` - Unknown values of a non-exhaustive enum are lifted as this variant.
+    Default,
+    // `[Variadic]` - The trailing sequence argument is a variadic parameter in the bindings.
+    Variadic,
+    // `[Packed]` - Adjacent `bool` fields of a dictionary are packed into bytes when serialized.
+    Packed,
+    // `[Target="ios"]` - The bindings only generate the item for the named target variant.
+    Target(String),
+    // `[Extension=Name]` - The function is an extension of the type of its first argument.
+    Extension(String),
+    // `[DefaultOnNone="value"]` - The bindings also generate a variant of a function returning an
+    // optional, which returns the value instead of `None`.
+    DefaultOnNone(String),
+    // `[Observable]` - The bindings generate a stream of the events passed to the interface's
+    // observers.
+    Observable,
+    // `[Cursor]` - The bindings generate helpers iterating over the interface's items, which are
+    // fetched in batches with its `next_batch` method.
+    Cursor,
+    // `[ThreadBound]` - The interface's methods must be called on the thread which constructed it.
+    ThreadBound,
+    // `[WithWarnings]` - The function returns its value along with a list of non-fatal warnings.
+    WithWarnings,
+    // `[Segmented]` - The byte segments returned by the function, or passed as the argument,
+    // are passed over the FFI without concatenating them.
+    Segmented,
+    // `[HasDefault]` - The bindings generate a constructor returning the dictionary's Rust
+    // `Default::default()` value.
+    HasDefault,
+    // `[Progress]` - The callback interface argument of an async function receives its progress
+    // events, which the bindings can pass to a closure instead.
+    Progress,
+    // `[Discriminant=u16_le]` - The enum's discriminant is serialized with a fixed width and byte
+    // order.
+    Discriminant(DiscriminantEncoding),
+    // `[OnPoison=recover]` - What the interface's methods do when they panic on state poisoned
+    // by an earlier call.
+    OnPoison(OnPoison),
 }
 
 // A type defined in Rust via procmacros but which should be available
@@ -66,6 +130,27 @@ impl Attribute {
     pub fn is_enum(&self) -> bool {
         matches!(self, Attribute::Enum)
     }
+    pub fn get_deprecated(&self) -> Option<&str> {
+        match self {
+            Attribute::Deprecated(message) => Some(message),
+            _ => None,
+        }
+    }
+    pub fn get_throws_err(&self) -> Option<&str> {
+        match self {
+            // This will hopefully return a helpful compilation error
+            // if the error is not defined.
+            Attribute::Throws(inner) => Some(inner),
+            Attribute::ThrowsUnion { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+    pub fn get_throws_union(&self) -> Option<(&str, &[String])> {
+        match self {
+            Attribute::ThrowsUnion { name, members } => Some((name, members)),
+            _ => None,
+        }
+    }
 }
 
 /// Convert a weedle `ExtendedAttribute` into an `Attribute` for a `InterfaceCollector` member,
@@ -83,8 +168,23 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
                 "Error" => Ok(Attribute::Error),
                 "Custom" => Ok(Attribute::Custom),
                 "Trait" => Ok(Attribute::Trait),
+                "Singleton" => Ok(Attribute::Singleton),
                 "Async" => Ok(Attribute::Async),
                 "NonExhaustive" => Ok(Attribute::NonExhaustive),
+                "Retryable" => Ok(Attribute::Retryable),
+                "MainThread" => Ok(Attribute::MainThread),
+                "Chunked" => Ok(Attribute::Chunked),
+                "WithWarnings" => Ok(Attribute::WithWarnings),
+                "Segmented" => Ok(Attribute::Segmented),
+                "Cancellable" => Ok(Attribute::Cancellable),
+                "Default" => Ok(Attribute::Default),
+                "Variadic" => Ok(Attribute::Variadic),
+                "Packed" => Ok(Attribute::Packed),
+                "Observable" => Ok(Attribute::Observable),
+                "Cursor" => Ok(Attribute::Cursor),
+                "ThreadBound" => Ok(Attribute::ThreadBound),
+                "HasDefault" => Ok(Attribute::HasDefault),
+                "Progress" => Ok(Attribute::Progress),
                 _ => anyhow::bail!("ExtendedAttributeNoArgs not supported: {:?}", (attr.0).0),
             },
             // Matches assignment-style attributes like ["Throws=Error"]
@@ -93,6 +193,15 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
                     "Name" => Ok(Attribute::Name(name_from_id_or_string(&identity.rhs))),
                     "Throws" => Ok(Attribute::Throws(name_from_id_or_string(&identity.rhs))),
                     "Self" => Ok(Attribute::SelfType(SelfType::try_from(&identity.rhs)?)),
+                    "Namespace" => Ok(Attribute::Namespace(name_from_id_or_string(&identity.rhs))),
+                    "Deprecated" => {
+                        Ok(Attribute::Deprecated(name_from_id_or_string(&identity.rhs)))
+                    }
+                    "Target" => Ok(Attribute::Target(name_from_id_or_string(&identity.rhs))),
+                    "Extension" => Ok(Attribute::Extension(name_from_id_or_string(&identity.rhs))),
+                    "DefaultOnNone" => Ok(Attribute::DefaultOnNone(name_from_id_or_string(
+                        &identity.rhs,
+                    ))),
                     "External" => Ok(Attribute::External {
                         crate_name: name_from_id_or_string(&identity.rhs),
                         kind: ExternalKind::DataClass,
@@ -126,6 +235,30 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
                     "Rust" => Ok(Attribute::Rust {
                         kind: rust_kind_from_id_or_string(&identity.rhs)?,
                     }),
+                    "Order" => {
+                        let order = name_from_id_or_string(&identity.rhs);
+                        match order.parse() {
+                            Ok(order) => Ok(Attribute::Order(order)),
+                            Err(_) => anyhow::bail!("`[Order=N]` must be a number, got {order:?}"),
+                        }
+                    }
+                    "Discriminant" => {
+                        let encoding = name_from_id_or_string(&identity.rhs);
+                        Ok(Attribute::Discriminant(encoding.parse()?))
+                    }
+                    "OnPoison" => {
+                        let policy = name_from_id_or_string(&identity.rhs);
+                        Ok(Attribute::OnPoison(policy.parse()?))
+                    }
+                    "Retry" => {
+                        let retries = name_from_id_or_string(&identity.rhs);
+                        match retries.parse() {
+                            Ok(retries) => Ok(Attribute::Retry(retries)),
+                            Err(_) => {
+                                anyhow::bail!("`[Retry=N]` must be a number, got {retries:?}")
+                            }
+                        }
+                    }
                     _ => anyhow::bail!(
                         "Attribute identity Identifier not supported: {:?}",
                         identity.lhs_identifier.0
@@ -143,6 +276,31 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
                             .map(|i| i.0.to_string())
                             .collect(),
                     )),
+                    "Implements" => Ok(Attribute::Implements(
+                        attr_list
+                            .list
+                            .body
+                            .list
+                            .iter()
+                            .map(|i| i.0.to_string())
+                            .collect(),
+                    )),
+                    "Throws" => {
+                        let members: Vec<String> = attr_list
+                            .list
+                            .body
+                            .list
+                            .iter()
+                            .map(|i| i.0.to_string())
+                            .collect();
+                        if members.len() < 2 {
+                            anyhow::bail!("`[Throws=(...)]` needs at least two error types");
+                        }
+                        Ok(Attribute::ThrowsUnion {
+                            name: members.join("Or"),
+                            members,
+                        })
+                    }
                     _ => anyhow::bail!(
                         "Attribute identity list not supported: {:?}",
                         attr_list.identifier.0
@@ -224,6 +382,13 @@ impl EnumAttributes {
             .iter()
             .any(|attr| matches!(attr, Attribute::NonExhaustive))
     }
+
+    pub fn get_discriminant(&self) -> Option<DiscriminantEncoding> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Discriminant(encoding) => Some(*encoding),
+            _ => None,
+        })
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for EnumAttributes {
@@ -234,6 +399,7 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for EnumAttributes {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
             Attribute::Error => Ok(()),
             Attribute::NonExhaustive => Ok(()),
+            Attribute::Discriminant(_) => Ok(()),
             // Allow `[Enum]`, since we may be parsing an attribute list from an interface with the
             // `[Enum]` attribute.
             Attribute::Enum => Ok(()),
@@ -258,21 +424,97 @@ impl<T: TryInto<EnumAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for E
 /// This supports:
 ///   * `[Throws=ErrorName]` attribute for functions that can produce an error.
 ///   * `[Async] for async functions
+///   * `[Retry=N]` for async functions which the bindings retry on a retryable error
+///   * `[MainThread]` for functions which must only be called from the main thread
+///   * `[Chunked]` for functions whose returned sequence is passed in chunks
+///   * `[Cancellable]` for async functions which also get a variant returning a cancellable handle
+///   * `[Target="name"]` for functions which the bindings only generate for a target variant
+///   * `[Extension=Name]` for functions which the bindings generate as an extension of a type
+///   * `[DefaultOnNone="value"]` for functions returning an optional, which also get a variant
+///     returning the value instead of `None`
+///   * `[WithWarnings]` for functions which return their value along with non-fatal warnings
+///   * `[Segmented]` for functions whose returned byte segments aren't concatenated
 #[derive(Debug, Clone, Checksum, Default)]
 pub(super) struct FunctionAttributes(Vec<Attribute>);
 
 impl FunctionAttributes {
     pub(super) fn get_throws_err(&self) -> Option<&str> {
+        self.0.iter().find_map(Attribute::get_throws_err)
+    }
+
+    pub(super) fn get_throws_union(&self) -> Option<(&str, &[String])> {
+        self.0.iter().find_map(Attribute::get_throws_union)
+    }
+
+    pub(super) fn is_async(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Async))
+    }
+
+    pub(super) fn get_namespace_object(&self) -> Option<&str> {
         self.0.iter().find_map(|attr| match attr {
-            // This will hopefully return a helpful compilation error
-            // if the error is not defined.
-            Attribute::Throws(inner) => Some(inner.as_ref()),
+            Attribute::Namespace(name) => Some(name.as_ref()),
             _ => None,
         })
     }
 
-    pub(super) fn is_async(&self) -> bool {
-        self.0.iter().any(|attr| matches!(attr, Attribute::Async))
+    pub(super) fn get_deprecated(&self) -> Option<&str> {
+        self.0.iter().find_map(Attribute::get_deprecated)
+    }
+
+    pub(super) fn get_retries(&self) -> Option<u8> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Retry(retries) => Some(*retries),
+            _ => None,
+        })
+    }
+
+    pub(super) fn is_main_thread(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::MainThread))
+    }
+
+    pub(super) fn is_chunked(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Chunked))
+    }
+
+    pub(super) fn is_cancellable(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Cancellable))
+    }
+
+    pub(super) fn get_target(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Target(target) => Some(target.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub(super) fn get_extension(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Extension(name) => Some(name.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub(super) fn get_default_on_none(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::DefaultOnNone(value) => Some(value.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub(super) fn is_with_warnings(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::WithWarnings))
+    }
+
+    pub(super) fn is_segmented(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Segmented))
     }
 }
 
@@ -288,7 +530,20 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for FunctionAttribut
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::Throws(_) | Attribute::Async => Ok(()),
+            Attribute::Throws(_)
+            | Attribute::ThrowsUnion { .. }
+            | Attribute::Async
+            | Attribute::Namespace(_)
+            | Attribute::Deprecated(_)
+            | Attribute::Retry(_)
+            | Attribute::MainThread
+            | Attribute::Chunked
+            | Attribute::Cancellable
+            | Attribute::Target(_)
+            | Attribute::Extension(_)
+            | Attribute::DefaultOnNone(_)
+            | Attribute::WithWarnings
+            | Attribute::Segmented => Ok(()),
             _ => bail!(format!("{attr:?} not supported for functions")),
         })?;
         Ok(Self(attrs))
@@ -310,7 +565,10 @@ impl<T: TryInto<FunctionAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
 /// Represents UDL attributes that might appear on a function argument.
 ///
 /// This supports the `[ByRef]` attribute for arguments that should be passed
-/// by reference in the generated Rust scaffolding.
+/// by reference in the generated Rust scaffolding, the `[Variadic]` attribute for
+/// a trailing sequence argument which the bindings take as variadic arguments, the
+/// `[Segmented]` attribute for byte segments which aren't concatenated, and the `[Progress]`
+/// attribute for the callback interface which receives an async function's progress events.
 #[derive(Debug, Clone, Checksum, Default)]
 pub(super) struct ArgumentAttributes(Vec<Attribute>);
 
@@ -318,6 +576,24 @@ impl ArgumentAttributes {
     pub fn by_ref(&self) -> bool {
         self.0.iter().any(|attr| matches!(attr, Attribute::ByRef))
     }
+
+    pub fn variadic(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Variadic))
+    }
+
+    pub fn segmented(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Segmented))
+    }
+
+    pub fn progress(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Progress))
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ArgumentAttributes {
@@ -326,7 +602,9 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ArgumentAttribut
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::ByRef => Ok(()),
+            Attribute::ByRef | Attribute::Variadic | Attribute::Segmented | Attribute::Progress => {
+                Ok(())
+            }
             _ => bail!(format!("{attr:?} not supported for arguments")),
         })?;
         Ok(Self(attrs))
@@ -365,6 +643,36 @@ impl InterfaceAttributes {
             ObjectImpl::Struct
         }
     }
+
+    pub fn is_singleton(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Singleton))
+    }
+
+    pub fn is_observable(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Observable))
+    }
+
+    pub fn is_cursor(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Cursor))
+    }
+
+    pub fn is_thread_bound(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::ThreadBound))
+    }
+
+    pub fn get_on_poison(&self) -> Option<OnPoison> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::OnPoison(on_poison) => Some(*on_poison),
+            _ => None,
+        })
+    }
+
     pub fn get_traits(&self) -> Vec<String> {
         self.0
             .iter()
@@ -374,6 +682,16 @@ impl InterfaceAttributes {
             })
             .unwrap_or_default()
     }
+
+    pub fn get_implements(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .find_map(|attr| match attr {
+                Attribute::Implements(inner) => Some(inner.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for InterfaceAttributes {
@@ -386,12 +704,32 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for InterfaceAttribu
             Attribute::Error => Ok(()),
             Attribute::Trait => Ok(()),
             Attribute::Traits(_) => Ok(()),
+            Attribute::Implements(_) => Ok(()),
+            Attribute::Singleton => Ok(()),
+            Attribute::Observable => Ok(()),
+            Attribute::Cursor => Ok(()),
+            Attribute::ThreadBound => Ok(()),
+            Attribute::Discriminant(_) => Ok(()),
+            Attribute::OnPoison(_) => Ok(()),
             _ => bail!(format!("{attr:?} not supported for interface definition")),
         })?;
-        if attrs.iter().any(|a| matches!(a, Attribute::Enum)) && attrs.len() != 1 {
-            // If `[Enum]` is specified it must be the only attribute.
+        let is_enum = attrs.iter().any(|a| matches!(a, Attribute::Enum));
+        if is_enum
+            && attrs
+                .iter()
+                .any(|a| !matches!(a, Attribute::Enum | Attribute::Discriminant(_)))
+        {
+            // If `[Enum]` is specified it must be the only attribute, apart from the encoding of
+            // its discriminant.
             bail!("conflicting attributes on interface definition");
         }
+        if !is_enum
+            && attrs
+                .iter()
+                .any(|a| matches!(a, Attribute::Discriminant(_)))
+        {
+            bail!("`[Discriminant]` is only supported for enums");
+        }
         Ok(Self(attrs))
     }
 }
@@ -411,7 +749,8 @@ impl<T: TryInto<InterfaceAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
 /// Represents UDL attributes that might appear on a constructor.
 ///
 /// This supports the `[Throws=ErrorName]` attribute for constructors that can produce
-/// an error, and the `[Name=MethodName]` for non-default constructors.
+/// an error, the `[Name=MethodName]` for non-default constructors and `[Async]` for
+/// constructors that are async.
 #[derive(Debug, Clone, Checksum, Default)]
 pub(super) struct ConstructorAttributes(Vec<Attribute>);
 
@@ -423,12 +762,11 @@ impl FromIterator<Attribute> for ConstructorAttributes {
 
 impl ConstructorAttributes {
     pub(super) fn get_throws_err(&self) -> Option<&str> {
-        self.0.iter().find_map(|attr| match attr {
-            // This will hopefully return a helpful compilation error
-            // if the error is not defined.
-            Attribute::Throws(inner) => Some(inner.as_ref()),
-            _ => None,
-        })
+        self.0.iter().find_map(Attribute::get_throws_err)
+    }
+
+    pub(super) fn get_throws_union(&self) -> Option<(&str, &[String])> {
+        self.0.iter().find_map(Attribute::get_throws_union)
     }
 
     pub(super) fn get_name(&self) -> Option<&str> {
@@ -437,6 +775,10 @@ impl ConstructorAttributes {
             _ => None,
         })
     }
+
+    pub(super) fn is_async(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Async))
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttributes {
@@ -445,8 +787,9 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttri
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::Throws(_) => Ok(()),
+            Attribute::Throws(_) | Attribute::ThrowsUnion { .. } => Ok(()),
             Attribute::Name(_) => Ok(()),
+            Attribute::Async => Ok(()),
             _ => bail!(format!("{attr:?} not supported for constructors")),
         })?;
         Ok(Self(attrs))
@@ -462,12 +805,11 @@ pub(super) struct MethodAttributes(Vec<Attribute>);
 
 impl MethodAttributes {
     pub(super) fn get_throws_err(&self) -> Option<&str> {
-        self.0.iter().find_map(|attr| match attr {
-            // This will hopefully return a helpful compilation error
-            // if the error is not defined.
-            Attribute::Throws(inner) => Some(inner.as_ref()),
-            _ => None,
-        })
+        self.0.iter().find_map(Attribute::get_throws_err)
+    }
+
+    pub(super) fn get_throws_union(&self) -> Option<(&str, &[String])> {
+        self.0.iter().find_map(Attribute::get_throws_union)
     }
 
     pub(super) fn is_async(&self) -> bool {
@@ -493,7 +835,10 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for MethodAttributes
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::SelfType(_) | Attribute::Throws(_) | Attribute::Async => Ok(()),
+            Attribute::SelfType(_)
+            | Attribute::Throws(_)
+            | Attribute::ThrowsUnion { .. }
+            | Attribute::Async => Ok(()),
             _ => bail!(format!("{attr:?} not supported for methods")),
         })?;
         Ok(Self(attrs))
@@ -535,9 +880,144 @@ impl TryFrom<&weedle::attribute::IdentifierOrString<'_>> for SelfType {
     }
 }
 
+/// Represents UDL attributes that might appear on a `dictionary` definition.
+///
+/// This supports the `[Deprecated="message"]`, `[Packed]` and `[HasDefault]` attributes.
+#[derive(Debug, Clone, Checksum, Default)]
+pub(super) struct DictionaryAttributes(Vec<Attribute>);
+
+impl DictionaryAttributes {
+    pub(super) fn get_deprecated(&self) -> Option<&str> {
+        self.0.iter().find_map(Attribute::get_deprecated)
+    }
+
+    pub(super) fn is_packed(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Packed))
+    }
+
+    pub(super) fn has_default(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::HasDefault))
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for DictionaryAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::Deprecated(_) | Attribute::Packed | Attribute::HasDefault => Ok(()),
+            _ => bail!(format!("{attr:?} not supported for dictionaries")),
+        })?;
+        Ok(Self(attrs))
+    }
+}
+
+impl<T: TryInto<DictionaryAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
+    for DictionaryAttributes
+{
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
+/// Represents UDL attributes that might appear on a `dictionary` member.
+///
+/// This supports the `[Order=N]` attribute.
+#[derive(Debug, Clone, Checksum, Default)]
+pub(super) struct FieldAttributes(Vec<Attribute>);
+
+impl FieldAttributes {
+    pub(super) fn get_order(&self) -> Option<u8> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Order(order) => Some(*order),
+            _ => None,
+        })
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for FieldAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::Order(_) => Ok(()),
+            _ => bail!(format!("{attr:?} not supported for dictionary members")),
+        })?;
+        Ok(Self(attrs))
+    }
+}
+
+impl<T: TryInto<FieldAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for FieldAttributes {
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
+/// Represents UDL attributes that might appear on an enum variant, either in an `enum` or an
+/// `[Enum] interface`.
+///
+/// This supports the `[Deprecated="message"]`, `[Retryable]` and `[Default]` attributes.
+#[derive(Debug, Clone, Checksum, Default)]
+pub(super) struct VariantAttributes(Vec<Attribute>);
+
+impl VariantAttributes {
+    pub(super) fn get_deprecated(&self) -> Option<&str> {
+        self.0.iter().find_map(Attribute::get_deprecated)
+    }
+
+    pub(super) fn is_retryable(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Retryable))
+    }
+
+    pub(super) fn is_default(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Default))
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for VariantAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::Deprecated(_) | Attribute::Retryable | Attribute::Default => Ok(()),
+            _ => bail!(format!("{attr:?} not supported for enum variants")),
+        })?;
+        Ok(Self(attrs))
+    }
+}
+
+impl<T: TryInto<VariantAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
+    for VariantAttributes
+{
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
 /// Represents UDL attributes that might appear on a typedef
 ///
-/// This supports the `[External="crate_name"]` and `[Custom]` attributes for types.
+/// This supports the `[External="crate_name"]` and `[Custom]` attributes for types.  External
+/// enums without fields are declared with `[External="crate_name", Enum]`, since they're passed
+/// across the FFI as an integer rather than in a `RustBuffer`.
 #[derive(Debug, Clone, Checksum, Default)]
 pub(super) struct TypedefAttributes(Vec<Attribute>);
 
@@ -558,11 +1038,24 @@ impl TypedefAttributes {
             .any(|attr| matches!(attr, Attribute::Custom { .. }))
     }
 
+    pub(super) fn is_error(&self) -> bool {
+        self.0.iter().any(|attr| attr.is_error())
+    }
+
     pub(super) fn external_kind(&self) -> Option<ExternalKind> {
-        self.0.iter().find_map(|attr| match attr {
+        let kind = self.0.iter().find_map(|attr| match attr {
             Attribute::External { kind, .. } => Some(*kind),
             _ => None,
-        })
+        })?;
+        if self.is_enum() {
+            Some(ExternalKind::ScalarEnum)
+        } else {
+            Some(kind)
+        }
+    }
+
+    fn is_enum(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Enum))
     }
 
     pub(super) fn rust_kind(&self) -> Option<RustKind> {
@@ -588,10 +1081,28 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for TypedefAttribute
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::External { .. } | Attribute::Custom | Attribute::Rust { .. } => Ok(()),
+            Attribute::External { .. }
+            | Attribute::Custom
+            | Attribute::Rust { .. }
+            | Attribute::Error
+            | Attribute::Enum => Ok(()),
             _ => bail!(format!("{attr:?} not supported for typedefs")),
         })?;
-        Ok(Self(attrs))
+        let attrs = Self(attrs);
+        if attrs.is_enum()
+            && !attrs.0.iter().any(|attr| {
+                matches!(
+                    attr,
+                    Attribute::External {
+                        kind: ExternalKind::DataClass,
+                        ..
+                    }
+                )
+            })
+        {
+            bail!("Enum is only supported for typedefs with [External] or [ExternalExport]");
+        }
+        Ok(attrs)
     }
 }
 
@@ -686,6 +1197,18 @@ mod test {
             "ExtendedAttributeNoArgs not supported: \"Throws\""
         );
 
+        let (_, node) = weedle::attribute::ExtendedAttribute::parse("Throws=(One, Two)").unwrap();
+        let attr = Attribute::try_from(&node)?;
+        assert!(matches!(attr, Attribute::ThrowsUnion { name, members }
+            if name == "OneOrTwo" && members == ["One", "Two"]));
+
+        let (_, node) = weedle::attribute::ExtendedAttribute::parse("Throws=(One)").unwrap();
+        let err = Attribute::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`[Throws=(...)]` needs at least two error types"
+        );
+
         Ok(())
     }
 
@@ -732,6 +1255,148 @@ mod test {
         let attrs = FunctionAttributes::try_from(&node).unwrap();
         assert!(matches!(attrs.get_throws_err(), Some("Error")));
         assert!(attrs.is_async());
+        assert!(attrs.get_namespace_object().is_none());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Namespace=MyApi]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_namespace_object(), Some("MyApi")));
+        assert!(attrs.get_deprecated().is_none());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse(r#"[Deprecated="use foo instead"]"#)
+                .unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_deprecated(), Some("use foo instead")));
+        assert!(attrs.get_retries().is_none());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Async, Retry=3, Throws=Error]")
+                .unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_retries(), Some(3));
+        assert!(!attrs.is_main_thread());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Retry=many]").unwrap();
+        let err = FunctionAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`[Retry=N]` must be a number, got \"many\""
+        );
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[MainThread]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_main_thread());
+        assert!(!attrs.is_chunked());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Chunked]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_chunked());
+        assert!(!attrs.is_cancellable());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Async, Cancellable]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_cancellable());
+        assert!(attrs.get_default_on_none().is_none());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse(r#"[DefaultOnNone="0"]"#).unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_default_on_none(), Some("0"));
+        assert!(!attrs.is_with_warnings());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[WithWarnings, Throws=Error]")
+                .unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_with_warnings());
+        assert_eq!(attrs.get_throws_err(), Some("Error"));
+        assert!(!attrs.is_segmented());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Segmented]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_segmented());
+    }
+
+    #[test]
+    fn test_retryable_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Retryable]").unwrap();
+        let attrs = VariantAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_retryable());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = VariantAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_retryable());
+    }
+
+    #[test]
+    fn test_default_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Default]").unwrap();
+        let attrs = VariantAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_default());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Retryable]").unwrap();
+        let attrs = VariantAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_default());
+    }
+
+    #[test]
+    fn test_packed_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Packed]").unwrap();
+        let attrs = DictionaryAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_packed());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = DictionaryAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_packed());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Packed]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Packed not supported for interface definition"
+        );
+    }
+
+    #[test]
+    fn test_deprecated_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse(r#"[Deprecated="use Other"]"#).unwrap();
+        let attrs = DictionaryAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_deprecated(), Some("use Other")));
+        let attrs = VariantAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_deprecated(), Some("use Other")));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = DictionaryAttributes::try_from(&node).unwrap();
+        assert!(attrs.get_deprecated().is_none());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Error]").unwrap();
+        let err = DictionaryAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Error not supported for dictionaries");
+        let err = VariantAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Error not supported for enum variants");
+    }
+
+    #[test]
+    fn test_target_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse(r#"[Target="ios"]"#).unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_target(), Some("ios")));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(attrs.get_target().is_none());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse(r#"[Target="ios"]"#).unwrap();
+        let err = DictionaryAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Target(\"ios\") not supported for dictionaries"
+        );
     }
 
     #[test]
@@ -805,6 +1470,14 @@ mod test {
         let attrs = ConstructorAttributes::try_from(&node).unwrap();
         assert!(matches!(attrs.get_throws_err(), Some("Error")));
         assert!(matches!(attrs.get_name(), Some("MyFactory")));
+        assert!(!attrs.is_async());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Async, Name=MyFactory]").unwrap();
+        let attrs = ConstructorAttributes::try_from(&node).unwrap();
+        assert!(attrs.get_throws_err().is_none());
+        assert!(matches!(attrs.get_name(), Some("MyFactory")));
+        assert!(attrs.is_async());
     }
 
     #[test]
@@ -832,6 +1505,26 @@ mod test {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
         let attrs = ArgumentAttributes::try_from(&node).unwrap();
         assert!(matches!(attrs.by_ref(), false));
+        assert!(!attrs.variadic());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Variadic]").unwrap();
+        let attrs = ArgumentAttributes::try_from(&node).unwrap();
+        assert!(attrs.variadic());
+        assert!(!attrs.by_ref());
+        assert!(!attrs.segmented());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Segmented]").unwrap();
+        let attrs = ArgumentAttributes::try_from(&node).unwrap();
+        assert!(attrs.segmented());
+        assert!(!attrs.progress());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Progress]").unwrap();
+        let attrs = ArgumentAttributes::try_from(&node).unwrap();
+        assert!(attrs.progress());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Progress]").unwrap();
+        let err = FunctionAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Progress not supported for functions");
     }
 
     #[test]
@@ -856,6 +1549,124 @@ mod test {
         assert_eq!(attrs.object_impl(), ObjectImpl::Struct);
     }
 
+    #[test]
+    fn test_implements_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Implements=(Button, Toggle)]")
+                .unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_implements(), vec!["Button", "Toggle"]);
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.get_implements().is_empty());
+    }
+
+    #[test]
+    fn test_singleton_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Singleton]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_singleton());
+        assert_eq!(attrs.object_impl(), ObjectImpl::Struct);
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_singleton());
+    }
+
+    #[test]
+    fn test_observable_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Observable]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_observable());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_observable());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Observable]").unwrap();
+        let err = DictionaryAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Observable not supported for dictionaries");
+    }
+
+    #[test]
+    fn test_cursor_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Cursor]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_cursor());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_cursor());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Cursor]").unwrap();
+        let err = DictionaryAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Cursor not supported for dictionaries");
+    }
+
+    #[test]
+    fn test_has_default_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[HasDefault]").unwrap();
+        let attrs = DictionaryAttributes::try_from(&node).unwrap();
+        assert!(attrs.has_default());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Packed]").unwrap();
+        let attrs = DictionaryAttributes::try_from(&node).unwrap();
+        assert!(!attrs.has_default());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[HasDefault]").unwrap();
+        let err = EnumAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "HasDefault not supported for enums");
+    }
+
+    #[test]
+    fn test_thread_bound_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ThreadBound]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_thread_bound());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_thread_bound());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ThreadBound]").unwrap();
+        let err = FunctionAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "ThreadBound not supported for functions");
+    }
+
+    #[test]
+    fn test_on_poison_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[OnPoison=recover]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_on_poison(), Some(OnPoison::Recover));
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[OnPoison=error]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_on_poison(), Some(OnPoison::Error));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_on_poison(), None);
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[OnPoison=ignore]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown poison policy \"ignore\", expected `error` or `recover`"
+        );
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[OnPoison=error]").unwrap();
+        let err = FunctionAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "OnPoison(Error) not supported for functions"
+        );
+    }
+
     #[test]
     fn test_enum_attribute_on_interface() {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Enum]").unwrap();
@@ -892,6 +1703,48 @@ mod test {
         assert_eq!(err.to_string(), "Trait not supported for enums");
     }
 
+    #[test]
+    fn test_discriminant_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Discriminant=u16_le]").unwrap();
+        let attrs = EnumAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_discriminant(), Some(DiscriminantEncoding::U16Le));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[NonExhaustive]").unwrap();
+        let attrs = EnumAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_discriminant(), None);
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Discriminant=u64_le]").unwrap();
+        let err = EnumAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown discriminant encoding \"u64_le\", expected one of `u8`, `u16_le`, `u16_be`, `u32_le` or `u32_be`"
+        );
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Discriminant=u8]").unwrap();
+        let err = DictionaryAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Discriminant(U8) not supported for dictionaries"
+        );
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Enum, Discriminant=u8]").unwrap();
+        assert!(InterfaceAttributes::try_from(&node).is_ok());
+        let attrs = EnumAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_discriminant(), Some(DiscriminantEncoding::U8));
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Discriminant=u8]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`[Discriminant]` is only supported for enums"
+        );
+    }
+
     // Test parsing attributes for interface definitions with the `[Enum]` attribute
     #[test]
     fn test_enum_attributes_from_interface() {
@@ -938,6 +1791,12 @@ mod test {
         let attrs = TypedefAttributes::try_from(&node).unwrap();
         assert!(!attrs.is_custom());
         assert_eq!(attrs.get_crate_name(), "crate_name");
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[External=crate_name, Enum]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.external_kind(), Some(ExternalKind::ScalarEnum));
+        assert_eq!(attrs.get_crate_name(), "crate_name");
     }
 
     #[test]
@@ -962,5 +1821,14 @@ mod test {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ByRef]").unwrap();
         let err = TypedefAttributes::try_from(&node).unwrap_err();
         assert_eq!(err.to_string(), "ByRef not supported for typedefs");
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[ExternalInterface=crate_name, Enum]")
+                .unwrap();
+        let err = TypedefAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Enum is only supported for typedefs with [External] or [ExternalExport]"
+        );
     }
 }
@@ -8,6 +8,7 @@ use crate::attributes;
 use crate::converters::{convert_docstring, APIConverter};
 use crate::finder;
 use crate::resolver::TypeResolver;
+use crate::span::{Source, SpannedError};
 use anyhow::{bail, Result};
 use std::collections::{hash_map, BTreeSet, HashMap};
 use uniffi_meta::Type;
@@ -22,12 +23,17 @@ pub(crate) struct InterfaceCollector {
     pub types: TypeCollector,
     /// The output we collect and supply to our consumer.
     pub items: BTreeSet<uniffi_meta::Metadata>,
+    /// The UDL, to locate errors in it.
+    pub source: Source,
 }
 
 impl InterfaceCollector {
     /// Parse an `InterfaceCollector` from a string containing a WebIDL definition.
     pub fn from_webidl(idl: &str, crate_name: &str) -> Result<Self> {
-        let mut ci = Self::default();
+        let mut ci = Self {
+            source: Source::new(idl),
+            ..Self::default()
+        };
         // There's some lifetime thing with the errors returned from weedle::Definitions::parse
         // that my own lifetime is too short to worry about figuring out; unwrap and move on.
 
@@ -40,6 +46,7 @@ impl InterfaceCollector {
             println!("{remaining}");
             bail!("parse error");
         }
+        let defns = merge_partial_interfaces(defns)?;
         // We process the WebIDL definitions in 3 passes.
         // First, find the namespace.
         // XXX - TODO: it's no longer necessary to do this pass.
@@ -50,6 +57,7 @@ impl InterfaceCollector {
 
         // With those names resolved, we can build a complete representation of the API.
         APIBuilder::process(&defns, &mut ci)?;
+        ci.check_throws_types()?;
         // Any misc items we need to add to the set.
         for t in ci.types.type_definitions.values() {
             if let Type::Custom {
@@ -71,6 +79,34 @@ impl InterfaceCollector {
         Ok(ci)
     }
 
+    /// Parse an `InterfaceCollector` from several WebIDL files which make up one namespace.
+    ///
+    /// `files` are `(file_name, udl)` pairs.  Every file must declare the same namespace, and
+    /// no function or type may be defined in more than one file.  The file names are only used
+    /// in error messages.
+    pub fn from_webidl_multi(files: &[(&str, &str)], crate_name: &str) -> Result<Self> {
+        check_multi_definitions(files)?;
+        let mut idl = String::new();
+        let mut starts = Vec::with_capacity(files.len());
+        for (_, udl) in files {
+            starts.push(idl.len());
+            idl.push_str(udl);
+            idl.push('\n');
+        }
+        Self::from_webidl(&idl, crate_name).map_err(|err| {
+            // Locate errors in the file they came from, rather than in the concatenated UDL.
+            let Some(spanned) = err.downcast_ref::<SpannedError>() else {
+                return err;
+            };
+            let index = starts.partition_point(|&start| start <= spanned.span.start) - 1;
+            let (file_name, udl) = files[index];
+            let start = starts[index];
+            let span = spanned.span.start - start..spanned.span.end - start;
+            let message = format!("{} in `{file_name}`", spanned.message);
+            SpannedError::new(udl, message, span).into()
+        })
+    }
+
     fn find_namespace(&mut self, defns: &Vec<weedle::Definition<'_>>) -> Result<String> {
         for defn in defns {
             if let weedle::Definition::Namespace(n) = defn {
@@ -123,6 +159,111 @@ impl InterfaceCollector {
         })
     }
 
+    /// Add the error synthesized for a `[Throws=(ErrorA, ErrorB)]` attribute.
+    ///
+    /// This is a rich error with a variant for each of the errors, which wraps the error in an
+    /// `error` field.  The variants are named after the errors, without an `Error` suffix, so that
+    /// they don't shadow the errors themselves.  The Rust code must define an error enum to match.
+    pub fn add_union_error(&mut self, name: &str, members: &[String]) -> Result<()> {
+        if self.get_type(name).is_some() {
+            // Already added by another function which throws the same errors.
+            return Ok(());
+        }
+        let variants = members
+            .iter()
+            .map(|member| {
+                let ty = match self.get_type(member) {
+                    Some(ty @ Type::Enum { .. }) => ty,
+                    Some(_) => bail!("`{member}` in `[Throws=(...)]` is not an error"),
+                    None => bail!("unknown type for error: {member}"),
+                };
+                Ok(uniffi_meta::VariantMetadata {
+                    name: member.strip_suffix("Error").unwrap_or(member).to_string(),
+                    discr: None,
+                    fields: vec![uniffi_meta::FieldMetadata {
+                        name: "error".to_string(),
+                        ty,
+                        default: None,
+                        docstring: None,
+                        order: None,
+                    }],
+                    docstring: None,
+                    deprecated: None,
+                    retryable: false,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.types.add_type_definition(
+            name,
+            Type::Enum {
+                module_path: self.module_path(),
+                name: name.to_string(),
+            },
+        )?;
+        self.add_definition(
+            uniffi_meta::ErrorMetadata::Enum {
+                enum_: uniffi_meta::EnumMetadata {
+                    module_path: self.module_path(),
+                    name: name.to_string(),
+                    variants,
+                    non_exhaustive: false,
+                    default_variant: None,
+                    discriminant: None,
+                    docstring: None,
+                    since: None,
+                },
+                is_flat: false,
+            }
+            .into(),
+        )
+    }
+
+    /// Check that the types named in `[Throws]` attributes are declared as errors.
+    ///
+    /// Types which are defined elsewhere, for example external types, can't be checked here.
+    fn check_throws_types(&self) -> Result<()> {
+        for item in &self.items {
+            let (callable_name, throws) = match item {
+                uniffi_meta::Metadata::Func(meta) => (meta.name.clone(), &meta.throws),
+                uniffi_meta::Metadata::Constructor(meta) => {
+                    (format!("{}.{}", meta.self_name, meta.name), &meta.throws)
+                }
+                uniffi_meta::Metadata::Method(meta) => {
+                    (format!("{}.{}", meta.self_name, meta.name), &meta.throws)
+                }
+                uniffi_meta::Metadata::TraitMethod(meta) => {
+                    (format!("{}.{}", meta.trait_name, meta.name), &meta.throws)
+                }
+                _ => continue,
+            };
+            let Some(throws) = throws else {
+                continue;
+            };
+            for other in &self.items {
+                match (throws, other) {
+                    (Type::Enum { name, .. }, uniffi_meta::Metadata::Enum(e))
+                        if *name == e.name =>
+                    {
+                        bail!(
+                            "`{callable_name}` throws `{name}`, which is an enum but not an error. \
+                             Add `[Error]` to the definition of `{name}`."
+                        );
+                    }
+                    (Type::Record { name, .. }, uniffi_meta::Metadata::Record(r))
+                        if *name == r.name =>
+                    {
+                        bail!(
+                            "`{callable_name}` throws `{name}`, which is a dictionary. \
+                             Only types declared with `[Error]` can be thrown."
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Called by `APIBuilder` impls to add a newly-parsed definition to the `InterfaceCollector`.
     fn add_definition(&mut self, defn: uniffi_meta::Metadata) -> Result<()> {
         self.items.insert(defn);
@@ -130,6 +271,119 @@ impl InterfaceCollector {
     }
 }
 
+/// Check the files passed to `InterfaceCollector::from_webidl_multi` agree on their namespace,
+/// and don't define the same function or type twice.
+fn check_multi_definitions(files: &[(&str, &str)]) -> Result<()> {
+    use weedle::Parse;
+    let mut namespace: Option<(&str, &str)> = None;
+    let mut functions = HashMap::new();
+    let mut types = HashMap::new();
+    for &(file_name, udl) in files {
+        let defns = match weedle::Definitions::parse(udl.trim()) {
+            Ok(("", defns)) => defns,
+            Ok((remaining, _)) => {
+                println!("Error parsing `{file_name}`. Text remaining to be parsed is:");
+                println!("{remaining}");
+                bail!("parse error in `{file_name}`");
+            }
+            Err(_) => bail!("parse error in `{file_name}`"),
+        };
+        for defn in &defns {
+            let type_name = match defn {
+                weedle::Definition::Namespace(n) => {
+                    let name = n.identifier.0;
+                    match namespace {
+                        None => namespace = Some((name, file_name)),
+                        Some((first, first_file)) if first != name => bail!(
+                            "namespace `{name}` in `{file_name}` doesn't match namespace `{first}` in `{first_file}`"
+                        ),
+                        Some(_) => (),
+                    }
+                    for member in &n.members.body {
+                        if let weedle::namespace::NamespaceMember::Operation(op) = member {
+                            if let Some(ident) = &op.identifier {
+                                if let Some(first_file) = functions.insert(ident.0, file_name) {
+                                    if first_file != file_name {
+                                        bail!(
+                                            "function `{}` is defined in both `{first_file}` and `{file_name}`",
+                                            ident.0
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    continue;
+                }
+                weedle::Definition::Dictionary(d) => d.identifier.0,
+                weedle::Definition::Enum(e) => e.identifier.0,
+                weedle::Definition::Interface(i) => i.identifier.0,
+                weedle::Definition::CallbackInterface(c) => c.identifier.0,
+                weedle::Definition::Callback(c) => c.identifier.0,
+                weedle::Definition::Typedef(t) => t.identifier.0,
+                _ => continue,
+            };
+            if let Some(first_file) = types.insert(type_name, file_name) {
+                if first_file != file_name {
+                    bail!("`{type_name}` is defined in both `{first_file}` and `{file_name}`");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Merge each `partial interface` into the `interface` of the same name.
+///
+/// The members of the partial definitions are appended to the full definition, which is the
+/// only one allowed to carry attributes, so that large interfaces can be split into pieces.
+fn merge_partial_interfaces(
+    defns: Vec<weedle::Definition<'_>>,
+) -> Result<Vec<weedle::Definition<'_>>> {
+    use weedle::interface::InterfaceMember;
+
+    let (partials, mut defns): (Vec<_>, Vec<_>) = defns
+        .into_iter()
+        .partition(|d| matches!(d, weedle::Definition::PartialInterface(_)));
+    for partial in partials {
+        let weedle::Definition::PartialInterface(partial) = partial else {
+            unreachable!()
+        };
+        let name = partial.identifier.0;
+        if partial.attributes.is_some() {
+            bail!("partial interface `{name}` can't have attributes");
+        }
+        let Some(full) = defns.iter_mut().find_map(|d| match d {
+            weedle::Definition::Interface(i) if i.identifier.0 == name => Some(i),
+            _ => None,
+        }) else {
+            bail!("partial interface `{name}` has no matching interface definition");
+        };
+        for member in partial.members.body {
+            if let InterfaceMember::Operation(op) = &member {
+                let existing = full.members.body.iter().find_map(|m| match m {
+                    InterfaceMember::Operation(o) if o.identifier == op.identifier => Some(o),
+                    _ => None,
+                });
+                if let (Some(existing), Some(method)) = (existing, op.identifier) {
+                    let method = method.0;
+                    if existing.args == op.args
+                        && existing.return_type == op.return_type
+                        && existing.attributes == op.attributes
+                    {
+                        bail!("Duplicate method `{name}.{method}` in partial interface");
+                    }
+                    bail!(
+                        "Conflicting definitions for method `{name}.{method}` in partial interface"
+                    );
+                }
+            }
+            full.members.body.push(member);
+        }
+    }
+    Ok(defns)
+}
+
 /// Turn our internal object into an outgoing public `MetadataGroup`.
 impl From<InterfaceCollector> for uniffi_meta::MetadataGroup {
     fn from(value: InterfaceCollector) -> Self {
@@ -221,12 +475,33 @@ impl APIBuilder for weedle::NamespaceDefinition<'_> {
         }
         ci.types.namespace_docstring = self.docstring.as_ref().map(|v| convert_docstring(&v.0));
         for func in self.members.body.convert(ci)? {
+            // Functions share the namespace, so one would shadow the other in the bindings.
+            // Methods are checked separately, since each interface has its own members.
+            let existing = ci.items.iter().find_map(|item| match item {
+                uniffi_meta::Metadata::Func(f) if f.name == func.name => Some(f),
+                _ => None,
+            });
+            if let Some(existing) = existing {
+                bail!(
+                    "Duplicate function name `{}` in namespace `{}`: `{}` and `{}`",
+                    func.name,
+                    ci.types.namespace,
+                    describe_fn(existing),
+                    describe_fn(&func)
+                );
+            }
             ci.add_definition(func.into())?;
         }
         Ok(())
     }
 }
 
+// The function's name and argument names, to tell duplicate functions apart in errors.
+fn describe_fn(func: &uniffi_meta::FnMetadata) -> String {
+    let args: Vec<_> = func.inputs.iter().map(|arg| arg.name.as_str()).collect();
+    format!("{}({})", func.name, args.join(", "))
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct TypeCollector {
     /// The unique prefix that we'll use for namespacing when exposing this component's API.
@@ -5,14 +5,14 @@
 use super::APIConverter;
 use crate::attributes::ArgumentAttributes;
 use crate::attributes::{ConstructorAttributes, FunctionAttributes, MethodAttributes};
-use crate::converters::convert_docstring;
+use crate::converters::convert_callable_docstring;
 use crate::literal::convert_default_value;
 use crate::InterfaceCollector;
 use anyhow::{bail, Result};
 
 use uniffi_meta::{
-    ConstructorMetadata, FieldMetadata, FnMetadata, FnParamMetadata, MethodMetadata,
-    TraitMethodMetadata, Type,
+    ConstructorMetadata, FieldMetadata, FnMetadata, FnParamMetadata, LiteralMetadata,
+    MethodMetadata, TraitMethodMetadata, Type,
 };
 
 impl APIConverter<FieldMetadata> for weedle::argument::Argument<'_> {
@@ -43,6 +43,7 @@ impl APIConverter<FieldMetadata> for weedle::argument::SingleArgument<'_> {
             ty: type_,
             default: None,
             docstring: None,
+            order: None,
         })
     }
 }
@@ -63,17 +64,124 @@ impl APIConverter<FnParamMetadata> for weedle::argument::SingleArgument<'_> {
             None => None,
             Some(v) => Some(convert_default_value(&v.value, &type_)?),
         };
-        let by_ref = ArgumentAttributes::try_from(self.attributes.as_ref())?.by_ref();
+        let attrs = ArgumentAttributes::try_from(self.attributes.as_ref())?;
         Ok(FnParamMetadata {
             name: self.identifier.0.to_string(),
             ty: type_,
-            by_ref,
+            by_ref: attrs.by_ref(),
             optional: self.optional.is_some(),
             default,
+            variadic: attrs.variadic(),
+            segmented: attrs.segmented(),
+            progress: attrs.progress(),
+            // `&mut T` out-parameters are only supported by `#[uniffi::export]`
+            out: false,
+            docstring: None,
         })
     }
 }
 
+/// Defaults can only be applied to trailing arguments, otherwise positional callers in the
+/// foreign languages couldn't omit them.
+fn check_trailing_defaults(inputs: &[FnParamMetadata]) -> Result<()> {
+    if let Some(first_default) = inputs.iter().position(|arg| arg.default.is_some()) {
+        if let Some(arg) = inputs[first_default..]
+            .iter()
+            .find(|arg| arg.default.is_none())
+        {
+            bail!(
+                "argument `{}` must have a default value, since it follows the defaulted argument `{}`",
+                arg.name,
+                inputs[first_default].name
+            );
+        }
+    }
+    Ok(())
+}
+
+/// An extension function is called on its first argument, so that must be of the extended type
+/// and always be passed.
+fn check_extension(name: &str, extension: &str, inputs: &[FnParamMetadata]) -> Result<()> {
+    let receiver = inputs.first().filter(|arg| match &arg.ty {
+        Type::Object { name, .. } | Type::Record { name, .. } | Type::Enum { name, .. } => {
+            name == extension
+        }
+        _ => false,
+    });
+    match receiver {
+        None => bail!(
+            "[Extension={extension}] function `{name}` must take a `{extension}` as its first \
+             argument"
+        ),
+        Some(arg) if arg.default.is_some() => bail!(
+            "argument `{}` can't have a default value, since `{name}` is an extension of it",
+            arg.name
+        ),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Only the last argument can be variadic, and it must be a sequence without a default value.
+/// The bindings collect the variadic arguments into that sequence.
+fn check_variadic(inputs: &[FnParamMetadata]) -> Result<()> {
+    for (index, arg) in inputs.iter().enumerate() {
+        if !arg.variadic {
+            continue;
+        }
+        if index + 1 != inputs.len() {
+            bail!(
+                "argument `{}` can't be [Variadic], only the last argument can be",
+                arg.name
+            );
+        }
+        if !matches!(arg.ty, Type::Sequence { .. }) {
+            bail!(
+                "argument `{}` must be a sequence to be [Variadic]",
+                arg.name
+            );
+        }
+        if arg.default.is_some() {
+            bail!(
+                "argument `{}` can't have a default value, since it's [Variadic]",
+                arg.name
+            );
+        }
+    }
+    Ok(())
+}
+
+// The value of `[DefaultOnNone="value"]`, which must be a literal of the type the function returns
+// an optional of.  Strings and enum variants are written without quotes.
+fn convert_default_on_none(
+    name: &str,
+    value: &str,
+    return_type: Option<&Type>,
+) -> Result<LiteralMetadata> {
+    let inner_type = match return_type {
+        Some(Type::Optional { inner_type }) if !matches!(**inner_type, Type::Optional { .. }) => {
+            inner_type
+        }
+        _ => bail!("\"{name}\" must return an optional to have [DefaultOnNone]"),
+    };
+    let literal = match &**inner_type {
+        Type::String => LiteralMetadata::String(value.to_string()),
+        Type::Enum { .. } => LiteralMetadata::Enum(value.to_string(), (**inner_type).clone()),
+        _ => {
+            use weedle::Parse;
+            let converted = match weedle::literal::DefaultValue::parse(value) {
+                Ok(("", default)) => convert_default_value(&default, inner_type),
+                _ => Err(anyhow::anyhow!("it isn't a literal")),
+            };
+            converted.map_err(|e| {
+                anyhow::anyhow!(
+                    "[DefaultOnNone=\"{value}\"] isn't a valid default for \"{name}\": {e}"
+                )
+            })?
+        }
+    };
+    Ok(literal)
+}
+
 impl APIConverter<FnMetadata> for weedle::namespace::NamespaceMember<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<FnMetadata> {
         match self {
@@ -92,6 +200,9 @@ impl APIConverter<FnMetadata> for weedle::namespace::OperationNamespaceMember<'_
         };
         let attrs = FunctionAttributes::try_from(self.attributes.as_ref())?;
         let is_async = attrs.is_async();
+        if let Some((name, members)) = attrs.get_throws_union() {
+            ci.add_union_error(name, members)?;
+        }
         let throws = match attrs.get_throws_err() {
             None => None,
             Some(name) => match ci.get_type(name) {
@@ -99,15 +210,45 @@ impl APIConverter<FnMetadata> for weedle::namespace::OperationNamespaceMember<'_
                 None => bail!("unknown type for error: {name}"),
             },
         };
+        let mut inputs: Vec<_> = self.args.body.list.convert(ci)?;
+        check_trailing_defaults(&inputs)?;
+        check_variadic(&inputs)?;
+        if let Some(extension) = attrs.get_extension() {
+            check_extension(&name, extension, &inputs)?;
+            if attrs.get_namespace_object().is_some() {
+                bail!("Extension functions can't be grouped under a namespace object: \"{name}\"");
+            }
+        }
+        let default_on_none = match attrs.get_default_on_none() {
+            Some(value) => Some(convert_default_on_none(&name, value, return_type.as_ref())?),
+            None => None,
+        };
+        let docstring = convert_callable_docstring(self.docstring.as_ref(), &mut inputs)?;
         Ok(FnMetadata {
             module_path: ci.module_path(),
             name,
             is_async,
             return_type,
-            inputs: self.args.body.list.convert(ci)?,
+            inputs,
             throws,
-            docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            docstring: docstring.body,
+            return_docstring: docstring.returns,
+            since: docstring.since,
             checksum: None,
+            namespace_object: attrs.get_namespace_object().map(ToOwned::to_owned),
+            deprecated: attrs.get_deprecated().map(ToOwned::to_owned),
+            retries: attrs.get_retries(),
+            main_thread: attrs.is_main_thread(),
+            chunked: attrs.is_chunked(),
+            with_warnings: attrs.is_with_warnings(),
+            segmented: attrs.is_segmented(),
+            cancellable: attrs.is_cancellable(),
+            target: attrs.get_target().map(ToOwned::to_owned),
+            extension: attrs.get_extension().map(ToOwned::to_owned),
+            default_on_none,
+            // The UDL scaffolding always returns owned values.
+            static_return: false,
+            runtime_initializer: false,
         })
     }
 }
@@ -118,19 +259,31 @@ impl APIConverter<ConstructorMetadata> for weedle::interface::ConstructorInterfa
             Some(attr) => ConstructorAttributes::try_from(attr)?,
             None => Default::default(),
         };
+        if let Some((name, members)) = attributes.get_throws_union() {
+            ci.add_union_error(name, members)?;
+        }
         let throws = attributes
             .get_throws_err()
             .map(|name| ci.get_type(name).expect("invalid throws type"));
+        let mut inputs: Vec<_> = self.args.body.list.convert(ci)?;
+        check_trailing_defaults(&inputs)?;
+        check_variadic(&inputs)?;
+        let docstring = convert_callable_docstring(self.docstring.as_ref(), &mut inputs)?;
+        if docstring.returns.is_some() {
+            bail!("Constructors can not have a `@returns` tag");
+        }
         Ok(ConstructorMetadata {
             module_path: ci.module_path(),
             name: String::from(attributes.get_name().unwrap_or("new")),
             // We don't know the name of the containing `Object` at this point, fill it in later.
             self_name: Default::default(),
+            is_async: attributes.is_async(),
             // Also fill in checksum_fn_name later, since it depends on object_name
-            inputs: self.args.body.list.convert(ci)?,
+            inputs,
             throws,
             checksum: None,
-            docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            docstring: docstring.body,
+            since: docstring.since,
         })
     }
 }
@@ -140,13 +293,18 @@ impl APIConverter<MethodMetadata> for weedle::interface::OperationInterfaceMembe
         if self.special.is_some() {
             bail!("special operations not supported");
         }
-        if self.modifier.is_some() {
-            bail!("method modifiers are not supported")
+        // `stringifier` is handled by the interface converter, which also wires the method up as
+        // the `Display` implementation of the interface.
+        if let Some(weedle::interface::StringifierOrStatic::Static(_)) = self.modifier {
+            bail!("static methods are not supported")
         }
         let return_type = ci.resolve_return_type_expression(&self.return_type)?;
         let attributes = MethodAttributes::try_from(self.attributes.as_ref())?;
         let is_async = attributes.is_async();
 
+        if let Some((name, members)) = attributes.get_throws_union() {
+            ci.add_union_error(name, members)?;
+        }
         let throws = match attributes.get_throws_err() {
             Some(name) => match ci.get_type(name) {
                 Some(t) => Some(t),
@@ -156,6 +314,10 @@ impl APIConverter<MethodMetadata> for weedle::interface::OperationInterfaceMembe
         };
 
         let takes_self_by_arc = attributes.get_self_by_arc();
+        let mut inputs: Vec<_> = self.args.body.list.convert(ci)?;
+        check_trailing_defaults(&inputs)?;
+        check_variadic(&inputs)?;
+        let docstring = convert_callable_docstring(self.docstring.as_ref(), &mut inputs)?;
         Ok(MethodMetadata {
             module_path: ci.module_path(),
             name: match self.identifier {
@@ -171,12 +333,15 @@ impl APIConverter<MethodMetadata> for weedle::interface::OperationInterfaceMembe
             // We don't know the name of the containing `Object` at this point, fill it in later.
             self_name: Default::default(),
             is_async,
-            inputs: self.args.body.list.convert(ci)?,
+            inputs,
             return_type,
             throws,
             takes_self_by_arc,
+            takes_self_by_value: false,
             checksum: None,
-            docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            docstring: docstring.body,
+            return_docstring: docstring.returns,
+            since: docstring.since,
         })
     }
 }
@@ -193,6 +358,9 @@ impl APIConverter<TraitMethodMetadata> for weedle::interface::OperationInterface
         let attributes = MethodAttributes::try_from(self.attributes.as_ref())?;
         let is_async = attributes.is_async();
 
+        if let Some((name, members)) = attributes.get_throws_union() {
+            ci.add_union_error(name, members)?;
+        }
         let throws = match attributes.get_throws_err() {
             Some(name) => match ci.get_type(name) {
                 Some(t) => Some(t),
@@ -202,6 +370,17 @@ impl APIConverter<TraitMethodMetadata> for weedle::interface::OperationInterface
         };
 
         let takes_self_by_arc = attributes.get_self_by_arc();
+        let mut inputs: Vec<_> = self.args.body.list.convert(ci)?;
+        check_trailing_defaults(&inputs)?;
+        // The foreign implementations are called with the sequence itself, which can't be passed
+        // on as variadic arguments.
+        if let Some(arg) = inputs.iter().find(|arg| arg.variadic) {
+            bail!(
+                "callback interface method arguments can't be [Variadic]: `{}`",
+                arg.name
+            );
+        }
+        let docstring = convert_callable_docstring(self.docstring.as_ref(), &mut inputs)?;
         Ok(TraitMethodMetadata {
             module_path: ci.module_path(),
             trait_name: Default::default(), // we'll fill these in later.
@@ -217,12 +396,15 @@ impl APIConverter<TraitMethodMetadata> for weedle::interface::OperationInterface
                 }
             },
             is_async,
-            inputs: self.args.body.list.convert(ci)?,
+            has_default: false,
+            inputs,
             return_type,
             throws,
             takes_self_by_arc,
             checksum: None,
-            docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            docstring: docstring.body,
+            return_docstring: docstring.returns,
+            since: docstring.since,
         })
     }
 }
@@ -3,10 +3,15 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use super::APIConverter;
-use crate::{attributes::EnumAttributes, converters::convert_docstring, InterfaceCollector};
+use crate::{
+    attributes::{EnumAttributes, VariantAttributes},
+    converters::{convert_docstring, convert_type_docstring},
+    InterfaceCollector,
+};
 use anyhow::{bail, Result};
+use std::collections::HashSet;
 
-use uniffi_meta::{EnumMetadata, ErrorMetadata, VariantMetadata};
+use uniffi_meta::{EnumMetadata, ErrorMetadata, FieldMetadata, VariantMetadata};
 
 // Note that we have four `APIConverter` impls here - one for the `enum` case,
 // one for the `[Error] enum` case, and and one for the `[Enum] interface` case,
@@ -14,25 +19,71 @@ use uniffi_meta::{EnumMetadata, ErrorMetadata, VariantMetadata};
 impl APIConverter<EnumMetadata> for weedle::EnumDefinition<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<EnumMetadata> {
         let attributes = EnumAttributes::try_from(self.attributes.as_ref())?;
+        let (docstring, since) = convert_type_docstring(self.docstring.as_ref())?;
+        let mut default_variant = None;
+        let mut names = HashSet::new();
+        let variants = self
+            .values
+            .body
+            .list
+            .iter()
+            .map::<Result<_>, _>(|v| {
+                check_unique_variant(ci, &mut names, self.identifier.0, v.value.name())?;
+                let attributes = VariantAttributes::try_from(v.attributes.as_ref())?;
+                let fields = match &v.fields {
+                    None => vec![],
+                    Some(fields) => fields.body.list.convert(ci)?,
+                };
+                if attributes.is_default() {
+                    if default_variant.is_some() {
+                        return Err(ci.source.error_at(
+                            v.value.name(),
+                            format!(
+                                "`{}` can only have one `[Default]` variant",
+                                self.identifier.0
+                            ),
+                        ));
+                    }
+                    default_variant = Some(v.value.name().to_string());
+                }
+                Ok(VariantMetadata {
+                    name: v.value.name().to_string(),
+                    discr: None,
+                    fields,
+                    docstring: v.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+                    deprecated: attributes.get_deprecated().map(ToOwned::to_owned),
+                    retryable: attributes.is_retryable(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let non_exhaustive = attributes.contains_non_exhaustive_attr();
+        if default_variant.is_some() {
+            if !non_exhaustive {
+                return Err(ci.source.error_at(
+                    self.identifier.0,
+                    format!(
+                        "`{}` has a `[Default]` variant, so it must be `[NonExhaustive]`",
+                        self.identifier.0
+                    ),
+                ));
+            }
+            // Unknown values can only be skipped if they don't have any fields.
+            if variants.iter().any(|v| !v.fields.is_empty()) {
+                bail!(
+                    "`{}` has a `[Default]` variant, so its variants can't have fields",
+                    self.identifier.0
+                );
+            }
+        }
         Ok(EnumMetadata {
             module_path: ci.module_path(),
             name: self.identifier.0.to_string(),
-            variants: self
-                .values
-                .body
-                .list
-                .iter()
-                .map::<Result<_>, _>(|v| {
-                    Ok(VariantMetadata {
-                        name: v.value.0.to_string(),
-                        discr: None,
-                        fields: vec![],
-                        docstring: v.docstring.as_ref().map(|v| convert_docstring(&v.0)),
-                    })
-                })
-                .collect::<Result<Vec<_>>>()?,
-            non_exhaustive: attributes.contains_non_exhaustive_attr(),
-            docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            variants,
+            non_exhaustive,
+            default_variant,
+            discriminant: attributes.get_discriminant(),
+            docstring,
+            since,
         })
     }
 }
@@ -40,6 +91,9 @@ impl APIConverter<EnumMetadata> for weedle::EnumDefinition<'_> {
 impl APIConverter<ErrorMetadata> for weedle::EnumDefinition<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<ErrorMetadata> {
         let attributes = EnumAttributes::try_from(self.attributes.as_ref())?;
+        check_no_discriminant(&attributes, self.identifier.0)?;
+        let (docstring, since) = convert_type_docstring(self.docstring.as_ref())?;
+        let mut names = HashSet::new();
         Ok(ErrorMetadata::Enum {
             enum_: EnumMetadata {
                 module_path: ci.module_path(),
@@ -50,28 +104,85 @@ impl APIConverter<ErrorMetadata> for weedle::EnumDefinition<'_> {
                     .list
                     .iter()
                     .map::<Result<_>, _>(|v| {
+                        check_unique_variant(ci, &mut names, self.identifier.0, v.value.name())?;
+                        let attributes = VariantAttributes::try_from(v.attributes.as_ref())?;
+                        if attributes.is_default() {
+                            bail!("`[Default]` variants are only supported in an `enum`");
+                        }
+                        if v.fields.is_some() {
+                            return Err(ci.source.error_at(
+                                v.value.name(),
+                                format!(
+                                    "variant `{}` of `[Error] enum {}` can't have fields, use `[Error] interface` instead",
+                                    v.value.name(),
+                                    self.identifier.0
+                                ),
+                            ));
+                        }
                         Ok(VariantMetadata {
-                            name: v.value.0.to_string(),
+                            name: v.value.name().to_string(),
                             discr: None,
                             fields: vec![],
                             docstring: v.docstring.as_ref().map(|v| v.0.clone()),
+                            deprecated: attributes.get_deprecated().map(ToOwned::to_owned),
+                            retryable: attributes.is_retryable(),
                         })
                     })
                     .collect::<Result<Vec<_>>>()?,
                 non_exhaustive: attributes.contains_non_exhaustive_attr(),
-                docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+                default_variant: None,
+                discriminant: None,
+                docstring,
+                since,
             },
             is_flat: true,
         })
     }
 }
 
+// Errors are always serialized with the usual `i32` discriminant.
+fn check_no_discriminant(attributes: &EnumAttributes, name: &str) -> Result<()> {
+    if attributes.get_discriminant().is_some() {
+        bail!("`{name}` is an `[Error]`, so it can't set its `[Discriminant]`");
+    }
+    Ok(())
+}
+
+// Variants are identified by their name, so an enum can't have two with the same one.
+fn check_unique_variant<'a>(
+    ci: &InterfaceCollector,
+    names: &mut HashSet<&'a str>,
+    enum_name: &str,
+    name: &'a str,
+) -> Result<()> {
+    if !names.insert(name) {
+        return Err(ci.source.error_at(
+            name,
+            format!("Duplicate variant `{name}` in enum `{enum_name}`"),
+        ));
+    }
+    Ok(())
+}
+
+impl APIConverter<FieldMetadata> for weedle::EnumVariantField<'_> {
+    fn convert(&self, ci: &mut InterfaceCollector) -> Result<FieldMetadata> {
+        Ok(FieldMetadata {
+            name: self.identifier.0.to_string(),
+            ty: ci.resolve_type_expression(&self.type_)?,
+            default: None,
+            docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            order: None,
+        })
+    }
+}
+
 impl APIConverter<EnumMetadata> for weedle::InterfaceDefinition<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<EnumMetadata> {
         if self.inheritance.is_some() {
             bail!("interface inheritance is not supported for enum interfaces");
         }
         let attributes = EnumAttributes::try_from(self.attributes.as_ref())?;
+        let (docstring, since) = convert_type_docstring(self.docstring.as_ref())?;
         Ok(EnumMetadata {
             module_path: ci.module_path(),
             name: self.identifier.0.to_string(),
@@ -88,7 +199,10 @@ impl APIConverter<EnumMetadata> for weedle::InterfaceDefinition<'_> {
                 })
                 .collect::<Result<Vec<_>>>()?,
             non_exhaustive: attributes.contains_non_exhaustive_attr(),
-            docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            default_variant: None,
+            discriminant: attributes.get_discriminant(),
+            docstring,
+            since,
             // Enums declared using the `[Enum] interface` syntax might have variants with fields.
             //flat: false,
         })
@@ -101,6 +215,8 @@ impl APIConverter<ErrorMetadata> for weedle::InterfaceDefinition<'_> {
             bail!("interface inheritance is not supported for enum interfaces");
         }
         let attributes = EnumAttributes::try_from(self.attributes.as_ref())?;
+        check_no_discriminant(&attributes, self.identifier.0)?;
+        let (docstring, since) = convert_type_docstring(self.docstring.as_ref())?;
         Ok(ErrorMetadata::Enum {
             enum_: EnumMetadata {
                 module_path: ci.module_path(),
@@ -118,7 +234,10 @@ impl APIConverter<ErrorMetadata> for weedle::InterfaceDefinition<'_> {
                     })
                     .collect::<Result<Vec<_>>>()?,
                 non_exhaustive: attributes.contains_non_exhaustive_attr(),
-                docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+                default_variant: None,
+                discriminant: None,
+                docstring,
+                since,
             },
             is_flat: false,
         })
@@ -134,18 +253,102 @@ mod test {
     fn test_duplicate_variants() {
         const UDL: &str = r#"
             namespace test{};
-            // Weird, but currently allowed!
-            // We should probably disallow this...
             enum Testing { "one", "two", "one" };
         "#;
+        let err = InterfaceCollector::from_webidl(UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Duplicate variant `one` in enum `Testing` at 3:43"
+        );
+        // The span covers the variant's name, so tools can highlight it.
+        let spanned = err.downcast_ref::<crate::SpannedError>().unwrap();
+        assert_eq!(&UDL[spanned.span.clone()], "one");
+        assert_eq!(UDL[..spanned.span.start].matches("\"one\"").count(), 1);
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [Error]
+            enum Testing { One, Two, One };
+        "#;
+        let err = InterfaceCollector::from_webidl(UDL2, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Duplicate variant `One` in enum `Testing` at 4:38"
+        );
+    }
+
+    #[test]
+    fn test_variants_with_named_fields() {
+        const UDL: &str = r#"
+            namespace test{};
+            enum Event { Click { u32 x, u32 y }, Close };
+        "#;
         let mut ci = InterfaceCollector::from_webidl(UDL, "crate_name").unwrap();
-        assert_eq!(ci.types.namespace, "test");
-        assert_eq!(ci.types.module_path(), "crate_name");
-        assert_eq!(ci.items.len(), 1);
         let e = &ci.items.pop_first().unwrap();
         match e {
-            Metadata::Enum(e) => assert_eq!(e.variants.len(), 3),
+            Metadata::Enum(e) => {
+                assert_eq!(e.variants.len(), 2);
+                assert_eq!(e.variants[0].name, "Click");
+                let field_names: Vec<_> = e.variants[0]
+                    .fields
+                    .iter()
+                    .map(|f| f.name.as_str())
+                    .collect();
+                assert_eq!(field_names, vec!["x", "y"]);
+                assert_eq!(e.variants[0].fields[0].ty, uniffi_meta::Type::UInt32);
+                assert_eq!(e.variants[1].name, "Close");
+                assert!(e.variants[1].fields.is_empty());
+            }
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_variant_fields_with_records_declared_later() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Enum]
+            interface Event {
+                Created(User user);
+                Renamed(User user, sequence<Tag> tags);
+            };
+            dictionary User { string name; Tag? tag; };
+            dictionary Tag { string label; };
+        "#;
+        let ci = InterfaceCollector::from_webidl(UDL, "crate_name").unwrap();
+        let e = ci
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Metadata::Enum(e) => Some(e),
+                _ => None,
+            })
+            .unwrap();
+        let record = |name: &str| uniffi_meta::Type::Record {
+            module_path: "crate_name".into(),
+            name: name.into(),
+        };
+        assert_eq!(e.variants[0].fields[0].ty, record("User"));
+        assert_eq!(e.variants[1].fields[0].ty, record("User"));
+        assert_eq!(
+            e.variants[1].fields[1].ty,
+            uniffi_meta::Type::Sequence {
+                inner_type: Box::new(record("Tag"))
+            }
+        );
+    }
+
+    #[test]
+    fn test_error_variants_cant_have_fields() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Error]
+            enum Failure { Timeout { u32 seconds } };
+        "#;
+        let err = InterfaceCollector::from_webidl(UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "variant `Timeout` of `[Error] enum Failure` can't have fields, use `[Error] interface` instead at 4:28"
+        );
+    }
 }
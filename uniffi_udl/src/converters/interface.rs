@@ -4,19 +4,20 @@
 
 use super::APIConverter;
 use crate::attributes::InterfaceAttributes;
-use crate::{converters::convert_docstring, InterfaceCollector};
+use crate::literal::ConstEvaluator;
+use crate::{
+    converters::{convert_docstring, convert_type_docstring},
+    InterfaceCollector,
+};
 use anyhow::{bail, Result};
 use std::collections::HashSet;
 use uniffi_meta::{
-    ConstructorMetadata, FnParamMetadata, MethodMetadata, ObjectImpl, ObjectMetadata, Type,
-    UniffiTraitMetadata,
+    ConstantMetadata, ConstructorMetadata, FnParamMetadata, MethodMetadata, ObjectImpl,
+    ObjectMetadata, Type, UniffiTraitMetadata,
 };
 
 impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<ObjectMetadata> {
-        if self.inheritance.is_some() {
-            bail!("interface inheritance is not supported");
-        }
         let attributes = match &self.attributes {
             Some(attrs) => InterfaceAttributes::try_from(attrs)?,
             None => Default::default(),
@@ -24,9 +25,68 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
 
         let object_name = self.identifier.0;
         let object_impl = attributes.object_impl();
-        // Convert each member into a constructor or method, guarding against duplicate names.
-        // They get added to the ci and aren't carried in ObjectMetadata.
+        let implements = attributes
+            .get_implements()
+            .into_iter()
+            .map(|trait_name| match ci.get_type(&trait_name) {
+                Some(
+                    ty @ Type::Object {
+                        imp: ObjectImpl::Trait,
+                        ..
+                    },
+                ) => Ok(ty),
+                _ => bail!(
+                    "\"{object_name}\" can only implement trait interfaces, not \"{trait_name}\""
+                ),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if object_impl == ObjectImpl::Trait && !implements.is_empty() {
+            bail!("Trait interfaces can not implement other traits: \"{object_name}\"")
+        }
+        let is_singleton = attributes.is_singleton();
+        if object_impl == ObjectImpl::Trait && is_singleton {
+            bail!("Trait interfaces can not be singletons: \"{object_name}\"")
+        }
+        let is_thread_bound = attributes.is_thread_bound();
+        if is_thread_bound && (object_impl == ObjectImpl::Trait || is_singleton) {
+            bail!("Trait and singleton interfaces can not be thread-bound: \"{object_name}\"")
+        }
+        let on_poison = attributes.get_on_poison();
+        if on_poison.is_some() && object_impl == ObjectImpl::Trait {
+            bail!("Trait interfaces can not have an OnPoison policy: \"{object_name}\"")
+        }
+        let parent = match self.inheritance {
+            Some(inheritance) => {
+                let parent_name = inheritance.identifier.0;
+                if object_impl == ObjectImpl::Trait {
+                    bail!(
+                        "Trait interfaces can not inherit from other interfaces: \"{object_name}\""
+                    )
+                }
+                // Upcasting would pass the object to the foreign code as its parent, which
+                // isn't bound to a thread.
+                if is_thread_bound {
+                    bail!("Thread-bound interfaces can not inherit from other interfaces: \"{object_name}\"")
+                }
+                match ci.get_type(parent_name) {
+                    Some(Type::Object {
+                        imp: ObjectImpl::Struct,
+                        ..
+                    }) => Some(parent_name.to_string()),
+                    _ => bail!(
+                        "\"{object_name}\" can only inherit from interfaces, not \"{parent_name}\""
+                    ),
+                }
+            }
+            None => None,
+        };
+        // Convert each member into a constructor, method or constant, guarding against duplicate
+        // names.  Constructors and methods get added to the ci and aren't carried in
+        // ObjectMetadata.
         let mut member_names = HashSet::new();
+        let mut const_members = vec![];
+        // The method marked as `stringifier`, if any.
+        let mut stringifier: Option<MethodMetadata> = None;
         for member in &self.members.body {
             match member {
                 weedle::interface::InterfaceMember::Constructor(t) => {
@@ -37,6 +97,24 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                             cons.name
                         )
                     }
+                    if is_singleton {
+                        bail!(
+                            "Singleton interfaces can not have constructors: \"{}\"",
+                            cons.name
+                        )
+                    }
+                    if is_thread_bound && cons.is_async {
+                        bail!(
+                            "Thread-bound interfaces can not have async constructors: \"{}\"",
+                            cons.name
+                        )
+                    }
+                    if on_poison.is_some() && cons.is_async {
+                        bail!(
+                            "Interfaces with an OnPoison policy can not have async constructors: \"{}\"",
+                            cons.name
+                        )
+                    }
                     if !member_names.insert(cons.name.clone()) {
                         bail!("Duplicate interface member name: \"{}\"", cons.name)
                     }
@@ -45,15 +123,75 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                 }
                 weedle::interface::InterfaceMember::Operation(t) => {
                     let mut method: MethodMetadata = t.convert(ci)?;
+                    if object_impl == ObjectImpl::Trait {
+                        if let Some(arg) = method.inputs.iter().find(|arg| arg.variadic) {
+                            bail!(
+                                "Trait interface method arguments can not be [Variadic]: \"{}\"",
+                                arg.name
+                            )
+                        }
+                    }
+                    if is_thread_bound && method.is_async {
+                        bail!(
+                            "Thread-bound interfaces can not have async methods: \"{}\"",
+                            method.name
+                        )
+                    }
+                    if on_poison.is_some() && method.is_async {
+                        bail!(
+                            "Interfaces with an OnPoison policy can not have async methods: \"{}\"",
+                            method.name
+                        )
+                    }
                     if !member_names.insert(method.name.clone()) {
                         bail!("Duplicate interface member name: \"{}\"", method.name)
                     }
                     method.self_name = object_name.to_string();
+                    if let Some(weedle::interface::StringifierOrStatic::Stringifier(_)) = t.modifier
+                    {
+                        check_stringifier(&method, object_impl)?;
+                        if stringifier.is_some() {
+                            bail!("Only one stringifier is allowed: \"{}\"", method.name)
+                        }
+                        stringifier = Some(method.clone());
+                    }
                     ci.items.insert(method.into());
                 }
+                weedle::interface::InterfaceMember::Const(t) => {
+                    let name = t.identifier.0;
+                    if t.attributes.is_some() {
+                        bail!("constant attributes are not supported yet");
+                    }
+                    if object_impl == ObjectImpl::Trait {
+                        bail!("Trait interfaces can not have constants: \"{name}\"")
+                    }
+                    if !member_names.insert(name.to_string()) {
+                        bail!("Duplicate interface member name: \"{name}\"")
+                    }
+                    let ty = ci.resolve_type_expression(&t.const_type)?;
+                    const_members.push((t, ty));
+                }
                 _ => bail!("no support for interface member type {:?} yet", member),
             }
         }
+        // Constants are evaluated once they've all been seen, as they can refer to each other.
+        let mut evaluator = ConstEvaluator::new(
+            &ci.source,
+            const_members
+                .iter()
+                .map(|(t, ty)| (t.identifier.0, &t.const_value, ty.clone())),
+        );
+        let constants = const_members
+            .iter()
+            .map(|(t, ty)| {
+                Ok(ConstantMetadata {
+                    name: t.identifier.0.to_string(),
+                    ty: ty.clone(),
+                    value: evaluator.value(t.identifier.0)?,
+                    docstring: t.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
         // A helper for our trait methods
         let make_trait_method = |name: &str,
                                  inputs: Vec<FnParamMetadata>,
@@ -69,8 +207,11 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                 return_type,
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
                 checksum: None,
                 docstring: None,
+                return_docstring: None,
+                since: None,
             })
         };
         // Trait methods are in the Metadata.
@@ -98,6 +239,11 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                                 by_ref: true,
                                 default: None,
                                 optional: false,
+                                variadic: false,
+                                segmented: false,
+                                progress: false,
+                                out: false,
+                                docstring: None,
                             }],
                             Some(Type::Boolean),
                         )?,
@@ -113,6 +259,11 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                                 by_ref: true,
                                 default: None,
                                 optional: false,
+                                variadic: false,
+                                segmented: false,
+                                progress: false,
+                                out: false,
+                                docstring: None,
                             }],
                             Some(Type::Boolean),
                         )?,
@@ -124,14 +275,61 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                 })
             })
             .collect::<Result<Vec<_>>>()?;
+        // A stringifier is exposed like a `Display` implementation, so an interface can't have
+        // both.
+        let uniffi_traits = match stringifier {
+            None => uniffi_traits,
+            Some(fmt) => {
+                if uniffi_traits
+                    .iter()
+                    .any(|ut| matches!(ut, UniffiTraitMetadata::Display { .. }))
+                {
+                    bail!("Interfaces with a stringifier can't also have the Display trait")
+                }
+                uniffi_traits
+                    .into_iter()
+                    .chain([UniffiTraitMetadata::Stringifier { fmt }])
+                    .collect()
+            }
+        };
         for ut in uniffi_traits {
             ci.items.insert(ut.into());
         }
+        let (docstring, since) = convert_type_docstring(self.docstring.as_ref())?;
         Ok(ObjectMetadata {
             module_path: ci.module_path(),
             name: object_name.to_string(),
             imp: object_impl,
-            docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            implements,
+            is_singleton,
+            parent,
+            constants,
+            observable: attributes.is_observable(),
+            cursor: attributes.is_cursor(),
+            thread_bound: is_thread_bound,
+            on_poison,
+            docstring,
+            since,
         })
     }
 }
+
+fn check_stringifier(method: &MethodMetadata, object_impl: ObjectImpl) -> Result<()> {
+    if object_impl == ObjectImpl::Trait {
+        bail!(
+            "Trait interfaces can not have stringifiers: \"{}\"",
+            method.name
+        )
+    }
+    if !method.inputs.is_empty()
+        || method.return_type != Some(Type::String)
+        || method.throws.is_some()
+        || method.is_async
+    {
+        bail!(
+            "Stringifiers must take no arguments and return a string: \"{}\"",
+            method.name
+        )
+    }
+    Ok(())
+}
@@ -2,12 +2,15 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use crate::attributes::{DictionaryAttributes, FieldAttributes, VariantAttributes};
 use crate::literal::convert_default_value;
 use crate::InterfaceCollector;
 use anyhow::{bail, Result};
+use std::collections::HashSet;
 
 use uniffi_meta::{
-    CallbackInterfaceMetadata, FieldMetadata, RecordMetadata, TraitMethodMetadata, VariantMetadata,
+    CallbackInterfaceMetadata, FieldMetadata, FnParamMetadata, RecordMetadata, TraitMethodMetadata,
+    VariantMetadata,
 };
 
 mod callables;
@@ -34,6 +37,130 @@ pub(crate) fn convert_docstring(docstring: &str) -> String {
     textwrap::dedent(docstring)
 }
 
+// Convert the UDL docstring of a function, method or constructor into metadata docstrings.
+//
+// `@param <name> <description>`, `@returns <description>` and `@since <version>` tags are split
+// out of the docstring. The parameter descriptions are stored on the matching `inputs`, and the
+// rest of the docstring is returned along with the return value description and version.
+pub(crate) fn convert_callable_docstring(
+    docstring: Option<&weedle::common::Docstring>,
+    inputs: &mut [FnParamMetadata],
+) -> Result<DocstringTags> {
+    let mut docstring = match docstring {
+        Some(docstring) => parse_docstring_tags(&convert_docstring(&docstring.0))?,
+        None => return Ok(DocstringTags::default()),
+    };
+    for (name, description) in std::mem::take(&mut docstring.params) {
+        match inputs.iter_mut().find(|input| input.name == name) {
+            Some(input) => input.docstring = Some(description),
+            None => bail!("`@param` tag for unknown argument `{name}`"),
+        }
+    }
+    Ok(docstring)
+}
+
+// Convert the UDL docstring of a type into its metadata docstring and the version from its
+// `@since` tag, if any.
+pub(crate) fn convert_type_docstring(
+    docstring: Option<&weedle::common::Docstring>,
+) -> Result<(Option<String>, Option<String>)> {
+    let docstring = match docstring {
+        Some(docstring) => parse_docstring_tags(&convert_docstring(&docstring.0))?,
+        None => return Ok((None, None)),
+    };
+    if !docstring.params.is_empty() || docstring.returns.is_some() {
+        bail!("Only functions, methods and constructors can have `@param` and `@returns` tags");
+    }
+    Ok((docstring.body, docstring.since))
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct DocstringTags {
+    pub(crate) body: Option<String>,
+    params: Vec<(String, String)>,
+    pub(crate) returns: Option<String>,
+    pub(crate) since: Option<String>,
+}
+
+// A tag's description continues until the next tag, so tags should come after the rest of the
+// docstring.
+fn parse_docstring_tags(docstring: &str) -> Result<DocstringTags> {
+    enum Section {
+        Body,
+        Param(usize),
+        Returns,
+    }
+
+    // Strip `tag` from the start of a line, if it's followed by whitespace or nothing.
+    fn strip_tag<'a>(line: &'a str, tag: &str) -> Option<&'a str> {
+        let rest = line.trim_start().strip_prefix(tag)?;
+        (rest.is_empty() || rest.starts_with(char::is_whitespace)).then(|| rest.trim())
+    }
+
+    let mut tags = DocstringTags::default();
+    let mut body = vec![];
+    let mut section = Section::Body;
+    for line in docstring.lines() {
+        if let Some(rest) = strip_tag(line, "@param") {
+            let (name, description) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            if name.is_empty() {
+                bail!("`@param` tag without an argument name");
+            }
+            if tags.params.iter().any(|(n, _)| n == name) {
+                bail!("Duplicate `@param` tag for `{name}`");
+            }
+            tags.params
+                .push((name.to_string(), description.trim().to_string()));
+            section = Section::Param(tags.params.len() - 1);
+        } else if let Some(rest) =
+            strip_tag(line, "@returns").or_else(|| strip_tag(line, "@return"))
+        {
+            if tags.returns.is_some() {
+                bail!("Only one `@returns` tag is allowed");
+            }
+            tags.returns = Some(rest.to_string());
+            section = Section::Returns;
+        } else if let Some(rest) = strip_tag(line, "@since") {
+            if rest.is_empty() {
+                bail!("`@since` tag without a version");
+            }
+            if tags.since.is_some() {
+                bail!("Only one `@since` tag is allowed");
+            }
+            tags.since = Some(rest.to_string());
+            // The version is a single line, so anything after it is part of the body again.
+            section = Section::Body;
+        } else {
+            let description = match section {
+                Section::Body => {
+                    body.push(line);
+                    continue;
+                }
+                Section::Param(index) => &mut tags.params[index].1,
+                Section::Returns => tags.returns.as_mut().unwrap(),
+            };
+            description.push('\n');
+            description.push_str(line.trim());
+        }
+    }
+
+    if tags.params.is_empty() && tags.returns.is_none() && tags.since.is_none() {
+        // Leave docstrings without any tags untouched.
+        tags.body = Some(docstring.to_string());
+        return Ok(tags);
+    }
+    for (_, description) in tags.params.iter_mut() {
+        *description = description.trim_end().to_string();
+    }
+    if let Some(returns) = &mut tags.returns {
+        *returns = returns.trim_end().to_string();
+    }
+    let body = body.join("\n");
+    let body = body.trim_end();
+    tags.body = (!body.is_empty()).then(|| body.to_string());
+    Ok(tags)
+}
+
 /// Convert a list of weedle items into a list of `InterfaceCollector` items,
 /// by doing a direct item-by-item mapping.
 impl<U, T: APIConverter<U>> APIConverter<Vec<U>> for Vec<T> {
@@ -57,6 +184,10 @@ impl APIConverter<VariantMetadata> for weedle::interface::OperationInterfaceMemb
         if self.identifier.is_some() {
             bail!("enum interface members must not have a method name");
         }
+        let attributes = VariantAttributes::try_from(self.attributes.as_ref())?;
+        if attributes.is_default() {
+            bail!("`[Default]` variants are only supported in an `enum`");
+        }
         let name: String = {
             use weedle::types::{
                 NonAnyType::{self, Identifier},
@@ -86,32 +217,46 @@ impl APIConverter<VariantMetadata> for weedle::interface::OperationInterfaceMemb
                 .map(|arg| arg.convert(ci))
                 .collect::<Result<Vec<_>>>()?,
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            deprecated: attributes.get_deprecated().map(ToOwned::to_owned),
+            retryable: attributes.is_retryable(),
         })
     }
 }
 
 impl APIConverter<RecordMetadata> for weedle::DictionaryDefinition<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<RecordMetadata> {
-        if self.attributes.is_some() {
-            bail!("dictionary attributes are not supported yet");
-        }
+        let attributes = DictionaryAttributes::try_from(self.attributes.as_ref())?;
         if self.inheritance.is_some() {
             bail!("dictionary inheritance is not supported");
         }
+        let (docstring, since) = convert_type_docstring(self.docstring.as_ref())?;
+        // Fields are identified by their name, so a record can't have two with the same one.
+        let mut names = HashSet::new();
+        for member in &self.members.body {
+            let name = member.identifier.0;
+            if !names.insert(name) {
+                return Err(ci.source.error_at(
+                    name,
+                    format!("Duplicate field `{name}` in record `{}`", self.identifier.0),
+                ));
+            }
+        }
         Ok(RecordMetadata {
             module_path: ci.module_path(),
             name: self.identifier.0.to_string(),
             fields: self.members.body.convert(ci)?,
-            docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            packed: attributes.is_packed(),
+            has_default: attributes.has_default(),
+            docstring,
+            since,
+            deprecated: attributes.get_deprecated().map(ToOwned::to_owned),
         })
     }
 }
 
 impl APIConverter<FieldMetadata> for weedle::dictionary::DictionaryMember<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<FieldMetadata> {
-        if self.attributes.is_some() {
-            bail!("dictionary member attributes are not supported yet");
-        }
+        let attributes = FieldAttributes::try_from(self.attributes.as_ref())?;
         let type_ = ci.resolve_type_expression(&self.type_)?;
         let default = match self.default {
             None => None,
@@ -122,6 +267,7 @@ impl APIConverter<FieldMetadata> for weedle::dictionary::DictionaryMember<'_> {
             ty: type_,
             default,
             docstring: self.docstring.as_ref().map(|v| convert_docstring(&v.0)),
+            order: attributes.get_order(),
         })
     }
 }
@@ -229,4 +375,355 @@ mod test {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_duplicate_record_fields() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Point {
+                i32 x;
+                i32 y;
+                i32 x;
+            };
+        "#;
+        let err = InterfaceCollector::from_webidl(UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Duplicate field `x` in record `Point` at 6:21"
+        );
+
+        // The same name can be used by fields of different records.
+        const VALID_UDL: &str = r#"
+            namespace test{};
+            dictionary Point {
+                i32 x;
+                i32 y;
+            };
+            dictionary Size {
+                i32 x;
+            };
+        "#;
+        let ci = InterfaceCollector::from_webidl(VALID_UDL, "crate_name").unwrap();
+        assert_eq!(ci.items.len(), 2);
+    }
+
+    #[test]
+    fn test_docstring_tags() {
+        const UDL: &str = r#"
+            namespace test{
+                /// Add two numbers.
+                ///
+                /// @param left The first number
+                /// @param right The second number,
+                ///   which can be negative.
+                /// @returns The sum
+                i32 add(i32 left, i32 right);
+            };
+        "#;
+        let ci = InterfaceCollector::from_webidl(UDL, "crate-name").unwrap();
+        match ci.items.first().unwrap() {
+            Metadata::Func(func) => {
+                assert_eq!(func.docstring.as_deref(), Some("Add two numbers."));
+                assert_eq!(func.inputs[0].name, "left");
+                assert_eq!(
+                    func.inputs[0].docstring.as_deref(),
+                    Some("The first number")
+                );
+                assert_eq!(func.inputs[1].name, "right");
+                assert_eq!(
+                    func.inputs[1].docstring.as_deref(),
+                    Some("The second number,\nwhich can be negative.")
+                );
+                assert_eq!(func.return_docstring.as_deref(), Some("The sum"));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_docstring_tags_unknown_argument() {
+        const UDL: &str = r#"
+            namespace test{
+                /// @param other Not an argument
+                void test(i32 value);
+            };
+        "#;
+        let err = InterfaceCollector::from_webidl(UDL, "crate-name").unwrap_err();
+        assert_eq!(err.to_string(), "`@param` tag for unknown argument `other`");
+    }
+
+    #[test]
+    fn test_docstring_since_tag() {
+        const UDL: &str = r#"
+            namespace test{
+                /// Add two numbers.
+                /// @since 1.2.0
+                /// @returns The sum
+                i32 add(i32 left, i32 right);
+            };
+            /// A point on a plane.
+            ///
+            /// @since 1.3.0
+            dictionary Point {
+                i32 x;
+            };
+        "#;
+        let ci = InterfaceCollector::from_webidl(UDL, "crate-name").unwrap();
+        let func = ci
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Metadata::Func(func) => Some(func),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(func.docstring.as_deref(), Some("Add two numbers."));
+        assert_eq!(func.since.as_deref(), Some("1.2.0"));
+        assert_eq!(func.return_docstring.as_deref(), Some("The sum"));
+        let record = ci
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Metadata::Record(record) => Some(record),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(record.docstring.as_deref(), Some("A point on a plane."));
+        assert_eq!(record.since.as_deref(), Some("1.3.0"));
+
+        const TWICE_UDL: &str = r#"
+            namespace test{
+                /// @since 1.0
+                /// @since 2.0
+                void test();
+            };
+        "#;
+        let err = InterfaceCollector::from_webidl(TWICE_UDL, "crate-name").unwrap_err();
+        assert_eq!(err.to_string(), "Only one `@since` tag is allowed");
+
+        const PARAM_UDL: &str = r#"
+            namespace test{};
+            /// @param x Not an argument
+            dictionary Point {
+                i32 x;
+            };
+        "#;
+        let err = InterfaceCollector::from_webidl(PARAM_UDL, "crate-name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Only functions, methods and constructors can have `@param` and `@returns` tags"
+        );
+    }
+
+    #[test]
+    fn test_trailing_default_arguments() {
+        const UDL: &str = r#"
+            namespace test{
+                void test(i32 value, optional i32 other = 1, optional string name = "hi");
+            };
+        "#;
+        let ci = InterfaceCollector::from_webidl(UDL, "crate-name").unwrap();
+        match ci.items.first().unwrap() {
+            Metadata::Func(func) => {
+                assert!(func.inputs[0].default.is_none());
+                assert!(func.inputs[1].default.is_some());
+                assert!(func.inputs[2].default.is_some());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_interleaved_default_arguments() {
+        const UDL: &str = r#"
+            namespace test{
+                void test(optional i32 value = 1, i32 other);
+            };
+        "#;
+        let err = InterfaceCollector::from_webidl(UDL, "crate-name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "argument `other` must have a default value, since it follows the defaulted argument `value`"
+        );
+
+        const METHOD_UDL: &str = r#"
+            namespace test{};
+            interface Test {
+                constructor(optional i32 value = 1, i32 other);
+            };
+        "#;
+        let err = InterfaceCollector::from_webidl(METHOD_UDL, "crate-name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "argument `other` must have a default value, since it follows the defaulted argument `value`"
+        );
+    }
+
+    #[test]
+    fn test_variadic_arguments() {
+        const UDL: &str = r#"
+            namespace test{
+                u32 sum(string label, [Variadic] sequence<u32> values);
+            };
+        "#;
+        let ci = InterfaceCollector::from_webidl(UDL, "crate-name").unwrap();
+        match ci.items.first().unwrap() {
+            Metadata::Func(func) => {
+                assert!(!func.inputs[0].variadic);
+                assert!(func.inputs[1].variadic);
+            }
+            _ => unreachable!(),
+        }
+
+        let check_err = |udl: &str, message: &str| {
+            let err = InterfaceCollector::from_webidl(udl, "crate-name").unwrap_err();
+            assert_eq!(err.to_string(), message);
+        };
+        check_err(
+            r#"
+            namespace test{
+                void test([Variadic] sequence<u32> first, [Variadic] sequence<u32> second);
+            };
+            "#,
+            "argument `first` can't be [Variadic], only the last argument can be",
+        );
+        check_err(
+            r#"
+            namespace test{
+                void test([Variadic] u32 value);
+            };
+            "#,
+            "argument `value` must be a sequence to be [Variadic]",
+        );
+        check_err(
+            r#"
+            namespace test{};
+            interface Test {
+                constructor([Variadic] optional sequence<u32> values = []);
+            };
+            "#,
+            "argument `values` can't have a default value, since it's [Variadic]",
+        );
+        check_err(
+            r#"
+            namespace test{};
+            callback interface Listener {
+                void on_values([Variadic] sequence<u32> values);
+            };
+            "#,
+            "callback interface method arguments can't be [Variadic]: `values`",
+        );
+        check_err(
+            r#"
+            namespace test{};
+            [Trait]
+            interface Summer {
+                u32 sum([Variadic] sequence<u32> values);
+            };
+            "#,
+            "Trait interface method arguments can not be [Variadic]: \"values\"",
+        );
+    }
+
+    #[test]
+    fn test_extension_functions() {
+        const UDL: &str = r#"
+            namespace test{
+                [Extension=Client]
+                string process(Client client, u32 count);
+            };
+            interface Client {};
+        "#;
+        let ci = InterfaceCollector::from_webidl(UDL, "crate-name").unwrap();
+        let func = ci
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Metadata::Func(func) => Some(func),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(func.extension.as_deref(), Some("Client"));
+
+        let check_err = |udl: &str, message: &str| {
+            let err = InterfaceCollector::from_webidl(udl, "crate-name").unwrap_err();
+            assert_eq!(err.to_string(), message);
+        };
+        check_err(
+            r#"
+            namespace test{
+                [Extension=Client]
+                string process(u32 count, Client client);
+            };
+            interface Client {};
+            "#,
+            "[Extension=Client] function `process` must take a `Client` as its first argument",
+        );
+        check_err(
+            r#"
+            namespace test{
+                [Extension=Client]
+                string process();
+            };
+            interface Client {};
+            "#,
+            "[Extension=Client] function `process` must take a `Client` as its first argument",
+        );
+        check_err(
+            r#"
+            namespace test{
+                [Extension=Client, Namespace=Tools]
+                string process(Client client);
+            };
+            interface Client {};
+            "#,
+            "Extension functions can't be grouped under a namespace object: \"process\"",
+        );
+    }
+
+    #[test]
+    fn test_default_variant() {
+        const UDL: &str = r#"
+            namespace test{};
+            [NonExhaustive]
+            enum Color { "Red", "Green", [Default] "Unknown" };
+        "#;
+        let ci = InterfaceCollector::from_webidl(UDL, "crate-name").unwrap();
+        match ci.items.first().unwrap() {
+            Metadata::Enum(e) => assert_eq!(e.default_variant.as_deref(), Some("Unknown")),
+            _ => unreachable!(),
+        }
+
+        const EXHAUSTIVE_UDL: &str = r#"
+            namespace test{};
+            enum Color { "Red", [Default] "Unknown" };
+        "#;
+        let err = InterfaceCollector::from_webidl(EXHAUSTIVE_UDL, "crate-name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`Color` has a `[Default]` variant, so it must be `[NonExhaustive]` at 3:18"
+        );
+
+        const TWICE_UDL: &str = r#"
+            namespace test{};
+            [NonExhaustive]
+            enum Color { [Default] "Red", [Default] "Unknown" };
+        "#;
+        let err = InterfaceCollector::from_webidl(TWICE_UDL, "crate-name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`Color` can only have one `[Default]` variant at 4:54"
+        );
+
+        const FIELDS_UDL: &str = r#"
+            namespace test{};
+            [NonExhaustive]
+            enum Shape { Circle { f64 radius }, [Default] Unknown };
+        "#;
+        let err = InterfaceCollector::from_webidl(FIELDS_UDL, "crate-name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`Shape` has a `[Default]` variant, so its variants can't have fields"
+        );
+    }
 }
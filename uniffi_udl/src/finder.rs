@@ -116,7 +116,17 @@ impl TypeFinder for weedle::TypedefDefinition<'_> {
         // > let t = types.resolve_type_expression(&self.type_)?;
         // > types.add_type_definition(name, t)
         // But we don't - `typedef`s are reserved for external types.
-        if attrs.is_custom() {
+        if attrs.is_error() {
+            // `typedef [Error] any DynError;` declares that functions can throw
+            // `uniffi::DynError`.
+            if !matches!(
+                self.type_.type_,
+                weedle::types::Type::Single(weedle::types::SingleType::Any(_))
+            ) {
+                bail!("[Error] typedefs must use the `any` type");
+            }
+            types.add_type_definition(self.identifier.0, Type::DynError)
+        } else if attrs.is_custom() {
             // A local type which wraps a builtin and for which we will generate an
             // `FfiConverter` implementation.
             let builtin = types.resolve_type_expression(&self.type_)?;
@@ -293,6 +303,26 @@ mod test {
         err.to_string()
     }
 
+    #[test]
+    fn test_dyn_error_typedef() {
+        test_a_finding(
+            r#"
+            [Error]
+            typedef any DynError;
+        "#,
+            |types| {
+                assert_eq!(
+                    types.get_type_definition("DynError").unwrap(),
+                    Type::DynError
+                );
+            },
+        );
+        assert_eq!(
+            get_err("[Error] typedef string DynError;"),
+            "[Error] typedefs must use the `any` type"
+        );
+    }
+
     #[test]
     #[should_panic]
     fn test_typedef_error_on_no_attr() {
@@ -16,9 +16,11 @@ mod converters;
 mod finder;
 mod literal;
 mod resolver;
+mod span;
 
 use anyhow::Result;
 use collectors::{InterfaceCollector, TypeCollector};
+pub use span::SpannedError;
 use uniffi_meta::Type;
 
 /// The single entry-point to this module.
@@ -26,6 +28,14 @@ pub fn parse_udl(udl: &str, crate_name: &str) -> Result<uniffi_meta::MetadataGro
     Ok(InterfaceCollector::from_webidl(udl, crate_name)?.into())
 }
 
+/// Parse several UDL files which make up a single namespace, as `(file_name, udl)` pairs.
+pub fn parse_udl_multi(
+    files: &[(&str, &str)],
+    crate_name: &str,
+) -> Result<uniffi_meta::MetadataGroup> {
+    Ok(InterfaceCollector::from_webidl_multi(files, crate_name)?.into())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -44,4 +54,339 @@ mod test {
             uniffi_meta::Metadata::Record(r) if r.module_path == "crate_name" && r.name == "Empty" && r.fields.is_empty()
         ));
     }
+
+    #[test]
+    fn test_throws_plain_enum() {
+        const UDL: &str = r#"
+            namespace test{
+                [Throws=PlainEnum]
+                void oops();
+            };
+            enum PlainEnum { "A", "B" };
+        "#;
+        let err = parse_udl(UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`oops` throws `PlainEnum`, which is an enum but not an error. \
+             Add `[Error]` to the definition of `PlainEnum`."
+        );
+
+        const UDL_METHOD: &str = r#"
+            namespace test{};
+            interface Thing {
+                [Throws=Record]
+                void oops();
+            };
+            dictionary Record { u32 field; };
+        "#;
+        let err = parse_udl(UDL_METHOD, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`Thing.oops` throws `Record`, which is a dictionary. \
+             Only types declared with `[Error]` can be thrown."
+        );
+
+        const UDL_ERROR: &str = r#"
+            namespace test{
+                [Throws=ErrorEnum]
+                void oops();
+            };
+            [Error]
+            enum ErrorEnum { "A", "B" };
+        "#;
+        parse_udl(UDL_ERROR, "crate_name").unwrap();
+    }
+
+    #[test]
+    fn test_throws_union() {
+        const UDL: &str = r#"
+            namespace test{
+                [Throws=(NetworkError, ParseError)]
+                void fetch();
+                [Throws=(NetworkError, ParseError)]
+                void fetch_again();
+            };
+            [Error]
+            enum NetworkError { "Offline" };
+            [Error]
+            interface ParseError { Invalid(string reason); };
+        "#;
+        let group = parse_udl(UDL, "crate_name").unwrap();
+        let union_errors: Vec<_> = group
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                uniffi_meta::Metadata::Error(uniffi_meta::ErrorMetadata::Enum {
+                    enum_,
+                    is_flat,
+                }) if enum_.name == "NetworkErrorOrParseError" => Some((enum_, is_flat)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(union_errors.len(), 1);
+        let (enum_, is_flat) = union_errors[0];
+        assert!(!is_flat);
+        let variants: Vec<_> = enum_
+            .variants
+            .iter()
+            .map(|v| (v.name.as_str(), v.fields[0].name.as_str(), &v.fields[0].ty))
+            .collect();
+        assert_eq!(
+            variants,
+            [
+                (
+                    "Network",
+                    "error",
+                    &Type::Enum {
+                        module_path: "crate_name".into(),
+                        name: "NetworkError".into()
+                    }
+                ),
+                (
+                    "Parse",
+                    "error",
+                    &Type::Enum {
+                        module_path: "crate_name".into(),
+                        name: "ParseError".into()
+                    }
+                ),
+            ]
+        );
+        let fetch = group.items.iter().find_map(|item| match item {
+            uniffi_meta::Metadata::Func(f) if f.name == "fetch" => Some(f),
+            _ => None,
+        });
+        assert_eq!(
+            fetch.unwrap().throws,
+            Some(Type::Enum {
+                module_path: "crate_name".into(),
+                name: "NetworkErrorOrParseError".into()
+            })
+        );
+
+        const UDL_UNKNOWN: &str = r#"
+            namespace test{
+                [Throws=(NetworkError, MissingError)]
+                void fetch();
+            };
+            [Error]
+            enum NetworkError { "Offline" };
+        "#;
+        let err = parse_udl(UDL_UNKNOWN, "crate_name").unwrap_err();
+        assert_eq!(err.to_string(), "unknown type for error: MissingError");
+    }
+
+    #[test]
+    fn test_partial_interface() {
+        const UDL: &str = r#"
+            namespace test{};
+            partial interface Client {
+                string fetch(string url);
+            };
+            interface Client {
+                constructor();
+                void connect();
+            };
+            partial interface Client {
+                void disconnect();
+            };
+        "#;
+        let group = parse_udl(UDL, "crate_name").unwrap();
+        let methods: Vec<_> = group
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                uniffi_meta::Metadata::Method(m) if m.self_name == "Client" => {
+                    Some(m.name.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(methods, ["connect", "disconnect", "fetch"]);
+
+        const UDL_DUPLICATE: &str = r#"
+            namespace test{};
+            interface Client {
+                void connect();
+            };
+            partial interface Client {
+                void connect();
+            };
+        "#;
+        let err = parse_udl(UDL_DUPLICATE, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Duplicate method `Client.connect` in partial interface"
+        );
+
+        const UDL_CONFLICT: &str = r#"
+            namespace test{};
+            interface Client {
+                void connect();
+            };
+            partial interface Client {
+                boolean connect(u32 timeout);
+            };
+        "#;
+        let err = parse_udl(UDL_CONFLICT, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Conflicting definitions for method `Client.connect` in partial interface"
+        );
+
+        const UDL_MISSING: &str = r#"
+            namespace test{};
+            partial interface Client {
+                void connect();
+            };
+        "#;
+        let err = parse_udl(UDL_MISSING, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "partial interface `Client` has no matching interface definition"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_functions() {
+        const UDL: &str = r#"
+            namespace test{
+                u32 get(u32 id);
+                void put(u32 id);
+                string get(string name, boolean exact);
+            };
+        "#;
+        let err = parse_udl(UDL, "crate_name").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Duplicate function name `get` in namespace `test`: `get(id)` and `get(name, exact)`"
+        );
+
+        // Methods with the same name on different interfaces, or the same name as a function,
+        // don't clash.
+        const UDL_METHODS: &str = r#"
+            namespace test{
+                void connect();
+            };
+            interface Client {
+                void connect();
+            };
+            interface Server {
+                void connect();
+            };
+        "#;
+        let group = parse_udl(UDL_METHODS, "crate_name").unwrap();
+        let connects = group
+            .items
+            .iter()
+            .filter(|item| match item {
+                uniffi_meta::Metadata::Func(f) => f.name == "connect",
+                uniffi_meta::Metadata::Method(m) => m.name == "connect",
+                _ => false,
+            })
+            .count();
+        assert_eq!(connects, 3);
+    }
+
+    #[test]
+    fn test_multi() {
+        const COLORS: &str = r#"
+            namespace test{
+                Color mix(Color a, Color b);
+            };
+            enum Color { "Red", "Blue" };
+        "#;
+        const SHAPES: &str = r#"
+            namespace test{
+                Shape rotate(Shape shape, Color color);
+            };
+            enum Shape { "Square", "Circle" };
+        "#;
+        let group = parse_udl_multi(
+            &[("colors.udl", COLORS), ("shapes.udl", SHAPES)],
+            "crate_name",
+        )
+        .unwrap();
+        assert_eq!(group.namespace.name, "test");
+        let mut names: Vec<_> = group
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                uniffi_meta::Metadata::Func(f) => Some(f.name.as_str()),
+                uniffi_meta::Metadata::Enum(e) => Some(e.name.as_str()),
+                _ => None,
+            })
+            .collect();
+        names.sort();
+        assert_eq!(names, ["Color", "Shape", "mix", "rotate"]);
+    }
+
+    #[test]
+    fn test_multi_conflicts() {
+        const COLORS: &str = r#"
+            namespace test{
+                Color mix(Color a, Color b);
+            };
+            enum Color { "Red", "Blue" };
+        "#;
+
+        const DUPLICATE_TYPE: &str = r#"
+            namespace test{};
+            enum Color { "Red", "Blue" };
+        "#;
+        let err = parse_udl_multi(
+            &[("colors.udl", COLORS), ("paint.udl", DUPLICATE_TYPE)],
+            "crate_name",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`Color` is defined in both `colors.udl` and `paint.udl`"
+        );
+
+        const DUPLICATE_FUNCTION: &str = r#"
+            namespace test{
+                Color mix(Color a, Color b);
+            };
+        "#;
+        let err = parse_udl_multi(
+            &[("colors.udl", COLORS), ("paint.udl", DUPLICATE_FUNCTION)],
+            "crate_name",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "function `mix` is defined in both `colors.udl` and `paint.udl`"
+        );
+
+        const OTHER_NAMESPACE: &str = r#"
+            namespace other{};
+        "#;
+        let err = parse_udl_multi(
+            &[("colors.udl", COLORS), ("other.udl", OTHER_NAMESPACE)],
+            "crate_name",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "namespace `other` in `other.udl` doesn't match namespace `test` in `colors.udl`"
+        );
+
+        // Errors located in the UDL are reported against the file they're in.
+        const BAD_ENUM: &str = r#"
+            namespace test{};
+            enum Shape { "Square", "Square" };
+        "#;
+        let err = parse_udl_multi(
+            &[("colors.udl", COLORS), ("shapes.udl", BAD_ENUM)],
+            "crate_name",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Duplicate variant `Square` in enum `Shape` in `shapes.udl` at 3:37"
+        );
+        let spanned = err.downcast_ref::<SpannedError>().unwrap();
+        assert_eq!(&BAD_ENUM[spanned.span.clone()], "Square");
+    }
 }
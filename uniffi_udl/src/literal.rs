@@ -2,7 +2,9 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use crate::span::Source;
 use anyhow::{bail, Result};
+use std::collections::HashMap;
 use uniffi_meta::{LiteralMetadata, Radix, Type};
 
 // We are able to use LiteralMetadata directly.
@@ -95,6 +97,226 @@ pub(super) fn convert_default_value(
     })
 }
 
+// Convert the value of a `const` member, which is a subset of the default values.
+pub(super) fn convert_const_value(
+    const_value: &weedle::literal::ConstValue<'_>,
+    type_: &Type,
+) -> Result<LiteralMetadata> {
+    let default_value = match const_value {
+        weedle::literal::ConstValue::Boolean(v) => weedle::literal::DefaultValue::Boolean(*v),
+        weedle::literal::ConstValue::Float(v) => weedle::literal::DefaultValue::Float(*v),
+        weedle::literal::ConstValue::Integer(v) => weedle::literal::DefaultValue::Integer(*v),
+        weedle::literal::ConstValue::Null(v) => weedle::literal::DefaultValue::Null(*v),
+    };
+    convert_default_value(&default_value, type_)
+}
+
+/// Evaluates the `const` members of an interface, whose values can be computed from each other,
+/// for example `const u32 BUFFER_SIZE = PAGE_SIZE * 4;`.
+///
+/// Expressions combine integer literals and the names of other integer constants with
+/// `+ - * / % << >> & | ^`, which have the same precedence as in Rust.  Constants can be declared
+/// in any order, but can't refer to themselves.
+pub(super) struct ConstEvaluator<'a> {
+    source: &'a Source,
+    exprs: HashMap<&'a str, (&'a weedle::literal::ConstExpr<'a>, Type)>,
+    values: HashMap<&'a str, Literal>,
+    // The constants being evaluated, innermost last, to detect cycles.
+    evaluating: Vec<&'a str>,
+}
+
+impl<'a> ConstEvaluator<'a> {
+    pub fn new(
+        source: &'a Source,
+        consts: impl IntoIterator<Item = (&'a str, &'a weedle::literal::ConstExpr<'a>, Type)>,
+    ) -> Self {
+        Self {
+            source,
+            exprs: consts
+                .into_iter()
+                .map(|(name, expr, ty)| (name, (expr, ty)))
+                .collect(),
+            values: HashMap::new(),
+            evaluating: vec![],
+        }
+    }
+
+    /// The value of the constant `name`, which is the slice of the UDL it was referred to by.
+    pub fn value(&mut self, name: &'a str) -> Result<Literal> {
+        if let Some(value) = self.values.get(name) {
+            return Ok(value.clone());
+        }
+        if let Some(start) = self.evaluating.iter().position(|n| *n == name) {
+            let cycle = self.evaluating[start..]
+                .iter()
+                .chain([&name])
+                .map(|n| format!("`{n}`"))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(self
+                .source
+                .error_at(name, format!("Constant `{name}` refers to itself: {cycle}")));
+        }
+        let Some((expr, ty)) = self.exprs.get(name).cloned() else {
+            return Err(self
+                .source
+                .error_at(name, format!("Unknown constant `{name}`")));
+        };
+        self.evaluating.push(name);
+        let value = self.evaluate(name, expr, &ty);
+        self.evaluating.pop();
+        let value = value?;
+        self.values.insert(name, value.clone());
+        Ok(value)
+    }
+
+    fn evaluate(
+        &mut self,
+        name: &'a str,
+        expr: &'a weedle::literal::ConstExpr<'a>,
+        ty: &Type,
+    ) -> Result<Literal> {
+        use weedle::literal::ConstOperand;
+
+        if expr.rest.is_empty() {
+            match &expr.first {
+                ConstOperand::Value(v) => return convert_const_value(v, ty),
+                // Other types of constant can be aliased, but not computed.
+                ConstOperand::Identifier(id) if !is_integer(ty) => {
+                    let value = self.value(id.0)?;
+                    if self.exprs[id.0].1 != *ty {
+                        bail!("`{name}` and `{}` have different types", id.0);
+                    }
+                    return Ok(value);
+                }
+                ConstOperand::Identifier(_) => (),
+            }
+        }
+        if !is_integer(ty) {
+            bail!("Only integer constants can be computed from expressions: `{name}`");
+        }
+
+        // Precedence climbing over the flat list of operands and operators.
+        let mut operands = vec![self.operand(&expr.first)?];
+        let mut operators: Vec<&str> = vec![];
+        for (op, operand) in &expr.rest {
+            while operators
+                .last()
+                .map_or(false, |top| precedence(top) >= precedence(op.0))
+            {
+                apply(name, &mut operands, operators.pop().unwrap())?;
+            }
+            operators.push(op.0);
+            operands.push(self.operand(operand)?);
+        }
+        while let Some(op) = operators.pop() {
+            apply(name, &mut operands, op)?;
+        }
+        let value = operands[0];
+
+        let (min, max) = integer_range(ty);
+        if value < min || value > max {
+            bail!("The value of `{name}` ({value}) is out of range for its type");
+        }
+        Ok(match ty {
+            Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 => {
+                Literal::Int(value as i64, Radix::Decimal, ty.clone())
+            }
+            _ => Literal::UInt(value as u64, Radix::Decimal, ty.clone()),
+        })
+    }
+
+    fn operand(&mut self, operand: &'a weedle::literal::ConstOperand<'a>) -> Result<i128> {
+        match operand {
+            weedle::literal::ConstOperand::Value(weedle::literal::ConstValue::Integer(lit)) => {
+                integer_value(lit)
+            }
+            weedle::literal::ConstOperand::Value(v) => {
+                bail!("Only integers can be used in constant expressions, not {v:?}")
+            }
+            weedle::literal::ConstOperand::Identifier(id) => match self.value(id.0)? {
+                Literal::Int(v, ..) => Ok(v.into()),
+                Literal::UInt(v, ..) => Ok(v.into()),
+                _ => Err(self
+                    .source
+                    .error_at(id.0, format!("`{}` isn't an integer constant", id.0))),
+            },
+        }
+    }
+}
+
+fn is_integer(ty: &Type) -> bool {
+    integer_range(ty) != (0, 0)
+}
+
+// The smallest and largest values of an integer type, or `(0, 0)` for other types.
+fn integer_range(ty: &Type) -> (i128, i128) {
+    match ty {
+        Type::Int8 => (i8::MIN.into(), i8::MAX.into()),
+        Type::Int16 => (i16::MIN.into(), i16::MAX.into()),
+        Type::Int32 => (i32::MIN.into(), i32::MAX.into()),
+        Type::Int64 => (i64::MIN.into(), i64::MAX.into()),
+        Type::UInt8 => (0, u8::MAX.into()),
+        Type::UInt16 => (0, u16::MAX.into()),
+        Type::UInt32 => (0, u32::MAX.into()),
+        Type::UInt64 => (0, u64::MAX.into()),
+        _ => (0, 0),
+    }
+}
+
+fn integer_value(literal: &weedle::literal::IntegerLit<'_>) -> Result<i128> {
+    let (string, radix) = match literal {
+        weedle::literal::IntegerLit::Dec(v) => (v.0, 10),
+        weedle::literal::IntegerLit::Hex(v) => (v.0, 16),
+        weedle::literal::IntegerLit::Oct(v) => (v.0, 8),
+    };
+    let (negative, digits) = match string.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, string),
+    };
+    let digits = digits.trim_start_matches("0x").trim_start_matches("0X");
+    let digits = if digits.is_empty() { "0" } else { digits };
+    let value = i128::from_str_radix(digits, radix)?;
+    Ok(if negative { -value } else { value })
+}
+
+fn precedence(op: &str) -> u8 {
+    match op {
+        "*" | "/" | "%" => 5,
+        "+" | "-" => 4,
+        "<<" | ">>" => 3,
+        "&" => 2,
+        "^" => 1,
+        _ => 0,
+    }
+}
+
+// Replace the top two operands with the result of applying `op` to them.
+fn apply(name: &str, operands: &mut Vec<i128>, op: &str) -> Result<()> {
+    let rhs = operands.pop().unwrap();
+    let lhs = operands.pop().unwrap();
+    let shift = || u32::try_from(rhs).ok().filter(|s| *s < 64);
+    let value = match op {
+        "+" => lhs.checked_add(rhs),
+        "-" => lhs.checked_sub(rhs),
+        "*" => lhs.checked_mul(rhs),
+        "/" | "%" if rhs == 0 => bail!("Division by zero in the value of `{name}`"),
+        "/" => lhs.checked_div(rhs),
+        "%" => lhs.checked_rem(rhs),
+        "<<" => shift().and_then(|s| lhs.checked_mul(1 << s)),
+        ">>" => shift().and_then(|s| lhs.checked_shr(s)),
+        "&" => Some(lhs & rhs),
+        "|" => Some(lhs | rhs),
+        "^" => Some(lhs ^ rhs),
+        _ => bail!("Unsupported operator `{op}` in the value of `{name}`"),
+    };
+    match value {
+        Some(value) => operands.push(value),
+        None => bail!("The value of `{name}` overflows at `{lhs} {op} {rhs}`"),
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
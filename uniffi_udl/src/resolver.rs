@@ -127,6 +127,27 @@ impl TypeResolver for weedle::types::SequenceType<'_> {
     }
 }
 
+impl TypeResolver for &weedle::types::ConstType<'_> {
+    fn resolve_type_expression(&self, types: &mut TypeCollector) -> Result<Type> {
+        (*self).resolve_type_expression(types)
+    }
+}
+
+impl TypeResolver for weedle::types::ConstType<'_> {
+    fn resolve_type_expression(&self, types: &mut TypeCollector) -> Result<Type> {
+        use weedle::types::ConstType::*;
+        match self {
+            Integer(t) => t.resolve_type_expression(types),
+            FloatingPoint(t) => t.resolve_type_expression(types),
+            Boolean(t) => t.resolve_type_expression(types),
+            Byte(_) | Octet(_) => bail!(
+                "WebIDL Byte or Octet types not implemented ({self:?}); consider using i8 or u8",
+            ),
+            Identifier(t) => t.resolve_type_expression(types),
+        }
+    }
+}
+
 impl TypeResolver for weedle::types::RecordKeyType<'_> {
     fn resolve_type_expression(&self, types: &mut TypeCollector) -> Result<Type> {
         use weedle::types::RecordKeyType::*;
@@ -209,6 +230,11 @@ pub(crate) fn resolve_builtin_type(name: &str) -> Option<Type> {
         "f64" => Some(Type::Float64),
         "timestamp" => Some(Type::Timestamp),
         "duration" => Some(Type::Duration),
+        "duration_range" => Some(Type::DurationRange { inclusive: false }),
+        "duration_range_inclusive" => Some(Type::DurationRange { inclusive: true }),
+        "path" => Some(Type::Path),
+        "json" => Some(Type::Json),
+        "opaque_handle" => Some(Type::OpaqueHandle),
         _ => None,
     }
 }
@@ -0,0 +1,105 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Locating errors in the UDL source.
+//!
+//! weedle doesn't record positions, but every name in its parse tree is a slice of the UDL it
+//! parsed.  We remember where that UDL was, so the offset of a name can be worked out from its
+//! address.
+
+use std::fmt;
+use std::ops::Range;
+
+/// The UDL being parsed, used to find where the names in the parse tree came from.
+#[derive(Debug, Default)]
+pub(crate) struct Source {
+    text: String,
+    // The address of the UDL that was parsed, which the names in the parse tree point into.
+    // It's only compared with other addresses, never dereferenced.
+    addr: usize,
+}
+
+impl Source {
+    pub fn new(udl: &str) -> Self {
+        Self {
+            text: udl.to_string(),
+            addr: udl.as_ptr() as usize,
+        }
+    }
+
+    /// The byte range of `s` in the UDL, or None if it isn't a slice of it.
+    pub fn span(&self, s: &str) -> Option<Range<usize>> {
+        let start = (s.as_ptr() as usize).checked_sub(self.addr)?;
+        let end = start + s.len();
+        (end <= self.text.len()).then_some(start..end)
+    }
+
+    /// An error about `s`, which is located in the UDL if `s` is a slice of it.
+    pub fn error_at(&self, s: &str, message: impl Into<String>) -> anyhow::Error {
+        let message = message.into();
+        match self.span(s) {
+            Some(span) => anyhow::Error::new(SpannedError::new(&self.text, message, span)),
+            None => anyhow::Error::msg(message),
+        }
+    }
+}
+
+/// An error about a specific part of the UDL.
+///
+/// Tools can downcast the errors from parsing UDL to this type, to highlight the offending UDL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpannedError {
+    pub message: String,
+    /// The byte range of the offending UDL.
+    pub span: Range<usize>,
+    /// The line the offending UDL starts on, starting from 1.
+    pub line: usize,
+    /// The column the offending UDL starts at, in characters and starting from 1.
+    pub column: usize,
+}
+
+impl SpannedError {
+    /// An error about the `span` of `text`, with the line and column worked out from it.
+    pub(crate) fn new(text: &str, message: String, span: Range<usize>) -> Self {
+        let before = &text[..span.start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            message,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            span,
+        }
+    }
+}
+
+impl fmt::Display for SpannedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}:{}", self.message, self.line, self.column)
+    }
+}
+
+impl std::error::Error for SpannedError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_error_at() {
+        let udl = "namespace test {};\nenum Färg { \"red\" };\n";
+        let source = Source::new(udl);
+        let name = &udl[33..36];
+        assert_eq!(name, "red");
+        let err = source.error_at(name, "Bad variant `red`");
+        assert_eq!(err.to_string(), "Bad variant `red` at 2:14");
+        let spanned = err.downcast_ref::<SpannedError>().unwrap();
+        assert_eq!(spanned.span, 33..36);
+        assert_eq!(&udl[spanned.span.clone()], "red");
+
+        // Strings from elsewhere aren't located.
+        let err = source.error_at(&String::from("red"), "Bad variant `red`");
+        assert_eq!(err.to_string(), "Bad variant `red`");
+        assert!(err.downcast_ref::<SpannedError>().is_none());
+    }
+}
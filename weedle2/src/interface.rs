@@ -1,7 +1,7 @@
 use crate::argument::ArgumentList;
 use crate::attribute::ExtendedAttributeList;
 use crate::common::{Docstring, Generics, Identifier, Parenthesized};
-use crate::literal::ConstValue;
+use crate::literal::ConstExpr;
 use crate::types::{AttributedType, ConstType, ReturnType};
 
 /// Parses interface members
@@ -17,14 +17,15 @@ ast_types! {
 
     /// Parses one of the interface member variants
     enum InterfaceMember<'a> {
-        /// Parses a const interface member `[attributes]? const type identifier = value;`
+        /// Parses a const interface member `[attributes]? const type identifier = expression;`
         Const(struct ConstMember<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             const_: term!(const),
             const_type: ConstType<'a>,
             identifier: Identifier<'a>,
             assign: term!(=),
-            const_value: ConstValue<'a>,
+            const_value: ConstExpr<'a>,
             semi_colon: term!(;),
         }),
         /// Parses `[attributes]? (stringifier|inherit|static)? readonly? attribute attributedtype identifier;`
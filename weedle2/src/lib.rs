@@ -29,7 +29,7 @@ use self::interface::{Inheritance, InterfaceMembers};
 use self::literal::StringLit;
 use self::mixin::MixinMembers;
 use self::namespace::NamespaceMembers;
-use self::types::{AttributedType, ReturnType};
+use self::types::{AttributedType, ReturnType, Type};
 pub use nom::{error::Error, Err, IResult};
 
 #[macro_use]
@@ -230,15 +230,44 @@ ast_types! {
 }
 
 ast_types! {
+    /// Parses `[attributes]? name ( { fields } )?`
     struct EnumVariant<'a> {
         docstring: Option<Docstring>,
-        value: StringLit<'a>,
+        attributes: Option<ExtendedAttributeList<'a>>,
+        value: EnumVariantName<'a>,
+        fields: Option<Braced<EnumVariantFieldList<'a>>>,
+    }
+
+    /// Parses an enum variant name, either `"name"` or `name`
+    enum EnumVariantName<'a> {
+        String(StringLit<'a>),
+        Identifier(Identifier<'a>),
+    }
+
+    /// Parses a named enum variant field `type identifier`
+    struct EnumVariantField<'a> {
+        docstring: Option<Docstring>,
+        type_: Type<'a>,
+        identifier: Identifier<'a>,
+    }
+}
+
+impl<'a> EnumVariantName<'a> {
+    /// The name of the variant, without the quotes for the string form
+    pub fn name(&self) -> &'a str {
+        match self {
+            EnumVariantName::String(s) => s.0,
+            EnumVariantName::Identifier(i) => i.0,
+        }
     }
 }
 
 /// Parses a non-empty enum value list
 pub type EnumValueList<'a> = PunctuatedNonEmpty<EnumVariant<'a>, term!(,)>;
 
+/// Parses a non-empty list of named enum variant fields
+pub type EnumVariantFieldList<'a> = PunctuatedNonEmpty<EnumVariantField<'a>, term!(,)>;
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -266,6 +295,34 @@ mod test {
         values.body.list.len() == 2;
     });
 
+    test!(should_parse_enum_variant_with_attributes { r#"[Deprecated="use first"] "second""# =>
+        "";
+        EnumVariant;
+        attributes.is_some();
+        value.name() == "second";
+        fields.is_none();
+    });
+
+    test!(should_parse_enum_with_named_fields { "enum Event { Click { u32 x, u32 y }, Close };" =>
+        "";
+        EnumDefinition;
+        identifier.0 == "Event";
+        values.body.list.len() == 2;
+    });
+
+    test!(should_parse_enum_variant_with_named_fields { "Click { u32 x, u32 y }" =>
+        "";
+        EnumVariant;
+        value.name() == "Click";
+        fields.is_some();
+    });
+
+    test!(should_parse_enum_variant_field { "sequence<string> names" =>
+        "";
+        EnumVariantField;
+        identifier.0 == "names";
+    });
+
     test!(should_parse_dictionary { "dictionary A { long c; long g; };" =>
         "";
         DictionaryDefinition;
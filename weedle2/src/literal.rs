@@ -75,6 +75,30 @@ ast_types! {
         Null(term!(null)),
     }
 
+    /// Represents a constant expression, `operand (operator operand)*`, where each operand is a
+    /// value or the name of another constant. Ex: `PAGE_SIZE * 4`
+    struct ConstExpr<'a> {
+        first: ConstOperand<'a>,
+        rest: Vec<(ConstOperator<'a>, ConstOperand<'a>)>,
+    }
+
+    /// Represents an operand of a constant expression
+    #[derive(Copy)]
+    enum ConstOperand<'a> {
+        Value(ConstValue<'a>),
+        Identifier(crate::common::Identifier<'a>),
+    }
+
+    /// Represents a binary operator in a constant expression, one of `+ - * / % << >> & | ^`
+    #[derive(Copy)]
+    struct ConstOperator<'a>(
+        &'a str = crate::whitespace::ws(nom::branch::alt((
+            nom::bytes::complete::tag("<<"),
+            nom::bytes::complete::tag(">>"),
+            nom::combinator::recognize(nom::character::complete::one_of("+-*/%&|^")),
+        ))),
+    )
+
     /// Represents either `true` or `false`
     #[derive(Copy)]
     struct BooleanLit(
@@ -266,4 +290,17 @@ mod test {
         "";
         BooleanLit => BooleanLit(false)
     });
+
+    test!(should_parse_const_expr_value { "45;" =>
+        ";";
+        ConstExpr;
+        rest.is_empty();
+    });
+
+    test!(should_parse_const_expr { "PAGE_SIZE * 4 << 1;" =>
+        ";";
+        ConstExpr;
+        first == ConstOperand::Identifier(crate::common::Identifier("PAGE_SIZE"));
+        rest.len() == 2;
+    });
 }